@@ -0,0 +1,54 @@
+-- Scheduled report delivery
+
+CREATE TYPE report_type AS ENUM ('findings_csv', 'executive_pdf');
+
+CREATE TYPE delivery_method AS ENUM ('email', 'webhook');
+
+CREATE TYPE report_run_status AS ENUM ('running', 'succeeded', 'failed');
+
+-- ============================================================
+-- REPORT SCHEDULES
+-- ============================================================
+
+CREATE TABLE report_schedules (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name            VARCHAR(255) NOT NULL,
+    report_type     report_type NOT NULL,
+    -- Report parameters: FindingFilters for findings_csv, {"period": ...} for executive_pdf
+    filters         JSONB NOT NULL DEFAULT '{}'::JSONB,
+    cron_expression VARCHAR(100) NOT NULL,
+    delivery_method delivery_method NOT NULL,
+    -- Email addresses or webhook URLs depending on delivery_method
+    recipients      JSONB NOT NULL DEFAULT '[]'::JSONB,
+    is_active       BOOLEAN NOT NULL DEFAULT true,
+    next_run_at     TIMESTAMPTZ,
+    last_run_at     TIMESTAMPTZ,
+    created_by      UUID REFERENCES users(id),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_report_schedules_due ON report_schedules(next_run_at) WHERE is_active;
+
+CREATE TRIGGER update_report_schedules_updated_at
+    BEFORE UPDATE ON report_schedules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- ============================================================
+-- REPORT RUN HISTORY
+-- ============================================================
+
+CREATE TABLE report_runs (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    schedule_id     UUID NOT NULL REFERENCES report_schedules(id) ON DELETE CASCADE,
+    status          report_run_status NOT NULL DEFAULT 'running',
+    -- 'schedule' for cron-triggered runs, 'manual' for on-demand runs
+    triggered_by    VARCHAR(20) NOT NULL,
+    file_name       VARCHAR(255),
+    byte_size       INTEGER,
+    error_message   TEXT,
+    started_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at    TIMESTAMPTZ
+);
+
+CREATE INDEX idx_report_runs_schedule ON report_runs(schedule_id, started_at DESC);
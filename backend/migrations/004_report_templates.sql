@@ -0,0 +1,34 @@
+-- Customizable report templates (Tera)
+
+CREATE TYPE report_template_kind AS ENUM ('executive', 'dora', 'gdpr');
+
+CREATE TYPE report_template_format AS ENUM ('html', 'pdf');
+
+-- ============================================================
+-- REPORT TEMPLATES
+-- ============================================================
+
+CREATE TABLE report_templates (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name            VARCHAR(255) NOT NULL,
+    report_kind     report_template_kind NOT NULL,
+    output_format   report_template_format NOT NULL,
+    -- BCP 47 language tag, e.g. 'en' or 'it'
+    language        VARCHAR(10) NOT NULL DEFAULT 'en',
+    -- Tera source; PDF templates emit report markup, HTML templates emit HTML
+    body            TEXT NOT NULL,
+    logo_url        TEXT,
+    is_active       BOOLEAN NOT NULL DEFAULT true,
+    created_by      UUID REFERENCES users(id),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one active template per report, format, and language
+CREATE UNIQUE INDEX idx_report_templates_active
+    ON report_templates(report_kind, output_format, language)
+    WHERE is_active;
+
+CREATE TRIGGER update_report_templates_updated_at
+    BEFORE UPDATE ON report_templates
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,21 @@
+-- Daily finding snapshots backing the dashboard trend series
+
+-- ============================================================
+-- FINDING DAILY SNAPSHOTS
+-- ============================================================
+
+-- One row per day, severity, and category. Rows for the current day are
+-- overwritten by each snapshot run, so a finished day holds its last capture.
+CREATE TABLE finding_daily_snapshots (
+    snapshot_date       DATE NOT NULL,
+    normalized_severity severity_level NOT NULL,
+    finding_category    finding_category NOT NULL,
+    -- Findings first seen on snapshot_date
+    opened              INTEGER NOT NULL DEFAULT 0,
+    -- Findings moved to Closed, Invalidated, or False_Positive on snapshot_date
+    closed              INTEGER NOT NULL DEFAULT 0,
+    -- Findings open at capture time
+    open_total          INTEGER NOT NULL DEFAULT 0,
+    captured_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (snapshot_date, normalized_severity, finding_category)
+);
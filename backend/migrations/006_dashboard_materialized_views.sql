@@ -0,0 +1,38 @@
+-- Materialized views backing the dashboard statistics
+
+-- ============================================================
+-- FINDING COUNTS
+-- ============================================================
+
+-- Finding counts per severity, status, application, and scanner. Refreshed
+-- by a background job and after each ingestion.
+CREATE MATERIALIZED VIEW mv_finding_counts AS
+SELECT
+    normalized_severity,
+    status,
+    application_id,
+    source_tool,
+    COUNT(*) AS finding_count
+FROM findings
+GROUP BY normalized_severity, status, application_id, source_tool;
+
+-- Required for REFRESH MATERIALIZED VIEW CONCURRENTLY
+CREATE UNIQUE INDEX idx_mv_finding_counts_key
+    ON mv_finding_counts (normalized_severity, status, application_id, source_tool);
+CREATE INDEX idx_mv_finding_counts_app ON mv_finding_counts (application_id);
+
+-- ============================================================
+-- SLA BUCKETS
+-- ============================================================
+
+-- Finding counts per SLA status and lifecycle status.
+CREATE MATERIALIZED VIEW mv_sla_buckets AS
+SELECT
+    sla_status,
+    status,
+    COUNT(*) AS finding_count
+FROM findings
+WHERE sla_status IS NOT NULL
+GROUP BY sla_status, status;
+
+CREATE UNIQUE INDEX idx_mv_sla_buckets_key ON mv_sla_buckets (sla_status, status);
@@ -0,0 +1,27 @@
+-- User-defined saved dashboards
+
+CREATE TYPE dashboard_visibility AS ENUM ('private', 'shared');
+
+-- ============================================================
+-- SAVED DASHBOARDS
+-- ============================================================
+
+CREATE TABLE saved_dashboards (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name            VARCHAR(255) NOT NULL,
+    description     TEXT,
+    -- 'private' dashboards are visible to their owner only
+    visibility      dashboard_visibility NOT NULL DEFAULT 'private',
+    -- Ordered widgets: title, FindingFilters, and aggregation
+    widgets         JSONB NOT NULL DEFAULT '[]'::JSONB,
+    owner_id        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_saved_dashboards_owner ON saved_dashboards(owner_id);
+CREATE INDEX idx_saved_dashboards_shared ON saved_dashboards(visibility) WHERE visibility = 'shared';
+
+CREATE TRIGGER update_saved_dashboards_updated_at
+    BEFORE UPDATE ON saved_dashboards
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
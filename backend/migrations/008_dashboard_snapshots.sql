@@ -0,0 +1,28 @@
+-- Nightly open-finding snapshots for historical dashboard comparisons
+
+-- ============================================================
+-- DASHBOARD SNAPSHOTS
+-- ============================================================
+
+-- Open findings per day, severity, status, and application. Taken once per
+-- day by the first snapshot run after midnight UTC; later runs that day
+-- leave the rows untouched.
+CREATE TABLE dashboard_snapshots (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    snapshot_date       DATE NOT NULL,
+    normalized_severity severity_level NOT NULL,
+    status              finding_status NOT NULL,
+    -- NULL for findings not mapped to an application
+    application_id      UUID REFERENCES applications(id) ON DELETE CASCADE,
+    finding_count       INTEGER NOT NULL,
+    captured_at         TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One row per combination and day; unmapped findings share the nil UUID
+CREATE UNIQUE INDEX idx_dashboard_snapshots_key ON dashboard_snapshots (
+    snapshot_date,
+    normalized_severity,
+    status,
+    COALESCE(application_id, '00000000-0000-0000-0000-000000000000'::UUID)
+);
+CREATE INDEX idx_dashboard_snapshots_app ON dashboard_snapshots (application_id, snapshot_date);
@@ -0,0 +1,22 @@
+-- Per-ingestion finding occurrences for deduplication metrics
+
+-- ============================================================
+-- FINDING OCCURRENCES
+-- ============================================================
+
+-- One row per scanner record that resolved to a finding during ingestion.
+-- `outcome` is 'new', 'updated' (fingerprint matched an open finding), or
+-- 'reopened' (fingerprint matched a closed finding).
+CREATE TABLE finding_occurrences (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    finding_id          UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    ingestion_log_id    UUID NOT NULL REFERENCES ingestion_logs(id) ON DELETE CASCADE,
+    fingerprint         VARCHAR(128) NOT NULL,
+    source_tool         VARCHAR(100) NOT NULL,
+    outcome             VARCHAR(20) NOT NULL,
+    seen_at             TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_finding_occurrences_finding ON finding_occurrences(finding_id);
+CREATE INDEX idx_finding_occurrences_ingestion ON finding_occurrences(ingestion_log_id, fingerprint);
+CREATE INDEX idx_finding_occurrences_fingerprint ON finding_occurrences(fingerprint);
@@ -0,0 +1,31 @@
+-- Named finding-list views: saved filters, sort, and visible columns
+
+CREATE TYPE filter_scope AS ENUM ('personal', 'shared');
+
+-- ============================================================
+-- SAVED FILTERS
+-- ============================================================
+
+CREATE TABLE saved_filters (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name            VARCHAR(255) NOT NULL,
+    description     TEXT,
+    -- 'personal' filters are visible to their owner only
+    scope           filter_scope NOT NULL DEFAULT 'personal',
+    -- Serialized FindingFilters query parameters
+    filters         JSONB NOT NULL DEFAULT '{}'::JSONB,
+    -- Serialized FindingSort (sort_by, sort_dir)
+    sort            JSONB NOT NULL DEFAULT '{}'::JSONB,
+    -- Ordered list of visible column keys
+    columns         JSONB NOT NULL DEFAULT '[]'::JSONB,
+    owner_id        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (owner_id, name)
+);
+
+CREATE INDEX idx_saved_filters_shared ON saved_filters(scope) WHERE scope = 'shared';
+
+CREATE TRIGGER update_saved_filters_updated_at
+    BEFORE UPDATE ON saved_filters
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
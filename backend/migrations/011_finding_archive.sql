@@ -0,0 +1,6 @@
+-- Soft archive for findings hidden from working lists
+
+ALTER TABLE findings ADD COLUMN archived_at TIMESTAMPTZ;
+
+-- List queries filter on unarchived findings by default
+CREATE INDEX idx_findings_unarchived ON findings(id) WHERE archived_at IS NULL;
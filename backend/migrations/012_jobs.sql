@@ -0,0 +1,31 @@
+-- Persistent background job queue processed by in-process workers
+
+CREATE TYPE job_status AS ENUM ('queued', 'running', 'succeeded', 'failed');
+
+-- ============================================================
+-- JOBS
+-- ============================================================
+
+CREATE TABLE jobs (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Handler name, e.g. 'dashboard.refresh'
+    kind            VARCHAR(100) NOT NULL,
+    payload         JSONB NOT NULL DEFAULT '{}'::JSONB,
+    status          job_status NOT NULL DEFAULT 'queued',
+    -- Attempts started so far; failed attempts are retried until max_attempts
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    max_attempts    INTEGER NOT NULL DEFAULT 3,
+    -- Earliest time the job may start (scheduling and retry backoff)
+    run_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Set while a worker holds the job; stale locks are recovered
+    locked_at       TIMESTAMPTZ,
+    last_error      TEXT,
+    created_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at      TIMESTAMPTZ,
+    completed_at    TIMESTAMPTZ
+);
+
+CREATE INDEX idx_jobs_due ON jobs(run_at) WHERE status = 'queued';
+CREATE INDEX idx_jobs_running ON jobs(locked_at) WHERE status = 'running';
+CREATE INDEX idx_jobs_created ON jobs(created_at DESC);
@@ -0,0 +1,12 @@
+-- Trigram indexes for substring (ILIKE '%...%') searches
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Finding filters and global search on package, path, and URL fragments
+CREATE INDEX idx_sca_package_name_trgm ON finding_sca USING GIN (package_name gin_trgm_ops);
+CREATE INDEX idx_sast_file_path_trgm ON finding_sast USING GIN (file_path gin_trgm_ops);
+CREATE INDEX idx_dast_target_url_trgm ON finding_dast USING GIN (target_url gin_trgm_ops);
+
+-- Application search matches name or code
+CREATE INDEX idx_applications_app_name_trgm ON applications USING GIN (app_name gin_trgm_ops);
+CREATE INDEX idx_applications_app_code_trgm ON applications USING GIN (app_code gin_trgm_ops);
@@ -0,0 +1,267 @@
+-- Optional monthly range partitioning of findings (by first_seen) and
+-- finding_history (by created_at)
+--
+-- Nothing is partitioned by this migration. With TIME_PARTITIONING_ENABLED
+-- the application calls partition_by_month() for both tables at startup; a
+-- plain table is converted in place and an already partitioned one is left
+-- alone. Existing rows, and rows of the month of the conversion, stay in the
+-- table's default partition, whose CHECK constraint lets later partitions be
+-- created without scanning it. The following months get their own
+-- partitions, created ahead of time by the application
+-- (PARTITION_MONTHS_AHEAD), so time-bounded queries only scan the months
+-- they need.
+--
+-- A partitioned table can only be the target of a foreign key that includes
+-- the partition key, and every table holding a finding id references
+-- findings(id) alone. Converting a table therefore replaces the foreign keys
+-- that reference it with triggers: inserts and updates on the referencing
+-- table check that the id exists, and deletes from the partitioned table
+-- apply the replaced key's ON DELETE action.
+
+CREATE TABLE partitioned_references (
+    referenced_table    TEXT NOT NULL,
+    referencing_table   TEXT NOT NULL,
+    referencing_column  TEXT NOT NULL,
+    -- pg_constraint.confdeltype: 'c' cascades, 'n' sets NULL, others restrict
+    on_delete           TEXT NOT NULL,
+    PRIMARY KEY (referenced_table, referencing_table, referencing_column)
+);
+
+-- BEFORE INSERT OR UPDATE on a referencing table.
+-- Arguments: referenced table, referencing column.
+CREATE FUNCTION check_partitioned_reference()
+RETURNS TRIGGER AS $$
+DECLARE
+    referenced_id UUID := (to_jsonb(NEW) ->> TG_ARGV[1])::UUID;
+    matched INTEGER;
+BEGIN
+    IF referenced_id IS NULL THEN
+        RETURN NEW;
+    END IF;
+    -- Lock the referenced row the way a foreign key check does
+    EXECUTE format('SELECT 1 FROM %I WHERE id = $1 FOR KEY SHARE', TG_ARGV[0])
+        USING referenced_id;
+    GET DIAGNOSTICS matched = ROW_COUNT;
+    IF matched = 0 THEN
+        RAISE EXCEPTION 'insert or update on table "%" violates reference to table "%"',
+                TG_TABLE_NAME, TG_ARGV[0]
+            USING ERRCODE = 'foreign_key_violation',
+                  DETAIL = format('Key (%s)=(%s) is not present in table "%s".',
+                                  TG_ARGV[1], referenced_id, TG_ARGV[0]);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- AFTER DELETE on a partitioned table. Argument: the partitioned table.
+CREATE FUNCTION propagate_partitioned_delete()
+RETURNS TRIGGER AS $$
+DECLARE
+    ref RECORD;
+    matched INTEGER;
+BEGIN
+    -- An update that moves a row to another partition deletes and re-inserts
+    -- it under the same id; its references stay valid
+    EXECUTE format('SELECT 1 FROM %I WHERE id = $1', TG_ARGV[0]) USING OLD.id;
+    GET DIAGNOSTICS matched = ROW_COUNT;
+    IF matched > 0 THEN
+        RETURN OLD;
+    END IF;
+
+    FOR ref IN
+        SELECT referencing_table, referencing_column, on_delete
+        FROM partitioned_references
+        WHERE referenced_table = TG_ARGV[0]
+    LOOP
+        IF ref.on_delete = 'c' THEN
+            EXECUTE format('DELETE FROM %s WHERE %I = $1',
+                           ref.referencing_table, ref.referencing_column)
+                USING OLD.id;
+        ELSIF ref.on_delete = 'n' THEN
+            EXECUTE format('UPDATE %1$s SET %2$I = NULL WHERE %2$I = $1',
+                           ref.referencing_table, ref.referencing_column)
+                USING OLD.id;
+        ELSE
+            EXECUTE format('SELECT 1 FROM %s WHERE %I = $1 LIMIT 1',
+                           ref.referencing_table, ref.referencing_column)
+                USING OLD.id;
+            GET DIAGNOSTICS matched = ROW_COUNT;
+            IF matched > 0 THEN
+                RAISE EXCEPTION 'delete on table "%" violates reference from table "%"',
+                        TG_ARGV[0], ref.referencing_table
+                    USING ERRCODE = 'foreign_key_violation';
+            END IF;
+        END IF;
+    END LOOP;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Convert the plain table `target` to monthly range partitions on
+-- `partition_key`, keeping its rows in `<target>_default`. Indexes, outgoing
+-- foreign keys, triggers and dependent views are carried over. Returns false
+-- when the table is already partitioned.
+CREATE FUNCTION partition_by_month(target TEXT, partition_key TEXT)
+RETURNS BOOLEAN AS $$
+DECLARE
+    parent TEXT := target || '_partitioned';
+    default_partition TEXT := target || '_default';
+    bound TIMESTAMPTZ := date_trunc('month', NOW()) + INTERVAL '1 month';
+    pk_name TEXT;
+    pk_columns TEXT;
+    item RECORD;
+    drop_views TEXT[] := '{}';
+    create_views TEXT[] := '{}';
+    statement TEXT;
+    referenced BOOLEAN := FALSE;
+BEGIN
+    -- Instances starting together convert the table once
+    PERFORM pg_advisory_xact_lock(hashtext('partition_by_month'), hashtext(target));
+    IF (SELECT relkind FROM pg_class WHERE oid = target::regclass) = 'p' THEN
+        RETURN FALSE;
+    END IF;
+    EXECUTE format('LOCK TABLE %I IN ACCESS EXCLUSIVE MODE', target);
+
+    IF EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE contype = 'f' AND confrelid = target::regclass AND cardinality(conkey) > 1
+    ) THEN
+        RAISE EXCEPTION 'multi-column foreign keys to % are not supported', target;
+    END IF;
+
+    -- Views reading the table, including views of those views, are dropped
+    -- and recreated on the partitioned table
+    FOR item IN
+        WITH RECURSIVE dependents(oid) AS (
+            SELECT r.ev_class
+            FROM pg_depend d
+            JOIN pg_rewrite r ON r.oid = d.objid
+            WHERE d.classid = 'pg_rewrite'::regclass
+              AND d.refobjid = target::regclass
+              AND r.ev_class <> target::regclass
+            UNION
+            SELECT r.ev_class
+            FROM dependents v
+            JOIN pg_depend d ON d.refobjid = v.oid
+            JOIN pg_rewrite r ON r.oid = d.objid
+            WHERE d.classid = 'pg_rewrite'::regclass
+              AND r.ev_class <> v.oid
+        )
+        SELECT c.oid, c.oid::regclass::text AS name, c.relkind, c.relispopulated
+        FROM pg_class c
+        WHERE c.oid IN (SELECT oid FROM dependents)
+        ORDER BY c.oid
+    LOOP
+        IF item.relkind = 'm' THEN
+            drop_views := format('DROP MATERIALIZED VIEW %s', item.name) || drop_views;
+            create_views := create_views || format(
+                'CREATE MATERIALIZED VIEW %s AS %s WITH %s DATA',
+                item.name, rtrim(pg_get_viewdef(item.oid), ';'),
+                CASE WHEN item.relispopulated THEN '' ELSE 'NO' END
+            );
+            create_views := create_views || ARRAY(
+                SELECT pg_get_indexdef(indexrelid) FROM pg_index WHERE indrelid = item.oid
+            );
+        ELSE
+            drop_views := format('DROP VIEW %s', item.name) || drop_views;
+            create_views := create_views || format(
+                'CREATE VIEW %s AS %s', item.name, rtrim(pg_get_viewdef(item.oid), ';')
+            );
+        END IF;
+    END LOOP;
+    FOREACH statement IN ARRAY drop_views LOOP
+        EXECUTE statement;
+    END LOOP;
+
+    -- Same columns, defaults, generated columns and checks
+    EXECUTE format(
+        'CREATE TABLE %I (LIKE %I INCLUDING DEFAULTS INCLUDING GENERATED '
+        'INCLUDING CONSTRAINTS INCLUDING STORAGE INCLUDING COMMENTS) '
+        'PARTITION BY RANGE (%I)',
+        parent, target, partition_key
+    );
+
+    -- The partition key joins the primary key
+    SELECT c.conname, string_agg(quote_ident(a.attname), ', ' ORDER BY k.ord)
+    INTO pk_name, pk_columns
+    FROM pg_constraint c
+    CROSS JOIN unnest(c.conkey) WITH ORDINALITY AS k(attnum, ord)
+    JOIN pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = k.attnum
+    WHERE c.conrelid = target::regclass AND c.contype = 'p'
+    GROUP BY c.conname;
+    EXECUTE format('ALTER TABLE %I RENAME CONSTRAINT %I TO %I',
+                   target, pk_name, default_partition || '_pkey');
+    EXECUTE format('ALTER TABLE %I ADD CONSTRAINT %I PRIMARY KEY (%s, %I)',
+                   parent, pk_name, pk_columns, partition_key);
+
+    -- Secondary indexes move to the parent under their names; the table's own
+    -- copies are attached to them rather than rebuilt
+    FOR item IN
+        SELECT i.indexrelid::regclass::text AS name, pg_get_indexdef(i.indexrelid) AS definition
+        FROM pg_index i
+        WHERE i.indrelid = target::regclass AND NOT i.indisprimary
+    LOOP
+        EXECUTE format('ALTER INDEX %s RENAME TO %I', item.name, left(item.name, 54) || '_default');
+        EXECUTE regexp_replace(item.definition, ' ON \S+ ', format(' ON %I ', parent));
+    END LOOP;
+
+    FOR item IN
+        SELECT conname, pg_get_constraintdef(oid) AS definition
+        FROM pg_constraint
+        WHERE conrelid = target::regclass AND contype = 'f'
+    LOOP
+        EXECUTE format('ALTER TABLE %I ADD CONSTRAINT %I %s', parent, item.conname, item.definition);
+    END LOOP;
+
+    FOR item IN
+        SELECT tgname, pg_get_triggerdef(oid) AS definition
+        FROM pg_trigger
+        WHERE tgrelid = target::regclass AND NOT tgisinternal
+    LOOP
+        EXECUTE format('DROP TRIGGER %I ON %I', item.tgname, target);
+        EXECUTE regexp_replace(item.definition, ' ON \S+ ', format(' ON %I ', parent));
+    END LOOP;
+
+    -- Foreign keys to the table become triggers
+    FOR item IN
+        SELECT c.conname, c.conrelid::regclass::text AS referencing_table,
+               a.attname::text AS referencing_column, c.confdeltype::text AS on_delete
+        FROM pg_constraint c
+        JOIN pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = c.conkey[1]
+        WHERE c.contype = 'f' AND c.confrelid = target::regclass AND c.conparentid = 0
+    LOOP
+        referenced := TRUE;
+        EXECUTE format('ALTER TABLE %s DROP CONSTRAINT %I', item.referencing_table, item.conname);
+        INSERT INTO partitioned_references (referenced_table, referencing_table, referencing_column, on_delete)
+        VALUES (target, item.referencing_table, item.referencing_column, item.on_delete)
+        ON CONFLICT DO NOTHING;
+        EXECUTE format(
+            'CREATE TRIGGER %I BEFORE INSERT OR UPDATE OF %I ON %s '
+            'FOR EACH ROW EXECUTE FUNCTION check_partitioned_reference(%L, %L)',
+            item.conname, item.referencing_column, item.referencing_table,
+            target, item.referencing_column
+        );
+    END LOOP;
+    IF referenced THEN
+        EXECUTE format(
+            'CREATE TRIGGER %I AFTER DELETE ON %I '
+            'FOR EACH ROW EXECUTE FUNCTION propagate_partitioned_delete(%L)',
+            target || '_propagate_delete', parent, target
+        );
+    END IF;
+
+    -- Attaching builds the new primary key index on the table
+    EXECUTE format('ALTER TABLE %I DROP CONSTRAINT %I', target, default_partition || '_pkey');
+    EXECUTE format('ALTER TABLE %I RENAME TO %I', target, default_partition);
+    EXECUTE format('ALTER TABLE %I RENAME TO %I', parent, target);
+    -- Rows after the current month always land in monthly partitions
+    EXECUTE format('ALTER TABLE %I ADD CONSTRAINT %I CHECK (%I < %L)',
+                   default_partition, default_partition || '_bound', partition_key, bound);
+    EXECUTE format('ALTER TABLE %I ATTACH PARTITION %I DEFAULT', target, default_partition);
+
+    FOREACH statement IN ARRAY create_views LOOP
+        EXECUTE statement;
+    END LOOP;
+    RETURN TRUE;
+END;
+$$ LANGUAGE plpgsql;
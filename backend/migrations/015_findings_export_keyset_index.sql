@@ -0,0 +1,7 @@
+-- Findings exports read in keyset-paginated batches ordered by
+-- (first_seen DESC, id DESC). A composite index lets every batch start
+-- where the previous one ended instead of re-sorting the remaining rows.
+-- It also serves first_seen range filters, so the single-column index goes.
+
+CREATE INDEX idx_findings_first_seen_id ON findings(first_seen, id);
+DROP INDEX IF EXISTS idx_findings_first_seen;
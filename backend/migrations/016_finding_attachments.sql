@@ -0,0 +1,23 @@
+-- Evidence attachments (screenshots, HTTP traces, packet captures) on findings.
+-- File contents live in the configured storage backend under storage_key;
+-- this table holds the metadata.
+
+-- ============================================================
+-- FINDING ATTACHMENTS
+-- ============================================================
+
+CREATE TABLE finding_attachments (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    finding_id      UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    -- Sanitized client file name, used for downloads
+    file_name       VARCHAR(255) NOT NULL,
+    -- Derived from the file extension, never taken from the client
+    content_type    VARCHAR(100) NOT NULL,
+    size_bytes      BIGINT NOT NULL,
+    sha256          CHAR(64) NOT NULL,
+    storage_key     TEXT NOT NULL UNIQUE,
+    uploaded_by     UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_finding_attachments_finding ON finding_attachments(finding_id, created_at);
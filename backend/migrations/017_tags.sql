@@ -0,0 +1,55 @@
+-- First-class tags: names, colors, and descriptions for the labels stored in
+-- findings.tags. The JSONB array on findings stays the source of truth for
+-- which findings carry a tag; this table describes the tags themselves.
+
+-- ============================================================
+-- TAGS
+-- ============================================================
+
+CREATE TABLE tags (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Exactly as stored in findings.tags
+    name            TEXT NOT NULL UNIQUE,
+    -- '#rrggbb'
+    color           VARCHAR(7),
+    description     TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_tags_updated_at
+    BEFORE UPDATE ON tags
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Usage counts, rename, and merge look findings up by tag
+CREATE INDEX idx_findings_tags ON findings USING GIN(tags);
+
+-- Register every tag already in use
+INSERT INTO tags (name)
+SELECT DISTINCT t.name
+FROM findings f,
+     jsonb_array_elements_text(
+         CASE WHEN jsonb_typeof(f.tags) = 'array' THEN f.tags ELSE '[]'::JSONB END
+     ) AS t(name)
+WHERE btrim(t.name) <> ''
+ON CONFLICT (name) DO NOTHING;
+
+-- Register tags that findings pick up later (manual edits, bulk tagging,
+-- ingestion) so the table never misses a tag in use
+CREATE OR REPLACE FUNCTION register_finding_tags()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO tags (name)
+    SELECT DISTINCT t.name
+    FROM jsonb_array_elements_text(NEW.tags) AS t(name)
+    WHERE btrim(t.name) <> ''
+    ON CONFLICT (name) DO NOTHING;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER register_findings_tags
+    AFTER INSERT OR UPDATE OF tags ON findings
+    FOR EACH ROW
+    WHEN (jsonb_typeof(NEW.tags) = 'array' AND NEW.tags <> '[]'::JSONB)
+    EXECUTE FUNCTION register_finding_tags();
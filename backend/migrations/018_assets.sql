@@ -0,0 +1,28 @@
+-- Asset inventory: hosts, DNS names, URLs, repositories, and build artifacts
+-- linked to the applications that own them. Ingestion consults linked
+-- assets before the regex app code patterns.
+
+CREATE TYPE asset_type AS ENUM ('host', 'dns_name', 'url', 'repository', 'artifact');
+
+-- ============================================================
+-- ASSETS
+-- ============================================================
+
+CREATE TABLE assets (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    asset_type      asset_type NOT NULL,
+    -- Normalized: hosts, DNS names, and URLs are lowercase; URLs and
+    -- artifacts match scanner values by prefix
+    identifier      TEXT NOT NULL,
+    application_id  UUID REFERENCES applications(id) ON DELETE SET NULL,
+    description     TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (asset_type, identifier)
+);
+
+CREATE INDEX idx_assets_application ON assets(application_id);
+
+CREATE TRIGGER update_assets_updated_at
+    BEFORE UPDATE ON assets
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
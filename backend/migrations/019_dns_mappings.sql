@@ -0,0 +1,28 @@
+-- Managed DNS name and URL prefix to application mappings for DAST findings.
+-- Ingestion consults them after the asset inventory and before the regex
+-- app code patterns, since scanner hostnames often do not contain the app code.
+
+CREATE TYPE dns_mapping_kind AS ENUM ('dns_name', 'url_prefix');
+
+-- ============================================================
+-- DNS MAPPINGS
+-- ============================================================
+
+CREATE TABLE dns_mappings (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kind            dns_mapping_kind NOT NULL,
+    -- Lowercase. DNS names may start with '*.' to match every subdomain;
+    -- URL prefixes include the scheme
+    pattern         TEXT NOT NULL,
+    application_id  UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    description     TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (kind, pattern)
+);
+
+CREATE INDEX idx_dns_mappings_application ON dns_mappings(application_id);
+
+CREATE TRIGGER update_dns_mappings_updated_at
+    BEFORE UPDATE ON dns_mappings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,40 @@
+-- SBOM storage per application and release. Components are matched to SCA
+-- findings by package name and version when an SBOM is read.
+
+CREATE TYPE sbom_format AS ENUM ('cyclonedx', 'spdx');
+
+-- ============================================================
+-- SBOMS
+-- ============================================================
+
+CREATE TABLE sboms (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    application_id  UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    -- Release or version label; NULL for an unversioned upload
+    release         VARCHAR(255),
+    format          sbom_format NOT NULL,
+    spec_version    VARCHAR(50),
+    document_name   TEXT,
+    component_count INTEGER NOT NULL DEFAULT 0,
+    uploaded_by     UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_sboms_application ON sboms(application_id, release, created_at DESC);
+
+CREATE TABLE sbom_components (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    sbom_id         UUID NOT NULL REFERENCES sboms(id) ON DELETE CASCADE,
+    name            TEXT NOT NULL,
+    -- CycloneDX group or package URL namespace (e.g. a Maven groupId)
+    group_name      TEXT,
+    version         TEXT NOT NULL DEFAULT '',
+    purl            TEXT,
+    -- Package URL type (maven, npm, pypi, ...)
+    package_type    VARCHAR(50),
+    license         TEXT
+);
+
+CREATE INDEX idx_sbom_components_sbom ON sbom_components(sbom_id);
+-- "Which applications contain package X@Y"
+CREATE INDEX idx_sbom_components_name ON sbom_components(lower(name), version);
@@ -0,0 +1,34 @@
+-- Application releases, and the releases that introduced and fixed each
+-- finding, for release notes and first-introduced analytics.
+
+-- ============================================================
+-- RELEASES
+-- ============================================================
+
+CREATE TABLE releases (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    application_id  UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    version         VARCHAR(100) NOT NULL,
+    build_date      TIMESTAMPTZ,
+    commit_sha      VARCHAR(64),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (application_id, version)
+);
+
+CREATE TRIGGER update_releases_updated_at
+    BEFORE UPDATE ON releases
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- ============================================================
+-- FINDING RELEASE LINKS
+-- ============================================================
+
+ALTER TABLE findings
+    ADD COLUMN introduced_in_release_id UUID REFERENCES releases(id) ON DELETE SET NULL,
+    ADD COLUMN fixed_in_release_id UUID REFERENCES releases(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_findings_introduced_release ON findings(introduced_in_release_id)
+    WHERE introduced_in_release_id IS NOT NULL;
+CREATE INDEX idx_findings_fixed_release ON findings(fixed_in_release_id)
+    WHERE fixed_in_release_id IS NOT NULL;
@@ -0,0 +1,25 @@
+-- User-to-application ownership: scopes what non-AppSec users may manage
+-- for an application and names the default recipients of its notifications.
+
+-- Declared from least to most privileged; comparisons rely on the order
+CREATE TYPE ownership_role AS ENUM ('viewer', 'champion', 'owner');
+
+-- ============================================================
+-- APPLICATION OWNERS
+-- ============================================================
+
+CREATE TABLE application_owners (
+    application_id  UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role            ownership_role NOT NULL,
+    assigned_by     UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (application_id, user_id)
+);
+
+CREATE INDEX idx_application_owners_user ON application_owners(user_id);
+
+CREATE TRIGGER update_application_owners_updated_at
+    BEFORE UPDATE ON application_owners
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
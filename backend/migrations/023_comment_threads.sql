@@ -0,0 +1,15 @@
+-- Threaded finding comments with markdown content and @mentions
+
+CREATE TYPE comment_format AS ENUM ('plain', 'markdown');
+
+-- Existing comments were entered as plain text; new ones default to markdown
+ALTER TABLE finding_comments
+    ADD COLUMN parent_id UUID REFERENCES finding_comments(id) ON DELETE CASCADE,
+    ADD COLUMN content_format comment_format NOT NULL DEFAULT 'plain',
+    -- Active users mentioned as @username, resolved when the comment is saved
+    ADD COLUMN mentioned_user_ids UUID[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE finding_comments ALTER COLUMN content_format SET DEFAULT 'markdown';
+
+CREATE INDEX idx_comments_parent ON finding_comments(parent_id) WHERE parent_id IS NOT NULL;
+CREATE INDEX idx_comments_mentions ON finding_comments USING GIN (mentioned_user_ids);
@@ -0,0 +1,7 @@
+-- Editable and deletable finding comments. Previous content is kept in
+-- finding_history; deleted comments stay as empty placeholders so their
+-- replies keep a parent.
+
+ALTER TABLE finding_comments
+    ADD COLUMN edited_at TIMESTAMPTZ,
+    ADD COLUMN deleted_at TIMESTAMPTZ;
@@ -0,0 +1,23 @@
+-- In-app notification inbox
+
+CREATE TYPE notification_kind AS ENUM ('status_change', 'sla_breach', 'mention', 'assignment');
+
+-- ============================================================
+-- NOTIFICATIONS
+-- ============================================================
+
+CREATE TABLE notifications (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind            notification_kind NOT NULL,
+    title           TEXT NOT NULL,
+    body            TEXT,
+    finding_id      UUID REFERENCES findings(id) ON DELETE CASCADE,
+    application_id  UUID REFERENCES applications(id) ON DELETE CASCADE,
+    actor_name      VARCHAR(255),
+    read_at         TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notifications_user ON notifications(user_id, created_at DESC);
+CREATE INDEX idx_notifications_unread ON notifications(user_id) WHERE read_at IS NULL;
@@ -0,0 +1,40 @@
+-- Per-user notification preferences and the email outbox
+
+CREATE TYPE notification_channel AS ENUM ('in_app', 'email');
+CREATE TYPE notification_frequency AS ENUM ('immediate', 'daily_digest', 'off');
+
+-- ============================================================
+-- NOTIFICATION PREFERENCES
+-- ============================================================
+-- Missing rows fall back to the defaults in services::notification_preferences
+
+CREATE TABLE notification_preferences (
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind            notification_kind NOT NULL,
+    channel         notification_channel NOT NULL,
+    frequency       notification_frequency NOT NULL,
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, kind, channel)
+);
+
+-- ============================================================
+-- NOTIFICATION EMAILS
+-- ============================================================
+-- Emails awaiting the mailer: immediate rows go out on its next tick, digest
+-- rows are batched into one email per user per day
+
+CREATE TABLE notification_emails (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind            notification_kind NOT NULL,
+    frequency       notification_frequency NOT NULL CHECK (frequency <> 'off'),
+    title           TEXT NOT NULL,
+    body            TEXT,
+    finding_id      UUID REFERENCES findings(id) ON DELETE CASCADE,
+    application_id  UUID REFERENCES applications(id) ON DELETE CASCADE,
+    actor_name      VARCHAR(255),
+    sent_at         TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notification_emails_pending ON notification_emails(frequency, user_id) WHERE sent_at IS NULL;
@@ -0,0 +1,24 @@
+-- CVE details cached from the NVD 2.0 API
+
+-- ============================================================
+-- CVE CATALOG
+-- ============================================================
+
+CREATE TABLE cve_catalog (
+    cve_id              VARCHAR(32) PRIMARY KEY,
+    -- false when NVD has no record for the id; kept so it is not re-queried every run
+    found               BOOLEAN NOT NULL DEFAULT true,
+    description         TEXT,
+    cvss_v3_vector      VARCHAR(255),
+    cvss_v3_score       REAL,
+    cvss_v3_severity    VARCHAR(16),
+    cvss_v2_vector      VARCHAR(255),
+    cvss_v2_score       REAL,
+    cwe_ids             TEXT[] NOT NULL DEFAULT '{}',
+    reference_links     JSONB NOT NULL DEFAULT '[]'::JSONB,
+    published_at        TIMESTAMPTZ,
+    last_modified_at    TIMESTAMPTZ,
+    fetched_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_cve_catalog_fetched ON cve_catalog(fetched_at);
@@ -0,0 +1,8 @@
+-- OSV.dev enrichment of SCA findings
+
+ALTER TABLE finding_sca
+    ADD COLUMN affected_ranges       JSONB NOT NULL DEFAULT '[]'::JSONB,
+    ADD COLUMN vulnerability_aliases TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN osv_checked_at        TIMESTAMPTZ;
+
+CREATE INDEX idx_sca_osv_pending ON finding_sca(osv_checked_at NULLS FIRST) WHERE fixed_version IS NULL;
@@ -0,0 +1,24 @@
+-- GitHub Security Advisories resolved to their CVE aliases
+
+-- ============================================================
+-- GHSA ADVISORIES
+-- ============================================================
+
+CREATE TABLE ghsa_advisories (
+    ghsa_id             VARCHAR(32) PRIMARY KEY,
+    -- false when GitHub has no advisory for the id; kept so it is not re-queried every run
+    found               BOOLEAN NOT NULL DEFAULT true,
+    cve_ids             TEXT[] NOT NULL DEFAULT '{}',
+    summary             TEXT,
+    severity            VARCHAR(16),
+    cvss_vector         VARCHAR(255),
+    cvss_score          REAL,
+    cwe_ids             TEXT[] NOT NULL DEFAULT '{}',
+    html_url            VARCHAR(500),
+    published_at        TIMESTAMPTZ,
+    advisory_updated_at TIMESTAMPTZ,
+    withdrawn_at        TIMESTAMPTZ,
+    fetched_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_ghsa_advisories_cves ON ghsa_advisories USING GIN(cve_ids);
@@ -0,0 +1,88 @@
+-- CWE dictionary: names and the Research Concepts (view 1000) hierarchy.
+-- Seeded with the most common weaknesses and their ancestors; the full
+-- dictionary is loaded through POST /api/v1/cwes/import.
+
+-- ============================================================
+-- CWE CATALOG
+-- ============================================================
+
+CREATE TABLE cwe_catalog (
+    cwe_id          INTEGER PRIMARY KEY,
+    name            VARCHAR(500) NOT NULL,
+    -- Pillar, Class, Base, or Variant
+    abstraction     VARCHAR(20),
+    status          VARCHAR(20),
+    description     TEXT,
+    -- ChildOf parents in view 1000, primary parent first
+    parent_ids      INTEGER[] NOT NULL DEFAULT '{}',
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_cwe_catalog_parents ON cwe_catalog USING GIN(parent_ids);
+
+INSERT INTO cwe_catalog (cwe_id, name, abstraction, parent_ids) VALUES
+    (284, 'Improper Access Control', 'Pillar', '{}'),
+    (664, 'Improper Control of a Resource Through its Lifetime', 'Pillar', '{}'),
+    (682, 'Incorrect Calculation', 'Pillar', '{}'),
+    (693, 'Protection Mechanism Failure', 'Pillar', '{}'),
+    (703, 'Improper Check or Handling of Exceptional Conditions', 'Pillar', '{}'),
+    (707, 'Improper Neutralization', 'Pillar', '{}'),
+    (20, 'Improper Input Validation', 'Class', '{707}'),
+    (74, 'Improper Neutralization of Special Elements in Output Used by a Downstream Component (''Injection'')', 'Class', '{707}'),
+    (116, 'Improper Encoding or Escaping of Output', 'Class', '{707}'),
+    (117, 'Improper Output Neutralization for Logs', 'Base', '{116}'),
+    (77, 'Improper Neutralization of Special Elements used in a Command (''Command Injection'')', 'Class', '{74}'),
+    (78, 'Improper Neutralization of Special Elements used in an OS Command (''OS Command Injection'')', 'Base', '{77}'),
+    (79, 'Improper Neutralization of Input During Web Page Generation (''Cross-site Scripting'')', 'Base', '{74}'),
+    (94, 'Improper Control of Generation of Code (''Code Injection'')', 'Base', '{74}'),
+    (943, 'Improper Neutralization of Special Elements in Data Query Logic', 'Class', '{74}'),
+    (89, 'Improper Neutralization of Special Elements used in an SQL Command (''SQL Injection'')', 'Base', '{943}'),
+    (285, 'Improper Authorization', 'Class', '{284}'),
+    (862, 'Missing Authorization', 'Class', '{285}'),
+    (863, 'Incorrect Authorization', 'Class', '{285}'),
+    (732, 'Incorrect Permission Assignment for Critical Resource', 'Class', '{285}'),
+    (276, 'Incorrect Default Permissions', 'Base', '{732}'),
+    (269, 'Improper Privilege Management', 'Class', '{284}'),
+    (287, 'Improper Authentication', 'Class', '{284}'),
+    (295, 'Improper Certificate Validation', 'Base', '{287}'),
+    (306, 'Missing Authentication for Critical Function', 'Base', '{287}'),
+    (1390, 'Weak Authentication', 'Class', '{287}'),
+    (1391, 'Use of Weak Credentials', 'Class', '{1390}'),
+    (798, 'Use of Hard-coded Credentials', 'Base', '{1391}'),
+    (118, 'Incorrect Access of Indexable Resource (''Range Error'')', 'Class', '{664}'),
+    (119, 'Improper Restriction of Operations within the Bounds of a Memory Buffer', 'Class', '{118}'),
+    (125, 'Out-of-bounds Read', 'Base', '{119}'),
+    (787, 'Out-of-bounds Write', 'Base', '{119}'),
+    (825, 'Expired Pointer Dereference', 'Base', '{119}'),
+    (416, 'Use After Free', 'Variant', '{825}'),
+    (400, 'Uncontrolled Resource Consumption', 'Class', '{664}'),
+    (770, 'Allocation of Resources Without Limits or Throttling', 'Base', '{400}'),
+    (610, 'Externally Controlled Reference to a Resource in Another Sphere', 'Class', '{664}'),
+    (441, 'Unintended Proxy or Intermediary (''Confused Deputy'')', 'Class', '{610}'),
+    (918, 'Server-Side Request Forgery (SSRF)', 'Base', '{441}'),
+    (601, 'URL Redirection to Untrusted Site (''Open Redirect'')', 'Base', '{610}'),
+    (611, 'Improper Restriction of XML External Entity Reference', 'Base', '{610}'),
+    (384, 'Session Fixation', 'Compound', '{610}'),
+    (668, 'Exposure of Resource to Wrong Sphere', 'Class', '{664}'),
+    (200, 'Exposure of Sensitive Information to an Unauthorized Actor', 'Class', '{668}'),
+    (209, 'Generation of Error Message Containing Sensitive Information', 'Base', '{200}'),
+    (669, 'Incorrect Resource Transfer Between Spheres', 'Class', '{664}'),
+    (434, 'Unrestricted Upload of File with Dangerous Type', 'Base', '{669}'),
+    (706, 'Use of Incorrectly-Resolved Name or Reference', 'Class', '{664}'),
+    (22, 'Improper Limitation of a Pathname to a Restricted Directory (''Path Traversal'')', 'Base', '{706}'),
+    (913, 'Improper Control of Dynamically-Managed Code Resources', 'Class', '{664}'),
+    (502, 'Deserialization of Untrusted Data', 'Base', '{913}'),
+    (915, 'Improperly Controlled Modification of Dynamically-Determined Object Attributes', 'Base', '{913}'),
+    (1321, 'Improperly Controlled Modification of Object Prototype Attributes (''Prototype Pollution'')', 'Variant', '{915}'),
+    (922, 'Insecure Storage of Sensitive Information', 'Class', '{664}'),
+    (312, 'Cleartext Storage of Sensitive Information', 'Base', '{922}'),
+    (345, 'Insufficient Verification of Data Authenticity', 'Class', '{693}'),
+    (352, 'Cross-Site Request Forgery (CSRF)', 'Compound', '{345}'),
+    (311, 'Missing Encryption of Sensitive Data', 'Class', '{693}'),
+    (319, 'Cleartext Transmission of Sensitive Information', 'Base', '{311}'),
+    (326, 'Inadequate Encryption Strength', 'Class', '{693}'),
+    (327, 'Use of a Broken or Risky Cryptographic Algorithm', 'Class', '{693}'),
+    (330, 'Use of Insufficiently Random Values', 'Class', '{693}'),
+    (190, 'Integer Overflow or Wraparound', 'Base', '{682}'),
+    (754, 'Improper Check for Unusual or Exceptional Conditions', 'Class', '{703}'),
+    (476, 'NULL Pointer Dereference', 'Base', '{754}');
@@ -0,0 +1,125 @@
+-- OWASP Top 10 (2021) categories and the CWE mapping used to derive a
+-- finding's owasp_category when the scanner does not report one.
+
+-- ============================================================
+-- CATEGORIES AND CWE MAPPING
+-- ============================================================
+
+CREATE TABLE owasp_categories (
+    -- e.g. 'A03:2021'
+    code            VARCHAR(10) PRIMARY KEY,
+    name            VARCHAR(100) NOT NULL
+);
+
+INSERT INTO owasp_categories (code, name) VALUES
+    ('A01:2021', 'Broken Access Control'),
+    ('A02:2021', 'Cryptographic Failures'),
+    ('A03:2021', 'Injection'),
+    ('A04:2021', 'Insecure Design'),
+    ('A05:2021', 'Security Misconfiguration'),
+    ('A06:2021', 'Vulnerable and Outdated Components'),
+    ('A07:2021', 'Identification and Authentication Failures'),
+    ('A08:2021', 'Software and Data Integrity Failures'),
+    ('A09:2021', 'Security Logging and Monitoring Failures'),
+    ('A10:2021', 'Server-Side Request Forgery');
+
+-- One category per CWE; CWEs listed under two categories by OWASP are kept
+-- in the more specific one
+CREATE TABLE owasp_cwe_mappings (
+    cwe_id          INTEGER PRIMARY KEY,
+    category        VARCHAR(10) NOT NULL REFERENCES owasp_categories(code) ON DELETE CASCADE,
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_owasp_cwe_mappings_category ON owasp_cwe_mappings (category);
+
+INSERT INTO owasp_cwe_mappings (cwe_id, category)
+SELECT cwe_id, 'A01:2021' FROM UNNEST(ARRAY[
+    22, 23, 35, 59, 200, 201, 219, 264, 275, 276, 284, 285, 352, 359, 377,
+    402, 425, 441, 497, 538, 540, 548, 552, 566, 601, 639, 651, 668, 706,
+    732, 862, 863, 913, 922, 1275
+]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A02:2021' FROM UNNEST(ARRAY[
+    261, 296, 310, 319, 321, 322, 323, 324, 325, 326, 327, 328, 329, 330,
+    331, 335, 336, 337, 338, 340, 347, 523, 720, 757, 759, 760, 780, 818, 916
+]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A03:2021' FROM UNNEST(ARRAY[
+    20, 74, 75, 77, 78, 79, 80, 83, 87, 88, 89, 90, 91, 93, 94, 95, 96, 97,
+    98, 99, 100, 113, 116, 138, 184, 470, 471, 564, 610, 643, 644, 652, 917, 943
+]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A04:2021' FROM UNNEST(ARRAY[
+    73, 183, 209, 213, 235, 256, 257, 266, 269, 280, 311, 312, 313, 316, 419,
+    430, 434, 444, 451, 472, 501, 522, 525, 539, 579, 598, 602, 642, 646, 650,
+    653, 656, 657, 799, 807, 840, 841, 927, 1021, 1173
+]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A05:2021' FROM UNNEST(ARRAY[
+    2, 11, 13, 15, 16, 260, 315, 520, 526, 537, 541, 547, 611, 614, 756, 776,
+    942, 1004, 1032, 1174
+]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A06:2021' FROM UNNEST(ARRAY[937, 1035, 1104]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A07:2021' FROM UNNEST(ARRAY[
+    255, 259, 287, 288, 290, 294, 295, 297, 300, 302, 304, 306, 307, 346, 384,
+    521, 613, 620, 640, 798, 940, 1216
+]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A08:2021' FROM UNNEST(ARRAY[
+    345, 353, 426, 494, 502, 565, 784, 829, 830, 915
+]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A09:2021' FROM UNNEST(ARRAY[117, 223, 532, 778]) AS cwe_id
+UNION ALL
+SELECT cwe_id, 'A10:2021' FROM UNNEST(ARRAY[918]) AS cwe_id;
+
+-- ============================================================
+-- CATEGORY CODE
+-- ============================================================
+
+-- Category code ('A03') of a stored owasp_category, whatever the scanner's
+-- spelling: 'A03:2021', 'A03:2021-Injection', 'OWASP-A03', 'owasp-a3'
+CREATE FUNCTION owasp_code(category TEXT) RETURNS TEXT
+LANGUAGE SQL IMMUTABLE PARALLEL SAFE AS $$
+    SELECT 'A' || lpad(substring(upper(category) FROM 'A([0-9]{1,2})'), 2, '0')
+$$;
+
+CREATE INDEX idx_findings_owasp_code ON findings (owasp_code(owasp_category));
+
+-- ============================================================
+-- BACKFILL
+-- ============================================================
+
+-- Derive the category of existing findings from their first mapped CWE
+UPDATE findings f
+SET owasp_category = derived.category
+FROM (
+    SELECT DISTINCT ON (f.id) f.id, m.category
+    FROM findings f
+    CROSS JOIN LATERAL jsonb_array_elements_text(f.cwe_ids) WITH ORDINALITY AS c(value, position)
+    JOIN owasp_cwe_mappings m ON m.cwe_id = substring(c.value FROM '[0-9]+')::int
+    WHERE f.owasp_category IS NULL
+      AND c.value ~ '[0-9]'
+    ORDER BY f.id, c.position
+) derived
+WHERE f.id = derived.id;
+
+-- ============================================================
+-- DASHBOARD VIEW
+-- ============================================================
+
+-- Finding counts per OWASP category code and lifecycle status. Refreshed
+-- with the other dashboard views.
+CREATE MATERIALIZED VIEW mv_owasp_counts AS
+SELECT
+    owasp_code(owasp_category) AS category,
+    status,
+    COUNT(*) AS finding_count
+FROM findings
+WHERE owasp_code(owasp_category) IS NOT NULL
+GROUP BY owasp_code(owasp_category), status;
+
+CREATE UNIQUE INDEX idx_mv_owasp_counts_key ON mv_owasp_counts (category, status);
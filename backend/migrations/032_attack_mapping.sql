@@ -0,0 +1,122 @@
+-- MITRE ATT&CK techniques reached through CAPEC attack patterns: a finding's
+-- CWEs map to the CAPEC patterns exploiting them, and those patterns to
+-- ATT&CK techniques. Seeded with common patterns; the full catalogs are
+-- loaded through POST /api/v1/attack/capec/import and
+-- POST /api/v1/attack/techniques/import.
+
+-- ============================================================
+-- ATT&CK TECHNIQUES
+-- ============================================================
+
+CREATE TABLE attack_techniques (
+    -- e.g. 'T1110' or 'T1110.004'
+    technique_id    VARCHAR(20) PRIMARY KEY,
+    name            VARCHAR(255) NOT NULL,
+    -- Enterprise kill chain phases, e.g. 'credential-access'
+    tactics         TEXT[] NOT NULL DEFAULT '{}',
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO attack_techniques (technique_id, name, tactics) VALUES
+    ('T1005', 'Data from Local System', '{collection}'),
+    ('T1021', 'Remote Services', '{lateral-movement}'),
+    ('T1040', 'Network Sniffing', '{credential-access,discovery}'),
+    ('T1070', 'Indicator Removal', '{defense-evasion}'),
+    ('T1078', 'Valid Accounts', '{defense-evasion,persistence,privilege-escalation,initial-access}'),
+    ('T1078.001', 'Valid Accounts: Default Accounts', '{defense-evasion,persistence,privilege-escalation,initial-access}'),
+    ('T1083', 'File and Directory Discovery', '{discovery}'),
+    ('T1110', 'Brute Force', '{credential-access}'),
+    ('T1110.001', 'Brute Force: Password Guessing', '{credential-access}'),
+    ('T1110.002', 'Brute Force: Password Cracking', '{credential-access}'),
+    ('T1110.003', 'Brute Force: Password Spraying', '{credential-access}'),
+    ('T1110.004', 'Brute Force: Credential Stuffing', '{credential-access}'),
+    ('T1133', 'External Remote Services', '{persistence,initial-access}'),
+    ('T1190', 'Exploit Public-Facing Application', '{initial-access}'),
+    ('T1195.001', 'Supply Chain Compromise: Compromise Software Dependencies and Development Tools', '{initial-access}'),
+    ('T1195.002', 'Supply Chain Compromise: Compromise Software Supply Chain', '{initial-access}'),
+    ('T1498.001', 'Network Denial of Service: Direct Network Flood', '{impact}'),
+    ('T1499', 'Endpoint Denial of Service', '{impact}'),
+    ('T1499.003', 'Endpoint Denial of Service: Application Exhaustion Flood', '{impact}'),
+    ('T1505.003', 'Server Software Component: Web Shell', '{persistence}'),
+    ('T1539', 'Steal Web Session Cookie', '{credential-access}'),
+    ('T1548', 'Abuse Elevation Control Mechanism', '{privilege-escalation,defense-evasion}'),
+    ('T1552', 'Unsecured Credentials', '{credential-access}'),
+    ('T1552.001', 'Unsecured Credentials: Credentials In Files', '{credential-access}'),
+    ('T1557', 'Adversary-in-the-Middle', '{credential-access,collection}'),
+    ('T1562.002', 'Impair Defenses: Disable Windows Event Logging', '{defense-evasion}'),
+    ('T1566', 'Phishing', '{initial-access}'),
+    ('T1574.010', 'Hijack Execution Flow: Services File Permissions Weakness', '{persistence,privilege-escalation,defense-evasion}');
+
+-- ============================================================
+-- CAPEC ATTACK PATTERNS
+-- ============================================================
+
+CREATE TABLE capec_patterns (
+    capec_id        INTEGER PRIMARY KEY,
+    name            VARCHAR(500) NOT NULL,
+    -- Related Weaknesses
+    cwe_ids         INTEGER[] NOT NULL DEFAULT '{}',
+    -- ATT&CK taxonomy mappings
+    technique_ids   TEXT[] NOT NULL DEFAULT '{}',
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_capec_patterns_cwes ON capec_patterns USING GIN(cwe_ids);
+
+INSERT INTO capec_patterns (capec_id, name, cwe_ids, technique_ids) VALUES
+    (1, 'Accessing Functionality Not Properly Constrained by ACLs', '{276,285,434,693,732}', '{T1574.010}'),
+    (16, 'Dictionary-based Password Attack', '{257,262,263,307,308,309,521,654}', '{T1110.002}'),
+    (17, 'Using Malicious Files', '{59,270,272,282,285,693,732}', '{T1574.010}'),
+    (49, 'Password Brute Forcing', '{257,262,263,307,308,309,521,654}', '{T1110.001}'),
+    (55, 'Rainbow Table Password Cracking', '{261,521,759,916}', '{T1110.002}'),
+    (70, 'Try Common or Default Usernames and Passwords', '{262,263,521,798,1392,1393}', '{T1078.001}'),
+    (81, 'Web Server Logs Tampering', '{20,75,93,96,117,150,221,276,279}', '{T1070,T1562.002}'),
+    (94, 'Adversary in the Middle (AiTM)', '{287,290,294,300,593}', '{T1557}'),
+    (98, 'Phishing', '{451}', '{T1566}'),
+    (102, 'Session Sidejacking', '{294,319,522,523,614}', '{T1539}'),
+    (112, 'Brute Force', '{326,330,521}', '{T1110}'),
+    (122, 'Privilege Abuse', '{269,732}', '{T1548}'),
+    (125, 'Flooding', '{404,770}', '{T1498.001,T1499}'),
+    (130, 'Excessive Allocation', '{404,770}', '{T1499.003}'),
+    (150, 'Collect Data from Common Resource Locations', '{552}', '{T1005,T1552}'),
+    (157, 'Sniffing Attacks', '{311}', '{T1040}'),
+    (180, 'Exploiting Incorrectly Configured Access Control Security Levels', '{732}', '{T1574.010}'),
+    (185, 'Malicious Software Download', '{494}', '{T1195.002}'),
+    (497, 'File Discovery', '{200,552}', '{T1083}'),
+    (538, 'Open-Source Library Manipulation', '{494,829}', '{T1195.001}'),
+    (555, 'Remote Services with Stolen Credentials', '{262,263,294,308,309,521,522}', '{T1021,T1133}'),
+    (560, 'Use of Known Domain Credentials', '{262,263,654}', '{T1078}'),
+    (565, 'Password Spraying', '{262,263,307,308,309,521,654}', '{T1110.003}'),
+    (600, 'Credential Stuffing', '{262,263,307,308,309,522,654}', '{T1110.004}'),
+    (639, 'Probe System Files', '{552}', '{T1552.001}'),
+    (650, 'Upload a Web Shell to a Web Server', '{287,553}', '{T1505.003}');
+
+-- ============================================================
+-- FINDING TECHNIQUES
+-- ============================================================
+
+ALTER TABLE findings ADD COLUMN attack_technique_ids JSONB NOT NULL DEFAULT '[]';
+
+CREATE INDEX idx_findings_attack_techniques ON findings USING GIN(attack_technique_ids);
+
+-- Techniques for a finding: those of the CAPEC patterns exploiting its CWEs,
+-- plus technique ids carried in its tags (e.g. 'attack.t1190', 'T1059.001')
+CREATE FUNCTION attack_techniques_for(cwe_ids JSONB, tags JSONB) RETURNS JSONB
+LANGUAGE SQL STABLE PARALLEL SAFE AS $$
+    SELECT COALESCE(jsonb_agg(DISTINCT technique ORDER BY technique), '[]')
+    FROM (
+        SELECT unnest(p.technique_ids) AS technique
+        FROM jsonb_array_elements_text(cwe_ids) AS c(value)
+        JOIN capec_patterns p
+            ON p.cwe_ids @> ARRAY[substring(c.value FROM '[0-9]+')::int]
+        WHERE c.value ~ '[0-9]'
+        UNION
+        SELECT substring(upper(t.value) FROM '(?:^|[^A-Z0-9])(T[0-9]{4}(?:\.[0-9]{3})?)(?:$|[^0-9])')
+        FROM jsonb_array_elements_text(tags) AS t(value)
+    ) techniques
+    WHERE technique IS NOT NULL
+$$;
+
+UPDATE findings
+SET attack_technique_ids = attack_techniques_for(cwe_ids, tags)
+WHERE cwe_ids <> '[]' OR tags <> '[]';
@@ -0,0 +1,25 @@
+-- Public exploits per CVE from the Exploit-DB and Metasploit module indexes.
+-- Each source's rows are replaced on every download; SCA findings take the
+-- highest maturity of their CVEs' exploits.
+
+-- ============================================================
+-- EXPLOIT REFERENCES
+-- ============================================================
+
+CREATE TYPE exploit_source AS ENUM ('exploit_db', 'metasploit');
+
+CREATE TABLE exploit_references (
+    source          exploit_source NOT NULL,
+    -- Exploit-DB id or Metasploit module path
+    reference       VARCHAR(500) NOT NULL,
+    cve_id          VARCHAR(30) NOT NULL,
+    title           TEXT NOT NULL,
+    -- Metasploit exploit modules are weaponized, auxiliary modules and
+    -- verified Exploit-DB entries functional, other entries proofs of concept
+    maturity        exploit_maturity NOT NULL,
+    url             TEXT,
+    published_on    DATE,
+    PRIMARY KEY (source, reference, cve_id)
+);
+
+CREATE INDEX idx_exploit_references_cve ON exploit_references (cve_id);
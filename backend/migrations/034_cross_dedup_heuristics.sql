@@ -0,0 +1,25 @@
+-- Configurable cross-tool deduplication heuristics. Each heuristic either
+-- records matched pairs as confirmed duplicates ('auto_merge') or queues
+-- them for analyst review ('review').
+
+INSERT INTO system_config (key, value, description) VALUES
+    ('cross_dedup_heuristics', '{
+        "same_cve_package": {"enabled": true, "action": "auto_merge"},
+        "same_cwe_file_lines": {"enabled": true, "action": "review", "line_tolerance": 0}
+    }'::JSONB, 'Cross-tool duplicate heuristics: same CVE + package, same CWE + file + overlapping lines')
+ON CONFLICT (key) DO NOTHING;
+
+-- ============================================================
+-- REJECTED DUPLICATE PAIRS
+-- ============================================================
+
+-- Pairs an analyst rejected as duplicates, so heuristic runs do not suggest
+-- them again
+CREATE TABLE dedup_rejections (
+    source_finding_id   UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    target_finding_id   UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    rejected_by         UUID REFERENCES users(id),
+    rejected_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (source_finding_id, target_finding_id)
+);
@@ -0,0 +1,13 @@
+-- Fuzzy near-duplicate matching: trigram similarity of titles and locations
+-- (SAST file path or DAST target URL), plus a shared CWE
+
+CREATE INDEX idx_findings_title_trgm ON findings USING GIN (title gin_trgm_ops);
+
+INSERT INTO system_config (key, value, description) VALUES
+    ('finding_similarity', '{
+        "min_score": 0.6,
+        "title_weight": 0.5,
+        "location_weight": 0.3,
+        "cwe_weight": 0.2
+    }'::JSONB, 'Near-duplicate scoring: component weights and the minimum score for a pair to be suggested')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,46 @@
+-- Manual merges of duplicate findings
+--
+-- Merging finding B (source) into finding A (target) moves B's comments,
+-- history, occurrences, attachments and relationships onto A, keeps the
+-- richer of the two category rows on A, adds B's tags to A and archives B.
+-- Each merge records exactly what it changed so it can be undone.
+
+-- ============================================================
+-- FINDING MERGES
+-- ============================================================
+
+CREATE TABLE finding_merges (
+    id                              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    target_finding_id               UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    source_finding_id               UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    justification                   TEXT,
+    moved_comment_ids               UUID[] NOT NULL DEFAULT '{}',
+    moved_history_ids               UUID[] NOT NULL DEFAULT '{}',
+    moved_occurrence_ids            UUID[] NOT NULL DEFAULT '{}',
+    moved_attachment_ids            UUID[] NOT NULL DEFAULT '{}',
+    -- Relationships re-pointed from the source to the target, by end
+    moved_outgoing_relationship_ids UUID[] NOT NULL DEFAULT '{}',
+    moved_incoming_relationship_ids UUID[] NOT NULL DEFAULT '{}',
+    -- Whether the source's richer category row replaced the target's, and
+    -- the target's own row (NULL when it had none)
+    category_data_replaced          BOOLEAN NOT NULL DEFAULT false,
+    replaced_category_data          JSONB,
+    -- Source tags the target did not have
+    added_tags                      JSONB NOT NULL DEFAULT '[]'::JSONB,
+    source_archived_at_before       TIMESTAMPTZ,
+    -- Whether the merge created the source -> target duplicate_of
+    -- relationship, or else the confidence it had before
+    duplicate_relationship_created  BOOLEAN NOT NULL DEFAULT false,
+    duplicate_confidence_before     confidence_level,
+    merged_by                       UUID REFERENCES users(id),
+    merged_at                       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    unmerged_by                     UUID REFERENCES users(id),
+    unmerged_at                     TIMESTAMPTZ,
+
+    CHECK (target_finding_id <> source_finding_id)
+);
+
+CREATE INDEX idx_finding_merges_target ON finding_merges(target_finding_id);
+-- A finding can only be merged into one other finding at a time
+CREATE UNIQUE INDEX idx_finding_merges_active_source ON finding_merges(source_finding_id)
+    WHERE unmerged_at IS NULL;
@@ -0,0 +1,34 @@
+-- Deduplication review queue: record which matcher suggested each duplicate
+-- pair and how strongly, and keep every reviewer decision so thresholds can
+-- be tuned against them
+
+ALTER TABLE finding_relationships
+    ADD COLUMN match_source VARCHAR(50),
+    ADD COLUMN match_score  REAL;
+
+-- Fuzzy matches at or above this score are recorded as confirmed duplicates;
+-- null queues every fuzzy match for review
+UPDATE system_config
+SET value = value || '{"auto_confirm_score": null}'::JSONB
+WHERE key = 'finding_similarity' AND NOT value ? 'auto_confirm_score';
+
+-- ============================================================
+-- DEDUP REVIEW DECISIONS
+-- ============================================================
+
+-- Relationships are deleted on rejection, so the decision keeps its own copy
+-- of the pair and how it was matched
+CREATE TABLE dedup_review_decisions (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    relationship_id     UUID NOT NULL,
+    source_finding_id   UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    target_finding_id   UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    match_source        VARCHAR(50),
+    match_score         REAL,
+    confidence_before   confidence_level,
+    decision            VARCHAR(10) NOT NULL CHECK (decision IN ('accepted', 'rejected')),
+    decided_by          UUID REFERENCES users(id),
+    decided_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_dedup_decisions_source ON dedup_review_decisions(match_source, decided_at);
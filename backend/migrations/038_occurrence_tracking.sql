@@ -0,0 +1,37 @@
+-- Occurrence tracking: each occurrence records when the scan ran and what it
+-- covered (application, branch or target), so a finding's presence can be
+-- followed scan by scan within the same tool, application and branch
+
+ALTER TABLE finding_occurrences
+    ADD COLUMN application_id   UUID REFERENCES applications(id) ON DELETE SET NULL,
+    ADD COLUMN scan_date        TIMESTAMPTZ,
+    ADD COLUMN branch           VARCHAR(255),
+    ADD COLUMN target_url       TEXT;
+
+UPDATE finding_occurrences o
+SET application_id = f.application_id,
+    scan_date = o.seen_at
+FROM findings f
+WHERE f.id = o.finding_id;
+
+UPDATE finding_occurrences o
+SET branch = s.branch
+FROM finding_sast s
+WHERE s.finding_id = o.finding_id;
+
+UPDATE finding_occurrences o
+SET target_url = d.target_url
+FROM finding_dast d
+WHERE d.finding_id = o.finding_id;
+
+ALTER TABLE finding_occurrences
+    ALTER COLUMN scan_date SET DEFAULT NOW(),
+    ALTER COLUMN scan_date SET NOT NULL;
+
+-- Scans of a scope, newest first
+CREATE INDEX idx_finding_occurrences_scope
+    ON finding_occurrences(source_tool, application_id, branch, scan_date DESC);
+
+INSERT INTO system_config (key, value, description) VALUES
+    ('auto_close_missed_scans', '3'::JSONB, 'Consecutive scans of the same tool, application and branch a finding must be missing from before it is eligible for auto-close')
+ON CONFLICT (key) DO NOTHING;
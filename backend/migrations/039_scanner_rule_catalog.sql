@@ -0,0 +1,68 @@
+-- Scanner rule catalog: every (tool, rule) seen during ingestion, with a
+-- per-rule default severity for new findings and an optional suppression.
+-- The rule is the SAST rule id, the DAST plugin, or the first CVE of an SCA
+-- finding.
+
+CREATE TABLE scanner_rules (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    source_tool         VARCHAR(100) NOT NULL,
+    rule_id             VARCHAR(255) NOT NULL,
+    rule_name           VARCHAR(1000),
+    times_seen          BIGINT NOT NULL DEFAULT 0,
+    first_seen_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    default_severity    severity_level,
+    suppressed          BOOLEAN NOT NULL DEFAULT FALSE,
+    suppression_reason  TEXT,
+    policy_updated_by   UUID REFERENCES users(id) ON DELETE SET NULL,
+    policy_updated_at   TIMESTAMPTZ,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (source_tool, rule_id)
+);
+
+CREATE TRIGGER update_scanner_rules_updated_at
+    BEFORE UPDATE ON scanner_rules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE INDEX idx_scanner_rules_last_seen ON scanner_rules(last_seen_at DESC);
+
+ALTER TABLE findings
+    ADD COLUMN scanner_rule_id UUID REFERENCES scanner_rules(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_findings_scanner_rule ON findings(scanner_rule_id);
+
+-- Backfill the catalog and finding links from existing findings
+WITH keyed AS (
+    SELECT f.id, f.source_tool, f.first_seen, f.last_seen,
+           COALESCE(
+               s.rule_id,
+               CASE WHEN f.finding_category = 'DAST' THEN NULLIF(f.metadata->>'plugin', '') END,
+               CASE WHEN f.finding_category = 'SCA' THEN f.cve_ids->>0 END
+           ) AS rule_id,
+           COALESCE(s.rule_name, f.title) AS rule_name
+    FROM findings f
+    LEFT JOIN finding_sast s ON s.finding_id = f.id
+)
+INSERT INTO scanner_rules (source_tool, rule_id, rule_name, times_seen, first_seen_at, last_seen_at)
+SELECT source_tool, LEFT(rule_id, 255), MAX(LEFT(rule_name, 1000)), COUNT(*), MIN(first_seen), MAX(last_seen)
+FROM keyed
+WHERE rule_id IS NOT NULL
+GROUP BY source_tool, LEFT(rule_id, 255);
+
+UPDATE findings f
+SET scanner_rule_id = r.id
+FROM finding_sast s, scanner_rules r
+WHERE s.finding_id = f.id
+  AND r.source_tool = f.source_tool
+  AND r.rule_id = LEFT(s.rule_id, 255);
+
+UPDATE findings f
+SET scanner_rule_id = r.id
+FROM scanner_rules r
+WHERE f.scanner_rule_id IS NULL
+  AND r.source_tool = f.source_tool
+  AND r.rule_id = LEFT(CASE f.finding_category
+                           WHEN 'DAST' THEN f.metadata->>'plugin'
+                           WHEN 'SCA' THEN f.cve_ids->>0
+                       END, 255);
@@ -0,0 +1,14 @@
+-- Analyst severity override: kept apart from the tool-derived
+-- normalized_severity, with the justification of the latest change.
+-- Changes are recorded in finding_history. effective_severity is what
+-- filtering, SLA and risk scoring go by.
+
+ALTER TABLE findings
+    ADD COLUMN severity_override               severity_level,
+    ADD COLUMN severity_override_justification TEXT,
+    ADD COLUMN severity_override_by            UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN severity_override_at            TIMESTAMPTZ,
+    ADD COLUMN effective_severity              severity_level
+        GENERATED ALWAYS AS (COALESCE(severity_override, normalized_severity)) STORED;
+
+CREATE INDEX idx_findings_effective_severity ON findings(effective_severity);
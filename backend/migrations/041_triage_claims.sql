@@ -0,0 +1,15 @@
+-- Triage claims: an analyst claims a New finding from the triage queue so
+-- nobody else triages it at the same time. Claims lapse at expires_at.
+
+CREATE TABLE triage_claims (
+    finding_id  UUID PRIMARY KEY REFERENCES findings(id) ON DELETE CASCADE,
+    claimed_by  UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    claimed_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at  TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_triage_claims_claimed_by ON triage_claims(claimed_by);
+
+-- The queue lists New findings oldest first
+CREATE INDEX idx_findings_new_first_seen ON findings(first_seen, id)
+    WHERE status = 'New' AND archived_at IS NULL;
@@ -0,0 +1,20 @@
+-- Finding splits
+--
+-- Splitting a finding that covers several applications keeps the first part
+-- on the original finding and creates a finding per further part, each with
+-- a copy of the original's history. The split is recorded here.
+
+CREATE TABLE finding_splits (
+    id                       UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    original_finding_id      UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    -- Findings created by the split, in part order
+    split_finding_ids        UUID[] NOT NULL,
+    -- Application of the original finding before the split
+    original_application_id  UUID REFERENCES applications(id) ON DELETE SET NULL,
+    justification            TEXT NOT NULL,
+    split_by                 UUID REFERENCES users(id),
+    split_at                 TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_finding_splits_original ON finding_splits(original_finding_id);
+CREATE INDEX idx_finding_splits_parts ON finding_splits USING GIN(split_finding_ids);
@@ -0,0 +1,54 @@
+-- CVE and package watchlists
+--
+-- Users watch a CVE or a package (optionally one version). Every finding of
+-- an ingestion run matching an entry, and every status change of a matching
+-- finding, is recorded as a hit and notifies the entry's owner.
+
+ALTER TYPE notification_kind ADD VALUE 'watchlist_hit';
+
+CREATE TYPE watchlist_kind AS ENUM ('cve', 'package');
+CREATE TYPE watchlist_trigger AS ENUM ('ingestion', 'status_change');
+
+-- ============================================================
+-- WATCHLIST ENTRIES
+-- ============================================================
+-- CVE ids are stored upper case and package names lower case, the forms
+-- they are matched in
+
+CREATE TABLE watchlist_entries (
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id          UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind             watchlist_kind NOT NULL,
+    identifier       VARCHAR(500) NOT NULL,
+    -- Package entries only; NULL watches every version
+    package_version  VARCHAR(100),
+    note             TEXT,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CHECK (kind = 'package' OR package_version IS NULL)
+);
+
+CREATE UNIQUE INDEX idx_watchlist_entries_unique
+    ON watchlist_entries(user_id, kind, identifier, COALESCE(package_version, ''));
+CREATE INDEX idx_watchlist_entries_identifier ON watchlist_entries(kind, identifier);
+
+-- ============================================================
+-- WATCHLIST HITS
+-- ============================================================
+
+CREATE TABLE watchlist_hits (
+    id                UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    entry_id          UUID NOT NULL REFERENCES watchlist_entries(id) ON DELETE CASCADE,
+    finding_id        UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    trigger           watchlist_trigger NOT NULL,
+    ingestion_log_id  UUID REFERENCES ingestion_logs(id) ON DELETE SET NULL,
+    -- Ingestion outcome ('new', 'updated', 'reopened') or 'Old -> New' status
+    detail            VARCHAR(100),
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_watchlist_hits_entry ON watchlist_hits(entry_id, created_at DESC);
+CREATE INDEX idx_watchlist_hits_finding ON watchlist_hits(finding_id);
+
+-- Package watches match SCA findings by lower-cased name
+CREATE INDEX idx_sca_package_lower ON finding_sca(lower(package_name));
@@ -0,0 +1,4 @@
+-- Severity each scan reported for a finding, so successive scans can be
+-- compared for severity changes. NULL for occurrences recorded before.
+
+ALTER TABLE finding_occurrences ADD COLUMN normalized_severity severity_level;
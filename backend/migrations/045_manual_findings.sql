@@ -0,0 +1,30 @@
+-- Manual findings
+--
+-- Penetration test and bug bounty results entered by hand, without a parser.
+-- They are regular findings of category MANUAL, so they take part in risk
+-- scoring, SLA tracking and every finding list; this layer keeps what the
+-- report said about them.
+
+ALTER TYPE finding_category ADD VALUE 'MANUAL';
+
+CREATE TYPE manual_finding_source AS ENUM ('pentest', 'bug_bounty');
+
+-- ============================================================
+-- MANUAL FINDING LAYER
+-- ============================================================
+
+CREATE TABLE finding_manual (
+    finding_id      UUID PRIMARY KEY REFERENCES findings(id) ON DELETE CASCADE,
+    source          manual_finding_source NOT NULL,
+    -- Host, URL, component or anything else the tester reported against
+    affected_asset  VARCHAR(2000) NOT NULL,
+    evidence        TEXT,
+    -- Tester or bug bounty researcher who reported the finding
+    reporter        VARCHAR(255) NOT NULL,
+    -- Pentest engagement or bug bounty program
+    engagement      VARCHAR(255),
+    reported_at     TIMESTAMPTZ
+);
+
+CREATE INDEX idx_manual_affected_asset ON finding_manual(affected_asset);
+CREATE INDEX idx_manual_engagement ON finding_manual(engagement);
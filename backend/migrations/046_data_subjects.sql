@@ -0,0 +1,15 @@
+-- Data subjects
+--
+-- People pseudonymized after an erasure request. Their names and emails are
+-- replaced everywhere by a pseudonym, and only the pseudonym is kept here: the
+-- mapping back to the person is not stored. A later request for the same
+-- person, or an alias missed the first time, reuses the pseudonym so the
+-- audit trail stays consistent.
+
+CREATE TABLE data_subjects (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    pseudonym       VARCHAR(64) NOT NULL UNIQUE,
+    user_id         UUID UNIQUE REFERENCES users(id) ON DELETE SET NULL,
+    created_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,49 @@
+-- License compliance policies
+--
+-- Policies flag SCA findings by the license of their package: a deny rule
+-- flags the listed licenses everywhere, a copyleft rule flags copyleft
+-- licenses in applications shipped to third parties. Violations annotate the
+-- finding; they are recomputed when policies change and after each ingestion.
+
+-- Applications whose code is shipped outside the organization
+ALTER TABLE applications ADD COLUMN is_distributed BOOLEAN NOT NULL DEFAULT false;
+
+CREATE TYPE license_policy_rule AS ENUM ('deny', 'copyleft_distributed');
+
+-- ============================================================
+-- LICENSE POLICIES
+-- ============================================================
+-- Licenses are SPDX ids stored lower case without -only/-or-later suffixes,
+-- the form they are matched in. An empty list on a copyleft rule means the
+-- built-in copyleft list.
+
+CREATE TABLE license_policies (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name            VARCHAR(255) NOT NULL UNIQUE,
+    rule            license_policy_rule NOT NULL,
+    licenses        TEXT[] NOT NULL DEFAULT '{}',
+    severity        severity_level NOT NULL,
+    enabled         BOOLEAN NOT NULL DEFAULT true,
+    created_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_license_policies_updated_at
+    BEFORE UPDATE ON license_policies
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- ============================================================
+-- LICENSE VIOLATIONS
+-- ============================================================
+
+CREATE TABLE license_violations (
+    policy_id       UUID NOT NULL REFERENCES license_policies(id) ON DELETE CASCADE,
+    finding_id      UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    -- The license of the package that matched the policy
+    license         VARCHAR(255) NOT NULL,
+    detected_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (policy_id, finding_id)
+);
+
+CREATE INDEX idx_license_violations_finding ON license_violations(finding_id);
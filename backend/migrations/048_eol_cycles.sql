@@ -0,0 +1,19 @@
+-- End-of-life release cycles
+--
+-- Release cycles of the products tracked by endoflife.date, refreshed on a
+-- schedule. SCA packages and SBOM components in a cycle past its end of
+-- support get an informational finding, whether or not a CVE affects them.
+
+CREATE TABLE eol_cycles (
+    product         VARCHAR(100) NOT NULL,
+    cycle           VARCHAR(50) NOT NULL,
+    release_date    DATE,
+    -- End of support; NULL when the dataset gives no date
+    eol_date        DATE,
+    -- The dataset's yes/no answer, for cycles without a date
+    eol_reached     BOOLEAN NOT NULL DEFAULT false,
+    -- Latest release of the cycle
+    latest          VARCHAR(100),
+    fetched_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (product, cycle)
+);
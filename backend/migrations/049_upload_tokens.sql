@@ -0,0 +1,30 @@
+-- Upload tokens
+--
+-- Tokens for CI jobs uploading scanner output, sent in the X-API-Key header.
+-- Each is bound to one parser type, optionally to the source tool the file
+-- must report, and optionally to a set of applications: an upload resolving
+-- findings to any other application is rejected whole. Only a hash of the
+-- token is stored; the token is shown once, when created.
+
+CREATE TABLE upload_tokens (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name            VARCHAR(255) NOT NULL,
+    token_hash      CHAR(64) NOT NULL UNIQUE,
+    -- First characters of the token, to recognize it in listings
+    token_prefix    VARCHAR(16) NOT NULL,
+    parser_type     VARCHAR(50) NOT NULL,
+    -- NULL accepts any tool the parser reports
+    source_tool     VARCHAR(100),
+    -- Empty allows every application
+    application_ids UUID[] NOT NULL DEFAULT '{}',
+    -- Uploads run as this user, and stop working when the user is deactivated
+    created_by      UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at      TIMESTAMPTZ,
+    last_used_at    TIMESTAMPTZ,
+    revoked_at      TIMESTAMPTZ,
+    revoked_by      UUID REFERENCES users(id) ON DELETE SET NULL
+);
+
+ALTER TABLE ingestion_logs
+    ADD COLUMN upload_token_id UUID REFERENCES upload_tokens(id) ON DELETE SET NULL;
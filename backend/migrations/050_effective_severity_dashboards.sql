@@ -0,0 +1,19 @@
+-- Dashboard finding counts go by effective_severity so analyst severity
+-- overrides show up in the severity breakdowns.
+
+DROP MATERIALIZED VIEW mv_finding_counts;
+
+CREATE MATERIALIZED VIEW mv_finding_counts AS
+SELECT
+    effective_severity,
+    status,
+    application_id,
+    source_tool,
+    COUNT(*) AS finding_count
+FROM findings
+GROUP BY effective_severity, status, application_id, source_tool;
+
+-- Required for REFRESH MATERIALIZED VIEW CONCURRENTLY
+CREATE UNIQUE INDEX idx_mv_finding_counts_key
+    ON mv_finding_counts (effective_severity, status, application_id, source_tool);
+CREATE INDEX idx_mv_finding_counts_app ON mv_finding_counts (application_id);
@@ -31,6 +31,9 @@ async fn main() -> anyhow::Result<()> {
     seed_dast_findings(&pool).await?;
     seed_correlation_findings(&pool).await?;
 
+    synapsec::services::dashboard::refresh_views(&pool).await?;
+    println!("[done] Refreshed dashboard views");
+
     println!("\n=== Seed complete! ===");
     println!("Admin login: admin / {ADMIN_PASSWORD}");
 
@@ -161,6 +164,7 @@ async fn seed_sample_findings(pool: &PgPool) -> anyhow::Result<()> {
         &synapsec::services::ingestion::ParserType::Sonarqube,
         &synapsec::parsers::InputFormat::Json,
         admin_id.unwrap_or_default(),
+        None,
     )
     .await?;
 
@@ -210,6 +214,7 @@ async fn seed_sca_findings(pool: &PgPool) -> anyhow::Result<()> {
         &synapsec::services::ingestion::ParserType::JfrogXray,
         &synapsec::parsers::InputFormat::Json,
         admin_id.unwrap_or_default(),
+        None,
     )
     .await?;
 
@@ -259,6 +264,7 @@ async fn seed_dast_findings(pool: &PgPool) -> anyhow::Result<()> {
         &synapsec::services::ingestion::ParserType::TenableWas,
         &synapsec::parsers::InputFormat::Csv,
         admin_id.unwrap_or_default(),
+        None,
     )
     .await?;
 
@@ -313,6 +319,7 @@ async fn seed_correlation_findings(pool: &PgPool) -> anyhow::Result<()> {
         &synapsec::services::ingestion::ParserType::Sonarqube,
         &synapsec::parsers::InputFormat::Csv,
         admin_id,
+        None,
     )
     .await?;
     println!(
@@ -330,6 +337,7 @@ async fn seed_correlation_findings(pool: &PgPool) -> anyhow::Result<()> {
         &synapsec::services::ingestion::ParserType::JfrogXray,
         &synapsec::parsers::InputFormat::Json,
         admin_id,
+        None,
     )
     .await?;
     println!(
@@ -347,6 +355,7 @@ async fn seed_correlation_findings(pool: &PgPool) -> anyhow::Result<()> {
         &synapsec::services::ingestion::ParserType::TenableWas,
         &synapsec::parsers::InputFormat::Csv,
         admin_id,
+        None,
     )
     .await?;
     println!(
@@ -1,10 +1,20 @@
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+use std::str::FromStr;
 
-/// Application configuration loaded from environment variables.
+use crate::middleware::cors;
+
+/// Application configuration loaded from environment variables, optionally
+/// layered over a TOML file (see [`AppConfig::load`]).
 #[derive(Debug, Clone)]
 pub struct AppConfig {
     pub database_url: String,
     pub database_max_connections: u32,
+    /// Read replica for read-only queries; the primary serves them when unset.
+    pub database_replica_url: Option<String>,
     pub redis_url: String,
     pub host: String,
     pub port: u16,
@@ -12,38 +22,625 @@ pub struct AppConfig {
     pub jwt_access_token_expiry_secs: i64,
     pub jwt_refresh_token_expiry_secs: i64,
     pub frontend_url: String,
+    /// Origins allowed to call the API, exact or `https://*.example.com`
+    /// patterns; only `frontend_url` when empty.
+    pub cors_allowed_origins: Vec<String>,
+    /// Path prefixes of route groups any origin may call, without credentials.
+    pub cors_public_paths: Vec<String>,
     pub tls_cert_path: Option<String>,
     pub tls_key_path: Option<String>,
+    /// CA bundle for client certificates; when set, the ingestion routes
+    /// require a client certificate signed by it (mutual TLS).
+    pub tls_client_ca_path: Option<String>,
+    /// Base URL of the Splunk HTTP Event Collector; forwarding is off when unset.
+    pub splunk_hec_url: Option<String>,
+    pub splunk_hec_token: Option<String>,
+    pub splunk_hec_index: Option<String>,
+    /// Maximum number of events sent in one HEC request.
+    pub splunk_hec_batch_size: usize,
+    /// Seconds between flushes of a partially filled batch.
+    pub splunk_hec_flush_interval_secs: u64,
+    /// SMTP relay for emailed reports; email delivery fails when unset.
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    /// Sender address for emailed reports.
+    pub smtp_from: String,
+    /// Seconds between checks for due report schedules.
+    pub report_scheduler_interval_secs: u64,
+    /// Seconds between refreshes of today's finding trend snapshot.
+    pub trend_snapshot_interval_secs: u64,
+    /// Seconds dashboard statistics are served from cache.
+    pub dashboard_cache_ttl_secs: u64,
+    /// Seconds app code patterns and `system_config` settings are cached.
+    pub config_cache_ttl_secs: u64,
+    /// Seconds between refreshes of the dashboard materialized views.
+    pub dashboard_view_refresh_interval_secs: u64,
+    /// Seconds between checks for a due nightly dashboard snapshot.
+    pub dashboard_snapshot_interval_secs: u64,
+    /// Seconds between sweeps that mark overdue findings as SLA breached.
+    pub sla_monitor_interval_secs: u64,
+    /// Seconds between checks of the notification email queue.
+    pub notification_mailer_interval_secs: u64,
+    /// UTC hour from which the daily notification digest is sent.
+    pub notification_digest_hour_utc: u32,
+    /// Whether CVEs seen in findings are enriched from the NVD API.
+    pub nvd_enrichment_enabled: bool,
+    /// NVD CVE API 2.0 endpoint.
+    pub nvd_api_url: String,
+    /// NVD API key; raises the request rate limit when set.
+    pub nvd_api_key: Option<String>,
+    /// Seconds between NVD enrichment runs.
+    pub nvd_enrichment_interval_secs: u64,
+    /// Days after which cached CVE details are fetched again.
+    pub nvd_refresh_after_days: i32,
+    /// Whether SCA findings without a fixed version are enriched from OSV on a schedule.
+    pub osv_enrichment_enabled: bool,
+    /// OSV.dev API base URL.
+    pub osv_api_url: String,
+    /// Seconds between scheduled OSV enrichment runs.
+    pub osv_enrichment_interval_secs: u64,
+    /// Days after which an SCA finding still lacking a fix is looked up again.
+    pub osv_recheck_after_days: i32,
+    /// Whether GHSA ids in findings are resolved to CVE aliases via GitHub.
+    pub ghsa_resolution_enabled: bool,
+    /// GitHub REST API base URL.
+    pub github_api_url: String,
+    /// GitHub token; raises the advisory API rate limit when set.
+    pub github_token: Option<String>,
+    /// Seconds between GHSA resolution runs.
+    pub ghsa_resolution_interval_secs: u64,
+    /// Whether Exploit-DB and Metasploit indexes are downloaded to rate exploit maturity.
+    pub exploit_enrichment_enabled: bool,
+    /// Exploit-DB `files_exploits.csv` download URL.
+    pub exploitdb_index_url: String,
+    /// Metasploit `modules_metadata_base.json` download URL.
+    pub metasploit_index_url: String,
+    /// Seconds between exploit index downloads.
+    pub exploit_enrichment_interval_secs: u64,
+    /// Whether endoflife.date release cycles are fetched to flag end-of-life packages.
+    pub eol_detection_enabled: bool,
+    /// endoflife.date API base URL.
+    pub eol_api_url: String,
+    /// Seconds between end-of-life dataset refreshes.
+    pub eol_detection_interval_secs: u64,
+    /// Whether the Redis-backed API rate limiter is active.
+    pub rate_limit_enabled: bool,
+    /// Ingestion uploads allowed per caller per minute.
+    pub rate_limit_ingestion_per_minute: u32,
+    /// Finding list, search, and export requests allowed per caller per minute.
+    pub rate_limit_search_per_minute: u32,
+    /// Reverse proxies whose `X-Forwarded-For` is believed when keying
+    /// anonymous callers; otherwise the peer address is used.
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Return RFC 7807 problem details for all errors, not only to clients
+    /// sending `Accept: application/problem+json`.
+    pub problem_json_errors: bool,
+    /// Whether mutating API requests are recorded in the audit log.
+    pub request_audit_enabled: bool,
+    /// Background job workers per instance; 0 disables job processing.
+    pub job_workers: usize,
+    /// Seconds an idle job worker waits before polling the queue again.
+    pub job_poll_interval_secs: u64,
+    /// Seconds after which a running job is assumed abandoned and requeued.
+    pub job_lock_timeout_secs: u64,
+    /// Seconds to drain in-flight requests and running jobs after SIGTERM or
+    /// SIGINT before the remaining work is dropped.
+    pub shutdown_timeout_secs: u64,
+    /// Parser for the finding `search` filter: `plain` or `websearch`.
+    pub finding_search_syntax: String,
+    /// Convert `findings` and `finding_history` to monthly partitions at
+    /// startup (see `services::partitions`).
+    pub time_partitioning_enabled: bool,
+    /// Monthly partitions kept ready ahead of time on the partitioned tables;
+    /// at least 1 when partitioning is enabled.
+    pub partition_months_ahead: u32,
+    /// Finding attachment storage backend: `local` or `s3`.
+    pub attachment_storage: String,
+    /// Directory for `local` attachment storage.
+    pub attachment_local_dir: String,
+    /// Largest accepted attachment, in bytes.
+    pub attachment_max_bytes: usize,
+    pub attachment_s3_bucket: Option<String>,
+    pub attachment_s3_region: String,
+    /// S3-compatible endpoint (e.g. MinIO); defaults to AWS for the region.
+    pub attachment_s3_endpoint: Option<String>,
+    pub attachment_s3_access_key_id: Option<String>,
+    pub attachment_s3_secret_access_key: Option<String>,
+    /// Largest accepted SBOM upload, in bytes.
+    pub sbom_max_bytes: usize,
+    /// Deadline for reading the body and handling a request, in seconds.
+    pub request_timeout_secs: u64,
+    /// Largest request body outside the upload routes, in bytes.
+    pub request_max_body_bytes: usize,
+    /// Request deadline for upload, import and finding export routes, in seconds.
+    pub upload_timeout_secs: u64,
+    /// Largest scanner file accepted by ingestion, in bytes.
+    pub ingestion_max_body_bytes: usize,
+}
+
+/// One problem found while loading configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigIssue {
+    /// A required key is unset or empty.
+    Missing { key: String },
+    /// A key is set to a value that does not parse as the expected type.
+    Invalid {
+        key: String,
+        value: String,
+        expected: &'static str,
+    },
+    /// A key in the config file that no setting reads, usually a typo.
+    UnknownKey { key: String },
+    /// The config file could not be read or is not valid TOML.
+    File { path: String, message: String },
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { key } => write!(f, "{key} is required"),
+            Self::Invalid {
+                key,
+                value,
+                expected,
+            } => write!(f, "{key} = '{value}' is not a valid {expected}"),
+            Self::UnknownKey { key } => {
+                write!(f, "{key} in the config file is not a known setting")
+            }
+            Self::File { path, message } => write!(f, "cannot load config file {path}: {message}"),
+        }
+    }
+}
+
+/// Every issue found while loading configuration, reported together.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid configuration: {}", .issues.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+pub struct ConfigError {
+    pub issues: Vec<ConfigIssue>,
+}
+
+/// Settings source: environment variables over the keys of an optional TOML
+/// file. File keys are the lowercase variable names (`database_url`,
+/// `backend_port`); nested tables are not used.
+struct Source {
+    env: HashMap<String, String>,
+    file: toml::Table,
+    read: HashSet<String>,
+    issues: Vec<ConfigIssue>,
+}
+
+impl Source {
+    fn new(env: HashMap<String, String>, file: toml::Table) -> Self {
+        Self {
+            env,
+            file,
+            read: HashSet::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    fn from_file(env: HashMap<String, String>, path: &Path) -> Result<Self, ConfigError> {
+        let file_error = |message: String| ConfigError {
+            issues: vec![ConfigIssue::File {
+                path: path.display().to_string(),
+                message,
+            }],
+        };
+        let contents = std::fs::read_to_string(path).map_err(|e| file_error(e.to_string()))?;
+        let file = contents
+            .parse::<toml::Table>()
+            .map_err(|e| file_error(e.to_string()))?;
+        Ok(Self::new(env, file))
+    }
+
+    fn raw(&mut self, var: &str) -> Option<String> {
+        let key = var.to_ascii_lowercase();
+        self.read.insert(key.clone());
+        if let Some(value) = self.env.get(var) {
+            return Some(value.clone());
+        }
+        match self.file.get(&key)? {
+            toml::Value::String(s) => Some(s.clone()),
+            toml::Value::Integer(i) => Some(i.to_string()),
+            toml::Value::Float(f) => Some(f.to_string()),
+            toml::Value::Boolean(b) => Some(b.to_string()),
+            other => {
+                self.issues.push(ConfigIssue::Invalid {
+                    key,
+                    value: other.to_string(),
+                    expected: "string, number, or boolean",
+                });
+                None
+            }
+        }
+    }
+
+    fn required(&mut self, var: &str) -> String {
+        match self.raw(var) {
+            Some(value) if !value.is_empty() => value,
+            _ => {
+                self.issues.push(ConfigIssue::Missing {
+                    key: var.to_string(),
+                });
+                String::new()
+            }
+        }
+    }
+
+    fn optional(&mut self, var: &str) -> Option<String> {
+        self.raw(var).filter(|v| !v.is_empty())
+    }
+
+    /// A comma-separated list; empty when unset.
+    fn list(&mut self, var: &str) -> Vec<String> {
+        self.raw(var)
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// A comma-separated list of IP addresses; empty when unset.
+    fn ip_list(&mut self, var: &str) -> Vec<IpAddr> {
+        let mut addrs = Vec::new();
+        for entry in self.list(var) {
+            match entry.parse() {
+                Ok(addr) => addrs.push(addr),
+                Err(_) => self.issues.push(ConfigIssue::Invalid {
+                    key: var.to_string(),
+                    value: entry,
+                    expected: "comma-separated IP addresses",
+                }),
+            }
+        }
+        addrs
+    }
+
+    fn string_or(&mut self, var: &str, default: &str) -> String {
+        self.raw(var).unwrap_or_else(|| default.to_string())
+    }
+
+    fn parse_or<T: FromStr>(&mut self, var: &str, default: T) -> T {
+        let Some(value) = self.raw(var) else {
+            return default;
+        };
+        match value.trim().parse() {
+            Ok(parsed) => parsed,
+            Err(_) => {
+                self.issues.push(ConfigIssue::Invalid {
+                    key: var.to_string(),
+                    value,
+                    expected: std::any::type_name::<T>(),
+                });
+                default
+            }
+        }
+    }
+
+    /// Fail with every issue found, including file keys nothing read.
+    fn finish(mut self) -> Result<(), ConfigError> {
+        let mut unknown: Vec<&String> = self
+            .file
+            .keys()
+            .filter(|k| !self.read.contains(k.as_str()))
+            .collect();
+        unknown.sort();
+        self.issues.extend(
+            unknown
+                .into_iter()
+                .map(|key| ConfigIssue::UnknownKey { key: key.clone() }),
+        );
+        if self.issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError {
+                issues: self.issues,
+            })
+        }
+    }
 }
 
 impl AppConfig {
-    pub fn from_env() -> Result<Self, env::VarError> {
-        Ok(Self {
-            database_url: env::var("DATABASE_URL")?,
-            database_max_connections: env::var("DATABASE_MAX_CONNECTIONS")
-                .unwrap_or_else(|_| "10".to_string())
-                .parse()
-                .unwrap_or(10),
-            redis_url: env::var("REDIS_URL")
-                .unwrap_or_else(|_| "redis://localhost:6379".to_string()),
-            host: env::var("BACKEND_HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
-            port: env::var("BACKEND_PORT")
-                .unwrap_or_else(|_| "3000".to_string())
-                .parse()
-                .unwrap_or(3000),
-            jwt_secret: env::var("JWT_SECRET")?,
-            jwt_access_token_expiry_secs: env::var("JWT_ACCESS_TOKEN_EXPIRY_SECS")
-                .unwrap_or_else(|_| "900".to_string())
-                .parse()
-                .unwrap_or(900),
-            jwt_refresh_token_expiry_secs: env::var("JWT_REFRESH_TOKEN_EXPIRY_SECS")
-                .unwrap_or_else(|_| "604800".to_string())
-                .parse()
-                .unwrap_or(604800),
-            frontend_url: env::var("FRONTEND_URL")
-                .unwrap_or_else(|_| "https://localhost:5173".to_string()),
-            tls_cert_path: env::var("TLS_CERT_PATH").ok(),
-            tls_key_path: env::var("TLS_KEY_PATH").ok(),
-        })
+    /// Load configuration from environment variables only.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_source(Source::new(env::vars().collect(), toml::Table::new()))
+    }
+
+    /// Load configuration from the TOML file named by `CONFIG_FILE`, if set,
+    /// with environment variables taking precedence over its keys.
+    pub fn load() -> Result<Self, ConfigError> {
+        match env::var("CONFIG_FILE") {
+            Ok(path) if !path.is_empty() => {
+                Self::from_source(Source::from_file(env::vars().collect(), Path::new(&path))?)
+            }
+            _ => Self::from_env(),
+        }
+    }
+
+    fn from_source(mut src: Source) -> Result<Self, ConfigError> {
+        let config = Self {
+            database_url: src.required("DATABASE_URL"),
+            database_max_connections: src.parse_or("DATABASE_MAX_CONNECTIONS", 10),
+            database_replica_url: src.optional("DATABASE_REPLICA_URL"),
+            redis_url: src.string_or("REDIS_URL", "redis://localhost:6379"),
+            host: src.string_or("BACKEND_HOST", "0.0.0.0"),
+            port: src.parse_or("BACKEND_PORT", 3000),
+            jwt_secret: src.required("JWT_SECRET"),
+            jwt_access_token_expiry_secs: src.parse_or("JWT_ACCESS_TOKEN_EXPIRY_SECS", 900),
+            jwt_refresh_token_expiry_secs: src.parse_or("JWT_REFRESH_TOKEN_EXPIRY_SECS", 604800),
+            frontend_url: src.string_or("FRONTEND_URL", "https://localhost:5173"),
+            cors_allowed_origins: src.list("CORS_ALLOWED_ORIGINS"),
+            cors_public_paths: src.list("CORS_PUBLIC_PATHS"),
+            tls_cert_path: src.optional("TLS_CERT_PATH"),
+            tls_key_path: src.optional("TLS_KEY_PATH"),
+            tls_client_ca_path: src.optional("TLS_CLIENT_CA_PATH"),
+            splunk_hec_url: src.optional("SPLUNK_HEC_URL"),
+            splunk_hec_token: src.optional("SPLUNK_HEC_TOKEN"),
+            splunk_hec_index: src.optional("SPLUNK_HEC_INDEX"),
+            splunk_hec_batch_size: src.parse_or("SPLUNK_HEC_BATCH_SIZE", 100),
+            splunk_hec_flush_interval_secs: src.parse_or("SPLUNK_HEC_FLUSH_INTERVAL_SECS", 5),
+            smtp_host: src.optional("SMTP_HOST"),
+            smtp_port: src.parse_or("SMTP_PORT", 587),
+            smtp_username: src.optional("SMTP_USERNAME"),
+            smtp_password: src.optional("SMTP_PASSWORD"),
+            smtp_from: src.string_or("SMTP_FROM", "SynApSec <noreply@localhost>"),
+            report_scheduler_interval_secs: src.parse_or("REPORT_SCHEDULER_INTERVAL_SECS", 60),
+            trend_snapshot_interval_secs: src.parse_or("TREND_SNAPSHOT_INTERVAL_SECS", 3600),
+            dashboard_cache_ttl_secs: src.parse_or("DASHBOARD_CACHE_TTL_SECS", 30),
+            config_cache_ttl_secs: src.parse_or("CONFIG_CACHE_TTL_SECS", 60),
+            dashboard_view_refresh_interval_secs: src.parse_or("DASHBOARD_VIEW_REFRESH_INTERVAL_SECS", 300),
+            dashboard_snapshot_interval_secs: src.parse_or("DASHBOARD_SNAPSHOT_INTERVAL_SECS", 3600),
+            sla_monitor_interval_secs: src.parse_or("SLA_MONITOR_INTERVAL_SECS", 900),
+            notification_mailer_interval_secs: src.parse_or("NOTIFICATION_MAILER_INTERVAL_SECS", 60),
+            notification_digest_hour_utc: src.parse_or("NOTIFICATION_DIGEST_HOUR_UTC", 7),
+            nvd_enrichment_enabled: src.parse_or("NVD_ENRICHMENT_ENABLED", false),
+            nvd_api_url: src.string_or("NVD_API_URL", "https://services.nvd.nist.gov/rest/json/cves/2.0"),
+            nvd_api_key: src.optional("NVD_API_KEY"),
+            nvd_enrichment_interval_secs: src.parse_or("NVD_ENRICHMENT_INTERVAL_SECS", 3600),
+            nvd_refresh_after_days: src.parse_or("NVD_REFRESH_AFTER_DAYS", 7),
+            osv_enrichment_enabled: src.parse_or("OSV_ENRICHMENT_ENABLED", false),
+            osv_api_url: src.string_or("OSV_API_URL", "https://api.osv.dev"),
+            osv_enrichment_interval_secs: src.parse_or("OSV_ENRICHMENT_INTERVAL_SECS", 3600),
+            osv_recheck_after_days: src.parse_or("OSV_RECHECK_AFTER_DAYS", 7),
+            ghsa_resolution_enabled: src.parse_or("GHSA_RESOLUTION_ENABLED", false),
+            github_api_url: src.string_or("GITHUB_API_URL", "https://api.github.com"),
+            github_token: src.optional("GITHUB_TOKEN"),
+            ghsa_resolution_interval_secs: src.parse_or("GHSA_RESOLUTION_INTERVAL_SECS", 3600),
+            exploit_enrichment_enabled: src.parse_or("EXPLOIT_ENRICHMENT_ENABLED", false),
+            exploitdb_index_url: src.string_or("EXPLOITDB_INDEX_URL", "https://gitlab.com/exploit-database/exploitdb/-/raw/main/files_exploits.csv"),
+            metasploit_index_url: src.string_or("METASPLOIT_INDEX_URL", "https://raw.githubusercontent.com/rapid7/metasploit-framework/master/db/modules_metadata_base.json"),
+            exploit_enrichment_interval_secs: src.parse_or("EXPLOIT_ENRICHMENT_INTERVAL_SECS", 86400),
+            eol_detection_enabled: src.parse_or("EOL_DETECTION_ENABLED", false),
+            eol_api_url: src.string_or("EOL_API_URL", "https://endoflife.date/api"),
+            eol_detection_interval_secs: src.parse_or("EOL_DETECTION_INTERVAL_SECS", 86400),
+            rate_limit_enabled: src.parse_or("RATE_LIMIT_ENABLED", true),
+            rate_limit_ingestion_per_minute: src.parse_or("RATE_LIMIT_INGESTION_PER_MINUTE", 30),
+            rate_limit_search_per_minute: src.parse_or("RATE_LIMIT_SEARCH_PER_MINUTE", 300),
+            trusted_proxies: src.ip_list("TRUSTED_PROXIES"),
+            problem_json_errors: src.parse_or("PROBLEM_JSON_ERRORS", false),
+            request_audit_enabled: src.parse_or("REQUEST_AUDIT_ENABLED", true),
+            job_workers: src.parse_or("JOB_WORKERS", 2),
+            job_poll_interval_secs: src.parse_or("JOB_POLL_INTERVAL_SECS", 5),
+            job_lock_timeout_secs: src.parse_or("JOB_LOCK_TIMEOUT_SECS", 900),
+            shutdown_timeout_secs: src.parse_or("SHUTDOWN_TIMEOUT_SECS", 25),
+            finding_search_syntax: src.string_or("FINDING_SEARCH_SYNTAX", "plain"),
+            time_partitioning_enabled: src.parse_or("TIME_PARTITIONING_ENABLED", false),
+            partition_months_ahead: src.parse_or("PARTITION_MONTHS_AHEAD", 3),
+            attachment_storage: src.string_or("ATTACHMENT_STORAGE", "local"),
+            attachment_local_dir: src.string_or("ATTACHMENT_LOCAL_DIR", "./data/attachments"),
+            attachment_max_bytes: src.parse_or("ATTACHMENT_MAX_BYTES", 10_485_760),
+            attachment_s3_bucket: src.optional("ATTACHMENT_S3_BUCKET"),
+            attachment_s3_region: src.string_or("ATTACHMENT_S3_REGION", "us-east-1"),
+            attachment_s3_endpoint: src.optional("ATTACHMENT_S3_ENDPOINT"),
+            attachment_s3_access_key_id: src.optional("ATTACHMENT_S3_ACCESS_KEY_ID"),
+            attachment_s3_secret_access_key: src.optional("ATTACHMENT_S3_SECRET_ACCESS_KEY"),
+            sbom_max_bytes: src.parse_or("SBOM_MAX_BYTES", 52_428_800),
+            request_timeout_secs: src.parse_or("REQUEST_TIMEOUT_SECS", 30),
+            request_max_body_bytes: src.parse_or("REQUEST_MAX_BODY_BYTES", 2_097_152),
+            upload_timeout_secs: src.parse_or("UPLOAD_TIMEOUT_SECS", 600),
+            ingestion_max_body_bytes: src.parse_or("INGESTION_MAX_BODY_BYTES", 104_857_600),
+        };
+        // CORS entries are checked here so a bad origin is reported with the
+        // other issues instead of failing once the server is half started
+        let (origin_key, origins) = if config.cors_allowed_origins.is_empty() {
+            ("FRONTEND_URL", std::slice::from_ref(&config.frontend_url))
+        } else {
+            ("CORS_ALLOWED_ORIGINS", &config.cors_allowed_origins[..])
+        };
+        for origin in origins {
+            if !cors::is_valid_origin(origin) {
+                src.issues.push(ConfigIssue::Invalid {
+                    key: origin_key.to_string(),
+                    value: origin.clone(),
+                    expected: "origin such as https://app.example.com or https://*.example.com",
+                });
+            }
+        }
+        for path in &config.cors_public_paths {
+            if !cors::is_valid_public_path(path) {
+                src.issues.push(ConfigIssue::Invalid {
+                    key: "CORS_PUBLIC_PATHS".to_string(),
+                    value: path.clone(),
+                    expected: "absolute path prefix such as /api/v1/webhooks",
+                });
+            }
+        }
+        // New rows past the last partition would be rejected by the default
+        // partition's bound, so the current month's successor must always exist
+        if config.time_partitioning_enabled && config.partition_months_ahead == 0 {
+            src.issues.push(ConfigIssue::Invalid {
+                key: "PARTITION_MONTHS_AHEAD".to_string(),
+                value: "0".to_string(),
+                expected: "at least 1 when TIME_PARTITIONING_ENABLED is set",
+            });
+        }
+        src.finish()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn environment_overrides_the_config_file() {
+        let file: toml::Table = r#"
+            database_url = "postgres://file/synapsec"
+            jwt_secret = "from-file"
+            backend_port = 8080
+            job_workers = 4
+        "#
+        .parse()
+        .unwrap();
+        let config =
+            AppConfig::from_source(Source::new(env(&[("JOB_WORKERS", "1")]), file)).unwrap();
+        assert_eq!(config.database_url, "postgres://file/synapsec");
+        assert_eq!(config.port, 8080);
+        assert_eq!(config.job_workers, 1);
+        assert_eq!(config.shutdown_timeout_secs, 25);
+    }
+
+    #[test]
+    fn all_issues_are_reported_together() {
+        let file: toml::Table = "job_wrokers = 4".parse().unwrap();
+        let err = AppConfig::from_source(Source::new(
+            env(&[("BACKEND_PORT", "http"), ("RATE_LIMIT_ENABLED", "yes")]),
+            file,
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err.issues,
+            [
+                ConfigIssue::Missing {
+                    key: "DATABASE_URL".to_string()
+                },
+                ConfigIssue::Invalid {
+                    key: "BACKEND_PORT".to_string(),
+                    value: "http".to_string(),
+                    expected: "u16",
+                },
+                ConfigIssue::Missing {
+                    key: "JWT_SECRET".to_string()
+                },
+                ConfigIssue::Invalid {
+                    key: "RATE_LIMIT_ENABLED".to_string(),
+                    value: "yes".to_string(),
+                    expected: "bool",
+                },
+                ConfigIssue::UnknownKey {
+                    key: "job_wrokers".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn trusted_proxies_are_parsed_as_addresses() {
+        let config = AppConfig::from_source(Source::new(
+            env(&[
+                ("DATABASE_URL", "postgres://env/synapsec"),
+                ("JWT_SECRET", "secret"),
+                ("TRUSTED_PROXIES", "10.0.0.1, ::1"),
+            ]),
+            toml::Table::new(),
+        ))
+        .unwrap();
+        assert_eq!(
+            config.trusted_proxies,
+            ["10.0.0.1".parse::<IpAddr>().unwrap(), "::1".parse().unwrap()]
+        );
+
+        let err = AppConfig::from_source(Source::new(
+            env(&[
+                ("DATABASE_URL", "postgres://env/synapsec"),
+                ("JWT_SECRET", "secret"),
+                ("TRUSTED_PROXIES", "10.0.0.0/8"),
+            ]),
+            toml::Table::new(),
+        ))
+        .unwrap_err();
+        assert!(matches!(
+            &err.issues[..],
+            [ConfigIssue::Invalid { key, .. }] if key == "TRUSTED_PROXIES"
+        ));
+    }
+
+    #[test]
+    fn partitioning_needs_months_ahead() {
+        let err = AppConfig::from_source(Source::new(
+            env(&[
+                ("DATABASE_URL", "postgres://env/synapsec"),
+                ("JWT_SECRET", "secret"),
+                ("TIME_PARTITIONING_ENABLED", "true"),
+                ("PARTITION_MONTHS_AHEAD", "0"),
+            ]),
+            toml::Table::new(),
+        ))
+        .unwrap_err();
+        assert!(matches!(
+            &err.issues[..],
+            [ConfigIssue::Invalid { key, .. }] if key == "PARTITION_MONTHS_AHEAD"
+        ));
+
+        let config = AppConfig::from_source(Source::new(
+            env(&[
+                ("DATABASE_URL", "postgres://env/synapsec"),
+                ("JWT_SECRET", "secret"),
+                ("PARTITION_MONTHS_AHEAD", "0"),
+            ]),
+            toml::Table::new(),
+        ))
+        .unwrap();
+        assert!(!config.time_partitioning_enabled);
+    }
+
+    #[test]
+    fn cors_entries_are_validated() {
+        let err = AppConfig::from_source(Source::new(
+            env(&[
+                ("DATABASE_URL", "postgres://env/synapsec"),
+                ("JWT_SECRET", "secret"),
+                ("CORS_ALLOWED_ORIGINS", "https://a.example,a.example"),
+                ("CORS_PUBLIC_PATHS", "webhooks"),
+            ]),
+            toml::Table::new(),
+        ))
+        .unwrap_err();
+        assert!(matches!(
+            &err.issues[..],
+            [
+                ConfigIssue::Invalid { key: origins, value, .. },
+                ConfigIssue::Invalid { key: paths, .. },
+            ] if origins == "CORS_ALLOWED_ORIGINS"
+                && value == "a.example"
+                && paths == "CORS_PUBLIC_PATHS"
+        ));
+
+        let err = AppConfig::from_source(Source::new(
+            env(&[
+                ("DATABASE_URL", "postgres://env/synapsec"),
+                ("JWT_SECRET", "secret"),
+                ("FRONTEND_URL", "localhost:5173"),
+            ]),
+            toml::Table::new(),
+        ))
+        .unwrap_err();
+        assert!(matches!(
+            &err.issues[..],
+            [ConfigIssue::Invalid { key, .. }] if key == "FRONTEND_URL"
+        ));
     }
 }
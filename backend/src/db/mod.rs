@@ -1,6 +1,6 @@
-//! Database connection pool and migration utilities.
+//! Database connection pools and migration utilities.
 
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use sqlx::PgPool;
 
 /// Create a PostgreSQL connection pool.
@@ -10,3 +10,25 @@ pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgP
         .connect(database_url)
         .await
 }
+
+/// Create the pool for read-only queries.
+///
+/// With a replica URL, connections go to the replica and every transaction
+/// is read-only, so a write routed there by mistake fails instead of
+/// diverging. Without one, the primary pool is shared.
+pub async fn create_read_pool(
+    replica_url: Option<&str>,
+    primary: &PgPool,
+    max_connections: u32,
+) -> Result<PgPool, sqlx::Error> {
+    let Some(url) = replica_url else {
+        return Ok(primary.clone());
+    };
+    let options = url
+        .parse::<PgConnectOptions>()?
+        .options([("default_transaction_read_only", "on")]);
+    PgPoolOptions::new()
+        .max_connections(max_connections)
+        .connect_with(options)
+        .await
+}
@@ -1,21 +1,39 @@
 //! Unified error handling with consistent API response envelope.
 
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde::Serialize;
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use crate::middleware::request_id;
 
 /// Error detail in the API response envelope.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiError {
     pub code: String,
     pub message: String,
+    /// Offending request fields; only present for field validation errors.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+    /// ID of the failed request, as in the `X-Request-Id` response header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// A request field that failed validation.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldError {
+    /// Field path as sent by the client, e.g. `title` or `widgets[0].title`.
+    pub field: String,
+    pub message: String,
 }
 
 /// Consistent JSON envelope for all API responses.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiResponse<T: Serialize> {
     pub data: Option<T>,
     pub error: Option<ApiError>,
@@ -30,6 +48,34 @@ impl<T: Serialize> ApiResponse<T> {
         })
     }
 
+    /// Wrap a successful result with a strong `ETag` computed from the body,
+    /// answering `304 Not Modified` when `If-None-Match` already carries it.
+    pub fn success_with_etag(data: T, headers: &HeaderMap) -> Result<Response, AppError> {
+        let body = serde_json::to_vec(&Self {
+            data: Some(data),
+            error: None,
+        })
+        .map_err(|e| AppError::Internal(format!("Failed to serialize response: {e}")))?;
+        let etag = etag_for(&body);
+
+        let mut response = if if_none_match(headers, &etag) {
+            StatusCode::NOT_MODIFIED.into_response()
+        } else {
+            ([(header::CONTENT_TYPE, "application/json")], body).into_response()
+        };
+        let response_headers = response.headers_mut();
+        response_headers.insert(
+            header::ETAG,
+            HeaderValue::from_str(&etag).map_err(|e| AppError::Internal(e.to_string()))?,
+        );
+        // Clients may store the body but must revalidate before reusing it.
+        response_headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("private, no-cache"),
+        );
+        Ok(response)
+    }
+
     /// Wrap an error in the envelope.
     pub fn error(code: &str, message: &str) -> Json<Self> {
         Json(Self {
@@ -37,11 +83,30 @@ impl<T: Serialize> ApiResponse<T> {
             error: Some(ApiError {
                 code: code.to_string(),
                 message: message.to_string(),
+                fields: Vec::new(),
+                request_id: request_id::current(),
             }),
         })
     }
 }
 
+/// Strong entity tag: the quoted, truncated SHA-256 of a response body.
+fn etag_for(body: &[u8]) -> String {
+    let digest = hex::encode(Sha256::digest(body));
+    format!("\"{}\"", &digest[..32])
+}
+
+/// Whether `If-None-Match` lists `etag` or `*`, using weak comparison.
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
 /// Application error type mapping to HTTP status codes.
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
@@ -51,6 +116,9 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Validation failed for {} field(s)", .0.len())]
+    InvalidFields(Vec<FieldError>),
+
     #[error("Unauthorized")]
     Unauthorized,
 
@@ -63,6 +131,12 @@ pub enum AppError {
     #[error("Invalid state transition: {0}")]
     InvalidTransition(String),
 
+    #[error("Rate limit exceeded; retry after {retry_after_secs}s")]
+    RateLimited { retry_after_secs: u64 },
+
+    #[error("Request timed out after {0}s")]
+    Timeout(u64),
+
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 
@@ -80,6 +154,17 @@ impl AppError {
     pub fn is_unauthorized(&self) -> bool {
         matches!(self, Self::Unauthorized)
     }
+
+    /// Map a unique violation on a `what` named `name` to a readable
+    /// conflict; other database errors pass through.
+    pub fn name_conflict(e: sqlx::Error, what: &str, name: &str) -> Self {
+        match e {
+            sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+                Self::Conflict(format!("A {what} named '{name}' already exists"))
+            }
+            _ => Self::Database(e),
+        }
+    }
 }
 
 impl IntoResponse for AppError {
@@ -89,6 +174,11 @@ impl IntoResponse for AppError {
             AppError::Validation(msg) => {
                 (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone())
             }
+            AppError::InvalidFields(_) => (
+                StatusCode::BAD_REQUEST,
+                "VALIDATION_ERROR",
+                "One or more fields are invalid".to_string(),
+            ),
             AppError::Unauthorized => (
                 StatusCode::UNAUTHORIZED,
                 "UNAUTHORIZED",
@@ -99,6 +189,16 @@ impl IntoResponse for AppError {
             AppError::InvalidTransition(msg) => {
                 (StatusCode::BAD_REQUEST, "INVALID_TRANSITION", msg.clone())
             }
+            AppError::RateLimited { retry_after_secs } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "RATE_LIMITED",
+                format!("Too many requests; retry after {retry_after_secs} seconds"),
+            ),
+            AppError::Timeout(secs) => (
+                StatusCode::REQUEST_TIMEOUT,
+                "REQUEST_TIMEOUT",
+                format!("The request was not completed within {secs} seconds"),
+            ),
             AppError::Database(e) => {
                 tracing::error!(error = %e, "Database error");
                 (
@@ -122,10 +222,89 @@ impl IntoResponse for AppError {
             error: Some(ApiError {
                 code: code.to_string(),
                 message,
+                fields: match self {
+                    AppError::InvalidFields(ref fields) => fields.clone(),
+                    _ => Vec::new(),
+                },
+                request_id: request_id::current(),
             }),
         };
 
-        (status, Json(body)).into_response()
+        let mut response = (status, Json(body)).into_response();
+        if let AppError::RateLimited { retry_after_secs } = self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+        }
+        response
+    }
+}
+
+impl From<validator::ValidationErrors> for AppError {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let mut fields = Vec::new();
+        collect_field_errors(&errors, "", &mut fields);
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        AppError::InvalidFields(fields)
+    }
+}
+
+/// Flatten nested validator errors into `FieldError`s with dotted paths.
+fn collect_field_errors(
+    errors: &validator::ValidationErrors,
+    prefix: &str,
+    out: &mut Vec<FieldError>,
+) {
+    use validator::ValidationErrorsKind;
+
+    for (field, kind) in errors.errors() {
+        let path = match (prefix.is_empty(), field.as_ref()) {
+            (true, "__all__") => String::new(),
+            (false, "__all__") => prefix.to_string(),
+            (true, name) => name.to_string(),
+            (false, name) => format!("{prefix}.{name}"),
+        };
+        match kind {
+            ValidationErrorsKind::Field(list) => {
+                out.extend(list.iter().map(|e| FieldError {
+                    field: path.clone(),
+                    message: describe_validation_error(e),
+                }));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(nested, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(nested, &format!("{path}[{index}]"), out);
+                }
+            }
+        }
+    }
+}
+
+/// Human-readable message for a validator error, preferring an explicit one.
+fn describe_validation_error(error: &validator::ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+    let param = |name: &str| error.params.get(name).map(|v| v.to_string());
+    match error.code.as_ref() {
+        "length" => match (param("min"), param("max"), param("equal")) {
+            (_, _, Some(equal)) => format!("must have a length of {equal}"),
+            (Some(min), Some(max), _) => format!("must have a length between {min} and {max}"),
+            (Some(min), None, _) => format!("must have a length of at least {min}"),
+            (None, Some(max), _) => format!("must have a length of at most {max}"),
+            (None, None, _) => "has an invalid length".to_string(),
+        },
+        "range" => match (param("min"), param("max")) {
+            (Some(min), Some(max)) => format!("must be between {min} and {max}"),
+            (Some(min), None) => format!("must be at least {min}"),
+            (None, Some(max)) => format!("must be at most {max}"),
+            (None, None) => "is out of range".to_string(),
+        },
+        "email" => "must be a valid email address".to_string(),
+        "url" => "must be a valid URL".to_string(),
+        "required" => "is required".to_string(),
+        code => format!("is invalid ({code})"),
     }
 }
 
@@ -150,6 +329,45 @@ mod tests {
         assert_eq!(json["error"]["message"], "Item not found");
     }
 
+    #[test]
+    fn etag_is_stable_and_content_based() {
+        let etag = etag_for(b"{\"data\":1}");
+        assert_eq!(etag, etag_for(b"{\"data\":1}"));
+        assert_ne!(etag, etag_for(b"{\"data\":2}"));
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag.len(), 34);
+    }
+
+    #[test]
+    fn if_none_match_accepts_lists_weak_tags_and_wildcard() {
+        let etag = "\"abc\"";
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"xyz\", W/\"abc\""));
+        assert!(if_none_match(&headers, etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"xyz\""));
+        assert!(!if_none_match(&headers, etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(if_none_match(&headers, etag));
+    }
+
+    #[test]
+    fn conditional_response_returns_not_modified() {
+        let headers = HeaderMap::new();
+        let first = ApiResponse::success_with_etag("hello", &headers).unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers().get(header::ETAG).unwrap().clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag);
+        let second = ApiResponse::success_with_etag("hello", &headers).unwrap();
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert!(second.headers().contains_key(header::ETAG));
+    }
+
     #[test]
     fn app_error_is_not_found() {
         let err = AppError::NotFound("user".to_string());
@@ -163,6 +381,74 @@ mod tests {
         assert_eq!(err.to_string(), "Validation error: email is required");
     }
 
+    #[test]
+    fn rate_limited_sets_retry_after() {
+        let response = AppError::RateLimited {
+            retry_after_secs: 7,
+        }
+        .into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "7");
+    }
+
+    #[derive(validator::Validate)]
+    struct Sample {
+        #[validate(length(min = 1, max = 5))]
+        name: String,
+        #[validate(email)]
+        email: String,
+        #[validate(range(min = 0.0, max = 10.0, message = "must be a CVSS score"))]
+        score: f32,
+    }
+
+    #[test]
+    fn validator_errors_become_sorted_field_errors() {
+        use validator::Validate;
+
+        let sample = Sample {
+            name: String::new(),
+            email: "not-an-email".to_string(),
+            score: 11.0,
+        };
+        let err = AppError::from(sample.validate().unwrap_err());
+        let AppError::InvalidFields(fields) = &err else {
+            panic!("expected field errors, got {err:?}");
+        };
+        assert_eq!(
+            fields,
+            &vec![
+                FieldError {
+                    field: "email".to_string(),
+                    message: "must be a valid email address".to_string(),
+                },
+                FieldError {
+                    field: "name".to_string(),
+                    message: "must have a length between 1 and 5".to_string(),
+                },
+                FieldError {
+                    field: "score".to_string(),
+                    message: "must be a CVSS score".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn field_errors_are_listed_in_the_envelope() {
+        let response = AppError::InvalidFields(vec![FieldError {
+            field: "title".to_string(),
+            message: "must have a length between 1 and 1000".to_string(),
+        }])
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"]["code"], "VALIDATION_ERROR");
+        assert_eq!(json["error"]["fields"][0]["field"], "title");
+    }
+
     #[test]
     fn app_error_from_sqlx() {
         let sqlx_err = sqlx::Error::RowNotFound;
@@ -0,0 +1,256 @@
+//! Read-only GraphQL API over findings, applications, relationships, and
+//! history, served at `/api/v1/graphql`.
+//!
+//! Resolvers call the same services as the REST routes, so filtering,
+//! archiving, validation, and ownership rules are shared. The request's
+//! database pool and authenticated user are attached per request; the schema
+//! itself is built once at startup.
+
+mod types;
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Schema};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::middleware::auth::CurrentUser;
+use crate::models::ownership::OwnershipRole;
+use crate::models::pagination::Pagination;
+use crate::services::application::{
+    self as application_service, ApplicationFilters, ApplicationSort,
+};
+use crate::services::finding::{self as finding_service, FindingFilters, FindingWithDetails};
+use crate::services::ownership;
+
+pub use types::{ApplicationNode, ApplicationPage, FindingNode, FindingPage};
+
+/// Executable schema type shared by the route handlers.
+pub type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Maximum selection depth; deep enough for finding → relationship → finding → application.
+const MAX_DEPTH: usize = 10;
+
+/// Maximum query complexity (one point per field).
+const MAX_COMPLEXITY: usize = 2000;
+
+/// Build the schema with depth and complexity limits.
+pub fn build_schema() -> ApiSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// Convert a service error into a GraphQL error carrying the REST error code.
+/// Database and internal failures are logged and reported generically.
+pub(crate) fn gql_error(err: AppError) -> async_graphql::Error {
+    let (code, message) = match &err {
+        AppError::NotFound(msg) => ("NOT_FOUND", msg.clone()),
+        AppError::Validation(msg) => ("VALIDATION_ERROR", msg.clone()),
+        AppError::InvalidFields(fields) => (
+            "VALIDATION_ERROR",
+            fields
+                .iter()
+                .map(|f| format!("{}: {}", f.field, f.message))
+                .collect::<Vec<_>>()
+                .join("; "),
+        ),
+        AppError::Unauthorized => ("UNAUTHORIZED", "Authentication required".to_string()),
+        AppError::Forbidden(msg) => ("FORBIDDEN", msg.clone()),
+        AppError::Conflict(msg) => ("CONFLICT", msg.clone()),
+        AppError::InvalidTransition(msg) => ("INVALID_TRANSITION", msg.clone()),
+        AppError::RateLimited { .. } => ("RATE_LIMITED", err.to_string()),
+        AppError::Timeout(_) => ("REQUEST_TIMEOUT", err.to_string()),
+        AppError::Database(_) | AppError::Internal(_) => {
+            tracing::error!(error = %err, "GraphQL resolver error");
+            ("INTERNAL_ERROR", "An internal error occurred".to_string())
+        }
+    };
+    async_graphql::Error::new(message).extend_with(|_, ext| ext.set("code", code))
+}
+
+/// Serialized name of an enum value, matching the REST representation.
+pub(crate) fn label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+pub(crate) fn pool<'a>(ctx: &Context<'a>) -> &'a PgPool {
+    ctx.data_unchecked::<PgPool>()
+}
+
+pub(crate) fn current_user<'a>(ctx: &Context<'a>) -> &'a CurrentUser {
+    ctx.data_unchecked::<CurrentUser>()
+}
+
+/// Load a finding the requesting user may view.
+pub(crate) async fn viewable_finding(
+    ctx: &Context<'_>,
+    id: Uuid,
+) -> Result<FindingWithDetails, AppError> {
+    ownership::authorize_finding(pool(ctx), current_user(ctx), id, OwnershipRole::Viewer).await?;
+    finding_service::find_by_id(pool(ctx), id).await
+}
+
+/// Build finding filters from GraphQL arguments, validating enum names the
+/// same way the REST query string does.
+pub(crate) fn finding_filters(
+    application_id: Option<Uuid>,
+    severity: Option<String>,
+    status: Option<String>,
+    search: Option<String>,
+) -> async_graphql::Result<FindingFilters> {
+    serde_json::from_value(serde_json::json!({
+        "application_id": application_id,
+        "severity": severity,
+        "status": status,
+        "search": search,
+    }))
+    .map_err(|e| gql_error(AppError::Validation(format!("Invalid finding filter: {e}"))))
+}
+
+pub(crate) fn pagination(page: Option<i64>, per_page: Option<i64>) -> Pagination {
+    Pagination { page, per_page }
+}
+
+/// Root query type.
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// A finding by ID, including archived findings.
+    async fn finding(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> async_graphql::Result<Option<FindingNode>> {
+        match viewable_finding(ctx, id).await {
+            Ok(finding) => Ok(Some(FindingNode(finding))),
+            Err(AppError::NotFound(_)) => Ok(None),
+            Err(e) => Err(gql_error(e)),
+        }
+    }
+
+    /// Non-archived findings, highest risk score first.
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "each filter is a separate GraphQL argument"
+    )]
+    async fn findings(
+        &self,
+        ctx: &Context<'_>,
+        application_id: Option<Uuid>,
+        severity: Option<String>,
+        status: Option<String>,
+        search: Option<String>,
+        page: Option<i64>,
+        per_page: Option<i64>,
+    ) -> async_graphql::Result<FindingPage> {
+        let mut filters = finding_filters(application_id, severity, status, search)?;
+        filters.visible_to = ownership::read_scope(current_user(ctx));
+        FindingPage::load(pool(ctx), &filters, &pagination(page, per_page)).await
+    }
+
+    /// An application by ID.
+    async fn application(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> async_graphql::Result<Option<ApplicationNode>> {
+        match application_service::find_by_id(pool(ctx), id).await {
+            Ok(app) => {
+                ownership::authorize(pool(ctx), current_user(ctx), app.id, OwnershipRole::Viewer)
+                    .await
+                    .map_err(gql_error)?;
+                Ok(Some(ApplicationNode(app)))
+            }
+            Err(AppError::NotFound(_)) => Ok(None),
+            Err(e) => Err(gql_error(e)),
+        }
+    }
+
+    /// An application by its application code.
+    async fn application_by_code(
+        &self,
+        ctx: &Context<'_>,
+        app_code: String,
+    ) -> async_graphql::Result<Option<ApplicationNode>> {
+        let app = application_service::find_by_app_code(pool(ctx), &app_code)
+            .await
+            .map_err(gql_error)?;
+        if let Some(app) = &app {
+            ownership::authorize(pool(ctx), current_user(ctx), app.id, OwnershipRole::Viewer)
+                .await
+                .map_err(gql_error)?;
+        }
+        Ok(app.map(ApplicationNode))
+    }
+
+    /// Applications matching `search` on name or code.
+    async fn applications(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        page: Option<i64>,
+        per_page: Option<i64>,
+    ) -> async_graphql::Result<ApplicationPage> {
+        let filters = ApplicationFilters {
+            search,
+            visible_to: ownership::read_scope(current_user(ctx)),
+            ..Default::default()
+        };
+        ApplicationPage::load(
+            pool(ctx),
+            &filters,
+            &ApplicationSort::default(),
+            &pagination(page, per_page),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::FindingStatus;
+
+    #[test]
+    fn schema_exposes_nested_finding_fields() {
+        let sdl = build_schema().sdl();
+        for needle in [
+            "type Finding",
+            "application: Application",
+            "relationships: [Relationship!]!",
+            "comments: [Comment!]!",
+            "history: [HistoryEntry!]!",
+        ] {
+            assert!(sdl.contains(needle), "missing {needle} in schema");
+        }
+        assert!(sdl.contains("applicationByCode(appCode: String!): Application"));
+    }
+
+    #[test]
+    fn enum_labels_match_rest_representation() {
+        assert_eq!(
+            label(&FindingStatus::FalsePositiveRequested),
+            "False_Positive_Requested"
+        );
+    }
+
+    #[test]
+    fn filter_arguments_are_validated() {
+        assert!(finding_filters(None, Some("Critical".to_string()), None, None).is_ok());
+        assert!(finding_filters(None, Some("Urgent".to_string()), None, None).is_err());
+    }
+
+    #[test]
+    fn internal_errors_are_not_leaked() {
+        let err = gql_error(AppError::Internal(
+            "connection refused on 10.0.0.3".to_string(),
+        ));
+        assert_eq!(err.message, "An internal error occurred");
+    }
+}
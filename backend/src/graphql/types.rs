@@ -0,0 +1,568 @@
+//! GraphQL object types wrapping the REST models.
+//!
+//! Enum values are exposed as strings in their REST spelling, and free-form
+//! JSONB columns as `JSON` scalars.
+
+use async_graphql::{Context, Json, Object};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{current_user, finding_filters, gql_error, label, pagination, pool, viewable_finding};
+use crate::models::application::Application;
+use crate::models::finding::{FindingComment, FindingHistory, FindingRelationship};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::application::{
+    self as application_service, ApplicationFilters, ApplicationSort,
+};
+use crate::services::attack_chains::{self, AppAttackChainDetail, AttackChainFilters};
+use crate::services::correlation_service::{self, CorrelationGroupDetail};
+use crate::services::finding::{
+    self as finding_service, FindingFilters, FindingSort, FindingWithDetails,
+};
+use crate::services::ownership;
+
+// ---------------------------------------------------------------------------
+// Findings
+// ---------------------------------------------------------------------------
+
+/// A finding with its category-specific details.
+pub struct FindingNode(pub FindingWithDetails);
+
+#[Object(name = "Finding")]
+impl FindingNode {
+    async fn id(&self) -> Uuid {
+        self.0.finding.id
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.finding.title
+    }
+
+    async fn description(&self) -> &str {
+        &self.0.finding.description
+    }
+
+    async fn source_tool(&self) -> &str {
+        &self.0.finding.source_tool
+    }
+
+    async fn source_finding_id(&self) -> &str {
+        &self.0.finding.source_finding_id
+    }
+
+    /// `SAST`, `SCA`, or `DAST`.
+    async fn category(&self) -> String {
+        label(&self.0.finding.finding_category)
+    }
+
+    /// The analyst override when set, else the normalized scanner severity.
+    async fn severity(&self) -> &str {
+        self.0.finding.effective_severity.label()
+    }
+
+    /// Severity normalized from the scanner's rating.
+    async fn normalized_severity(&self) -> &str {
+        self.0.finding.normalized_severity.label()
+    }
+
+    /// Severity set by an analyst, replacing the normalized one.
+    async fn severity_override(&self) -> Option<&str> {
+        self.0.finding.severity_override.as_ref().map(|s| s.label())
+    }
+
+    async fn original_severity(&self) -> &str {
+        &self.0.finding.original_severity
+    }
+
+    async fn status(&self) -> String {
+        label(&self.0.finding.status)
+    }
+
+    async fn cvss_score(&self) -> Option<f32> {
+        self.0.finding.cvss_score
+    }
+
+    async fn risk_score(&self) -> Option<f32> {
+        self.0.finding.composite_risk_score
+    }
+
+    async fn cve_ids(&self) -> Json<serde_json::Value> {
+        Json(self.0.finding.cve_ids.clone())
+    }
+
+    async fn cwe_ids(&self) -> Json<serde_json::Value> {
+        Json(self.0.finding.cwe_ids.clone())
+    }
+
+    async fn tags(&self) -> Json<serde_json::Value> {
+        Json(self.0.finding.tags.clone())
+    }
+
+    async fn fingerprint(&self) -> &str {
+        &self.0.finding.fingerprint
+    }
+
+    async fn application_id(&self) -> Option<Uuid> {
+        self.0.finding.application_id
+    }
+
+    async fn remediation_owner(&self) -> Option<&str> {
+        self.0.finding.remediation_owner.as_deref()
+    }
+
+    async fn remediation_guidance(&self) -> Option<&str> {
+        self.0.finding.remediation_guidance.as_deref()
+    }
+
+    async fn sla_status(&self) -> Option<String> {
+        self.0.finding.sla_status.as_ref().map(label)
+    }
+
+    async fn sla_due_date(&self) -> Option<DateTime<Utc>> {
+        self.0.finding.sla_due_date
+    }
+
+    async fn first_seen(&self) -> DateTime<Utc> {
+        self.0.finding.first_seen
+    }
+
+    async fn last_seen(&self) -> DateTime<Utc> {
+        self.0.finding.last_seen
+    }
+
+    async fn status_changed_at(&self) -> DateTime<Utc> {
+        self.0.finding.status_changed_at
+    }
+
+    async fn archived_at(&self) -> Option<DateTime<Utc>> {
+        self.0.finding.archived_at
+    }
+
+    async fn introduced_in_release_id(&self) -> Option<Uuid> {
+        self.0.finding.introduced_in_release_id
+    }
+
+    async fn fixed_in_release_id(&self) -> Option<Uuid> {
+        self.0.finding.fixed_in_release_id
+    }
+
+    /// SAST, SCA, or DAST fields for the finding's category.
+    async fn category_details(&self) -> Option<Json<serde_json::Value>> {
+        let details = match (&self.0.sast, &self.0.sca, &self.0.dast) {
+            (Some(sast), _, _) => serde_json::to_value(sast),
+            (_, Some(sca), _) => serde_json::to_value(sca),
+            (_, _, Some(dast)) => serde_json::to_value(dast),
+            _ => return None,
+        };
+        details.ok().map(Json)
+    }
+
+    /// The owning application, if the finding is mapped to one.
+    async fn application(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<ApplicationNode>> {
+        let Some(app_id) = self.0.finding.application_id else {
+            return Ok(None);
+        };
+        let app = application_service::find_by_id(pool(ctx), app_id)
+            .await
+            .map_err(gql_error)?;
+        Ok(Some(ApplicationNode(app)))
+    }
+
+    /// Relationships where this finding is source or target.
+    async fn relationships(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<RelationshipNode>> {
+        let relationships =
+            correlation_service::list_relationships_for_finding(pool(ctx), self.0.finding.id)
+                .await
+                .map_err(gql_error)?;
+        Ok(relationships.into_iter().map(RelationshipNode).collect())
+    }
+
+    /// The correlation chain rooted at this finding, with the member findings
+    /// the user may view.
+    async fn correlation_group(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<Json<CorrelationGroupDetail>>> {
+        match correlation_service::get_group(pool(ctx), self.0.finding.id).await {
+            Ok(mut group) if group.members.len() > 1 => {
+                if let Some(user_id) = ownership::read_scope(current_user(ctx)) {
+                    let visible = ownership::assigned_application_ids(pool(ctx), user_id)
+                        .await
+                        .map_err(gql_error)?;
+                    group.members.retain(|member| {
+                        member
+                            .application_id
+                            .is_some_and(|app_id| visible.contains(&app_id))
+                    });
+                }
+                Ok(Some(Json(group)))
+            }
+            Ok(_) | Err(crate::errors::AppError::NotFound(_)) => Ok(None),
+            Err(e) => Err(gql_error(e)),
+        }
+    }
+
+    /// Comments, oldest first.
+    async fn comments(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<CommentNode>> {
+        let comments = finding_service::list_comments(pool(ctx), self.0.finding.id)
+            .await
+            .map_err(gql_error)?;
+        Ok(comments.into_iter().map(CommentNode).collect())
+    }
+
+    /// Change history, newest first.
+    async fn history(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<HistoryNode>> {
+        let history = finding_service::get_history(pool(ctx), self.0.finding.id)
+            .await
+            .map_err(gql_error)?;
+        Ok(history.into_iter().map(HistoryNode).collect())
+    }
+}
+
+/// A page of findings.
+pub struct FindingPage {
+    page: PagedResult<FindingNode>,
+}
+
+impl FindingPage {
+    /// List matching finding IDs, then load full findings in one batch.
+    pub(crate) async fn load(
+        pool: &PgPool,
+        filters: &FindingFilters,
+        pagination: &Pagination,
+    ) -> async_graphql::Result<Self> {
+        let summaries = finding_service::list(pool, filters, &FindingSort::default(), pagination)
+            .await
+            .map_err(gql_error)?;
+        let ids: Vec<Uuid> = summaries.items.iter().map(|s| s.id).collect();
+        let batch = finding_service::find_many(pool, &ids)
+            .await
+            .map_err(gql_error)?;
+        Ok(Self {
+            page: PagedResult {
+                items: batch.items.into_iter().map(FindingNode).collect(),
+                total: summaries.total,
+                page: summaries.page,
+                per_page: summaries.per_page,
+                total_pages: summaries.total_pages,
+            },
+        })
+    }
+}
+
+#[Object]
+impl FindingPage {
+    async fn items(&self) -> &[FindingNode] {
+        &self.page.items
+    }
+
+    async fn total(&self) -> i64 {
+        self.page.total
+    }
+
+    async fn page(&self) -> i64 {
+        self.page.page
+    }
+
+    async fn per_page(&self) -> i64 {
+        self.page.per_page
+    }
+
+    async fn total_pages(&self) -> i64 {
+        self.page.total_pages
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Relationships, comments, history
+// ---------------------------------------------------------------------------
+
+/// A link between two findings.
+pub struct RelationshipNode(pub FindingRelationship);
+
+#[Object(name = "Relationship")]
+impl RelationshipNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    /// `duplicate_of`, `correlated_with`, `grouped_under`, or `superseded_by`.
+    async fn relationship_type(&self) -> String {
+        label(&self.0.relationship_type)
+    }
+
+    async fn confidence(&self) -> Option<String> {
+        self.0.confidence.as_ref().map(label)
+    }
+
+    async fn notes(&self) -> Option<&str> {
+        self.0.notes.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn source_finding_id(&self) -> Uuid {
+        self.0.source_finding_id
+    }
+
+    async fn target_finding_id(&self) -> Uuid {
+        self.0.target_finding_id
+    }
+
+    async fn source(&self, ctx: &Context<'_>) -> async_graphql::Result<FindingNode> {
+        let finding = viewable_finding(ctx, self.0.source_finding_id)
+            .await
+            .map_err(gql_error)?;
+        Ok(FindingNode(finding))
+    }
+
+    async fn target(&self, ctx: &Context<'_>) -> async_graphql::Result<FindingNode> {
+        let finding = viewable_finding(ctx, self.0.target_finding_id)
+            .await
+            .map_err(gql_error)?;
+        Ok(FindingNode(finding))
+    }
+}
+
+/// A comment on a finding.
+pub struct CommentNode(pub FindingComment);
+
+#[Object(name = "Comment")]
+impl CommentNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn author_id(&self) -> Uuid {
+        self.0.author_id
+    }
+
+    async fn author_name(&self) -> &str {
+        &self.0.author_name
+    }
+
+    async fn content(&self) -> &str {
+        &self.0.content
+    }
+
+    /// Comment this one replies to.
+    async fn parent_id(&self) -> Option<Uuid> {
+        self.0.parent_id
+    }
+
+    async fn mentioned_user_ids(&self) -> &[Uuid] {
+        &self.0.mentioned_user_ids
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+}
+
+/// One entry of a finding's change history.
+pub struct HistoryNode(pub FindingHistory);
+
+#[Object(name = "HistoryEntry")]
+impl HistoryNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn action(&self) -> &str {
+        &self.0.action
+    }
+
+    async fn field_changed(&self) -> Option<&str> {
+        self.0.field_changed.as_deref()
+    }
+
+    async fn old_value(&self) -> Option<&str> {
+        self.0.old_value.as_deref()
+    }
+
+    async fn new_value(&self) -> Option<&str> {
+        self.0.new_value.as_deref()
+    }
+
+    async fn actor_id(&self) -> Option<Uuid> {
+        self.0.actor_id
+    }
+
+    async fn actor_name(&self) -> &str {
+        &self.0.actor_name
+    }
+
+    async fn justification(&self) -> Option<&str> {
+        self.0.justification.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Applications
+// ---------------------------------------------------------------------------
+
+/// A registered application.
+pub struct ApplicationNode(pub Application);
+
+#[Object(name = "Application")]
+impl ApplicationNode {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn app_name(&self) -> &str {
+        &self.0.app_name
+    }
+
+    async fn app_code(&self) -> &str {
+        &self.0.app_code
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn criticality(&self) -> Option<String> {
+        self.0.criticality.as_ref().map(label)
+    }
+
+    async fn tier(&self) -> String {
+        label(&self.0.tier)
+    }
+
+    async fn status(&self) -> String {
+        label(&self.0.status)
+    }
+
+    async fn business_unit(&self) -> Option<&str> {
+        self.0.business_unit.as_deref()
+    }
+
+    async fn business_owner(&self) -> Option<&str> {
+        self.0.business_owner.as_deref()
+    }
+
+    async fn technical_owner(&self) -> Option<&str> {
+        self.0.technical_owner.as_deref()
+    }
+
+    async fn security_champion(&self) -> Option<&str> {
+        self.0.security_champion.as_deref()
+    }
+
+    async fn is_verified(&self) -> bool {
+        self.0.is_verified
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    /// The application's findings, filtered like the top-level `findings` query.
+    async fn findings(
+        &self,
+        ctx: &Context<'_>,
+        severity: Option<String>,
+        status: Option<String>,
+        page: Option<i64>,
+        per_page: Option<i64>,
+    ) -> async_graphql::Result<FindingPage> {
+        let filters = finding_filters(Some(self.0.id), severity, status, None)?;
+        FindingPage::load(pool(ctx), &filters, &pagination(page, per_page)).await
+    }
+
+    /// Attack chains built from the application's correlation groups.
+    async fn attack_chains(
+        &self,
+        ctx: &Context<'_>,
+        branch: Option<String>,
+    ) -> async_graphql::Result<Json<AppAttackChainDetail>> {
+        let filters = AttackChainFilters {
+            branch,
+            ..Default::default()
+        };
+        let detail = attack_chains::get_by_app(pool(ctx), self.0.id, &filters)
+            .await
+            .map_err(gql_error)?;
+        Ok(Json(detail))
+    }
+}
+
+/// A page of applications.
+pub struct ApplicationPage {
+    page: PagedResult<ApplicationNode>,
+}
+
+impl ApplicationPage {
+    /// List matching application IDs, then load the full records.
+    pub(crate) async fn load(
+        pool: &PgPool,
+        filters: &ApplicationFilters,
+        sort: &ApplicationSort,
+        pagination: &Pagination,
+    ) -> async_graphql::Result<Self> {
+        let summaries = application_service::list(pool, filters, sort, pagination)
+            .await
+            .map_err(gql_error)?;
+        let mut items = Vec::with_capacity(summaries.items.len());
+        for summary in &summaries.items {
+            let app = application_service::find_by_id(pool, summary.id)
+                .await
+                .map_err(gql_error)?;
+            items.push(ApplicationNode(app));
+        }
+        Ok(Self {
+            page: PagedResult {
+                items,
+                total: summaries.total,
+                page: summaries.page,
+                per_page: summaries.per_page,
+                total_pages: summaries.total_pages,
+            },
+        })
+    }
+}
+
+#[Object]
+impl ApplicationPage {
+    async fn items(&self) -> &[ApplicationNode] {
+        &self.page.items
+    }
+
+    async fn total(&self) -> i64 {
+        self.page.total
+    }
+
+    async fn page(&self) -> i64 {
+        self.page.page
+    }
+
+    async fn per_page(&self) -> i64 {
+        self.page.per_page
+    }
+
+    async fn total_pages(&self) -> i64 {
+        self.page.total_pages
+    }
+}
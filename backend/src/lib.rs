@@ -1,10 +1,14 @@
 pub mod config;
 pub mod db;
 pub mod errors;
+pub mod graphql;
 pub mod middleware;
 pub mod models;
+pub mod openapi;
 pub mod routes;
 pub mod services;
+pub mod shutdown;
+pub mod tls;
 
 pub mod parsers;
 
@@ -14,5 +18,16 @@ use sqlx::PgPool;
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub db: PgPool,
+    /// Pool for read-only queries (lists, dashboards, reports, exports): the
+    /// replica when `DATABASE_REPLICA_URL` is set, otherwise the primary.
+    pub db_read: PgPool,
     pub config: config::AppConfig,
+    /// Splunk HEC forwarder; `None` when forwarding is not configured.
+    pub hec: Option<services::splunk_hec::HecSink>,
+    /// Cached dashboard statistics, invalidated when the dashboard views refresh.
+    pub dashboard_cache: services::dashboard::StatsCache,
+    /// Storage backend for finding attachments.
+    pub attachments: services::attachment_storage::AttachmentStorage,
+    /// Shared Redis connection; `None` when `REDIS_URL` is invalid.
+    pub redis: Option<services::redis_store::RedisStore>,
 }
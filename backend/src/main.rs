@@ -5,14 +5,20 @@ use axum::{
     Router,
 };
 use mimalloc::MiMalloc;
-use synapsec::{config::AppConfig, db, routes, AppState};
-use axum::http::header;
+use synapsec::middleware::rate_limit::{self, RateLimiter, RouteGroup};
+use synapsec::middleware::request_limits::{self, RequestLimits};
+use synapsec::shutdown::Shutdown;
+use synapsec::{config::AppConfig, db, openapi::ApiDoc, routes, AppState};
 use tower_http::{
-    compression::CompressionLayer,
-    cors::CorsLayer,
+    compression::{
+        predicate::{DefaultPredicate, NotForContentType, Predicate},
+        CompressionLayer,
+    },
     trace::TraceLayer,
 };
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 // M-MIMALLOC-APP: Use mimalloc as global allocator for improved performance.
 #[global_allocator]
@@ -35,42 +41,179 @@ async fn main() -> anyhow::Result<()> {
         .with(tracing_subscriber::fmt::layer().json())
         .init();
 
-    let config = AppConfig::from_env().expect("Failed to load configuration");
+    let config = AppConfig::load()?;
 
     // Database pool
     let pool = db::create_pool(&config.database_url, config.database_max_connections).await?;
     tracing::info!("Database connection pool created");
+    let read_pool = db::create_read_pool(
+        config.database_replica_url.as_deref(),
+        &pool,
+        config.database_max_connections,
+    )
+    .await?;
+    if config.database_replica_url.is_some() {
+        tracing::info!("Read replica connection pool created");
+    }
 
     // Run migrations
     sqlx::migrate!("./migrations").run(&pool).await?;
     tracing::info!("Database migrations applied");
 
-    // CORS
-    let cors = CorsLayer::new()
-        .allow_origin(
-            config
-                .frontend_url
-                .parse::<axum::http::HeaderValue>()
-                .expect("Invalid FRONTEND_URL"),
+    // Monthly partitioning of findings and finding history
+    if config.time_partitioning_enabled {
+        synapsec::services::partitions::enable(
+            &pool,
+            chrono::Utc::now().date_naive(),
+            config.partition_months_ahead,
         )
-        .allow_methods([
-            axum::http::Method::GET,
-            axum::http::Method::POST,
-            axum::http::Method::PUT,
-            axum::http::Method::PATCH,
-            axum::http::Method::DELETE,
-        ])
-        .allow_headers([
-            header::CONTENT_TYPE,
-            header::AUTHORIZATION,
-            header::ACCEPT,
-        ])
-        .allow_credentials(true);
+        .await?;
+        tracing::info!("Time partitioning enabled");
+    }
+
+    // CORS
+    let cors = synapsec::middleware::cors::CorsPolicy::from_config(&config)
+        .map_err(anyhow::Error::msg)?
+        .layer();
+
+    let redis = synapsec::services::redis_store::RedisStore::from_config(&config);
+    let rate_limiter = RateLimiter::from_config(&config, redis.as_ref(), &pool);
+
+    synapsec::services::config_cache::configure(std::time::Duration::from_secs(
+        config.config_cache_ttl_secs.max(1),
+    ));
+    if let Some(redis) = &redis {
+        synapsec::services::config_cache::spawn_invalidation_listener(redis.clone());
+    }
+    synapsec::services::finding::set_search_syntax(
+        config
+            .finding_search_syntax
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid FINDING_SEARCH_SYNTAX: {e}"))?,
+    );
 
     let state = AppState {
         db: pool,
+        db_read: read_pool,
         config: config.clone(),
+        hec: synapsec::services::splunk_hec::HecSettings::from_config(&config)
+            .map(synapsec::services::splunk_hec::spawn),
+        dashboard_cache: synapsec::services::dashboard::StatsCache::new(
+            std::time::Duration::from_secs(config.dashboard_cache_ttl_secs.max(1)),
+            redis.clone(),
+        ),
+        attachments: synapsec::services::attachment_storage::AttachmentStorage::from_config(
+            &config,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to initialise attachment storage: {e}"))?,
+        redis: redis.clone(),
     };
+    if state.hec.is_some() {
+        tracing::info!("Splunk HEC forwarding enabled");
+    }
+
+    // Scheduled report delivery
+    let report_delivery = synapsec::services::report_delivery::ReportDelivery::from_config(&config)
+        .map_err(|e| anyhow::anyhow!("Failed to initialise report delivery: {e}"))?;
+    synapsec::services::report_schedule::spawn_scheduler(
+        state.db.clone(),
+        report_delivery.clone(),
+        std::time::Duration::from_secs(config.report_scheduler_interval_secs.max(1)),
+    );
+    tracing::info!("Report scheduler started");
+
+    // Daily finding snapshots for dashboard trends
+    synapsec::services::finding_trends::spawn_snapshotter(
+        state.db.clone(),
+        std::time::Duration::from_secs(config.trend_snapshot_interval_secs.max(1)),
+    );
+    tracing::info!("Trend snapshotter started");
+
+    // Materialized views behind the dashboard statistics
+    synapsec::services::dashboard::spawn_view_refresher(
+        state.db.clone(),
+        state.dashboard_cache.clone(),
+        std::time::Duration::from_secs(config.dashboard_view_refresh_interval_secs.max(1)),
+    );
+    tracing::info!("Dashboard view refresher started");
+
+    // Nightly open-finding snapshots for historical comparisons
+    synapsec::services::dashboard_snapshots::spawn_snapshotter(
+        state.db.clone(),
+        std::time::Duration::from_secs(config.dashboard_snapshot_interval_secs.max(1)),
+    );
+    tracing::info!("Dashboard snapshotter started");
+
+    // SLA breach detection and notifications
+    synapsec::services::sla_monitor::spawn_monitor(
+        state.db.clone(),
+        std::time::Duration::from_secs(config.sla_monitor_interval_secs.max(1)),
+    );
+    tracing::info!("SLA monitor started");
+
+    // Immediate and daily digest notification emails
+    synapsec::services::notification_mailer::spawn_mailer(
+        state.db.clone(),
+        report_delivery,
+        std::time::Duration::from_secs(config.notification_mailer_interval_secs.max(1)),
+        config.notification_digest_hour_utc.min(23),
+    );
+    tracing::info!("Notification mailer started");
+
+    // CVE enrichment from NVD
+    if let Some(nvd) = synapsec::services::nvd::NvdSettings::from_config(&config) {
+        synapsec::services::nvd::spawn_enricher(state.db.clone(), nvd);
+        tracing::info!("NVD CVE enrichment started");
+    }
+
+    // Fixed-version backfill for SCA findings from OSV
+    if config.osv_enrichment_enabled {
+        synapsec::services::osv::spawn_enricher(
+            state.db.clone(),
+            synapsec::services::osv::OsvSettings::from_config(&config),
+        );
+        tracing::info!("OSV enrichment started");
+    }
+
+    // GHSA id to CVE alias resolution
+    if let Some(ghsa) = synapsec::services::ghsa::GhsaSettings::from_config(&config) {
+        synapsec::services::ghsa::spawn_resolver(state.db.clone(), ghsa);
+        tracing::info!("GHSA resolver started");
+    }
+
+    // Exploit-DB and Metasploit exploit maturity enrichment
+    if let Some(exploits) = synapsec::services::exploits::ExploitSettings::from_config(&config) {
+        synapsec::services::exploits::spawn_enricher(state.db.clone(), exploits);
+        tracing::info!("Exploit enrichment started");
+    }
+
+    // End-of-life package detection from endoflife.date
+    if let Some(eol) = synapsec::services::eol::EolSettings::from_config(&config) {
+        synapsec::services::eol::spawn_detector(state.db.clone(), eol);
+        tracing::info!("End-of-life detection started");
+    }
+
+    // Upcoming monthly partitions, for tables converted now or earlier
+    synapsec::services::partitions::spawn_maintainer(
+        state.db.clone(),
+        config.partition_months_ahead,
+    );
+
+    // Background job workers
+    let dashboard_cache = state.dashboard_cache.clone();
+    let job_registry = synapsec::services::job::JobRegistry::new().register(
+        synapsec::services::dashboard::REFRESH_JOB,
+        move |pool, _payload| synapsec::services::dashboard::run_refresh_job(pool, dashboard_cache.clone()),
+    );
+    let shutdown = Shutdown::new();
+    let mut shutdown_listener = shutdown.listener();
+    let job_workers = synapsec::services::job::spawn_workers(
+        state.db.clone(),
+        job_registry,
+        synapsec::services::job::WorkerSettings::from_config(&config),
+        shutdown_listener.clone(),
+    );
+    tracing::info!(workers = config.job_workers, "Job workers started");
 
     // API v1 auth routes
     let auth_routes = Router::new()
@@ -86,26 +229,238 @@ async fn main() -> anyhow::Result<()> {
         .route("/applications/unverified", get(routes::applications::list_unverified))
         .route("/applications/import", post(routes::applications::import_bulk))
         .route("/applications/import/apm", post(routes::applications::import_apm))
+        .route("/applications/import/apm/preview", post(routes::applications::preview_apm))
         .route("/applications/code/{code}", get(routes::applications::get_by_code))
-        .route("/applications/{id}", get(routes::applications::get_by_id).put(routes::applications::update));
+        .route("/applications/{id}", get(routes::applications::get_by_id).put(routes::applications::update))
+        .route("/applications/{id}/posture", get(routes::applications::posture))
+        .route("/applications/{id}/stats", get(routes::applications::stats))
+        .route("/applications/{id}/auto-close-candidates", get(routes::occurrences::auto_close_candidates))
+        .route("/applications/{id}/merge/{dup_id}", post(routes::applications::merge));
+
+    // API v1 application ownership routes
+    let ownership_routes = Router::new()
+        .route("/applications/{id}/owners", get(routes::ownership::list))
+        .route(
+            "/applications/{id}/owners/{user_id}",
+            put(routes::ownership::assign).delete(routes::ownership::remove),
+        )
+        .route("/me/applications", get(routes::ownership::my_applications));
+
+    // API v1 notification routes
+    let notification_routes = Router::new()
+        .route("/me/notifications", get(routes::notifications::list))
+        .route("/me/notifications/unread-count", get(routes::notifications::unread_count))
+        .route("/me/notifications/read", post(routes::notifications::mark_read))
+        .route("/me/notifications/read-all", post(routes::notifications::mark_all_read))
+        .route(
+            "/me/notification-preferences",
+            get(routes::notifications::get_preferences).put(routes::notifications::update_preferences),
+        );
+
+    // API v1 asset inventory routes
+    let asset_routes = Router::new()
+        .route("/assets", get(routes::assets::list).post(routes::assets::create))
+        .route(
+            "/assets/{id}",
+            get(routes::assets::get_by_id)
+                .put(routes::assets::update)
+                .delete(routes::assets::delete),
+        )
+        .route("/assets/{id}/application", put(routes::assets::link))
+        .route("/applications/{id}/assets", get(routes::assets::list_for_application));
+
+    // API v1 app code pattern routes
+    let app_code_pattern_routes = Router::new()
+        .route("/app-code-patterns", get(routes::app_code_patterns::list).post(routes::app_code_patterns::create))
+        .route("/app-code-patterns/order", put(routes::app_code_patterns::reorder))
+        .route("/app-code-patterns/test", post(routes::app_code_patterns::test))
+        .route("/app-code-patterns/{id}", get(routes::app_code_patterns::get_by_id).put(routes::app_code_patterns::update))
+        .route("/app-code-patterns/{id}/deactivate", post(routes::app_code_patterns::deactivate));
+
+    // API v1 scanner rule catalog routes
+    let scanner_rule_routes = Router::new()
+        .route("/scanner-rules", get(routes::scanner_rules::list))
+        .route("/scanner-rules/{id}", get(routes::scanner_rules::get_by_id))
+        .route("/scanner-rules/{id}/policy", put(routes::scanner_rules::update_policy));
+
+    // API v1 triage queue routes
+    let triage_routes = Router::new()
+        .route("/triage/queue", get(routes::triage::queue))
+        .route("/triage/queue/{id}/claim", post(routes::triage::claim).delete(routes::triage::release));
+
+    // API v1 watchlist routes
+    let watchlist_routes = Router::new()
+        .route("/watchlist", get(routes::watchlist::list).post(routes::watchlist::create))
+        .route("/watchlist/hits", get(routes::watchlist::hits))
+        .route("/watchlist/{id}", delete(routes::watchlist::delete));
+
+    // API v1 upload token routes
+    let upload_token_routes = Router::new()
+        .route("/upload-tokens", get(routes::upload_tokens::list).post(routes::upload_tokens::create))
+        .route("/upload-tokens/{id}", delete(routes::upload_tokens::revoke));
+
+    // API v1 end-of-life report
+    let eol_routes = Router::new().route("/eol/report", get(routes::eol::report));
+
+    // API v1 license compliance routes
+    let license_policy_routes = Router::new()
+        .route("/license-policies", get(routes::license_policies::list).post(routes::license_policies::create))
+        .route(
+            "/license-policies/{id}",
+            put(routes::license_policies::update).delete(routes::license_policies::delete),
+        )
+        .route("/applications/{id}/licenses", get(routes::license_policies::application_report));
+
+    // API v1 DNS mapping routes
+    let dns_mapping_routes = Router::new()
+        .route("/dns-mappings", get(routes::dns_mappings::list).post(routes::dns_mappings::create))
+        .route(
+            "/dns-mappings/{id}",
+            get(routes::dns_mappings::get_by_id)
+                .put(routes::dns_mappings::update)
+                .delete(routes::dns_mappings::delete),
+        );
 
     // API v1 finding routes
     let finding_routes = Router::new()
         .route("/findings", get(routes::findings::list).post(routes::findings::create))
-        .route("/findings/export", get(routes::findings::export_findings))
+        .route("/findings/manual", post(routes::findings::create_manual))
+        .route("/findings/batch-get", post(routes::findings::batch_get))
         .route("/findings/bulk/status", post(routes::findings::bulk_status))
         .route("/findings/bulk/assign", post(routes::findings::bulk_assign))
         .route("/findings/bulk/tag", post(routes::findings::bulk_tag))
+        .route("/findings/bulk/archive", post(routes::findings::bulk_archive))
+        .route("/findings/bulk/delete", post(routes::findings::bulk_delete))
+        .route("/findings/bulk/reassign", post(routes::finding_reassign::bulk_reassign))
         .route("/findings/{id}", get(routes::findings::get_by_id).put(routes::findings::update))
         .route("/findings/{id}/status", patch(routes::findings::update_status))
+        .route("/findings/{id}/severity-override", put(routes::findings::set_severity_override))
         .route("/findings/{id}/comments", get(routes::findings::list_comments).post(routes::findings::add_comment))
-        .route("/findings/{id}/history", get(routes::findings::get_history));
+        .route(
+            "/findings/{id}/comments/{comment_id}",
+            put(routes::findings::update_comment).delete(routes::findings::delete_comment),
+        )
+        .route("/findings/{id}/history", get(routes::findings::get_history))
+        .route("/findings/{id}/raw", get(routes::findings::get_raw))
+        .route("/findings/{id}/similar", get(routes::findings::get_similar))
+        .route("/findings/{id}/merge", post(routes::finding_merges::merge))
+        .route("/findings/{id}/merges", get(routes::finding_merges::list))
+        .route("/findings/merges/{merge_id}/unmerge", post(routes::finding_merges::unmerge))
+        .route("/findings/{id}/split", post(routes::finding_reassign::split))
+        .route("/findings/{id}/splits", get(routes::finding_reassign::list_splits))
+        .route("/findings/{id}/occurrences", get(routes::occurrences::list))
+        .route("/findings/{id}/occurrences/summary", get(routes::occurrences::summary))
+        .route("/findings/{id}/enrich/osv", post(routes::enrichment::enrich_osv))
+        .route("/me/mentions", get(routes::findings::list_my_mentions));
+
+    // API v1 finding attachment routes; the body limit leaves room for multipart framing
+    let attachment_routes = Router::new()
+        .route(
+            "/findings/{id}/attachments",
+            get(routes::attachments::list).post(routes::attachments::upload),
+        )
+        .route(
+            "/findings/{id}/attachments/{attachment_id}",
+            get(routes::attachments::download).delete(routes::attachments::delete),
+        )
+        .layer(axum::extract::DefaultBodyLimit::max(
+            config.attachment_max_bytes + 64 * 1024,
+        ));
+
+    // API v1 SBOM routes; documents can be far larger than the default body limit
+    let sbom_routes = Router::new()
+        .route(
+            "/applications/{id}/sbom",
+            get(routes::sbom::get_latest).post(routes::sbom::upload),
+        )
+        .route("/applications/{id}/sboms", get(routes::sbom::list))
+        .route("/sbom/packages", get(routes::sbom::find_package))
+        .layer(axum::extract::DefaultBodyLimit::max(config.sbom_max_bytes));
+
+    // API v1 release routes
+    let release_routes = Router::new()
+        .route(
+            "/applications/{id}/releases",
+            get(routes::releases::list).post(routes::releases::create),
+        )
+        .route(
+            "/releases/{id}",
+            get(routes::releases::get_by_id)
+                .put(routes::releases::update)
+                .delete(routes::releases::delete),
+        )
+        .route("/releases/{id}/notes", get(routes::releases::notes))
+        .route("/findings/{id}/releases", put(routes::releases::set_finding_releases));
+
+    // API v1 saved filter routes
+    let saved_filter_routes = Router::new()
+        .route("/saved-filters", get(routes::saved_filters::list).post(routes::saved_filters::create))
+        .route(
+            "/saved-filters/{id}",
+            get(routes::saved_filters::get_by_id)
+                .put(routes::saved_filters::update)
+                .delete(routes::saved_filters::delete),
+        );
+
+    // API v1 tag routes
+    let tag_routes = Router::new()
+        .route("/tags", get(routes::tags::list).post(routes::tags::create))
+        .route("/tags/autocomplete", get(routes::tags::autocomplete))
+        .route(
+            "/tags/{id}",
+            get(routes::tags::get_by_id)
+                .put(routes::tags::update)
+                .delete(routes::tags::delete),
+        )
+        .route("/tags/{id}/merge", post(routes::tags::merge));
+
+    // API v1 GraphQL (read-only)
+    let graphql_routes = Router::new()
+        .route("/graphql", get(routes::graphql::graphiql).post(routes::graphql::execute))
+        .layer(axum::Extension(synapsec::graphql::build_schema()));
+
+    // API v1 global search
+    let search_routes = Router::new().route("/search", get(routes::search::search));
+
+    // API v1 CVE catalog routes
+    let cve_routes = Router::new()
+        .route("/cves/{id}", get(routes::cves::get_by_id))
+        .route("/advisories/ghsa/{id}", get(routes::cves::get_ghsa_advisory));
+
+    // API v1 CWE catalog routes; the MITRE dictionary export is several MB
+    let cwe_routes = Router::new()
+        .route("/cwes", get(routes::cwes::list))
+        .route("/cwes/rollup", get(routes::cwes::rollup))
+        .route("/cwes/{id}", get(routes::cwes::get_by_id))
+        .route(
+            "/cwes/import",
+            post(routes::cwes::import)
+                .layer(axum::extract::DefaultBodyLimit::max(32 * 1024 * 1024)),
+        );
+
+    // API v1 ATT&CK catalog imports; the ATT&CK STIX bundle is tens of MB
+    let attack_routes = Router::new()
+        .route("/attack/capec/import", post(routes::attack::import_capec))
+        .route("/attack/techniques/import", post(routes::attack::import_techniques))
+        .layer(axum::extract::DefaultBodyLimit::max(128 * 1024 * 1024));
+
+    // API v1 OWASP Top 10 mapping routes
+    let owasp_routes = Router::new()
+        .route("/owasp/categories", get(routes::owasp::list_categories))
+        .route("/owasp/mappings", put(routes::owasp::update_mappings));
 
     // API v1 ingestion routes
     let ingestion_routes = Router::new()
         .route("/ingestion/upload", post(routes::ingestion::upload))
         .route("/ingestion/history", get(routes::ingestion::history))
-        .route("/ingestion/{id}", get(routes::ingestion::get_log));
+        .route("/ingestion/{id}", get(routes::ingestion::get_log))
+        .route("/ingestion/{id}/diff", get(routes::ingestion::diff));
+    // Mutual TLS: with a client CA configured, ingestion needs a verified client certificate
+    let ingestion_routes = if config.tls_client_ca_path.is_some() {
+        ingestion_routes.layer(axum::middleware::from_fn(synapsec::tls::require_client_certificate))
+    } else {
+        ingestion_routes
+    };
 
     // API v1 correlation routes
     let correlation_routes = Router::new()
@@ -120,37 +475,216 @@ async fn main() -> anyhow::Result<()> {
     // API v1 deduplication dashboard routes
     let dedup_routes = Router::new()
         .route("/deduplication/stats", get(routes::deduplication::stats))
+        .route("/deduplication/effectiveness", get(routes::deduplication::effectiveness))
         .route("/deduplication/pending", get(routes::deduplication::pending))
         .route("/deduplication/history", get(routes::deduplication::history))
+        .route("/deduplication/review-metrics", get(routes::deduplication::review_metrics))
         .route("/deduplication/{relationship_id}/confirm", post(routes::deduplication::confirm))
-        .route("/deduplication/{relationship_id}/reject", post(routes::deduplication::reject));
+        .route("/deduplication/{relationship_id}/reject", post(routes::deduplication::reject))
+        .route("/deduplication/run/{app_id}", post(routes::deduplication::run))
+        .route("/deduplication/similar/{app_id}", post(routes::deduplication::queue_similar));
 
     // API v1 dashboard routes
     let dashboard_routes = Router::new()
-        .route("/dashboard/stats", get(routes::dashboard::stats));
+        .route("/dashboard/stats", get(routes::dashboard::stats))
+        .route("/dashboard/trends", get(routes::dashboard::trends))
+        .route("/dashboard/mttr", get(routes::dashboard::mttr))
+        .route("/dashboard/burndown", get(routes::dashboard::burndown))
+        .route("/dashboard/business-units", get(routes::dashboard::business_units))
+        .route("/dashboard/coverage-gaps", get(routes::dashboard::coverage_gaps))
+        .route("/dashboard/top-apps", get(routes::dashboard::top_apps))
+        .route("/dashboard/snapshots", get(routes::dashboard::snapshots))
+        .route("/dashboard/snapshots/compare", get(routes::dashboard::compare_snapshots))
+        .route("/dashboard/workload", get(routes::dashboard::workload))
+        .route("/dashboards", get(routes::saved_dashboards::list).post(routes::saved_dashboards::create))
+        .route(
+            "/dashboards/{id}",
+            get(routes::saved_dashboards::get_by_id)
+                .put(routes::saved_dashboards::update)
+                .delete(routes::saved_dashboards::delete),
+        );
 
     // API v1 attack chain routes
     let attack_chain_routes = Router::new()
         .route("/attack-chains", get(routes::attack_chains::list))
-        .route("/attack-chains/{app_id}", get(routes::attack_chains::get_by_app));
+        .route("/attack-chains/{app_id}", get(routes::attack_chains::get_by_app))
+        .route(
+            "/attack-chains/{app_id}/tactics",
+            get(routes::attack_chains::get_tactics_by_app),
+        );
+
+    // API v1 remediation routes
+    let remediation_routes = Router::new()
+        .route("/remediation/upgrade-plan", get(routes::remediation::upgrade_plan))
+        .route("/applications/{id}/upgrade-plan", get(routes::remediation::application_upgrade_plan));
+
+    // API v1 VEX routes
+    let vex_routes = Router::new()
+        .route("/applications/{id}/vex", get(routes::vex::export).post(routes::vex::import));
+
+    // API v1 finding export routes; XLSX workbooks are only sent once
+    // complete, which for large exports takes longer than the standard deadline
+    let export_routes = Router::new()
+        .merge(rate_limit::apply(
+            Router::new().route("/findings/export", get(routes::findings::export_findings)),
+            rate_limiter.as_ref(),
+            RouteGroup::Search,
+        ))
+        .route(
+            "/applications/{id}/findings/export",
+            get(routes::exports::export_application_findings),
+        );
+
+    // API v1 report routes
+    let report_routes = Router::new()
+        .route("/reports/executive", get(routes::reports::executive))
+        .route("/reports/dora", get(routes::reports::dora))
+        .route("/reports/gdpr", get(routes::reports::gdpr))
+        .route("/reports/false-positives", get(routes::reports::false_positives))
+        .route("/report-schedules", get(routes::report_schedules::list).post(routes::report_schedules::create))
+        .route(
+            "/report-schedules/{id}",
+            get(routes::report_schedules::get_by_id)
+                .put(routes::report_schedules::update)
+                .delete(routes::report_schedules::delete),
+        )
+        .route("/report-schedules/{id}/run", post(routes::report_schedules::run_now))
+        .route("/report-schedules/{id}/runs", get(routes::report_schedules::list_runs))
+        .route("/report-templates", get(routes::report_templates::list).post(routes::report_templates::create))
+        .route(
+            "/report-templates/{id}",
+            get(routes::report_templates::get_by_id)
+                .put(routes::report_templates::update)
+                .delete(routes::report_templates::delete),
+        );
+
+    // API v1 audit log routes
+    let audit_routes = Router::new()
+        .route("/audit-log", get(routes::audit_log::list))
+        .route("/audit-log/export", get(routes::audit_log::export));
+
+    // API v1 background job administration
+    let job_routes = Router::new()
+        .route("/admin/jobs", get(routes::jobs::list))
+        .route("/admin/jobs/{id}", get(routes::jobs::get_by_id))
+        .route("/admin/jobs/{id}/retry", post(routes::jobs::retry));
+
+    // API v1 fingerprint profile administration
+    let fingerprint_routes = Router::new()
+        .route("/admin/fingerprint-profiles", get(routes::fingerprint_profiles::list))
+        .route(
+            "/admin/fingerprint-profiles/{source_tool}",
+            put(routes::fingerprint_profiles::set).delete(routes::fingerprint_profiles::remove),
+        );
+
+    // API v1 referential hygiene maintenance
+    let data_hygiene_routes = Router::new()
+        .route("/admin/data-hygiene", get(routes::data_hygiene::report))
+        .route("/admin/data-hygiene/fix", post(routes::data_hygiene::fix));
+
+    // API v1 data subject pseudonymization
+    let data_subject_routes = Router::new()
+        .route("/admin/data-subjects/pseudonymize", post(routes::data_subjects::pseudonymize));
+
+    // API v1 configuration bundle export/import
+    let config_bundle_routes = Router::new().route(
+        "/admin/config-bundle",
+        get(routes::config_bundle::export)
+            .post(routes::config_bundle::import)
+            .layer(axum::extract::DefaultBodyLimit::max(32 * 1024 * 1024)),
+    );
+
+    // API v1 groups with the standard request deadline and body limit
+    let api_routes = Router::new()
+        .merge(auth_routes)
+        .merge(app_routes)
+        .merge(ownership_routes)
+        .merge(notification_routes)
+        .merge(asset_routes)
+        .merge(app_code_pattern_routes)
+        .merge(dns_mapping_routes)
+        .merge(scanner_rule_routes)
+        .merge(triage_routes)
+        .merge(watchlist_routes)
+        .merge(license_policy_routes)
+        .merge(eol_routes)
+        .merge(upload_token_routes)
+        .merge(rate_limit::apply(finding_routes, rate_limiter.as_ref(), RouteGroup::Search))
+        .merge(release_routes)
+        .merge(saved_filter_routes)
+        .merge(tag_routes)
+        .merge(graphql_routes)
+        .merge(rate_limit::apply(search_routes, rate_limiter.as_ref(), RouteGroup::Search))
+        .merge(cve_routes)
+        .merge(owasp_routes)
+        .merge(correlation_routes)
+        .merge(dedup_routes)
+        .merge(dashboard_routes)
+        .merge(attack_chain_routes)
+        .merge(remediation_routes)
+        .merge(vex_routes)
+        .merge(report_routes)
+        .merge(audit_routes)
+        .merge(job_routes)
+        .merge(fingerprint_routes);
+
+    // API v1 upload, import, export and maintenance groups: long deadline and
+    // large bodies
+    let upload_routes = Router::new()
+        .merge(export_routes)
+        .merge(attachment_routes)
+        .merge(sbom_routes)
+        .merge(cwe_routes)
+        .merge(attack_routes)
+        .merge(rate_limit::apply(ingestion_routes, rate_limiter.as_ref(), RouteGroup::Ingestion))
+        .merge(config_bundle_routes)
+        .merge(data_hygiene_routes)
+        .merge(data_subject_routes);
 
     let app = Router::new()
         // Health endpoints (no auth required)
         .route("/health/live", get(routes::health::live))
         .route("/health/ready", get(routes::health::ready))
+        // OpenAPI document and Swagger UI (no auth required)
+        .merge(SwaggerUi::new("/api/v1/docs").url("/api/v1/openapi.json", ApiDoc::openapi()))
         // API v1
-        .nest("/api/v1", auth_routes)
-        .nest("/api/v1", app_routes)
-        .nest("/api/v1", finding_routes)
-        .nest("/api/v1", ingestion_routes)
-        .nest("/api/v1", correlation_routes)
-        .nest("/api/v1", dedup_routes)
-        .nest("/api/v1", dashboard_routes)
-        .nest("/api/v1", attack_chain_routes)
+        .nest("/api/v1", request_limits::apply(api_routes, RequestLimits::standard(&config)))
+        .nest("/api/v1", request_limits::apply(upload_routes, RequestLimits::upload(&config)))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            synapsec::middleware::request_audit::record,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            config.problem_json_errors,
+            synapsec::middleware::problem_details::negotiate,
+        ))
         .layer(cors)
         .layer(TraceLayer::new_for_http())
-        .layer(CompressionLayer::new())
-        .with_state(state);
+        // Workbooks are already zip archives; compressing them again only
+        // delays the download
+        .layer(
+            CompressionLayer::new().compress_when(DefaultPredicate::new().and(
+                NotForContentType::const_new(synapsec::services::xlsx_export::CONTENT_TYPE),
+            )),
+        )
+        // Outermost, so every span and error of the request carries its ID
+        .layer(axum::middleware::from_fn(
+            synapsec::middleware::request_id::assign,
+        ))
+        .with_state(state.clone());
+
+    // Graceful shutdown: the first SIGTERM/SIGINT stops new connections and
+    // job claims; in-flight work gets until the drain deadline
+    let drain_timeout = std::time::Duration::from_secs(config.shutdown_timeout_secs);
+    tokio::spawn(async move {
+        synapsec::shutdown::signal().await;
+        shutdown.trigger();
+    });
+    let mut deadline_listener = shutdown_listener.clone();
+    let drain_deadline = async move {
+        let at = deadline_listener.triggered().await;
+        tokio::time::sleep_until(at + drain_timeout).await;
+    };
 
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
 
@@ -163,22 +697,67 @@ async fn main() -> anyhow::Result<()> {
             tracing::info!(path = %cert_path.display(), "Certificate");
             tracing::info!(path = %key_path.display(), "Private key");
 
+            let client_ca_path = config.tls_client_ca_path.as_ref().map(std::path::PathBuf::from);
+            if let Some(ca) = &client_ca_path {
+                tracing::info!(path = %ca.display(), "Client CA (mutual TLS for ingestion)");
+            }
+
             let tls_config =
-                axum_server::tls_rustls::RustlsConfig::from_pem_file(&cert_path, &key_path)
-                    .await
+                synapsec::tls::server_config(&cert_path, &key_path, client_ca_path.as_deref())
                     .map_err(|e| anyhow::anyhow!("Failed to load TLS certificates: {e}"))?;
 
+            let handle = axum_server::Handle::new();
+            let mut tls_listener = shutdown_listener.clone();
+            let tls_handle = handle.clone();
+            tokio::spawn(async move {
+                let at = tls_listener.triggered().await;
+                let remaining =
+                    (at + drain_timeout).saturating_duration_since(tokio::time::Instant::now());
+                tls_handle.graceful_shutdown(Some(remaining));
+            });
+
             tracing::info!(host = %addr, "HTTPS server listening");
-            axum_server::bind_rustls(addr, tls_config)
-                .serve(app.into_make_service())
+            axum_server::bind(addr)
+                .acceptor(synapsec::tls::ClientCertAcceptor::new(tls_config))
+                .handle(handle)
+                .serve(app.into_make_service_with_connect_info::<SocketAddr>())
                 .await?;
         }
         _ => {
+            if config.tls_client_ca_path.is_some() {
+                anyhow::bail!("TLS_CLIENT_CA_PATH requires TLS_CERT_PATH and TLS_KEY_PATH");
+            }
             tracing::warn!(host = %addr, "Starting HTTP server (TLS not configured)");
             let listener = tokio::net::TcpListener::bind(addr).await?;
-            axum::serve(listener, app).await?;
+            let mut http_listener = shutdown_listener.clone();
+            let server = async move {
+                axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+                    .with_graceful_shutdown(async move {
+                        http_listener.triggered().await;
+                    })
+                    .await
+            };
+            tokio::select! {
+                result = server => result?,
+                _ = drain_deadline => {
+                    tracing::warn!("Shutdown timeout elapsed with requests still in flight");
+                }
+            }
         }
     }
 
+    // Let running jobs finish within what is left of the drain deadline
+    let deadline = shutdown_listener.triggered().await + drain_timeout;
+    let workers = futures_util::future::join_all(job_workers);
+    if tokio::time::timeout_at(deadline, workers).await.is_err() {
+        tracing::warn!(
+            "Shutdown timeout elapsed with jobs still running; they are requeued as stale"
+        );
+    }
+
+    state.db.close().await;
+    state.db_read.close().await;
+    tracing::info!("Shutdown complete");
+
     Ok(())
 }
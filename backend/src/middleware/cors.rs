@@ -0,0 +1,246 @@
+//! Cross-origin policy.
+//!
+//! Browsers may call the API from the configured origins: exact origins such
+//! as `https://synapsec.example.com`, or wildcard subdomain patterns such as
+//! `https://*.example.com`, which match any subdomain (not the bare domain)
+//! over that scheme and port. Route groups under a public path prefix, such
+//! as webhook receivers called from third-party pages, accept any origin but
+//! never credentials.
+
+use std::sync::Arc;
+
+use axum::http::{header, request::Parts, HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowCredentials, AllowOrigin, CorsLayer};
+
+use crate::config::AppConfig;
+use crate::middleware::request_id::REQUEST_ID_HEADER;
+
+/// One entry of the allowed origin list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OriginPattern {
+    Exact(String),
+    /// `scheme://*.suffix`; `suffix` keeps its leading dot and any port.
+    Subdomain {
+        scheme: String,
+        suffix: String,
+    },
+}
+
+impl OriginPattern {
+    fn parse(value: &str) -> Result<Self, String> {
+        let origin = value.trim().trim_end_matches('/').to_ascii_lowercase();
+        let (scheme, host) = origin
+            .split_once("://")
+            .filter(|(scheme, _)| matches!(*scheme, "http" | "https"))
+            .ok_or_else(|| format!("CORS origin '{value}' must start with http:// or https://"))?;
+        if host.is_empty() || host.contains(['/', '?', '#', '@']) {
+            return Err(format!(
+                "CORS origin '{value}' must be scheme://host[:port]"
+            ));
+        }
+        match host.strip_prefix('*') {
+            Some(suffix) if suffix.starts_with('.') && suffix.len() > 1 && !suffix.contains('*') => {
+                Ok(Self::Subdomain {
+                    scheme: scheme.to_string(),
+                    suffix: suffix.to_string(),
+                })
+            }
+            Some(_) => Err(format!(
+                "CORS origin '{value}' may only use a wildcard as its leftmost label, as in https://*.example.com"
+            )),
+            None if host.contains('*') => Err(format!(
+                "CORS origin '{value}' may only use a wildcard as its leftmost label, as in https://*.example.com"
+            )),
+            None => Ok(Self::Exact(origin)),
+        }
+    }
+
+    fn matches(&self, origin: &str) -> bool {
+        match self {
+            Self::Exact(allowed) => allowed.eq_ignore_ascii_case(origin),
+            Self::Subdomain { scheme, suffix } => {
+                let origin = origin.to_ascii_lowercase();
+                let Some(host) = origin
+                    .strip_prefix(scheme.as_str())
+                    .and_then(|rest| rest.strip_prefix("://"))
+                else {
+                    return false;
+                };
+                host.strip_suffix(suffix.as_str()).is_some_and(|sub| {
+                    !sub.is_empty()
+                        && !sub.starts_with('.')
+                        && !sub.ends_with('.')
+                        && sub
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+                })
+            }
+        }
+    }
+}
+
+/// Whether `value` is a valid allowed origin, exact or wildcard.
+pub(crate) fn is_valid_origin(value: &str) -> bool {
+    OriginPattern::parse(value).is_ok()
+}
+
+/// Whether `value` is a valid public path prefix.
+pub(crate) fn is_valid_public_path(value: &str) -> bool {
+    let prefix = value.trim().trim_end_matches('/');
+    prefix.starts_with('/') && prefix.len() >= 2
+}
+
+/// Allowed origins and the relaxed public path prefixes.
+#[derive(Debug, Clone, Default)]
+pub struct CorsPolicy {
+    origins: Vec<OriginPattern>,
+    public_prefixes: Vec<String>,
+}
+
+impl CorsPolicy {
+    /// The policy from `CORS_ALLOWED_ORIGINS` (falling back to `FRONTEND_URL`)
+    /// and `CORS_PUBLIC_PATHS`. Configuration loading already rejects invalid
+    /// entries, so this only fails for a config built by hand.
+    pub fn from_config(config: &AppConfig) -> Result<Self, String> {
+        let origins = if config.cors_allowed_origins.is_empty() {
+            std::slice::from_ref(&config.frontend_url)
+        } else {
+            config.cors_allowed_origins.as_slice()
+        };
+        let mut policy = Self {
+            origins: origins
+                .iter()
+                .map(|o| OriginPattern::parse(o))
+                .collect::<Result<_, _>>()?,
+            public_prefixes: Vec::new(),
+        };
+        for prefix in &config.cors_public_paths {
+            policy = policy.relax(prefix)?;
+        }
+        Ok(policy)
+    }
+
+    /// Open the route group under `prefix` to any origin, without credentials.
+    pub fn relax(mut self, prefix: &str) -> Result<Self, String> {
+        if !is_valid_public_path(prefix) {
+            return Err(format!(
+                "CORS public path '{prefix}' must be an absolute path prefix such as /api/v1/webhooks"
+            ));
+        }
+        let prefix = prefix.trim().trim_end_matches('/');
+        self.public_prefixes.push(prefix.to_string());
+        Ok(self)
+    }
+
+    fn is_public(&self, path: &str) -> bool {
+        self.public_prefixes.iter().any(|prefix| {
+            path.strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    fn allows(&self, origin: &str, path: &str) -> bool {
+        self.is_public(path) || self.origins.iter().any(|p| p.matches(origin))
+    }
+
+    /// The CORS layer enforcing the policy.
+    pub fn layer(self) -> CorsLayer {
+        let policy = Arc::new(self);
+        let origin_policy = Arc::clone(&policy);
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::predicate(
+                move |origin: &HeaderValue, parts: &Parts| {
+                    origin
+                        .to_str()
+                        .is_ok_and(|o| origin_policy.allows(o, parts.uri.path()))
+                },
+            ))
+            .allow_credentials(AllowCredentials::predicate(
+                move |_origin: &HeaderValue, parts: &Parts| !policy.is_public(parts.uri.path()),
+            ))
+            .allow_methods([
+                Method::GET,
+                Method::POST,
+                Method::PUT,
+                Method::PATCH,
+                Method::DELETE,
+            ])
+            .allow_headers([
+                header::CONTENT_TYPE,
+                header::AUTHORIZATION,
+                header::ACCEPT,
+                header::IF_NONE_MATCH,
+                REQUEST_ID_HEADER.clone(),
+            ])
+            .expose_headers([
+                header::ETAG,
+                header::RETRY_AFTER,
+                HeaderName::from_static("x-ratelimit-limit"),
+                HeaderName::from_static("x-ratelimit-remaining"),
+                HeaderName::from_static("x-ratelimit-reset"),
+                REQUEST_ID_HEADER.clone(),
+            ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(origins: &[&str]) -> CorsPolicy {
+        CorsPolicy {
+            origins: origins
+                .iter()
+                .map(|o| OriginPattern::parse(o).unwrap())
+                .collect(),
+            public_prefixes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn exact_origins_match_ignoring_case_and_trailing_slash() {
+        let policy = policy(&["https://App.example.com/", "http://localhost:5173"]);
+        assert!(policy.allows("https://app.example.com", "/api/v1/findings"));
+        assert!(policy.allows("http://localhost:5173", "/api/v1/findings"));
+        assert!(!policy.allows("http://app.example.com", "/api/v1/findings"));
+        assert!(!policy.allows("https://app.example.com.evil.io", "/api/v1/findings"));
+    }
+
+    #[test]
+    fn wildcard_patterns_match_subdomains_only() {
+        let policy = policy(&["https://*.example.com"]);
+        assert!(policy.allows("https://app.example.com", "/"));
+        assert!(policy.allows("https://eu.app.example.com", "/"));
+        assert!(!policy.allows("https://example.com", "/"));
+        assert!(!policy.allows("https://evilexample.com", "/"));
+        assert!(!policy.allows("http://app.example.com", "/"));
+        assert!(!policy.allows("https://app.example.com:8443", "/"));
+        assert!(!policy.allows("https://a@b.example.com", "/"));
+    }
+
+    #[test]
+    fn malformed_origins_are_rejected() {
+        for origin in [
+            "app.example.com",
+            "ftp://app.example.com",
+            "https://app.*.com",
+            "https://*example.com",
+            "https://app.example.com/path",
+            "https://",
+        ] {
+            assert!(OriginPattern::parse(origin).is_err(), "{origin}");
+        }
+    }
+
+    #[test]
+    fn public_prefixes_accept_any_origin() {
+        let policy = policy(&["https://app.example.com"])
+            .relax("/api/v1/webhooks/")
+            .unwrap();
+        assert!(policy.allows("https://tracker.io", "/api/v1/webhooks"));
+        assert!(policy.allows("https://tracker.io", "/api/v1/webhooks/jira"));
+        assert!(!policy.allows("https://tracker.io", "/api/v1/webhooks-admin"));
+        assert!(!policy.allows("https://tracker.io", "/api/v1/findings"));
+        assert!(policy.clone().relax("webhooks").is_err());
+    }
+}
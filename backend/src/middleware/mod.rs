@@ -1,4 +1,13 @@
-//! Middleware for authentication and authorization.
+//! Middleware and extractors for authentication, authorization, CORS, rate
+//! limiting, request timeouts and body limits, request validation, request
+//! auditing and correlation IDs, and error formatting.
 
 pub mod auth;
+pub mod cors;
+pub mod problem_details;
+pub mod rate_limit;
 pub mod rbac;
+pub mod request_audit;
+pub mod request_id;
+pub mod request_limits;
+pub mod validation;
@@ -0,0 +1,215 @@
+//! Optional RFC 7807 `application/problem+json` error responses.
+//!
+//! Handlers always produce the `{data, error}` envelope. This layer rewrites
+//! error envelopes into problem details when the client sends
+//! `Accept: application/problem+json`, or for every client when
+//! `PROBLEM_JSON_ERRORS` is set. Other headers (`Retry-After`, rate limit
+//! headers) are kept; successful responses pass through untouched.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::errors::FieldError;
+
+/// Media type of RFC 7807 problem details.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Largest error body that is rewritten; larger bodies pass through.
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+/// RFC 7807 problem details, with the envelope's error code and field errors
+/// as extension members.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProblemDetails {
+    /// URN identifying the problem type, derived from `code`.
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// Short summary of the problem type (the HTTP reason phrase).
+    pub title: String,
+    pub status: u16,
+    /// Explanation specific to this occurrence.
+    pub detail: String,
+    /// Request path that produced the problem.
+    pub instance: String,
+    /// Error code from the standard envelope, e.g. `VALIDATION_ERROR`.
+    pub code: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+    /// ID of the failed request, as in the `X-Request-Id` response header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl ProblemDetails {
+    /// Build problem details from an error envelope body, or `None` when the
+    /// body is not an error envelope.
+    fn from_envelope(status: StatusCode, body: &[u8], instance: &str) -> Option<Self> {
+        let envelope: serde_json::Value = serde_json::from_slice(body).ok()?;
+        let error = envelope.get("error")?.as_object()?;
+        let code = error.get("code")?.as_str()?.to_string();
+        let detail = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let errors = error
+            .get("fields")
+            .and_then(|f| f.as_array())
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(|f| {
+                        Some(FieldError {
+                            field: f.get("field")?.as_str()?.to_string(),
+                            message: f.get("message")?.as_str()?.to_string(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let request_id = error
+            .get("request_id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string);
+
+        Some(Self {
+            problem_type: format!(
+                "urn:synapsec:problem:{}",
+                code.to_ascii_lowercase().replace('_', "-")
+            ),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail,
+            instance: instance.to_string(),
+            code,
+            errors,
+            request_id,
+        })
+    }
+}
+
+/// Whether the `Accept` header lists `application/problem+json`.
+fn accepts_problem_json(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|range| {
+            range
+                .split(';')
+                .next()
+                .is_some_and(|media| media.trim().eq_ignore_ascii_case(PROBLEM_JSON))
+        })
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
+
+/// Rewrite error envelopes as problem details when requested.
+///
+/// The state is the configured default: `true` converts errors for every
+/// client, `false` only for clients that ask for problem details.
+pub async fn negotiate(State(always): State<bool>, request: Request, next: Next) -> Response {
+    let wanted = always || accepts_problem_json(request.headers());
+    let instance = request.uri().path().to_string();
+    let response = next.run(request).await;
+
+    let status = response.status();
+    if !wanted
+        || !(status.is_client_error() || status.is_server_error())
+        || !is_json(response.headers())
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_ERROR_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!(error = %e, "Could not buffer error body for problem details");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let Some(problem) = ProblemDetails::from_envelope(status, &bytes, &instance) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let Ok(json) = serde_json::to_vec(&problem) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    Response::from_parts(parts, Body::from(json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_problem_json_in_accept_lists() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_problem_json(&headers));
+
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("application/json, application/problem+json;q=0.9"),
+        );
+        assert!(accepts_problem_json(&headers));
+
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+        assert!(!accepts_problem_json(&headers));
+    }
+
+    #[test]
+    fn converts_error_envelope_with_field_errors() {
+        let body = serde_json::json!({
+            "data": null,
+            "error": {
+                "code": "VALIDATION_ERROR",
+                "message": "One or more fields are invalid",
+                "fields": [{"field": "title", "message": "must have a length between 1 and 1000"}],
+                "request_id": "req-42"
+            }
+        });
+        let problem = ProblemDetails::from_envelope(
+            StatusCode::BAD_REQUEST,
+            &serde_json::to_vec(&body).unwrap(),
+            "/api/v1/findings",
+        )
+        .unwrap();
+
+        assert_eq!(
+            problem.problem_type,
+            "urn:synapsec:problem:validation-error"
+        );
+        assert_eq!(problem.title, "Bad Request");
+        assert_eq!(problem.status, 400);
+        assert_eq!(problem.instance, "/api/v1/findings");
+        assert_eq!(problem.errors.len(), 1);
+        assert_eq!(problem.errors[0].field, "title");
+        assert_eq!(problem.request_id.as_deref(), Some("req-42"));
+    }
+
+    #[test]
+    fn ignores_bodies_that_are_not_error_envelopes() {
+        assert!(ProblemDetails::from_envelope(StatusCode::NOT_FOUND, b"not json", "/").is_none());
+        assert!(
+            ProblemDetails::from_envelope(StatusCode::NOT_FOUND, br#"{"data": 1}"#, "/").is_none()
+        );
+    }
+}
@@ -0,0 +1,443 @@
+//! Redis-backed token-bucket rate limiting per user or API key.
+//!
+//! Each route group has its own bucket per caller, shared across backend
+//! instances through Redis. Callers are keyed by their authenticated user or
+//! upload token, else by client address: the peer address, or the
+//! `X-Forwarded-For` client when the peer is a configured trusted proxy. Responses carry `X-RateLimit-*` headers and
+//! exhausted buckets are answered with `429 Too Many Requests`. When Redis is
+//! unreachable requests are let through, so an outage of the limiter never
+//! takes the API down with it.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderValue},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use sqlx::PgPool;
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::services::auth as auth_service;
+use crate::services::redis_store::RedisStore;
+use crate::services::upload_token;
+use crate::AppState;
+
+/// Refill the bucket for the elapsed time, then take one token if available.
+/// Returns `{allowed, tokens_left}`; tokens are returned as a string because
+/// Lua numbers are truncated to integers on the way out.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill_per_ms = tonumber(ARGV[2])
+local now_ms = tonumber(ARGV[3])
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(bucket[1]) or capacity
+local ts = tonumber(bucket[2]) or now_ms
+tokens = math.min(capacity, tokens + math.max(0, now_ms - ts) * refill_per_ms)
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now_ms)
+redis.call('PEXPIRE', KEYS[1], ARGV[4])
+return {allowed, tostring(tokens)}
+"#;
+
+/// Route groups with separately configured limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteGroup {
+    /// Scanner file uploads.
+    Ingestion,
+    /// Finding listing, search, and export.
+    Search,
+}
+
+impl RouteGroup {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Ingestion => "ingestion",
+            Self::Search => "search",
+        }
+    }
+}
+
+/// Token bucket size and refill rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketConfig {
+    /// Maximum burst, and the figure reported in `X-RateLimit-Limit`.
+    pub capacity: u32,
+    pub refill_per_sec: f64,
+}
+
+impl BucketConfig {
+    /// A bucket allowing `limit` requests per minute with bursts up to `limit`.
+    pub fn per_minute(limit: u32) -> Self {
+        let capacity = limit.max(1);
+        Self {
+            capacity,
+            refill_per_sec: f64::from(capacity) / 60.0,
+        }
+    }
+
+    /// Milliseconds for an empty bucket to fill, used as the key expiry.
+    fn fill_time_ms(&self) -> u64 {
+        (f64::from(self.capacity) / self.refill_per_sec * 1000.0).ceil() as u64
+    }
+}
+
+/// Outcome of taking a token, with the values for the response headers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Seconds until the bucket is full again.
+    pub reset_secs: u64,
+    /// Seconds until the next token; zero when the request was allowed.
+    pub retry_after_secs: u64,
+}
+
+impl Decision {
+    fn from_bucket(bucket: BucketConfig, allowed: bool, tokens_left: f64) -> Self {
+        let tokens_left = tokens_left.clamp(0.0, f64::from(bucket.capacity));
+        let reset_secs =
+            ((f64::from(bucket.capacity) - tokens_left) / bucket.refill_per_sec).ceil() as u64;
+        let retry_after_secs = if allowed {
+            0
+        } else {
+            ((1.0 - tokens_left) / bucket.refill_per_sec)
+                .ceil()
+                .max(1.0) as u64
+        };
+        Self {
+            allowed,
+            limit: bucket.capacity,
+            remaining: tokens_left.floor() as u32,
+            reset_secs,
+            retry_after_secs,
+        }
+    }
+
+    fn apply_headers(&self, headers: &mut HeaderMap) {
+        headers.insert("x-ratelimit-limit", HeaderValue::from(self.limit));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from(self.remaining));
+        headers.insert("x-ratelimit-reset", HeaderValue::from(self.reset_secs));
+    }
+}
+
+/// Shared limiter keeping its buckets in Redis.
+#[derive(Clone)]
+pub struct RateLimiter {
+    redis: RedisStore,
+    db: PgPool,
+    script: Arc<redis::Script>,
+    jwt_secret: String,
+    trusted_proxies: Vec<IpAddr>,
+    ingestion: BucketConfig,
+    search: BucketConfig,
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("ingestion", &self.ingestion)
+            .field("search", &self.search)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RateLimiter {
+    /// Build the limiter, or `None` when rate limiting is disabled or Redis
+    /// is not configured.
+    pub fn from_config(
+        config: &AppConfig,
+        redis: Option<&RedisStore>,
+        db: &PgPool,
+    ) -> Option<Self> {
+        if !config.rate_limit_enabled {
+            return None;
+        }
+        let Some(redis) = redis else {
+            tracing::error!("Rate limiting enabled without a usable REDIS_URL; disabled");
+            return None;
+        };
+        Some(Self {
+            redis: redis.clone(),
+            db: db.clone(),
+            script: Arc::new(redis::Script::new(TOKEN_BUCKET_SCRIPT)),
+            jwt_secret: config.jwt_secret.clone(),
+            trusted_proxies: config.trusted_proxies.clone(),
+            ingestion: BucketConfig::per_minute(config.rate_limit_ingestion_per_minute),
+            search: BucketConfig::per_minute(config.rate_limit_search_per_minute),
+        })
+    }
+
+    fn bucket(&self, group: RouteGroup) -> BucketConfig {
+        match group {
+            RouteGroup::Ingestion => self.ingestion,
+            RouteGroup::Search => self.search,
+        }
+    }
+
+    /// Take a token from the caller's bucket for the group.
+    pub async fn check(&self, group: RouteGroup, subject: &str) -> redis::RedisResult<Decision> {
+        let bucket = self.bucket(group);
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        let mut conn = self.redis.connection().await?;
+        let result: redis::RedisResult<(i64, String)> = self
+            .script
+            .key(format!("ratelimit:{}:{subject}", group.name()))
+            .arg(bucket.capacity)
+            .arg(bucket.refill_per_sec / 1000.0)
+            .arg(now_ms)
+            .arg(bucket.fill_time_ms())
+            .invoke_async(&mut conn)
+            .await;
+
+        match result {
+            Ok((allowed, tokens_left)) => Ok(Decision::from_bucket(
+                bucket,
+                allowed == 1,
+                tokens_left.parse().unwrap_or(0.0),
+            )),
+            Err(e) => {
+                // Drop the connection so the next request reconnects.
+                self.redis.reset().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Bucket key for the caller: the authenticated user, else the active
+    /// upload token, else the client address. Unauthenticated credentials
+    /// are ignored so callers cannot spread requests over invented keys.
+    async fn subject(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> String {
+        let bearer = headers
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        if let Some(claims) =
+            bearer.and_then(|token| auth_service::validate_token(token, &self.jwt_secret).ok())
+        {
+            return format!("user:{}", claims.user_id);
+        }
+        let key = headers
+            .get("X-API-Key")
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| !v.trim().is_empty());
+        if let Some(key) = key {
+            match upload_token::active_token_id(&self.db, key).await {
+                Ok(Some(token_id)) => return format!("token:{token_id}"),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(error = %e, "Upload token lookup failed; limiting by address")
+                }
+            }
+        }
+        client_addr(headers, peer, &self.trusted_proxies)
+            .map(|ip| format!("ip:{ip}"))
+            .unwrap_or_else(|| "anonymous".to_string())
+    }
+}
+
+/// The client address: the peer, unless it is a trusted proxy, in which case
+/// the nearest `X-Forwarded-For` hop that is not itself a trusted proxy.
+/// Hops beyond that one, or beyond a malformed hop, are client-supplied and
+/// ignored.
+pub(crate) fn client_addr(
+    headers: &HeaderMap,
+    peer: Option<IpAddr>,
+    trusted: &[IpAddr],
+) -> Option<IpAddr> {
+    let mut client = peer?;
+    let hops: Vec<&str> = headers
+        .get_all("X-Forwarded-For")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .collect();
+    for hop in hops.iter().rev() {
+        if !trusted.contains(&client) {
+            break;
+        }
+        match hop.trim().parse() {
+            Ok(addr) => client = addr,
+            Err(_) => break,
+        }
+    }
+    Some(client)
+}
+
+/// Add the rate limit layer for `group` to a router; a no-op without a limiter.
+pub fn apply(
+    router: Router<AppState>,
+    limiter: Option<&RateLimiter>,
+    group: RouteGroup,
+) -> Router<AppState> {
+    match limiter {
+        Some(limiter) => router.route_layer(middleware::from_fn_with_state(
+            (limiter.clone(), group),
+            limit,
+        )),
+        None => router,
+    }
+}
+
+async fn limit(
+    State((limiter, group)): State<(RateLimiter, RouteGroup)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let subject = limiter.subject(request.headers(), peer).await;
+    let decision = match limiter.check(group, &subject).await {
+        Ok(decision) => decision,
+        Err(e) => {
+            tracing::warn!(error = %e, group = group.name(), "Rate limiter unavailable; allowing request");
+            return next.run(request).await;
+        }
+    };
+
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        AppError::RateLimited {
+            retry_after_secs: decision.retry_after_secs,
+        }
+        .into_response()
+    };
+    decision.apply_headers(response.headers_mut());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_minute_bucket_refills_over_a_minute() {
+        let bucket = BucketConfig::per_minute(120);
+        assert_eq!(bucket.capacity, 120);
+        assert_eq!(bucket.refill_per_sec, 2.0);
+        assert_eq!(bucket.fill_time_ms(), 60_000);
+        assert_eq!(BucketConfig::per_minute(0).capacity, 1);
+    }
+
+    #[test]
+    fn allowed_decision_reports_remaining_and_reset() {
+        let decision = Decision::from_bucket(BucketConfig::per_minute(60), true, 57.4);
+        assert!(decision.allowed);
+        assert_eq!(decision.limit, 60);
+        assert_eq!(decision.remaining, 57);
+        assert_eq!(decision.reset_secs, 3);
+        assert_eq!(decision.retry_after_secs, 0);
+    }
+
+    #[test]
+    fn denied_decision_waits_for_next_token() {
+        let decision = Decision::from_bucket(BucketConfig::per_minute(30), false, 0.25);
+        assert!(!decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        // Half a token per second: 0.75 tokens missing takes 1.5 s.
+        assert_eq!(decision.retry_after_secs, 2);
+    }
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn subject_ignores_unauthenticated_credentials() {
+        let limiter = RateLimiter {
+            redis: RedisStore::new(redis::Client::open("redis://localhost:6379").unwrap()),
+            // Never connected: upload token lookups fail.
+            db: sqlx::postgres::PgPoolOptions::new()
+                .acquire_timeout(std::time::Duration::from_millis(100))
+                .connect_lazy("postgres://synapsec@127.0.0.1:1/unreachable")
+                .unwrap(),
+            script: Arc::new(redis::Script::new(TOKEN_BUCKET_SCRIPT)),
+            jwt_secret: "secret".to_string(),
+            trusted_proxies: Vec::new(),
+            ingestion: BucketConfig::per_minute(30),
+            search: BucketConfig::per_minute(300),
+        };
+        let mut headers = HeaderMap::new();
+        assert_eq!(limiter.subject(&headers, None).await, "anonymous");
+
+        // Forwarded addresses from an untrusted peer are not believed.
+        headers.insert("X-Forwarded-For", HeaderValue::from_static("10.0.0.5"));
+        assert_eq!(
+            limiter.subject(&headers, Some(ip("203.0.113.9"))).await,
+            "ip:203.0.113.9"
+        );
+
+        // Neither an invalid bearer token nor an unverified API key
+        // identifies the caller.
+        headers.insert("X-API-Key", HeaderValue::from_static("invented-key"));
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_static("Bearer not-a-jwt"),
+        );
+        assert_eq!(
+            limiter.subject(&headers, Some(ip("203.0.113.9"))).await,
+            "ip:203.0.113.9"
+        );
+    }
+
+    #[test]
+    fn client_addr_walks_forwarded_hops_through_trusted_proxies() {
+        let trusted = [ip("10.0.0.1"), ip("10.0.0.2")];
+        let mut headers = HeaderMap::new();
+        assert_eq!(client_addr(&headers, None, &trusted), None);
+        assert_eq!(
+            client_addr(&headers, Some(ip("10.0.0.1")), &trusted),
+            Some(ip("10.0.0.1"))
+        );
+
+        // The leftmost hop is client-supplied; the nearest untrusted one wins.
+        headers.insert(
+            "X-Forwarded-For",
+            HeaderValue::from_static("198.51.100.7, 203.0.113.9, 10.0.0.2"),
+        );
+        assert_eq!(
+            client_addr(&headers, Some(ip("10.0.0.1")), &trusted),
+            Some(ip("203.0.113.9"))
+        );
+        assert_eq!(
+            client_addr(&headers, Some(ip("192.0.2.1")), &trusted),
+            Some(ip("192.0.2.1"))
+        );
+
+        // A malformed hop stops the walk at the last trusted proxy.
+        headers.insert(
+            "X-Forwarded-For",
+            HeaderValue::from_static("203.0.113.9, spoofed"),
+        );
+        assert_eq!(
+            client_addr(&headers, Some(ip("10.0.0.1")), &trusted),
+            Some(ip("10.0.0.1"))
+        );
+    }
+
+    #[test]
+    fn decision_headers() {
+        let mut headers = HeaderMap::new();
+        Decision::from_bucket(BucketConfig::per_minute(10), true, 9.0).apply_headers(&mut headers);
+        assert_eq!(headers["x-ratelimit-limit"], "10");
+        assert_eq!(headers["x-ratelimit-remaining"], "9");
+        assert_eq!(headers["x-ratelimit-reset"], "6");
+    }
+}
@@ -8,6 +8,7 @@ use axum::{
 use crate::errors::AppError;
 use crate::middleware::auth::CurrentUser;
 use crate::models::user::UserRole;
+use crate::services::upload_token::{self, UploadScope};
 use crate::AppState;
 
 /// Extractor that requires the user to have Platform_Admin role.
@@ -74,3 +75,32 @@ impl FromRequestParts<AppState> for RequireAnalyst {
         }
     }
 }
+
+/// Extractor for scanner uploads: an upload token in the `X-API-Key` header,
+/// or else a Platform_Admin or AppSec_Manager session.
+#[derive(Debug, Clone)]
+pub enum RequireUploader {
+    Manager(CurrentUser),
+    Token(UploadScope),
+}
+
+impl FromRequestParts<AppState> for RequireUploader {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let key = parts
+            .headers
+            .get("x-api-key")
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| !v.trim().is_empty());
+        if let Some(key) = key {
+            let scope = upload_token::authenticate(&state.db, key).await?;
+            return Ok(RequireUploader::Token(scope));
+        }
+        let RequireManager(user) = RequireManager::from_request_parts(parts, state).await?;
+        Ok(RequireUploader::Manager(user))
+    }
+}
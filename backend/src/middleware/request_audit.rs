@@ -0,0 +1,286 @@
+//! Audit trail of mutating API calls.
+//!
+//! Every `POST`, `PUT`, `PATCH`, and `DELETE` under `/api/v1` is written to
+//! `audit_log` with `entity_type = 'api_request'`: method, route, actor,
+//! response status, latency, request ID, and a summary of the JSON body.
+//! Calls made with an upload token are attributed to the user who issued it,
+//! with the token's id in the details. The client address is resolved
+//! through `TRUSTED_PROXIES` the same way as for rate limiting.
+//! Secrets are redacted and long values are reduced to their size. This
+//! complements the entity-level entries services already write; read-only POST
+//! endpoints and credential exchanges are skipped. Disable with
+//! `REQUEST_AUDIT_ENABLED=false`.
+
+use std::net::SocketAddr;
+use std::time::Instant;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{header, HeaderMap, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::middleware::rate_limit::client_addr;
+use crate::middleware::request_id;
+use crate::models::audit::CreateAuditLog;
+use crate::services::audit_log;
+use crate::services::auth as auth_service;
+use crate::services::upload_token;
+use crate::AppState;
+
+/// `entity_type` of request audit entries.
+pub const API_REQUEST_ENTITY: &str = "api_request";
+
+/// Largest declared JSON body that is buffered for the summary.
+const MAX_SUMMARIZED_BODY_BYTES: usize = 256 * 1024;
+
+/// String values longer than this are replaced by their length.
+const MAX_LOGGED_VALUE_CHARS: usize = 120;
+
+/// POST routes that only read data or exchange credentials.
+const SKIPPED_ROUTES: &[&str] = &[
+    "/api/v1/auth/login",
+    "/api/v1/auth/refresh",
+    "/api/v1/graphql",
+    "/api/v1/findings/batch-get",
+];
+
+/// Substrings of body keys whose values are never logged.
+const SENSITIVE_KEYS: &[&str] = &["password", "secret", "token", "api_key", "apikey"];
+
+fn is_mutating(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+/// Whether the body is JSON with a declared length small enough to buffer.
+fn is_small_json(headers: &HeaderMap) -> bool {
+    let json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    let small = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok())
+        .is_some_and(|len| len <= MAX_SUMMARIZED_BODY_BYTES);
+    json && small
+}
+
+/// Summarize a request body without logging secrets or large values.
+fn summarize_body(body: &Value) -> Value {
+    match body {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), summarize_field(key, value)))
+                .collect::<Map<_, _>>(),
+        ),
+        other => summarize_field("", other),
+    }
+}
+
+fn summarize_field(key: &str, value: &Value) -> Value {
+    let lower = key.to_ascii_lowercase();
+    if SENSITIVE_KEYS.iter().any(|s| lower.contains(s)) {
+        return Value::String("[redacted]".to_string());
+    }
+    match value {
+        Value::String(s) if s.chars().count() > MAX_LOGGED_VALUE_CHARS => {
+            Value::String(format!("[{} chars]", s.chars().count()))
+        }
+        Value::Array(items) => Value::String(format!("[{} items]", items.len())),
+        Value::Object(fields) => Value::String(format!("[{} fields]", fields.len())),
+        other => other.clone(),
+    }
+}
+
+/// First UUID path segment, taken as the affected entity.
+fn entity_id_from_path(path: &str) -> Option<Uuid> {
+    path.split('/').find_map(|segment| segment.parse().ok())
+}
+
+/// Actor from a valid bearer token, else `anonymous`.
+fn actor(headers: &HeaderMap, jwt_secret: &str) -> (Option<Uuid>, String) {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .and_then(|token| auth_service::validate_token(token, jwt_secret).ok())
+        .map(|claims| (claims.user_id.parse().ok(), claims.sub))
+        .unwrap_or_else(|| (None, "anonymous".to_string()))
+}
+
+/// The active upload token in `X-API-Key` and its issuing user, if any.
+async fn upload_token_user(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Option<upload_token::TokenUser> {
+    let key = headers
+        .get("X-API-Key")
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.trim().is_empty())?;
+    match upload_token::active_token_user(&state.db, key).await {
+        Ok(user) => user,
+        Err(e) => {
+            tracing::warn!(error = %e, "Upload token lookup failed; auditing as anonymous");
+            None
+        }
+    }
+}
+
+/// Record mutating requests in the audit log once the response is ready.
+///
+/// The entry is written in the background so the response is not delayed;
+/// a failed write is logged and never affects the request.
+pub async fn record(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    if !state.config.request_audit_enabled
+        || !is_mutating(request.method())
+        || !path.starts_with("/api/v1/")
+        || SKIPPED_ROUTES.contains(&path.as_str())
+    {
+        return next.run(request).await;
+    }
+
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string());
+    let (mut actor_id, mut actor_name) = actor(request.headers(), &state.config.jwt_secret);
+    let mut upload_token_id = None;
+    if actor_id.is_none() {
+        if let Some(user) = upload_token_user(&state, request.headers()).await {
+            actor_id = Some(user.user_id);
+            actor_name = user.username;
+            upload_token_id = Some(user.token_id);
+        }
+    }
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let ip_address = client_addr(request.headers(), peer, &state.config.trusted_proxies)
+        .map(|ip| ip.to_string());
+
+    // Buffer small JSON bodies for the summary and hand them on unchanged;
+    // larger or streamed bodies are logged without a summary.
+    let (request, summary) = if is_small_json(request.headers()) {
+        let (parts, body) = request.into_parts();
+        let bytes = match to_bytes(body, MAX_SUMMARIZED_BODY_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return AppError::Validation(format!("Could not read request body: {e}"))
+                    .into_response();
+            }
+        };
+        let summary = serde_json::from_slice::<Value>(&bytes)
+            .ok()
+            .map(|v| summarize_body(&v));
+        (Request::from_parts(parts, Body::from(bytes)), summary)
+    } else {
+        (request, None)
+    };
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let entry = CreateAuditLog {
+        entity_type: API_REQUEST_ENTITY.to_string(),
+        entity_id: entity_id_from_path(&path),
+        action: method.clone(),
+        actor_id,
+        actor_name,
+        details: Some(serde_json::json!({
+            "method": method,
+            "path": path,
+            "route": route,
+            "status": response.status().as_u16(),
+            "latency_ms": latency_ms,
+            "body": summary,
+            "request_id": request_id::current(),
+            "upload_token_id": upload_token_id,
+        })),
+        ip_address,
+    };
+    let pool = state.db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = audit_log::record(&pool, &entry).await {
+            tracing::warn!(error = %e, "Failed to record API request audit entry");
+        }
+    });
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_redacts_secrets_and_shortens_large_values() {
+        let body = serde_json::json!({
+            "status": "Closed",
+            "password": "hunter2",
+            "finding_ids": ["a", "b", "c"],
+            "description": "x".repeat(500),
+            "category_data": {"sast": {}},
+            "dry_run": true,
+        });
+        let summary = summarize_body(&body);
+        assert_eq!(summary["status"], "Closed");
+        assert_eq!(summary["password"], "[redacted]");
+        assert_eq!(summary["finding_ids"], "[3 items]");
+        assert_eq!(summary["description"], "[500 chars]");
+        assert_eq!(summary["category_data"], "[1 fields]");
+        assert_eq!(summary["dry_run"], true);
+    }
+
+    #[test]
+    fn entity_id_is_first_uuid_segment() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            entity_id_from_path(&format!("/api/v1/findings/{id}/status")),
+            Some(id)
+        );
+        assert_eq!(entity_id_from_path("/api/v1/findings/bulk/status"), None);
+    }
+
+    #[test]
+    fn only_small_json_bodies_are_buffered() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
+        assert!(!is_small_json(&headers));
+
+        headers.insert(header::CONTENT_LENGTH, "512".parse().unwrap());
+        assert!(is_small_json(&headers));
+
+        headers.insert(header::CONTENT_TYPE, "multipart/form-data".parse().unwrap());
+        assert!(!is_small_json(&headers));
+    }
+
+    #[test]
+    fn only_mutating_methods_are_audited() {
+        assert!(is_mutating(&Method::PATCH));
+        assert!(is_mutating(&Method::DELETE));
+        assert!(!is_mutating(&Method::GET));
+        assert!(!is_mutating(&Method::OPTIONS));
+    }
+
+    #[test]
+    fn unauthenticated_requests_are_attributed_to_anonymous() {
+        assert_eq!(
+            actor(&HeaderMap::new(), "secret"),
+            (None, "anonymous".to_string())
+        );
+    }
+}
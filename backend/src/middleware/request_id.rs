@@ -0,0 +1,96 @@
+//! Request correlation IDs.
+//!
+//! Every request gets an ID: the caller's `X-Request-Id` when it is a
+//! reasonable token (so IDs from a gateway carry through), otherwise a fresh
+//! UUID. The ID is echoed in the `X-Request-Id` response header, recorded on
+//! a `request` span wrapping all logs of the request, and added to error
+//! envelopes as `error.request_id` so a user-reported failure can be matched
+//! to the server logs.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// Header carrying the request ID in both directions.
+pub static REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest caller-supplied ID that is kept.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static CURRENT: RequestId;
+}
+
+/// The ID of the request being handled, also available as a request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// ID of the request handled by the current task; `None` outside a request
+/// (background jobs, tasks spawned from a handler).
+pub fn current() -> Option<String> {
+    CURRENT.try_with(|id| id.0.clone()).ok()
+}
+
+/// Whether a caller-supplied ID is safe to log and echo back.
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+/// Assign the request ID and run the rest of the stack under it. Must be the
+/// outermost layer so that every inner span and error sees the ID.
+pub async fn assign(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| is_valid(v))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    request.extensions_mut().insert(RequestId(id.clone()));
+
+    let span = tracing::info_span!("request", request_id = %id);
+    let mut response = CURRENT
+        .scope(RequestId(id.clone()), next.run(request))
+        .instrument(span)
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER.clone(), value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caller_ids_must_be_short_tokens() {
+        assert!(is_valid("3f2b9c4e-1d2a-4b7e-9a51-0c8e2f6d7a10"));
+        assert!(is_valid("gw:req_42.1"));
+        assert!(!is_valid(""));
+        assert!(!is_valid("id with spaces"));
+        assert!(!is_valid("id\r\nx-injected: 1"));
+        assert!(!is_valid(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+    }
+
+    #[tokio::test]
+    async fn current_id_is_scoped_to_the_request_task() {
+        assert_eq!(current(), None);
+        let inside = CURRENT
+            .scope(RequestId("req-1".to_string()), async { current() })
+            .await;
+        assert_eq!(inside.as_deref(), Some("req-1"));
+    }
+}
@@ -0,0 +1,94 @@
+//! Request timeouts and body size limits per route group.
+//!
+//! Upload, import and finding export groups get a long deadline and large
+//! bodies; every other group a short deadline and small bodies, so a client
+//! trickling a request cannot hold a connection open indefinitely. The
+//! deadline covers reading the body and running the handler; streamed
+//! response bodies (exports) are not cut off. A `DefaultBodyLimit` set on a
+//! route inside the group still takes precedence over the group's limit.
+
+use std::time::Duration;
+
+use axum::{
+    extract::{DefaultBodyLimit, Request, State},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+
+/// Deadline and body size cap applied to a route group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    pub timeout: Duration,
+    pub max_body_bytes: usize,
+}
+
+impl RequestLimits {
+    /// Limits for ordinary API routes.
+    pub fn standard(config: &AppConfig) -> Self {
+        Self {
+            timeout: Duration::from_secs(config.request_timeout_secs),
+            max_body_bytes: config.request_max_body_bytes,
+        }
+    }
+
+    /// Limits for upload, import and finding export routes.
+    pub fn upload(config: &AppConfig) -> Self {
+        Self {
+            timeout: Duration::from_secs(config.upload_timeout_secs),
+            max_body_bytes: config.ingestion_max_body_bytes,
+        }
+    }
+}
+
+/// Apply `limits` to every route of `router`.
+pub fn apply<S>(router: Router<S>, limits: RequestLimits) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(DefaultBodyLimit::max(limits.max_body_bytes))
+        .layer(middleware::from_fn_with_state(limits.timeout, deadline))
+}
+
+async fn deadline(State(timeout): State<Duration>, request: Request, next: Next) -> Response {
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(timeout_secs = timeout.as_secs(), "Request timed out");
+            AppError::Timeout(timeout.as_secs()).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::get};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn slow_requests_get_a_timeout_error() {
+        let router = Router::new().route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "done"
+            }),
+        );
+        let limits = RequestLimits {
+            timeout: Duration::from_millis(10),
+            max_body_bytes: 1024,
+        };
+        let app = apply(router, limits);
+
+        let response = app
+            .oneshot(Request::get("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+}
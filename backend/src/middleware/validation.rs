@@ -0,0 +1,36 @@
+//! JSON body extractor that runs `validator` rules before the handler.
+
+use axum::{
+    extract::{FromRequest, Request},
+    Json,
+};
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+use crate::errors::AppError;
+
+/// Deserialized and validated JSON request body.
+///
+/// Malformed JSON is rejected with a `VALIDATION_ERROR`; rule violations are
+/// rejected with the offending fields listed in `error.fields`:
+/// ```ignore
+/// async fn create(ValidatedJson(body): ValidatedJson<CreateApplication>) { ... }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| AppError::Validation(rejection.body_text()))?;
+        value.validate()?;
+        Ok(Self(value))
+    }
+}
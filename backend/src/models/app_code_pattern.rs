@@ -3,9 +3,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use validator::Validate;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct AppCodePattern {
     pub id: Uuid,
     pub source_tool: String,
@@ -17,3 +19,66 @@ pub struct AppCodePattern {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateAppCodePattern {
+    #[validate(length(min = 1, max = 100))]
+    pub source_tool: String,
+    #[validate(length(min = 1, max = 100))]
+    pub field_name: String,
+    /// Regex with an `app_code` named capture group.
+    #[validate(length(min = 1, max = 1000))]
+    pub regex_pattern: String,
+    /// Higher priorities are tried first; defaults to 0.
+    pub priority: Option<i32>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateAppCodePattern {
+    #[validate(length(min = 1, max = 100))]
+    pub field_name: Option<String>,
+    #[validate(length(min = 1, max = 1000))]
+    pub regex_pattern: Option<String>,
+    pub priority: Option<i32>,
+    pub description: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+/// New priority order of a source tool's patterns.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ReorderAppCodePatterns {
+    #[validate(length(min = 1, max = 100))]
+    pub source_tool: String,
+    /// Every pattern of the tool, the one to try first leading.
+    #[validate(length(min = 1))]
+    pub pattern_ids: Vec<Uuid>,
+}
+
+/// Filters for listing patterns.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AppCodePatternFilters {
+    pub source_tool: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+/// An ad-hoc pattern to try instead of the stored ones.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TestPattern {
+    pub field_name: String,
+    pub regex_pattern: String,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// A pattern set and a sample scanner metadata payload to run it against.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct TestAppCodePatterns {
+    #[validate(length(min = 1, max = 100))]
+    pub source_tool: String,
+    /// Finding metadata as the parser produces it; string fields are matched.
+    pub metadata: serde_json::Value,
+    /// Patterns to try; the tool's active stored patterns when omitted.
+    pub patterns: Option<Vec<TestPattern>>,
+}
@@ -3,9 +3,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::Validate;
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "asset_criticality")]
 pub enum AssetCriticality {
     #[sqlx(rename = "Very_High")]
@@ -22,7 +24,7 @@ pub enum AssetCriticality {
     Low,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash, ToSchema)]
 #[sqlx(type_name = "asset_tier")]
 pub enum AssetTier {
     #[sqlx(rename = "Tier_1")]
@@ -36,7 +38,7 @@ pub enum AssetTier {
     Tier3,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "exposure_level")]
 pub enum ExposureLevel {
     #[sqlx(rename = "Internet_Facing")]
@@ -50,7 +52,7 @@ pub enum ExposureLevel {
     DevTest,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "data_classification")]
 pub enum DataClassification {
     Public,
@@ -59,7 +61,7 @@ pub enum DataClassification {
     Restricted,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "app_status")]
 pub enum AppStatus {
     Active,
@@ -67,7 +69,7 @@ pub enum AppStatus {
     Decommissioned,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Application {
     pub id: Uuid,
     pub app_name: String,
@@ -88,6 +90,8 @@ pub struct Application {
     pub scanner_project_ids: serde_json::Value,
     pub status: AppStatus,
     pub is_verified: bool,
+    /// Code shipped outside the organization; copyleft license policies apply.
+    pub is_distributed: bool,
 
     // Corporate APM enrichment
     pub ssa_code: Option<String>,
@@ -109,16 +113,22 @@ pub struct Application {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateApplication {
+    #[validate(length(min = 1, max = 255))]
     pub app_name: String,
+    #[validate(length(min = 1, max = 10))]
     pub app_code: String,
     pub description: Option<String>,
     pub criticality: Option<AssetCriticality>,
     pub tier: Option<AssetTier>,
+    #[validate(length(max = 255))]
     pub business_unit: Option<String>,
+    #[validate(length(max = 255))]
     pub business_owner: Option<String>,
+    #[validate(length(max = 255))]
     pub technical_owner: Option<String>,
+    #[validate(length(max = 255))]
     pub security_champion: Option<String>,
     pub technology_stack: Option<Vec<String>>,
     pub exposure: Option<ExposureLevel>,
@@ -126,25 +136,31 @@ pub struct CreateApplication {
     pub repository_urls: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema, Validate)]
 pub struct UpdateApplication {
+    #[validate(length(min = 1, max = 255))]
     pub app_name: Option<String>,
     pub description: Option<String>,
     pub criticality: Option<AssetCriticality>,
     pub tier: Option<AssetTier>,
+    #[validate(length(max = 255))]
     pub business_unit: Option<String>,
+    #[validate(length(max = 255))]
     pub business_owner: Option<String>,
+    #[validate(length(max = 255))]
     pub technical_owner: Option<String>,
+    #[validate(length(max = 255))]
     pub security_champion: Option<String>,
     pub technology_stack: Option<Vec<String>>,
     pub exposure: Option<ExposureLevel>,
     pub data_classification: Option<DataClassification>,
     pub repository_urls: Option<Vec<String>>,
     pub status: Option<AppStatus>,
+    pub is_distributed: Option<bool>,
 }
 
 /// Summary DTO for list views.
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct ApplicationSummary {
     pub id: Uuid,
     pub app_name: String,
@@ -0,0 +1,60 @@
+//! Asset inventory models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash, ToSchema)]
+#[sqlx(type_name = "asset_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AssetType {
+    /// Host name or IP address.
+    Host,
+    /// Fully qualified DNS name.
+    DnsName,
+    /// URL; scanner URLs match by prefix.
+    Url,
+    /// Source repository or SonarQube project key.
+    Repository,
+    /// Build artifact (GAV coordinate or repository path); matches by prefix.
+    Artifact,
+}
+
+/// An inventoried asset, with the app code of its linked application.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Asset {
+    pub id: Uuid,
+    pub asset_type: AssetType,
+    pub identifier: String,
+    pub application_id: Option<Uuid>,
+    /// App code of the linked application.
+    pub app_code: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateAsset {
+    pub asset_type: AssetType,
+    #[validate(length(min = 1, max = 2048))]
+    pub identifier: String,
+    pub application_id: Option<Uuid>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateAsset {
+    #[validate(length(min = 1, max = 2048))]
+    pub identifier: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Link an asset to an application, or unlink it with `null`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LinkAsset {
+    pub application_id: Option<Uuid>,
+}
@@ -0,0 +1,23 @@
+//! Finding evidence attachment models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct FindingAttachment {
+    pub id: Uuid,
+    pub finding_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex-encoded SHA-256 of the file contents.
+    pub sha256: String,
+    /// Location in the storage backend; internal only.
+    #[serde(skip)]
+    pub storage_key: String,
+    pub uploaded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
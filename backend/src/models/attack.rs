@@ -0,0 +1,36 @@
+//! MITRE ATT&CK techniques and the CAPEC patterns mapping CWEs to them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+/// An ATT&CK technique or sub-technique.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AttackTechnique {
+    /// e.g. `T1110` or `T1110.004`
+    pub technique_id: String,
+    pub name: String,
+    /// Enterprise kill chain phases, e.g. `credential-access`.
+    pub tactics: Vec<String>,
+}
+
+/// A CAPEC attack pattern with the weaknesses it exploits.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CapecPattern {
+    pub capec_id: i32,
+    pub name: String,
+    pub cwe_ids: Vec<i32>,
+    pub technique_ids: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AttackImportResult {
+    /// Entries inserted or updated.
+    pub imported: u64,
+    /// Entries skipped for a missing or malformed id or name.
+    pub skipped: usize,
+    /// Findings whose technique ids were recomputed.
+    pub findings_remapped: u64,
+}
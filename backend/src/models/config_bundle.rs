@@ -0,0 +1,128 @@
+//! Portable configuration bundle for promoting setup between environments.
+//!
+//! Entities carry no database ids; import matches them by natural key
+//! (application code, rule name, pattern, setting key).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use crate::models::application::{
+    AppStatus, AssetCriticality, AssetTier, DataClassification, ExposureLevel,
+};
+use crate::models::finding::ConfidenceLevel;
+
+/// Bundle layout version written by export and accepted by import.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigBundle {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub applications: Vec<BundleApplication>,
+    #[serde(default)]
+    pub correlation_rules: Vec<BundleCorrelationRule>,
+    #[serde(default)]
+    pub app_code_patterns: Vec<BundleAppCodePattern>,
+    #[serde(default)]
+    pub triage_rules: Vec<BundleTriageRule>,
+    /// `system_config` settings: SLA matrix, tier mapping, risk weights, ...
+    #[serde(default)]
+    pub settings: Vec<BundleSetting>,
+}
+
+/// An application, matched on import by `app_code`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BundleApplication {
+    pub app_name: String,
+    pub app_code: String,
+    pub description: Option<String>,
+    pub criticality: Option<AssetCriticality>,
+    pub tier: AssetTier,
+    pub business_unit: Option<String>,
+    pub business_owner: Option<String>,
+    pub technical_owner: Option<String>,
+    pub security_champion: Option<String>,
+    pub technology_stack: serde_json::Value,
+    pub deployment_environment: serde_json::Value,
+    pub exposure: Option<ExposureLevel>,
+    pub data_classification: Option<DataClassification>,
+    pub regulatory_scope: serde_json::Value,
+    pub repository_urls: serde_json::Value,
+    pub scanner_project_ids: serde_json::Value,
+    pub status: AppStatus,
+    pub is_verified: bool,
+    pub ssa_code: Option<String>,
+    pub ssa_name: Option<String>,
+    pub functional_reference_email: Option<String>,
+    pub technical_reference_email: Option<String>,
+    pub effective_office_owner: Option<String>,
+    pub effective_office_name: Option<String>,
+    pub confidentiality_level: Option<String>,
+    pub integrity_level: Option<String>,
+    pub availability_level: Option<String>,
+    pub is_dora_fei: Option<bool>,
+    pub is_gdpr_subject: Option<bool>,
+    pub has_pci_data: Option<bool>,
+    pub is_psd2_relevant: Option<bool>,
+    pub apm_metadata: serde_json::Value,
+}
+
+/// A correlation rule, matched on import by `name`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BundleCorrelationRule {
+    pub name: String,
+    pub description: Option<String>,
+    pub rule_type: String,
+    pub conditions: serde_json::Value,
+    pub confidence: ConfidenceLevel,
+    pub is_active: bool,
+    pub priority: i32,
+}
+
+/// An app code pattern, matched on import by tool, field, and regex.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BundleAppCodePattern {
+    pub source_tool: String,
+    pub field_name: String,
+    pub regex_pattern: String,
+    pub priority: i32,
+    pub description: Option<String>,
+    pub is_active: bool,
+}
+
+/// A triage rule, matched on import by `name`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BundleTriageRule {
+    pub name: String,
+    pub description: Option<String>,
+    pub conditions: serde_json::Value,
+    pub is_active: bool,
+    pub priority: i32,
+}
+
+/// A `system_config` setting, matched on import by `key`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BundleSetting {
+    pub key: String,
+    pub value: serde_json::Value,
+    pub description: Option<String>,
+}
+
+/// Entities of one kind created and updated by an import.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ImportCounts {
+    pub created: u64,
+    pub updated: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct BundleImportResult {
+    pub applications: ImportCounts,
+    pub correlation_rules: ImportCounts,
+    pub app_code_patterns: ImportCounts,
+    pub triage_rules: ImportCounts,
+    pub settings: ImportCounts,
+}
@@ -3,11 +3,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::models::finding::ConfidenceLevel;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct CorrelationRule {
     pub id: Uuid,
     pub name: String,
@@ -22,20 +24,24 @@ pub struct CorrelationRule {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateCorrelationRule {
+    #[validate(length(min = 1, max = 255))]
     pub name: String,
     pub description: Option<String>,
+    #[validate(length(min = 1, max = 50))]
     pub rule_type: String,
     pub conditions: serde_json::Value,
     pub confidence: Option<ConfidenceLevel>,
     pub priority: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct UpdateCorrelationRule {
+    #[validate(length(min = 1, max = 255))]
     pub name: Option<String>,
     pub description: Option<String>,
+    #[validate(length(min = 1, max = 50))]
     pub rule_type: Option<String>,
     pub conditions: Option<serde_json::Value>,
     pub confidence: Option<ConfidenceLevel>,
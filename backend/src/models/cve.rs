@@ -0,0 +1,65 @@
+//! CVE catalog models: NVD details cached per CVE id.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::exploit::ExploitReference;
+use crate::models::finding::{FindingCategory, FindingStatus, SeverityLevel};
+
+/// A CVE as cached from NVD.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CveRecord {
+    pub cve_id: String,
+    /// False when NVD had no record for the id at the last fetch.
+    pub found: bool,
+    pub description: Option<String>,
+    /// CVSS v3.x vector, preferring NVD's primary score.
+    pub cvss_v3_vector: Option<String>,
+    pub cvss_v3_score: Option<f32>,
+    pub cvss_v3_severity: Option<String>,
+    pub cvss_v2_vector: Option<String>,
+    pub cvss_v2_score: Option<f32>,
+    pub cwe_ids: Vec<String>,
+    /// Array of [`CveReference`].
+    #[schema(value_type = Vec<CveReference>)]
+    pub reference_links: serde_json::Value,
+    pub published_at: Option<DateTime<Utc>>,
+    pub last_modified_at: Option<DateTime<Utc>>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// An advisory, patch, or exploit link for a CVE.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CveReference {
+    pub url: String,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A finding reporting a CVE.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CveFinding {
+    pub id: Uuid,
+    pub title: String,
+    pub finding_category: FindingCategory,
+    pub normalized_severity: SeverityLevel,
+    pub status: FindingStatus,
+    pub application_id: Option<Uuid>,
+    pub app_code: Option<String>,
+    pub first_seen: DateTime<Utc>,
+}
+
+/// A CVE with its cached NVD details and the findings reporting it.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CveDetail {
+    pub cve_id: String,
+    /// `None` until the enrichment service has fetched the CVE.
+    pub details: Option<CveRecord>,
+    /// Known public exploits, most mature first.
+    pub exploits: Vec<ExploitReference>,
+    pub findings: Vec<CveFinding>,
+}
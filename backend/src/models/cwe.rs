@@ -0,0 +1,60 @@
+//! CWE catalog models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+/// A CWE dictionary entry.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CweEntry {
+    pub cwe_id: i32,
+    pub name: String,
+    /// Pillar, Class, Base, Variant, or Compound.
+    pub abstraction: Option<String>,
+    pub status: Option<String>,
+    pub description: Option<String>,
+    /// ChildOf parents in the Research Concepts view (1000), primary first.
+    pub parent_ids: Vec<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A CWE id with its display label, e.g. `CWE-89: Improper Neutralization ...`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CweSummary {
+    pub cwe_id: i32,
+    pub name: String,
+    pub label: String,
+}
+
+/// A CWE with its place in the hierarchy.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CweDetail {
+    #[serde(flatten)]
+    pub entry: CweEntry,
+    pub label: String,
+    pub parents: Vec<CweSummary>,
+    pub children: Vec<CweSummary>,
+    /// Primary-parent chain from the direct parent up to the pillar.
+    pub ancestors: Vec<CweSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CweImportResult {
+    /// Entries inserted or updated.
+    pub imported: u64,
+    /// Rows skipped for a missing or malformed CWE id or name.
+    pub skipped: usize,
+}
+
+/// Open findings rolled up to the top of their CWE's primary-parent chain.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CweFamilyCount {
+    pub family_id: i32,
+    /// `None` when the CWE is not in the catalog.
+    pub family_name: Option<String>,
+    pub findings: i64,
+    /// The findings' own CWE ids within the family.
+    pub cwe_ids: Vec<i32>,
+}
+
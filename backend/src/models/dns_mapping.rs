@@ -0,0 +1,48 @@
+//! DNS name and URL prefix to application mapping models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, ToSchema)]
+#[sqlx(type_name = "dns_mapping_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DnsMappingKind {
+    /// Host name, exact or `*.`-prefixed wildcard.
+    DnsName,
+    /// URL prefix including the scheme.
+    UrlPrefix,
+}
+
+/// A mapping with the app code of its application.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct DnsMapping {
+    pub id: Uuid,
+    pub kind: DnsMappingKind,
+    pub pattern: String,
+    pub application_id: Uuid,
+    pub app_code: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateDnsMapping {
+    pub kind: DnsMappingKind,
+    #[validate(length(min = 1, max = 2048))]
+    pub pattern: String,
+    pub application_id: Uuid,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateDnsMapping {
+    #[validate(length(min = 1, max = 2048))]
+    pub pattern: Option<String>,
+    pub application_id: Option<Uuid>,
+    pub description: Option<String>,
+}
@@ -0,0 +1,29 @@
+//! Public exploit references from the Exploit-DB and Metasploit indexes.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use crate::models::finding_sca::ExploitMaturity;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash, ToSchema)]
+#[sqlx(type_name = "exploit_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ExploitSource {
+    ExploitDb,
+    Metasploit,
+}
+
+/// A public exploit for a CVE.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ExploitReference {
+    pub source: ExploitSource,
+    /// Exploit-DB id or Metasploit module path.
+    pub reference: String,
+    pub cve_id: String,
+    pub title: String,
+    pub maturity: ExploitMaturity,
+    pub url: Option<String>,
+    pub published_on: Option<NaiveDate>,
+}
@@ -3,20 +3,24 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::Validate;
 
 // -- Enums matching PostgreSQL --
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "finding_category", rename_all = "SCREAMING_SNAKE_CASE")]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum FindingCategory {
     Sast,
     Sca,
     Dast,
+    /// Penetration test or bug bounty result entered by hand.
+    Manual,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "finding_status")]
 pub enum FindingStatus {
     New,
@@ -42,7 +46,7 @@ pub enum FindingStatus {
     Invalidated,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "severity_level")]
 pub enum SeverityLevel {
     Critical,
@@ -53,6 +57,42 @@ pub enum SeverityLevel {
 }
 
 impl SeverityLevel {
+    /// Levels from most to least severe.
+    pub const ALL: [SeverityLevel; 5] = [
+        SeverityLevel::Critical,
+        SeverityLevel::High,
+        SeverityLevel::Medium,
+        SeverityLevel::Low,
+        SeverityLevel::Info,
+    ];
+
+    /// Label as stored in the database and serialized, e.g. `Critical`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Critical => "Critical",
+            Self::High => "High",
+            Self::Medium => "Medium",
+            Self::Low => "Low",
+            Self::Info => "Info",
+        }
+    }
+
+    /// Level for a label as returned by [`SeverityLevel::label`].
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.label() == label)
+    }
+
+    /// Sort rank, higher for more severe levels.
+    pub fn rank(&self) -> u8 {
+        match self {
+            Self::Critical => 4,
+            Self::High => 3,
+            Self::Medium => 2,
+            Self::Low => 1,
+            Self::Info => 0,
+        }
+    }
+
     /// Numeric weight for risk score calculation (0.0–1.0 scale).
     pub fn weight(&self) -> f32 {
         match self {
@@ -65,7 +105,13 @@ impl SeverityLevel {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+/// Sort rank of a severity label, higher for more severe levels; labels that
+/// are not a severity rank below `Info`.
+pub fn severity_rank(label: &str) -> u8 {
+    SeverityLevel::from_label(label).map_or(0, |s| s.rank() + 1)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "sla_status")]
 pub enum SlaStatus {
     #[sqlx(rename = "On_Track")]
@@ -77,7 +123,7 @@ pub enum SlaStatus {
     Breached,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "confidence_level")]
 pub enum ConfidenceLevel {
     High,
@@ -85,7 +131,7 @@ pub enum ConfidenceLevel {
     Low,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash, ToSchema)]
 #[sqlx(type_name = "relationship_type")]
 pub enum RelationshipType {
     #[sqlx(rename = "duplicate_of")]
@@ -104,7 +150,7 @@ pub enum RelationshipType {
 
 // -- Core Finding --
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Finding {
     pub id: Uuid,
     pub source_tool: String,
@@ -115,6 +161,14 @@ pub struct Finding {
     pub description: String,
     pub normalized_severity: SeverityLevel,
     pub original_severity: String,
+    /// Analyst override of the tool-derived `normalized_severity`.
+    pub severity_override: Option<SeverityLevel>,
+    pub severity_override_justification: Option<String>,
+    pub severity_override_by: Option<Uuid>,
+    pub severity_override_at: Option<DateTime<Utc>>,
+    /// The override when set, otherwise `normalized_severity`; filtering,
+    /// SLA and risk scoring go by it.
+    pub effective_severity: SeverityLevel,
     pub cvss_score: Option<f32>,
     pub cvss_vector: Option<String>,
     pub cwe_ids: serde_json::Value,
@@ -139,24 +193,41 @@ pub struct Finding {
     pub remediation_guidance: Option<String>,
     pub raw_finding: serde_json::Value,
     pub metadata: serde_json::Value,
+    /// Set when the finding was archived; archived findings are hidden from lists.
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Release in which the finding first appeared.
+    pub introduced_in_release_id: Option<Uuid>,
+    /// Release that fixed the finding.
+    pub fixed_in_release_id: Option<Uuid>,
+    /// ATT&CK technique ids derived from the CWEs (via CAPEC) and tags.
+    pub attack_technique_ids: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateFinding {
+    #[validate(length(min = 1, max = 100))]
     pub source_tool: String,
+    #[validate(length(max = 50))]
     pub source_tool_version: Option<String>,
+    #[validate(length(min = 1, max = 500))]
     pub source_finding_id: String,
     pub finding_category: FindingCategory,
+    #[validate(length(min = 1, max = 1000))]
     pub title: String,
     pub description: String,
     pub normalized_severity: SeverityLevel,
+    #[validate(length(min = 1, max = 100))]
     pub original_severity: String,
+    #[validate(range(min = 0.0, max = 10.0))]
     pub cvss_score: Option<f32>,
+    #[validate(length(max = 255))]
     pub cvss_vector: Option<String>,
     pub cwe_ids: Vec<String>,
     pub cve_ids: Vec<String>,
+    #[validate(length(max = 100))]
     pub owasp_category: Option<String>,
     pub confidence: Option<ConfidenceLevel>,
+    #[validate(length(min = 1, max = 128))]
     pub fingerprint: String,
     pub application_id: Option<Uuid>,
     pub tags: Vec<String>,
@@ -165,13 +236,16 @@ pub struct CreateFinding {
     pub metadata: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema, Validate)]
 pub struct UpdateFinding {
     pub normalized_severity: Option<SeverityLevel>,
     pub status: Option<FindingStatus>,
     pub application_id: Option<Uuid>,
+    #[validate(length(max = 255))]
     pub remediation_owner: Option<String>,
+    #[validate(length(max = 255))]
     pub office_owner: Option<String>,
+    #[validate(length(max = 255))]
     pub office_manager: Option<String>,
     pub sla_due_date: Option<DateTime<Utc>>,
     pub sla_status: Option<SlaStatus>,
@@ -181,13 +255,15 @@ pub struct UpdateFinding {
 }
 
 /// Response DTO excluding raw_finding for list views.
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct FindingSummary {
     pub id: Uuid,
     pub source_tool: String,
     pub finding_category: FindingCategory,
     pub title: String,
     pub normalized_severity: SeverityLevel,
+    /// Analyst override of the tool-derived severity.
+    pub severity_override: Option<SeverityLevel>,
     pub status: FindingStatus,
     pub composite_risk_score: Option<f32>,
     pub fingerprint: String,
@@ -202,7 +278,7 @@ pub struct FindingSummary {
 /// Only the fields relevant to the finding's category will be populated;
 /// all others will be `None`. Uses `#[serde(skip_serializing_if)]` to keep
 /// responses compact.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
 pub struct FindingCategoryData {
     // SAST fields
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -237,10 +313,16 @@ pub struct FindingCategoryData {
     pub parameter: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub web_application_name: Option<String>,
+
+    // Manual fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affected_asset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<String>,
 }
 
 /// Finding summary enriched with optional category-specific fields.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FindingSummaryWithCategory {
     #[serde(flatten)]
     pub summary: FindingSummary,
@@ -250,7 +332,7 @@ pub struct FindingSummaryWithCategory {
 
 // -- Finding Relationships --
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct FindingRelationship {
     pub id: Uuid,
     pub source_finding_id: Uuid,
@@ -264,7 +346,7 @@ pub struct FindingRelationship {
 
 // -- Finding History --
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct FindingHistory {
     pub id: Uuid,
     pub finding_id: Uuid,
@@ -280,7 +362,7 @@ pub struct FindingHistory {
 
 // -- Finding Comments --
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct FindingComment {
     pub id: Uuid,
     pub finding_id: Uuid,
@@ -289,10 +371,41 @@ pub struct FindingComment {
     pub content: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Comment this one replies to; `None` for a top-level comment.
+    pub parent_id: Option<Uuid>,
+    /// How `content` should be rendered.
+    pub content_format: CommentFormat,
+    /// Users mentioned as `@username` in the content.
+    pub mentioned_user_ids: Vec<Uuid>,
+    /// Set when the content was last edited.
+    pub edited_at: Option<DateTime<Utc>>,
+    /// Set when the comment was deleted; its content is then empty.
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, ToSchema)]
+#[sqlx(type_name = "comment_format", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum CommentFormat {
+    Plain,
+    #[default]
+    Markdown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateComment {
+    #[validate(length(min = 1, max = 10000))]
+    pub content: String,
+    /// Comment on the same finding to reply to.
+    pub parent_id: Option<Uuid>,
+    /// Defaults to markdown.
+    #[serde(default)]
+    pub content_format: CommentFormat,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateComment {
+    #[validate(length(min = 1, max = 10000))]
     pub content: String,
 }
 
@@ -300,6 +413,19 @@ pub struct CreateComment {
 mod tests {
     use super::*;
 
+    #[test]
+    fn severity_labels_round_trip_in_rank_order() {
+        for pair in SeverityLevel::ALL.windows(2) {
+            assert!(pair[0].rank() > pair[1].rank());
+            assert!(severity_rank(pair[0].label()) > severity_rank(pair[1].label()));
+        }
+        for level in SeverityLevel::ALL {
+            assert_eq!(SeverityLevel::from_label(level.label()), Some(level));
+        }
+        assert_eq!(severity_rank("Unknown"), 0);
+        assert!(severity_rank("Info") > 0);
+    }
+
     #[test]
     fn finding_status_serialization() {
         let status = FindingStatus::InRemediation;
@@ -376,6 +502,7 @@ mod tests {
             finding_category: FindingCategory::Sast,
             title: "SQL Injection".to_string(),
             normalized_severity: SeverityLevel::High,
+            severity_override: None,
             status: FindingStatus::New,
             composite_risk_score: Some(85.0),
             fingerprint: "abc123".to_string(),
@@ -407,6 +534,7 @@ mod tests {
                 finding_category: FindingCategory::Sast,
                 title: "XSS".to_string(),
                 normalized_severity: SeverityLevel::Medium,
+                severity_override: None,
                 status: FindingStatus::Confirmed,
                 composite_risk_score: None,
                 fingerprint: "def456".to_string(),
@@ -446,6 +574,7 @@ mod tests {
                 finding_category: FindingCategory::Sca,
                 title: "CVE-2024-1234".to_string(),
                 normalized_severity: SeverityLevel::Critical,
+                severity_override: None,
                 status: FindingStatus::New,
                 composite_risk_score: Some(95.0),
                 fingerprint: "ghi789".to_string(),
@@ -483,6 +612,7 @@ mod tests {
                 finding_category: FindingCategory::Dast,
                 title: "Reflected XSS".to_string(),
                 normalized_severity: SeverityLevel::High,
+                severity_override: None,
                 status: FindingStatus::New,
                 composite_risk_score: None,
                 fingerprint: "jkl012".to_string(),
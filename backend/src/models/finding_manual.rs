@@ -0,0 +1,46 @@
+//! Manual (penetration test and bug bounty) finding layer model.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, ToSchema)]
+#[sqlx(type_name = "manual_finding_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ManualFindingSource {
+    Pentest,
+    BugBounty,
+}
+
+impl ManualFindingSource {
+    /// Name recorded as the finding's source tool.
+    pub fn source_tool(self) -> &'static str {
+        match self {
+            Self::Pentest => "Pentest",
+            Self::BugBounty => "Bug Bounty",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct FindingManual {
+    pub finding_id: Uuid,
+    pub source: ManualFindingSource,
+    pub affected_asset: String,
+    pub evidence: Option<String>,
+    pub reporter: String,
+    pub engagement: Option<String>,
+    pub reported_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateFindingManual {
+    pub source: ManualFindingSource,
+    pub affected_asset: String,
+    pub evidence: Option<String>,
+    pub reporter: String,
+    pub engagement: Option<String>,
+    pub reported_at: Option<DateTime<Utc>>,
+}
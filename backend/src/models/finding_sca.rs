@@ -1,17 +1,19 @@
 //! SCA-specific finding layer model.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "dependency_type")]
 pub enum DependencyType {
     Direct,
     Transitive,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "exploit_maturity")]
 pub enum ExploitMaturity {
     #[sqlx(rename = "Proof_of_Concept")]
@@ -21,7 +23,7 @@ pub enum ExploitMaturity {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct FindingSca {
     pub finding_id: Uuid,
     pub package_name: String,
@@ -38,9 +40,27 @@ pub struct FindingSca {
     pub exploit_maturity: Option<ExploitMaturity>,
     pub affected_artifact: Option<String>,
     pub build_project: Option<String>,
+    /// Array of [`AffectedRange`] from OSV.
+    #[schema(value_type = Vec<AffectedRange>)]
+    pub affected_ranges: serde_json::Value,
+    /// Advisory ids and aliases (CVE, GHSA, ...) OSV reports for the package version.
+    pub vulnerability_aliases: Vec<String>,
+    /// Last OSV lookup; `None` if never enriched.
+    pub osv_checked_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A vulnerable version range of a package, as reported by OSV.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AffectedRange {
+    pub vulnerability_id: String,
+    /// OSV range type: ECOSYSTEM, SEMVER, or GIT.
+    pub range_type: String,
+    pub introduced: Option<String>,
+    pub fixed: Option<String>,
+    pub last_affected: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateFindingSca {
     pub package_name: String,
     pub package_version: String,
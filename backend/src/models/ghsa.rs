@@ -0,0 +1,26 @@
+//! GitHub Security Advisory models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+/// A GHSA advisory as cached from the GitHub advisory database.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GhsaAdvisory {
+    pub ghsa_id: String,
+    /// False when GitHub had no advisory for the id at the last fetch.
+    pub found: bool,
+    /// CVE aliases; findings reporting the GHSA id also get these.
+    pub cve_ids: Vec<String>,
+    pub summary: Option<String>,
+    pub severity: Option<String>,
+    pub cvss_vector: Option<String>,
+    pub cvss_score: Option<f32>,
+    pub cwe_ids: Vec<String>,
+    pub html_url: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub advisory_updated_at: Option<DateTime<Utc>>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    pub fetched_at: DateTime<Utc>,
+}
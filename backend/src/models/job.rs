@@ -0,0 +1,63 @@
+//! Background job queue models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
+#[sqlx(type_name = "job_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for `run_at`, including failed attempts awaiting retry.
+    Queued,
+    Running,
+    Succeeded,
+    /// Every attempt failed; can be retried manually.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Job {
+    pub id: Uuid,
+    /// Handler name, e.g. `dashboard.refresh`.
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: JobStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// Earliest time the next attempt may start.
+    pub run_at: DateTime<Utc>,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A job to enqueue.
+#[derive(Debug, Clone)]
+pub struct NewJob {
+    pub kind: String,
+    pub payload: serde_json::Value,
+    /// Defaults to now.
+    pub run_at: Option<DateTime<Utc>>,
+    /// Defaults to 3.
+    pub max_attempts: Option<i32>,
+    pub created_by: Option<Uuid>,
+}
+
+impl NewJob {
+    /// A job of `kind` to run as soon as a worker is free.
+    pub fn now(kind: &str, payload: serde_json::Value) -> Self {
+        Self {
+            kind: kind.to_string(),
+            payload,
+            run_at: None,
+            max_attempts: None,
+            created_by: None,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! License compliance policy models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::finding::SeverityLevel;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, ToSchema)]
+#[sqlx(type_name = "license_policy_rule", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LicensePolicyRule {
+    /// The listed licenses are not allowed in any application.
+    Deny,
+    /// Copyleft licenses are not allowed in distributed applications.
+    CopyleftDistributed,
+}
+
+/// A license policy, with the open findings currently violating it.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LicensePolicy {
+    pub id: Uuid,
+    pub name: String,
+    pub rule: LicensePolicyRule,
+    /// SPDX ids, lower case without `-only`/`-or-later`. Empty on a copyleft
+    /// rule means the built-in copyleft list.
+    pub licenses: Vec<String>,
+    pub severity: SeverityLevel,
+    pub enabled: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub violation_count: i64,
+}
+
+/// Create a license policy, or replace one.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpsertLicensePolicy {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub rule: LicensePolicyRule,
+    /// SPDX license ids; required for a deny rule.
+    #[serde(default)]
+    pub licenses: Vec<String>,
+    pub severity: SeverityLevel,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// An open SCA finding whose package license violates a policy.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LicenseViolation {
+    pub policy_id: Uuid,
+    pub policy_name: String,
+    pub rule: LicensePolicyRule,
+    pub severity: SeverityLevel,
+    pub finding_id: Uuid,
+    pub package_name: String,
+    pub package_version: String,
+    /// The license that matched the policy.
+    pub license: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Packages of an application's open SCA findings sharing one license.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LicenseUsage {
+    /// License as reported by the scanner; `None` when it reported none.
+    pub license: Option<String>,
+    pub packages: i64,
+    pub findings: i64,
+}
+
+/// The licenses used by an application and the policies they violate.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApplicationLicenseReport {
+    pub application_id: Uuid,
+    pub app_code: String,
+    pub is_distributed: bool,
+    pub licenses: Vec<LicenseUsage>,
+    pub violations: Vec<LicenseViolation>,
+}
@@ -2,11 +2,36 @@
 
 pub mod app_code_pattern;
 pub mod application;
+pub mod asset;
+pub mod attachment;
+pub mod attack;
 pub mod audit;
+pub mod config_bundle;
 pub mod correlation_rule;
+pub mod cve;
+pub mod cwe;
+pub mod dns_mapping;
+pub mod exploit;
 pub mod finding;
 pub mod finding_dast;
+pub mod finding_manual;
 pub mod finding_sast;
 pub mod finding_sca;
+pub mod ghsa;
+pub mod job;
+pub mod license_policy;
+pub mod notification;
+pub mod owasp;
+pub mod ownership;
 pub mod pagination;
+pub mod release;
+pub mod report_schedule;
+pub mod report_template;
+pub mod saved_dashboard;
+pub mod saved_filter;
+pub mod scanner_rule;
+pub mod sbom;
+pub mod tag;
+pub mod upload_token;
 pub mod user;
+pub mod watchlist;
@@ -0,0 +1,101 @@
+//! Notification models: inbox entries and delivery preferences.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash, ToSchema)]
+#[sqlx(type_name = "notification_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A finding of an owned application changed status.
+    StatusChange,
+    /// A finding of an owned application passed its SLA due date.
+    SlaBreach,
+    /// The user was mentioned in a comment.
+    Mention,
+    /// The user was assigned a finding or an application role.
+    Assignment,
+    /// A CVE or package on the user's watchlist matched a finding.
+    WatchlistHit,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 5] = [
+        NotificationKind::StatusChange,
+        NotificationKind::SlaBreach,
+        NotificationKind::Mention,
+        NotificationKind::Assignment,
+        NotificationKind::WatchlistHit,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash, ToSchema)]
+#[sqlx(type_name = "notification_channel", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    InApp,
+    Email,
+}
+
+impl NotificationChannel {
+    pub const ALL: [NotificationChannel; 2] =
+        [NotificationChannel::InApp, NotificationChannel::Email];
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, Hash, ToSchema)]
+#[sqlx(type_name = "notification_frequency", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationFrequency {
+    Immediate,
+    /// Batched into one email per day; email channel only.
+    DailyDigest,
+    Off,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: Option<String>,
+    pub finding_id: Option<Uuid>,
+    pub application_id: Option<Uuid>,
+    /// User whose action caused the notification; `None` for system events.
+    pub actor_name: Option<String>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UnreadCount {
+    pub unread: i64,
+}
+
+/// Mark the given notifications of the current user as read.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MarkRead {
+    pub ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MarkReadResult {
+    pub updated: u64,
+}
+
+/// How a user receives one kind of notification on one channel.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, FromRow, PartialEq, Eq, ToSchema)]
+pub struct NotificationPreference {
+    pub kind: NotificationKind,
+    pub channel: NotificationChannel,
+    pub frequency: NotificationFrequency,
+}
+
+/// Preferences to change; kinds and channels not listed keep their setting.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateNotificationPreferences {
+    pub preferences: Vec<NotificationPreference>,
+}
@@ -0,0 +1,41 @@
+//! OWASP Top 10 categories and their CWE mapping.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+/// An OWASP Top 10 category with the CWEs mapped to it.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct OwaspCategory {
+    /// e.g. `A03:2021`
+    pub code: String,
+    pub name: String,
+    pub cwe_ids: Vec<i32>,
+}
+
+/// The category a CWE maps to.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct OwaspCweMapping {
+    pub cwe_id: i32,
+    pub category: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// CWEs to map or remap; `category: null` removes the mapping.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateOwaspMappings {
+    pub mappings: Vec<OwaspMappingChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OwaspMappingChange {
+    pub cwe_id: i32,
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OwaspMappingResult {
+    pub updated: u64,
+    pub removed: u64,
+}
@@ -0,0 +1,50 @@
+//! User-to-application ownership models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A user's role on an application, ordered from least to most privileged.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, PartialOrd, Ord, ToSchema,
+)]
+#[sqlx(type_name = "ownership_role", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum OwnershipRole {
+    /// Read access to the application's findings.
+    Viewer,
+    /// Security champion; receives notifications.
+    Champion,
+    /// Application owner; receives notifications and manages assignments.
+    Owner,
+}
+
+/// An ownership assignment with the assigned user's details.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ApplicationOwner {
+    pub application_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: String,
+    pub email: String,
+    pub role: OwnershipRole,
+    pub assigned_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An application the current user is assigned to.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct OwnedApplication {
+    pub application_id: Uuid,
+    pub app_code: String,
+    pub app_name: String,
+    pub role: OwnershipRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssignOwnership {
+    pub role: OwnershipRole,
+}
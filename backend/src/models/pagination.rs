@@ -1,11 +1,15 @@
 //! Pagination and filtering primitives shared across all list endpoints.
 
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 /// Pagination query parameters.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct Pagination {
+    /// 1-based page number (defaults to 1).
     pub page: Option<i64>,
+    /// Items per page (defaults to 25, capped at 100).
     pub per_page: Option<i64>,
 }
 
@@ -32,9 +36,26 @@ impl Pagination {
     }
 }
 
+/// Sort direction for list endpoints that accept `sort_dir`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+}
+
 /// Paged result envelope returned by list endpoints.
-#[derive(Debug, Clone, Serialize)]
-pub struct PagedResult<T: Serialize> {
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PagedResult<T> {
     pub items: Vec<T>,
     pub total: i64,
     pub page: i64,
@@ -89,6 +110,14 @@ mod tests {
         assert_eq!(p.offset(), 20);
     }
 
+    #[test]
+    fn sort_direction_deserializes_lowercase() {
+        let dir: SortDirection = serde_json::from_str("\"desc\"").unwrap();
+        assert_eq!(dir, SortDirection::Desc);
+        assert_eq!(dir.as_sql(), "DESC");
+        assert!(serde_json::from_str::<SortDirection>("\"DESC\"").is_err());
+    }
+
     #[test]
     fn paged_result_total_pages() {
         let p = Pagination {
@@ -0,0 +1,76 @@
+//! Application release models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::finding::{FindingCategory, FindingStatus, SeverityLevel};
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Release {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    pub version: String,
+    pub build_date: Option<DateTime<Utc>>,
+    pub commit_sha: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A release with the number of findings it introduced and fixed.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReleaseWithCounts {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub release: Release,
+    pub introduced_count: i64,
+    pub fixed_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateRelease {
+    #[validate(length(min = 1, max = 100))]
+    pub version: String,
+    pub build_date: Option<DateTime<Utc>>,
+    #[validate(length(min = 1, max = 64))]
+    pub commit_sha: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateRelease {
+    #[validate(length(min = 1, max = 100))]
+    pub version: Option<String>,
+    pub build_date: Option<DateTime<Utc>>,
+    #[validate(length(min = 1, max = 64))]
+    pub commit_sha: Option<String>,
+}
+
+/// Replace the releases linked to a finding; an omitted or `null` field clears that link.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SetFindingReleases {
+    pub introduced_in_release_id: Option<Uuid>,
+    pub fixed_in_release_id: Option<Uuid>,
+}
+
+/// A finding as listed in release notes.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReleaseFinding {
+    pub id: Uuid,
+    pub title: String,
+    pub finding_category: FindingCategory,
+    pub normalized_severity: SeverityLevel,
+    pub status: FindingStatus,
+    pub cve_ids: serde_json::Value,
+    pub first_seen: DateTime<Utc>,
+}
+
+/// Findings introduced and fixed in a release.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReleaseNotes {
+    pub release: Release,
+    pub fixed: Vec<ReleaseFinding>,
+    pub introduced: Vec<ReleaseFinding>,
+}
@@ -0,0 +1,94 @@
+//! Scheduled report and report run history models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
+#[sqlx(type_name = "report_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReportType {
+    /// Filtered findings as CSV; `filters` holds `FindingFilters`.
+    FindingsCsv,
+    /// Executive summary PDF; `filters` holds `{"period": "month|quarter|year"}`.
+    ExecutivePdf,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
+#[sqlx(type_name = "delivery_method", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryMethod {
+    Email,
+    Webhook,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
+#[sqlx(type_name = "report_run_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReportRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ReportSchedule {
+    pub id: Uuid,
+    pub name: String,
+    pub report_type: ReportType,
+    pub filters: serde_json::Value,
+    pub cron_expression: String,
+    pub delivery_method: DeliveryMethod,
+    pub recipients: serde_json::Value,
+    pub is_active: bool,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateReportSchedule {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub report_type: ReportType,
+    pub filters: Option<serde_json::Value>,
+    #[validate(length(min = 1, max = 100))]
+    pub cron_expression: String,
+    pub delivery_method: DeliveryMethod,
+    #[validate(length(min = 1, max = 50))]
+    pub recipients: Vec<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateReportSchedule {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    pub report_type: Option<ReportType>,
+    pub filters: Option<serde_json::Value>,
+    #[validate(length(min = 1, max = 100))]
+    pub cron_expression: Option<String>,
+    pub delivery_method: Option<DeliveryMethod>,
+    #[validate(length(min = 1, max = 50))]
+    pub recipients: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ReportRun {
+    pub id: Uuid,
+    pub schedule_id: Uuid,
+    pub status: ReportRunStatus,
+    /// `schedule` for cron-triggered runs, `manual` for on-demand runs.
+    pub triggered_by: String,
+    pub file_name: Option<String>,
+    pub byte_size: Option<i32>,
+    pub error_message: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
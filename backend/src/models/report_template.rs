@@ -0,0 +1,73 @@
+//! Customizable report template models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
+#[sqlx(type_name = "report_template_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    /// Context: `report` is an `ExecutiveSummary`.
+    Executive,
+    /// Context: `report` is a `DoraReport`.
+    Dora,
+    /// Context: `report` is a `GdprReport`.
+    Gdpr,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
+#[sqlx(type_name = "report_template_format", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateFormat {
+    /// Template output is served as an HTML page.
+    Html,
+    /// Template output is report markup laid out as a PDF.
+    Pdf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ReportTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub report_kind: ReportKind,
+    pub output_format: TemplateFormat,
+    pub language: String,
+    pub body: String,
+    pub logo_url: Option<String>,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateReportTemplate {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub report_kind: ReportKind,
+    pub output_format: TemplateFormat,
+    #[validate(length(min = 2, max = 10))]
+    pub language: Option<String>,
+    #[validate(length(min = 1))]
+    pub body: String,
+    #[validate(url)]
+    pub logo_url: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateReportTemplate {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[validate(length(min = 2, max = 10))]
+    pub language: Option<String>,
+    #[validate(length(min = 1))]
+    pub body: Option<String>,
+    #[validate(url)]
+    pub logo_url: Option<String>,
+    pub is_active: Option<bool>,
+}
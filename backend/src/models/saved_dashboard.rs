@@ -0,0 +1,86 @@
+//! User-defined saved dashboard models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
+#[sqlx(type_name = "dashboard_visibility", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardVisibility {
+    /// Visible to the owner only.
+    #[default]
+    Private,
+    /// Visible to every user; only the owner can change it.
+    Shared,
+}
+
+/// Finding attribute a widget groups its results by.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WidgetGroupBy {
+    Severity,
+    Status,
+    Category,
+    SourceTool,
+    Application,
+    SlaStatus,
+}
+
+/// Value a widget computes for each group.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WidgetMetric {
+    /// Number of matching findings.
+    #[default]
+    Count,
+    /// Summed composite risk score of matching findings.
+    RiskScore,
+}
+
+/// One widget: a stored finding filter plus an aggregation over it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct DashboardWidget {
+    pub title: String,
+    /// `FindingFilters` applied before aggregating.
+    #[serde(default)]
+    pub filters: serde_json::Value,
+    pub group_by: WidgetGroupBy,
+    #[serde(default)]
+    pub metric: WidgetMetric,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SavedDashboard {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub visibility: DashboardVisibility,
+    /// Ordered list of `DashboardWidget`.
+    pub widgets: serde_json::Value,
+    pub owner_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateSavedDashboard {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub description: Option<String>,
+    pub visibility: Option<DashboardVisibility>,
+    #[serde(default)]
+    pub widgets: Vec<DashboardWidget>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateSavedDashboard {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub visibility: Option<DashboardVisibility>,
+    pub widgets: Option<Vec<DashboardWidget>>,
+}
@@ -0,0 +1,61 @@
+//! Saved finding filter (view) models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
+#[sqlx(type_name = "filter_scope", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FilterScope {
+    /// Visible to the owner only.
+    #[default]
+    Personal,
+    /// Visible to every user; only the owner can change it.
+    Shared,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SavedFilter {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub scope: FilterScope,
+    /// `FindingFilters` query parameters.
+    pub filters: serde_json::Value,
+    /// `FindingSort` query parameters.
+    pub sort: serde_json::Value,
+    /// Ordered list of visible column keys.
+    pub columns: serde_json::Value,
+    pub owner_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateSavedFilter {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub description: Option<String>,
+    pub scope: Option<FilterScope>,
+    #[serde(default)]
+    pub filters: serde_json::Value,
+    #[serde(default)]
+    pub sort: serde_json::Value,
+    #[serde(default)]
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateSavedFilter {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub scope: Option<FilterScope>,
+    pub filters: Option<serde_json::Value>,
+    pub sort: Option<serde_json::Value>,
+    pub columns: Option<Vec<String>>,
+}
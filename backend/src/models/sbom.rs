@@ -0,0 +1,74 @@
+//! SBOM (software bill of materials) models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, ToSchema)]
+#[sqlx(type_name = "sbom_format", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum SbomFormat {
+    Cyclonedx,
+    Spdx,
+}
+
+/// An uploaded SBOM.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Sbom {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    pub release: Option<String>,
+    pub format: SbomFormat,
+    pub spec_version: Option<String>,
+    pub document_name: Option<String>,
+    pub component_count: i32,
+    pub uploaded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SbomComponent {
+    pub id: Uuid,
+    pub sbom_id: Uuid,
+    pub name: String,
+    /// CycloneDX group or package URL namespace.
+    pub group_name: Option<String>,
+    pub version: String,
+    pub purl: Option<String>,
+    /// Package URL type (maven, npm, pypi, ...).
+    pub package_type: Option<String>,
+    pub license: Option<String>,
+}
+
+/// A component with the application's SCA findings for the same package version.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct SbomComponentWithFindings {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub component: SbomComponent,
+    pub finding_ids: Vec<Uuid>,
+}
+
+/// An SBOM with its components.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SbomDetail {
+    pub sbom: Sbom,
+    pub components: Vec<SbomComponentWithFindings>,
+}
+
+/// An application whose latest SBOM for a release contains a package.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PackageOccurrence {
+    pub application_id: Uuid,
+    pub app_code: String,
+    pub app_name: String,
+    pub sbom_id: Uuid,
+    pub release: Option<String>,
+    pub sbom_created_at: DateTime<Utc>,
+    pub name: String,
+    pub group_name: Option<String>,
+    pub version: String,
+    pub purl: Option<String>,
+}
@@ -0,0 +1,49 @@
+//! Scanner rule catalog models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::finding::SeverityLevel;
+
+/// A (tool, rule) seen during ingestion, with stats over its findings.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ScannerRule {
+    pub id: Uuid,
+    pub source_tool: String,
+    /// SAST rule id, DAST plugin, or the CVE of an SCA finding.
+    pub rule_id: String,
+    pub rule_name: Option<String>,
+    /// Records reporting the rule across all ingestions, suppressed ones included.
+    pub times_seen: i64,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// Severity given to new findings of the rule instead of the tool's.
+    pub default_severity: Option<SeverityLevel>,
+    /// Records of a suppressed rule are skipped at ingestion.
+    pub suppressed: bool,
+    pub suppression_reason: Option<String>,
+    pub policy_updated_by: Option<Uuid>,
+    pub policy_updated_at: Option<DateTime<Utc>>,
+    pub finding_count: i64,
+    pub open_count: i64,
+    pub false_positive_count: i64,
+    /// False positives over all findings of the rule; `None` without findings.
+    pub false_positive_rate: Option<f64>,
+    /// Mean severity of the rule's findings, from 0 (Info) to 4 (Critical).
+    pub avg_severity: Option<f64>,
+}
+
+/// Default severity and suppression of a rule; replaces the current policy.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateRulePolicy {
+    /// Severity for new findings of the rule; `null` keeps the tool's.
+    pub default_severity: Option<SeverityLevel>,
+    pub suppressed: bool,
+    /// Required when suppressing.
+    #[validate(length(min = 1, max = 2000))]
+    pub suppression_reason: Option<String>,
+}
@@ -0,0 +1,53 @@
+//! Finding tag models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Tag {
+    pub id: Uuid,
+    /// Exactly as stored in the `tags` array of findings.
+    pub name: String,
+    /// Display color as `#rrggbb`.
+    pub color: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A tag with the number of findings carrying it.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TagWithUsage {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub tag: Tag,
+    pub usage_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateTag {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Tag changes; a new `name` is applied to every finding carrying the tag.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct UpdateTag {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Tags to fold into the target tag.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct MergeTags {
+    #[validate(length(min = 1, max = 100))]
+    pub source_ids: Vec<Uuid>,
+}
@@ -0,0 +1,53 @@
+//! Upload token models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::services::ingestion::ParserType;
+
+/// An upload token, without its secret.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct UploadToken {
+    pub id: Uuid,
+    pub name: String,
+    /// First characters of the token.
+    pub token_prefix: String,
+    /// Parser type the token may upload (`sonarqube`, `sarif`, ...).
+    pub parser_type: String,
+    /// Source tool the uploaded file must report; `None` accepts any.
+    pub source_tool: Option<String>,
+    /// Applications the token may write to; empty allows every application.
+    pub application_ids: Vec<Uuid>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_by: Option<Uuid>,
+}
+
+/// Issue an upload token.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateUploadToken {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    pub parser_type: ParserType,
+    #[validate(length(min = 1, max = 100))]
+    pub source_tool: Option<String>,
+    #[serde(default)]
+    pub application_ids: Vec<Uuid>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A newly issued token. The secret is only ever returned here.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IssuedUploadToken {
+    #[serde(flatten)]
+    pub token: UploadToken,
+    /// Send as the `X-API-Key` header of upload requests.
+    pub secret: String,
+}
@@ -3,9 +3,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::Validate;
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
 #[sqlx(type_name = "user_role")]
 pub enum UserRole {
     #[sqlx(rename = "Platform_Admin")]
@@ -39,7 +41,7 @@ pub struct User {
 }
 
 /// User response DTO — excludes password_hash and internal fields.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserResponse {
     pub id: Uuid,
     pub username: String,
@@ -66,18 +68,24 @@ impl From<User> for UserResponse {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema, Validate)]
 pub struct CreateUser {
+    #[validate(length(min = 1, max = 255))]
     pub username: String,
+    #[validate(email)]
     pub email: String,
+    #[validate(length(min = 8, max = 128))]
     pub password: String,
+    #[validate(length(min = 1, max = 255))]
     pub display_name: String,
     pub role: UserRole,
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, Validate)]
 pub struct UpdateUser {
+    #[validate(email)]
     pub email: Option<String>,
+    #[validate(length(min = 1, max = 255))]
     pub display_name: Option<String>,
     pub role: Option<UserRole>,
     pub is_active: Option<bool>,
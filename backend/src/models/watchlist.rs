@@ -0,0 +1,77 @@
+//! CVE and package watchlist models.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, ToSchema)]
+#[sqlx(type_name = "watchlist_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WatchlistKind {
+    /// A CVE id, matched against findings' CVE ids.
+    Cve,
+    /// A package name, matched against SCA findings.
+    Package,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, Eq, ToSchema)]
+#[sqlx(type_name = "watchlist_trigger", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WatchlistTrigger {
+    /// A matching finding was reported by an ingestion run.
+    Ingestion,
+    /// A matching finding changed status.
+    StatusChange,
+}
+
+/// A CVE or package watched by a user.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct WatchlistEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: WatchlistKind,
+    /// CVE id (upper case) or package name (lower case).
+    pub identifier: String,
+    /// Watched package version; `None` watches every version.
+    pub package_version: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Open findings currently matching the entry.
+    pub open_findings: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
+}
+
+/// Add a CVE or package to the current user's watchlist.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateWatchlistEntry {
+    pub kind: WatchlistKind,
+    #[validate(length(min = 1, max = 500))]
+    pub identifier: String,
+    /// Package entries only.
+    #[validate(length(min = 1, max = 100))]
+    pub package_version: Option<String>,
+    #[validate(length(max = 2000))]
+    pub note: Option<String>,
+}
+
+/// A finding matching a watchlist entry, with what brought it up.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct WatchlistHit {
+    pub id: Uuid,
+    pub entry_id: Uuid,
+    pub kind: WatchlistKind,
+    pub identifier: String,
+    pub package_version: Option<String>,
+    pub finding_id: Uuid,
+    pub finding_title: String,
+    pub application_id: Option<Uuid>,
+    pub app_code: Option<String>,
+    pub trigger: WatchlistTrigger,
+    pub ingestion_log_id: Option<Uuid>,
+    /// Ingestion outcome (`new`, `updated`, `reopened`) or `Old -> New` status.
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
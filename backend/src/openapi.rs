@@ -0,0 +1,472 @@
+//! OpenAPI document for the HTTP API, served at `/api/v1/openapi.json` with
+//! Swagger UI at `/api/v1/docs`.
+//!
+//! Paths come from the `#[utoipa::path]` annotations on the route handlers;
+//! schemas referenced from request and response bodies are collected from
+//! there. A new handler only needs to be added to `paths(...)` below.
+
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::errors::ApiError;
+use crate::middleware::problem_details::ProblemDetails;
+use crate::models::application::{AppStatus, AssetCriticality};
+use crate::models::finding::{FindingCategory, FindingStatus, SeverityLevel, SlaStatus};
+use crate::models::job::JobStatus;
+use crate::models::pagination::SortDirection;
+use crate::routes;
+use crate::services::application::ApplicationSortField;
+use crate::services::business_units::RollupGroup;
+use crate::services::dashboard_snapshots::SnapshotGroupBy;
+use crate::services::executive_report::ReportPeriod;
+use crate::services::finding::FindingSortField;
+use crate::services::finding_trends::TrendInterval;
+use crate::services::ingestion::IngestionResult;
+use crate::services::search::SearchKind;
+use crate::services::vex::VexFormat;
+
+/// Registers the JWT bearer scheme referenced by authenticated endpoints, and
+/// the upload token scheme accepted by the upload endpoint.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "SynApSec API",
+        description = "Application security findings platform. JSON responses use the \
+            `{ data, error }` envelope; on failure `data` is null and `error` carries a \
+            machine-readable `code` and a `message`."
+    ),
+    paths(
+        routes::health::live,
+        routes::health::ready,
+        routes::auth::login,
+        routes::auth::refresh,
+        routes::auth::logout,
+        routes::auth::create_user,
+        routes::auth::me,
+        routes::applications::list,
+        routes::applications::create,
+        routes::applications::list_unverified,
+        routes::applications::import_bulk,
+        routes::applications::import_apm,
+        routes::applications::preview_apm,
+        routes::applications::get_by_code,
+        routes::applications::get_by_id,
+        routes::applications::update,
+        routes::applications::merge,
+        routes::applications::posture,
+        routes::applications::stats,
+        routes::occurrences::auto_close_candidates,
+        routes::ownership::list,
+        routes::ownership::assign,
+        routes::ownership::remove,
+        routes::ownership::my_applications,
+        routes::notifications::list,
+        routes::notifications::unread_count,
+        routes::notifications::mark_read,
+        routes::notifications::mark_all_read,
+        routes::notifications::get_preferences,
+        routes::notifications::update_preferences,
+        routes::assets::list,
+        routes::assets::create,
+        routes::assets::get_by_id,
+        routes::assets::update,
+        routes::assets::link,
+        routes::assets::delete,
+        routes::assets::list_for_application,
+        routes::app_code_patterns::list,
+        routes::app_code_patterns::create,
+        routes::app_code_patterns::get_by_id,
+        routes::app_code_patterns::update,
+        routes::app_code_patterns::deactivate,
+        routes::app_code_patterns::reorder,
+        routes::app_code_patterns::test,
+        routes::dns_mappings::list,
+        routes::dns_mappings::create,
+        routes::dns_mappings::get_by_id,
+        routes::dns_mappings::update,
+        routes::dns_mappings::delete,
+        routes::scanner_rules::list,
+        routes::scanner_rules::get_by_id,
+        routes::scanner_rules::update_policy,
+        routes::triage::queue,
+        routes::triage::claim,
+        routes::triage::release,
+        routes::watchlist::list,
+        routes::watchlist::create,
+        routes::watchlist::delete,
+        routes::watchlist::hits,
+        routes::license_policies::list,
+        routes::license_policies::create,
+        routes::license_policies::update,
+        routes::license_policies::delete,
+        routes::license_policies::application_report,
+        routes::eol::report,
+        routes::upload_tokens::list,
+        routes::upload_tokens::create,
+        routes::upload_tokens::revoke,
+        routes::findings::list,
+        routes::findings::create,
+        routes::findings::create_manual,
+        routes::findings::export_findings,
+        routes::findings::bulk_status,
+        routes::findings::bulk_assign,
+        routes::findings::bulk_tag,
+        routes::findings::bulk_archive,
+        routes::findings::bulk_delete,
+        routes::findings::get_by_id,
+        routes::findings::get_raw,
+        routes::findings::batch_get,
+        routes::findings::update,
+        routes::findings::update_status,
+        routes::findings::set_severity_override,
+        routes::findings::list_comments,
+        routes::findings::add_comment,
+        routes::findings::update_comment,
+        routes::findings::delete_comment,
+        routes::findings::get_history,
+        routes::findings::get_similar,
+        routes::finding_merges::merge,
+        routes::finding_merges::list,
+        routes::finding_merges::unmerge,
+        routes::finding_reassign::split,
+        routes::finding_reassign::list_splits,
+        routes::finding_reassign::bulk_reassign,
+        routes::occurrences::list,
+        routes::occurrences::summary,
+        routes::findings::list_my_mentions,
+        routes::attachments::upload,
+        routes::attachments::list,
+        routes::attachments::download,
+        routes::attachments::delete,
+        routes::sbom::upload,
+        routes::sbom::get_latest,
+        routes::sbom::list,
+        routes::sbom::find_package,
+        routes::releases::list,
+        routes::releases::create,
+        routes::releases::get_by_id,
+        routes::releases::update,
+        routes::releases::delete,
+        routes::releases::notes,
+        routes::releases::set_finding_releases,
+        routes::ingestion::upload,
+        routes::ingestion::history,
+        routes::ingestion::get_log,
+        routes::ingestion::diff,
+        routes::correlation::list_groups,
+        routes::correlation::get_group,
+        routes::correlation::list_rules,
+        routes::correlation::create_rule,
+        routes::correlation::update_rule,
+        routes::correlation::run_correlation,
+        routes::correlation::create_relationship,
+        routes::correlation::delete_relationship,
+        routes::deduplication::stats,
+        routes::deduplication::effectiveness,
+        routes::deduplication::pending,
+        routes::deduplication::history,
+        routes::deduplication::review_metrics,
+        routes::deduplication::confirm,
+        routes::deduplication::reject,
+        routes::deduplication::run,
+        routes::deduplication::queue_similar,
+        routes::dashboard::stats,
+        routes::dashboard::trends,
+        routes::dashboard::mttr,
+        routes::dashboard::burndown,
+        routes::dashboard::business_units,
+        routes::dashboard::coverage_gaps,
+        routes::dashboard::top_apps,
+        routes::dashboard::snapshots,
+        routes::dashboard::compare_snapshots,
+        routes::dashboard::workload,
+        routes::saved_dashboards::list,
+        routes::saved_dashboards::create,
+        routes::saved_dashboards::get_by_id,
+        routes::saved_dashboards::update,
+        routes::saved_dashboards::delete,
+        routes::saved_filters::list,
+        routes::saved_filters::create,
+        routes::saved_filters::get_by_id,
+        routes::saved_filters::update,
+        routes::saved_filters::delete,
+        routes::search::search,
+        routes::cves::get_by_id,
+        routes::cves::get_ghsa_advisory,
+        routes::cwes::list,
+        routes::cwes::rollup,
+        routes::cwes::get_by_id,
+        routes::cwes::import,
+        routes::owasp::list_categories,
+        routes::owasp::update_mappings,
+        routes::attack::import_capec,
+        routes::attack::import_techniques,
+        routes::enrichment::enrich_osv,
+        routes::remediation::upgrade_plan,
+        routes::remediation::application_upgrade_plan,
+        routes::tags::list,
+        routes::tags::autocomplete,
+        routes::tags::create,
+        routes::tags::get_by_id,
+        routes::tags::update,
+        routes::tags::delete,
+        routes::tags::merge,
+        routes::graphql::execute,
+        routes::graphql::graphiql,
+        routes::attack_chains::list,
+        routes::attack_chains::get_by_app,
+        routes::attack_chains::get_tactics_by_app,
+        routes::vex::export,
+        routes::vex::import,
+        routes::exports::export_application_findings,
+        routes::reports::executive,
+        routes::reports::dora,
+        routes::reports::gdpr,
+        routes::reports::false_positives,
+        routes::report_schedules::list,
+        routes::report_schedules::create,
+        routes::report_schedules::get_by_id,
+        routes::report_schedules::update,
+        routes::report_schedules::delete,
+        routes::report_schedules::run_now,
+        routes::report_schedules::list_runs,
+        routes::report_templates::list,
+        routes::report_templates::create,
+        routes::report_templates::get_by_id,
+        routes::report_templates::update,
+        routes::report_templates::delete,
+        routes::audit_log::list,
+        routes::audit_log::export,
+        routes::jobs::list,
+        routes::jobs::get_by_id,
+        routes::jobs::retry,
+        routes::config_bundle::export,
+        routes::config_bundle::import,
+        routes::fingerprint_profiles::list,
+        routes::fingerprint_profiles::set,
+        routes::fingerprint_profiles::remove,
+        routes::data_hygiene::report,
+        routes::data_hygiene::fix,
+        routes::data_subjects::pseudonymize,
+    ),
+    components(schemas(
+        ApiError,
+        ProblemDetails,
+        IngestionResult,
+        // Enums referenced only from query parameters.
+        AppStatus,
+        AssetCriticality,
+        FindingCategory,
+        FindingStatus,
+        SeverityLevel,
+        SlaStatus,
+        ReportPeriod,
+        TrendInterval,
+        RollupGroup,
+        SnapshotGroupBy,
+        SortDirection,
+        FindingSortField,
+        ApplicationSortField,
+        SearchKind,
+        JobStatus,
+        routes::reports::ReportFormat,
+        routes::findings::ExportFormat,
+        routes::exports::AppExportFormat,
+        routes::audit_log::AuditExportFormat,
+        VexFormat,
+    )),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "health", description = "Liveness and readiness probes"),
+        (name = "auth", description = "Login, token refresh, and user management"),
+        (name = "applications", description = "Application registry and APM import"),
+        (name = "ownership", description = "User-to-application ownership assignments"),
+        (name = "notifications", description = "In-app notification inbox and delivery preferences"),
+        (name = "assets", description = "Asset inventory linked to applications"),
+        (name = "app-code-patterns", description = "Regex patterns resolving app codes from scanner fields"),
+        (name = "scanner-rules", description = "Catalog of scanner rules seen during ingestion, with per-rule severity and suppression"),
+        (name = "triage", description = "Queue of New findings awaiting triage, with claims"),
+        (name = "watchlist", description = "Per-user CVE and package watchlists with hit alerts"),
+        (name = "license-policies", description = "License compliance policies for SCA packages"),
+        (name = "eol", description = "End-of-life packages and runtimes from endoflife.date"),
+        (name = "upload-tokens", description = "Scoped tokens for CI uploads of scanner output"),
+        (name = "dns-mappings", description = "DNS name and URL prefix to application mappings"),
+        (name = "findings", description = "Findings lifecycle, comments, bulk actions, and export"),
+        (name = "attachments", description = "Evidence files attached to findings"),
+        (name = "sbom", description = "SBOM import per application and package lookup"),
+        (name = "releases", description = "Application releases and release notes"),
+        (name = "saved-filters", description = "Named finding-list views"),
+        (name = "tags", description = "Tag definitions, rename, merge, and autocomplete"),
+        (name = "graphql", description = "Read-only GraphQL API over findings and applications"),
+        (name = "search", description = "Global search across findings, applications, CVEs, packages, and comments"),
+        (name = "cves", description = "CVE details from NVD and GHSA advisories with linked findings"),
+        (name = "cwes", description = "CWE catalog, hierarchy, and findings by CWE family"),
+        (name = "owasp", description = "OWASP Top 10 categories and their CWE mapping"),
+        (name = "attack", description = "MITRE ATT&CK and CAPEC catalogs for technique mapping"),
+        (name = "enrichment", description = "On-demand vulnerability database lookups for findings"),
+        (name = "remediation", description = "SCA upgrade plans across the portfolio and per application"),
+        (name = "ingestion", description = "Scanner output upload and ingestion history"),
+        (name = "correlation", description = "Correlation groups, rules, and relationships"),
+        (name = "deduplication", description = "Cross-tool duplicate review"),
+        (name = "dashboard", description = "Overview statistics, trends, and remediation times"),
+        (name = "saved-dashboards", description = "User-defined dashboards"),
+        (name = "attack-chains", description = "Per-application attack chains"),
+        (name = "vex", description = "VEX import and export"),
+        (name = "exports", description = "Findings export for third-party tools"),
+        (name = "reports", description = "Executive and compliance reports"),
+        (name = "report-schedules", description = "Scheduled report delivery"),
+        (name = "report-templates", description = "Customizable report layouts"),
+        (name = "audit-log", description = "Audit evidence export"),
+        (name = "jobs", description = "Background job queue administration"),
+        (name = "config-bundle", description = "Configuration export and import between environments"),
+        (name = "fingerprints", description = "Per-tool fingerprint components used for deduplication"),
+        (name = "data-hygiene", description = "Orphaned data detection and cleanup"),
+        (name = "data-subjects", description = "Pseudonymization of people for erasure requests"),
+    )
+)]
+pub struct ApiDoc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> serde_json::Value {
+        serde_json::to_value(ApiDoc::openapi()).unwrap()
+    }
+
+    #[test]
+    fn documents_every_route_group() {
+        let spec = spec();
+        let paths = spec["paths"].as_object().unwrap();
+        for path in [
+            "/health/ready",
+            "/api/v1/auth/login",
+            "/api/v1/applications/{id}",
+            "/api/v1/applications/{id}/owners/{user_id}",
+            "/api/v1/me/applications",
+            "/api/v1/me/mentions",
+            "/api/v1/me/notifications",
+            "/api/v1/me/notification-preferences",
+            "/api/v1/assets/{id}/application",
+            "/api/v1/app-code-patterns/{id}",
+            "/api/v1/app-code-patterns/test",
+            "/api/v1/dns-mappings/{id}",
+            "/api/v1/scanner-rules/{id}/policy",
+            "/api/v1/triage/queue/{id}/claim",
+            "/api/v1/watchlist/hits",
+            "/api/v1/license-policies/{id}",
+            "/api/v1/applications/{id}/licenses",
+            "/api/v1/applications/{id}/stats",
+            "/api/v1/applications/import/apm/preview",
+            "/api/v1/eol/report",
+            "/api/v1/upload-tokens/{id}",
+            "/api/v1/findings",
+            "/api/v1/findings/{id}/attachments",
+            "/api/v1/findings/{id}/split",
+            "/api/v1/findings/bulk/reassign",
+            "/api/v1/findings/manual",
+            "/api/v1/admin/data-hygiene",
+            "/api/v1/admin/data-subjects/pseudonymize",
+            "/api/v1/applications/{id}/sbom",
+            "/api/v1/sbom/packages",
+            "/api/v1/releases/{id}/notes",
+            "/api/v1/search",
+            "/api/v1/cves/{id}",
+            "/api/v1/cwes/{id}",
+            "/api/v1/owasp/categories",
+            "/api/v1/advisories/ghsa/{id}",
+            "/api/v1/findings/{id}/enrich/osv",
+            "/api/v1/remediation/upgrade-plan",
+            "/api/v1/applications/{id}/upgrade-plan",
+            "/api/v1/tags/{id}/merge",
+            "/api/v1/ingestion/upload",
+            "/api/v1/ingestion/{id}/diff",
+            "/api/v1/correlations/groups",
+            "/api/v1/deduplication/pending",
+            "/api/v1/deduplication/review-metrics",
+            "/api/v1/deduplication/run/{app_id}",
+            "/api/v1/deduplication/similar/{app_id}",
+            "/api/v1/findings/{id}/similar",
+            "/api/v1/findings/{id}/raw",
+            "/api/v1/findings/{id}/merge",
+            "/api/v1/findings/{id}/occurrences/summary",
+            "/api/v1/applications/{id}/auto-close-candidates",
+            "/api/v1/findings/merges/{merge_id}/unmerge",
+            "/api/v1/dashboard/stats",
+            "/api/v1/attack-chains/{app_id}",
+            "/api/v1/attack-chains/{app_id}/tactics",
+            "/api/v1/attack/capec/import",
+            "/api/v1/applications/{id}/vex",
+            "/api/v1/reports/dora",
+            "/api/v1/reports/false-positives",
+            "/api/v1/report-schedules/{id}/runs",
+            "/api/v1/report-templates/{id}",
+            "/api/v1/audit-log/export",
+            "/api/v1/admin/jobs/{id}/retry",
+            "/api/v1/admin/config-bundle",
+            "/api/v1/admin/fingerprint-profiles/{source_tool}",
+        ] {
+            assert!(paths.contains_key(path), "missing path {path}");
+        }
+    }
+
+    #[test]
+    fn documents_finding_filters_as_query_parameters() {
+        let spec = spec();
+        let params = spec["paths"]["/api/v1/findings"]["get"]["parameters"]
+            .as_array()
+            .unwrap();
+        let names: Vec<&str> = params.iter().filter_map(|p| p["name"].as_str()).collect();
+        for name in [
+            "page",
+            "per_page",
+            "severity",
+            "status",
+            "include_category_data",
+        ] {
+            assert!(names.contains(&name), "missing parameter {name}");
+        }
+    }
+
+    #[test]
+    fn registers_envelope_schemas_and_bearer_scheme() {
+        let spec = spec();
+        let components = &spec["components"];
+        assert!(components["schemas"]["ApiError"].is_object());
+        assert!(components["schemas"]["IngestionResult"].is_object());
+        assert_eq!(
+            components["securitySchemes"]["bearer_auth"]["scheme"],
+            "bearer"
+        );
+    }
+
+    #[test]
+    fn public_routes_carry_no_security_requirement() {
+        let spec = spec();
+        assert!(spec["paths"]["/api/v1/auth/login"]["post"]
+            .get("security")
+            .is_none());
+        assert!(spec["paths"]["/api/v1/auth/me"]["get"]["security"].is_array());
+    }
+}
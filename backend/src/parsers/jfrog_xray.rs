@@ -89,10 +89,8 @@ struct XrayRow {
     references: Vec<String>,
     #[serde(default)]
     project_keys: Vec<String>,
-    #[expect(dead_code, reason = "present in real Xray export, captured for completeness")]
     applicability: Option<serde_json::Value>,
     #[serde(default)]
-    #[expect(dead_code, reason = "present in real Xray export, captured for completeness")]
     applicability_result: Option<String>,
 }
 
@@ -115,6 +113,14 @@ struct GavCoordinate {
 impl JfrogXrayParser {
     fn parse_json(&self, data: &[u8]) -> Result<ParseResult, anyhow::Error> {
         let export: XrayExport = serde_json::from_slice(data)?;
+        // Rows of one export come from one scan; take the latest time reported
+        let scanned_at = export
+            .rows
+            .iter()
+            .filter_map(|row| row.artifact_scan_time.as_deref())
+            .filter_map(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&chrono::Utc))
+            .max();
         let mut findings = Vec::new();
         let mut errors = Vec::new();
 
@@ -130,6 +136,7 @@ impl JfrogXrayParser {
             errors,
             source_tool: self.source_tool().to_string(),
             source_tool_version: None,
+            scanned_at,
         })
     }
 
@@ -374,6 +381,10 @@ mod tests {
         assert_eq!(result.findings.len(), 6);
         assert_eq!(result.errors.len(), 0);
         assert_eq!(result.source_tool, "JFrog Xray");
+        assert_eq!(
+            result.scanned_at.map(|t| t.to_rfc3339()).as_deref(),
+            Some("2025-12-04T16:23:55+00:00")
+        );
     }
 
     #[test]
@@ -5,9 +5,12 @@
 
 pub mod jfrog_xray;
 pub mod sarif;
+pub mod semgrep;
 pub mod sonarqube;
 pub mod tenable_was;
 
+use chrono::{DateTime, Utc};
+
 use crate::models::finding::{CreateFinding, FindingCategory, SeverityLevel};
 use crate::services::finding::CategoryData;
 
@@ -25,6 +28,8 @@ pub struct ParseResult {
     pub errors: Vec<ParseError>,
     pub source_tool: String,
     pub source_tool_version: Option<String>,
+    /// When the scan ran, if the report says so.
+    pub scanned_at: Option<DateTime<Utc>>,
 }
 
 /// Error encountered while parsing an individual record.
@@ -36,7 +41,7 @@ pub struct ParseError {
 }
 
 /// Input format for scanner data.
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum InputFormat {
     Json,
@@ -59,6 +59,14 @@ struct SarifDocument {
 struct SarifRun {
     tool: SarifTool,
     results: Vec<SarifResult>,
+    #[serde(default)]
+    invocations: Vec<SarifInvocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifInvocation {
+    #[serde(rename = "startTimeUtc")]
+    start_time_utc: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -174,11 +182,21 @@ impl SarifParser {
             .first()
             .and_then(|r| r.tool.driver.version.clone());
 
+        let scanned_at = document
+            .runs
+            .iter()
+            .flat_map(|r| &r.invocations)
+            .filter_map(|i| i.start_time_utc.as_deref())
+            .filter_map(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&chrono::Utc))
+            .min();
+
         Ok(ParseResult {
             findings,
             errors,
             source_tool,
             source_tool_version: source_version,
+            scanned_at,
         })
     }
 
@@ -374,6 +392,28 @@ mod tests {
         assert_eq!(result.source_tool_version.as_deref(), Some("3.2.1"));
     }
 
+    #[test]
+    fn scan_time_comes_from_the_earliest_invocation() {
+        let parser = SarifParser::new();
+        let data = br#"{"runs": [{
+            "tool": {"driver": {"name": "Scanner"}},
+            "results": [],
+            "invocations": [
+                {"startTimeUtc": "2026-03-02T08:00:00Z"},
+                {"startTimeUtc": "2026-03-01T22:30:00Z"}
+            ]
+        }]}"#;
+        let result = parser.parse(data, InputFormat::Sarif).unwrap();
+        assert_eq!(
+            result.scanned_at.map(|t| t.to_rfc3339()).as_deref(),
+            Some("2026-03-01T22:30:00+00:00")
+        );
+
+        let data = include_bytes!("../../tests/fixtures/sarif_sample.json");
+        let result = parser.parse(data, InputFormat::Sarif).unwrap();
+        assert!(result.scanned_at.is_none());
+    }
+
     #[test]
     fn sarif_severity_mapping() {
         let parser = SarifParser::new();
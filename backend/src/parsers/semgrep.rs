@@ -0,0 +1,888 @@
+//! Semgrep CI output parser supporting the native JSON and SARIF formats.
+//!
+//! Both `semgrep ci --json` and `semgrep ci --sarif` output map to the same
+//! normalized SAST finding: rule metadata supplies the CWE ids, the OWASP
+//! category, the confidence and the rule URL, and the dataflow trace of taint
+//! rules supplies the taint source and sink. Results ignored with
+//! `nosemgrep` comments or suppressed in the SARIF log are skipped.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::models::finding::{ConfidenceLevel, CreateFinding, FindingCategory, SeverityLevel};
+use crate::models::finding_sast::CreateFindingSast;
+use crate::parsers::{InputFormat, ParseError, ParseResult, ParsedFinding, Parser};
+use crate::services::finding::CategoryData;
+use crate::services::fingerprint;
+
+/// Placeholder Semgrep prints for code lines and fingerprints when the CLI
+/// runs without being logged in.
+const REQUIRES_LOGIN: &str = "requires login";
+
+/// Semgrep parser instance.
+#[derive(Default)]
+pub struct SemgrepParser;
+
+impl SemgrepParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Parser for SemgrepParser {
+    fn parse(&self, data: &[u8], format: InputFormat) -> Result<ParseResult, anyhow::Error> {
+        match format {
+            InputFormat::Sarif => self.parse_sarif(serde_json::from_slice(data)?),
+            InputFormat::Json => {
+                // SARIF logs are JSON too; accept either under the JSON format
+                let document: Value = serde_json::from_slice(data)?;
+                if document.get("runs").is_some() {
+                    self.parse_sarif(document)
+                } else {
+                    self.parse_json(document)
+                }
+            }
+            _ => anyhow::bail!("Semgrep parser only supports JSON and SARIF formats"),
+        }
+    }
+
+    fn source_tool(&self) -> &str {
+        "Semgrep"
+    }
+
+    fn category(&self) -> FindingCategory {
+        FindingCategory::Sast
+    }
+
+    fn map_severity(&self, tool_severity: &str) -> SeverityLevel {
+        // Semgrep severities, then the SARIF levels they are exported as
+        match tool_severity.to_uppercase().as_str() {
+            "CRITICAL" => SeverityLevel::Critical,
+            "ERROR" | "HIGH" => SeverityLevel::High,
+            "WARNING" | "MEDIUM" => SeverityLevel::Medium,
+            "INFO" | "LOW" | "NOTE" => SeverityLevel::Low,
+            "INVENTORY" | "EXPERIMENT" | "NONE" => SeverityLevel::Info,
+            _ => SeverityLevel::Medium,
+        }
+    }
+}
+
+/// A Semgrep result in either output format, before normalization.
+#[derive(Debug, Default)]
+struct SemgrepMatch {
+    rule_id: String,
+    message: String,
+    severity: String,
+    path: String,
+    line_start: Option<i32>,
+    line_end: Option<i32>,
+    code_snippet: Option<String>,
+    /// Semgrep's own fingerprint of the match, when the CLI was logged in.
+    match_id: Option<String>,
+    metadata: RuleMetadata,
+    /// Rule documentation (SARIF only; native JSON has just the message).
+    rule_description: Option<String>,
+    taint_source: Option<String>,
+    taint_sink: Option<String>,
+    raw: Value,
+}
+
+/// Rule metadata as Semgrep registry rules declare it. Values may be a
+/// single string or a list.
+#[derive(Debug, Default, Deserialize)]
+struct RuleMetadata {
+    #[serde(default, deserialize_with = "one_or_many")]
+    cwe: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    owasp: Vec<String>,
+    confidence: Option<String>,
+    category: Option<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    subcategory: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    technology: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    vulnerability_class: Vec<String>,
+    /// Rule page on the Semgrep registry.
+    source: Option<String>,
+    shortlink: Option<String>,
+    application_code: Option<String>,
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        Some(OneOrMany::One(value)) => vec![value],
+        Some(OneOrMany::Many(values)) => values,
+        None => Vec::new(),
+    })
+}
+
+// -- Native JSON output (subset) --
+
+#[derive(Debug, Deserialize)]
+struct SemgrepOutput {
+    version: Option<String>,
+    #[serde(default)]
+    results: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SemgrepResult {
+    check_id: String,
+    path: String,
+    start: SemgrepPosition,
+    end: Option<SemgrepPosition>,
+    extra: SemgrepExtra,
+}
+
+#[derive(Debug, Deserialize)]
+struct SemgrepPosition {
+    line: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SemgrepExtra {
+    #[serde(default)]
+    message: String,
+    severity: Option<String>,
+    lines: Option<String>,
+    fingerprint: Option<String>,
+    #[serde(default)]
+    metadata: RuleMetadata,
+    dataflow_trace: Option<SemgrepDataflowTrace>,
+    #[serde(default)]
+    is_ignored: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SemgrepDataflowTrace {
+    taint_source: Option<Value>,
+    taint_sink: Option<Value>,
+}
+
+// -- SARIF output (subset) --
+
+#[derive(Debug, Deserialize)]
+struct SarifDocument {
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifRun {
+    tool: SarifTool,
+    #[serde(default)]
+    results: Vec<Value>,
+    #[serde(default)]
+    invocations: Vec<SarifInvocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifInvocation {
+    #[serde(rename = "startTimeUtc")]
+    start_time_utc: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifDriver {
+    #[serde(rename = "semanticVersion")]
+    semantic_version: Option<String>,
+    version: Option<String>,
+    #[serde(default)]
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifRule {
+    id: String,
+    #[serde(rename = "fullDescription")]
+    full_description: Option<SarifMessage>,
+    help: Option<SarifMessage>,
+    #[serde(rename = "helpUri")]
+    help_uri: Option<String>,
+    #[serde(rename = "defaultConfiguration")]
+    default_configuration: Option<SarifConfiguration>,
+    #[serde(default)]
+    properties: SarifRuleProperties,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SarifRuleProperties {
+    #[serde(default)]
+    tags: Vec<String>,
+    precision: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifConfiguration {
+    level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: Option<String>,
+    message: SarifMessage,
+    #[serde(default)]
+    locations: Vec<SarifLocation>,
+    #[serde(default, rename = "codeFlows")]
+    code_flows: Vec<SarifCodeFlow>,
+    #[serde(default)]
+    fingerprints: serde_json::Map<String, Value>,
+    #[serde(default)]
+    suppressions: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: Option<SarifPhysicalLocation>,
+    message: Option<SarifMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: Option<SarifArtifactLocation>,
+    region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifRegion {
+    #[serde(rename = "startLine")]
+    start_line: Option<i32>,
+    #[serde(rename = "endLine")]
+    end_line: Option<i32>,
+    snippet: Option<SarifMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifCodeFlow {
+    #[serde(default, rename = "threadFlows")]
+    thread_flows: Vec<SarifThreadFlow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifThreadFlow {
+    #[serde(default)]
+    locations: Vec<SarifThreadFlowLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SarifThreadFlowLocation {
+    location: SarifLocation,
+}
+
+impl SarifLocation {
+    fn uri(&self) -> Option<&str> {
+        self.physical_location
+            .as_ref()
+            .and_then(|p| p.artifact_location.as_ref())
+            .map(|a| a.uri.as_str())
+    }
+
+    fn region(&self) -> Option<&SarifRegion> {
+        self.physical_location
+            .as_ref()
+            .and_then(|p| p.region.as_ref())
+    }
+
+    /// `path:line: code` of a dataflow step, as the native JSON trace reads.
+    fn describe(&self) -> Option<String> {
+        let region = self.region();
+        let content = region
+            .and_then(|r| r.snippet.as_ref())
+            .or(self.message.as_ref())
+            .map(|m| m.text.as_str());
+        describe_step(self.uri()?, region.and_then(|r| r.start_line), content)
+    }
+}
+
+impl SemgrepParser {
+    fn parse_json(&self, document: Value) -> Result<ParseResult, anyhow::Error> {
+        let output: SemgrepOutput = serde_json::from_value(document)?;
+        let mut findings = Vec::new();
+        let mut errors = Vec::new();
+
+        for (i, raw) in output.results.into_iter().enumerate() {
+            let result = match SemgrepResult::deserialize(&raw) {
+                Ok(result) => result,
+                Err(e) => {
+                    errors.push(ParseError {
+                        record_index: i,
+                        field: "result".to_string(),
+                        message: format!("Invalid Semgrep result: {e}"),
+                    });
+                    continue;
+                }
+            };
+            if result.extra.is_ignored {
+                continue;
+            }
+            let trace = result.extra.dataflow_trace.as_ref();
+            let semgrep_match = SemgrepMatch {
+                severity: result
+                    .extra
+                    .severity
+                    .clone()
+                    .unwrap_or_else(|| "WARNING".to_string()),
+                line_start: Some(result.start.line),
+                line_end: result.end.as_ref().map(|p| p.line),
+                code_snippet: result.extra.lines.clone().filter(|l| is_reported(l)),
+                match_id: result.extra.fingerprint.clone().filter(|f| is_reported(f)),
+                taint_source: trace
+                    .and_then(|t| t.taint_source.as_ref())
+                    .and_then(trace_step),
+                taint_sink: trace
+                    .and_then(|t| t.taint_sink.as_ref())
+                    .and_then(trace_step),
+                rule_id: result.check_id,
+                message: result.extra.message,
+                path: result.path,
+                metadata: result.extra.metadata,
+                rule_description: None,
+                raw,
+            };
+            findings.push(self.convert_match(semgrep_match, output.version.as_deref()));
+        }
+
+        Ok(ParseResult {
+            findings,
+            errors,
+            source_tool: self.source_tool().to_string(),
+            source_tool_version: output.version,
+            scanned_at: None,
+        })
+    }
+
+    fn parse_sarif(&self, document: Value) -> Result<ParseResult, anyhow::Error> {
+        let document: SarifDocument = serde_json::from_value(document)?;
+        let source_tool_version = document.runs.first().and_then(|r| {
+            r.tool
+                .driver
+                .semantic_version
+                .clone()
+                .or_else(|| r.tool.driver.version.clone())
+        });
+        let mut findings = Vec::new();
+        let mut errors = Vec::new();
+        let mut record_index = 0;
+
+        for run in &document.runs {
+            for raw in &run.results {
+                let index = record_index;
+                record_index += 1;
+                let result = match SarifResult::deserialize(raw) {
+                    Ok(result) => result,
+                    Err(e) => {
+                        errors.push(ParseError {
+                            record_index: index,
+                            field: "result".to_string(),
+                            message: format!("Invalid SARIF result: {e}"),
+                        });
+                        continue;
+                    }
+                };
+                if !result.suppressions.is_empty() {
+                    continue;
+                }
+                let rule = run
+                    .tool
+                    .driver
+                    .rules
+                    .iter()
+                    .find(|r| r.id == result.rule_id);
+                let semgrep_match = sarif_match(result, rule, raw.clone());
+                findings.push(self.convert_match(semgrep_match, source_tool_version.as_deref()));
+            }
+        }
+
+        let scanned_at = document
+            .runs
+            .iter()
+            .flat_map(|r| &r.invocations)
+            .filter_map(|i| i.start_time_utc.as_deref())
+            .filter_map(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&chrono::Utc))
+            .min();
+
+        Ok(ParseResult {
+            findings,
+            errors,
+            source_tool: self.source_tool().to_string(),
+            source_tool_version,
+            scanned_at,
+        })
+    }
+
+    fn convert_match(&self, m: SemgrepMatch, tool_version: Option<&str>) -> ParsedFinding {
+        let app_code = m.metadata.application_code.clone().unwrap_or_default();
+        let branch = "main";
+        let normalized_severity = self.map_severity(&m.severity);
+
+        let cwe_ids: Vec<String> = m.metadata.cwe.iter().filter_map(|c| cwe_id(c)).collect();
+        let owasp_category = latest_owasp_category(&m.metadata.owasp);
+        let confidence =
+            m.metadata
+                .confidence
+                .as_deref()
+                .and_then(|c| match c.to_uppercase().as_str() {
+                    "HIGH" => Some(ConfidenceLevel::High),
+                    "MEDIUM" => Some(ConfidenceLevel::Medium),
+                    "LOW" => Some(ConfidenceLevel::Low),
+                    _ => None,
+                });
+
+        // Rule ids are dotted paths; the last segment names the rule
+        let rule_name = m
+            .rule_id
+            .rsplit('.')
+            .next()
+            .unwrap_or(&m.rule_id)
+            .to_string();
+        let title = humanize(&rule_name);
+        let description = if m.message.trim().is_empty() {
+            m.rule_description.clone().unwrap_or_else(|| title.clone())
+        } else {
+            m.message.clone()
+        };
+
+        let fingerprint = fingerprint::compute_sast(&app_code, &m.path, &m.rule_id, branch);
+        let source_finding_id = m.match_id.clone().unwrap_or_else(|| {
+            format!(
+                "{}:{}:{}",
+                m.rule_id,
+                m.path,
+                m.line_start.map(|l| l.to_string()).unwrap_or_default()
+            )
+        });
+
+        let issue_type = m.metadata.category.as_deref().map(|category| {
+            match category.to_lowercase().as_str() {
+                "security"
+                    if m.metadata
+                        .subcategory
+                        .iter()
+                        .any(|s| s.eq_ignore_ascii_case("audit")) =>
+                {
+                    "SECURITY_HOTSPOT"
+                }
+                "security" => "VULNERABILITY",
+                "correctness" => "BUG",
+                _ => "CODE_SMELL",
+            }
+            .to_string()
+        });
+
+        let scanner_tags: Vec<String> = m
+            .metadata
+            .category
+            .iter()
+            .chain(&m.metadata.subcategory)
+            .chain(&m.metadata.technology)
+            .chain(&m.metadata.vulnerability_class)
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        let core = CreateFinding {
+            source_tool: self.source_tool().to_string(),
+            source_tool_version: tool_version.map(str::to_string),
+            source_finding_id,
+            finding_category: self.category(),
+            title,
+            description,
+            normalized_severity,
+            original_severity: m.severity.clone(),
+            cvss_score: None,
+            cvss_vector: None,
+            cwe_ids,
+            cve_ids: vec![],
+            owasp_category,
+            confidence,
+            fingerprint,
+            application_id: None, // Resolved during ingestion
+            tags: scanner_tags.clone(),
+            remediation_guidance: None,
+            raw_finding: m.raw,
+            metadata: serde_json::json!({
+                "app_code": app_code,
+                "file_path": m.path,
+                "rule_id": m.rule_id,
+            }),
+        };
+
+        let sast = CreateFindingSast {
+            file_path: m.path,
+            line_number_start: m.line_start,
+            line_number_end: m.line_end,
+            project: self.source_tool().to_string(),
+            rule_name,
+            rule_id: m.rule_id,
+            issue_type,
+            branch: Some(branch.to_string()),
+            source_url: m.metadata.source.or(m.metadata.shortlink),
+            scanner_creation_date: None,
+            baseline_date: None,
+            last_analysis_date: None,
+            code_snippet: m.code_snippet,
+            taint_source: m.taint_source,
+            taint_sink: m.taint_sink,
+            language: None,
+            framework: m.metadata.technology.into_iter().next(),
+            scanner_description: m.rule_description,
+            scanner_tags,
+            quality_gate: None,
+        };
+
+        ParsedFinding {
+            core,
+            category_data: CategoryData::Sast(sast),
+        }
+    }
+}
+
+/// A SARIF result with the metadata Semgrep exports on its rule: CWE and
+/// OWASP entries and the confidence travel as rule tags.
+fn sarif_match(result: SarifResult, rule: Option<&SarifRule>, raw: Value) -> SemgrepMatch {
+    let location = result.locations.first();
+    let region = location.and_then(SarifLocation::region);
+
+    let mut metadata = RuleMetadata::default();
+    if let Some(rule) = rule {
+        for tag in &rule.properties.tags {
+            let upper = tag.to_uppercase();
+            if upper.starts_with("CWE-") {
+                metadata.cwe.push(tag.clone());
+            } else if let Some(owasp) = upper.strip_prefix("OWASP-") {
+                metadata
+                    .owasp
+                    .push(tag[tag.len() - owasp.len()..].to_string());
+            } else if let Some(confidence) = upper.strip_suffix(" CONFIDENCE") {
+                metadata.confidence = Some(confidence.to_string());
+            } else {
+                metadata.technology.push(tag.clone());
+            }
+        }
+        if metadata.confidence.is_none() {
+            metadata.confidence = rule.properties.precision.clone();
+        }
+        metadata.source = rule.help_uri.clone();
+    }
+
+    // Thread flows run from the taint source to the sink
+    let steps: Vec<&SarifLocation> = result
+        .code_flows
+        .first()
+        .and_then(|f| f.thread_flows.first())
+        .map(|t| t.locations.iter().map(|l| &l.location).collect())
+        .unwrap_or_default();
+    let taint_source = steps.first().and_then(|l| l.describe());
+    let taint_sink = (steps.len() > 1)
+        .then(|| steps.last().and_then(|l| l.describe()))
+        .flatten();
+
+    SemgrepMatch {
+        rule_id: result.rule_id,
+        message: result.message.text,
+        severity: result
+            .level
+            .clone()
+            .or_else(|| {
+                rule.and_then(|r| r.default_configuration.as_ref())
+                    .and_then(|c| c.level.clone())
+            })
+            .unwrap_or_else(|| "warning".to_string()),
+        path: location
+            .and_then(SarifLocation::uri)
+            .unwrap_or_default()
+            .to_string(),
+        line_start: region.and_then(|r| r.start_line),
+        line_end: region.and_then(|r| r.end_line),
+        code_snippet: region
+            .and_then(|r| r.snippet.as_ref())
+            .map(|s| s.text.clone())
+            .filter(|s| is_reported(s)),
+        match_id: result
+            .fingerprints
+            .values()
+            .filter_map(Value::as_str)
+            .find(|f| is_reported(f))
+            .map(str::to_string),
+        metadata,
+        rule_description: rule
+            .and_then(|r| r.full_description.as_ref().or(r.help.as_ref()))
+            .map(|d| d.text.clone()),
+        taint_source,
+        taint_sink,
+        raw,
+    }
+}
+
+fn is_reported(value: &str) -> bool {
+    !value.trim().is_empty() && value.trim() != REQUIRES_LOGIN
+}
+
+fn describe_step(path: &str, line: Option<i32>, content: Option<&str>) -> Option<String> {
+    if path.is_empty() {
+        return None;
+    }
+    let location = match line {
+        Some(line) => format!("{path}:{line}"),
+        None => path.to_string(),
+    };
+    Some(match content.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(content) => format!("{location}: {content}"),
+        None => location,
+    })
+}
+
+/// The first `[location, content]` pair of a native JSON dataflow trace
+/// step. Steps are tagged arrays: `["CliLoc", [location, content]]` for a
+/// direct source or sink, `["CliCall", [[location, content], ...]]` when it
+/// is reached through a call.
+fn trace_step(step: &Value) -> Option<String> {
+    match step {
+        Value::Array(items) => {
+            if let [Value::Object(location), Value::String(content)] = items.as_slice() {
+                let path = location.get("path").and_then(Value::as_str)?;
+                let line = location
+                    .get("start")
+                    .and_then(|s| s.get("line"))
+                    .and_then(Value::as_i64)
+                    .and_then(|l| i32::try_from(l).ok());
+                return describe_step(path, line, Some(content));
+            }
+            items.iter().find_map(trace_step)
+        }
+        _ => None,
+    }
+}
+
+/// `CWE-89` from Semgrep's `CWE-89: Improper Neutralization of ...`.
+fn cwe_id(value: &str) -> Option<String> {
+    let value = value.trim();
+    value.get(..4).filter(|p| p.eq_ignore_ascii_case("CWE-"))?;
+    let digits: String = value[4..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    (!digits.is_empty()).then(|| format!("CWE-{digits}"))
+}
+
+/// The most recent Top 10 entry, as `A03:2021`, from Semgrep's list such as
+/// `["A01:2017 - Injection", "A03:2021 - Injection"]`.
+fn latest_owasp_category(entries: &[String]) -> Option<String> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let code = entry.split_whitespace().next()?;
+            let (category, year) = code.split_once(':')?;
+            let number = category
+                .strip_prefix(['A', 'a'])
+                .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))?;
+            let year: u16 = year.parse().ok()?;
+            Some((year, format!("A{number:0>2}:{year}")))
+        })
+        .enumerate()
+        .max_by_key(|(i, (year, _))| (*year, std::cmp::Reverse(*i)))
+        .map(|(_, (_, code))| code)
+}
+
+/// `Sql injection using db cursor execute` from the last segment of a rule
+/// id, `sql-injection-using-db-cursor-execute`.
+fn humanize(rule_name: &str) -> String {
+    let words = rule_name.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => rule_name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_sample() -> ParseResult {
+        let data = include_bytes!("../../tests/fixtures/semgrep_sample.json");
+        SemgrepParser::new().parse(data, InputFormat::Json).unwrap()
+    }
+
+    fn sarif_sample() -> ParseResult {
+        let data = include_bytes!("../../tests/fixtures/semgrep_sample.sarif");
+        SemgrepParser::new()
+            .parse(data, InputFormat::Sarif)
+            .unwrap()
+    }
+
+    fn sast(finding: &ParsedFinding) -> &CreateFindingSast {
+        match &finding.category_data {
+            CategoryData::Sast(sast) => sast,
+            _ => panic!("Expected SAST category data"),
+        }
+    }
+
+    #[test]
+    fn parse_json_skips_ignored_results() {
+        let result = json_sample();
+        assert_eq!(result.findings.len(), 3);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].record_index, 4);
+        assert_eq!(result.source_tool, "Semgrep");
+        assert_eq!(result.source_tool_version.as_deref(), Some("1.85.0"));
+    }
+
+    #[test]
+    fn json_rule_metadata_maps_to_finding() {
+        let result = json_sample();
+        let sqli = &result.findings[0];
+        assert_eq!(sqli.core.title, "Sqlalchemy execute raw query");
+        assert_eq!(sqli.core.normalized_severity, SeverityLevel::High);
+        assert_eq!(sqli.core.original_severity, "ERROR");
+        assert_eq!(sqli.core.cwe_ids, vec!["CWE-89".to_string()]);
+        assert_eq!(sqli.core.owasp_category.as_deref(), Some("A03:2021"));
+        assert_eq!(sqli.core.confidence, Some(ConfidenceLevel::High));
+        assert_eq!(sqli.core.source_finding_id, "3f1c9e0b7d");
+        assert_eq!(sqli.core.source_tool_version.as_deref(), Some("1.85.0"));
+        assert_eq!(sqli.core.fingerprint.len(), 64);
+
+        let sast = sast(sqli);
+        assert_eq!(sast.file_path, "app/db/users.py");
+        assert_eq!(sast.line_number_start, Some(42));
+        assert_eq!(sast.line_number_end, Some(43));
+        assert_eq!(sast.rule_name, "sqlalchemy-execute-raw-query");
+        assert_eq!(
+            sast.rule_id,
+            "python.sqlalchemy.security.sqlalchemy-execute-raw-query.sqlalchemy-execute-raw-query"
+        );
+        assert_eq!(sast.issue_type.as_deref(), Some("VULNERABILITY"));
+        assert_eq!(sast.framework.as_deref(), Some("sqlalchemy"));
+        assert_eq!(
+            sast.source_url.as_deref(),
+            Some("https://semgrep.dev/r/python.sqlalchemy.security.sqlalchemy-execute-raw-query.sqlalchemy-execute-raw-query")
+        );
+        assert!(sast.scanner_tags.contains(&"security".to_string()));
+    }
+
+    #[test]
+    fn json_taint_trace_gives_source_and_sink() {
+        let result = json_sample();
+        let sast = sast(&result.findings[1]);
+        assert_eq!(
+            sast.taint_source.as_deref(),
+            Some("app/views.py:17: request.args.get(\"name\")")
+        );
+        assert_eq!(
+            sast.taint_sink.as_deref(),
+            Some("app/views.py:21: render_template_string(page)")
+        );
+        assert_eq!(sast.issue_type.as_deref(), Some("VULNERABILITY"));
+    }
+
+    #[test]
+    fn json_placeholders_of_logged_out_runs_are_dropped() {
+        let result = json_sample();
+        let secret = &result.findings[2];
+        assert_eq!(
+            secret.core.source_finding_id,
+            "generic.secrets.security.detected-aws-access-key-id-value.detected-aws-access-key-id-value:config/settings.py:3"
+        );
+        assert!(sast(secret).code_snippet.is_none());
+        assert_eq!(secret.core.owasp_category.as_deref(), Some("A07:2021"));
+        assert_eq!(secret.core.cwe_ids, vec!["CWE-798".to_string()]);
+        assert_eq!(secret.core.confidence, Some(ConfidenceLevel::Low));
+        assert_eq!(sast(secret).issue_type.as_deref(), Some("SECURITY_HOTSPOT"));
+    }
+
+    #[test]
+    fn parse_sarif_maps_rule_tags() {
+        let result = sarif_sample();
+        assert_eq!(result.findings.len(), 2);
+        assert_eq!(result.source_tool, "Semgrep");
+        assert_eq!(result.source_tool_version.as_deref(), Some("1.85.0"));
+
+        let sqli = &result.findings[0];
+        assert_eq!(sqli.core.normalized_severity, SeverityLevel::High);
+        assert_eq!(sqli.core.cwe_ids, vec!["CWE-89".to_string()]);
+        assert_eq!(sqli.core.owasp_category.as_deref(), Some("A03:2021"));
+        assert_eq!(sqli.core.confidence, Some(ConfidenceLevel::High));
+        assert_eq!(sqli.core.source_finding_id, "9a2b7c");
+        let sast = sast(sqli);
+        assert_eq!(sast.code_snippet.as_deref(), Some("cursor.execute(query)"));
+        assert_eq!(
+            sast.scanner_description.as_deref(),
+            Some("User input reaches a raw SQL query.")
+        );
+        assert_eq!(
+            sast.taint_source.as_deref(),
+            Some("app/db/users.py:30: request.form[\"id\"]")
+        );
+        assert_eq!(
+            sast.taint_sink.as_deref(),
+            Some("app/db/users.py:42: cursor.execute(query)")
+        );
+    }
+
+    #[test]
+    fn json_format_accepts_sarif_logs() {
+        let data = include_bytes!("../../tests/fixtures/semgrep_sample.sarif");
+        let result = SemgrepParser::new().parse(data, InputFormat::Json).unwrap();
+        assert_eq!(result.findings.len(), 2);
+    }
+
+    #[test]
+    fn owasp_prefers_the_latest_edition() {
+        let entries = vec![
+            "A01:2017 - Injection".to_string(),
+            "A03:2021 - Injection".to_string(),
+            "not a category".to_string(),
+        ];
+        assert_eq!(latest_owasp_category(&entries).as_deref(), Some("A03:2021"));
+        assert_eq!(
+            latest_owasp_category(&["A1:2017".to_string()]).as_deref(),
+            Some("A01:2017")
+        );
+        assert_eq!(latest_owasp_category(&[]), None);
+    }
+
+    #[test]
+    fn semgrep_severity_mapping() {
+        let parser = SemgrepParser::new();
+        assert_eq!(parser.map_severity("ERROR"), SeverityLevel::High);
+        assert_eq!(parser.map_severity("WARNING"), SeverityLevel::Medium);
+        assert_eq!(parser.map_severity("INFO"), SeverityLevel::Low);
+        assert_eq!(parser.map_severity("CRITICAL"), SeverityLevel::Critical);
+        assert_eq!(parser.map_severity("note"), SeverityLevel::Low);
+    }
+
+    #[test]
+    fn rejects_unsupported_format() {
+        let parser = SemgrepParser::new();
+        assert!(parser.parse(b"", InputFormat::Csv).is_err());
+    }
+}
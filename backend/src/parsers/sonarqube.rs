@@ -93,6 +93,7 @@ impl SonarQubeParser {
             errors,
             source_tool: self.source_tool().to_string(),
             source_tool_version: None,
+            scanned_at: None,
         })
     }
 
@@ -124,6 +125,7 @@ impl SonarQubeParser {
             errors,
             source_tool: self.source_tool().to_string(),
             source_tool_version: None,
+            scanned_at: None,
         })
     }
 
@@ -204,6 +204,7 @@ impl TenableWasParser {
             errors,
             source_tool: self.source_tool().to_string(),
             source_tool_version: None,
+            scanned_at: None,
         })
     }
 
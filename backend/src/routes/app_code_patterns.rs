@@ -0,0 +1,170 @@
+//! App code pattern routes: CRUD, priority reordering, and dry runs of the
+//! regex patterns that resolve app codes from scanner fields.
+//!
+//! Any authenticated user can view patterns; managers maintain and test them.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::app_code_pattern::{
+    AppCodePattern, AppCodePatternFilters, CreateAppCodePattern, ReorderAppCodePatterns,
+    TestAppCodePatterns, UpdateAppCodePattern,
+};
+use crate::services::app_code_pattern::{self, PatternTestResult};
+use crate::AppState;
+
+/// GET /api/v1/app-code-patterns — list patterns by tool, highest priority first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/app-code-patterns",
+    tag = "app-code-patterns",
+    params(AppCodePatternFilters),
+    responses(
+        (status = 200, description = "Patterns by source tool and priority", body = ApiResponse<Vec<AppCodePattern>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(filters): Query<AppCodePatternFilters>,
+) -> Result<Json<ApiResponse<Vec<AppCodePattern>>>, AppError> {
+    let patterns = app_code_pattern::list(&state.db_read, &filters).await?;
+    Ok(ApiResponse::success(patterns))
+}
+
+/// POST /api/v1/app-code-patterns — create a pattern (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/app-code-patterns",
+    tag = "app-code-patterns",
+    request_body = CreateAppCodePattern,
+    responses(
+        (status = 200, description = "Created pattern", body = ApiResponse<AppCodePattern>),
+        (status = 400, description = "Invalid regex or no app_code capture group")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<CreateAppCodePattern>,
+) -> Result<Json<ApiResponse<AppCodePattern>>, AppError> {
+    let pattern = app_code_pattern::create(&state.db, &body).await?;
+    Ok(ApiResponse::success(pattern))
+}
+
+/// GET /api/v1/app-code-patterns/:id — get a pattern.
+#[utoipa::path(
+    get,
+    path = "/api/v1/app-code-patterns/{id}",
+    tag = "app-code-patterns",
+    params(("id" = Uuid, Path, description = "App code pattern ID")),
+    responses(
+        (status = 200, description = "Pattern", body = ApiResponse<AppCodePattern>),
+        (status = 404, description = "Pattern not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<AppCodePattern>>, AppError> {
+    let pattern = app_code_pattern::find_by_id(&state.db, id).await?;
+    Ok(ApiResponse::success(pattern))
+}
+
+/// PUT /api/v1/app-code-patterns/:id — update a pattern (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/app-code-patterns/{id}",
+    tag = "app-code-patterns",
+    params(("id" = Uuid, Path, description = "App code pattern ID")),
+    request_body = UpdateAppCodePattern,
+    responses(
+        (status = 200, description = "Updated pattern", body = ApiResponse<AppCodePattern>),
+        (status = 400, description = "Invalid regex or no app_code capture group"),
+        (status = 404, description = "Pattern not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateAppCodePattern>,
+) -> Result<Json<ApiResponse<AppCodePattern>>, AppError> {
+    let pattern = app_code_pattern::update(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(pattern))
+}
+
+/// POST /api/v1/app-code-patterns/:id/deactivate — stop using a pattern (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/app-code-patterns/{id}/deactivate",
+    tag = "app-code-patterns",
+    params(("id" = Uuid, Path, description = "App code pattern ID")),
+    responses(
+        (status = 200, description = "Deactivated pattern", body = ApiResponse<AppCodePattern>),
+        (status = 404, description = "Pattern not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn deactivate(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<AppCodePattern>>, AppError> {
+    let pattern = app_code_pattern::deactivate(&state.db, id).await?;
+    Ok(ApiResponse::success(pattern))
+}
+
+/// PUT /api/v1/app-code-patterns/order — reorder a tool's patterns (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/app-code-patterns/order",
+    tag = "app-code-patterns",
+    request_body = ReorderAppCodePatterns,
+    responses(
+        (status = 200, description = "The tool's patterns with their new priorities", body = ApiResponse<Vec<AppCodePattern>>),
+        (status = 400, description = "The order does not list each of the tool's patterns once")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn reorder(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<ReorderAppCodePatterns>,
+) -> Result<Json<ApiResponse<Vec<AppCodePattern>>>, AppError> {
+    let patterns = app_code_pattern::reorder(&state.db, &body).await?;
+    Ok(ApiResponse::success(patterns))
+}
+
+/// POST /api/v1/app-code-patterns/test — run a pattern set against sample
+/// metadata and report what would resolve (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/app-code-patterns/test",
+    tag = "app-code-patterns",
+    request_body = TestAppCodePatterns,
+    responses(
+        (status = 200, description = "Per-pattern outcomes and the resolved app code", body = ApiResponse<PatternTestResult>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn test(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<TestAppCodePatterns>,
+) -> Result<Json<ApiResponse<PatternTestResult>>, AppError> {
+    let result = app_code_pattern::test_patterns(&state.db_read, &body).await?;
+    Ok(ApiResponse::success(result))
+}
@@ -1,73 +1,239 @@
-//! Application registry routes: CRUD, bulk import, and APM CSV import.
+//! Application registry routes: CRUD, bulk import, and APM CSV import with preview.
 
 use axum::{
     extract::{Multipart, Path, Query, State},
+    http::HeaderMap,
+    response::Response,
     Json,
 };
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
 use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
 use crate::models::application::{Application, ApplicationSummary, CreateApplication, UpdateApplication};
+use crate::models::ownership::OwnershipRole;
 use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::app_posture::{self, AppPosture};
+use crate::services::apm_preview::{self, ApmImportPreview, ApmPreviewParams};
+use crate::services::app_stats::{self, AppStats};
 use crate::services::application::{
     self as app_service, ApmFieldMapping, ApmFormat, ApmImportResult, ApplicationFilters,
-    ImportResult,
+    ApplicationSort, ImportResult, MergeResult,
 };
+use crate::services::executive_report::ReportPeriod;
+use crate::services::ownership;
 use crate::AppState;
 
 /// GET /api/v1/applications — list applications with filters and pagination.
+/// Developers only see the applications they are assigned to.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications",
+    tag = "applications",
+    params(Pagination, ApplicationFilters, ApplicationSort),
+    responses(
+        (status = 200, description = "Page of applications", body = ApiResponse<PagedResult<ApplicationSummary>>)
+    )
+)]
 pub async fn list(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Query(pagination): Query<Pagination>,
-    Query(filters): Query<ApplicationFilters>,
+    Query(mut filters): Query<ApplicationFilters>,
+    Query(sort): Query<ApplicationSort>,
 ) -> Result<Json<ApiResponse<PagedResult<ApplicationSummary>>>, AppError> {
-    let result = app_service::list(&state.db, &filters, &pagination).await?;
+    filters.visible_to = ownership::read_scope(&current_user);
+    let result = app_service::list(&state.db_read, &filters, &sort, &pagination).await?;
     Ok(ApiResponse::success(result))
 }
 
 /// POST /api/v1/applications — create a new application (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/applications",
+    tag = "applications",
+    request_body = CreateApplication,
+    responses(
+        (status = 200, description = "Created application", body = ApiResponse<Application>),
+        (status = 409, description = "Application code already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn create(
     State(state): State<AppState>,
     RequireManager(_manager): RequireManager,
-    Json(body): Json<CreateApplication>,
+    ValidatedJson(body): ValidatedJson<CreateApplication>,
 ) -> Result<Json<ApiResponse<Application>>, AppError> {
     let app = app_service::create(&state.db, &body).await?;
     Ok(ApiResponse::success(app))
 }
 
-/// GET /api/v1/applications/:id — get application by ID.
+/// GET /api/v1/applications/:id — get application by ID, honouring `If-None-Match`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}",
+    tag = "applications",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Application", body = ApiResponse<Application>),
+        (status = 304, description = "Application unchanged since the given ETag"),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    )
+)]
 pub async fn get_by_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ApiResponse<Application>>, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    ownership::authorize(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
     let app = app_service::find_by_id(&state.db, id).await?;
-    Ok(ApiResponse::success(app))
+    ApiResponse::success_with_etag(app, &headers)
+}
+
+/// Query parameters for the posture endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PostureParams {
+    /// Window for the risk trend (defaults to `quarter`).
+    #[serde(default)]
+    pub period: ReportPeriod,
+}
+
+/// GET /api/v1/applications/:id/posture — combined security posture for app owners.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/posture",
+    tag = "applications",
+    params(("id" = Uuid, Path, description = "Application ID"), PostureParams),
+    responses(
+        (status = 200, description = "Application security posture", body = ApiResponse<AppPosture>),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    )
+)]
+pub async fn posture(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<PostureParams>,
+) -> Result<Json<ApiResponse<AppPosture>>, AppError> {
+    ownership::authorize(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let posture = app_posture::build(&state.db_read, id, params.period).await?;
+    Ok(ApiResponse::success(posture))
+}
+
+/// GET /api/v1/applications/:id/stats — open findings by severity, status
+/// and category, SLA posture, last ingestion per tool, and risk trend.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/stats",
+    tag = "applications",
+    params(("id" = Uuid, Path, description = "Application ID"), PostureParams),
+    responses(
+        (status = 200, description = "Application statistics", body = ApiResponse<AppStats>),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    )
+)]
+pub async fn stats(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<PostureParams>,
+) -> Result<Json<ApiResponse<AppStats>>, AppError> {
+    ownership::authorize(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let stats = app_stats::build(&state.db_read, id, params.period).await?;
+    Ok(ApiResponse::success(stats))
 }
 
 /// PUT /api/v1/applications/:id — update application (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/applications/{id}",
+    tag = "applications",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    request_body = UpdateApplication,
+    responses(
+        (status = 200, description = "Updated application", body = ApiResponse<Application>),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn update(
     State(state): State<AppState>,
     RequireManager(_manager): RequireManager,
     Path(id): Path<Uuid>,
-    Json(body): Json<UpdateApplication>,
+    ValidatedJson(body): ValidatedJson<UpdateApplication>,
 ) -> Result<Json<ApiResponse<Application>>, AppError> {
     let app = app_service::update(&state.db, id, &body).await?;
     Ok(ApiResponse::success(app))
 }
 
+/// POST /api/v1/applications/:id/merge/:dup_id — merge a duplicate into the canonical application (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/applications/{id}/merge/{dup_id}",
+    tag = "applications",
+    params(
+        ("id" = Uuid, Path, description = "Canonical application ID, kept by the merge"),
+        ("dup_id" = Uuid, Path, description = "Duplicate application ID, deleted by the merge")
+    ),
+    responses(
+        (status = 200, description = "Merged application and moved record counts", body = ApiResponse<MergeResult>),
+        (status = 400, description = "Both IDs refer to the same application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn merge(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    Path((keep_id, dup_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<MergeResult>>, AppError> {
+    let result =
+        app_service::merge(&state.db, keep_id, dup_id, manager.id, &manager.username).await?;
+    Ok(ApiResponse::success(result))
+}
+
 /// GET /api/v1/applications/code/:code — get application by app_code.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/code/{code}",
+    tag = "applications",
+    params(("code" = String, Path, description = "Application code")),
+    responses(
+        (status = 200, description = "Application", body = ApiResponse<Application>),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    )
+)]
 pub async fn get_by_code(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(code): Path<String>,
 ) -> Result<Json<ApiResponse<Application>>, AppError> {
     let app = app_service::find_by_app_code(&state.db, &code)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Application with code '{code}' not found")))?;
+    ownership::authorize(&state.db, &current_user, app.id, OwnershipRole::Viewer).await?;
     Ok(ApiResponse::success(app))
 }
 
 /// POST /api/v1/applications/import — bulk import from JSON array (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/applications/import",
+    tag = "applications",
+    request_body = Vec<CreateApplication>,
+    responses((status = 200, description = "Import outcome", body = ApiResponse<ImportResult>)),
+    security(("bearer_auth" = []))
+)]
 pub async fn import_bulk(
     State(state): State<AppState>,
     RequireManager(_manager): RequireManager,
@@ -77,12 +243,66 @@ pub async fn import_bulk(
     Ok(ApiResponse::success(result))
 }
 
+/// Multipart body for the APM import endpoint.
+#[derive(ToSchema)]
+pub struct ApmImportForm {
+    /// APM export; `.xlsx` files are read as spreadsheets, anything else as CSV.
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    /// Optional column mapping overriding the defaults.
+    #[schema(value_type = Option<ApmFieldMapping>)]
+    pub mapping: Option<String>,
+}
+
 /// POST /api/v1/applications/import/apm — import from corporate APM CSV/XLSX (manager+, multipart).
+#[utoipa::path(
+    post,
+    path = "/api/v1/applications/import/apm",
+    tag = "applications",
+    request_body(content = ApmImportForm, content_type = "multipart/form-data"),
+    responses((status = 200, description = "Import outcome", body = ApiResponse<ApmImportResult>)),
+    security(("bearer_auth" = []))
+)]
 pub async fn import_apm(
     State(state): State<AppState>,
     RequireManager(_manager): RequireManager,
-    mut multipart: Multipart,
+    multipart: Multipart,
 ) -> Result<Json<ApiResponse<ApmImportResult>>, AppError> {
+    let (data, mapping, format) = read_apm_form(multipart).await?;
+    let result = app_service::import_apm(&state.db, &data, &mapping, &format).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// POST /api/v1/applications/import/apm/preview — detected headers, proposed
+/// column mapping and the first rows of an APM file, without importing it
+/// (manager+, multipart).
+#[utoipa::path(
+    post,
+    path = "/api/v1/applications/import/apm/preview",
+    tag = "applications",
+    params(ApmPreviewParams),
+    request_body(content = ApmImportForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Import preview", body = ApiResponse<ApmImportPreview>),
+        (status = 400, description = "Missing or unreadable file")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn preview_apm(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Query(params): Query<ApmPreviewParams>,
+    multipart: Multipart,
+) -> Result<Json<ApiResponse<ApmImportPreview>>, AppError> {
+    let (data, mapping, format) = read_apm_form(multipart).await?;
+    let preview = apm_preview::preview(&state.db_read, &data, &mapping, &format, &params).await?;
+    Ok(ApiResponse::success(preview))
+}
+
+/// Read the file, optional mapping and format of an APM import form.
+async fn read_apm_form(
+    mut multipart: Multipart,
+) -> Result<(Vec<u8>, ApmFieldMapping, ApmFormat), AppError> {
     let mut file_data: Option<Vec<u8>> = None;
     let mut filename: Option<String> = None;
     let mut mapping = ApmFieldMapping::default();
@@ -125,15 +345,31 @@ pub async fn import_apm(
         .and_then(ApmFormat::from_filename)
         .unwrap_or(ApmFormat::Csv);
 
-    let result = app_service::import_apm(&state.db, &data, &mapping, &format).await?;
-    Ok(ApiResponse::success(result))
+    Ok((data, mapping, format))
 }
 
-/// GET /api/v1/applications/unverified — list unverified stub applications.
+/// GET /api/v1/applications/unverified — list unverified stub applications
+/// (any role that reads every application).
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/unverified",
+    tag = "applications",
+    params(Pagination),
+    responses(
+        (status = 200, description = "Page of unverified stub applications", body = ApiResponse<PagedResult<ApplicationSummary>>),
+        (status = 403, description = "Role limited to assigned applications")
+    )
+)]
 pub async fn list_unverified(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Query(pagination): Query<Pagination>,
 ) -> Result<Json<ApiResponse<PagedResult<ApplicationSummary>>>, AppError> {
-    let result = app_service::list_unverified(&state.db, &pagination).await?;
+    if ownership::read_scope(&current_user).is_some() {
+        return Err(AppError::Forbidden(
+            "Unverified applications are listed to AppSec and audit roles only".to_string(),
+        ));
+    }
+    let result = app_service::list_unverified(&state.db_read, &pagination).await?;
     Ok(ApiResponse::success(result))
 }
@@ -0,0 +1,174 @@
+//! Asset inventory routes: CRUD and application links.
+//!
+//! Any authenticated user can browse assets; managers maintain them, since
+//! linked assets decide which application ingested findings belong to.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::asset::{Asset, CreateAsset, LinkAsset, UpdateAsset};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::asset::{self, AssetFilters};
+use crate::AppState;
+
+/// GET /api/v1/assets — list assets with filters and pagination.
+#[utoipa::path(
+    get,
+    path = "/api/v1/assets",
+    tag = "assets",
+    params(Pagination, AssetFilters),
+    responses(
+        (status = 200, description = "Page of assets by type and identifier", body = ApiResponse<PagedResult<Asset>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(pagination): Query<Pagination>,
+    Query(filters): Query<AssetFilters>,
+) -> Result<Json<ApiResponse<PagedResult<Asset>>>, AppError> {
+    let result = asset::list(&state.db_read, &filters, &pagination).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// POST /api/v1/assets — create an asset (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/assets",
+    tag = "assets",
+    request_body = CreateAsset,
+    responses(
+        (status = 200, description = "Created asset", body = ApiResponse<Asset>),
+        (status = 400, description = "Invalid identifier"),
+        (status = 404, description = "Linked application not found"),
+        (status = 409, description = "Asset already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<CreateAsset>,
+) -> Result<Json<ApiResponse<Asset>>, AppError> {
+    let created = asset::create(&state.db, &body).await?;
+    Ok(ApiResponse::success(created))
+}
+
+/// GET /api/v1/assets/:id — get an asset.
+#[utoipa::path(
+    get,
+    path = "/api/v1/assets/{id}",
+    tag = "assets",
+    params(("id" = Uuid, Path, description = "Asset ID")),
+    responses(
+        (status = 200, description = "Asset", body = ApiResponse<Asset>),
+        (status = 404, description = "Asset not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Asset>>, AppError> {
+    let found = asset::find_by_id(&state.db, id).await?;
+    Ok(ApiResponse::success(found))
+}
+
+/// PUT /api/v1/assets/:id — update an asset's identifier or description (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/assets/{id}",
+    tag = "assets",
+    params(("id" = Uuid, Path, description = "Asset ID")),
+    request_body = UpdateAsset,
+    responses(
+        (status = 200, description = "Updated asset", body = ApiResponse<Asset>),
+        (status = 400, description = "Invalid identifier"),
+        (status = 404, description = "Asset not found"),
+        (status = 409, description = "Another asset has this identifier")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateAsset>,
+) -> Result<Json<ApiResponse<Asset>>, AppError> {
+    let updated = asset::update(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(updated))
+}
+
+/// PUT /api/v1/assets/:id/application — link or unlink an application (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/assets/{id}/application",
+    tag = "assets",
+    params(("id" = Uuid, Path, description = "Asset ID")),
+    request_body = LinkAsset,
+    responses(
+        (status = 200, description = "Asset with its new link", body = ApiResponse<Asset>),
+        (status = 404, description = "Asset or application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn link(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    Json(body): Json<LinkAsset>,
+) -> Result<Json<ApiResponse<Asset>>, AppError> {
+    let linked = asset::link(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(linked))
+}
+
+/// DELETE /api/v1/assets/:id — delete an asset (manager+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/assets/{id}",
+    tag = "assets",
+    params(("id" = Uuid, Path, description = "Asset ID")),
+    responses(
+        (status = 200, description = "Asset deleted"),
+        (status = 404, description = "Asset not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    asset::delete(&state.db, id).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// GET /api/v1/applications/:id/assets — list an application's assets.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/assets",
+    tag = "assets",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Linked assets", body = ApiResponse<Vec<Asset>>),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_for_application(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Asset>>>, AppError> {
+    let assets = asset::list_for_application(&state.db_read, id).await?;
+    Ok(ApiResponse::success(assets))
+}
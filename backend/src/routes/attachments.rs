@@ -0,0 +1,182 @@
+//! Finding attachment routes: evidence upload, listing, download, and deletion.
+//!
+//! Analysts and above can upload. Listing and downloading are open to every
+//! role that works with findings, on findings of applications the caller may
+//! view; executives and service accounts are refused.
+
+use axum::{
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireAnalyst;
+use crate::models::attachment::FindingAttachment;
+use crate::models::ownership::OwnershipRole;
+use crate::services::{attachment, ownership};
+use crate::AppState;
+
+/// Multipart body for the upload endpoint.
+#[derive(ToSchema)]
+pub struct AttachmentUploadForm {
+    /// Evidence file; the extension determines the stored content type.
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// POST /api/v1/findings/:id/attachments — upload an evidence file (analyst+, multipart).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/{id}/attachments",
+    tag = "attachments",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    request_body(content = AttachmentUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Stored attachment", body = ApiResponse<FindingAttachment>),
+        (status = 400, description = "Missing file, unsupported type, or file too large"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload(
+    State(state): State<AppState>,
+    RequireAnalyst(user): RequireAnalyst,
+    Path(finding_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<FindingAttachment>>, AppError> {
+    let mut file: Option<(String, Vec<u8>)> = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::Validation(format!("Multipart error: {e}")))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let data = field
+            .bytes()
+            .await
+            .map_err(|e| AppError::Validation(format!("Failed to read file: {e}")))?;
+        file = Some((file_name, data.to_vec()));
+    }
+
+    let (file_name, data) =
+        file.ok_or_else(|| AppError::Validation("Missing 'file' field".to_string()))?;
+    let stored = attachment::upload(
+        &state.db,
+        &state.attachments,
+        finding_id,
+        &file_name,
+        data,
+        state.config.attachment_max_bytes,
+        user.id,
+    )
+    .await?;
+    Ok(ApiResponse::success(stored))
+}
+
+/// GET /api/v1/findings/:id/attachments — list a finding's attachments.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/attachments",
+    tag = "attachments",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "Attachment metadata, oldest first", body = ApiResponse<Vec<FindingAttachment>>),
+        (status = 403, description = "Role cannot access attachments, or not assigned to the finding's application")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(finding_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<FindingAttachment>>>, AppError> {
+    attachment::ensure_can_view(&current_user.role)?;
+    ownership::authorize_finding(&state.db, &current_user, finding_id, OwnershipRole::Viewer)
+        .await?;
+    let attachments = attachment::list(&state.db, finding_id).await?;
+    Ok(ApiResponse::success(attachments))
+}
+
+/// GET /api/v1/findings/:id/attachments/:attachment_id — download an attachment.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/attachments/{attachment_id}",
+    tag = "attachments",
+    params(
+        ("id" = Uuid, Path, description = "Finding ID"),
+        ("attachment_id" = Uuid, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 200, description = "Attachment contents", content_type = "application/octet-stream"),
+        (status = 403, description = "Role cannot access attachments, or not assigned to the finding's application"),
+        (status = 404, description = "Attachment not found on this finding")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn download(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path((finding_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, AppError> {
+    attachment::ensure_can_view(&current_user.role)?;
+    ownership::authorize_finding(&state.db, &current_user, finding_id, OwnershipRole::Viewer)
+        .await?;
+    let (meta, data) =
+        attachment::download(&state.db, &state.attachments, finding_id, attachment_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, meta.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                attachment::content_disposition(&meta.file_name),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+/// DELETE /api/v1/findings/:id/attachments/:attachment_id — delete an attachment (uploader or manager+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/findings/{id}/attachments/{attachment_id}",
+    tag = "attachments",
+    params(
+        ("id" = Uuid, Path, description = "Finding ID"),
+        ("attachment_id" = Uuid, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 200, description = "Attachment deleted"),
+        (status = 403, description = "Caller is neither the uploader nor a manager"),
+        (status = 404, description = "Attachment not found on this finding")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path((finding_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    attachment::delete(
+        &state.db,
+        &state.attachments,
+        finding_id,
+        attachment_id,
+        current_user.id,
+        &current_user.role,
+    )
+    .await?;
+    Ok(ApiResponse::success(()))
+}
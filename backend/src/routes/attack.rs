@@ -0,0 +1,85 @@
+//! ATT&CK mapping routes: CAPEC and ATT&CK catalog imports.
+
+use axum::{
+    extract::{Multipart, State},
+    Json,
+};
+use utoipa::ToSchema;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAdmin;
+use crate::models::attack::AttackImportResult;
+use crate::services::attack;
+use crate::AppState;
+
+/// Multipart body for the catalog imports.
+#[derive(ToSchema)]
+pub struct AttackCatalogForm {
+    /// CAPEC CSV export (`1000.csv`) or ATT&CK STIX bundle (`enterprise-attack.json`).
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// Read the `file` field of a multipart upload.
+async fn read_file(mut multipart: Multipart) -> Result<Vec<u8>, AppError> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::Validation(format!("Multipart error: {e}")))?
+    {
+        if field.name() == Some("file") {
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|e| AppError::Validation(format!("Failed to read file: {e}")))?;
+            return Ok(bytes.to_vec());
+        }
+    }
+    Err(AppError::Validation(
+        "Missing 'file' field in multipart request".to_string(),
+    ))
+}
+
+/// POST /api/v1/attack/capec/import — load CAPEC patterns and remap findings (admin, multipart).
+#[utoipa::path(
+    post,
+    path = "/api/v1/attack/capec/import",
+    tag = "attack",
+    request_body(content = AttackCatalogForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Import outcome", body = ApiResponse<AttackImportResult>),
+        (status = 400, description = "Missing file or unrecognized CSV")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import_capec(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    multipart: Multipart,
+) -> Result<Json<ApiResponse<AttackImportResult>>, AppError> {
+    let data = read_file(multipart).await?;
+    let result = attack::import_capec(&state.db, &data).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// POST /api/v1/attack/techniques/import — load ATT&CK technique names and tactics (admin, multipart).
+#[utoipa::path(
+    post,
+    path = "/api/v1/attack/techniques/import",
+    tag = "attack",
+    request_body(content = AttackCatalogForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Import outcome", body = ApiResponse<AttackImportResult>),
+        (status = 400, description = "Missing file or invalid STIX bundle")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import_techniques(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    multipart: Multipart,
+) -> Result<Json<ApiResponse<AttackImportResult>>, AppError> {
+    let data = read_file(multipart).await?;
+    let result = attack::import_techniques(&state.db, &data).await?;
+    Ok(ApiResponse::success(result))
+}
@@ -11,30 +11,80 @@ use uuid::Uuid;
 
 use crate::errors::{ApiResponse, AppError};
 use crate::middleware::auth::CurrentUser;
+use crate::models::ownership::OwnershipRole;
 use crate::models::pagination::{PagedResult, Pagination};
 use crate::services::attack_chains::{
-    self, AppAttackChainDetail, AppAttackChainSummary, AttackChainFilters,
+    self, AppAttackChainDetail, AppAttackChainSummary, AppAttackTactics, AttackChainFilters,
 };
+use crate::services::ownership;
 use crate::AppState;
 
 /// GET /api/v1/attack-chains -- list applications with attack chain summaries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/attack-chains",
+    tag = "attack-chains",
+    params(Pagination, AttackChainFilters),
+    responses(
+        (status = 200, description = "Page of per-application attack chain summaries", body = ApiResponse<PagedResult<AppAttackChainSummary>>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn list(
     State(state): State<AppState>,
-    _user: CurrentUser,
+    current_user: CurrentUser,
     Query(pagination): Query<Pagination>,
-    Query(filters): Query<AttackChainFilters>,
+    Query(mut filters): Query<AttackChainFilters>,
 ) -> Result<Json<ApiResponse<PagedResult<AppAttackChainSummary>>>, AppError> {
-    let result = attack_chains::list_summaries(&state.db, &filters, &pagination).await?;
+    filters.visible_to = ownership::read_scope(&current_user);
+    let result = attack_chains::list_summaries(&state.db_read, &filters, &pagination).await?;
     Ok(ApiResponse::success(result))
 }
 
 /// GET /api/v1/attack-chains/:app_id -- get attack chains for one application.
+#[utoipa::path(
+    get,
+    path = "/api/v1/attack-chains/{app_id}",
+    tag = "attack-chains",
+    params(("app_id" = Uuid, Path, description = "Application ID"), AttackChainFilters),
+    responses(
+        (status = 200, description = "Attack chains of the application", body = ApiResponse<AppAttackChainDetail>),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_by_app(
     State(state): State<AppState>,
-    _user: CurrentUser,
+    current_user: CurrentUser,
     Path(app_id): Path<Uuid>,
     Query(filters): Query<AttackChainFilters>,
 ) -> Result<Json<ApiResponse<AppAttackChainDetail>>, AppError> {
-    let detail = attack_chains::get_by_app(&state.db, app_id, &filters).await?;
+    ownership::authorize(&state.db, &current_user, app_id, OwnershipRole::Viewer).await?;
+    let detail = attack_chains::get_by_app(&state.db_read, app_id, &filters).await?;
     Ok(ApiResponse::success(detail))
 }
+
+/// GET /api/v1/attack-chains/:app_id/tactics -- attack chains grouped by ATT&CK tactic.
+#[utoipa::path(
+    get,
+    path = "/api/v1/attack-chains/{app_id}/tactics",
+    tag = "attack-chains",
+    params(("app_id" = Uuid, Path, description = "Application ID"), AttackChainFilters),
+    responses(
+        (status = 200, description = "Chains per ATT&CK tactic, in kill chain order", body = ApiResponse<AppAttackTactics>),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_tactics_by_app(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(app_id): Path<Uuid>,
+    Query(filters): Query<AttackChainFilters>,
+) -> Result<Json<ApiResponse<AppAttackTactics>>, AppError> {
+    ownership::authorize(&state.db, &current_user, app_id, OwnershipRole::Viewer).await?;
+    let tactics = attack_chains::get_tactics_by_app(&state.db_read, app_id, &filters).await?;
+    Ok(ApiResponse::success(tactics))
+}
@@ -0,0 +1,108 @@
+//! Audit log routes: paginated browsing and filtered export for audit
+//! evidence collection.
+
+use axum::{
+    extract::{Query, State},
+    response::Response,
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAdmin;
+use crate::models::audit::AuditLog;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::routes::exports::attachment;
+use crate::services::audit_log::{self, AuditLogFilters};
+use crate::AppState;
+
+/// Output format for the audit log export.
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// Query parameters for the audit log export.
+#[derive(Debug, Deserialize)]
+pub struct AuditExportParams {
+    #[serde(default)]
+    pub format: AuditExportFormat,
+    #[serde(flatten)]
+    pub filters: AuditLogFilters,
+}
+
+/// GET /api/v1/audit-log — page through audit entries, newest first (admin only).
+///
+/// API request entries have `entity_type=api_request` and the HTTP method as
+/// `action`; their `details` hold the path, status, latency, and body summary.
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit-log",
+    tag = "audit-log",
+    params(AuditLogFilters, Pagination),
+    responses(
+        (status = 200, description = "Page of audit entries", body = ApiResponse<PagedResult<AuditLog>>),
+        (status = 400, description = "Invalid date range")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Query(filters): Query<AuditLogFilters>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ApiResponse<PagedResult<AuditLog>>>, AppError> {
+    let page = audit_log::list(&state.db_read, &filters, &pagination).await?;
+    Ok(ApiResponse::success(page))
+}
+
+/// GET /api/v1/audit-log/export — export audit entries (admin only).
+///
+/// Filters: `from`, `to` (RFC 3339), `actor_id`, `actor_name`, `entity_type`,
+/// `action`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit-log/export",
+    tag = "audit-log",
+    params(
+        ("format" = Option<AuditExportFormat>, Query, description = "Export format (defaults to `csv`)"),
+        AuditLogFilters
+    ),
+    responses(
+        (status = 200, description = "Audit log download", content(
+            (String = "text/csv"),
+            (Vec<AuditLog> = "application/json")
+        )),
+        (status = 400, description = "Invalid date range")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Query(params): Query<AuditExportParams>,
+) -> Result<Response, AppError> {
+    let entries = audit_log::list_for_export(&state.db_read, &params.filters).await?;
+    let stamp = chrono::Utc::now().format("%Y%m%d");
+
+    match params.format {
+        AuditExportFormat::Csv => Ok(attachment(
+            "text/csv; charset=utf-8",
+            &format!("audit_log_{stamp}.csv"),
+            audit_log::encode_csv(&entries)?,
+        )),
+        AuditExportFormat::Json => {
+            let body = serde_json::to_vec_pretty(&entries)
+                .map_err(|e| AppError::Internal(format!("JSON serialization failed: {e}")))?;
+            Ok(attachment(
+                "application/json",
+                &format!("audit_log_{stamp}.json"),
+                body,
+            ))
+        }
+    }
+}
@@ -2,27 +2,39 @@
 
 use axum::{extract::State, Json};
 use serde::Deserialize;
+use utoipa::ToSchema;
 
 use crate::errors::{ApiResponse, AppError};
 use crate::middleware::auth::CurrentUser;
 use crate::middleware::rbac::RequireAdmin;
+use crate::middleware::validation::ValidatedJson;
 use crate::models::user::{CreateUser, UserResponse};
 use crate::services::auth as auth_service;
 use crate::services::auth::TokenPair;
 use crate::AppState;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginRequest {
     pub username: String,
     pub password: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct RefreshRequest {
     pub refresh_token: String,
 }
 
 /// POST /api/v1/auth/login
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Access and refresh tokens", body = ApiResponse<TokenPair>),
+        (status = 401, description = "Invalid credentials or locked account")
+    )
+)]
 pub async fn login(
     State(state): State<AppState>,
     Json(body): Json<LoginRequest>,
@@ -41,12 +53,23 @@ pub async fn login(
 }
 
 /// POST /api/v1/auth/refresh
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/refresh",
+    tag = "auth",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "New token pair", body = ApiResponse<TokenPair>),
+        (status = 401, description = "Invalid or expired refresh token")
+    )
+)]
 pub async fn refresh(
     State(state): State<AppState>,
     Json(body): Json<RefreshRequest>,
 ) -> Result<Json<ApiResponse<TokenPair>>, AppError> {
     let tokens = auth_service::refresh_token(
         &state.db,
+        state.redis.as_ref(),
         &body.refresh_token,
         &state.config.jwt_secret,
         state.config.jwt_access_token_expiry_secs,
@@ -57,24 +80,72 @@ pub async fn refresh(
     Ok(ApiResponse::success(tokens))
 }
 
-/// POST /api/v1/auth/logout — client-side token discard (stateless JWT)
-pub async fn logout() -> Json<ApiResponse<&'static str>> {
-    // With stateless JWT, logout is handled client-side by discarding tokens.
-    // A token blocklist could be added via Redis if needed.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LogoutRequest {
+    pub refresh_token: Option<String>,
+}
+
+/// POST /api/v1/auth/logout — revoke the refresh token, if given
+///
+/// Access tokens stay valid until they expire; clients discard them.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout",
+    tag = "auth",
+    request_body(content = Option<LogoutRequest>),
+    responses((status = 200, description = "Logged out", body = ApiResponse<String>))
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    body: Option<Json<LogoutRequest>>,
+) -> Json<ApiResponse<&'static str>> {
+    let token = body.and_then(|Json(body)| body.refresh_token);
+    if let Some(token) = token {
+        // An invalid or already revoked token needs no revocation
+        if let Ok(claims) = auth_service::validate_refresh_token(
+            state.redis.as_ref(),
+            &token,
+            &state.config.jwt_secret,
+        )
+        .await
+        {
+            auth_service::revoke_refresh_token(state.redis.as_ref(), &claims).await;
+        }
+    }
     ApiResponse::success("Logged out successfully")
 }
 
 /// POST /api/v1/auth/users — admin-only user creation
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/users",
+    tag = "auth",
+    request_body = CreateUser,
+    responses(
+        (status = 200, description = "Created user", body = ApiResponse<UserResponse>),
+        (status = 409, description = "Username or email already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn create_user(
     State(state): State<AppState>,
     RequireAdmin(_admin): RequireAdmin,
-    Json(body): Json<CreateUser>,
+    ValidatedJson(body): ValidatedJson<CreateUser>,
 ) -> Result<Json<ApiResponse<UserResponse>>, AppError> {
     let user = auth_service::create_user(&state.db, &body).await?;
     Ok(ApiResponse::success(UserResponse::from(user)))
 }
 
 /// GET /api/v1/auth/me — current user profile
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Current user profile", body = ApiResponse<UserResponse>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn me(
     State(state): State<AppState>,
     current_user: CurrentUser,
@@ -0,0 +1,59 @@
+//! Configuration bundle routes: export and import between environments.
+
+use axum::{extract::State, response::Response, Json};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAdmin;
+use crate::models::config_bundle::{BundleImportResult, ConfigBundle};
+use crate::routes::exports::attachment;
+use crate::services::config_bundle;
+use crate::AppState;
+
+/// GET /api/v1/admin/config-bundle — download the configuration bundle (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/config-bundle",
+    tag = "config-bundle",
+    responses(
+        (status = 200, description = "Configuration bundle download", body = ConfigBundle, content_type = "application/json")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+) -> Result<Response, AppError> {
+    let bundle = config_bundle::export(&state.db_read).await?;
+    let body = serde_json::to_vec_pretty(&bundle)
+        .map_err(|e| AppError::Internal(format!("JSON serialization failed: {e}")))?;
+    let stamp = bundle.exported_at.format("%Y%m%d");
+    Ok(attachment(
+        "application/json",
+        &format!("synapsec_config_{stamp}.json"),
+        body,
+    ))
+}
+
+/// POST /api/v1/admin/config-bundle — apply a configuration bundle (admin only).
+///
+/// Entities are matched by natural key and created or updated; the whole
+/// bundle is rejected if any entry is invalid.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/config-bundle",
+    tag = "config-bundle",
+    request_body = ConfigBundle,
+    responses(
+        (status = 200, description = "Entities created and updated", body = ApiResponse<BundleImportResult>),
+        (status = 400, description = "Invalid bundle entries")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Json(bundle): Json<ConfigBundle>,
+) -> Result<Json<ApiResponse<BundleImportResult>>, AppError> {
+    let result = config_bundle::import(&state.db, &bundle, admin.id).await?;
+    Ok(ApiResponse::success(result))
+}
@@ -12,6 +12,7 @@ use uuid::Uuid;
 use crate::errors::{ApiResponse, AppError};
 use crate::middleware::auth::CurrentUser;
 use crate::middleware::rbac::{RequireAnalyst, RequireManager};
+use crate::middleware::validation::ValidatedJson;
 use crate::models::correlation_rule::CorrelationRule;
 use crate::models::correlation_rule::{CreateCorrelationRule, UpdateCorrelationRule};
 use crate::models::finding::FindingRelationship;
@@ -23,25 +24,52 @@ use crate::services::correlation_service::{
 use crate::AppState;
 
 /// GET /api/v1/correlations/groups -- list correlation groups with pagination.
+#[utoipa::path(
+    get,
+    path = "/api/v1/correlations/groups",
+    tag = "correlation",
+    params(Pagination, CorrelationGroupFilters),
+    responses(
+        (status = 200, description = "Page of correlation groups", body = ApiResponse<PagedResult<CorrelationGroup>>)
+    )
+)]
 pub async fn list_groups(
     State(state): State<AppState>,
     Query(pagination): Query<Pagination>,
     Query(filters): Query<CorrelationGroupFilters>,
 ) -> Result<Json<ApiResponse<PagedResult<CorrelationGroup>>>, AppError> {
-    let result = correlation_service::list_groups(&state.db, &filters, &pagination).await?;
+    let result = correlation_service::list_groups(&state.db_read, &filters, &pagination).await?;
     Ok(ApiResponse::success(result))
 }
 
 /// GET /api/v1/correlations/groups/:id -- get a group with member findings.
+#[utoipa::path(
+    get,
+    path = "/api/v1/correlations/groups/{id}",
+    tag = "correlation",
+    params(("id" = Uuid, Path, description = "Primary finding ID of the group")),
+    responses(
+        (status = 200, description = "Correlation group with member findings", body = ApiResponse<CorrelationGroupDetail>),
+        (status = 404, description = "Correlation group not found")
+    )
+)]
 pub async fn get_group(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<ApiResponse<CorrelationGroupDetail>>, AppError> {
-    let detail = correlation_service::get_group(&state.db, id).await?;
+    let detail = correlation_service::get_group(&state.db_read, id).await?;
     Ok(ApiResponse::success(detail))
 }
 
 /// GET /api/v1/correlations/rules -- list all correlation rules.
+#[utoipa::path(
+    get,
+    path = "/api/v1/correlations/rules",
+    tag = "correlation",
+    responses(
+        (status = 200, description = "All correlation rules", body = ApiResponse<Vec<CorrelationRule>>)
+    )
+)]
 pub async fn list_rules(
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<Vec<CorrelationRule>>>, AppError> {
@@ -50,27 +78,57 @@ pub async fn list_rules(
 }
 
 /// POST /api/v1/correlations/rules -- create a custom correlation rule (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/correlations/rules",
+    tag = "correlation",
+    request_body = CreateCorrelationRule,
+    responses((status = 200, description = "Created rule", body = ApiResponse<CorrelationRule>)),
+    security(("bearer_auth" = []))
+)]
 pub async fn create_rule(
     State(state): State<AppState>,
     RequireManager(manager): RequireManager,
-    Json(body): Json<CreateCorrelationRule>,
+    ValidatedJson(body): ValidatedJson<CreateCorrelationRule>,
 ) -> Result<Json<ApiResponse<CorrelationRule>>, AppError> {
     let rule = correlation_service::create_rule(&state.db, &body, manager.id).await?;
     Ok(ApiResponse::success(rule))
 }
 
 /// PUT /api/v1/correlations/rules/:id -- update a correlation rule (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/correlations/rules/{id}",
+    tag = "correlation",
+    params(("id" = Uuid, Path, description = "Rule ID")),
+    request_body = UpdateCorrelationRule,
+    responses(
+        (status = 200, description = "Updated rule", body = ApiResponse<CorrelationRule>),
+        (status = 404, description = "Rule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn update_rule(
     State(state): State<AppState>,
     RequireManager(_manager): RequireManager,
     Path(id): Path<Uuid>,
-    Json(body): Json<UpdateCorrelationRule>,
+    ValidatedJson(body): ValidatedJson<UpdateCorrelationRule>,
 ) -> Result<Json<ApiResponse<CorrelationRule>>, AppError> {
     let rule = correlation_service::update_rule(&state.db, id, &body).await?;
     Ok(ApiResponse::success(rule))
 }
 
 /// POST /api/v1/correlations/run/:app_id -- trigger correlation for an application (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/correlations/run/{app_id}",
+    tag = "correlation",
+    params(("app_id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Correlation run outcome", body = ApiResponse<CorrelationRunResult>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn run_correlation(
     State(state): State<AppState>,
     RequireManager(manager): RequireManager,
@@ -82,11 +140,21 @@ pub async fn run_correlation(
 }
 
 /// POST /api/v1/relationships -- manually create a finding relationship (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/relationships",
+    tag = "correlation",
+    request_body = CreateRelationshipRequest,
+    responses(
+        (status = 200, description = "Created relationship", body = ApiResponse<FindingRelationship>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn create_relationship(
     State(state): State<AppState>,
     RequireAnalyst(_analyst): RequireAnalyst,
     current_user: CurrentUser,
-    Json(body): Json<CreateRelationshipRequest>,
+    ValidatedJson(body): ValidatedJson<CreateRelationshipRequest>,
 ) -> Result<Json<ApiResponse<FindingRelationship>>, AppError> {
     let relationship =
         correlation_service::create_relationship(&state.db, &body, current_user.id).await?;
@@ -94,6 +162,17 @@ pub async fn create_relationship(
 }
 
 /// DELETE /api/v1/relationships/:id -- remove a finding relationship (analyst+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/relationships/{id}",
+    tag = "correlation",
+    params(("id" = Uuid, Path, description = "Relationship ID")),
+    responses(
+        (status = 200, description = "Relationship deleted"),
+        (status = 404, description = "Relationship not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn delete_relationship(
     State(state): State<AppState>,
     RequireAnalyst(_analyst): RequireAnalyst,
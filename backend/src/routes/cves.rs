@@ -0,0 +1,58 @@
+//! CVE routes: cached NVD details with the findings reporting each CVE, and
+//! resolved GitHub Security Advisories.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::models::cve::CveDetail;
+use crate::models::ghsa::GhsaAdvisory;
+use crate::services::{ghsa, nvd};
+use crate::AppState;
+
+/// GET /api/v1/cves/:id — CVE details and linked findings.
+#[utoipa::path(
+    get,
+    path = "/api/v1/cves/{id}",
+    tag = "cves",
+    params(("id" = String, Path, description = "CVE id, e.g. CVE-2021-44228")),
+    responses(
+        (status = 200, description = "Cached NVD details and linked findings", body = ApiResponse<CveDetail>),
+        (status = 400, description = "Malformed CVE id"),
+        (status = 404, description = "CVE neither cached nor reported by any finding")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<CveDetail>>, AppError> {
+    let detail = nvd::get_cve(&state.db_read, &id).await?;
+    Ok(ApiResponse::success(detail))
+}
+
+/// GET /api/v1/advisories/ghsa/:id — a resolved GHSA advisory with its CVE aliases.
+#[utoipa::path(
+    get,
+    path = "/api/v1/advisories/ghsa/{id}",
+    tag = "cves",
+    params(("id" = String, Path, description = "GHSA id, e.g. GHSA-jfh8-c2jp-5v3q")),
+    responses(
+        (status = 200, description = "Cached advisory", body = ApiResponse<GhsaAdvisory>),
+        (status = 400, description = "Malformed GHSA id"),
+        (status = 404, description = "Advisory not resolved yet")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_ghsa_advisory(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<GhsaAdvisory>>, AppError> {
+    let advisory = ghsa::get_advisory(&state.db_read, &id).await?;
+    Ok(ApiResponse::success(advisory))
+}
@@ -0,0 +1,127 @@
+//! CWE catalog routes: lookup, hierarchy, family roll-up, and dictionary import.
+
+use axum::{
+    extract::{Multipart, Path, Query, State},
+    Json,
+};
+use utoipa::ToSchema;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireAdmin;
+use crate::models::cwe::{CweDetail, CweFamilyCount, CweImportResult, CweSummary};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::cwe::{self, CweQuery, CweRollupQuery};
+use crate::AppState;
+
+/// GET /api/v1/cwes — search the CWE catalog.
+#[utoipa::path(
+    get,
+    path = "/api/v1/cwes",
+    tag = "cwes",
+    params(Pagination, CweQuery),
+    responses(
+        (status = 200, description = "Page of CWE entries", body = ApiResponse<PagedResult<CweSummary>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(pagination): Query<Pagination>,
+    Query(query): Query<CweQuery>,
+) -> Result<Json<ApiResponse<PagedResult<CweSummary>>>, AppError> {
+    let page = cwe::list(&state.db_read, &query, &pagination).await?;
+    Ok(ApiResponse::success(page))
+}
+
+/// GET /api/v1/cwes/rollup — open findings grouped by CWE family.
+#[utoipa::path(
+    get,
+    path = "/api/v1/cwes/rollup",
+    tag = "cwes",
+    params(CweRollupQuery),
+    responses(
+        (status = 200, description = "Open findings per CWE family", body = ApiResponse<Vec<CweFamilyCount>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn rollup(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(query): Query<CweRollupQuery>,
+) -> Result<Json<ApiResponse<Vec<CweFamilyCount>>>, AppError> {
+    let families = cwe::family_rollup(&state.db_read, &query).await?;
+    Ok(ApiResponse::success(families))
+}
+
+/// GET /api/v1/cwes/:id — a CWE with its parents, children, and ancestors.
+#[utoipa::path(
+    get,
+    path = "/api/v1/cwes/{id}",
+    tag = "cwes",
+    params(("id" = String, Path, description = "CWE id, e.g. CWE-89 or 89")),
+    responses(
+        (status = 200, description = "CWE entry and hierarchy", body = ApiResponse<CweDetail>),
+        (status = 400, description = "Malformed CWE id"),
+        (status = 404, description = "CWE not in the catalog")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<CweDetail>>, AppError> {
+    let detail = cwe::get(&state.db_read, &id).await?;
+    Ok(ApiResponse::success(detail))
+}
+
+/// Multipart body for the CWE dictionary import.
+#[derive(ToSchema)]
+pub struct CweImportForm {
+    /// MITRE CWE CSV export, e.g. the Research Concepts view (`1000.csv`).
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+/// POST /api/v1/cwes/import — load or refresh the catalog from the MITRE CSV (admin, multipart).
+#[utoipa::path(
+    post,
+    path = "/api/v1/cwes/import",
+    tag = "cwes",
+    request_body(content = CweImportForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Import outcome", body = ApiResponse<CweImportResult>),
+        (status = 400, description = "Missing file or unrecognized CSV")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<CweImportResult>>, AppError> {
+    let mut file_data: Option<Vec<u8>> = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::Validation(format!("Multipart error: {e}")))?
+    {
+        if field.name() == Some("file") {
+            file_data = Some(
+                field
+                    .bytes()
+                    .await
+                    .map_err(|e| AppError::Validation(format!("Failed to read file: {e}")))?
+                    .to_vec(),
+            );
+        }
+    }
+    let data = file_data.ok_or_else(|| {
+        AppError::Validation("Missing 'file' field in multipart request".to_string())
+    })?;
+
+    let result = cwe::import(&state.db, &data).await?;
+    Ok(ApiResponse::success(result))
+}
@@ -1,17 +1,244 @@
-//! Dashboard routes: aggregated statistics for the overview page.
+//! Dashboard routes: aggregated statistics and trends for the overview page.
 
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Response,
+    Json,
+};
 
 use crate::errors::{ApiResponse, AppError};
 use crate::middleware::auth::CurrentUser;
-use crate::services::dashboard::{self, DashboardStats};
+use crate::middleware::rbac::RequireManager;
+use crate::services::burndown::{self, Burndown, BurndownParams};
+use crate::services::business_units::{self, BusinessUnitReport, RollupParams};
+use crate::services::coverage_gaps::{self, CoverageGapParams, CoverageGapReport};
+use crate::services::dashboard::DashboardStats;
+use crate::services::dashboard_snapshots::{
+    self, CompareParams, SnapshotComparison, SnapshotParams, SnapshotSeries,
+};
+use crate::services::finding_trends::{self, FindingTrends, TrendParams};
+use crate::services::mttr::{self, MttrParams, MttrReport};
+use crate::services::top_apps::{self, TopApps, TopAppsParams};
+use crate::services::workload::{self, WorkloadParams, WorkloadReport};
 use crate::AppState;
 
-/// GET /api/v1/dashboard/stats — aggregated dashboard statistics.
+/// GET /api/v1/dashboard/stats — aggregated dashboard statistics, served from
+/// a short-lived cache and honouring `If-None-Match`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/stats",
+    tag = "dashboard",
+    responses(
+        (status = 200, description = "Aggregated dashboard statistics", body = ApiResponse<DashboardStats>),
+        (status = 304, description = "Statistics unchanged since the given ETag")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn stats(
     State(state): State<AppState>,
     _user: CurrentUser,
-) -> Result<Json<ApiResponse<DashboardStats>>, AppError> {
-    let stats = dashboard::get_stats(&state.db).await?;
-    Ok(ApiResponse::success(stats))
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let stats = state.dashboard_cache.get_or_load(&state.db_read).await?;
+    ApiResponse::success_with_etag(stats, &headers)
+}
+
+/// GET /api/v1/dashboard/trends — opened/closed/open finding counts over time
+/// (`interval=day|week|month`, optional `from`/`to` dates).
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/trends",
+    tag = "dashboard",
+    params(TrendParams),
+    responses(
+        (status = 200, description = "Trend series by severity and category", body = ApiResponse<FindingTrends>),
+        (status = 400, description = "Invalid date range")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn trends(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<TrendParams>,
+) -> Result<Json<ApiResponse<FindingTrends>>, AppError> {
+    let trends = finding_trends::get_trends(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(trends))
+}
+
+/// GET /api/v1/dashboard/mttr — mean and median time to remediate, overall and
+/// by severity, category, tool, and business unit (`period=month|quarter|year`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/mttr",
+    tag = "dashboard",
+    params(MttrParams),
+    responses(
+        (status = 200, description = "Time-to-remediate statistics", body = ApiResponse<MttrReport>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn mttr(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<MttrParams>,
+) -> Result<Json<ApiResponse<MttrReport>>, AppError> {
+    let report = mttr::get_mttr(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(report))
+}
+
+/// GET /api/v1/dashboard/burndown — open findings over time against the SLA
+/// target line for one application (`application_id`) or campaign `tag`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/burndown",
+    tag = "dashboard",
+    params(BurndownParams),
+    responses(
+        (status = 200, description = "Burn-down series", body = ApiResponse<Burndown>),
+        (status = 400, description = "Missing scope or invalid date range"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn burndown(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<BurndownParams>,
+) -> Result<Json<ApiResponse<Burndown>>, AppError> {
+    let burndown = burndown::get_burndown(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(burndown))
+}
+
+/// GET /api/v1/dashboard/business-units — findings, risk, SLA compliance, and
+/// scanner coverage per business unit (`group_by=business_unit|ssa_code`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/business-units",
+    tag = "dashboard",
+    params(RollupParams),
+    responses(
+        (status = 200, description = "Per-group rollup, riskiest first", body = ApiResponse<BusinessUnitReport>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn business_units(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<RollupParams>,
+) -> Result<Json<ApiResponse<BusinessUnitReport>>, AppError> {
+    let report = business_units::get_rollup(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(report))
+}
+
+/// GET /api/v1/dashboard/coverage-gaps — active applications missing expected
+/// scanner coverage within the last `days` (default 90), optionally for one
+/// `category`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/coverage-gaps",
+    tag = "dashboard",
+    params(CoverageGapParams),
+    responses(
+        (status = 200, description = "Applications with coverage gaps", body = ApiResponse<CoverageGapReport>),
+        (status = 400, description = "Invalid window")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn coverage_gaps(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<CoverageGapParams>,
+) -> Result<Json<ApiResponse<CoverageGapReport>>, AppError> {
+    let report = coverage_gaps::get_gaps(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(report))
+}
+
+/// GET /api/v1/dashboard/top-apps — applications ranked by aggregate risk with
+/// changes since the start of the period (`limit`, `period=month|quarter|year`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/top-apps",
+    tag = "dashboard",
+    params(TopAppsParams),
+    responses(
+        (status = 200, description = "Riskiest applications with deltas", body = ApiResponse<TopApps>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn top_apps(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<TopAppsParams>,
+) -> Result<Json<ApiResponse<TopApps>>, AppError> {
+    let top = top_apps::get_top_apps(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(top))
+}
+
+/// GET /api/v1/dashboard/snapshots — nightly open-finding counts over time
+/// (`group_by=severity|status|application`, optional `application_id`,
+/// `from`/`to` dates).
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/snapshots",
+    tag = "dashboard",
+    params(SnapshotParams),
+    responses(
+        (status = 200, description = "Snapshot series", body = ApiResponse<SnapshotSeries>),
+        (status = 400, description = "Invalid date range")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn snapshots(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<SnapshotParams>,
+) -> Result<Json<ApiResponse<SnapshotSeries>>, AppError> {
+    let series = dashboard_snapshots::get_series(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(series))
+}
+
+/// GET /api/v1/dashboard/snapshots/compare — open findings by severity and
+/// status in two snapshots (default: latest against one month earlier).
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/snapshots/compare",
+    tag = "dashboard",
+    params(CompareParams),
+    responses(
+        (status = 200, description = "Snapshot comparison", body = ApiResponse<SnapshotComparison>),
+        (status = 404, description = "No snapshot on or before a requested day")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn compare_snapshots(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<CompareParams>,
+) -> Result<Json<ApiResponse<SnapshotComparison>>, AppError> {
+    let comparison = dashboard_snapshots::compare(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(comparison))
+}
+
+/// GET /api/v1/dashboard/workload — per-user assigned, overdue, and pending
+/// false-positive findings plus recent activity (manager+).
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboard/workload",
+    tag = "dashboard",
+    params(WorkloadParams),
+    responses(
+        (status = 200, description = "Team workload, busiest first", body = ApiResponse<WorkloadReport>),
+        (status = 400, description = "Invalid activity window"),
+        (status = 403, description = "Manager or admin access required")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn workload(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Query(params): Query<WorkloadParams>,
+) -> Result<Json<ApiResponse<WorkloadReport>>, AppError> {
+    let report = workload::get_workload(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(report))
 }
@@ -0,0 +1,48 @@
+//! Referential hygiene routes: the orphaned data report and its repair
+//! (admin only).
+
+use axum::{extract::State, Json};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAdmin;
+use crate::services::data_hygiene::{self, HygieneFix, HygieneFixRequest, HygieneReport};
+use crate::AppState;
+
+/// GET /api/v1/admin/data-hygiene — orphaned and dangling data, by kind (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/data-hygiene",
+    tag = "data-hygiene",
+    responses(
+        (status = 200, description = "Issues found per kind, with sample ids", body = ApiResponse<HygieneReport>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn report(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+) -> Result<Json<ApiResponse<HygieneReport>>, AppError> {
+    let report = data_hygiene::report(&state.db_read).await?;
+    Ok(ApiResponse::success(report))
+}
+
+/// POST /api/v1/admin/data-hygiene/fix — repair fixable issues (admin only).
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/data-hygiene/fix",
+    tag = "data-hygiene",
+    request_body = HygieneFixRequest,
+    responses(
+        (status = 200, description = "Rows repaired per kind", body = ApiResponse<Vec<HygieneFix>>),
+        (status = 400, description = "A requested kind is report only")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn fix(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Json(body): Json<HygieneFixRequest>,
+) -> Result<Json<ApiResponse<Vec<HygieneFix>>>, AppError> {
+    let fixes = data_hygiene::fix(&state.db, &body, admin.id, &admin.username).await?;
+    Ok(ApiResponse::success(fixes))
+}
@@ -0,0 +1,32 @@
+//! Data subject routes: pseudonymization for erasure requests (admin only).
+
+use axum::{extract::State, Json};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAdmin;
+use crate::services::data_subject::{self, PseudonymizeRequest, PseudonymizeResult};
+use crate::AppState;
+
+/// POST /api/v1/admin/data-subjects/pseudonymize — replace a person's names
+/// and emails by a stable pseudonym (admin only).
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/data-subjects/pseudonymize",
+    tag = "data-subjects",
+    request_body = PseudonymizeRequest,
+    responses(
+        (status = 200, description = "Pseudonym used and rows changed per column", body = ApiResponse<PseudonymizeResult>),
+        (status = 400, description = "No usable identifiers or blank justification"),
+        (status = 404, description = "User or pseudonym not found"),
+        (status = 409, description = "The account already has another pseudonym")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn pseudonymize(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Json(body): Json<PseudonymizeRequest>,
+) -> Result<Json<ApiResponse<PseudonymizeResult>>, AppError> {
+    let result = data_subject::pseudonymize(&state.db, &body, admin.id, &admin.username).await?;
+    Ok(ApiResponse::success(result))
+}
@@ -1,7 +1,9 @@
 //! Deduplication dashboard API routes.
 //!
 //! Provides endpoints for viewing duplicate-pair statistics, pending reviews,
-//! decision history, and confirming or rejecting duplicate relationships.
+//! decision history and reviewer-decision metrics, confirming or rejecting duplicate relationships,
+//! running the cross-tool dedup heuristics for an application, and queueing
+//! its fuzzy near-duplicates for review.
 
 use axum::{
     extract::{Path, Query, State},
@@ -11,41 +13,123 @@ use uuid::Uuid;
 
 use crate::errors::{ApiResponse, AppError};
 use crate::middleware::auth::CurrentUser;
-use crate::middleware::rbac::RequireAnalyst;
+use crate::middleware::rbac::{RequireAnalyst, RequireManager};
 use crate::models::pagination::{PagedResult, Pagination};
-use crate::services::dedup_dashboard::{self, DedupDecision, DedupStats, PendingReview};
+use crate::services::cross_dedup_service::{self, CrossDedupRunResult};
+use crate::services::dedup_dashboard::{
+    self, DedupDecision, DedupEffectiveness, DedupStats, PendingReview, ReviewMetrics,
+};
+use crate::services::similarity::{self, SimilarityRunResult};
 use crate::AppState;
 
 /// GET /api/v1/deduplication/stats -- aggregated dedup statistics.
+#[utoipa::path(
+    get,
+    path = "/api/v1/deduplication/stats",
+    tag = "deduplication",
+    responses(
+        (status = 200, description = "Deduplication statistics", body = ApiResponse<DedupStats>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn stats(
     State(state): State<AppState>,
     _user: CurrentUser,
 ) -> Result<Json<ApiResponse<DedupStats>>, AppError> {
-    let result = dedup_dashboard::get_stats(&state.db).await?;
+    let result = dedup_dashboard::get_stats(&state.db_read).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// GET /api/v1/deduplication/effectiveness -- per-tool duplicate rates,
+/// cross-tool CVE overlap, and fingerprint collisions.
+#[utoipa::path(
+    get,
+    path = "/api/v1/deduplication/effectiveness",
+    tag = "deduplication",
+    responses(
+        (status = 200, description = "Deduplication effectiveness metrics", body = ApiResponse<DedupEffectiveness>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn effectiveness(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+) -> Result<Json<ApiResponse<DedupEffectiveness>>, AppError> {
+    let result = dedup_dashboard::get_effectiveness(&state.db_read).await?;
     Ok(ApiResponse::success(result))
 }
 
 /// GET /api/v1/deduplication/pending -- paginated pending duplicate pairs.
+#[utoipa::path(
+    get,
+    path = "/api/v1/deduplication/pending",
+    tag = "deduplication",
+    params(Pagination),
+    responses(
+        (status = 200, description = "Page of duplicate pairs awaiting review", body = ApiResponse<PagedResult<PendingReview>>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn pending(
     State(state): State<AppState>,
     _user: CurrentUser,
     Query(pagination): Query<Pagination>,
 ) -> Result<Json<ApiResponse<PagedResult<PendingReview>>>, AppError> {
-    let result = dedup_dashboard::list_pending(&state.db, &pagination).await?;
+    let result = dedup_dashboard::list_pending(&state.db_read, &pagination).await?;
     Ok(ApiResponse::success(result))
 }
 
 /// GET /api/v1/deduplication/history -- paginated decision history.
+#[utoipa::path(
+    get,
+    path = "/api/v1/deduplication/history",
+    tag = "deduplication",
+    params(Pagination),
+    responses(
+        (status = 200, description = "Page of confirm/reject decisions", body = ApiResponse<PagedResult<DedupDecision>>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn history(
     State(state): State<AppState>,
     _user: CurrentUser,
     Query(pagination): Query<Pagination>,
 ) -> Result<Json<ApiResponse<PagedResult<DedupDecision>>>, AppError> {
-    let result = dedup_dashboard::list_history(&state.db, &pagination).await?;
+    let result = dedup_dashboard::list_history(&state.db_read, &pagination).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// GET /api/v1/deduplication/review-metrics -- reviewer decisions per matcher
+/// and per similarity score band.
+#[utoipa::path(
+    get,
+    path = "/api/v1/deduplication/review-metrics",
+    tag = "deduplication",
+    responses(
+        (status = 200, description = "Review queue decision metrics", body = ApiResponse<ReviewMetrics>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn review_metrics(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+) -> Result<Json<ApiResponse<ReviewMetrics>>, AppError> {
+    let result = dedup_dashboard::get_review_metrics(&state.db_read).await?;
     Ok(ApiResponse::success(result))
 }
 
 /// POST /api/v1/deduplication/{relationship_id}/confirm -- analyst confirms a duplicate.
+#[utoipa::path(
+    post,
+    path = "/api/v1/deduplication/{relationship_id}/confirm",
+    tag = "deduplication",
+    params(("relationship_id" = Uuid, Path, description = "Duplicate relationship ID")),
+    responses(
+        (status = 200, description = "Duplicate confirmed"),
+        (status = 404, description = "Relationship not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn confirm(
     State(state): State<AppState>,
     RequireAnalyst(analyst): RequireAnalyst,
@@ -56,6 +140,17 @@ pub async fn confirm(
 }
 
 /// POST /api/v1/deduplication/{relationship_id}/reject -- analyst rejects a duplicate.
+#[utoipa::path(
+    post,
+    path = "/api/v1/deduplication/{relationship_id}/reject",
+    tag = "deduplication",
+    params(("relationship_id" = Uuid, Path, description = "Duplicate relationship ID")),
+    responses(
+        (status = 200, description = "Duplicate rejected"),
+        (status = 404, description = "Relationship not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn reject(
     State(state): State<AppState>,
     RequireAnalyst(analyst): RequireAnalyst,
@@ -64,3 +159,47 @@ pub async fn reject(
     dedup_dashboard::reject(&state.db, relationship_id, analyst.id).await?;
     Ok(ApiResponse::success(()))
 }
+
+/// POST /api/v1/deduplication/run/{app_id} -- run the cross-tool dedup
+/// heuristics over an application's open findings (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/deduplication/run/{app_id}",
+    tag = "deduplication",
+    params(("app_id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Deduplication run outcome", body = ApiResponse<CrossDedupRunResult>),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn run(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    Path(app_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<CrossDedupRunResult>>, AppError> {
+    let result = cross_dedup_service::run_for_application(&state.db, app_id, manager.id).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// POST /api/v1/deduplication/similar/{app_id} -- queue an application's
+/// probable near-duplicates for review (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/deduplication/similar/{app_id}",
+    tag = "deduplication",
+    params(("app_id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Similarity run outcome", body = ApiResponse<SimilarityRunResult>),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn queue_similar(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    Path(app_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<SimilarityRunResult>>, AppError> {
+    let result = similarity::queue_near_duplicates(&state.db, app_id, manager.id).await?;
+    Ok(ApiResponse::success(result))
+}
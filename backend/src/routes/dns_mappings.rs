@@ -0,0 +1,125 @@
+//! DNS mapping routes: CRUD for DNS name and URL prefix to application mappings.
+//!
+//! Any authenticated user can view mappings; managers maintain them.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::dns_mapping::{CreateDnsMapping, DnsMapping, UpdateDnsMapping};
+use crate::services::dns_mapping;
+use crate::AppState;
+
+/// GET /api/v1/dns-mappings — list all mappings.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dns-mappings",
+    tag = "dns-mappings",
+    responses(
+        (status = 200, description = "Mappings by kind and pattern", body = ApiResponse<Vec<DnsMapping>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<DnsMapping>>>, AppError> {
+    let mappings = dns_mapping::list(&state.db_read).await?;
+    Ok(ApiResponse::success(mappings))
+}
+
+/// POST /api/v1/dns-mappings — create a mapping (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/dns-mappings",
+    tag = "dns-mappings",
+    request_body = CreateDnsMapping,
+    responses(
+        (status = 200, description = "Created mapping", body = ApiResponse<DnsMapping>),
+        (status = 400, description = "Invalid DNS name, wildcard, or URL prefix"),
+        (status = 404, description = "Application not found"),
+        (status = 409, description = "A mapping for this pattern already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<CreateDnsMapping>,
+) -> Result<Json<ApiResponse<DnsMapping>>, AppError> {
+    let mapping = dns_mapping::create(&state.db, &body).await?;
+    Ok(ApiResponse::success(mapping))
+}
+
+/// GET /api/v1/dns-mappings/:id — get a mapping.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dns-mappings/{id}",
+    tag = "dns-mappings",
+    params(("id" = Uuid, Path, description = "DNS mapping ID")),
+    responses(
+        (status = 200, description = "Mapping", body = ApiResponse<DnsMapping>),
+        (status = 404, description = "Mapping not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<DnsMapping>>, AppError> {
+    let mapping = dns_mapping::find_by_id(&state.db, id).await?;
+    Ok(ApiResponse::success(mapping))
+}
+
+/// PUT /api/v1/dns-mappings/:id — update a mapping (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/dns-mappings/{id}",
+    tag = "dns-mappings",
+    params(("id" = Uuid, Path, description = "DNS mapping ID")),
+    request_body = UpdateDnsMapping,
+    responses(
+        (status = 200, description = "Updated mapping", body = ApiResponse<DnsMapping>),
+        (status = 400, description = "Invalid DNS name, wildcard, or URL prefix"),
+        (status = 404, description = "Mapping or application not found"),
+        (status = 409, description = "A mapping for this pattern already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateDnsMapping>,
+) -> Result<Json<ApiResponse<DnsMapping>>, AppError> {
+    let mapping = dns_mapping::update(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(mapping))
+}
+
+/// DELETE /api/v1/dns-mappings/:id — delete a mapping (manager+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/dns-mappings/{id}",
+    tag = "dns-mappings",
+    params(("id" = Uuid, Path, description = "DNS mapping ID")),
+    responses(
+        (status = 200, description = "Mapping deleted"),
+        (status = 404, description = "Mapping not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    dns_mapping::delete(&state.db, id).await?;
+    Ok(ApiResponse::success(()))
+}
@@ -0,0 +1,36 @@
+//! Enrichment routes: on-demand lookups against external vulnerability databases.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAnalyst;
+use crate::models::finding_sca::FindingSca;
+use crate::services::osv::{self, OsvSettings};
+use crate::AppState;
+
+/// POST /api/v1/findings/:id/enrich/osv — look up an SCA finding in OSV now (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/{id}/enrich/osv",
+    tag = "enrichment",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "SCA details with OSV ranges, aliases, and fixed version", body = ApiResponse<FindingSca>),
+        (status = 404, description = "Finding not found or not an SCA finding"),
+        (status = 500, description = "OSV lookup failed")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn enrich_osv(
+    State(state): State<AppState>,
+    RequireAnalyst(_analyst): RequireAnalyst,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<FindingSca>>, AppError> {
+    let settings = OsvSettings::from_config(&state.config);
+    let sca = osv::enrich_finding(&state.db, &osv::client()?, &settings, id).await?;
+    Ok(ApiResponse::success(sca))
+}
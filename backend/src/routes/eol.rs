@@ -0,0 +1,34 @@
+//! End-of-life routes: the portfolio report of packages past, or near, end
+//! of support.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::services::eol::{self, EolReport, EolReportParams};
+use crate::AppState;
+
+/// GET /api/v1/eol/report — product cycles in use that are past, or near,
+/// end of support, with the applications using them.
+#[utoipa::path(
+    get,
+    path = "/api/v1/eol/report",
+    tag = "eol",
+    params(EolReportParams),
+    responses(
+        (status = 200, description = "End-of-life cycles in use, past end of support first", body = ApiResponse<EolReport>),
+        (status = 400, description = "Invalid horizon")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn report(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<EolReportParams>,
+) -> Result<Json<ApiResponse<EolReport>>, AppError> {
+    let report = eol::report(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(report))
+}
@@ -0,0 +1,96 @@
+//! Per-application findings export in third-party tool formats.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::middleware::auth::CurrentUser;
+use crate::models::ownership::OwnershipRole;
+use crate::services::{defectdojo, ownership, sarif_export};
+use crate::AppState;
+
+/// Target format for an application findings export.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AppExportFormat {
+    /// DefectDojo Generic Findings Import JSON.
+    Defectdojo,
+    /// SARIF 2.1.0 log for code hosting platforms.
+    Sarif,
+}
+
+/// Query parameters for application findings export.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AppExportParams {
+    pub format: AppExportFormat,
+}
+
+/// GET /api/v1/applications/:id/findings/export — export an application's findings (`format=defectdojo|sarif`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/findings/export",
+    tag = "exports",
+    params(("id" = Uuid, Path, description = "Application ID"), AppExportParams),
+    responses(
+        (status = 200, description = "Findings export download", content(
+            (serde_json::Value = "application/json"),
+            (serde_json::Value = "application/sarif+json")
+        )),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_application_findings(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<AppExportParams>,
+) -> Result<Response, AppError> {
+    ownership::authorize(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    match params.format {
+        AppExportFormat::Defectdojo => {
+            let doc = defectdojo::export_for_application(&state.db_read, id).await?;
+            let body = serde_json::to_vec_pretty(&doc)
+                .map_err(|e| AppError::Internal(format!("JSON serialization failed: {e}")))?;
+            Ok(attachment(
+                "application/json",
+                &format!("defectdojo_{id}.json"),
+                body,
+            ))
+        }
+        AppExportFormat::Sarif => {
+            let log = sarif_export::export_for_application(&state.db_read, id).await?;
+            let body = serde_json::to_vec_pretty(&log)
+                .map_err(|e| AppError::Internal(format!("JSON serialization failed: {e}")))?;
+            Ok(attachment(
+                "application/sarif+json",
+                &format!("findings_{id}.sarif"),
+                body,
+            ))
+        }
+    }
+}
+
+/// Wrap an export body as a downloadable attachment.
+pub(crate) fn attachment(content_type: &str, filename: &str, body: Vec<u8>) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
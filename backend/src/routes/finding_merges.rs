@@ -0,0 +1,88 @@
+//! Finding merge API routes.
+//!
+//! Merge a duplicate finding into another, list a finding's merges, and undo
+//! a merge.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireAnalyst;
+use crate::models::ownership::OwnershipRole;
+use crate::services::finding_merge::{self, FindingMerge, MergeRequest};
+use crate::services::ownership;
+use crate::AppState;
+
+/// POST /api/v1/findings/{id}/merge -- merge a duplicate into the finding (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/{id}/merge",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding kept by the merge")),
+    request_body = MergeRequest,
+    responses(
+        (status = 200, description = "Merge performed", body = ApiResponse<FindingMerge>),
+        (status = 400, description = "Findings of different categories, or the same finding"),
+        (status = 404, description = "Finding not found"),
+        (status = 409, description = "A finding is already merged into another")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn merge(
+    State(state): State<AppState>,
+    RequireAnalyst(analyst): RequireAnalyst,
+    Path(id): Path<Uuid>,
+    Json(body): Json<MergeRequest>,
+) -> Result<Json<ApiResponse<FindingMerge>>, AppError> {
+    let merge = finding_merge::merge(&state.db, id, &body, analyst.id, &analyst.username).await?;
+    Ok(ApiResponse::success(merge))
+}
+
+/// GET /api/v1/findings/{id}/merges -- merges the finding took part in.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/merges",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "Merges into or out of the finding, newest first", body = ApiResponse<Vec<FindingMerge>>),
+        (status = 403, description = "Not assigned to the finding's application"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<FindingMerge>>>, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let merges = finding_merge::list_for_finding(&state.db_read, id).await?;
+    Ok(ApiResponse::success(merges))
+}
+
+/// POST /api/v1/findings/merges/{merge_id}/unmerge -- undo a merge (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/merges/{merge_id}/unmerge",
+    tag = "findings",
+    params(("merge_id" = Uuid, Path, description = "Merge ID")),
+    responses(
+        (status = 200, description = "Merge undone", body = ApiResponse<FindingMerge>),
+        (status = 404, description = "Merge not found"),
+        (status = 409, description = "Merge already undone, or a later merge must be undone first")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unmerge(
+    State(state): State<AppState>,
+    RequireAnalyst(analyst): RequireAnalyst,
+    Path(merge_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<FindingMerge>>, AppError> {
+    let merge = finding_merge::unmerge(&state.db, merge_id, analyst.id, &analyst.username).await?;
+    Ok(ApiResponse::success(merge))
+}
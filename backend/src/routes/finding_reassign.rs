@@ -0,0 +1,93 @@
+//! Finding reassignment API routes.
+//!
+//! Split a finding that covers several applications, list a finding's
+//! splits, and move findings between applications after an app-code
+//! correction.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::{RequireAnalyst, RequireManager};
+use crate::middleware::validation::ValidatedJson;
+use crate::models::ownership::OwnershipRole;
+use crate::services::finding::BulkResult;
+use crate::services::finding_reassign::{self, BulkReassign, FindingSplit, SplitRequest};
+use crate::services::ownership;
+use crate::AppState;
+
+/// POST /api/v1/findings/{id}/split -- split a finding into one per part (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/{id}/split",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding to split")),
+    request_body = SplitRequest,
+    responses(
+        (status = 200, description = "Split performed", body = ApiResponse<FindingSplit>),
+        (status = 400, description = "Invalid parts, unknown application or category field"),
+        (status = 404, description = "Finding not found"),
+        (status = 409, description = "Finding is archived")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn split(
+    State(state): State<AppState>,
+    RequireAnalyst(analyst): RequireAnalyst,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<SplitRequest>,
+) -> Result<Json<ApiResponse<FindingSplit>>, AppError> {
+    let split =
+        finding_reassign::split(&state.db, id, &body, analyst.id, &analyst.username).await?;
+    Ok(ApiResponse::success(split))
+}
+
+/// GET /api/v1/findings/{id}/splits -- splits the finding took part in.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/splits",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "Splits of or into the finding, newest first", body = ApiResponse<Vec<FindingSplit>>),
+        (status = 403, description = "Not assigned to the finding's application"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_splits(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<FindingSplit>>>, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let splits = finding_reassign::list_splits(&state.db_read, id).await?;
+    Ok(ApiResponse::success(splits))
+}
+
+/// POST /api/v1/findings/bulk/reassign -- move findings to another application (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/bulk/reassign",
+    tag = "findings",
+    request_body = BulkReassign,
+    responses(
+        (status = 200, description = "Reassigned count out of selected findings", body = ApiResponse<BulkResult>),
+        (status = 400, description = "Same source and target application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn bulk_reassign(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<BulkReassign>,
+) -> Result<Json<ApiResponse<BulkResult>>, AppError> {
+    let result =
+        finding_reassign::bulk_reassign(&state.db, &body, manager.id, &manager.username).await?;
+    Ok(ApiResponse::success(result))
+}
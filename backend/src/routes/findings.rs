@@ -1,46 +1,73 @@
 //! Finding routes: CRUD, status transitions, comments, history, bulk operations, and export.
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
 use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_util::io::ReaderStream;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::errors::{ApiResponse, AppError};
 use crate::middleware::auth::CurrentUser;
-use crate::middleware::rbac::{RequireAnalyst, RequireManager};
+use crate::middleware::rbac::{RequireAdmin, RequireAnalyst, RequireManager};
+use crate::middleware::validation::ValidatedJson;
 use crate::models::finding::{
     CreateComment, CreateFinding, Finding, FindingComment, FindingHistory,
-    FindingSummaryWithCategory, UpdateFinding,
+    FindingSummaryWithCategory, UpdateComment, UpdateFinding,
 };
+use crate::models::ownership::OwnershipRole;
 use crate::models::pagination::{PagedResult, Pagination};
 use crate::services::finding::{
-    self as finding_service, BulkAssign, BulkResult, BulkStatusUpdate, BulkTag, CategoryData,
-    FindingFilters, FindingWithDetails, StatusUpdateRequest,
+    self as finding_service, BatchGetRequest, BatchGetResult, BulkArchive, BulkAssign, BulkDelete,
+    BulkDeleteResult, BulkResult, BulkStatusUpdate, BulkTag, CategoryData, CommentListParams,
+    FindingFilters, FindingSort, FindingWithDetails, HistoryListParams, RawFindingDocument, SeverityOverrideRequest,
+    StatusUpdateRequest,
 };
+use crate::services::manual_finding::{self, CreateManualFinding};
+use crate::services::ownership;
+use crate::services::similarity::{self, SimilarFindings};
+use crate::services::splunk_hec::{self, PlatformEvent};
+use crate::services::{csv_export, xlsx_export};
 use crate::AppState;
 
 /// GET /api/v1/findings — list findings with filters, pagination, and search.
 ///
 /// Accepts `?include_category_data=true` to LEFT JOIN category tables
-/// (`finding_sast`, `finding_sca`, `finding_dast`) and include category-specific
-/// fields in each item. Without this parameter the response is backward-compatible
-/// with the original `FindingSummary` shape.
+/// (`finding_sast`, `finding_sca`, `finding_dast`, `finding_manual`) and
+/// include category-specific fields in each item. Without this parameter the
+/// response is backward-compatible with the original `FindingSummary` shape.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings",
+    tag = "findings",
+    params(Pagination, FindingFilters, FindingSort),
+    responses(
+        (status = 200, description = "Page of findings", body = ApiResponse<PagedResult<FindingSummaryWithCategory>>),
+        (status = 429, description = "Search rate limit exceeded")
+    )
+)]
 pub async fn list(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Query(pagination): Query<Pagination>,
-    Query(filters): Query<FindingFilters>,
+    Query(mut filters): Query<FindingFilters>,
+    Query(sort): Query<FindingSort>,
 ) -> Result<Json<ApiResponse<PagedResult<FindingSummaryWithCategory>>>, AppError> {
+    filters.visible_to = ownership::read_scope(&current_user);
     let include_category = filters.include_category_data.unwrap_or(false);
 
     let result = if include_category {
-        finding_service::list_with_category(&state.db, &filters, &pagination).await?
+        finding_service::list_with_category(&state.db_read, &filters, &sort, &pagination).await?
     } else {
         // Use the lightweight query without JOINs, then wrap results
-        let paged = finding_service::list(&state.db, &filters, &pagination).await?;
+        let paged = finding_service::list(&state.db_read, &filters, &sort, &pagination).await?;
         PagedResult::new(
             paged
                 .items
@@ -59,45 +86,197 @@ pub async fn list(
 }
 
 /// POST /api/v1/findings — create a finding (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings",
+    tag = "findings",
+    request_body = CreateFindingWithCategory,
+    responses((status = 200, description = "Created finding", body = ApiResponse<Finding>)),
+    security(("bearer_auth" = []))
+)]
 pub async fn create(
     State(state): State<AppState>,
     RequireAnalyst(_analyst): RequireAnalyst,
-    Json(body): Json<CreateFindingWithCategory>,
+    ValidatedJson(body): ValidatedJson<CreateFindingWithCategory>,
 ) -> Result<Json<ApiResponse<Finding>>, AppError> {
     let finding =
         finding_service::create(&state.db, &body.finding, &body.category_data).await?;
+    splunk_hec::emit(state.hec.as_ref(), PlatformEvent::finding_created(&finding));
+    Ok(ApiResponse::success(finding))
+}
+
+/// POST /api/v1/findings/manual — enter a pentest or bug bounty finding (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/manual",
+    tag = "findings",
+    request_body = CreateManualFinding,
+    responses(
+        (status = 200, description = "Created finding", body = ApiResponse<Finding>),
+        (status = 400, description = "Invalid or blank fields"),
+        (status = 404, description = "Application not found"),
+        (status = 409, description = "An open finding already tracks the issue")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_manual(
+    State(state): State<AppState>,
+    RequireAnalyst(analyst): RequireAnalyst,
+    ValidatedJson(body): ValidatedJson<CreateManualFinding>,
+) -> Result<Json<ApiResponse<Finding>>, AppError> {
+    let finding = manual_finding::create(&state.db, &body, analyst.id, &analyst.username).await?;
+    splunk_hec::emit(state.hec.as_ref(), PlatformEvent::finding_created(&finding));
     Ok(ApiResponse::success(finding))
 }
 
 /// Combined request body for creating a finding with category data.
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Deserialize, ToSchema)]
 pub struct CreateFindingWithCategory {
     #[serde(flatten)]
     pub finding: CreateFinding,
     pub category_data: CategoryData,
 }
 
-/// GET /api/v1/findings/:id — get finding by ID with category details.
+/// Validates the flattened finding fields, reported without a prefix.
+impl validator::Validate for CreateFindingWithCategory {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        validator::Validate::validate(&self.finding)
+    }
+}
+
+/// GET /api/v1/findings/:id — get finding by ID with category details,
+/// honouring `If-None-Match`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "Finding with category details", body = ApiResponse<FindingWithDetails>),
+        (status = 304, description = "Finding unchanged since the given ETag"),
+        (status = 403, description = "Not assigned to the finding's application"),
+        (status = 404, description = "Finding not found")
+    )
+)]
 pub async fn get_by_id(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ApiResponse<FindingWithDetails>>, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
     let result = finding_service::find_by_id(&state.db, id).await?;
+    ApiResponse::success_with_etag(result, &headers)
+}
+
+/// GET /api/v1/findings/{id}/raw — download the scanner record the finding
+/// was ingested from, with the tool's own severity.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/raw",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "Raw finding download", content(
+            (RawFindingDocument = "application/json")
+        )),
+        (status = 403, description = "Not assigned to the finding's application"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_raw(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Response, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let doc = finding_service::raw_document(&state.db_read, id).await?;
+    let body = serde_json::to_vec_pretty(&doc)
+        .map_err(|e| AppError::Internal(format!("JSON serialization failed: {e}")))?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", doc.file_name()),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// POST /api/v1/findings/batch-get — get several findings with category details.
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/batch-get",
+    tag = "findings",
+    request_body = BatchGetRequest,
+    responses(
+        (status = 200, description = "Findings in request order and unknown IDs", body = ApiResponse<BatchGetResult>),
+        (status = 400, description = "Too many IDs")
+    )
+)]
+pub async fn batch_get(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Json(body): Json<BatchGetRequest>,
+) -> Result<Json<ApiResponse<BatchGetResult>>, AppError> {
+    let mut result = finding_service::find_many(&state.db, &body.ids).await?;
+    // Findings outside the caller's applications are reported as unknown
+    if let Some(user_id) = ownership::read_scope(&current_user) {
+        let visible = ownership::assigned_application_ids(&state.db, user_id).await?;
+        let (items, hidden): (Vec<_>, Vec<_>) = result.items.into_iter().partition(|item| {
+            item.finding
+                .application_id
+                .is_some_and(|app_id| visible.contains(&app_id))
+        });
+        result.items = items;
+        result.missing.extend(hidden.into_iter().map(|item| item.finding.id));
+    }
     Ok(ApiResponse::success(result))
 }
 
 /// PUT /api/v1/findings/:id — update finding fields (analyst+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/findings/{id}",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    request_body = UpdateFinding,
+    responses(
+        (status = 200, description = "Updated finding", body = ApiResponse<Finding>),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn update(
     State(state): State<AppState>,
     RequireAnalyst(_analyst): RequireAnalyst,
     Path(id): Path<Uuid>,
-    Json(body): Json<UpdateFinding>,
+    ValidatedJson(body): ValidatedJson<UpdateFinding>,
 ) -> Result<Json<ApiResponse<Finding>>, AppError> {
     let finding = finding_service::update(&state.db, id, &body).await?;
     Ok(ApiResponse::success(finding))
 }
 
 /// PATCH /api/v1/findings/:id/status — update finding status with justification (analyst+).
+#[utoipa::path(
+    patch,
+    path = "/api/v1/findings/{id}/status",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    request_body = StatusUpdateRequest,
+    responses(
+        (status = 200, description = "Finding with its new status", body = ApiResponse<Finding>),
+        (status = 400, description = "Transition not allowed or justification missing"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn update_status(
     State(state): State<AppState>,
     RequireAnalyst(_analyst): RequireAnalyst,
@@ -112,19 +291,68 @@ pub async fn update_status(
         Some(current_user.id),
         &current_user.username,
         body.justification.as_deref(),
+        state.hec.as_ref(),
     )
     .await?;
     Ok(ApiResponse::success(finding))
 }
 
-/// POST /api/v1/findings/:id/comments — add a comment (analyst+).
+/// PUT /api/v1/findings/:id/severity-override — set or clear the severity
+/// override with justification (analyst+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/findings/{id}/severity-override",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    request_body = SeverityOverrideRequest,
+    responses(
+        (status = 200, description = "Finding with its new effective severity", body = ApiResponse<Finding>),
+        (status = 400, description = "Justification missing or no override to clear"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_severity_override(
+    State(state): State<AppState>,
+    RequireAnalyst(analyst): RequireAnalyst,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<SeverityOverrideRequest>,
+) -> Result<Json<ApiResponse<Finding>>, AppError> {
+    let finding = finding_service::set_severity_override(
+        &state.db,
+        id,
+        body.severity.as_ref(),
+        &body.justification,
+        analyst.id,
+        &analyst.username,
+    )
+    .await?;
+    Ok(ApiResponse::success(finding))
+}
+
+/// POST /api/v1/findings/:id/comments — add a comment or reply (analyst+, or
+/// champion or owner of the finding's application).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/{id}/comments",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    request_body = CreateComment,
+    responses(
+        (status = 200, description = "Created comment", body = ApiResponse<FindingComment>),
+        (status = 400, description = "Parent comment belongs to another finding"),
+        (status = 403, description = "Not an analyst or a champion of the finding's application"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn add_comment(
     State(state): State<AppState>,
-    RequireAnalyst(_analyst): RequireAnalyst,
     current_user: CurrentUser,
     Path(id): Path<Uuid>,
-    Json(body): Json<CreateComment>,
+    ValidatedJson(body): ValidatedJson<CreateComment>,
 ) -> Result<Json<ApiResponse<FindingComment>>, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Champion).await?;
     let comment = finding_service::add_comment(
         &state.db,
         id,
@@ -136,25 +364,178 @@ pub async fn add_comment(
     Ok(ApiResponse::success(comment))
 }
 
-/// GET /api/v1/findings/:id/comments — list comments.
+/// PUT /api/v1/findings/:id/comments/:comment_id — edit a comment (author or admin).
+#[utoipa::path(
+    put,
+    path = "/api/v1/findings/{id}/comments/{comment_id}",
+    tag = "findings",
+    params(
+        ("id" = Uuid, Path, description = "Finding ID"),
+        ("comment_id" = Uuid, Path, description = "Comment ID")
+    ),
+    request_body = UpdateComment,
+    responses(
+        (status = 200, description = "Edited comment", body = ApiResponse<FindingComment>),
+        (status = 403, description = "Not the author or an admin"),
+        (status = 404, description = "Comment not found or deleted")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_comment(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(body): ValidatedJson<UpdateComment>,
+) -> Result<Json<ApiResponse<FindingComment>>, AppError> {
+    let comment =
+        finding_service::update_comment(&state.db, id, comment_id, &current_user, &body).await?;
+    Ok(ApiResponse::success(comment))
+}
+
+/// DELETE /api/v1/findings/:id/comments/:comment_id — delete a comment (author or admin).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/findings/{id}/comments/{comment_id}",
+    tag = "findings",
+    params(
+        ("id" = Uuid, Path, description = "Finding ID"),
+        ("comment_id" = Uuid, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 200, description = "Comment deleted"),
+        (status = 403, description = "Not the author or an admin"),
+        (status = 404, description = "Comment not found or already deleted")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_comment(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path((id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    finding_service::delete_comment(&state.db, id, comment_id, &current_user).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// GET /api/v1/findings/:id/comments — list comments, paginated.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/comments",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID"), Pagination, CommentListParams),
+    responses(
+        (status = 200, description = "Comments on the finding", body = ApiResponse<PagedResult<FindingComment>>),
+        (status = 403, description = "Not assigned to the finding's application")
+    )
+)]
 pub async fn list_comments(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ApiResponse<Vec<FindingComment>>>, AppError> {
-    let comments = finding_service::list_comments(&state.db, id).await?;
+    Query(pagination): Query<Pagination>,
+    Query(params): Query<CommentListParams>,
+) -> Result<Json<ApiResponse<PagedResult<FindingComment>>>, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let comments =
+        finding_service::list_comments_page(&state.db, id, &params, &pagination).await?;
     Ok(ApiResponse::success(comments))
 }
 
-/// GET /api/v1/findings/:id/history — get finding history.
+/// GET /api/v1/me/mentions — comments mentioning the current user.
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/mentions",
+    tag = "findings",
+    params(Pagination),
+    responses(
+        (status = 200, description = "Comments mentioning the user, newest first", body = ApiResponse<PagedResult<FindingComment>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_my_mentions(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ApiResponse<PagedResult<FindingComment>>>, AppError> {
+    let mentions =
+        finding_service::list_mentions(&state.db_read, current_user.id, &pagination).await?;
+    Ok(ApiResponse::success(mentions))
+}
+
+/// GET /api/v1/findings/:id/history — get finding history, paginated and
+/// optionally filtered by action.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/history",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID"), Pagination, HistoryListParams),
+    responses(
+        (status = 200, description = "Change history of the finding", body = ApiResponse<PagedResult<FindingHistory>>),
+        (status = 403, description = "Not assigned to the finding's application")
+    )
+)]
 pub async fn get_history(
     State(state): State<AppState>,
+    current_user: CurrentUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ApiResponse<Vec<FindingHistory>>>, AppError> {
-    let history = finding_service::get_history(&state.db, id).await?;
+    Query(pagination): Query<Pagination>,
+    Query(params): Query<HistoryListParams>,
+) -> Result<Json<ApiResponse<PagedResult<FindingHistory>>>, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let history =
+        finding_service::get_history_page(&state.db, id, &params, &pagination).await?;
     Ok(ApiResponse::success(history))
 }
 
+/// Query parameters for the similar-findings endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SimilarQuery {
+    /// Maximum number of findings per list (default 25, max 100).
+    pub limit: Option<i64>,
+}
+
+/// GET /api/v1/findings/:id/similar — probable near-duplicates of a finding
+/// in the same application, and findings sharing its CVE, CWE, rule, file or
+/// package in any application.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/similar",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID"), SimilarQuery),
+    responses(
+        (status = 200, description = "Near-duplicates with their similarity score, and related findings with the attributes they share", body = ApiResponse<SimilarFindings>),
+        (status = 403, description = "Not assigned to the finding's application"),
+        (status = 404, description = "Finding not found")
+    )
+)]
+pub async fn get_similar(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SimilarQuery>,
+) -> Result<Json<ApiResponse<SimilarFindings>>, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let mut similar = similarity::similar_findings(&state.db_read, id, query.limit).await?;
+    if let Some(user_id) = ownership::read_scope(&current_user) {
+        let visible = ownership::assigned_application_ids(&state.db, user_id).await?;
+        similar.related.retain(|related| {
+            related
+                .application_id
+                .is_some_and(|app_id| visible.contains(&app_id))
+        });
+    }
+    Ok(ApiResponse::success(similar))
+}
+
 /// POST /api/v1/findings/bulk/status — bulk status update (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/bulk/status",
+    tag = "findings",
+    request_body = BulkStatusUpdate,
+    responses((status = 200, description = "Bulk update outcome", body = ApiResponse<BulkResult>)),
+    security(("bearer_auth" = []))
+)]
 pub async fn bulk_status(
     State(state): State<AppState>,
     RequireManager(_manager): RequireManager,
@@ -166,22 +547,39 @@ pub async fn bulk_status(
         &body,
         Some(current_user.id),
         &current_user.username,
+        state.hec.as_ref(),
     )
     .await?;
     Ok(ApiResponse::success(result))
 }
 
 /// POST /api/v1/findings/bulk/assign — bulk assign (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/bulk/assign",
+    tag = "findings",
+    request_body = BulkAssign,
+    responses((status = 200, description = "Bulk update outcome", body = ApiResponse<BulkResult>)),
+    security(("bearer_auth" = []))
+)]
 pub async fn bulk_assign(
     State(state): State<AppState>,
-    RequireManager(_manager): RequireManager,
+    RequireManager(manager): RequireManager,
     Json(body): Json<BulkAssign>,
 ) -> Result<Json<ApiResponse<BulkResult>>, AppError> {
-    let result = finding_service::bulk_assign(&state.db, &body).await?;
+    let result = finding_service::bulk_assign(&state.db, &body, &manager.username).await?;
     Ok(ApiResponse::success(result))
 }
 
 /// POST /api/v1/findings/bulk/tag — bulk tag (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/bulk/tag",
+    tag = "findings",
+    request_body = BulkTag,
+    responses((status = 200, description = "Bulk update outcome", body = ApiResponse<BulkResult>)),
+    security(("bearer_auth" = []))
+)]
 pub async fn bulk_tag(
     State(state): State<AppState>,
     RequireManager(_manager): RequireManager,
@@ -191,13 +589,59 @@ pub async fn bulk_tag(
     Ok(ApiResponse::success(result))
 }
 
+/// POST /api/v1/findings/bulk/archive — archive findings by ID or application (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/bulk/archive",
+    tag = "findings",
+    request_body = BulkArchive,
+    responses(
+        (status = 200, description = "Archived count out of matched findings", body = ApiResponse<BulkResult>),
+        (status = 400, description = "No findings or applications selected")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn bulk_archive(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    Json(body): Json<BulkArchive>,
+) -> Result<Json<ApiResponse<BulkResult>>, AppError> {
+    let result =
+        finding_service::bulk_archive(&state.db, &body, manager.id, &manager.username).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// POST /api/v1/findings/bulk/delete — permanently delete findings, with dry-run count (admin only).
+#[utoipa::path(
+    post,
+    path = "/api/v1/findings/bulk/delete",
+    tag = "findings",
+    request_body = BulkDelete,
+    responses(
+        (status = 200, description = "Matched and deleted counts", body = ApiResponse<BulkDeleteResult>),
+        (status = 400, description = "No findings or applications selected"),
+        (status = 403, description = "Admin access required")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn bulk_delete(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Json(body): Json<BulkDelete>,
+) -> Result<Json<ApiResponse<BulkDeleteResult>>, AppError> {
+    let result = finding_service::bulk_delete(&state.db, &body, admin.id, &admin.username).await?;
+    Ok(ApiResponse::success(result))
+}
+
 /// Export format selector for the export endpoint.
-#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ExportFormat {
     #[default]
     Csv,
     Json,
+    /// Workbook with a summary sheet and one sheet per finding category.
+    Xlsx,
 }
 
 /// Query parameters for the export endpoint.
@@ -211,198 +655,150 @@ pub struct ExportParams {
     pub filters: FindingFilters,
 }
 
-/// Flat CSV row for export. Flattens `FindingSummaryWithCategory` into a
-/// single record suitable for the `csv` crate's `Serializer`.
-#[derive(Debug, serde::Serialize)]
-struct CsvExportRow {
-    id: String,
-    source_tool: String,
-    finding_category: String,
-    title: String,
-    normalized_severity: String,
-    status: String,
-    composite_risk_score: Option<f32>,
-    fingerprint: String,
-    application_id: String,
-    first_seen: String,
-    last_seen: String,
-    sla_status: String,
-    // SAST fields
-    file_path: String,
-    line_number: String,
-    rule_id: String,
-    project: String,
-    language: String,
-    branch: String,
-    // SCA fields
-    package_name: String,
-    package_version: String,
-    fixed_version: String,
-    dependency_type: String,
-    known_exploited: String,
-    // DAST fields
-    target_url: String,
-    parameter: String,
-    web_application_name: String,
-}
-
-impl CsvExportRow {
-    /// Convert a `FindingSummaryWithCategory` to a flat CSV row.
-    fn from_finding(f: &FindingSummaryWithCategory) -> Self {
-        let s = &f.summary;
-        let cat = f.category_data.as_ref();
-
-        Self {
-            id: s.id.to_string(),
-            source_tool: s.source_tool.clone(),
-            finding_category: serde_json::to_string(&s.finding_category)
-                .unwrap_or_default()
-                .trim_matches('"')
-                .to_string(),
-            title: s.title.clone(),
-            normalized_severity: serde_json::to_string(&s.normalized_severity)
-                .unwrap_or_default()
-                .trim_matches('"')
-                .to_string(),
-            status: serde_json::to_string(&s.status)
-                .unwrap_or_default()
-                .trim_matches('"')
-                .to_string(),
-            composite_risk_score: s.composite_risk_score,
-            fingerprint: s.fingerprint.clone(),
-            application_id: s
-                .application_id
-                .map_or_else(String::new, |id| id.to_string()),
-            first_seen: s.first_seen.to_rfc3339(),
-            last_seen: s.last_seen.to_rfc3339(),
-            sla_status: s
-                .sla_status
-                .as_ref()
-                .map(|v| {
-                    serde_json::to_string(v)
-                        .unwrap_or_default()
-                        .trim_matches('"')
-                        .to_string()
-                })
-                .unwrap_or_default(),
-            // SAST
-            file_path: cat
-                .and_then(|c| c.file_path.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            line_number: cat
-                .and_then(|c| c.line_number)
-                .map_or_else(String::new, |n| n.to_string()),
-            rule_id: cat
-                .and_then(|c| c.rule_id.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            project: cat
-                .and_then(|c| c.project.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            language: cat
-                .and_then(|c| c.language.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            branch: cat
-                .and_then(|c| c.branch.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            // SCA
-            package_name: cat
-                .and_then(|c| c.package_name.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            package_version: cat
-                .and_then(|c| c.package_version.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            fixed_version: cat
-                .and_then(|c| c.fixed_version.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            dependency_type: cat
-                .and_then(|c| c.dependency_type.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            known_exploited: cat
-                .and_then(|c| c.known_exploited)
-                .map_or_else(String::new, |v| v.to_string()),
-            // DAST
-            target_url: cat
-                .and_then(|c| c.target_url.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            parameter: cat
-                .and_then(|c| c.parameter.as_deref())
-                .unwrap_or("")
-                .to_string(),
-            web_application_name: cat
-                .and_then(|c| c.web_application_name.as_deref())
-                .unwrap_or("")
-                .to_string(),
-        }
-    }
-}
-
-/// GET /api/v1/findings/export — export findings as CSV or JSON.
+/// GET /api/v1/findings/export — export findings as CSV, JSON, or XLSX.
 ///
 /// Accepts the same filter query parameters as the list endpoint plus
-/// `format=csv|json` (defaults to CSV). Returns all matching findings
-/// without pagination, with `Content-Disposition: attachment` headers.
+/// `format=csv|json|xlsx` (defaults to CSV). Returns all matching findings
+/// without pagination, newest first, with `Content-Disposition: attachment`
+/// headers. Rows are read in keyset-paginated batches: CSV and JSON are
+/// streamed batch by batch; XLSX rows go into constant-memory worksheets and
+/// the workbook is streamed from a temporary file once complete. XLSX
+/// exports are limited to `xlsx_export::MAX_ROWS` findings.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/export",
+    tag = "findings",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "Export format (defaults to `csv`)"),
+        FindingFilters
+    ),
+    responses(
+        (status = 200, description = "Findings export download", content(
+            (String = "text/csv"),
+            (Vec<FindingSummaryWithCategory> = "application/json"),
+            (String = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        )),
+        (status = 400, description = "XLSX export over the row limit")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn export_findings(
     State(state): State<AppState>,
-    _current_user: CurrentUser,
-    Query(params): Query<ExportParams>,
+    current_user: CurrentUser,
+    Query(mut params): Query<ExportParams>,
 ) -> Result<Response, AppError> {
     let format = params.format.unwrap_or_default();
-    let findings = finding_service::list_all_for_export(&state.db, &params.filters).await?;
+    params.filters.visible_to = ownership::read_scope(&current_user);
+    let batches = finding_service::export_batches(state.db_read.clone(), params.filters);
 
     match format {
-        ExportFormat::Json => {
-            let body = serde_json::to_vec(&findings)
-                .map_err(|e| AppError::Internal(format!("JSON serialization failed: {e}")))?;
+        ExportFormat::Json => Ok((
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "application/json"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"findings_export.json\"",
+                ),
+            ],
+            json_body(batches),
+        )
+            .into_response()),
+        ExportFormat::Csv => Ok((
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"findings_export.csv\"",
+                ),
+            ],
+            csv_body(batches),
+        )
+            .into_response()),
+        ExportFormat::Xlsx => {
+            let (tx, rx) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+            // Assembled in an anonymous temporary file, removed once the
+            // download closes it, so the workbook is never held in memory
+            let writer = tokio::task::spawn_blocking(move || {
+                let temp_file_error =
+                    |e: std::io::Error| AppError::Internal(format!("XLSX temporary file: {e}"));
+                let mut file = tempfile::tempfile().map_err(temp_file_error)?;
+                xlsx_export::write_workbook(rx, &mut file)?;
+                std::io::Seek::rewind(&mut file).map_err(temp_file_error)?;
+                Ok::<_, AppError>(file)
+            });
+            let mut batches = std::pin::pin!(batches);
+            'feed: while let Some(batch) = batches.try_next().await? {
+                for row in batch {
+                    if tx.send(row).await.is_err() {
+                        break 'feed;
+                    }
+                }
+            }
+            drop(tx);
+            let file = writer
+                .await
+                .map_err(|e| AppError::Internal(format!("XLSX export task failed: {e}")))??;
+            let body = Body::from_stream(ReaderStream::new(tokio::fs::File::from_std(file)));
 
             Ok((
                 StatusCode::OK,
                 [
-                    (header::CONTENT_TYPE, "application/json"),
+                    (header::CONTENT_TYPE, xlsx_export::CONTENT_TYPE),
                     (
                         header::CONTENT_DISPOSITION,
-                        "attachment; filename=\"findings_export.json\"",
+                        "attachment; filename=\"findings_export.xlsx\"",
                     ),
                 ],
                 body,
             )
                 .into_response())
         }
-        ExportFormat::Csv => {
-            let mut wtr = csv::Writer::from_writer(Vec::new());
-
-            for finding in &findings {
-                let row = CsvExportRow::from_finding(finding);
-                wtr.serialize(&row).map_err(|e| {
-                    AppError::Internal(format!("CSV serialization failed: {e}"))
-                })?;
-            }
+    }
+}
 
-            let body = wtr.into_inner().map_err(|e| {
-                AppError::Internal(format!("CSV flush failed: {e}"))
-            })?;
+/// Rows buffered between the export query and the XLSX writer.
+const EXPORT_CHANNEL_CAPACITY: usize = 1_000;
 
-            Ok((
-                StatusCode::OK,
-                [
-                    (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
-                    (
-                        header::CONTENT_DISPOSITION,
-                        "attachment; filename=\"findings_export.csv\"",
-                    ),
-                ],
-                body,
-            )
-                .into_response())
+/// Build a streaming CSV body, one chunk per export batch.
+///
+/// The header is emitted with the first row. If a batch query fails
+/// part-way, the body ends with an error so the client sees an aborted
+/// download rather than a silently truncated file.
+fn csv_body(
+    batches: impl Stream<Item = Result<Vec<FindingSummaryWithCategory>, AppError>> + Send + 'static,
+) -> Body {
+    let mut header_pending = true;
+    let chunks = batches.map(move |batch| {
+        let rows = batch.map_err(|e| std::io::Error::other(format!("CSV export aborted: {e}")))?;
+        let chunk =
+            csv_export::encode_rows(&rows, header_pending).map_err(std::io::Error::other)?;
+        header_pending &= rows.is_empty();
+        Ok::<_, std::io::Error>(chunk)
+    });
+    Body::from_stream(chunks)
+}
+
+/// Build a streaming JSON array body, one chunk per export batch.
+///
+/// Fails the same way as `csv_body()` when a batch query errors.
+fn json_body(
+    batches: impl Stream<Item = Result<Vec<FindingSummaryWithCategory>, AppError>> + Send + 'static,
+) -> Body {
+    let mut first = true;
+    let items = batches.map(move |batch| {
+        let rows = batch.map_err(|e| std::io::Error::other(format!("JSON export aborted: {e}")))?;
+        let mut chunk = Vec::new();
+        for row in &rows {
+            if !std::mem::take(&mut first) {
+                chunk.push(b',');
+            }
+            serde_json::to_writer(&mut chunk, row).map_err(std::io::Error::other)?;
         }
-    }
+        Ok::<_, std::io::Error>(chunk)
+    });
+    let open = stream::once(async { Ok(b"[".to_vec()) });
+    let close = stream::once(async { Ok(b"]".to_vec()) });
+    Body::from_stream(open.chain(items).chain(close))
 }
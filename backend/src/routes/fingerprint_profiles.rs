@@ -0,0 +1,76 @@
+//! Fingerprint profile routes: per-tool fingerprint components (admin only).
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAdmin;
+use crate::services::fingerprint_profile::{self, FingerprintProfile, SetFingerprintProfile};
+use crate::AppState;
+
+/// GET /api/v1/admin/fingerprint-profiles — configured fingerprint components
+/// per source tool (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/fingerprint-profiles",
+    tag = "fingerprints",
+    responses(
+        (status = 200, description = "Configured profiles; other tools use the built-in fingerprint", body = ApiResponse<Vec<FingerprintProfile>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+) -> Result<Json<ApiResponse<Vec<FingerprintProfile>>>, AppError> {
+    let profiles = fingerprint_profile::list(&state.db).await?;
+    Ok(ApiResponse::success(profiles))
+}
+
+/// PUT /api/v1/admin/fingerprint-profiles/:source_tool — set the fingerprint
+/// components of a source tool (admin only).
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/fingerprint-profiles/{source_tool}",
+    tag = "fingerprints",
+    params(("source_tool" = String, Path, description = "Source tool, e.g. SonarQube")),
+    request_body = SetFingerprintProfile,
+    responses(
+        (status = 200, description = "Profile saved", body = ApiResponse<FingerprintProfile>),
+        (status = 400, description = "Empty component list or components of several categories")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Path(source_tool): Path<String>,
+    Json(body): Json<SetFingerprintProfile>,
+) -> Result<Json<ApiResponse<FingerprintProfile>>, AppError> {
+    let profile = fingerprint_profile::set(&state.db, &source_tool, &body, admin.id).await?;
+    Ok(ApiResponse::success(profile))
+}
+
+/// DELETE /api/v1/admin/fingerprint-profiles/:source_tool — restore the
+/// built-in fingerprint of a source tool (admin only).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/fingerprint-profiles/{source_tool}",
+    tag = "fingerprints",
+    params(("source_tool" = String, Path, description = "Source tool")),
+    responses(
+        (status = 200, description = "Profile removed"),
+        (status = 404, description = "No profile for the tool")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Path(source_tool): Path<String>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    fingerprint_profile::remove(&state.db, &source_tool, admin.id).await?;
+    Ok(ApiResponse::success(()))
+}
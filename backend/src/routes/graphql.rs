@@ -0,0 +1,46 @@
+//! GraphQL endpoint and GraphiQL explorer.
+
+use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{extract::State, response::Html, Extension};
+
+use crate::graphql::ApiSchema;
+use crate::middleware::auth::CurrentUser;
+use crate::AppState;
+
+/// POST /api/v1/graphql — execute a read-only GraphQL query.
+#[utoipa::path(
+    post,
+    path = "/api/v1/graphql",
+    tag = "graphql",
+    request_body(content = serde_json::Value, description = "GraphQL request with `query`, optional `variables` and `operationName`"),
+    responses(
+        (status = 200, description = "GraphQL response with `data` and/or `errors`"),
+        (status = 401, description = "Missing or invalid credentials")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn execute(
+    State(state): State<AppState>,
+    Extension(schema): Extension<ApiSchema>,
+    user: CurrentUser,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema
+        .execute(request.into_inner().data(state.db_read.clone()).data(user))
+        .await
+        .into()
+}
+
+/// GET /api/v1/graphql — GraphiQL explorer; queries it sends still need a token.
+#[utoipa::path(
+    get,
+    path = "/api/v1/graphql",
+    tag = "graphql",
+    responses(
+        (status = 200, description = "GraphiQL HTML page", content_type = "text/html")
+    )
+)]
+pub async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/api/v1/graphql").finish())
+}
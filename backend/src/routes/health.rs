@@ -2,12 +2,13 @@
 
 use axum::{extract::State, Json};
 use serde::Serialize;
+use utoipa::ToSchema;
 
 use crate::errors::ApiResponse;
 use crate::AppState;
 
 /// Readiness probe detail.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct HealthStatus {
     pub status: String,
     pub database: String,
@@ -15,11 +16,27 @@ pub struct HealthStatus {
 }
 
 /// Liveness probe — always returns OK if the process is running.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "health",
+    responses(
+        (status = 200, description = "Process is running", body = String, content_type = "text/plain")
+    )
+)]
 pub async fn live() -> &'static str {
     "OK"
 }
 
 /// Readiness probe — checks database and Redis connectivity.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Dependency connectivity", body = ApiResponse<HealthStatus>)
+    )
+)]
 pub async fn ready(State(state): State<AppState>) -> Json<ApiResponse<HealthStatus>> {
     let db_status = match sqlx::query("SELECT 1").execute(&state.db).await {
         Ok(_) => "connected".to_string(),
@@ -29,18 +46,15 @@ pub async fn ready(State(state): State<AppState>) -> Json<ApiResponse<HealthStat
         }
     };
 
-    let redis_status = match redis::Client::open(state.config.redis_url.as_str()) {
-        Ok(client) => match client.get_multiplexed_async_connection().await {
-            Ok(_) => "connected".to_string(),
+    let redis_status = match &state.redis {
+        Some(redis) => match redis.ping().await {
+            Ok(()) => "connected".to_string(),
             Err(e) => {
                 tracing::warn!(error = %e, "Redis health check failed");
                 format!("error: {e}")
             }
         },
-        Err(e) => {
-            tracing::warn!(error = %e, "Redis client creation failed");
-            format!("error: {e}")
-        }
+        None => "error: invalid REDIS_URL".to_string(),
     };
 
     ApiResponse::success(HealthStatus {
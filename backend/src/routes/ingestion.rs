@@ -1,25 +1,55 @@
-//! Ingestion routes: file upload, history, and log details.
+//! Ingestion routes: file upload, history, log details, and the diff of an
+//! ingestion against the previous scan.
 
 use axum::{
     extract::{Multipart, Path, Query, State},
     Json,
 };
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::errors::{ApiResponse, AppError};
 use crate::middleware::auth::CurrentUser;
-use crate::middleware::rbac::RequireManager;
+use crate::middleware::rbac::RequireUploader;
+use crate::models::job::NewJob;
+use crate::models::ownership::OwnershipRole;
 use crate::models::pagination::{PagedResult, Pagination};
 use crate::parsers::InputFormat;
+use crate::services::{dashboard, job, ownership};
 use crate::services::ingestion::{
     self, IngestionLog, IngestionLogSummary, IngestionResult, ParserType,
 };
+use crate::services::occurrence::{self, IngestionDiff, IngestionDiffParams};
 use crate::AppState;
 
-/// POST /api/v1/ingestion/upload — upload scanner output for ingestion (manager+, multipart).
+/// Multipart body for the upload endpoint.
+#[derive(ToSchema)]
+pub struct IngestionUploadForm {
+    /// Scanner output file.
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    pub parser_type: ParserType,
+    pub format: InputFormat,
+}
+
+/// POST /api/v1/ingestion/upload — upload scanner output for ingestion
+/// (manager+ or an upload token in `X-API-Key`, multipart).
+#[utoipa::path(
+    post,
+    path = "/api/v1/ingestion/upload",
+    tag = "ingestion",
+    request_body(content = IngestionUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Ingestion run summary", body = ApiResponse<IngestionResult>),
+        (status = 400, description = "Missing field or unparseable file"),
+        (status = 403, description = "File outside the upload token's scope"),
+        (status = 429, description = "Ingestion rate limit exceeded")
+    ),
+    security(("bearer_auth" = []), ("api_key" = []))
+)]
 pub async fn upload(
     State(state): State<AppState>,
-    RequireManager(user): RequireManager,
+    uploader: RequireUploader,
     mut multipart: Multipart,
 ) -> Result<Json<ApiResponse<IngestionResult>>, AppError> {
     let mut file_data: Option<Vec<u8>> = None;
@@ -54,7 +84,7 @@ pub async fn upload(
                 parser_type = Some(serde_json::from_value(serde_json::Value::String(text.clone()))
                     .map_err(|_| {
                         AppError::Validation(format!(
-                            "Invalid parser_type '{text}'. Supported: sonarqube, sarif, jfrog_xray, tenable_was"
+                            "Invalid parser_type '{text}'. Supported: sonarqube, sarif, jfrog_xray, tenable_was, semgrep"
                         ))
                     })?);
             }
@@ -86,25 +116,66 @@ pub async fn upload(
         AppError::Validation("Missing 'format' field".to_string())
     })?;
 
-    let result =
-        ingestion::ingest_file(&state.db, &data, &file_name, &pt, &fmt, user.id).await?;
+    let (initiated_by, scope) = match &uploader {
+        RequireUploader::Manager(user) => (user.id, None),
+        RequireUploader::Token(scope) => (scope.created_by, Some(scope)),
+    };
+    let result = ingestion::ingest_file_scoped(
+        &state.db,
+        &data,
+        &file_name,
+        &pt,
+        &fmt,
+        initiated_by,
+        scope,
+        state.hec.as_ref(),
+    )
+    .await?;
+
+    // Bring dashboard counts up to date without delaying the response.
+    let refresh = NewJob::now(dashboard::REFRESH_JOB, serde_json::json!({}));
+    if let Err(e) = job::enqueue_once(&state.db, &refresh).await {
+        tracing::warn!(error = %e, "Failed to queue dashboard refresh");
+    }
 
     Ok(ApiResponse::success(result))
 }
 
 /// GET /api/v1/ingestion/history — list past ingestion events.
+#[utoipa::path(
+    get,
+    path = "/api/v1/ingestion/history",
+    tag = "ingestion",
+    params(Pagination),
+    responses(
+        (status = 200, description = "Page of past ingestion runs", body = ApiResponse<PagedResult<IngestionLogSummary>>)
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn history(
     State(state): State<AppState>,
     _user: CurrentUser,
     Query(pagination): Query<Pagination>,
 ) -> Result<Json<ApiResponse<PagedResult<IngestionLogSummary>>>, AppError> {
-    let total = ingestion::count_history(&state.db).await?;
-    let logs = ingestion::list_history(&state.db, pagination.limit(), pagination.offset()).await?;
+    let total = ingestion::count_history(&state.db_read).await?;
+    let logs =
+        ingestion::list_history(&state.db_read, pagination.limit(), pagination.offset()).await?;
     let paged = PagedResult::new(logs, total, &pagination);
     Ok(ApiResponse::success(paged))
 }
 
 /// GET /api/v1/ingestion/:id — get full ingestion log details.
+#[utoipa::path(
+    get,
+    path = "/api/v1/ingestion/{id}",
+    tag = "ingestion",
+    params(("id" = Uuid, Path, description = "Ingestion log ID")),
+    responses(
+        (status = 200, description = "Ingestion log", body = ApiResponse<IngestionLog>),
+        (status = 404, description = "Ingestion log not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_log(
     State(state): State<AppState>,
     _user: CurrentUser,
@@ -113,3 +184,35 @@ pub async fn get_log(
     let log = ingestion::get_log(&state.db, id).await?;
     Ok(ApiResponse::success(log))
 }
+
+/// GET /api/v1/ingestion/{id}/diff -- new, reopened, persisting, appeared,
+/// absent and severity-changed findings compared with the previous scan of
+/// each application and branch, or with the `against` ingestion.
+#[utoipa::path(
+    get,
+    path = "/api/v1/ingestion/{id}/diff",
+    tag = "ingestion",
+    params(("id" = Uuid, Path, description = "Ingestion log ID"), IngestionDiffParams),
+    responses(
+        (status = 200, description = "Ingestion diff", body = ApiResponse<IngestionDiff>),
+        (status = 400, description = "Ingestions of different tools or scopes"),
+        (status = 403, description = "Not assigned to every application the ingestions cover"),
+        (status = 404, description = "Ingestion log not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn diff(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<IngestionDiffParams>,
+) -> Result<Json<ApiResponse<IngestionDiff>>, AppError> {
+    let mut applications = occurrence::ingestion_applications(&state.db, id).await?;
+    if let Some(against) = params.against {
+        applications.extend(occurrence::ingestion_applications(&state.db, against).await?);
+    }
+    ownership::authorize_each(&state.db, &current_user, &applications, OwnershipRole::Viewer)
+        .await?;
+    let diff = occurrence::ingestion_diff(&state.db_read, id, params.against).await?;
+    Ok(ApiResponse::success(diff))
+}
@@ -0,0 +1,78 @@
+//! Background job routes: queue inspection and manual retry (admin only).
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAdmin;
+use crate::models::job::Job;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::job::{self, JobFilters};
+use crate::AppState;
+
+/// GET /api/v1/admin/jobs — list background jobs, newest first (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/jobs",
+    tag = "jobs",
+    params(JobFilters, Pagination),
+    responses(
+        (status = 200, description = "Page of jobs", body = ApiResponse<PagedResult<Job>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Query(filters): Query<JobFilters>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ApiResponse<PagedResult<Job>>>, AppError> {
+    let page = job::list(&state.db, &filters, &pagination).await?;
+    Ok(ApiResponse::success(page))
+}
+
+/// GET /api/v1/admin/jobs/:id — get a job with its last error (admin only).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/jobs/{id}",
+    tag = "jobs",
+    params(("id" = Uuid, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "Job", body = ApiResponse<Job>),
+        (status = 404, description = "Job not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Job>>, AppError> {
+    let job = job::find_by_id(&state.db, id).await?;
+    Ok(ApiResponse::success(job))
+}
+
+/// POST /api/v1/admin/jobs/:id/retry — requeue a failed job (admin only).
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/jobs/{id}/retry",
+    tag = "jobs",
+    params(("id" = Uuid, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "Requeued job", body = ApiResponse<Job>),
+        (status = 404, description = "Job not found"),
+        (status = 409, description = "Job has not failed")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn retry(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Job>>, AppError> {
+    let job = job::retry(&state.db, id).await?;
+    Ok(ApiResponse::success(job))
+}
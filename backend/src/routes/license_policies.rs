@@ -0,0 +1,128 @@
+//! License policy routes: the policies flagging SCA package licenses
+//! (manager+ to change) and the per-application license report.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::license_policy::{ApplicationLicenseReport, LicensePolicy, UpsertLicensePolicy};
+use crate::models::ownership::OwnershipRole;
+use crate::services::{license_policy, ownership};
+use crate::AppState;
+
+/// GET /api/v1/license-policies — all license policies, by name.
+#[utoipa::path(
+    get,
+    path = "/api/v1/license-policies",
+    tag = "license-policies",
+    responses(
+        (status = 200, description = "License policies with their open violations", body = ApiResponse<Vec<LicensePolicy>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<LicensePolicy>>>, AppError> {
+    let policies = license_policy::list(&state.db_read).await?;
+    Ok(ApiResponse::success(policies))
+}
+
+/// POST /api/v1/license-policies — create a policy and flag the open
+/// findings violating it (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/license-policies",
+    tag = "license-policies",
+    request_body = UpsertLicensePolicy,
+    responses(
+        (status = 200, description = "Policy created", body = ApiResponse<LicensePolicy>),
+        (status = 400, description = "Deny policy without licenses"),
+        (status = 409, description = "A policy with this name exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<UpsertLicensePolicy>,
+) -> Result<Json<ApiResponse<LicensePolicy>>, AppError> {
+    let policy = license_policy::create(&state.db, &body, manager.id).await?;
+    Ok(ApiResponse::success(policy))
+}
+
+/// PUT /api/v1/license-policies/{id} — replace a policy (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/license-policies/{id}",
+    tag = "license-policies",
+    params(("id" = Uuid, Path, description = "License policy ID")),
+    request_body = UpsertLicensePolicy,
+    responses(
+        (status = 200, description = "Policy updated", body = ApiResponse<LicensePolicy>),
+        (status = 404, description = "Policy not found"),
+        (status = 409, description = "A policy with this name exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpsertLicensePolicy>,
+) -> Result<Json<ApiResponse<LicensePolicy>>, AppError> {
+    let policy = license_policy::update(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(policy))
+}
+
+/// DELETE /api/v1/license-policies/{id} — delete a policy and its
+/// violations (manager+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/license-policies/{id}",
+    tag = "license-policies",
+    params(("id" = Uuid, Path, description = "License policy ID")),
+    responses(
+        (status = 200, description = "Policy deleted"),
+        (status = 404, description = "Policy not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    license_policy::delete(&state.db, id).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// GET /api/v1/applications/{id}/licenses — licenses of the application's
+/// open SCA findings and the policies they violate.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/licenses",
+    tag = "license-policies",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "License usage and violations", body = ApiResponse<ApplicationLicenseReport>),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn application_report(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ApplicationLicenseReport>>, AppError> {
+    ownership::authorize(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let report = license_policy::application_report(&state.db_read, id).await?;
+    Ok(ApiResponse::success(report))
+}
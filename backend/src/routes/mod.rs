@@ -1,11 +1,50 @@
 //! Route definitions for the SynApSec API.
 
+pub mod app_code_patterns;
 pub mod applications;
+pub mod assets;
+pub mod attachments;
+pub mod attack;
 pub mod attack_chains;
+pub mod audit_log;
 pub mod auth;
+pub mod config_bundle;
 pub mod correlation;
+pub mod cves;
+pub mod cwes;
 pub mod dashboard;
+pub mod data_hygiene;
+pub mod data_subjects;
 pub mod deduplication;
+pub mod dns_mappings;
+pub mod enrichment;
+pub mod eol;
+pub mod exports;
+pub mod finding_merges;
+pub mod finding_reassign;
 pub mod findings;
+pub mod fingerprint_profiles;
+pub mod graphql;
 pub mod health;
 pub mod ingestion;
+pub mod jobs;
+pub mod license_policies;
+pub mod notifications;
+pub mod occurrences;
+pub mod owasp;
+pub mod ownership;
+pub mod releases;
+pub mod remediation;
+pub mod report_schedules;
+pub mod report_templates;
+pub mod reports;
+pub mod saved_dashboards;
+pub mod saved_filters;
+pub mod sbom;
+pub mod scanner_rules;
+pub mod search;
+pub mod tags;
+pub mod triage;
+pub mod upload_tokens;
+pub mod vex;
+pub mod watchlist;
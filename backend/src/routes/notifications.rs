@@ -0,0 +1,139 @@
+//! Notification routes: the current user's in-app inbox and delivery
+//! preferences.
+//!
+//! Reads go to the primary pool so an inbox reloaded right after marking
+//! notifications read never shows them unread again.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::models::notification::{
+    MarkRead, MarkReadResult, Notification, NotificationPreference, UnreadCount,
+    UpdateNotificationPreferences,
+};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::notification::{self, NotificationFilters};
+use crate::services::notification_preferences;
+use crate::AppState;
+
+/// GET /api/v1/me/notifications — the current user's notifications.
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/notifications",
+    tag = "notifications",
+    params(Pagination, NotificationFilters),
+    responses(
+        (status = 200, description = "Notifications, newest first", body = ApiResponse<PagedResult<Notification>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(pagination): Query<Pagination>,
+    Query(filters): Query<NotificationFilters>,
+) -> Result<Json<ApiResponse<PagedResult<Notification>>>, AppError> {
+    let notifications =
+        notification::list_for_user(&state.db, user.id, &filters, &pagination).await?;
+    Ok(ApiResponse::success(notifications))
+}
+
+/// GET /api/v1/me/notifications/unread-count — number of unread notifications.
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/notifications/unread-count",
+    tag = "notifications",
+    responses(
+        (status = 200, description = "Unread notification count", body = ApiResponse<UnreadCount>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unread_count(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<ApiResponse<UnreadCount>>, AppError> {
+    let unread = notification::unread_count(&state.db, user.id).await?;
+    Ok(ApiResponse::success(UnreadCount { unread }))
+}
+
+/// POST /api/v1/me/notifications/read — mark notifications as read.
+#[utoipa::path(
+    post,
+    path = "/api/v1/me/notifications/read",
+    tag = "notifications",
+    request_body = MarkRead,
+    responses(
+        (status = 200, description = "Notifications newly marked read", body = ApiResponse<MarkReadResult>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn mark_read(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(body): Json<MarkRead>,
+) -> Result<Json<ApiResponse<MarkReadResult>>, AppError> {
+    let updated = notification::mark_read(&state.db, user.id, &body.ids).await?;
+    Ok(ApiResponse::success(MarkReadResult { updated }))
+}
+
+/// POST /api/v1/me/notifications/read-all — mark all notifications as read.
+#[utoipa::path(
+    post,
+    path = "/api/v1/me/notifications/read-all",
+    tag = "notifications",
+    responses(
+        (status = 200, description = "Notifications newly marked read", body = ApiResponse<MarkReadResult>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn mark_all_read(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<ApiResponse<MarkReadResult>>, AppError> {
+    let updated = notification::mark_all_read(&state.db, user.id).await?;
+    Ok(ApiResponse::success(MarkReadResult { updated }))
+}
+
+/// GET /api/v1/me/notification-preferences — effective preferences per kind and channel.
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/notification-preferences",
+    tag = "notifications",
+    responses(
+        (status = 200, description = "Preferences for every kind and channel, defaults included", body = ApiResponse<Vec<NotificationPreference>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_preferences(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<NotificationPreference>>>, AppError> {
+    let preferences = notification_preferences::list_for_user(&state.db, user.id).await?;
+    Ok(ApiResponse::success(preferences))
+}
+
+/// PUT /api/v1/me/notification-preferences — change preferences for some kinds and channels.
+#[utoipa::path(
+    put,
+    path = "/api/v1/me/notification-preferences",
+    tag = "notifications",
+    request_body = UpdateNotificationPreferences,
+    responses(
+        (status = 200, description = "Updated effective preferences", body = ApiResponse<Vec<NotificationPreference>>),
+        (status = 400, description = "Daily digest requested for the in-app channel")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_preferences(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Json(body): Json<UpdateNotificationPreferences>,
+) -> Result<Json<ApiResponse<Vec<NotificationPreference>>>, AppError> {
+    let preferences =
+        notification_preferences::update(&state.db, user.id, &body.preferences).await?;
+    Ok(ApiResponse::success(preferences))
+}
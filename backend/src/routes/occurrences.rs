@@ -0,0 +1,90 @@
+//! Finding occurrence API routes.
+//!
+//! A finding's sightings scan by scan, its streak and auto-close eligibility,
+//! and an application's auto-close candidates.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::models::ownership::OwnershipRole;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::occurrence::{self, AutoCloseCandidate, Occurrence, OccurrenceSummary};
+use crate::services::ownership;
+use crate::AppState;
+
+/// GET /api/v1/findings/{id}/occurrences -- a finding's occurrences, latest scan first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/occurrences",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID"), Pagination),
+    responses(
+        (status = 200, description = "Page of occurrences", body = ApiResponse<PagedResult<Occurrence>>),
+        (status = 403, description = "Not assigned to the finding's application"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ApiResponse<PagedResult<Occurrence>>>, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let result = occurrence::list_for_finding(&state.db_read, id, &pagination).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// GET /api/v1/findings/{id}/occurrences/summary -- consecutive scans,
+/// missed scans and auto-close eligibility of a finding.
+#[utoipa::path(
+    get,
+    path = "/api/v1/findings/{id}/occurrences/summary",
+    tag = "findings",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "Occurrence summary", body = ApiResponse<OccurrenceSummary>),
+        (status = 403, description = "Not assigned to the finding's application"),
+        (status = 404, description = "Finding not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn summary(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<OccurrenceSummary>>, AppError> {
+    ownership::authorize_finding(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let result = occurrence::summary_for_finding(&state.db_read, id).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// GET /api/v1/applications/{id}/auto-close-candidates -- open findings
+/// missing from enough scans to be auto-closed.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/auto-close-candidates",
+    tag = "applications",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Auto-close candidates, longest missing first", body = ApiResponse<Vec<AutoCloseCandidate>>),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn auto_close_candidates(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<AutoCloseCandidate>>>, AppError> {
+    ownership::authorize(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let result = occurrence::auto_close_candidates(&state.db_read, id).await?;
+    Ok(ApiResponse::success(result))
+}
@@ -0,0 +1,49 @@
+//! OWASP Top 10 routes: categories and the CWE mapping used to derive them.
+
+use axum::{extract::State, Json};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireAdmin;
+use crate::models::owasp::{OwaspCategory, OwaspMappingResult, UpdateOwaspMappings};
+use crate::services::owasp;
+use crate::AppState;
+
+/// GET /api/v1/owasp/categories — OWASP Top 10 categories with their mapped CWEs.
+#[utoipa::path(
+    get,
+    path = "/api/v1/owasp/categories",
+    tag = "owasp",
+    responses(
+        (status = 200, description = "Categories and CWE mappings", body = ApiResponse<Vec<OwaspCategory>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_categories(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<OwaspCategory>>>, AppError> {
+    let categories = owasp::list_categories(&state.db_read).await?;
+    Ok(ApiResponse::success(categories))
+}
+
+/// PUT /api/v1/owasp/mappings — add, change, or remove CWE mappings (admin).
+#[utoipa::path(
+    put,
+    path = "/api/v1/owasp/mappings",
+    tag = "owasp",
+    request_body = UpdateOwaspMappings,
+    responses(
+        (status = 200, description = "Mappings changed", body = ApiResponse<OwaspMappingResult>),
+        (status = 400, description = "Invalid CWE id or unknown category")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_mappings(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Json(body): Json<UpdateOwaspMappings>,
+) -> Result<Json<ApiResponse<OwaspMappingResult>>, AppError> {
+    let result = owasp::update_mappings(&state.db, &body).await?;
+    Ok(ApiResponse::success(result))
+}
@@ -0,0 +1,112 @@
+//! Application ownership routes: assignments per application and the
+//! current user's applications.
+//!
+//! Any authenticated user can view assignments. Managers and the
+//! application's owners manage them.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::models::ownership::{
+    ApplicationOwner, AssignOwnership, OwnedApplication, OwnershipRole,
+};
+use crate::services::ownership;
+use crate::AppState;
+
+/// GET /api/v1/applications/:id/owners — list an application's ownership assignments.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/owners",
+    tag = "ownership",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Assignments, most privileged first", body = ApiResponse<Vec<ApplicationOwner>>),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ApplicationOwner>>>, AppError> {
+    let owners = ownership::list_for_application(&state.db_read, id).await?;
+    Ok(ApiResponse::success(owners))
+}
+
+/// PUT /api/v1/applications/:id/owners/:user_id — assign a user a role (manager+ or application owner).
+#[utoipa::path(
+    put,
+    path = "/api/v1/applications/{id}/owners/{user_id}",
+    tag = "ownership",
+    params(
+        ("id" = Uuid, Path, description = "Application ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = AssignOwnership,
+    responses(
+        (status = 200, description = "Assignment", body = ApiResponse<ApplicationOwner>),
+        (status = 403, description = "Not a manager or owner of the application"),
+        (status = 404, description = "Application or user not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn assign(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<AssignOwnership>,
+) -> Result<Json<ApiResponse<ApplicationOwner>>, AppError> {
+    ownership::authorize(&state.db, &user, id, OwnershipRole::Owner).await?;
+    let owner = ownership::assign(&state.db, id, user_id, &body, &user).await?;
+    Ok(ApiResponse::success(owner))
+}
+
+/// DELETE /api/v1/applications/:id/owners/:user_id — remove an assignment (manager+ or application owner).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/applications/{id}/owners/{user_id}",
+    tag = "ownership",
+    params(
+        ("id" = Uuid, Path, description = "Application ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Assignment removed"),
+        (status = 403, description = "Not a manager or owner of the application"),
+        (status = 404, description = "Assignment not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    ownership::authorize(&state.db, &user, id, OwnershipRole::Owner).await?;
+    ownership::remove(&state.db, id, user_id).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// GET /api/v1/me/applications — applications the current user is assigned to.
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/applications",
+    tag = "ownership",
+    responses(
+        (status = 200, description = "Assigned applications by app code", body = ApiResponse<Vec<OwnedApplication>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn my_applications(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<OwnedApplication>>>, AppError> {
+    let applications = ownership::list_for_user(&state.db_read, user.id).await?;
+    Ok(ApiResponse::success(applications))
+}
@@ -0,0 +1,179 @@
+//! Release routes: per-application releases, release notes, and finding links.
+//!
+//! Any authenticated user can view releases. Analysts record releases and
+//! link findings to them; managers delete releases.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::{RequireAnalyst, RequireManager};
+use crate::middleware::validation::ValidatedJson;
+use crate::models::finding::Finding;
+use crate::models::release::{
+    CreateRelease, Release, ReleaseNotes, ReleaseWithCounts, SetFindingReleases, UpdateRelease,
+};
+use crate::services::release;
+use crate::AppState;
+
+/// GET /api/v1/applications/:id/releases — list an application's releases with finding counts.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/releases",
+    tag = "releases",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Releases, newest build first", body = ApiResponse<Vec<ReleaseWithCounts>>),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ReleaseWithCounts>>>, AppError> {
+    let releases = release::list_for_application(&state.db_read, id).await?;
+    Ok(ApiResponse::success(releases))
+}
+
+/// POST /api/v1/applications/:id/releases — record a release (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/applications/{id}/releases",
+    tag = "releases",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    request_body = CreateRelease,
+    responses(
+        (status = 200, description = "Created release", body = ApiResponse<Release>),
+        (status = 400, description = "Blank version or invalid commit hash"),
+        (status = 404, description = "Application not found"),
+        (status = 409, description = "The application already has this version")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireAnalyst(_user): RequireAnalyst,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<CreateRelease>,
+) -> Result<Json<ApiResponse<Release>>, AppError> {
+    let release = release::create(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(release))
+}
+
+/// GET /api/v1/releases/:id — get a release.
+#[utoipa::path(
+    get,
+    path = "/api/v1/releases/{id}",
+    tag = "releases",
+    params(("id" = Uuid, Path, description = "Release ID")),
+    responses(
+        (status = 200, description = "Release", body = ApiResponse<Release>),
+        (status = 404, description = "Release not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Release>>, AppError> {
+    let release = release::find_by_id(&state.db, id).await?;
+    Ok(ApiResponse::success(release))
+}
+
+/// PUT /api/v1/releases/:id — update a release (analyst+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/releases/{id}",
+    tag = "releases",
+    params(("id" = Uuid, Path, description = "Release ID")),
+    request_body = UpdateRelease,
+    responses(
+        (status = 200, description = "Updated release", body = ApiResponse<Release>),
+        (status = 400, description = "Blank version or invalid commit hash"),
+        (status = 404, description = "Release not found"),
+        (status = 409, description = "The application already has this version")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    RequireAnalyst(_user): RequireAnalyst,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateRelease>,
+) -> Result<Json<ApiResponse<Release>>, AppError> {
+    let release = release::update(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(release))
+}
+
+/// DELETE /api/v1/releases/:id — delete a release (manager+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/releases/{id}",
+    tag = "releases",
+    params(("id" = Uuid, Path, description = "Release ID")),
+    responses(
+        (status = 200, description = "Release deleted"),
+        (status = 404, description = "Release not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    release::delete(&state.db, id).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// GET /api/v1/releases/:id/notes — findings fixed in and introduced by a release.
+#[utoipa::path(
+    get,
+    path = "/api/v1/releases/{id}/notes",
+    tag = "releases",
+    params(("id" = Uuid, Path, description = "Release ID")),
+    responses(
+        (status = 200, description = "Release notes", body = ApiResponse<ReleaseNotes>),
+        (status = 404, description = "Release not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn notes(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ReleaseNotes>>, AppError> {
+    let notes = release::notes(&state.db_read, id).await?;
+    Ok(ApiResponse::success(notes))
+}
+
+/// PUT /api/v1/findings/:id/releases — link a finding to the releases that introduced and fixed it (analyst+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/findings/{id}/releases",
+    tag = "releases",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    request_body = SetFindingReleases,
+    responses(
+        (status = 200, description = "Updated finding", body = ApiResponse<Finding>),
+        (status = 400, description = "Release belongs to another application"),
+        (status = 404, description = "Finding or release not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_finding_releases(
+    State(state): State<AppState>,
+    RequireAnalyst(_user): RequireAnalyst,
+    Path(id): Path<Uuid>,
+    Json(body): Json<SetFindingReleases>,
+) -> Result<Json<ApiResponse<Finding>>, AppError> {
+    let finding = release::set_finding_releases(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(finding))
+}
@@ -0,0 +1,57 @@
+//! Remediation routes: SCA upgrade plans for the portfolio and per application.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::models::ownership::OwnershipRole;
+use crate::services::ownership;
+use crate::services::remediation::{self, PackageUpgrade, UpgradePlanQuery};
+use crate::AppState;
+
+/// GET /api/v1/remediation/upgrade-plan — minimal upgrade per vulnerable package.
+#[utoipa::path(
+    get,
+    path = "/api/v1/remediation/upgrade-plan",
+    tag = "remediation",
+    params(UpgradePlanQuery),
+    responses(
+        (status = 200, description = "Recommended package upgrades, most severe first", body = ApiResponse<Vec<PackageUpgrade>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upgrade_plan(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(query): Query<UpgradePlanQuery>,
+) -> Result<Json<ApiResponse<Vec<PackageUpgrade>>>, AppError> {
+    let upgrades = remediation::portfolio_plan(&state.db_read, &query).await?;
+    Ok(ApiResponse::success(upgrades))
+}
+
+/// GET /api/v1/applications/:id/upgrade-plan — upgrade plan for one application.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/upgrade-plan",
+    tag = "remediation",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "Recommended package upgrades, most severe first", body = ApiResponse<Vec<PackageUpgrade>>),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn application_upgrade_plan(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<PackageUpgrade>>>, AppError> {
+    ownership::authorize(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let upgrades = remediation::application_plan(&state.db_read, id).await?;
+    Ok(ApiResponse::success(upgrades))
+}
@@ -0,0 +1,168 @@
+//! Report schedule routes: schedule CRUD, on-demand runs, and run history.
+//!
+//! Schedules deliver platform data to external recipients, so every
+//! endpoint requires the manager role.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::report_schedule::{
+    CreateReportSchedule, ReportRun, ReportSchedule, UpdateReportSchedule,
+};
+use crate::services::report_delivery::ReportDelivery;
+use crate::services::report_schedule;
+use crate::AppState;
+
+/// GET /api/v1/report-schedules — list report schedules (manager+).
+#[utoipa::path(
+    get,
+    path = "/api/v1/report-schedules",
+    tag = "report-schedules",
+    responses(
+        (status = 200, description = "All report schedules", body = ApiResponse<Vec<ReportSchedule>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+) -> Result<Json<ApiResponse<Vec<ReportSchedule>>>, AppError> {
+    let schedules = report_schedule::list(&state.db).await?;
+    Ok(ApiResponse::success(schedules))
+}
+
+/// POST /api/v1/report-schedules — create a report schedule (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/report-schedules",
+    tag = "report-schedules",
+    request_body = CreateReportSchedule,
+    responses(
+        (status = 200, description = "Created schedule", body = ApiResponse<ReportSchedule>),
+        (status = 400, description = "Invalid cron expression or recipients")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<CreateReportSchedule>,
+) -> Result<Json<ApiResponse<ReportSchedule>>, AppError> {
+    let schedule = report_schedule::create(&state.db, &body, manager.id).await?;
+    Ok(ApiResponse::success(schedule))
+}
+
+/// GET /api/v1/report-schedules/:id — get a report schedule (manager+).
+#[utoipa::path(
+    get,
+    path = "/api/v1/report-schedules/{id}",
+    tag = "report-schedules",
+    params(("id" = Uuid, Path, description = "Schedule ID")),
+    responses(
+        (status = 200, description = "Report schedule", body = ApiResponse<ReportSchedule>),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ReportSchedule>>, AppError> {
+    let schedule = report_schedule::find_by_id(&state.db, id).await?;
+    Ok(ApiResponse::success(schedule))
+}
+
+/// PUT /api/v1/report-schedules/:id — update a report schedule (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/report-schedules/{id}",
+    tag = "report-schedules",
+    params(("id" = Uuid, Path, description = "Schedule ID")),
+    request_body = UpdateReportSchedule,
+    responses(
+        (status = 200, description = "Updated schedule", body = ApiResponse<ReportSchedule>),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateReportSchedule>,
+) -> Result<Json<ApiResponse<ReportSchedule>>, AppError> {
+    let schedule = report_schedule::update(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(schedule))
+}
+
+/// DELETE /api/v1/report-schedules/:id — delete a schedule and its history (manager+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/report-schedules/{id}",
+    tag = "report-schedules",
+    params(("id" = Uuid, Path, description = "Schedule ID")),
+    responses(
+        (status = 200, description = "Schedule deleted"),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    report_schedule::delete(&state.db, id).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// POST /api/v1/report-schedules/:id/run — render and deliver now, in the background (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/report-schedules/{id}/run",
+    tag = "report-schedules",
+    params(("id" = Uuid, Path, description = "Schedule ID")),
+    responses(
+        (status = 200, description = "Queued run", body = ApiResponse<ReportRun>),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn run_now(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ReportRun>>, AppError> {
+    let delivery = ReportDelivery::from_config(&state.config)?;
+    let run = report_schedule::trigger_now(&state.db, delivery, id).await?;
+    Ok(ApiResponse::success(run))
+}
+
+/// GET /api/v1/report-schedules/:id/runs — paginated run history (manager+).
+#[utoipa::path(
+    get,
+    path = "/api/v1/report-schedules/{id}/runs",
+    tag = "report-schedules",
+    params(("id" = Uuid, Path, description = "Schedule ID"), Pagination),
+    responses(
+        (status = 200, description = "Page of runs, newest first", body = ApiResponse<PagedResult<ReportRun>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_runs(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ApiResponse<PagedResult<ReportRun>>>, AppError> {
+    let runs = report_schedule::list_runs(&state.db, id, &pagination).await?;
+    Ok(ApiResponse::success(runs))
+}
@@ -0,0 +1,120 @@
+//! Report template routes: CRUD for customizable report layouts (admin only).
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAdmin;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::report_template::{CreateReportTemplate, ReportTemplate, UpdateReportTemplate};
+use crate::services::report_template;
+use crate::AppState;
+
+/// GET /api/v1/report-templates — list report templates (admin).
+#[utoipa::path(
+    get,
+    path = "/api/v1/report-templates",
+    tag = "report-templates",
+    responses(
+        (status = 200, description = "All report templates", body = ApiResponse<Vec<ReportTemplate>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+) -> Result<Json<ApiResponse<Vec<ReportTemplate>>>, AppError> {
+    let templates = report_template::list(&state.db).await?;
+    Ok(ApiResponse::success(templates))
+}
+
+/// POST /api/v1/report-templates — create a report template (admin).
+#[utoipa::path(
+    post,
+    path = "/api/v1/report-templates",
+    tag = "report-templates",
+    request_body = CreateReportTemplate,
+    responses(
+        (status = 200, description = "Created template", body = ApiResponse<ReportTemplate>),
+        (status = 400, description = "Template does not compile"),
+        (status = 409, description = "An active template already exists for this kind, format, and language")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    ValidatedJson(body): ValidatedJson<CreateReportTemplate>,
+) -> Result<Json<ApiResponse<ReportTemplate>>, AppError> {
+    let template = report_template::create(&state.db, &body, admin.id).await?;
+    Ok(ApiResponse::success(template))
+}
+
+/// GET /api/v1/report-templates/:id — get a report template (admin).
+#[utoipa::path(
+    get,
+    path = "/api/v1/report-templates/{id}",
+    tag = "report-templates",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 200, description = "Report template", body = ApiResponse<ReportTemplate>),
+        (status = 404, description = "Template not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ReportTemplate>>, AppError> {
+    let template = report_template::find_by_id(&state.db, id).await?;
+    Ok(ApiResponse::success(template))
+}
+
+/// PUT /api/v1/report-templates/:id — update a report template (admin).
+#[utoipa::path(
+    put,
+    path = "/api/v1/report-templates/{id}",
+    tag = "report-templates",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    request_body = UpdateReportTemplate,
+    responses(
+        (status = 200, description = "Updated template", body = ApiResponse<ReportTemplate>),
+        (status = 400, description = "Template does not compile"),
+        (status = 404, description = "Template not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateReportTemplate>,
+) -> Result<Json<ApiResponse<ReportTemplate>>, AppError> {
+    let template = report_template::update(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(template))
+}
+
+/// DELETE /api/v1/report-templates/:id — delete a report template (admin).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/report-templates/{id}",
+    tag = "report-templates",
+    params(("id" = Uuid, Path, description = "Template ID")),
+    responses(
+        (status = 200, description = "Template deleted"),
+        (status = 404, description = "Template not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    RequireAdmin(_admin): RequireAdmin,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    report_template::delete(&state.db, id).await?;
+    Ok(ApiResponse::success(()))
+}
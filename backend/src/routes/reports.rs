@@ -0,0 +1,238 @@
+//! Report routes: generated documents for management and compliance audiences.
+//!
+//! PDF and HTML output use the active report template for the requested
+//! language (`lang`, default `en`) when an admin has configured one, and the
+//! built-in layout otherwise.
+
+use axum::{
+    extract::{Query, State},
+    response::{Html, IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::models::report_template::ReportKind;
+use crate::routes::exports::attachment;
+use crate::services::dora_report::{self, DoraReport};
+use crate::services::executive_report::{self, ExecutiveSummary, ReportPeriod};
+use crate::services::false_positive_report::{self, FalsePositiveParams, FalsePositiveReport};
+use crate::services::gdpr_report::{self, GdprReport};
+use crate::services::pdf_report::ReportDocument;
+use crate::services::report_template::{self, DEFAULT_LANGUAGE};
+use crate::AppState;
+
+/// Output format for generated reports.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Pdf,
+    Html,
+}
+
+fn default_language() -> String {
+    DEFAULT_LANGUAGE.to_string()
+}
+
+/// Query parameters for the executive summary report.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExecutiveReportParams {
+    /// Reporting window ending now (defaults to `quarter`).
+    #[serde(default)]
+    pub period: ReportPeriod,
+    /// Defaults to `pdf`.
+    pub format: Option<ReportFormat>,
+    /// Template language (defaults to `en`).
+    #[serde(default = "default_language")]
+    pub lang: String,
+}
+
+/// GET /api/v1/reports/executive — executive summary (`period=month|quarter|year`,
+/// `format=pdf|html|json`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/executive",
+    tag = "reports",
+    params(ExecutiveReportParams),
+    responses(
+        (status = 200, description = "Executive summary report", content(
+            (ApiResponse<ExecutiveSummary> = "application/json"),
+            (String = "application/pdf"),
+            (String = "text/html")
+        ))
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn executive(
+    State(state): State<AppState>,
+    _current_user: CurrentUser,
+    Query(params): Query<ExecutiveReportParams>,
+) -> Result<Response, AppError> {
+    let summary = executive_report::build(&state.db_read, params.period).await?;
+    let filename = format!(
+        "executive_summary_{}",
+        summary.period_end.format("%Y-%m-%d")
+    );
+    respond(
+        &state,
+        ReportKind::Executive,
+        params.format.unwrap_or(ReportFormat::Pdf),
+        &params.lang,
+        &summary,
+        || executive_report::to_document(&summary),
+        &filename,
+    )
+    .await
+}
+
+/// Query parameters for the DORA compliance report.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DoraReportParams {
+    /// Window for remediation metrics (defaults to `quarter`).
+    #[serde(default)]
+    pub period: ReportPeriod,
+    /// Defaults to `json`.
+    #[serde(default)]
+    pub format: ReportFormat,
+    /// Template language (defaults to `en`).
+    #[serde(default = "default_language")]
+    pub lang: String,
+}
+
+/// GET /api/v1/reports/dora — DORA ICT-risk report for `is_dora_fei` applications
+/// (`format=json|pdf|html`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/dora",
+    tag = "reports",
+    params(DoraReportParams),
+    responses(
+        (status = 200, description = "DORA compliance report", content(
+            (ApiResponse<DoraReport> = "application/json"),
+            (String = "application/pdf"),
+            (String = "text/html")
+        ))
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn dora(
+    State(state): State<AppState>,
+    _current_user: CurrentUser,
+    Query(params): Query<DoraReportParams>,
+) -> Result<Response, AppError> {
+    let report = dora_report::build(&state.db_read, params.period).await?;
+    let filename = format!("dora_report_{}", report.period_end.format("%Y-%m-%d"));
+    respond(
+        &state,
+        ReportKind::Dora,
+        params.format,
+        &params.lang,
+        &report,
+        || dora_report::to_document(&report),
+        &filename,
+    )
+    .await
+}
+
+/// Query parameters for the GDPR report.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GdprReportParams {
+    /// Defaults to `json`.
+    #[serde(default)]
+    pub format: ReportFormat,
+    /// Template language (defaults to `en`).
+    #[serde(default = "default_language")]
+    pub lang: String,
+}
+
+/// GET /api/v1/reports/gdpr — personal-data findings and risk acceptances on
+/// `is_gdpr_subject` applications for DPO review (`format=json|pdf|html`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/gdpr",
+    tag = "reports",
+    params(GdprReportParams),
+    responses(
+        (status = 200, description = "GDPR personal-data report", content(
+            (ApiResponse<GdprReport> = "application/json"),
+            (String = "application/pdf"),
+            (String = "text/html")
+        ))
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn gdpr(
+    State(state): State<AppState>,
+    _current_user: CurrentUser,
+    Query(params): Query<GdprReportParams>,
+) -> Result<Response, AppError> {
+    let report = gdpr_report::build(&state.db_read).await?;
+    let filename = format!("gdpr_report_{}", report.generated_at.format("%Y-%m-%d"));
+    respond(
+        &state,
+        ReportKind::Gdpr,
+        params.format,
+        &params.lang,
+        &report,
+        || gdpr_report::to_document(&report),
+        &filename,
+    )
+    .await
+}
+
+/// GET /api/v1/reports/false-positives — false-positive rates per tool, scanner
+/// rule, and application from lifecycle history (`period=month|quarter|year`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/false-positives",
+    tag = "reports",
+    params(FalsePositiveParams),
+    responses(
+        (status = 200, description = "False-positive rates, noisiest first", body = ApiResponse<FalsePositiveReport>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn false_positives(
+    State(state): State<AppState>,
+    _current_user: CurrentUser,
+    Query(params): Query<FalsePositiveParams>,
+) -> Result<Json<ApiResponse<FalsePositiveReport>>, AppError> {
+    let report = false_positive_report::build(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(report))
+}
+
+/// Serve report data in the requested format.
+async fn respond<T: Serialize>(
+    state: &AppState,
+    kind: ReportKind,
+    format: ReportFormat,
+    lang: &str,
+    report: &T,
+    fallback: impl FnOnce() -> ReportDocument,
+    filename: &str,
+) -> Result<Response, AppError> {
+    match format {
+        ReportFormat::Json => Ok(ApiResponse::success(report).into_response()),
+        ReportFormat::Pdf => {
+            let pdf =
+                report_template::render_pdf(&state.db_read, kind, lang, report, fallback).await?;
+            Ok(attachment(
+                "application/pdf",
+                &format!("{filename}.pdf"),
+                pdf,
+            ))
+        }
+        ReportFormat::Html => {
+            let html =
+                report_template::render_html(&state.db_read, kind, lang, report, fallback).await?;
+            Ok(Html(html).into_response())
+        }
+    }
+}
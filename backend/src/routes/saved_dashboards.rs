@@ -0,0 +1,124 @@
+//! Saved dashboard routes: CRUD for user-defined dashboards.
+//!
+//! Any authenticated user can build dashboards; private ones are visible to
+//! their owner only, shared ones to everyone.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::saved_dashboard::{CreateSavedDashboard, SavedDashboard, UpdateSavedDashboard};
+use crate::services::saved_dashboard;
+use crate::AppState;
+
+/// GET /api/v1/dashboards — list own and shared dashboards.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboards",
+    tag = "saved-dashboards",
+    responses(
+        (status = 200, description = "Own and shared dashboards", body = ApiResponse<Vec<SavedDashboard>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<SavedDashboard>>>, AppError> {
+    let dashboards = saved_dashboard::list(&state.db, &current_user).await?;
+    Ok(ApiResponse::success(dashboards))
+}
+
+/// POST /api/v1/dashboards — create a dashboard owned by the caller.
+#[utoipa::path(
+    post,
+    path = "/api/v1/dashboards",
+    tag = "saved-dashboards",
+    request_body = CreateSavedDashboard,
+    responses(
+        (status = 200, description = "Created dashboard", body = ApiResponse<SavedDashboard>),
+        (status = 400, description = "Missing name or invalid widget")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    ValidatedJson(body): ValidatedJson<CreateSavedDashboard>,
+) -> Result<Json<ApiResponse<SavedDashboard>>, AppError> {
+    let dashboard = saved_dashboard::create(&state.db, &body, &current_user).await?;
+    Ok(ApiResponse::success(dashboard))
+}
+
+/// GET /api/v1/dashboards/:id — get an own or shared dashboard.
+#[utoipa::path(
+    get,
+    path = "/api/v1/dashboards/{id}",
+    tag = "saved-dashboards",
+    params(("id" = Uuid, Path, description = "Dashboard ID")),
+    responses(
+        (status = 200, description = "Saved dashboard", body = ApiResponse<SavedDashboard>),
+        (status = 404, description = "Dashboard not found or private to another user")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<SavedDashboard>>, AppError> {
+    let dashboard = saved_dashboard::find_by_id(&state.db, id, &current_user).await?;
+    Ok(ApiResponse::success(dashboard))
+}
+
+/// PUT /api/v1/dashboards/:id — update a dashboard (owner or admin).
+#[utoipa::path(
+    put,
+    path = "/api/v1/dashboards/{id}",
+    tag = "saved-dashboards",
+    params(("id" = Uuid, Path, description = "Dashboard ID")),
+    request_body = UpdateSavedDashboard,
+    responses(
+        (status = 200, description = "Updated dashboard", body = ApiResponse<SavedDashboard>),
+        (status = 400, description = "Invalid widget"),
+        (status = 403, description = "Shared dashboard owned by another user"),
+        (status = 404, description = "Dashboard not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateSavedDashboard>,
+) -> Result<Json<ApiResponse<SavedDashboard>>, AppError> {
+    let dashboard = saved_dashboard::update(&state.db, id, &body, &current_user).await?;
+    Ok(ApiResponse::success(dashboard))
+}
+
+/// DELETE /api/v1/dashboards/:id — delete a dashboard (owner or admin).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/dashboards/{id}",
+    tag = "saved-dashboards",
+    params(("id" = Uuid, Path, description = "Dashboard ID")),
+    responses(
+        (status = 200, description = "Dashboard deleted"),
+        (status = 403, description = "Shared dashboard owned by another user"),
+        (status = 404, description = "Dashboard not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    saved_dashboard::delete(&state.db, id, &current_user).await?;
+    Ok(ApiResponse::success(()))
+}
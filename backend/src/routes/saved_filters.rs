@@ -0,0 +1,126 @@
+//! Saved filter routes: CRUD for named finding-list views.
+//!
+//! Any authenticated user can save filters; personal ones are visible to
+//! their owner only, shared ones to everyone.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::saved_filter::{CreateSavedFilter, SavedFilter, UpdateSavedFilter};
+use crate::services::saved_filter;
+use crate::AppState;
+
+/// GET /api/v1/saved-filters — list own and shared filters.
+#[utoipa::path(
+    get,
+    path = "/api/v1/saved-filters",
+    tag = "saved-filters",
+    responses(
+        (status = 200, description = "Own and shared filters, own first", body = ApiResponse<Vec<SavedFilter>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<SavedFilter>>>, AppError> {
+    let filters = saved_filter::list(&state.db, &current_user).await?;
+    Ok(ApiResponse::success(filters))
+}
+
+/// POST /api/v1/saved-filters — save a filter owned by the caller.
+#[utoipa::path(
+    post,
+    path = "/api/v1/saved-filters",
+    tag = "saved-filters",
+    request_body = CreateSavedFilter,
+    responses(
+        (status = 200, description = "Created filter", body = ApiResponse<SavedFilter>),
+        (status = 400, description = "Missing name, invalid filters, sort, or columns"),
+        (status = 409, description = "Caller already has a filter with this name")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    ValidatedJson(body): ValidatedJson<CreateSavedFilter>,
+) -> Result<Json<ApiResponse<SavedFilter>>, AppError> {
+    let filter = saved_filter::create(&state.db, &body, &current_user).await?;
+    Ok(ApiResponse::success(filter))
+}
+
+/// GET /api/v1/saved-filters/:id — get an own or shared filter.
+#[utoipa::path(
+    get,
+    path = "/api/v1/saved-filters/{id}",
+    tag = "saved-filters",
+    params(("id" = Uuid, Path, description = "Saved filter ID")),
+    responses(
+        (status = 200, description = "Saved filter", body = ApiResponse<SavedFilter>),
+        (status = 404, description = "Filter not found or personal to another user")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<SavedFilter>>, AppError> {
+    let filter = saved_filter::find_by_id(&state.db, id, &current_user).await?;
+    Ok(ApiResponse::success(filter))
+}
+
+/// PUT /api/v1/saved-filters/:id — update a filter (owner or admin).
+#[utoipa::path(
+    put,
+    path = "/api/v1/saved-filters/{id}",
+    tag = "saved-filters",
+    params(("id" = Uuid, Path, description = "Saved filter ID")),
+    request_body = UpdateSavedFilter,
+    responses(
+        (status = 200, description = "Updated filter", body = ApiResponse<SavedFilter>),
+        (status = 400, description = "Invalid filters, sort, or columns"),
+        (status = 403, description = "Shared filter owned by another user"),
+        (status = 404, description = "Filter not found"),
+        (status = 409, description = "Owner already has a filter with this name")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateSavedFilter>,
+) -> Result<Json<ApiResponse<SavedFilter>>, AppError> {
+    let filter = saved_filter::update(&state.db, id, &body, &current_user).await?;
+    Ok(ApiResponse::success(filter))
+}
+
+/// DELETE /api/v1/saved-filters/:id — delete a filter (owner or admin).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/saved-filters/{id}",
+    tag = "saved-filters",
+    params(("id" = Uuid, Path, description = "Saved filter ID")),
+    responses(
+        (status = 200, description = "Filter deleted"),
+        (status = 403, description = "Shared filter owned by another user"),
+        (status = 404, description = "Filter not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    saved_filter::delete(&state.db, id, &current_user).await?;
+    Ok(ApiResponse::success(()))
+}
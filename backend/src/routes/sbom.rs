@@ -0,0 +1,117 @@
+//! SBOM routes: per-application upload and retrieval, and package lookup.
+//!
+//! Analysts upload SBOMs; any authenticated user can read them.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireAnalyst;
+use crate::models::sbom::{PackageOccurrence, Sbom, SbomDetail};
+use crate::services::sbom::{self, PackageQuery};
+use crate::AppState;
+
+/// Query parameters selecting an SBOM release.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SbomQuery {
+    /// Release label; omit for the SBOM without a release (upload) or the
+    /// latest SBOM of any release (retrieval).
+    pub release: Option<String>,
+}
+
+/// POST /api/v1/applications/:id/sbom — upload a CycloneDX or SPDX JSON SBOM (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/applications/{id}/sbom",
+    tag = "sbom",
+    params(("id" = Uuid, Path, description = "Application ID"), SbomQuery),
+    request_body(content = Object, description = "CycloneDX or SPDX JSON document"),
+    responses(
+        (status = 200, description = "Stored SBOM", body = ApiResponse<Sbom>),
+        (status = 400, description = "Unrecognized SBOM document"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload(
+    State(state): State<AppState>,
+    RequireAnalyst(user): RequireAnalyst,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SbomQuery>,
+    Json(doc): Json<Value>,
+) -> Result<Json<ApiResponse<Sbom>>, AppError> {
+    let sbom = sbom::import(&state.db, id, query.release.as_deref(), &doc, user.id).await?;
+    Ok(ApiResponse::success(sbom))
+}
+
+/// GET /api/v1/applications/:id/sbom — latest SBOM with components and linked SCA findings.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/sbom",
+    tag = "sbom",
+    params(("id" = Uuid, Path, description = "Application ID"), SbomQuery),
+    responses(
+        (status = 200, description = "SBOM with components", body = ApiResponse<SbomDetail>),
+        (status = 404, description = "No SBOM for the application or release")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_latest(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<SbomQuery>,
+) -> Result<Json<ApiResponse<SbomDetail>>, AppError> {
+    let detail = sbom::latest_for_application(&state.db_read, id, query.release.as_deref()).await?;
+    Ok(ApiResponse::success(detail))
+}
+
+/// GET /api/v1/applications/:id/sboms — list an application's SBOM uploads.
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/sboms",
+    tag = "sbom",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    responses(
+        (status = 200, description = "SBOMs, newest first", body = ApiResponse<Vec<Sbom>>),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Sbom>>>, AppError> {
+    let sboms = sbom::list_for_application(&state.db_read, id).await?;
+    Ok(ApiResponse::success(sboms))
+}
+
+/// GET /api/v1/sbom/packages — applications containing a package, by latest SBOM per release.
+#[utoipa::path(
+    get,
+    path = "/api/v1/sbom/packages",
+    tag = "sbom",
+    params(PackageQuery),
+    responses(
+        (status = 200, description = "Applications and releases containing the package", body = ApiResponse<Vec<PackageOccurrence>>),
+        (status = 400, description = "Missing package name")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn find_package(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(query): Query<PackageQuery>,
+) -> Result<Json<ApiResponse<Vec<PackageOccurrence>>>, AppError> {
+    let occurrences = sbom::find_package(&state.db_read, &query).await?;
+    Ok(ApiResponse::success(occurrences))
+}
@@ -0,0 +1,86 @@
+//! Scanner rule catalog routes: browse the rules seen during ingestion and
+//! set their default severity or suppression.
+//!
+//! Any authenticated user can browse the catalog; managers set rule policies.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::scanner_rule::{ScannerRule, UpdateRulePolicy};
+use crate::services::scanner_rule::{self, ScannerRuleFilters};
+use crate::AppState;
+
+/// GET /api/v1/scanner-rules — browse the rule catalog with finding stats.
+#[utoipa::path(
+    get,
+    path = "/api/v1/scanner-rules",
+    tag = "scanner-rules",
+    params(Pagination, ScannerRuleFilters),
+    responses(
+        (status = 200, description = "Paged rule catalog", body = ApiResponse<PagedResult<ScannerRule>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(pagination): Query<Pagination>,
+    Query(filters): Query<ScannerRuleFilters>,
+) -> Result<Json<ApiResponse<PagedResult<ScannerRule>>>, AppError> {
+    let result = scanner_rule::list(&state.db_read, &filters, &pagination).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// GET /api/v1/scanner-rules/:id — get a rule with its finding stats.
+#[utoipa::path(
+    get,
+    path = "/api/v1/scanner-rules/{id}",
+    tag = "scanner-rules",
+    params(("id" = Uuid, Path, description = "Scanner rule ID")),
+    responses(
+        (status = 200, description = "Scanner rule", body = ApiResponse<ScannerRule>),
+        (status = 404, description = "Scanner rule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ScannerRule>>, AppError> {
+    let rule = scanner_rule::find_by_id(&state.db_read, id).await?;
+    Ok(ApiResponse::success(rule))
+}
+
+/// PUT /api/v1/scanner-rules/:id/policy — set a rule's default severity and
+/// suppression (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/scanner-rules/{id}/policy",
+    tag = "scanner-rules",
+    params(("id" = Uuid, Path, description = "Scanner rule ID")),
+    request_body = UpdateRulePolicy,
+    responses(
+        (status = 200, description = "Rule with its new policy", body = ApiResponse<ScannerRule>),
+        (status = 400, description = "Suppression without a reason"),
+        (status = 404, description = "Scanner rule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_policy(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateRulePolicy>,
+) -> Result<Json<ApiResponse<ScannerRule>>, AppError> {
+    let rule = scanner_rule::update_policy(&state.db, id, &body, manager.id).await?;
+    Ok(ApiResponse::success(rule))
+}
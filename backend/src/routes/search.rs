@@ -0,0 +1,38 @@
+//! Global search route backing the omnisearch box.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::models::pagination::Pagination;
+use crate::services::ownership;
+use crate::services::search::{self, SearchParams, SearchResults};
+use crate::AppState;
+
+/// GET /api/v1/search — findings, applications, CVEs, packages, and comments
+/// matching `q`, grouped by type and ranked; `page`/`per_page` apply per group.
+#[utoipa::path(
+    get,
+    path = "/api/v1/search",
+    tag = "search",
+    params(SearchParams, Pagination),
+    responses(
+        (status = 200, description = "Ranked hits grouped by entity type", body = ApiResponse<SearchResults>),
+        (status = 400, description = "Query too short or too long"),
+        (status = 429, description = "Search rate limit exceeded")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn search(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Query(mut params): Query<SearchParams>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<ApiResponse<SearchResults>>, AppError> {
+    params.visible_to = ownership::read_scope(&current_user);
+    let results = search::search(&state.db_read, &params, &pagination).await?;
+    Ok(ApiResponse::success(results))
+}
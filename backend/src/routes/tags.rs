@@ -0,0 +1,170 @@
+//! Tag routes: CRUD, rename, merge, usage counts, and autocomplete.
+//!
+//! Any authenticated user can read tags. Creating and changing them is
+//! limited to managers, like bulk tagging, since renames, merges, and
+//! deletions rewrite the tags of every affected finding.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::tag::{CreateTag, MergeTags, TagWithUsage, UpdateTag};
+use crate::services::tag::{self, AutocompleteParams};
+use crate::AppState;
+
+/// GET /api/v1/tags — list tags with usage counts.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tags",
+    tag = "tags",
+    responses(
+        (status = 200, description = "All tags by name", body = ApiResponse<Vec<TagWithUsage>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<TagWithUsage>>>, AppError> {
+    let tags = tag::list(&state.db_read).await?;
+    Ok(ApiResponse::success(tags))
+}
+
+/// GET /api/v1/tags/autocomplete — suggest tags by name prefix.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tags/autocomplete",
+    tag = "tags",
+    params(AutocompleteParams),
+    responses(
+        (status = 200, description = "Matching tags, most used first", body = ApiResponse<Vec<TagWithUsage>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn autocomplete(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Query(params): Query<AutocompleteParams>,
+) -> Result<Json<ApiResponse<Vec<TagWithUsage>>>, AppError> {
+    let tags = tag::autocomplete(&state.db_read, &params).await?;
+    Ok(ApiResponse::success(tags))
+}
+
+/// POST /api/v1/tags — create a tag (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/tags",
+    tag = "tags",
+    request_body = CreateTag,
+    responses(
+        (status = 200, description = "Created tag", body = ApiResponse<TagWithUsage>),
+        (status = 400, description = "Invalid name or color"),
+        (status = 409, description = "A tag with this name already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<CreateTag>,
+) -> Result<Json<ApiResponse<TagWithUsage>>, AppError> {
+    let created = tag::create(&state.db, &body).await?;
+    Ok(ApiResponse::success(created))
+}
+
+/// GET /api/v1/tags/:id — get a tag with its usage count.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tags/{id}",
+    tag = "tags",
+    params(("id" = Uuid, Path, description = "Tag ID")),
+    responses(
+        (status = 200, description = "Tag", body = ApiResponse<TagWithUsage>),
+        (status = 404, description = "Tag not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_by_id(
+    State(state): State<AppState>,
+    _user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<TagWithUsage>>, AppError> {
+    let found = tag::find_by_id(&state.db, id).await?;
+    Ok(ApiResponse::success(found))
+}
+
+/// PUT /api/v1/tags/:id — update a tag; a rename applies to every finding (manager+).
+#[utoipa::path(
+    put,
+    path = "/api/v1/tags/{id}",
+    tag = "tags",
+    params(("id" = Uuid, Path, description = "Tag ID")),
+    request_body = UpdateTag,
+    responses(
+        (status = 200, description = "Updated tag", body = ApiResponse<TagWithUsage>),
+        (status = 400, description = "Invalid name or color"),
+        (status = 404, description = "Tag not found"),
+        (status = 409, description = "Another tag already has this name; merge instead")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<UpdateTag>,
+) -> Result<Json<ApiResponse<TagWithUsage>>, AppError> {
+    let updated = tag::update(&state.db, id, &body).await?;
+    Ok(ApiResponse::success(updated))
+}
+
+/// DELETE /api/v1/tags/:id — delete a tag and remove it from findings (manager+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tags/{id}",
+    tag = "tags",
+    params(("id" = Uuid, Path, description = "Tag ID")),
+    responses(
+        (status = 200, description = "Tag deleted"),
+        (status = 404, description = "Tag not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    tag::delete(&state.db, id).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// POST /api/v1/tags/:id/merge — merge other tags into this one (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/tags/{id}/merge",
+    tag = "tags",
+    params(("id" = Uuid, Path, description = "Target tag ID")),
+    request_body = MergeTags,
+    responses(
+        (status = 200, description = "Target tag after the merge", body = ApiResponse<TagWithUsage>),
+        (status = 400, description = "No sources, or the target is among them"),
+        (status = 404, description = "Target or source tag not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn merge(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+    Path(id): Path<Uuid>,
+    ValidatedJson(body): ValidatedJson<MergeTags>,
+) -> Result<Json<ApiResponse<TagWithUsage>>, AppError> {
+    let merged = tag::merge(&state.db, id, &body.source_ids).await?;
+    Ok(ApiResponse::success(merged))
+}
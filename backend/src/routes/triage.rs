@@ -0,0 +1,80 @@
+//! Triage queue routes: list New findings awaiting triage and claim or
+//! release them so two analysts don't work the same finding.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireAnalyst;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::triage::{self, TriageClaim, TriageQueueFilters, TriageQueueItem};
+use crate::AppState;
+
+/// GET /api/v1/triage/queue — New findings awaiting triage, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/triage/queue",
+    tag = "triage",
+    params(Pagination, TriageQueueFilters),
+    responses(
+        (status = 200, description = "Paged triage queue", body = ApiResponse<PagedResult<TriageQueueItem>>),
+        (status = 403, description = "Analyst role required")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn queue(
+    State(state): State<AppState>,
+    RequireAnalyst(analyst): RequireAnalyst,
+    Query(pagination): Query<Pagination>,
+    Query(filters): Query<TriageQueueFilters>,
+) -> Result<Json<ApiResponse<PagedResult<TriageQueueItem>>>, AppError> {
+    let result = triage::queue(&state.db_read, &filters, &pagination, analyst.id).await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// POST /api/v1/triage/queue/{id}/claim — claim a finding, or renew one's own claim.
+#[utoipa::path(
+    post,
+    path = "/api/v1/triage/queue/{id}/claim",
+    tag = "triage",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "Finding claimed", body = ApiResponse<TriageClaim>),
+        (status = 404, description = "Finding not found"),
+        (status = 409, description = "Finding claimed by another analyst or no longer New")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn claim(
+    State(state): State<AppState>,
+    RequireAnalyst(analyst): RequireAnalyst,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<TriageClaim>>, AppError> {
+    let claim = triage::claim(&state.db, id, analyst.id).await?;
+    Ok(ApiResponse::success(claim))
+}
+
+/// DELETE /api/v1/triage/queue/{id}/claim — release a claim.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/triage/queue/{id}/claim",
+    tag = "triage",
+    params(("id" = Uuid, Path, description = "Finding ID")),
+    responses(
+        (status = 200, description = "Claim released"),
+        (status = 403, description = "Claim held by another analyst"),
+        (status = 404, description = "Finding or claim not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn release(
+    State(state): State<AppState>,
+    RequireAnalyst(analyst): RequireAnalyst,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    triage::release(&state.db, id, &analyst).await?;
+    Ok(ApiResponse::success(()))
+}
@@ -0,0 +1,77 @@
+//! Upload token routes: issue, list, and revoke the tokens CI jobs upload
+//! scanner output with (manager+).
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::rbac::RequireManager;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::upload_token::{CreateUploadToken, IssuedUploadToken, UploadToken};
+use crate::services::upload_token;
+use crate::AppState;
+
+/// GET /api/v1/upload-tokens — all upload tokens, active first (manager+).
+#[utoipa::path(
+    get,
+    path = "/api/v1/upload-tokens",
+    tag = "upload-tokens",
+    responses(
+        (status = 200, description = "Upload tokens, without their secrets", body = ApiResponse<Vec<UploadToken>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    RequireManager(_manager): RequireManager,
+) -> Result<Json<ApiResponse<Vec<UploadToken>>>, AppError> {
+    let tokens = upload_token::list(&state.db_read).await?;
+    Ok(ApiResponse::success(tokens))
+}
+
+/// POST /api/v1/upload-tokens — issue a token; its secret is returned only
+/// in this response (manager+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/upload-tokens",
+    tag = "upload-tokens",
+    request_body = CreateUploadToken,
+    responses(
+        (status = 200, description = "Token issued", body = ApiResponse<IssuedUploadToken>),
+        (status = 400, description = "Blank name or expiry in the past"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    ValidatedJson(body): ValidatedJson<CreateUploadToken>,
+) -> Result<Json<ApiResponse<IssuedUploadToken>>, AppError> {
+    let issued = upload_token::create(&state.db, &body, manager.id, &manager.username).await?;
+    Ok(ApiResponse::success(issued))
+}
+
+/// DELETE /api/v1/upload-tokens/{id} — revoke a token (manager+).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/upload-tokens/{id}",
+    tag = "upload-tokens",
+    params(("id" = Uuid, Path, description = "Upload token ID")),
+    responses(
+        (status = 200, description = "Token revoked"),
+        (status = 404, description = "No active token with this ID")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn revoke(
+    State(state): State<AppState>,
+    RequireManager(manager): RequireManager,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    upload_token::revoke(&state.db, id, manager.id, &manager.username).await?;
+    Ok(ApiResponse::success(()))
+}
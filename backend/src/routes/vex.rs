@@ -0,0 +1,105 @@
+//! VEX routes: import exploitability statements and export SCA posture.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::rbac::RequireAnalyst;
+use crate::models::ownership::OwnershipRole;
+use crate::services::ownership;
+use crate::services::vex::{self, VexFormat, VexImportResult};
+use crate::AppState;
+
+/// Query parameters for VEX export.
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VexExportParams {
+    pub format: Option<VexFormat>,
+}
+
+/// POST /api/v1/applications/:id/vex — apply an OpenVEX or CycloneDX VEX document (analyst+).
+#[utoipa::path(
+    post,
+    path = "/api/v1/applications/{id}/vex",
+    tag = "vex",
+    params(("id" = Uuid, Path, description = "Application ID")),
+    request_body = serde_json::Value,
+    responses(
+        (status = 200, description = "Outcome of applying the statements", body = ApiResponse<VexImportResult>),
+        (status = 400, description = "Unrecognized VEX document")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import(
+    State(state): State<AppState>,
+    RequireAnalyst(user): RequireAnalyst,
+    Path(id): Path<Uuid>,
+    Json(body): Json<serde_json::Value>,
+) -> Result<Json<ApiResponse<VexImportResult>>, AppError> {
+    let result = vex::import_for_application(
+        &state.db,
+        id,
+        &body,
+        user.id,
+        &user.username,
+        state.hec.as_ref(),
+    )
+    .await?;
+    Ok(ApiResponse::success(result))
+}
+
+/// GET /api/v1/applications/:id/vex — export SCA posture as VEX (`format=openvex|cyclonedx`).
+#[utoipa::path(
+    get,
+    path = "/api/v1/applications/{id}/vex",
+    tag = "vex",
+    params(("id" = Uuid, Path, description = "Application ID"), VexExportParams),
+    responses(
+        (status = 200, description = "VEX document download", content(
+            (serde_json::Value = "application/json"),
+            (serde_json::Value = "application/vnd.cyclonedx+json")
+        )),
+        (status = 403, description = "Not assigned to the application"),
+        (status = 404, description = "Application not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export(
+    State(state): State<AppState>,
+    current_user: CurrentUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<VexExportParams>,
+) -> Result<Response, AppError> {
+    ownership::authorize(&state.db, &current_user, id, OwnershipRole::Viewer).await?;
+    let format = params.format.unwrap_or_default();
+    let doc = vex::export_for_application(&state.db_read, id, format).await?;
+
+    let body = serde_json::to_vec_pretty(&doc)
+        .map_err(|e| AppError::Internal(format!("JSON serialization failed: {e}")))?;
+
+    let (content_type, filename) = match format {
+        VexFormat::Openvex => ("application/json", format!("vex_{id}.openvex.json")),
+        VexFormat::Cyclonedx => ("application/vnd.cyclonedx+json", format!("vex_{id}.cdx.json")),
+    };
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
@@ -0,0 +1,98 @@
+//! Watchlist routes: the current user's watched CVEs and packages, and the
+//! findings that matched them.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::errors::{ApiResponse, AppError};
+use crate::middleware::auth::CurrentUser;
+use crate::middleware::validation::ValidatedJson;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::watchlist::{CreateWatchlistEntry, WatchlistEntry, WatchlistHit};
+use crate::services::watchlist::{self, WatchlistHitFilters};
+use crate::AppState;
+
+/// GET /api/v1/watchlist — the current user's watchlist.
+#[utoipa::path(
+    get,
+    path = "/api/v1/watchlist",
+    tag = "watchlist",
+    responses(
+        (status = 200, description = "Watched CVEs and packages, newest first", body = ApiResponse<Vec<WatchlistEntry>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list(
+    State(state): State<AppState>,
+    user: CurrentUser,
+) -> Result<Json<ApiResponse<Vec<WatchlistEntry>>>, AppError> {
+    let entries = watchlist::list(&state.db_read, user.id).await?;
+    Ok(ApiResponse::success(entries))
+}
+
+/// POST /api/v1/watchlist — watch a CVE or package.
+#[utoipa::path(
+    post,
+    path = "/api/v1/watchlist",
+    tag = "watchlist",
+    request_body = CreateWatchlistEntry,
+    responses(
+        (status = 200, description = "Entry added", body = ApiResponse<WatchlistEntry>),
+        (status = 400, description = "Invalid CVE id or package"),
+        (status = 409, description = "Already watched")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    ValidatedJson(body): ValidatedJson<CreateWatchlistEntry>,
+) -> Result<Json<ApiResponse<WatchlistEntry>>, AppError> {
+    let entry = watchlist::create(&state.db, user.id, &body).await?;
+    Ok(ApiResponse::success(entry))
+}
+
+/// DELETE /api/v1/watchlist/{id} — stop watching, dropping the entry's hits.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/watchlist/{id}",
+    tag = "watchlist",
+    params(("id" = Uuid, Path, description = "Watchlist entry ID")),
+    responses(
+        (status = 200, description = "Entry removed"),
+        (status = 404, description = "Entry not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    watchlist::delete(&state.db, user.id, id).await?;
+    Ok(ApiResponse::success(()))
+}
+
+/// GET /api/v1/watchlist/hits — findings that matched the user's entries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/watchlist/hits",
+    tag = "watchlist",
+    params(Pagination, WatchlistHitFilters),
+    responses(
+        (status = 200, description = "Paged hits, newest first", body = ApiResponse<PagedResult<WatchlistHit>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn hits(
+    State(state): State<AppState>,
+    user: CurrentUser,
+    Query(pagination): Query<Pagination>,
+    Query(filters): Query<WatchlistHitFilters>,
+) -> Result<Json<ApiResponse<PagedResult<WatchlistHit>>>, AppError> {
+    let result = watchlist::hits(&state.db_read, user.id, &filters, &pagination).await?;
+    Ok(ApiResponse::success(result))
+}
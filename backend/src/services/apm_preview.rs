@@ -0,0 +1,381 @@
+//! APM import preview: reads an APM export without importing it, proposes a
+//! column mapping and shows how the first rows would be imported.
+//!
+//! Exports regularly arrive with renamed headers (`COD. ACRONIMO`,
+//! `INTEGRITA'`, reordered words), which made the import skip every row.
+//! Each mapped field is matched to the closest header: headers are compared
+//! without case, accents or punctuation, by shared words (an abbreviation
+//! matches the word it starts) and shared letter pairs. Matches are assigned
+//! best first, so a header serves one field at most.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::errors::AppError;
+use crate::models::application::AssetCriticality;
+use crate::services::application::{
+    map_criticality, read_apm_file, resolve_effective_owner, ApmFieldMapping, ApmFormat,
+};
+
+/// Lowest similarity at which a header is proposed for a field.
+const MATCH_THRESHOLD: f64 = 0.7;
+
+/// Shortest abbreviation matched against the word it starts.
+const MIN_ABBREVIATION: usize = 3;
+
+const DEFAULT_PREVIEW_ROWS: usize = 20;
+const MAX_PREVIEW_ROWS: usize = 200;
+
+/// Query parameters for the preview endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ApmPreviewParams {
+    /// Rows to preview (defaults to 20, max 200).
+    pub rows: Option<usize>,
+}
+
+impl ApmPreviewParams {
+    fn limit(&self) -> usize {
+        self.rows
+            .unwrap_or(DEFAULT_PREVIEW_ROWS)
+            .clamp(1, MAX_PREVIEW_ROWS)
+    }
+}
+
+/// How an APM file would be imported.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApmImportPreview {
+    /// Headers in file order.
+    pub headers: Vec<String>,
+    pub total_rows: usize,
+    /// The mapping to import with: matched headers, and the requested
+    /// columns for fields without a match.
+    pub proposed_mapping: ApmFieldMapping,
+    pub matches: Vec<ApmColumnMatch>,
+    /// Headers no field is read from.
+    pub unmatched_headers: Vec<String>,
+    /// Rows the import would skip for lack of an app code.
+    pub skipped_rows: usize,
+    pub rows: Vec<ApmPreviewRow>,
+}
+
+/// The header proposed for one mapped field.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ApmColumnMatch {
+    /// Field of [`ApmFieldMapping`], e.g. `app_code_column`.
+    pub field: String,
+    /// Column requested for the field.
+    pub expected_column: String,
+    /// Closest header, if any is similar enough.
+    pub matched_column: Option<String>,
+    /// Similarity of the match, 0–1; 1 for an exact match.
+    pub score: f64,
+}
+
+/// One row as the import would read it.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApmPreviewRow {
+    /// Line in the file, counting the header as line 1.
+    pub row: usize,
+    /// `None` when the row would be skipped.
+    pub app_code: Option<String>,
+    pub app_name: Option<String>,
+    pub criticality: AssetCriticality,
+    pub ssa_code: Option<String>,
+    pub effective_office_owner: Option<String>,
+    pub effective_office_name: Option<String>,
+    /// Whether the import would update an existing application.
+    pub existing: bool,
+    /// Cell values, aligned with `headers`.
+    pub values: Vec<String>,
+}
+
+/// Headers and values compared without case, accents or punctuation.
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c.to_ascii_uppercase() {
+            'À' | 'Á' | 'Â' | 'Ä' | 'à' | 'á' | 'â' | 'ä' => 'A',
+            'È' | 'É' | 'Ê' | 'Ë' | 'è' | 'é' | 'ê' | 'ë' => 'E',
+            'Ì' | 'Í' | 'Î' | 'Ï' | 'ì' | 'í' | 'î' | 'ï' => 'I',
+            'Ò' | 'Ó' | 'Ô' | 'Ö' | 'ò' | 'ó' | 'ô' | 'ö' => 'O',
+            'Ù' | 'Ú' | 'Û' | 'Ü' | 'ù' | 'ú' | 'û' | 'ü' => 'U',
+            c if c.is_ascii_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn words_match(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short == long || (short.len() >= MIN_ABBREVIATION && long.starts_with(short))
+}
+
+/// Dice coefficient over words, an abbreviation matching its word.
+fn word_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<&str> = a.split(' ').collect();
+    let b: Vec<&str> = b.split(' ').collect();
+    let shared = a
+        .iter()
+        .filter(|w| b.iter().any(|o| words_match(w, o)))
+        .count()
+        + b.iter()
+            .filter(|w| a.iter().any(|o| words_match(w, o)))
+            .count();
+    shared as f64 / (a.len() + b.len()) as f64
+}
+
+fn bigrams(value: &str) -> HashSet<(char, char)> {
+    let chars: Vec<char> = value.chars().filter(|c| *c != ' ').collect();
+    chars.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// Dice coefficient over letter pairs, ignoring word breaks.
+fn bigram_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (bigrams(a), bigrams(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    2.0 * a.intersection(&b).count() as f64 / (a.len() + b.len()) as f64
+}
+
+/// Similarity of a header to an expected column, 0–1.
+fn similarity(header: &str, expected: &str) -> f64 {
+    let (a, b) = (normalize(header), normalize(expected));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    // Below 1 unless the names are the same
+    ((word_similarity(&a, &b) + bigram_similarity(&a, &b)) / 2.0).min(0.99)
+}
+
+/// Match each field of `requested` to a header, best matches first.
+fn propose_mapping(
+    headers: &[String],
+    requested: &ApmFieldMapping,
+) -> (ApmFieldMapping, Vec<ApmColumnMatch>) {
+    let columns = requested.columns();
+    let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+    for (f, (_, expected)) in columns.iter().enumerate() {
+        for (h, header) in headers.iter().enumerate() {
+            let score = similarity(header, expected);
+            if score >= MATCH_THRESHOLD {
+                candidates.push((score, f, h));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+    let mut assigned: Vec<Option<(usize, f64)>> = vec![None; columns.len()];
+    let mut used = vec![false; headers.len()];
+    for (score, f, h) in candidates {
+        if assigned[f].is_none() && !used[h] {
+            assigned[f] = Some((h, score));
+            used[h] = true;
+        }
+    }
+
+    let mut proposed = requested.clone();
+    let matches = columns
+        .iter()
+        .zip(&assigned)
+        .map(|((field, expected), found)| {
+            if let Some((h, _)) = found {
+                proposed.set_column(field, headers[*h].clone());
+            }
+            ApmColumnMatch {
+                field: field.to_string(),
+                expected_column: expected.to_string(),
+                matched_column: found.map(|(h, _)| headers[h].clone()),
+                score: found.map_or(0.0, |(_, s)| (s * 100.0).round() / 100.0),
+            }
+        })
+        .collect();
+    (proposed, matches)
+}
+
+/// Preview the import of an APM file with the mapping proposed for its
+/// headers, starting from `requested` (the defaults unless overridden).
+pub async fn preview(
+    pool: &PgPool,
+    data: &[u8],
+    requested: &ApmFieldMapping,
+    format: &ApmFormat,
+    params: &ApmPreviewParams,
+) -> Result<ApmImportPreview, AppError> {
+    let sheet = read_apm_file(data, format)?;
+    let (mapping, matches) = propose_mapping(&sheet.headers, requested);
+
+    let field = |row: &std::collections::HashMap<String, String>, col: &str| {
+        row.get(col)
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let skipped_rows = sheet
+        .rows
+        .iter()
+        .filter(|row| field(row, &mapping.app_code_column).is_none())
+        .count();
+
+    let shown = &sheet.rows[..sheet.rows.len().min(params.limit())];
+    let codes: Vec<String> = shown
+        .iter()
+        .filter_map(|row| field(row, &mapping.app_code_column))
+        .collect();
+    let existing: HashSet<String> = sqlx::query_scalar::<_, String>(
+        "SELECT app_code FROM applications WHERE app_code = ANY($1)",
+    )
+    .bind(&codes)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    let rows = shown
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let app_code = field(row, &mapping.app_code_column);
+            let app_name = app_code.as_ref().map(|code| {
+                field(row, &mapping.app_name_column).unwrap_or_else(|| format!("[APM] {code}"))
+            });
+            let (effective_office_owner, effective_office_name) = resolve_effective_owner(
+                &field(row, &mapping.office_owner_column),
+                &field(row, &mapping.office_name_column),
+                &field(row, &mapping.struttura_reale_owner_column),
+                &field(row, &mapping.struttura_reale_name_column),
+            );
+            ApmPreviewRow {
+                row: i + 2,
+                existing: app_code.as_ref().is_some_and(|c| existing.contains(c)),
+                app_code,
+                app_name,
+                criticality: map_criticality(field(row, &mapping.criticality_column).as_deref()),
+                ssa_code: field(row, &mapping.ssa_code_column),
+                effective_office_owner,
+                effective_office_name,
+                values: sheet
+                    .headers
+                    .iter()
+                    .map(|h| row.get(h).cloned().unwrap_or_default())
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let mapped: HashSet<&str> = matches
+        .iter()
+        .filter_map(|m| m.matched_column.as_deref())
+        .collect();
+    let unmatched_headers = sheet
+        .headers
+        .iter()
+        .filter(|h| !mapped.contains(h.as_str()))
+        .cloned()
+        .collect();
+
+    Ok(ApmImportPreview {
+        total_rows: sheet.rows.len(),
+        headers: sheet.headers,
+        proposed_mapping: mapping,
+        matches,
+        unmatched_headers,
+        skipped_rows,
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn matched<'a>(matches: &'a [ApmColumnMatch], field: &str) -> Option<&'a str> {
+        matches
+            .iter()
+            .find(|m| m.field == field)
+            .and_then(|m| m.matched_column.as_deref())
+    }
+
+    #[test]
+    fn normalization_drops_case_accents_and_punctuation() {
+        assert_eq!(normalize("Integrità"), "INTEGRITA");
+        assert_eq!(normalize("INTEGRITA'"), "INTEGRITA");
+        assert_eq!(normalize(" Cod. acronimo "), "COD ACRONIMO");
+        assert_eq!(similarity("Disponibilità", "DISPONIBILITA"), 1.0);
+    }
+
+    #[test]
+    fn renamed_headers_are_matched() {
+        let (mapping, matches) = propose_mapping(
+            &headers(&[
+                "COD. ACRONIMO",
+                "Descrizione Acronimo",
+                "CODICE SSA",
+                "DESCRIZIONE SSA",
+                "EMAIL REFERENTE TECNICO",
+                "Integrità",
+                "RISERVATEZZA DATI",
+            ]),
+            &ApmFieldMapping::default(),
+        );
+        assert_eq!(mapping.app_code_column, "COD. ACRONIMO");
+        assert_eq!(mapping.app_name_column, "Descrizione Acronimo");
+        assert_eq!(mapping.ssa_name_column, "DESCRIZIONE SSA");
+        assert_eq!(
+            mapping.technical_ref_email_column,
+            "EMAIL REFERENTE TECNICO"
+        );
+        assert_eq!(mapping.integrity_column, "Integrità");
+        assert_eq!(mapping.confidentiality_column, "RISERVATEZZA DATI");
+        assert_eq!(matched(&matches, "ssa_code_column"), Some("CODICE SSA"));
+        assert_eq!(matched(&matches, "functional_ref_email_column"), None);
+    }
+
+    #[test]
+    fn a_header_serves_one_field() {
+        // "DESCRIZIONE SSA" resembles the app name column too, but is taken
+        // by its exact match
+        let (mapping, matches) =
+            propose_mapping(&headers(&["DESCRIZIONE SSA"]), &ApmFieldMapping::default());
+        assert_eq!(mapping.ssa_name_column, "DESCRIZIONE SSA");
+        assert_eq!(matched(&matches, "app_name_column"), None);
+        assert_eq!(mapping.app_name_column, "DESCRIZIONE ACRONIMO");
+    }
+
+    #[test]
+    fn unrelated_headers_are_not_matched() {
+        let (mapping, matches) =
+            propose_mapping(&headers(&["HOSTNAME", "NOTE"]), &ApmFieldMapping::default());
+        assert_eq!(mapping, ApmFieldMapping::default());
+        assert!(matches
+            .iter()
+            .all(|m| m.matched_column.is_none() && m.score == 0.0));
+    }
+
+    #[test]
+    fn preview_rows_are_clamped() {
+        assert_eq!(
+            ApmPreviewParams { rows: None }.limit(),
+            DEFAULT_PREVIEW_ROWS
+        );
+        assert_eq!(ApmPreviewParams { rows: Some(0) }.limit(), 1);
+        assert_eq!(
+            ApmPreviewParams { rows: Some(10_000) }.limit(),
+            MAX_PREVIEW_ROWS
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! App code pattern management.
+//!
+//! CRUD for the per-tool regex patterns the resolver uses to extract app
+//! codes from scanner fields, priority reordering, and a dry run of a
+//! pattern set against a sample metadata payload that reports what each
+//! pattern matched and which app code ingestion would resolve. Writes drop
+//! the cached patterns so ingestion picks them up at once.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::app_code_pattern::{
+    AppCodePattern, AppCodePatternFilters, CreateAppCodePattern, ReorderAppCodePatterns,
+    TestAppCodePatterns, UpdateAppCodePattern,
+};
+use crate::services::app_code_resolver::{self, PatternEntry, PatternOutcome};
+use crate::services::ingestion::extract_resolver_fields;
+use crate::services::{application, config_cache};
+
+/// Priority step between reordered patterns, leaving room to slot new
+/// patterns in between.
+const PRIORITY_STEP: i32 = 10;
+
+/// One pattern of a dry run and how it fared.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PatternTrial {
+    /// Stored pattern, or `None` for an ad-hoc one.
+    pub pattern_id: Option<Uuid>,
+    pub field_name: String,
+    pub regex_pattern: String,
+    pub priority: i32,
+    pub outcome: PatternOutcome,
+    /// The pattern whose app code the resolver would use.
+    pub selected: bool,
+}
+
+/// Outcome of running a pattern set against sample metadata.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PatternTestResult {
+    pub source_tool: String,
+    /// `app_code` given in the metadata itself; ingestion uses it before
+    /// any pattern.
+    pub explicit_app_code: Option<String>,
+    /// App code of the first matching pattern.
+    pub resolved_app_code: Option<String>,
+    /// Existing application with the resolved app code; ingestion creates a
+    /// stub application when there is none.
+    pub application_id: Option<Uuid>,
+    /// Patterns in the order the resolver tries them.
+    pub trials: Vec<PatternTrial>,
+}
+
+/// List patterns by source tool, highest priority first.
+pub async fn list(
+    pool: &PgPool,
+    filters: &AppCodePatternFilters,
+) -> Result<Vec<AppCodePattern>, AppError> {
+    let patterns = sqlx::query_as::<_, AppCodePattern>(
+        r#"
+        SELECT * FROM app_code_patterns
+        WHERE ($1::text IS NULL OR source_tool = $1)
+          AND ($2::boolean IS NULL OR is_active = $2)
+        ORDER BY source_tool, priority DESC, created_at
+        "#,
+    )
+    .bind(&filters.source_tool)
+    .bind(filters.is_active)
+    .fetch_all(pool)
+    .await?;
+    Ok(patterns)
+}
+
+/// Fetch a pattern by ID.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<AppCodePattern, AppError> {
+    sqlx::query_as::<_, AppCodePattern>("SELECT * FROM app_code_patterns WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("App code pattern {id} not found")))
+}
+
+/// Create an active pattern.
+pub async fn create(
+    pool: &PgPool,
+    input: &CreateAppCodePattern,
+) -> Result<AppCodePattern, AppError> {
+    app_code_resolver::validate_regex(&input.regex_pattern).map_err(AppError::Validation)?;
+
+    let pattern = sqlx::query_as::<_, AppCodePattern>(
+        r#"
+        INSERT INTO app_code_patterns (source_tool, field_name, regex_pattern, priority, description)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(&input.source_tool)
+    .bind(&input.field_name)
+    .bind(&input.regex_pattern)
+    .bind(input.priority.unwrap_or(0))
+    .bind(&input.description)
+    .fetch_one(pool)
+    .await?;
+
+    config_cache::global().invalidate_patterns();
+    Ok(pattern)
+}
+
+/// Update a pattern; omitted fields keep their values.
+pub async fn update(
+    pool: &PgPool,
+    id: Uuid,
+    input: &UpdateAppCodePattern,
+) -> Result<AppCodePattern, AppError> {
+    let existing = find_by_id(pool, id).await?;
+    if let Some(regex_pattern) = &input.regex_pattern {
+        app_code_resolver::validate_regex(regex_pattern).map_err(AppError::Validation)?;
+    }
+
+    let pattern = sqlx::query_as::<_, AppCodePattern>(
+        r#"
+        UPDATE app_code_patterns
+        SET field_name = $1, regex_pattern = $2, priority = $3, description = $4, is_active = $5
+        WHERE id = $6
+        RETURNING *
+        "#,
+    )
+    .bind(input.field_name.as_ref().unwrap_or(&existing.field_name))
+    .bind(
+        input
+            .regex_pattern
+            .as_ref()
+            .unwrap_or(&existing.regex_pattern),
+    )
+    .bind(input.priority.unwrap_or(existing.priority))
+    .bind(input.description.as_ref().or(existing.description.as_ref()))
+    .bind(input.is_active.unwrap_or(existing.is_active))
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+
+    config_cache::global().invalidate_patterns();
+    Ok(pattern)
+}
+
+/// Deactivate a pattern so the resolver skips it; the row is kept.
+pub async fn deactivate(pool: &PgPool, id: Uuid) -> Result<AppCodePattern, AppError> {
+    let pattern = sqlx::query_as::<_, AppCodePattern>(
+        "UPDATE app_code_patterns SET is_active = false WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("App code pattern {id} not found")))?;
+
+    config_cache::global().invalidate_patterns();
+    Ok(pattern)
+}
+
+/// Give a source tool's patterns descending priorities in the order given.
+/// The list must hold every pattern of the tool, active or not.
+pub async fn reorder(
+    pool: &PgPool,
+    input: &ReorderAppCodePatterns,
+) -> Result<Vec<AppCodePattern>, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let existing = sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM app_code_patterns WHERE source_tool = $1 FOR UPDATE",
+    )
+    .bind(&input.source_tool)
+    .fetch_all(&mut *tx)
+    .await?;
+    check_complete_order(&existing, &input.pattern_ids)?;
+
+    let priorities = reordered_priorities(input.pattern_ids.len());
+    sqlx::query(
+        r#"
+        UPDATE app_code_patterns p
+        SET priority = o.priority
+        FROM UNNEST($1::uuid[], $2::int[]) AS o(id, priority)
+        WHERE p.id = o.id
+        "#,
+    )
+    .bind(&input.pattern_ids)
+    .bind(&priorities)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    config_cache::global().invalidate_patterns();
+
+    list(
+        pool,
+        &AppCodePatternFilters {
+            source_tool: Some(input.source_tool.clone()),
+            is_active: None,
+        },
+    )
+    .await
+}
+
+/// Run a pattern set against sample metadata without touching any finding.
+pub async fn test_patterns(
+    pool: &PgPool,
+    input: &TestAppCodePatterns,
+) -> Result<PatternTestResult, AppError> {
+    let candidates: Vec<(Option<Uuid>, PatternEntry)> = match &input.patterns {
+        Some(patterns) => patterns
+            .iter()
+            .map(|p| {
+                let entry = PatternEntry {
+                    field_name: p.field_name.clone(),
+                    regex_pattern: p.regex_pattern.clone(),
+                    priority: p.priority,
+                };
+                (None, entry)
+            })
+            .collect(),
+        None => list(
+            pool,
+            &AppCodePatternFilters {
+                source_tool: Some(input.source_tool.clone()),
+                is_active: Some(true),
+            },
+        )
+        .await?
+        .into_iter()
+        .map(|p| {
+            let entry = PatternEntry {
+                field_name: p.field_name,
+                regex_pattern: p.regex_pattern,
+                priority: p.priority,
+            };
+            (Some(p.id), entry)
+        })
+        .collect(),
+    };
+
+    let fields = extract_resolver_fields(&input.metadata);
+    let trials = run_trials(candidates, &fields);
+    let resolved_app_code = trials
+        .iter()
+        .find(|t| t.selected)
+        .and_then(|t| match &t.outcome {
+            PatternOutcome::Matched { app_code } => Some(app_code.clone()),
+            _ => None,
+        });
+    let application_id = match &resolved_app_code {
+        Some(app_code) => application::find_by_app_code(pool, app_code)
+            .await?
+            .map(|app| app.id),
+        None => None,
+    };
+    let explicit_app_code = input
+        .metadata
+        .get("app_code")
+        .and_then(|v| v.as_str())
+        .filter(|code| !code.is_empty())
+        .map(str::to_string);
+
+    Ok(PatternTestResult {
+        source_tool: input.source_tool.clone(),
+        explicit_app_code,
+        resolved_app_code,
+        application_id,
+        trials,
+    })
+}
+
+// -- Private helpers ----------------------------------------------------------
+
+/// Evaluate every pattern in resolver order (highest priority first, stable
+/// for ties) and mark the first match as selected.
+fn run_trials(
+    mut candidates: Vec<(Option<Uuid>, PatternEntry)>,
+    fields: &[(String, String)],
+) -> Vec<PatternTrial> {
+    candidates.sort_by_key(|(_, p)| std::cmp::Reverse(p.priority));
+
+    let mut selected = false;
+    candidates
+        .into_iter()
+        .map(|(pattern_id, entry)| {
+            let outcome = app_code_resolver::evaluate(&entry, fields);
+            let is_selected = !selected && matches!(outcome, PatternOutcome::Matched { .. });
+            selected |= is_selected;
+            PatternTrial {
+                pattern_id,
+                field_name: entry.field_name,
+                regex_pattern: entry.regex_pattern,
+                priority: entry.priority,
+                outcome,
+                selected: is_selected,
+            }
+        })
+        .collect()
+}
+
+/// Check a new order names each of the tool's patterns exactly once.
+fn check_complete_order(existing: &[Uuid], order: &[Uuid]) -> Result<(), AppError> {
+    let mut seen = HashSet::new();
+    if let Some(dup) = order.iter().find(|id| !seen.insert(**id)) {
+        return Err(AppError::Validation(format!(
+            "Pattern {dup} is listed more than once"
+        )));
+    }
+    let existing: HashSet<&Uuid> = existing.iter().collect();
+    if let Some(unknown) = order.iter().find(|id| !existing.contains(id)) {
+        return Err(AppError::Validation(format!(
+            "Pattern {unknown} does not belong to this source tool"
+        )));
+    }
+    if order.len() != existing.len() {
+        return Err(AppError::Validation(format!(
+            "The order must list all {} patterns of the source tool",
+            existing.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Priorities for `count` patterns, the first highest.
+fn reordered_priorities(count: usize) -> Vec<i32> {
+    (1..=count as i32)
+        .rev()
+        .map(|n| n * PRIORITY_STEP)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(field_name: &str, regex_pattern: &str, priority: i32) -> PatternEntry {
+        PatternEntry {
+            field_name: field_name.to_string(),
+            regex_pattern: regex_pattern.to_string(),
+            priority,
+        }
+    }
+
+    #[test]
+    fn trials_follow_resolver_order_and_select_the_first_match() {
+        let fields = vec![(
+            "DNS Name".to_string(),
+            "sacronym.env.domain.com".to_string(),
+        )];
+        let trials = run_trials(
+            vec![
+                (None, entry("DNS Name", r"^(?P<app_code>[^.]+)\.", 5)),
+                (None, entry("path", r"(?P<app_code>\w+)", 20)),
+                (None, entry("DNS Name", r"^[st](?P<app_code>[^.]+)\.", 10)),
+            ],
+            &fields,
+        );
+
+        let priorities: Vec<i32> = trials.iter().map(|t| t.priority).collect();
+        assert_eq!(priorities, vec![20, 10, 5]);
+        assert_eq!(trials[0].outcome, PatternOutcome::FieldMissing);
+        assert!(trials[1].selected);
+        assert_eq!(
+            trials[1].outcome,
+            PatternOutcome::Matched {
+                app_code: "acronym".to_string()
+            }
+        );
+        // Later matches are reported but not selected
+        assert!(matches!(trials[2].outcome, PatternOutcome::Matched { .. }));
+        assert!(!trials[2].selected);
+    }
+
+    #[test]
+    fn order_must_list_each_pattern_once() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(check_complete_order(&[a, b], &[b, a]).is_ok());
+        assert!(check_complete_order(&[a, b], &[a]).is_err());
+        assert!(check_complete_order(&[a, b], &[a, a]).is_err());
+        assert!(check_complete_order(&[a], &[a, Uuid::new_v4()]).is_err());
+    }
+
+    #[test]
+    fn reordered_priorities_descend_in_steps() {
+        assert_eq!(reordered_priorities(3), vec![30, 20, 10]);
+        assert!(reordered_priorities(0).is_empty());
+    }
+}
@@ -4,6 +4,8 @@
 //! with a non-empty `app_code` named capture group wins.
 
 use regex::Regex;
+use serde::Serialize;
+use utoipa::ToSchema;
 
 /// A single app code extraction pattern (loaded from DB or test fixture).
 #[derive(Debug, Clone)]
@@ -13,36 +15,78 @@ pub struct PatternEntry {
     pub priority: i32,
 }
 
-/// Resolve an app code from field name/value pairs using the given patterns.
-///
-/// Patterns are tried in descending priority order. Returns the first
-/// non-empty `app_code` capture, or `None` if nothing matches.
-pub fn resolve(patterns: &[PatternEntry], fields: &[(String, String)]) -> Option<String> {
-    let mut sorted: Vec<&PatternEntry> = patterns.iter().collect();
-    sorted.sort_by(|a, b| b.priority.cmp(&a.priority));
+/// How a single pattern fared against a finding's fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PatternOutcome {
+    /// The pattern captured a non-empty `app_code`.
+    Matched { app_code: String },
+    /// The field is present but no value yields an `app_code`.
+    NoMatch,
+    /// The finding has no such field.
+    FieldMissing,
+    /// The regex does not compile; the pattern is skipped.
+    InvalidRegex { error: String },
+}
 
-    for pattern in sorted {
-        let re = match Regex::new(&pattern.regex_pattern) {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
+/// Check that a regex compiles and has the `app_code` capture group the
+/// resolver reads.
+pub fn validate_regex(regex_pattern: &str) -> Result<(), String> {
+    let re = Regex::new(regex_pattern).map_err(|e| format!("invalid regex: {e}"))?;
+    if !re.capture_names().any(|name| name == Some("app_code")) {
+        return Err("regex has no (?P<app_code>...) capture group".to_string());
+    }
+    Ok(())
+}
 
-        for (field_name, field_value) in fields {
-            if field_name != &pattern.field_name {
-                continue;
+/// Run one pattern against field name/value pairs.
+pub fn evaluate(pattern: &PatternEntry, fields: &[(String, String)]) -> PatternOutcome {
+    let re = match Regex::new(&pattern.regex_pattern) {
+        Ok(r) => r,
+        Err(e) => {
+            return PatternOutcome::InvalidRegex {
+                error: e.to_string(),
             }
-            if let Some(caps) = re.captures(field_value) {
-                if let Some(m) = caps.name("app_code") {
-                    let code = m.as_str().to_string();
-                    if !code.is_empty() {
-                        return Some(code);
-                    }
+        }
+    };
+
+    let mut field_present = false;
+    for (field_name, field_value) in fields {
+        if field_name != &pattern.field_name {
+            continue;
+        }
+        field_present = true;
+        if let Some(caps) = re.captures(field_value) {
+            if let Some(m) = caps.name("app_code") {
+                let code = m.as_str().to_string();
+                if !code.is_empty() {
+                    return PatternOutcome::Matched { app_code: code };
                 }
             }
         }
     }
 
-    None
+    if field_present {
+        PatternOutcome::NoMatch
+    } else {
+        PatternOutcome::FieldMissing
+    }
+}
+
+/// Resolve an app code from field name/value pairs using the given patterns.
+///
+/// Patterns are tried in descending priority order. Returns the first
+/// non-empty `app_code` capture, or `None` if nothing matches.
+pub fn resolve(patterns: &[PatternEntry], fields: &[(String, String)]) -> Option<String> {
+    let mut sorted: Vec<&PatternEntry> = patterns.iter().collect();
+    sorted.sort_by_key(|p| std::cmp::Reverse(p.priority));
+
+    sorted
+        .into_iter()
+        .find_map(|pattern| match evaluate(pattern, fields) {
+            PatternOutcome::Matched { app_code } => Some(app_code),
+            _ => None,
+        })
 }
 
 #[cfg(test)]
@@ -154,4 +198,40 @@ mod tests {
         let result = resolve(&patterns, &fields);
         assert_eq!(result, Some("appcode".to_string()));
     }
+
+    #[test]
+    fn evaluate_reports_why_a_pattern_did_not_match() {
+        let pattern = |regex: &str| PatternEntry {
+            field_name: "path".to_string(),
+            regex_pattern: regex.to_string(),
+            priority: 0,
+        };
+        let fields = vec![("path".to_string(), "repo/appcode/rest".to_string())];
+
+        assert_eq!(
+            evaluate(&pattern(r"^[^/]+/(?P<app_code>[^/]+)/"), &fields),
+            PatternOutcome::Matched {
+                app_code: "appcode".to_string()
+            }
+        );
+        assert_eq!(
+            evaluate(&pattern(r"^other/(?P<app_code>[^/]+)/"), &fields),
+            PatternOutcome::NoMatch
+        );
+        assert_eq!(
+            evaluate(&pattern(r"(?P<app_code>\w+)"), &[]),
+            PatternOutcome::FieldMissing
+        );
+        assert!(matches!(
+            evaluate(&pattern(r"(?P<app_code>[unclosed"), &fields),
+            PatternOutcome::InvalidRegex { .. }
+        ));
+    }
+
+    #[test]
+    fn validate_regex_requires_the_app_code_group() {
+        assert!(validate_regex(r"^(?P<app_code>[^.]+)\.").is_ok());
+        assert!(validate_regex(r"^([^.]+)\.").is_err());
+        assert!(validate_regex(r"(?P<app_code>[unclosed").is_err());
+    }
 }
@@ -0,0 +1,402 @@
+//! Per-application security posture: the single view an application owner
+//! needs — open findings, SLA status, scanner coverage, attack chains, risk
+//! trend, and recent scan activity.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::application::AssetCriticality;
+use crate::models::finding::{FindingCategory, FindingStatus};
+use crate::services::attack_chains::{self, AppAttackChainDetail, AttackChainFilters};
+use crate::services::dashboard::{SeverityCounts, SlaSummary};
+use crate::services::executive_report::{ReportPeriod, RiskTrendPoint};
+
+/// Window for the recent scan activity section.
+const RECENT_ACTIVITY_DAYS: i64 = 30;
+
+/// Number of attack chains listed individually.
+const TOP_CHAINS_LIMIT: usize = 5;
+
+/// Complete posture of one application.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AppPosture {
+    pub application_id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    pub criticality: Option<AssetCriticality>,
+    pub generated_at: DateTime<Utc>,
+    /// Open findings by normalized severity.
+    pub severity_counts: SeverityCounts,
+    /// All findings by lifecycle status.
+    pub status_counts: Vec<StatusCount>,
+    /// SLA status of open findings.
+    pub sla: SlaSummary,
+    pub tool_coverage: ToolCoverage,
+    pub attack_chains: AttackChainOverview,
+    pub risk_trend: Vec<RiskTrendPoint>,
+    pub recent_activity: Vec<ScanActivity>,
+}
+
+/// Finding count for one lifecycle status.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct StatusCount {
+    pub status: FindingStatus,
+    pub count: i64,
+}
+
+/// Finding counts reported by one scanner for one category.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct ToolFindingCount {
+    pub source_tool: String,
+    pub finding_category: FindingCategory,
+    pub total_findings: i64,
+    pub open_findings: i64,
+}
+
+/// Which testing categories have reported findings for the application.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ToolCoverage {
+    pub covered_categories: Vec<FindingCategory>,
+    pub missing_categories: Vec<FindingCategory>,
+    pub tools: Vec<ToolFindingCount>,
+}
+
+/// Condensed attack chain view for the posture page.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttackChainOverview {
+    pub chain_count: usize,
+    pub correlated_findings: usize,
+    pub uncorrelated_findings: usize,
+    /// Largest chains first.
+    pub top_chains: Vec<ChainOverview>,
+}
+
+/// One attack chain reduced to its size and reach.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChainOverview {
+    pub group_id: Uuid,
+    pub finding_count: usize,
+    pub tool_coverage: Vec<String>,
+    pub max_severity: String,
+}
+
+/// Scanner activity for the application within the recent window.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct ScanActivity {
+    pub source_tool: String,
+    /// Most recent time the scanner reported any finding for the app.
+    pub last_seen: DateTime<Utc>,
+    /// Findings first reported within the window.
+    pub new_findings: i64,
+    /// Findings re-reported within the window.
+    pub seen_findings: i64,
+}
+
+/// Application identity row.
+#[derive(Debug, sqlx::FromRow)]
+struct AppRow {
+    app_name: String,
+    app_code: String,
+    criticality: Option<AssetCriticality>,
+}
+
+/// Intermediate row for open severity and SLA aggregation.
+#[derive(Debug, sqlx::FromRow)]
+struct OpenCountsRow {
+    critical: i64,
+    high: i64,
+    medium: i64,
+    low: i64,
+    info: i64,
+    on_track: i64,
+    at_risk: i64,
+    breached: i64,
+}
+
+/// Gather the posture of one application, with the risk trend over `period`.
+pub async fn build(
+    pool: &PgPool,
+    app_id: Uuid,
+    period: ReportPeriod,
+) -> Result<AppPosture, AppError> {
+    let app = sqlx::query_as::<_, AppRow>(
+        "SELECT app_name, app_code, criticality FROM applications WHERE id = $1",
+    )
+    .bind(app_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Application {app_id} not found")))?;
+
+    let chain_filters = AttackChainFilters::default();
+    let (open_counts, status_counts, tools, chains, risk_trend, recent_activity) = tokio::try_join!(
+        fetch_open_counts(pool, app_id),
+        fetch_status_counts(pool, app_id),
+        fetch_tool_counts(pool, app_id),
+        attack_chains::get_by_app(pool, app_id, &chain_filters),
+        fetch_risk_trend(pool, app_id, period),
+        fetch_recent_activity(pool, app_id),
+    )?;
+
+    Ok(AppPosture {
+        application_id: app_id,
+        app_name: app.app_name,
+        app_code: app.app_code,
+        criticality: app.criticality,
+        generated_at: Utc::now(),
+        severity_counts: SeverityCounts {
+            critical: open_counts.critical,
+            high: open_counts.high,
+            medium: open_counts.medium,
+            low: open_counts.low,
+            info: open_counts.info,
+        },
+        status_counts,
+        sla: SlaSummary {
+            on_track: open_counts.on_track,
+            at_risk: open_counts.at_risk,
+            breached: open_counts.breached,
+        },
+        tool_coverage: tool_coverage(tools),
+        attack_chains: chain_overview(chains),
+        risk_trend,
+        recent_activity,
+    })
+}
+
+/// Count open findings by severity and SLA status.
+async fn fetch_open_counts(pool: &PgPool, app_id: Uuid) -> Result<OpenCountsRow, AppError> {
+    let row = sqlx::query_as::<_, OpenCountsRow>(
+        r#"
+        SELECT
+            COALESCE(SUM(CASE WHEN effective_severity = 'Critical' THEN 1 ELSE 0 END), 0) AS critical,
+            COALESCE(SUM(CASE WHEN effective_severity = 'High'     THEN 1 ELSE 0 END), 0) AS high,
+            COALESCE(SUM(CASE WHEN effective_severity = 'Medium'   THEN 1 ELSE 0 END), 0) AS medium,
+            COALESCE(SUM(CASE WHEN effective_severity = 'Low'      THEN 1 ELSE 0 END), 0) AS low,
+            COALESCE(SUM(CASE WHEN effective_severity = 'Info'     THEN 1 ELSE 0 END), 0) AS info,
+            COALESCE(SUM(CASE WHEN sla_status = 'On_Track' THEN 1 ELSE 0 END), 0) AS on_track,
+            COALESCE(SUM(CASE WHEN sla_status = 'At_Risk'  THEN 1 ELSE 0 END), 0) AS at_risk,
+            COALESCE(SUM(CASE WHEN sla_status = 'Breached' THEN 1 ELSE 0 END), 0) AS breached
+        FROM findings
+        WHERE application_id = $1
+          AND status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+        "#,
+    )
+    .bind(app_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(row)
+}
+
+/// Count all findings of the application by lifecycle status.
+async fn fetch_status_counts(pool: &PgPool, app_id: Uuid) -> Result<Vec<StatusCount>, AppError> {
+    let rows = sqlx::query_as::<_, StatusCount>(
+        r#"
+        SELECT status, COUNT(*) AS count
+        FROM findings
+        WHERE application_id = $1
+        GROUP BY status
+        ORDER BY status
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Count findings per scanner and category.
+async fn fetch_tool_counts(pool: &PgPool, app_id: Uuid) -> Result<Vec<ToolFindingCount>, AppError> {
+    let rows = sqlx::query_as::<_, ToolFindingCount>(
+        r#"
+        SELECT
+            source_tool,
+            finding_category,
+            COUNT(*) AS total_findings,
+            COALESCE(SUM(CASE WHEN status NOT IN ('Closed', 'Invalidated', 'False_Positive') THEN 1 ELSE 0 END), 0) AS open_findings
+        FROM findings
+        WHERE application_id = $1
+        GROUP BY source_tool, finding_category
+        ORDER BY finding_category, source_tool
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Reconstruct the application's open findings and risk across the period,
+/// using the same point-in-time rule as the executive report trend.
+pub(crate) async fn fetch_risk_trend(
+    pool: &PgPool,
+    app_id: Uuid,
+    period: ReportPeriod,
+) -> Result<Vec<RiskTrendPoint>, AppError> {
+    let end = Utc::now();
+    let start = end - period.duration();
+    let rows = sqlx::query_as::<_, RiskTrendPoint>(
+        r#"
+        SELECT
+            b.at,
+            COUNT(f.id) AS open_findings,
+            COALESCE(SUM(CASE WHEN f.effective_severity IN ('Critical', 'High') THEN 1 ELSE 0 END), 0) AS open_critical_high,
+            COALESCE(SUM(f.composite_risk_score::double precision), 0) AS total_risk_score
+        FROM generate_series($1::timestamptz, $2::timestamptz, $3::interval) AS b(at)
+        LEFT JOIN findings f
+          ON f.application_id = $4
+         AND f.first_seen <= b.at
+         AND (f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+              OR f.status_changed_at > b.at)
+        GROUP BY b.at
+        ORDER BY b.at
+        "#,
+    )
+    .bind(start)
+    .bind(end)
+    .bind(period.trend_interval())
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Summarize scanner activity for the application over the recent window.
+async fn fetch_recent_activity(pool: &PgPool, app_id: Uuid) -> Result<Vec<ScanActivity>, AppError> {
+    let since = Utc::now() - Duration::days(RECENT_ACTIVITY_DAYS);
+    let rows = sqlx::query_as::<_, ScanActivity>(
+        r#"
+        SELECT
+            source_tool,
+            MAX(last_seen) AS last_seen,
+            COALESCE(SUM(CASE WHEN first_seen >= $2 THEN 1 ELSE 0 END), 0) AS new_findings,
+            COUNT(*) AS seen_findings
+        FROM findings
+        WHERE application_id = $1
+          AND last_seen >= $2
+        GROUP BY source_tool
+        ORDER BY last_seen DESC
+        "#,
+    )
+    .bind(app_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Derive covered and missing testing categories from per-tool counts.
+fn tool_coverage(tools: Vec<ToolFindingCount>) -> ToolCoverage {
+    let (covered_categories, missing_categories) = [
+        FindingCategory::Sast,
+        FindingCategory::Sca,
+        FindingCategory::Dast,
+    ]
+    .into_iter()
+    .partition(|category| tools.iter().any(|t| t.finding_category == *category));
+    ToolCoverage {
+        covered_categories,
+        missing_categories,
+        tools,
+    }
+}
+
+/// Reduce attack chain detail to counts and the largest chains.
+fn chain_overview(detail: AppAttackChainDetail) -> AttackChainOverview {
+    let mut top_chains: Vec<ChainOverview> = detail
+        .chains
+        .into_iter()
+        .map(|chain| ChainOverview {
+            group_id: chain.group_id,
+            finding_count: chain.findings.len(),
+            tool_coverage: chain.tool_coverage,
+            max_severity: chain.max_severity,
+        })
+        .collect();
+    top_chains.sort_by_key(|c| std::cmp::Reverse(c.finding_count));
+
+    let chain_count = top_chains.len();
+    let correlated_findings = top_chains.iter().map(|c| c.finding_count).sum();
+    top_chains.truncate(TOP_CHAINS_LIMIT);
+    AttackChainOverview {
+        chain_count,
+        correlated_findings,
+        uncorrelated_findings: detail.uncorrelated_findings.len(),
+        top_chains,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::attack_chains::AttackChain;
+
+    fn tool(source_tool: &str, category: FindingCategory) -> ToolFindingCount {
+        ToolFindingCount {
+            source_tool: source_tool.to_string(),
+            finding_category: category,
+            total_findings: 3,
+            open_findings: 1,
+        }
+    }
+
+    fn chain(size: usize) -> AttackChain {
+        AttackChain {
+            group_id: Uuid::new_v4(),
+            findings: (0..size)
+                .map(|_| attack_chains::ChainFinding {
+                    id: Uuid::new_v4(),
+                    title: "f".to_string(),
+                    source_tool: "SonarQube".to_string(),
+                    finding_category: "SAST".to_string(),
+                    normalized_severity: "High".to_string(),
+                    status: "Confirmed".to_string(),
+                    attack_technique_ids: vec![],
+                })
+                .collect(),
+            relationships: vec![],
+            tool_coverage: vec!["SonarQube".to_string()],
+            max_severity: "High".to_string(),
+            relationship_count: 0,
+        }
+    }
+
+    #[test]
+    fn coverage_reports_missing_categories() {
+        let coverage = tool_coverage(vec![
+            tool("SonarQube", FindingCategory::Sast),
+            tool("JFrog Xray", FindingCategory::Sca),
+        ]);
+        assert_eq!(
+            coverage.covered_categories,
+            vec![FindingCategory::Sast, FindingCategory::Sca]
+        );
+        assert_eq!(coverage.missing_categories, vec![FindingCategory::Dast]);
+    }
+
+    #[test]
+    fn coverage_with_no_tools_misses_everything() {
+        let coverage = tool_coverage(vec![]);
+        assert!(coverage.covered_categories.is_empty());
+        assert_eq!(coverage.missing_categories.len(), 3);
+    }
+
+    #[test]
+    fn chain_overview_counts_all_but_lists_largest() {
+        let detail = AppAttackChainDetail {
+            application_id: Uuid::new_v4(),
+            app_name: "app".to_string(),
+            app_code: "APP".to_string(),
+            chains: (2..=8).map(chain).collect(),
+            uncorrelated_findings: vec![],
+        };
+        let overview = chain_overview(detail);
+        assert_eq!(overview.chain_count, 7);
+        assert_eq!(overview.correlated_findings, (2..=8).sum::<usize>());
+        assert_eq!(overview.top_chains.len(), TOP_CHAINS_LIMIT);
+        assert_eq!(overview.top_chains[0].finding_count, 8);
+    }
+}
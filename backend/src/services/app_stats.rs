@@ -0,0 +1,270 @@
+//! Application detail statistics: the counts the application page shows,
+//! gathered in one call instead of a filtered findings query per widget.
+//!
+//! Open findings are those not Closed, Invalidated or False_Positive and not
+//! archived; severities are effective severities, so analyst overrides count.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::FindingCategory;
+use crate::services::app_posture::{self, StatusCount};
+use crate::services::dashboard::SeverityCounts;
+use crate::services::executive_report::{ReportPeriod, RiskTrendPoint};
+
+/// Statistics of one application's findings.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AppStats {
+    pub application_id: Uuid,
+    pub app_code: String,
+    pub generated_at: DateTime<Utc>,
+    pub open_findings: i64,
+    /// Open findings by effective severity.
+    pub by_severity: SeverityCounts,
+    /// Open findings by lifecycle status.
+    pub by_status: Vec<StatusCount>,
+    /// Open findings by category.
+    pub by_category: Vec<CategoryCount>,
+    pub sla: SlaPosture,
+    /// The latest scan of each tool that reported findings for the application.
+    pub last_ingestions: Vec<ToolIngestion>,
+    pub risk_trend: Vec<RiskTrendPoint>,
+}
+
+/// Open finding count for one category.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct CategoryCount {
+    pub finding_category: FindingCategory,
+    pub count: i64,
+}
+
+/// SLA status of the open findings.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SlaPosture {
+    pub on_track: i64,
+    pub at_risk: i64,
+    pub breached: i64,
+    /// Open findings without an SLA due date.
+    pub without_sla: i64,
+    /// Earliest due date of an open finding not yet breached.
+    pub next_due_at: Option<DateTime<Utc>>,
+    /// Share of open findings with an SLA that are not breached, 0–100;
+    /// `None` when no open finding has an SLA.
+    pub compliance_pct: Option<f64>,
+}
+
+/// The latest ingestion of one tool for the application.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct ToolIngestion {
+    pub source_tool: String,
+    pub ingestion_log_id: Uuid,
+    /// When the scan ran, as reported by the tool or else the upload time.
+    pub scanned_at: DateTime<Utc>,
+    pub ingested_at: DateTime<Utc>,
+    /// Findings of the application the scan reported.
+    pub findings_reported: i64,
+}
+
+/// Open finding counts by severity and SLA status.
+#[derive(Debug, Default, sqlx::FromRow)]
+struct OpenCountsRow {
+    total: i64,
+    critical: i64,
+    high: i64,
+    medium: i64,
+    low: i64,
+    info: i64,
+    on_track: i64,
+    at_risk: i64,
+    breached: i64,
+    without_sla: i64,
+    next_due_at: Option<DateTime<Utc>>,
+}
+
+impl OpenCountsRow {
+    fn severity_counts(&self) -> SeverityCounts {
+        SeverityCounts {
+            critical: self.critical,
+            high: self.high,
+            medium: self.medium,
+            low: self.low,
+            info: self.info,
+        }
+    }
+
+    fn sla_posture(&self) -> SlaPosture {
+        let with_sla = self.on_track + self.at_risk + self.breached;
+        let compliance_pct = (with_sla > 0).then(|| {
+            let pct = (with_sla - self.breached) as f64 * 100.0 / with_sla as f64;
+            (pct * 10.0).round() / 10.0
+        });
+        SlaPosture {
+            on_track: self.on_track,
+            at_risk: self.at_risk,
+            breached: self.breached,
+            without_sla: self.without_sla,
+            next_due_at: self.next_due_at,
+            compliance_pct,
+        }
+    }
+}
+
+/// Gather the statistics of one application, with the risk trend over `period`.
+pub async fn build(
+    pool: &PgPool,
+    app_id: Uuid,
+    period: ReportPeriod,
+) -> Result<AppStats, AppError> {
+    let app_code =
+        sqlx::query_scalar::<_, String>("SELECT app_code FROM applications WHERE id = $1")
+            .bind(app_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Application {app_id} not found")))?;
+
+    let (counts, by_status, by_category, last_ingestions, risk_trend) = tokio::try_join!(
+        fetch_open_counts(pool, app_id),
+        fetch_status_counts(pool, app_id),
+        fetch_category_counts(pool, app_id),
+        fetch_last_ingestions(pool, app_id),
+        app_posture::fetch_risk_trend(pool, app_id, period),
+    )?;
+
+    Ok(AppStats {
+        application_id: app_id,
+        app_code,
+        generated_at: Utc::now(),
+        open_findings: counts.total,
+        by_severity: counts.severity_counts(),
+        by_status,
+        by_category,
+        sla: counts.sla_posture(),
+        last_ingestions,
+        risk_trend,
+    })
+}
+
+async fn fetch_open_counts(pool: &PgPool, app_id: Uuid) -> Result<OpenCountsRow, AppError> {
+    let row = sqlx::query_as::<_, OpenCountsRow>(
+        r#"
+        SELECT
+            COUNT(*) AS total,
+            COUNT(*) FILTER (WHERE effective_severity = 'Critical') AS critical,
+            COUNT(*) FILTER (WHERE effective_severity = 'High') AS high,
+            COUNT(*) FILTER (WHERE effective_severity = 'Medium') AS medium,
+            COUNT(*) FILTER (WHERE effective_severity = 'Low') AS low,
+            COUNT(*) FILTER (WHERE effective_severity = 'Info') AS info,
+            COUNT(*) FILTER (WHERE sla_status = 'On_Track') AS on_track,
+            COUNT(*) FILTER (WHERE sla_status = 'At_Risk') AS at_risk,
+            COUNT(*) FILTER (WHERE sla_status = 'Breached') AS breached,
+            COUNT(*) FILTER (WHERE sla_due_date IS NULL) AS without_sla,
+            MIN(sla_due_date) FILTER (WHERE sla_status IS DISTINCT FROM 'Breached') AS next_due_at
+        FROM findings
+        WHERE application_id = $1
+          AND status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND archived_at IS NULL
+        "#,
+    )
+    .bind(app_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(row)
+}
+
+async fn fetch_status_counts(pool: &PgPool, app_id: Uuid) -> Result<Vec<StatusCount>, AppError> {
+    let rows = sqlx::query_as::<_, StatusCount>(
+        r#"
+        SELECT status, COUNT(*) AS count
+        FROM findings
+        WHERE application_id = $1
+          AND status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND archived_at IS NULL
+        GROUP BY status
+        ORDER BY status
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+async fn fetch_category_counts(
+    pool: &PgPool,
+    app_id: Uuid,
+) -> Result<Vec<CategoryCount>, AppError> {
+    let rows = sqlx::query_as::<_, CategoryCount>(
+        r#"
+        SELECT finding_category, COUNT(*) AS count
+        FROM findings
+        WHERE application_id = $1
+          AND status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND archived_at IS NULL
+        GROUP BY finding_category
+        ORDER BY finding_category
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// The newest scan per tool, from the occurrences recorded for the application.
+async fn fetch_last_ingestions(
+    pool: &PgPool,
+    app_id: Uuid,
+) -> Result<Vec<ToolIngestion>, AppError> {
+    let rows = sqlx::query_as::<_, ToolIngestion>(
+        r#"
+        SELECT DISTINCT ON (s.source_tool)
+            s.source_tool, s.ingestion_log_id, s.scanned_at, l.started_at AS ingested_at,
+            s.findings_reported
+        FROM (
+            SELECT source_tool, ingestion_log_id, MAX(scan_date) AS scanned_at,
+                   COUNT(DISTINCT finding_id) AS findings_reported
+            FROM finding_occurrences
+            WHERE application_id = $1
+            GROUP BY source_tool, ingestion_log_id
+        ) s
+        JOIN ingestion_logs l ON l.id = s.ingestion_log_id
+        ORDER BY s.source_tool, s.scanned_at DESC, l.started_at DESC
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sla_compliance_counts_only_findings_with_an_sla() {
+        let counts = OpenCountsRow {
+            on_track: 5,
+            at_risk: 2,
+            breached: 1,
+            without_sla: 10,
+            ..Default::default()
+        };
+        let sla = counts.sla_posture();
+        assert_eq!(sla.compliance_pct, Some(87.5));
+        assert_eq!(sla.without_sla, 10);
+    }
+
+    #[test]
+    fn sla_compliance_is_absent_without_slas() {
+        let counts = OpenCountsRow {
+            without_sla: 3,
+            ..Default::default()
+        };
+        assert_eq!(counts.sla_posture().compliance_pct, None);
+    }
+}
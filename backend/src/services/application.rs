@@ -4,6 +4,7 @@ use calamine::{open_workbook_from_rs, Reader, Xlsx};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::io::Cursor;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::errors::AppError;
@@ -11,10 +12,12 @@ use crate::models::application::{
     AppStatus, Application, ApplicationSummary, AssetCriticality, CreateApplication,
     UpdateApplication,
 };
-use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::pagination::{PagedResult, Pagination, SortDirection};
+use crate::services::{config_cache, license_policy};
 
 /// Filters for listing applications.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ApplicationFilters {
     pub status: Option<AppStatus>,
     pub criticality: Option<AssetCriticality>,
@@ -25,10 +28,71 @@ pub struct ApplicationFilters {
     pub has_pci_data: Option<bool>,
     pub is_psd2_relevant: Option<bool>,
     pub search: Option<String>,
+    /// Restrict to applications this user is assigned to. Set by the routes
+    /// from the caller's ownership scope, never from the query string.
+    #[serde(skip)]
+    #[param(ignore)]
+    pub visible_to: Option<Uuid>,
+}
+
+/// Sortable columns for application lists. Enum columns sort in declaration
+/// order, so `asc` on `criticality` lists Very_High first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplicationSortField {
+    #[default]
+    AppName,
+    AppCode,
+    Criticality,
+    Tier,
+    BusinessUnit,
+    Status,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl ApplicationSortField {
+    /// Column name; built only from this fixed set of variants, never from user input.
+    fn column(self) -> &'static str {
+        match self {
+            Self::AppName => "app_name",
+            Self::AppCode => "app_code",
+            Self::Criticality => "criticality",
+            Self::Tier => "tier",
+            Self::BusinessUnit => "business_unit",
+            Self::Status => "status",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+/// Sort parameters for application lists.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ApplicationSort {
+    /// Column to sort by (defaults to application name).
+    pub sort_by: Option<ApplicationSortField>,
+    /// Sort direction (defaults to descending for timestamps, ascending otherwise).
+    pub sort_dir: Option<SortDirection>,
+}
+
+impl ApplicationSort {
+    /// ORDER BY clause body. Ties fall back to the application ID so pages are stable.
+    fn order_by(&self) -> String {
+        let field = self.sort_by.unwrap_or_default();
+        let dir = self.sort_dir.unwrap_or(match field {
+            ApplicationSortField::CreatedAt | ApplicationSortField::UpdatedAt => {
+                SortDirection::Desc
+            }
+            _ => SortDirection::Asc,
+        });
+        format!("{} {} NULLS LAST, id", field.column(), dir.as_sql())
+    }
 }
 
 /// Result of a bulk import operation.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ImportResult {
     pub total: usize,
     pub created: usize,
@@ -37,7 +101,7 @@ pub struct ImportResult {
 }
 
 /// Individual import error.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ImportError {
     pub row: usize,
     pub app_code: Option<String>,
@@ -45,7 +109,7 @@ pub struct ImportError {
 }
 
 /// APM CSV import result with additional detail.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApmImportResult {
     pub total: usize,
     pub created: usize,
@@ -54,8 +118,21 @@ pub struct ApmImportResult {
     pub errors: Vec<ImportError>,
 }
 
+/// Outcome of merging a duplicate application.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MergeResult {
+    /// The canonical application after the merge.
+    pub application: Application,
+    pub findings_moved: u64,
+    pub assets_moved: u64,
+    pub dns_mappings_moved: u64,
+    pub sboms_moved: u64,
+    pub releases_moved: u64,
+    pub owners_moved: u64,
+}
+
 /// Configurable CSV-to-field mapping for corporate APM imports.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct ApmFieldMapping {
     #[serde(default = "default_app_code_column")]
     pub app_code_column: String,
@@ -151,6 +228,50 @@ impl Default for ApmFieldMapping {
     }
 }
 
+impl ApmFieldMapping {
+    /// Each mapped field with the column it is read from.
+    pub fn columns(&self) -> [(&'static str, &str); 14] {
+        [
+            ("app_code_column", &self.app_code_column),
+            ("app_name_column", &self.app_name_column),
+            ("ssa_code_column", &self.ssa_code_column),
+            ("ssa_name_column", &self.ssa_name_column),
+            ("criticality_column", &self.criticality_column),
+            ("functional_ref_email_column", &self.functional_ref_email_column),
+            ("technical_ref_email_column", &self.technical_ref_email_column),
+            ("office_owner_column", &self.office_owner_column),
+            ("office_name_column", &self.office_name_column),
+            ("struttura_reale_owner_column", &self.struttura_reale_owner_column),
+            ("struttura_reale_name_column", &self.struttura_reale_name_column),
+            ("confidentiality_column", &self.confidentiality_column),
+            ("integrity_column", &self.integrity_column),
+            ("availability_column", &self.availability_column),
+        ]
+    }
+
+    /// Read `field` (as named by [`Self::columns`]) from `column`.
+    pub fn set_column(&mut self, field: &str, column: String) {
+        let slot = match field {
+            "app_code_column" => &mut self.app_code_column,
+            "app_name_column" => &mut self.app_name_column,
+            "ssa_code_column" => &mut self.ssa_code_column,
+            "ssa_name_column" => &mut self.ssa_name_column,
+            "criticality_column" => &mut self.criticality_column,
+            "functional_ref_email_column" => &mut self.functional_ref_email_column,
+            "technical_ref_email_column" => &mut self.technical_ref_email_column,
+            "office_owner_column" => &mut self.office_owner_column,
+            "office_name_column" => &mut self.office_name_column,
+            "struttura_reale_owner_column" => &mut self.struttura_reale_owner_column,
+            "struttura_reale_name_column" => &mut self.struttura_reale_name_column,
+            "confidentiality_column" => &mut self.confidentiality_column,
+            "integrity_column" => &mut self.integrity_column,
+            "availability_column" => &mut self.availability_column,
+            _ => return,
+        };
+        *slot = column;
+    }
+}
+
 /// Create a new application.
 pub async fn create(pool: &PgPool, input: &CreateApplication) -> Result<Application, AppError> {
     let tech_stack = input
@@ -250,10 +371,11 @@ pub async fn find_or_create_stub(
     Ok(app)
 }
 
-/// List applications with filters and pagination.
+/// List applications with filters, sorting, and pagination.
 pub async fn list(
     pool: &PgPool,
     filters: &ApplicationFilters,
+    sort: &ApplicationSort,
     pagination: &Pagination,
 ) -> Result<PagedResult<ApplicationSummary>, AppError> {
     let mut conditions: Vec<String> = Vec::new();
@@ -298,6 +420,12 @@ pub async fn list(
             "(app_name ILIKE ${param_index} OR app_code ILIKE ${param_index})"
         ));
     }
+    if filters.visible_to.is_some() {
+        param_index += 1;
+        conditions.push(format!(
+            "id IN (SELECT application_id FROM application_owners WHERE user_id = ${param_index})"
+        ));
+    }
 
     let where_clause = if conditions.is_empty() {
         String::new()
@@ -310,7 +438,8 @@ pub async fn list(
     // Data query
     let data_sql = format!(
         "SELECT id, app_name, app_code, criticality, tier, business_unit, status, is_verified \
-         FROM applications {where_clause} ORDER BY app_name ASC LIMIT {} OFFSET {}",
+         FROM applications {where_clause} ORDER BY {} LIMIT {} OFFSET {}",
+        sort.order_by(),
         pagination.limit(),
         pagination.offset()
     );
@@ -358,6 +487,9 @@ pub async fn list(
         count_query = count_query.bind(pattern.clone());
         data_query = data_query.bind(pattern);
     }
+    if let Some(ref user_id) = filters.visible_to {
+        bind_both!(user_id);
+    }
 
     let total = count_query.fetch_one(pool).await?;
     let items = data_query.fetch_all(pool).await?;
@@ -414,6 +546,7 @@ pub async fn update(
             data_classification = COALESCE($12, data_classification),
             repository_urls = COALESCE($13, repository_urls),
             status = COALESCE($14, status),
+            is_distributed = COALESCE($15, is_distributed),
             updated_at = NOW()
         WHERE id = $1
         RETURNING *
@@ -433,12 +566,221 @@ pub async fn update(
     .bind(&input.data_classification)
     .bind(input.repository_urls.as_ref().map(|v| serde_json::to_value(v).unwrap_or_default()))
     .bind(&input.status)
+    .bind(input.is_distributed)
     .fetch_one(pool)
     .await?;
 
+    // Copyleft license policies only apply to distributed applications
+    if app.is_distributed != existing.is_distributed {
+        license_policy::evaluate(pool, None).await?;
+    }
+
     Ok(app)
 }
 
+/// Merge a duplicate application into the canonical one and delete the duplicate.
+///
+/// Findings, assets, DNS mappings, SBOMs, releases, and ownership assignments
+/// move to the canonical application; a release whose version the canonical
+/// application already has is dropped and its finding links re-pointed.
+/// Repository URLs and scanner project IDs are combined, keeping the canonical
+/// values on conflict. Each moved finding gets a history entry and the merge
+/// is recorded in the audit log.
+pub async fn merge(
+    pool: &PgPool,
+    keep_id: Uuid,
+    duplicate_id: Uuid,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<MergeResult, AppError> {
+    if keep_id == duplicate_id {
+        return Err(AppError::Validation(
+            "Cannot merge an application into itself".to_string(),
+        ));
+    }
+    let mut tx = pool.begin().await?;
+
+    // Lock both rows in a fixed order so concurrent merges cannot deadlock
+    let apps = sqlx::query_as::<_, Application>(
+        "SELECT * FROM applications WHERE id = ANY($1) ORDER BY id FOR UPDATE",
+    )
+    .bind([keep_id, duplicate_id].as_slice())
+    .fetch_all(&mut *tx)
+    .await?;
+    if !apps.iter().any(|a| a.id == keep_id) {
+        return Err(AppError::NotFound(format!(
+            "Application {keep_id} not found"
+        )));
+    }
+    let duplicate = apps
+        .into_iter()
+        .find(|a| a.id == duplicate_id)
+        .ok_or_else(|| AppError::NotFound(format!("Application {duplicate_id} not found")))?;
+
+    sqlx::query(
+        r#"
+        WITH pairs AS (
+            SELECT d.id AS duplicate_release, k.id AS keep_release
+            FROM releases d
+            JOIN releases k ON k.application_id = $1 AND k.version = d.version
+            WHERE d.application_id = $2
+        )
+        UPDATE findings f SET
+            introduced_in_release_id = COALESCE(
+                (SELECT keep_release FROM pairs WHERE duplicate_release = f.introduced_in_release_id),
+                f.introduced_in_release_id
+            ),
+            fixed_in_release_id = COALESCE(
+                (SELECT keep_release FROM pairs WHERE duplicate_release = f.fixed_in_release_id),
+                f.fixed_in_release_id
+            )
+        WHERE f.introduced_in_release_id IN (SELECT duplicate_release FROM pairs)
+           OR f.fixed_in_release_id IN (SELECT duplicate_release FROM pairs)
+        "#,
+    )
+    .bind(keep_id)
+    .bind(duplicate_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        r#"
+        DELETE FROM releases d
+        WHERE d.application_id = $2
+          AND EXISTS (SELECT 1 FROM releases k WHERE k.application_id = $1 AND k.version = d.version)
+        "#,
+    )
+    .bind(keep_id)
+    .bind(duplicate_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let moved_findings = sqlx::query_scalar::<_, Uuid>(
+        "UPDATE findings SET application_id = $1, updated_at = NOW() WHERE application_id = $2 RETURNING id",
+    )
+    .bind(keep_id)
+    .bind(duplicate_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name, justification)
+        SELECT id, 'application_merged', 'application_id', $2, $3, $4, $5, NULL
+        FROM UNNEST($1::uuid[]) AS id
+        "#,
+    )
+    .bind(&moved_findings)
+    .bind(duplicate_id.to_string())
+    .bind(keep_id.to_string())
+    .bind(actor_id)
+    .bind(actor_name)
+    .execute(&mut *tx)
+    .await?;
+
+    let mut moved = [0u64; 4];
+    for (count, table) in moved
+        .iter_mut()
+        .zip(["assets", "dns_mappings", "sboms", "releases"])
+    {
+        *count = sqlx::query(&format!(
+            "UPDATE {table} SET application_id = $1 WHERE application_id = $2"
+        ))
+        .bind(keep_id)
+        .bind(duplicate_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+    let [assets_moved, dns_mappings_moved, sboms_moved, releases_moved] = moved;
+
+    // Users assigned to both applications keep their higher role
+    let owners_moved = sqlx::query(
+        r#"
+        INSERT INTO application_owners (application_id, user_id, role, assigned_by)
+        SELECT $1, user_id, role, assigned_by FROM application_owners WHERE application_id = $2
+        ON CONFLICT (application_id, user_id)
+        DO UPDATE SET role = GREATEST(application_owners.role, EXCLUDED.role)
+        "#,
+    )
+    .bind(keep_id)
+    .bind(duplicate_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query(
+        r#"
+        UPDATE applications k SET
+            repository_urls = (
+                SELECT COALESCE(jsonb_agg(DISTINCT url), '[]'::jsonb)
+                FROM jsonb_array_elements(
+                    COALESCE(k.repository_urls, '[]'::jsonb) || COALESCE(d.repository_urls, '[]'::jsonb)
+                ) AS url
+            ),
+            scanner_project_ids =
+                COALESCE(d.scanner_project_ids, '{}'::jsonb) || COALESCE(k.scanner_project_ids, '{}'::jsonb),
+            updated_at = NOW()
+        FROM applications d
+        WHERE k.id = $1 AND d.id = $2
+        "#,
+    )
+    .bind(keep_id)
+    .bind(duplicate_id)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM applications WHERE id = $1")
+        .bind(duplicate_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('application', $1, 'merge', $2, $3, $4)
+        "#,
+    )
+    .bind(keep_id)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({
+        "duplicate_id": duplicate_id,
+        "duplicate_app_code": duplicate.app_code,
+        "duplicate_app_name": duplicate.app_name,
+        "findings_moved": moved_findings.len(),
+        "assets_moved": assets_moved,
+        "dns_mappings_moved": dns_mappings_moved,
+        "sboms_moved": sboms_moved,
+        "releases_moved": releases_moved,
+        "owners_moved": owners_moved,
+    }))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let cache = config_cache::global();
+    cache.invalidate_assets().await;
+    cache.invalidate_dns_mappings().await;
+
+    tracing::info!(
+        keep_id = %keep_id,
+        duplicate_id = %duplicate_id,
+        findings_moved = moved_findings.len(),
+        "Merged duplicate application"
+    );
+
+    Ok(MergeResult {
+        application: find_by_id(pool, keep_id).await?,
+        findings_moved: moved_findings.len() as u64,
+        assets_moved,
+        dns_mappings_moved,
+        sboms_moved,
+        releases_moved,
+        owners_moved,
+    })
+}
+
 /// Bulk import applications from a JSON array.
 pub async fn import_bulk(
     pool: &PgPool,
@@ -465,6 +807,7 @@ pub async fn import_bulk(
                     data_classification: input.data_classification.clone(),
                     repository_urls: input.repository_urls.clone(),
                     status: None,
+                    is_distributed: None,
                 };
                 match self::update(pool, existing.id, &update).await {
                     Ok(_) => updated += 1,
@@ -527,10 +870,7 @@ pub async fn import_apm(
     format: &ApmFormat,
 ) -> Result<ApmImportResult, AppError> {
     // Extract rows as Vec<HashMap<header, value>> from either format
-    let rows = match format {
-        ApmFormat::Csv => parse_csv_rows(data)?,
-        ApmFormat::Xlsx => parse_xlsx_rows(data)?,
-    };
+    let rows = read_apm_file(data, format)?.rows;
 
     let mut created = 0usize;
     let mut updated = 0usize;
@@ -658,10 +998,22 @@ pub async fn import_apm(
     })
 }
 
+/// Headers, in file order, and rows of an APM file.
+pub(crate) struct ApmSheet {
+    pub headers: Vec<String>,
+    pub rows: Vec<std::collections::HashMap<String, String>>,
+}
+
+/// Parse an APM file in either format.
+pub(crate) fn read_apm_file(data: &[u8], format: &ApmFormat) -> Result<ApmSheet, AppError> {
+    match format {
+        ApmFormat::Csv => parse_csv_rows(data),
+        ApmFormat::Xlsx => parse_xlsx_rows(data),
+    }
+}
+
 /// Parse CSV data into a list of header→value maps.
-fn parse_csv_rows(
-    data: &[u8],
-) -> Result<Vec<std::collections::HashMap<String, String>>, AppError> {
+fn parse_csv_rows(data: &[u8]) -> Result<ApmSheet, AppError> {
     let mut reader = csv::ReaderBuilder::new()
         .has_headers(true)
         .flexible(true)
@@ -686,13 +1038,11 @@ fn parse_csv_rows(
         }
         rows.push(map);
     }
-    Ok(rows)
+    Ok(ApmSheet { headers, rows })
 }
 
 /// Parse XLSX data into a list of header→value maps.
-fn parse_xlsx_rows(
-    data: &[u8],
-) -> Result<Vec<std::collections::HashMap<String, String>>, AppError> {
+fn parse_xlsx_rows(data: &[u8]) -> Result<ApmSheet, AppError> {
     let cursor = Cursor::new(data);
     let mut workbook: Xlsx<_> = open_workbook_from_rs(cursor)
         .map_err(|e| AppError::Validation(format!("Invalid XLSX file: {e}")))?;
@@ -733,13 +1083,13 @@ fn parse_xlsx_rows(
         rows.push(map);
     }
 
-    Ok(rows)
+    Ok(ApmSheet { headers, rows })
 }
 
 /// Map criticality string from APM CSV to the AssetCriticality enum.
 ///
 /// Falls back to Medium when the value is empty or unrecognized.
-fn map_criticality(value: Option<&str>) -> AssetCriticality {
+pub(crate) fn map_criticality(value: Option<&str>) -> AssetCriticality {
     match value.map(|v| v.trim().to_lowercase()).as_deref() {
         Some("very high") | Some("very_high") | Some("veryhigh") => AssetCriticality::VeryHigh,
         Some("high") => AssetCriticality::High,
@@ -757,7 +1107,7 @@ fn map_criticality(value: Option<&str>) -> AssetCriticality {
 ///
 /// If Struttura Reale fields are populated AND differ from the standard office,
 /// the Struttura Reale owner becomes the effective owner.
-fn resolve_effective_owner(
+pub(crate) fn resolve_effective_owner(
     office_owner: &Option<String>,
     office_name: &Option<String>,
     struttura_owner: &Option<String>,
@@ -786,6 +1136,16 @@ fn is_flag_true(value: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn application_sort_defaults_to_name() {
+        assert_eq!(ApplicationSort::default().order_by(), "app_name ASC NULLS LAST, id");
+        let sort = ApplicationSort {
+            sort_by: Some(ApplicationSortField::UpdatedAt),
+            sort_dir: None,
+        };
+        assert_eq!(sort.order_by(), "updated_at DESC NULLS LAST, id");
+    }
+
     #[test]
     fn criticality_mapping_standard_values() {
         assert_eq!(map_criticality(Some("Very High")), AssetCriticality::VeryHigh);
@@ -875,7 +1235,9 @@ mod tests {
     #[test]
     fn csv_parsing_to_row_maps() {
         let csv_data = b"A,B,C\n1,2,3\n4,5,6";
-        let rows = parse_csv_rows(csv_data).unwrap();
+        let sheet = parse_csv_rows(csv_data).unwrap();
+        assert_eq!(sheet.headers, ["A", "B", "C"]);
+        let rows = sheet.rows;
         assert_eq!(rows.len(), 2);
         assert_eq!(rows[0]["A"], "1");
         assert_eq!(rows[0]["B"], "2");
@@ -0,0 +1,417 @@
+//! Asset inventory: CRUD, application links, and app code resolution.
+//!
+//! Assets (hosts, DNS names, URLs, repositories, artifacts) linked to an
+//! application form an authoritative map that ingestion consults before the
+//! regex app code patterns. Identifiers are normalized on write so scanner
+//! values can be looked up directly; URLs and artifacts also match by prefix.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::asset::{Asset, AssetType, CreateAsset, LinkAsset, UpdateAsset};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::search::escape_like;
+use crate::services::{application, config_cache};
+
+/// Scanner metadata fields checked against the asset map, most specific first.
+const RESOLVER_FIELDS: &[(&str, AssetType)] = &[
+    ("dns_name", AssetType::DnsName),
+    ("ip_address", AssetType::Host),
+    ("host", AssetType::Host),
+    ("url", AssetType::Url),
+    ("project_key", AssetType::Repository),
+    ("repository", AssetType::Repository),
+    ("impacted_artifact", AssetType::Artifact),
+    ("path", AssetType::Artifact),
+];
+
+/// Asset columns plus the linked application's app code.
+const SELECT_ASSET: &str = r#"
+    SELECT s.id, s.asset_type, s.identifier, s.application_id, a.app_code,
+           s.description, s.created_at, s.updated_at
+    FROM assets s
+    LEFT JOIN applications a ON a.id = s.application_id
+"#;
+
+/// Filters shared by the count and page queries of [`list`].
+const LIST_CONDITIONS: &str = r#"
+    WHERE ($1::asset_type IS NULL OR s.asset_type = $1)
+      AND ($2::uuid IS NULL OR s.application_id = $2)
+      AND ($3::text IS NULL OR s.identifier ILIKE '%' || $3 || '%')
+"#;
+
+/// Query parameters for the asset list.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AssetFilters {
+    pub asset_type: Option<AssetType>,
+    pub application_id: Option<Uuid>,
+    /// Substring of the identifier (case-insensitive).
+    pub q: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Normalization and resolution
+// ---------------------------------------------------------------------------
+
+/// Whether identifiers of this type match scanner values by prefix.
+fn matches_by_prefix(asset_type: AssetType) -> bool {
+    matches!(asset_type, AssetType::Url | AssetType::Artifact)
+}
+
+/// Canonical form of a value for lookups: hosts, DNS names, and URLs are
+/// case-insensitive, and DNS names lose any trailing root dot.
+fn normalize_value(asset_type: AssetType, value: &str) -> String {
+    let value = value.trim();
+    match asset_type {
+        AssetType::Host | AssetType::DnsName => value.trim_end_matches('.').to_ascii_lowercase(),
+        AssetType::Url => value.to_ascii_lowercase(),
+        AssetType::Repository | AssetType::Artifact => value.to_string(),
+    }
+}
+
+/// Normalize an identifier for storage, rejecting blank values and host or
+/// DNS names containing whitespace or slashes.
+fn normalize_identifier(asset_type: AssetType, identifier: &str) -> Result<String, AppError> {
+    let normalized = normalize_value(asset_type, identifier);
+    if normalized.is_empty() {
+        return Err(AppError::Validation(
+            "Asset identifier is required".to_string(),
+        ));
+    }
+    let is_name = matches!(asset_type, AssetType::Host | AssetType::DnsName);
+    if is_name && normalized.contains(|c: char| c.is_whitespace() || c == '/') {
+        return Err(AppError::Validation(format!(
+            "'{identifier}' is not a valid host or DNS name"
+        )));
+    }
+    Ok(normalized)
+}
+
+/// Linked assets indexed for app code lookups.
+#[derive(Debug, Default)]
+pub struct AssetMap {
+    exact: HashMap<(AssetType, String), String>,
+    /// `(type, prefix, app_code)` for prefix-matched types, longest prefix first.
+    prefixes: Vec<(AssetType, String, String)>,
+}
+
+impl AssetMap {
+    /// Build the map from `(type, identifier, app_code)` entries.
+    pub fn from_entries(entries: impl IntoIterator<Item = (AssetType, String, String)>) -> Self {
+        let mut map = Self::default();
+        for (asset_type, identifier, app_code) in entries {
+            if matches_by_prefix(asset_type) {
+                map.prefixes
+                    .push((asset_type, identifier.clone(), app_code.clone()));
+            }
+            map.exact.insert((asset_type, identifier), app_code);
+        }
+        map.prefixes
+            .sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.1.cmp(&b.1)));
+        map
+    }
+
+    /// App code of the first asset matching a scanner field, checking fields
+    /// in [`RESOLVER_FIELDS`] order.
+    pub fn resolve(&self, fields: &[(String, String)]) -> Option<String> {
+        for (field_name, asset_type) in RESOLVER_FIELDS {
+            let Some((_, raw)) = fields.iter().find(|(name, _)| name == field_name) else {
+                continue;
+            };
+            let value = normalize_value(*asset_type, raw);
+            if value.is_empty() {
+                continue;
+            }
+            if let Some(app_code) = self.exact.get(&(*asset_type, value.clone())) {
+                return Some(app_code.clone());
+            }
+            let prefix_match = self
+                .prefixes
+                .iter()
+                .find(|(t, prefix, _)| t == asset_type && value.starts_with(prefix.as_str()));
+            if let Some((_, _, app_code)) = prefix_match {
+                return Some(app_code.clone());
+            }
+        }
+        None
+    }
+}
+
+/// Load every asset linked to an application into an [`AssetMap`].
+pub async fn load_map(pool: &PgPool) -> Result<AssetMap, AppError> {
+    let rows = sqlx::query_as::<_, (AssetType, String, String)>(
+        r#"
+        SELECT s.asset_type, s.identifier, a.app_code
+        FROM assets s
+        JOIN applications a ON a.id = s.application_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(AssetMap::from_entries(rows))
+}
+
+/// Map the `(asset_type, identifier)` unique violation to a readable conflict.
+fn identifier_conflict(e: sqlx::Error, identifier: &str) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            AppError::Conflict(format!("Asset '{identifier}' already exists"))
+        }
+        _ => AppError::Database(e),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CRUD
+// ---------------------------------------------------------------------------
+
+/// List assets matching the filters, by type and identifier.
+pub async fn list(
+    pool: &PgPool,
+    filters: &AssetFilters,
+    pagination: &Pagination,
+) -> Result<PagedResult<Asset>, AppError> {
+    let q = filters
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(escape_like);
+
+    let total =
+        sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM assets s {LIST_CONDITIONS}"))
+            .bind(filters.asset_type)
+            .bind(filters.application_id)
+            .bind(&q)
+            .fetch_one(pool)
+            .await?;
+
+    let items = sqlx::query_as::<_, Asset>(&format!(
+        "{SELECT_ASSET} {LIST_CONDITIONS} \
+         ORDER BY s.asset_type, s.identifier LIMIT $4 OFFSET $5"
+    ))
+    .bind(filters.asset_type)
+    .bind(filters.application_id)
+    .bind(&q)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(items, total, pagination))
+}
+
+/// All assets linked to an application.
+pub async fn list_for_application(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<Vec<Asset>, AppError> {
+    application::find_by_id(pool, application_id).await?;
+    let assets = sqlx::query_as::<_, Asset>(&format!(
+        "{SELECT_ASSET} WHERE s.application_id = $1 ORDER BY s.asset_type, s.identifier"
+    ))
+    .bind(application_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(assets)
+}
+
+/// Fetch an asset by ID.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Asset, AppError> {
+    sqlx::query_as::<_, Asset>(&format!("{SELECT_ASSET} WHERE s.id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Asset {id} not found")))
+}
+
+/// Create an asset, optionally linked to an application.
+pub async fn create(pool: &PgPool, input: &CreateAsset) -> Result<Asset, AppError> {
+    let identifier = normalize_identifier(input.asset_type, &input.identifier)?;
+    if let Some(application_id) = input.application_id {
+        application::find_by_id(pool, application_id).await?;
+    }
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO assets (asset_type, identifier, application_id, description)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+    )
+    .bind(input.asset_type)
+    .bind(&identifier)
+    .bind(input.application_id)
+    .bind(&input.description)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| identifier_conflict(e, &identifier))?;
+
+    config_cache::global().invalidate_assets().await;
+    find_by_id(pool, id).await
+}
+
+/// Update an asset's identifier or description; omitted fields keep their values.
+pub async fn update(pool: &PgPool, id: Uuid, input: &UpdateAsset) -> Result<Asset, AppError> {
+    let existing = find_by_id(pool, id).await?;
+    let identifier = match &input.identifier {
+        Some(identifier) => normalize_identifier(existing.asset_type, identifier)?,
+        None => existing.identifier,
+    };
+
+    sqlx::query("UPDATE assets SET identifier = $1, description = $2 WHERE id = $3")
+        .bind(&identifier)
+        .bind(input.description.as_ref().or(existing.description.as_ref()))
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| identifier_conflict(e, &identifier))?;
+
+    config_cache::global().invalidate_assets().await;
+    find_by_id(pool, id).await
+}
+
+/// Link an asset to an application, or unlink it.
+pub async fn link(pool: &PgPool, id: Uuid, input: &LinkAsset) -> Result<Asset, AppError> {
+    find_by_id(pool, id).await?;
+    if let Some(application_id) = input.application_id {
+        application::find_by_id(pool, application_id).await?;
+    }
+
+    sqlx::query("UPDATE assets SET application_id = $1 WHERE id = $2")
+        .bind(input.application_id)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    config_cache::global().invalidate_assets().await;
+    find_by_id(pool, id).await
+}
+
+/// Delete an asset.
+pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM assets WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Asset {id} not found")));
+    }
+    config_cache::global().invalidate_assets().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn map() -> AssetMap {
+        AssetMap::from_entries([
+            (
+                AssetType::DnsName,
+                "portal.example.com".to_string(),
+                "PRT".to_string(),
+            ),
+            (AssetType::Host, "10.0.0.5".to_string(), "HST".to_string()),
+            (
+                AssetType::Url,
+                "https://shop.example.com/".to_string(),
+                "SHP".to_string(),
+            ),
+            (
+                AssetType::Url,
+                "https://shop.example.com/admin".to_string(),
+                "ADM".to_string(),
+            ),
+            (
+                AssetType::Repository,
+                "org.example:payments".to_string(),
+                "PAY".to_string(),
+            ),
+            (
+                AssetType::Artifact,
+                "prod-release-local/gpe30/".to_string(),
+                "GPE30".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn identifiers_are_normalized_per_type() {
+        assert_eq!(
+            normalize_identifier(AssetType::DnsName, " Portal.Example.COM. ").unwrap(),
+            "portal.example.com"
+        );
+        assert_eq!(
+            normalize_identifier(AssetType::Repository, "Org.Example:Payments").unwrap(),
+            "Org.Example:Payments"
+        );
+        assert!(normalize_identifier(AssetType::Host, "  ").is_err());
+        assert!(normalize_identifier(AssetType::Host, "bad host").is_err());
+        assert!(normalize_identifier(AssetType::DnsName, "example.com/path").is_err());
+    }
+
+    #[test]
+    fn exact_identifiers_resolve_case_insensitively() {
+        let map = map();
+        assert_eq!(
+            map.resolve(&fields(&[("dns_name", "PORTAL.example.com")])),
+            Some("PRT".to_string())
+        );
+        assert_eq!(
+            map.resolve(&fields(&[("ip_address", "10.0.0.5")])),
+            Some("HST".to_string())
+        );
+        assert_eq!(
+            map.resolve(&fields(&[("project_key", "org.example:payments")])),
+            Some("PAY".to_string())
+        );
+        assert_eq!(
+            map.resolve(&fields(&[("dns_name", "other.example.com")])),
+            None
+        );
+    }
+
+    #[test]
+    fn urls_and_artifacts_resolve_by_longest_prefix() {
+        let map = map();
+        assert_eq!(
+            map.resolve(&fields(&[("url", "https://shop.example.com/admin/users")])),
+            Some("ADM".to_string())
+        );
+        assert_eq!(
+            map.resolve(&fields(&[("url", "https://shop.example.com/cart")])),
+            Some("SHP".to_string())
+        );
+        assert_eq!(
+            map.resolve(&fields(&[(
+                "path",
+                "prod-release-local/gpe30/gpe30-set/v1.2.0/set-ear.ear"
+            )])),
+            Some("GPE30".to_string())
+        );
+    }
+
+    #[test]
+    fn dns_name_takes_precedence_over_url() {
+        let map = map();
+        let resolved = map.resolve(&fields(&[
+            ("url", "https://shop.example.com/cart"),
+            ("dns_name", "portal.example.com"),
+        ]));
+        assert_eq!(resolved, Some("PRT".to_string()));
+    }
+}
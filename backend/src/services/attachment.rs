@@ -0,0 +1,332 @@
+//! Evidence attachments on findings: upload validation, metadata, and access rules.
+//!
+//! The content type of an attachment is derived from its file extension
+//! against a fixed allowlist, never from the client, and downloads are
+//! always served as `attachment` so stored files cannot render inline.
+
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::attachment::FindingAttachment;
+use crate::models::user::UserRole;
+use crate::services::attachment_storage::AttachmentStorage;
+
+/// Accepted file extensions and the content type stored for each.
+const ALLOWED_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("pdf", "application/pdf"),
+    ("txt", "text/plain"),
+    ("log", "text/plain"),
+    ("http", "text/plain"),
+    ("json", "application/json"),
+    ("har", "application/json"),
+    ("pcap", "application/vnd.tcpdump.pcap"),
+    ("pcapng", "application/vnd.tcpdump.pcap"),
+];
+
+/// Longest stored file name, in characters.
+const MAX_FILE_NAME_LEN: usize = 255;
+
+/// Content type for an allowed file name, by extension (case-insensitive).
+fn content_type_for(file_name: &str) -> Option<&'static str> {
+    let (_, ext) = file_name.rsplit_once('.')?;
+    let ext = ext.to_ascii_lowercase();
+    ALLOWED_TYPES
+        .iter()
+        .find(|(allowed, _)| *allowed == ext)
+        .map(|(_, content_type)| *content_type)
+}
+
+/// Reduce a client-supplied file name to a safe base name: no directory
+/// components, control characters, or quotes (which would break the
+/// `Content-Disposition` header).
+fn sanitize_file_name(file_name: &str) -> String {
+    let base = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    base.chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(MAX_FILE_NAME_LEN)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// `Content-Disposition` value that forces a download, with an ASCII
+/// fallback name and the exact UTF-8 name (RFC 6266).
+pub fn content_disposition(file_name: &str) -> String {
+    let ascii: String = file_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = file_name
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+                char::from(b).to_string()
+            } else {
+                format!("%{b:02X}")
+            }
+        })
+        .collect();
+    format!("attachment; filename=\"{ascii}\"; filename*=UTF-8''{encoded}")
+}
+
+/// Whether a role may list and download attachments.
+pub fn can_view(role: &UserRole) -> bool {
+    !matches!(role, UserRole::Executive | UserRole::ApiServiceAccount)
+}
+
+/// Whether a user may delete an attachment: its uploader, or a manager or admin.
+pub fn can_delete(user_id: Uuid, role: &UserRole, attachment: &FindingAttachment) -> bool {
+    attachment.uploaded_by == Some(user_id)
+        || matches!(role, UserRole::PlatformAdmin | UserRole::AppSecManager)
+}
+
+/// Fail with `Forbidden` unless the role may view attachments.
+pub fn ensure_can_view(role: &UserRole) -> Result<(), AppError> {
+    if can_view(role) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(
+            "Your role cannot access finding attachments".to_string(),
+        ))
+    }
+}
+
+/// Validate and store an attachment for a finding.
+///
+/// Rejects empty files, files over `max_bytes`, and extensions outside the
+/// allowlist. The file is written to storage before its metadata row; if the
+/// row cannot be inserted the stored file is removed again.
+pub async fn upload(
+    pool: &PgPool,
+    storage: &AttachmentStorage,
+    finding_id: Uuid,
+    file_name: &str,
+    data: Vec<u8>,
+    max_bytes: usize,
+    uploaded_by: Uuid,
+) -> Result<FindingAttachment, AppError> {
+    let file_name = sanitize_file_name(file_name);
+    if file_name.is_empty() {
+        return Err(AppError::Validation("File name is required".to_string()));
+    }
+    let content_type = content_type_for(&file_name).ok_or_else(|| {
+        AppError::Validation(format!(
+            "Unsupported file type for '{file_name}'; allowed extensions: {}",
+            ALLOWED_TYPES
+                .iter()
+                .map(|(ext, _)| *ext)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    })?;
+    if data.is_empty() {
+        return Err(AppError::Validation("File is empty".to_string()));
+    }
+    if data.len() > max_bytes {
+        return Err(AppError::Validation(format!(
+            "File exceeds the {max_bytes}-byte attachment limit"
+        )));
+    }
+
+    let finding_exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM findings WHERE id = $1)")
+            .bind(finding_id)
+            .fetch_one(pool)
+            .await?;
+    if !finding_exists {
+        return Err(AppError::NotFound(format!(
+            "Finding {finding_id} not found"
+        )));
+    }
+
+    let id = Uuid::new_v4();
+    let storage_key = format!("findings/{finding_id}/{id}");
+    let sha256 = hex::encode(Sha256::digest(&data));
+    let size_bytes = data.len() as i64;
+
+    storage.put(&storage_key, data, content_type).await?;
+
+    let inserted = sqlx::query_as::<_, FindingAttachment>(
+        r#"
+        INSERT INTO finding_attachments
+            (id, finding_id, file_name, content_type, size_bytes, sha256, storage_key, uploaded_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(finding_id)
+    .bind(&file_name)
+    .bind(content_type)
+    .bind(size_bytes)
+    .bind(&sha256)
+    .bind(&storage_key)
+    .bind(uploaded_by)
+    .fetch_one(pool)
+    .await;
+
+    match inserted {
+        Ok(attachment) => Ok(attachment),
+        Err(e) => {
+            if let Err(cleanup) = storage.delete(&storage_key).await {
+                tracing::warn!(key = %storage_key, error = %cleanup, "Failed to remove orphaned attachment");
+            }
+            Err(e.into())
+        }
+    }
+}
+
+/// List a finding's attachments, oldest first.
+pub async fn list(pool: &PgPool, finding_id: Uuid) -> Result<Vec<FindingAttachment>, AppError> {
+    let attachments = sqlx::query_as::<_, FindingAttachment>(
+        "SELECT * FROM finding_attachments WHERE finding_id = $1 ORDER BY created_at ASC",
+    )
+    .bind(finding_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(attachments)
+}
+
+/// Get an attachment's metadata; it must belong to `finding_id`.
+pub async fn find_by_id(
+    pool: &PgPool,
+    finding_id: Uuid,
+    id: Uuid,
+) -> Result<FindingAttachment, AppError> {
+    sqlx::query_as::<_, FindingAttachment>(
+        "SELECT * FROM finding_attachments WHERE id = $1 AND finding_id = $2",
+    )
+    .bind(id)
+    .bind(finding_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Attachment {id} not found")))
+}
+
+/// Read an attachment's metadata and contents.
+pub async fn download(
+    pool: &PgPool,
+    storage: &AttachmentStorage,
+    finding_id: Uuid,
+    id: Uuid,
+) -> Result<(FindingAttachment, Vec<u8>), AppError> {
+    let attachment = find_by_id(pool, finding_id, id).await?;
+    let data = storage.get(&attachment.storage_key).await?;
+    Ok((attachment, data))
+}
+
+/// Delete an attachment and its stored contents.
+///
+/// Only the uploader, a manager, or an admin may delete. The metadata row is
+/// removed first, so a storage failure leaves an unreferenced file rather
+/// than a row pointing at nothing.
+pub async fn delete(
+    pool: &PgPool,
+    storage: &AttachmentStorage,
+    finding_id: Uuid,
+    id: Uuid,
+    user_id: Uuid,
+    role: &UserRole,
+) -> Result<(), AppError> {
+    let attachment = find_by_id(pool, finding_id, id).await?;
+    if !can_delete(user_id, role, &attachment) {
+        return Err(AppError::Forbidden(
+            "Only the uploader or a manager can delete this attachment".to_string(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM finding_attachments WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    if let Err(e) = storage.delete(&attachment.storage_key).await {
+        tracing::warn!(key = %attachment.storage_key, error = %e, "Failed to remove attachment contents");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attachment(uploaded_by: Option<Uuid>) -> FindingAttachment {
+        FindingAttachment {
+            id: Uuid::new_v4(),
+            finding_id: Uuid::new_v4(),
+            file_name: "trace.har".to_string(),
+            content_type: "application/json".to_string(),
+            size_bytes: 10,
+            sha256: String::new(),
+            storage_key: String::new(),
+            uploaded_by,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn content_type_comes_from_allowed_extensions() {
+        assert_eq!(content_type_for("login.PNG"), Some("image/png"));
+        assert_eq!(
+            content_type_for("capture.pcapng"),
+            Some("application/vnd.tcpdump.pcap")
+        );
+        assert_eq!(content_type_for("payload.html"), None);
+        assert_eq!(content_type_for("no_extension"), None);
+    }
+
+    #[test]
+    fn file_names_are_reduced_to_safe_base_names() {
+        assert_eq!(sanitize_file_name("../../etc/passwd.txt"), "passwd.txt");
+        assert_eq!(sanitize_file_name("C:\\evidence\\shot.png"), "shot.png");
+        assert_eq!(sanitize_file_name("a\"b\r\n.txt"), "ab.txt");
+        assert_eq!(sanitize_file_name("dir/"), "");
+    }
+
+    #[test]
+    fn content_disposition_has_ascii_fallback() {
+        assert_eq!(
+            content_disposition("login page.png"),
+            "attachment; filename=\"login page.png\"; filename*=UTF-8''login%20page.png"
+        );
+        assert_eq!(
+            content_disposition("résumé.txt"),
+            "attachment; filename=\"r_sum_.txt\"; filename*=UTF-8''r%C3%A9sum%C3%A9.txt"
+        );
+    }
+
+    #[test]
+    fn executives_and_service_accounts_cannot_view() {
+        assert!(can_view(&UserRole::Developer));
+        assert!(can_view(&UserRole::Auditor));
+        assert!(!can_view(&UserRole::Executive));
+        assert!(!can_view(&UserRole::ApiServiceAccount));
+    }
+
+    #[test]
+    fn only_uploader_or_manager_can_delete() {
+        let uploader = Uuid::new_v4();
+        let item = attachment(Some(uploader));
+        assert!(can_delete(uploader, &UserRole::AppSecAnalyst, &item));
+        assert!(!can_delete(Uuid::new_v4(), &UserRole::AppSecAnalyst, &item));
+        assert!(can_delete(Uuid::new_v4(), &UserRole::AppSecManager, &item));
+        assert!(can_delete(
+            Uuid::new_v4(),
+            &UserRole::PlatformAdmin,
+            &attachment(None)
+        ));
+    }
+}
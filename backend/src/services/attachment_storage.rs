@@ -0,0 +1,339 @@
+//! Storage backends for finding attachments.
+//!
+//! Files are stored under keys generated by the attachment service, either
+//! in a local directory or in an S3-compatible bucket. S3 requests are signed
+//! with AWS Signature Version 4 and use path-style URLs, so MinIO and other
+//! compatible stores work with a custom endpoint.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::{Method, StatusCode};
+use sha2::{Digest, Sha256};
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+
+/// Timeout for a single object storage request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Headers covered by the request signature, in canonical order.
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+/// Where attachment contents are kept.
+#[derive(Debug, Clone)]
+pub enum AttachmentStorage {
+    Local(LocalStorage),
+    S3(S3Storage),
+}
+
+impl AttachmentStorage {
+    /// Build the backend selected by `ATTACHMENT_STORAGE` (`local` or `s3`).
+    pub fn from_config(config: &AppConfig) -> Result<Self, AppError> {
+        match config.attachment_storage.as_str() {
+            "local" => Ok(Self::Local(LocalStorage {
+                root: PathBuf::from(&config.attachment_local_dir),
+            })),
+            "s3" => S3Storage::from_config(config).map(Self::S3),
+            other => Err(AppError::Internal(format!(
+                "Unknown attachment storage '{other}' (expected 'local' or 's3')"
+            ))),
+        }
+    }
+
+    /// Store `data` under `key`, replacing any existing object.
+    pub async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<(), AppError> {
+        match self {
+            Self::Local(local) => local.put(key, &data).await,
+            Self::S3(s3) => s3.put(key, data, content_type).await,
+        }
+    }
+
+    /// Read the object stored under `key`.
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        match self {
+            Self::Local(local) => local.get(key).await,
+            Self::S3(s3) => s3.get(key).await,
+        }
+    }
+
+    /// Remove the object stored under `key`; a missing object is not an error.
+    pub async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match self {
+            Self::Local(local) => local.delete(key).await,
+            Self::S3(s3) => s3.delete(key).await,
+        }
+    }
+}
+
+/// Files under a local directory, one file per key.
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    async fn put(&self, key: &str, data: &[u8]) -> Result<(), AppError> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(storage_error)?;
+        }
+        tokio::fs::write(&path, data).await.map_err(storage_error)
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        tokio::fs::read(self.root.join(key))
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => {
+                    AppError::NotFound("Attachment content not found".to_string())
+                }
+                _ => storage_error(e),
+            })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.root.join(key)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(storage_error(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Objects in an S3-compatible bucket.
+#[derive(Clone)]
+pub struct S3Storage {
+    client: reqwest::Client,
+    /// Base URL without trailing slash, e.g. `https://s3.eu-west-1.amazonaws.com`.
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl std::fmt::Debug for S3Storage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3Storage")
+            .field("endpoint", &self.endpoint)
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"[redacted]")
+            .finish()
+    }
+}
+
+impl S3Storage {
+    fn from_config(config: &AppConfig) -> Result<Self, AppError> {
+        let required = |value: &Option<String>, name: &str| {
+            value
+                .clone()
+                .ok_or_else(|| AppError::Internal(format!("{name} is required for S3 storage")))
+        };
+        let region = config.attachment_s3_region.clone();
+        let endpoint = config
+            .attachment_s3_endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://s3.{region}.amazonaws.com"));
+
+        Ok(Self {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .map_err(|e| AppError::Internal(format!("Failed to build S3 client: {e}")))?,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket: required(&config.attachment_s3_bucket, "ATTACHMENT_S3_BUCKET")?,
+            region,
+            access_key_id: required(
+                &config.attachment_s3_access_key_id,
+                "ATTACHMENT_S3_ACCESS_KEY_ID",
+            )?,
+            secret_access_key: required(
+                &config.attachment_s3_secret_access_key,
+                "ATTACHMENT_S3_SECRET_ACCESS_KEY",
+            )?,
+        })
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<(), AppError> {
+        let response = self
+            .send(Method::PUT, key, data, Some(content_type))
+            .await?;
+        check_status(response).await.map(|_| ())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        let response = self.send(Method::GET, key, Vec::new(), None).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(AppError::NotFound(
+                "Attachment content not found".to_string(),
+            ));
+        }
+        let response = check_status(response).await?;
+        let body = response.bytes().await.map_err(storage_error)?;
+        Ok(body.to_vec())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let response = self.send(Method::DELETE, key, Vec::new(), None).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        check_status(response).await.map(|_| ())
+    }
+
+    /// Send a signed request for the object at `key`.
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response, AppError> {
+        // Keys are generated from UUIDs, so they need no percent-encoding
+        let url = reqwest::Url::parse(&format!("{}/{}/{}", self.endpoint, self.bucket, key))
+            .map_err(|e| AppError::Internal(format!("Invalid S3 object URL: {e}")))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(AppError::Internal("S3 endpoint has no host".to_string())),
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "{method}\n{path}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\n\
+             x-amz-date:{amz_date}\n\n{SIGNED_HEADERS}\n{payload_hash}",
+            path = url.path(),
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = signing_key(&self.secret_access_key, &date, &self.region, "s3");
+        let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={SIGNED_HEADERS}, \
+             Signature={signature}",
+            self.access_key_id
+        );
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header(reqwest::header::AUTHORIZATION, authorization);
+        if let Some(content_type) = content_type {
+            request = request.header(reqwest::header::CONTENT_TYPE, content_type);
+        }
+        request.body(body).send().await.map_err(storage_error)
+    }
+}
+
+/// Fail with the response body when S3 did not return a success status.
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, AppError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(AppError::Internal(format!(
+        "S3 request failed with {status}: {body}"
+    )))
+}
+
+fn storage_error(e: impl std::fmt::Display) -> AppError {
+    AppError::Internal(format!("Attachment storage error: {e}"))
+}
+
+/// SigV4 signing key for a date, region, and service.
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+    let key = hmac_sha256(format!("AWS4{secret}").as_bytes(), date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+/// HMAC-SHA256 (RFC 2104).
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(data);
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        // Test case 2
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Test case 6: key longer than the block size
+        assert_eq!(
+            hex::encode(hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn signing_key_matches_aws_example() {
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[tokio::test]
+    async fn local_storage_round_trip() {
+        let root =
+            std::env::temp_dir().join(format!("synapsec-attachments-{}", uuid::Uuid::new_v4()));
+        let storage = LocalStorage { root: root.clone() };
+
+        storage.put("findings/a/b", b"evidence").await.unwrap();
+        assert_eq!(storage.get("findings/a/b").await.unwrap(), b"evidence");
+
+        storage.delete("findings/a/b").await.unwrap();
+        storage.delete("findings/a/b").await.unwrap();
+        assert!(matches!(
+            storage.get("findings/a/b").await,
+            Err(AppError::NotFound(_))
+        ));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,462 @@
+//! MITRE ATT&CK technique mapping through CAPEC.
+//!
+//! A finding's techniques are those of the CAPEC patterns that exploit its
+//! CWEs, plus any technique ids its tags carry (scanner rule metadata such as
+//! `attack.t1190`). The mapping itself is the `attack_techniques_for` SQL
+//! function; this module keeps the stored ids current and loads the CAPEC
+//! CSV and ATT&CK STIX catalogs.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::attack::{AttackImportResult, AttackTechnique};
+
+/// Enterprise ATT&CK tactics in kill chain order.
+pub const TACTIC_ORDER: [&str; 14] = [
+    "reconnaissance",
+    "resource-development",
+    "initial-access",
+    "execution",
+    "persistence",
+    "privilege-escalation",
+    "defense-evasion",
+    "credential-access",
+    "discovery",
+    "lateral-movement",
+    "collection",
+    "command-and-control",
+    "exfiltration",
+    "impact",
+];
+
+/// Canonical technique id from `T1110.004`, `t1110.004`, or CAPEC's `1110.004`.
+pub fn technique_id(value: &str) -> Option<String> {
+    let value = value.trim();
+    let digits = value.strip_prefix(['T', 't']).unwrap_or(value);
+    let (base, sub) = match digits.split_once('.') {
+        Some((base, sub)) => (base, Some(sub)),
+        None => (digits, None),
+    };
+    let numeric = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+    if !numeric(base, 4) || !sub.map_or(true, |s| numeric(s, 3)) {
+        return None;
+    }
+    Some(match sub {
+        Some(sub) => format!("T{base}.{sub}"),
+        None => format!("T{base}"),
+    })
+}
+
+/// Recompute the stored technique ids of the given findings, or of every
+/// finding when `ids` is `None`. Returns the number of findings changed.
+pub async fn remap_findings(pool: &PgPool, ids: Option<&[Uuid]>) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE findings
+        SET attack_technique_ids = attack_techniques_for(cwe_ids, tags)
+        WHERE ($1::uuid[] IS NULL OR id = ANY($1))
+          AND attack_technique_ids IS DISTINCT FROM attack_techniques_for(cwe_ids, tags)
+        "#,
+    )
+    .bind(ids)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Catalog entries for the given technique ids.
+pub async fn techniques(
+    pool: &PgPool,
+    ids: &[String],
+) -> Result<HashMap<String, AttackTechnique>, AppError> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows = sqlx::query_as::<_, AttackTechnique>(
+        "SELECT technique_id, name, tactics FROM attack_techniques WHERE technique_id = ANY($1)",
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|t| (t.technique_id.clone(), t))
+        .collect())
+}
+
+// ---------------------------------------------------------------------------
+// CAPEC import
+// ---------------------------------------------------------------------------
+
+/// A CAPEC pattern parsed from the CSV export.
+#[derive(Debug, PartialEq)]
+struct CapecRow {
+    capec_id: i32,
+    name: String,
+    cwe_ids: Vec<i32>,
+    /// ATT&CK technique ids with the names CAPEC gives them.
+    techniques: Vec<(String, String)>,
+}
+
+/// CWE ids from a `Related Weaknesses` cell such as `::285::276::`.
+fn related_weaknesses(cell: &str) -> Vec<i32> {
+    let mut ids: Vec<i32> = cell
+        .split("::")
+        .filter_map(|id| id.trim().parse().ok())
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+/// ATT&CK entries of a `Taxonomy Mappings` cell such as
+/// `::TAXONOMY NAME:ATTACK:ENTRY ID:1110.004:ENTRY NAME:Brute Force:Credential Stuffing::`.
+fn attack_mappings(cell: &str) -> Vec<(String, String)> {
+    cell.split("::")
+        .filter_map(|entry| {
+            let rest = entry.strip_prefix("TAXONOMY NAME:ATTACK:ENTRY ID:")?;
+            let (id, rest) = rest.split_once(':').unwrap_or((rest, ""));
+            let name = rest.strip_prefix("ENTRY NAME:").unwrap_or("").trim();
+            let id = technique_id(id)?;
+            let name = if name.is_empty() {
+                id.clone()
+            } else {
+                name.to_string()
+            };
+            Some((id, name))
+        })
+        .collect()
+}
+
+/// Parse the CAPEC CSV export. Returns the rows and the number skipped.
+fn parse_capec(data: &[u8]) -> Result<(Vec<CapecRow>, usize), AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(data);
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::Validation(format!("Invalid CSV headers: {e}")))?
+        .clone();
+    // CAPEC writes the first header as 'ID
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.trim().trim_start_matches('\'') == name)
+    };
+    let (Some(id_col), Some(name_col)) = (column("ID"), column("Name")) else {
+        return Err(AppError::Validation(
+            "CAPEC catalog must have 'ID' and 'Name' columns".to_string(),
+        ));
+    };
+    let weaknesses_col = column("Related Weaknesses");
+    let taxonomy_col = column("Taxonomy Mappings");
+
+    let mut rows = Vec::new();
+    let mut skipped = 0;
+    for result in reader.records() {
+        let record = result.map_err(|e| AppError::Validation(format!("CSV parse error: {e}")))?;
+        let cell =
+            |col: Option<usize>| col.and_then(|c| record.get(c)).map(str::trim).unwrap_or("");
+        let capec_id = cell(Some(id_col))
+            .trim_start_matches("CAPEC-")
+            .parse::<i32>()
+            .ok()
+            .filter(|id| *id > 0);
+        let name = cell(Some(name_col));
+        let Some(capec_id) = capec_id.filter(|_| !name.is_empty()) else {
+            skipped += 1;
+            continue;
+        };
+        rows.push(CapecRow {
+            capec_id,
+            name: name.to_string(),
+            cwe_ids: related_weaknesses(cell(weaknesses_col)),
+            techniques: attack_mappings(cell(taxonomy_col)),
+        });
+    }
+    Ok((rows, skipped))
+}
+
+/// Insert or update CAPEC patterns from the CSV export, register the
+/// techniques they map to, and remap every finding.
+pub async fn import_capec(pool: &PgPool, data: &[u8]) -> Result<AttackImportResult, AppError> {
+    let (rows, skipped) = parse_capec(data)?;
+    if rows.is_empty() {
+        return Err(AppError::Validation(
+            "CAPEC catalog has no entries".to_string(),
+        ));
+    }
+
+    // Ragged arrays travel as comma-separated text
+    let ids: Vec<i32> = rows.iter().map(|r| r.capec_id).collect();
+    let names: Vec<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+    let cwes: Vec<String> = rows
+        .iter()
+        .map(|r| {
+            r.cwe_ids
+                .iter()
+                .map(i32::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect();
+    let techniques: Vec<String> = rows
+        .iter()
+        .map(|r| {
+            r.techniques
+                .iter()
+                .map(|(id, _)| id.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect();
+    let mut technique_names: HashMap<&str, &str> = HashMap::new();
+    for row in &rows {
+        for (id, name) in &row.techniques {
+            technique_names.entry(id.as_str()).or_insert(name.as_str());
+        }
+    }
+    let (technique_ids, technique_labels): (Vec<&str>, Vec<&str>) =
+        technique_names.into_iter().unzip();
+
+    let mut tx = pool.begin().await?;
+    let imported = sqlx::query(
+        r#"
+        INSERT INTO capec_patterns (capec_id, name, cwe_ids, technique_ids)
+        SELECT DISTINCT ON (capec_id) capec_id, name,
+               COALESCE(string_to_array(NULLIF(cwes, ''), ',')::int[], '{}'),
+               COALESCE(string_to_array(NULLIF(techniques, ''), ','), '{}')
+        FROM UNNEST($1::int[], $2::text[], $3::text[], $4::text[])
+            AS r(capec_id, name, cwes, techniques)
+        ON CONFLICT (capec_id) DO UPDATE SET
+            name = EXCLUDED.name,
+            cwe_ids = EXCLUDED.cwe_ids,
+            technique_ids = EXCLUDED.technique_ids,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(&ids)
+    .bind(&names)
+    .bind(&cwes)
+    .bind(&techniques)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    // Names and tactics from an ATT&CK import take precedence
+    sqlx::query(
+        r#"
+        INSERT INTO attack_techniques (technique_id, name)
+        SELECT * FROM UNNEST($1::text[], $2::text[])
+        ON CONFLICT (technique_id) DO NOTHING
+        "#,
+    )
+    .bind(&technique_ids)
+    .bind(&technique_labels)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let findings_remapped = remap_findings(pool, None).await?;
+    Ok(AttackImportResult {
+        imported,
+        skipped,
+        findings_remapped,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// ATT&CK import
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct StixBundle {
+    objects: Vec<StixObject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StixObject {
+    #[serde(rename = "type")]
+    kind: String,
+    name: Option<String>,
+    #[serde(default)]
+    revoked: bool,
+    #[serde(default)]
+    x_mitre_deprecated: bool,
+    #[serde(default)]
+    external_references: Vec<StixReference>,
+    #[serde(default)]
+    kill_chain_phases: Vec<StixPhase>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StixReference {
+    source_name: String,
+    external_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StixPhase {
+    kill_chain_name: String,
+    phase_name: String,
+}
+
+/// Techniques of an ATT&CK STIX bundle, skipping revoked and deprecated
+/// ones. Sub-technique names are prefixed with their parent's, as in CAPEC.
+/// Returns the techniques and the number of attack patterns skipped.
+fn parse_stix(data: &[u8]) -> Result<(Vec<AttackTechnique>, usize), AppError> {
+    let bundle: StixBundle = serde_json::from_slice(data)
+        .map_err(|e| AppError::Validation(format!("Invalid ATT&CK STIX bundle: {e}")))?;
+
+    let mut skipped = 0;
+    let mut techniques: Vec<AttackTechnique> = Vec::new();
+    for object in bundle.objects {
+        if object.kind != "attack-pattern" || object.revoked || object.x_mitre_deprecated {
+            continue;
+        }
+        let id = object
+            .external_references
+            .iter()
+            .find(|r| r.source_name == "mitre-attack")
+            .and_then(|r| technique_id(r.external_id.as_deref()?));
+        let (Some(id), Some(name)) = (id, object.name) else {
+            skipped += 1;
+            continue;
+        };
+        let tactics = object
+            .kill_chain_phases
+            .into_iter()
+            .filter(|p| p.kill_chain_name == "mitre-attack")
+            .map(|p| p.phase_name)
+            .collect();
+        techniques.push(AttackTechnique {
+            technique_id: id,
+            name,
+            tactics,
+        });
+    }
+
+    let parents: HashMap<String, String> = techniques
+        .iter()
+        .filter(|t| !t.technique_id.contains('.'))
+        .map(|t| (t.technique_id.clone(), t.name.clone()))
+        .collect();
+    for technique in &mut techniques {
+        let parent = technique
+            .technique_id
+            .split_once('.')
+            .and_then(|(base, _)| parents.get(base));
+        if let Some(parent) = parent {
+            technique.name = format!("{parent}: {}", technique.name);
+        }
+    }
+    Ok((techniques, skipped))
+}
+
+/// Insert or update technique names and tactics from the ATT&CK Enterprise
+/// STIX bundle (`enterprise-attack.json`).
+pub async fn import_techniques(pool: &PgPool, data: &[u8]) -> Result<AttackImportResult, AppError> {
+    let (techniques, skipped) = parse_stix(data)?;
+    if techniques.is_empty() {
+        return Err(AppError::Validation(
+            "ATT&CK bundle has no techniques".to_string(),
+        ));
+    }
+
+    let ids: Vec<&str> = techniques.iter().map(|t| t.technique_id.as_str()).collect();
+    let names: Vec<&str> = techniques.iter().map(|t| t.name.as_str()).collect();
+    let tactics: Vec<String> = techniques.iter().map(|t| t.tactics.join(",")).collect();
+    let imported = sqlx::query(
+        r#"
+        INSERT INTO attack_techniques (technique_id, name, tactics)
+        SELECT DISTINCT ON (technique_id) technique_id, name,
+               COALESCE(string_to_array(NULLIF(tactics, ''), ','), '{}')
+        FROM UNNEST($1::text[], $2::text[], $3::text[]) AS t(technique_id, name, tactics)
+        ON CONFLICT (technique_id) DO UPDATE SET
+            name = EXCLUDED.name,
+            tactics = EXCLUDED.tactics,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(&ids)
+    .bind(&names)
+    .bind(&tactics)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(AttackImportResult {
+        imported,
+        skipped,
+        findings_remapped: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn technique_ids_are_normalized() {
+        assert_eq!(technique_id("T1110").as_deref(), Some("T1110"));
+        assert_eq!(technique_id("t1110.004").as_deref(), Some("T1110.004"));
+        assert_eq!(technique_id("1574.010").as_deref(), Some("T1574.010"));
+        assert_eq!(technique_id("T111"), None);
+        assert_eq!(technique_id("T1110.4"), None);
+        assert_eq!(technique_id("TA0006"), None);
+    }
+
+    #[test]
+    fn capec_csv_yields_weaknesses_and_attack_mappings() {
+        let csv = "'ID,Name,Abstraction,Related Weaknesses,Taxonomy Mappings\n\
+                   600,Credential Stuffing,Standard,::522::307::522::,\"TAXONOMY NAME:ATTACK:ENTRY ID:1110.004:ENTRY NAME:Brute Force:Credential Stuffing::TAXONOMY NAME:OWASP Attacks:ENTRY NAME:Credential stuffing::\"\n\
+                   66,SQL Injection,Standard,::89::1286::,\n\
+                   ,Nameless,Standard,,\n";
+        let (rows, skipped) = parse_capec(csv.as_bytes()).unwrap();
+        assert_eq!(skipped, 1);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].capec_id, 600);
+        assert_eq!(rows[0].cwe_ids, [307, 522]);
+        assert_eq!(
+            rows[0].techniques,
+            [(
+                "T1110.004".to_string(),
+                "Brute Force:Credential Stuffing".to_string()
+            )]
+        );
+        assert!(rows[1].techniques.is_empty());
+    }
+
+    #[test]
+    fn stix_bundle_prefixes_sub_technique_names() {
+        let bundle = serde_json::json!({
+            "type": "bundle",
+            "objects": [
+                {
+                    "type": "attack-pattern",
+                    "name": "Brute Force",
+                    "external_references": [{"source_name": "mitre-attack", "external_id": "T1110"}],
+                    "kill_chain_phases": [{"kill_chain_name": "mitre-attack", "phase_name": "credential-access"}]
+                },
+                {
+                    "type": "attack-pattern",
+                    "name": "Credential Stuffing",
+                    "external_references": [{"source_name": "mitre-attack", "external_id": "T1110.004"}],
+                    "kill_chain_phases": [{"kill_chain_name": "mitre-attack", "phase_name": "credential-access"}]
+                },
+                {"type": "attack-pattern", "name": "Old", "revoked": true},
+                {"type": "intrusion-set", "name": "APT0"}
+            ]
+        });
+        let (techniques, skipped) = parse_stix(bundle.to_string().as_bytes()).unwrap();
+        assert_eq!(skipped, 0);
+        assert_eq!(techniques.len(), 2);
+        assert_eq!(techniques[1].name, "Brute Force: Credential Stuffing");
+        assert_eq!(techniques[1].tactics, ["credential-access"]);
+    }
+}
@@ -4,15 +4,24 @@
 //! cross-tool finding relationships, severity breakdowns, and
 //! tool coverage for security posture assessment.
 
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::models::attack::AttackTechnique;
+use crate::models::finding::{severity_rank, SeverityLevel};
 use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::attack::{self, TACTIC_ORDER};
+
+/// Tactic for techniques missing from the catalog or without a tactic.
+const UNCLASSIFIED_TACTIC: &str = "unclassified";
 
 /// Application-level attack chain summary.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AppAttackChainSummary {
     pub application_id: Uuid,
     pub app_name: String,
@@ -27,7 +36,7 @@ pub struct AppAttackChainSummary {
 }
 
 /// Severity count breakdown.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SeverityBreakdown {
     pub critical: i64,
     pub high: i64,
@@ -37,7 +46,7 @@ pub struct SeverityBreakdown {
 }
 
 /// Detailed attack chains for a single application.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AppAttackChainDetail {
     pub application_id: Uuid,
     pub app_name: String,
@@ -47,7 +56,7 @@ pub struct AppAttackChainDetail {
 }
 
 /// A single attack chain (correlation group).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AttackChain {
     pub group_id: Uuid,
     pub findings: Vec<ChainFinding>,
@@ -58,7 +67,7 @@ pub struct AttackChain {
 }
 
 /// Relationship edge within an attack chain.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ChainRelationship {
     pub id: Uuid,
     pub source_finding_id: Uuid,
@@ -68,7 +77,7 @@ pub struct ChainRelationship {
 }
 
 /// Finding within an attack chain.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ChainFinding {
     pub id: Uuid,
     pub title: String,
@@ -76,10 +85,11 @@ pub struct ChainFinding {
     pub finding_category: String,
     pub normalized_severity: String,
     pub status: String,
+    pub attack_technique_ids: Vec<String>,
 }
 
 /// Uncorrelated finding summary.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UncorrelatedFinding {
     pub id: Uuid,
     pub title: String,
@@ -87,19 +97,58 @@ pub struct UncorrelatedFinding {
     pub finding_category: String,
     pub normalized_severity: String,
     pub status: String,
+    pub attack_technique_ids: Vec<String>,
+}
+
+/// Attack chains of an application grouped by the ATT&CK tactics their
+/// findings' techniques belong to.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AppAttackTactics {
+    pub application_id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    /// In kill chain order; `unclassified` last.
+    pub tactics: Vec<TacticGroup>,
+}
+
+/// The chains reaching one tactic.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TacticGroup {
+    /// Kill chain phase, e.g. `credential-access`.
+    pub tactic: String,
+    pub techniques: Vec<AttackTechnique>,
+    /// Most severe first.
+    pub chains: Vec<TacticChain>,
+}
+
+/// A chain, or a single uncorrelated finding, within a tactic.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TacticChain {
+    pub group_id: Uuid,
+    pub max_severity: String,
+    pub finding_ids: Vec<Uuid>,
+    /// The chain's techniques in this tactic.
+    pub technique_ids: Vec<String>,
 }
 
 /// Query filters for attack chains.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct AttackChainFilters {
     pub branch: Option<String>,
+    /// Restrict summaries to applications this user is assigned to. Set by
+    /// the routes from the caller's ownership scope, never from the query string.
+    #[serde(skip)]
+    #[param(ignore)]
+    pub visible_to: Option<Uuid>,
 }
 
 // ---------------------------------------------------------------------------
 // Internal row types for sqlx queries
 // ---------------------------------------------------------------------------
 
-/// Row for application summary with finding counts and severity breakdown.
+/// Row for application summary with finding counts, severity breakdown,
+/// tool coverage, and correlation counts.
 #[derive(Debug, sqlx::FromRow)]
 struct AppSummaryRow {
     application_id: Uuid,
@@ -112,17 +161,7 @@ struct AppSummaryRow {
     low: i64,
     info: i64,
     avg_risk_score: Option<f64>,
-}
-
-/// Row for tool coverage per application.
-#[derive(Debug, sqlx::FromRow)]
-struct ToolCoverageRow {
-    source_tool: String,
-}
-
-/// Row for correlation group count per application.
-#[derive(Debug, sqlx::FromRow)]
-struct CorrelationCountRow {
+    tool_coverage: Vec<String>,
     correlated_findings: i64,
     group_count: i64,
 }
@@ -136,6 +175,7 @@ struct FindingRow {
     finding_category: String,
     normalized_severity: String,
     status: String,
+    attack_technique_ids: Vec<String>,
 }
 
 /// Row for a relationship edge (union-find grouping).
@@ -173,35 +213,29 @@ pub async fn list_summaries(
     pagination: &Pagination,
 ) -> Result<PagedResult<AppAttackChainSummary>, AppError> {
     // Step 1: Count total applications with findings
-    let total = if filters.branch.is_some() {
-        sqlx::query_scalar::<_, i64>(
-            r#"
-            SELECT COUNT(DISTINCT f.application_id)
-            FROM findings f
-            JOIN finding_sast fs ON fs.finding_id = f.id
-            WHERE f.application_id IS NOT NULL
-              AND fs.branch = $1
-            "#,
-        )
-        .bind(filters.branch.as_deref())
-        .fetch_one(pool)
-        .await?
-    } else {
-        sqlx::query_scalar::<_, i64>(
-            r#"
-            SELECT COUNT(DISTINCT application_id)
-            FROM findings
-            WHERE application_id IS NOT NULL
-            "#,
-        )
-        .fetch_one(pool)
-        .await?
-    };
+    let total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(DISTINCT f.application_id)
+        FROM findings f
+        WHERE f.application_id IS NOT NULL
+          AND ($1::text IS NULL OR EXISTS (
+              SELECT 1 FROM finding_sast fs WHERE fs.finding_id = f.id AND fs.branch = $1
+          ))
+          AND ($2::uuid IS NULL OR f.application_id IN (
+              SELECT application_id FROM application_owners WHERE user_id = $2
+          ))
+        "#,
+    )
+    .bind(filters.branch.as_deref())
+    .bind(filters.visible_to)
+    .fetch_one(pool)
+    .await?;
 
-    // Step 2: Fetch application summaries with severity breakdown
-    let app_rows = if filters.branch.is_some() {
-        sqlx::query_as::<_, AppSummaryRow>(
-            r#"
+    // Step 2: Fetch the page of application summaries with severity breakdown,
+    // tool coverage, and correlation counts in one statement
+    let app_rows = sqlx::query_as::<_, AppSummaryRow>(
+        r#"
+        WITH apps AS (
             SELECT
                 f.application_id,
                 a.app_name,
@@ -215,86 +249,59 @@ pub async fn list_summaries(
                 AVG(f.composite_risk_score::double precision) AS avg_risk_score
             FROM findings f
             JOIN applications a ON a.id = f.application_id
-            JOIN finding_sast fs ON fs.finding_id = f.id
             WHERE f.application_id IS NOT NULL
-              AND fs.branch = $1
+              AND ($1::text IS NULL OR EXISTS (
+                  SELECT 1 FROM finding_sast fs WHERE fs.finding_id = f.id AND fs.branch = $1
+              ))
+              AND ($4::uuid IS NULL OR f.application_id IN (
+                  SELECT application_id FROM application_owners WHERE user_id = $4
+              ))
             GROUP BY f.application_id, a.app_name, a.app_code
             ORDER BY COUNT(f.id) DESC
             LIMIT $2 OFFSET $3
-            "#,
-        )
-        .bind(filters.branch.as_deref())
-        .bind(pagination.limit())
-        .bind(pagination.offset())
-        .fetch_all(pool)
-        .await?
-    } else {
-        sqlx::query_as::<_, AppSummaryRow>(
-            r#"
-            SELECT
-                f.application_id,
-                a.app_name,
-                a.app_code,
-                COUNT(f.id) AS total_findings,
-                COALESCE(SUM(CASE WHEN f.normalized_severity::text = 'Critical' THEN 1 ELSE 0 END), 0) AS critical,
-                COALESCE(SUM(CASE WHEN f.normalized_severity::text = 'High'     THEN 1 ELSE 0 END), 0) AS high,
-                COALESCE(SUM(CASE WHEN f.normalized_severity::text = 'Medium'   THEN 1 ELSE 0 END), 0) AS medium,
-                COALESCE(SUM(CASE WHEN f.normalized_severity::text = 'Low'      THEN 1 ELSE 0 END), 0) AS low,
-                COALESCE(SUM(CASE WHEN f.normalized_severity::text = 'Info'     THEN 1 ELSE 0 END), 0) AS info,
-                AVG(f.composite_risk_score::double precision) AS avg_risk_score
-            FROM findings f
-            JOIN applications a ON a.id = f.application_id
-            WHERE f.application_id IS NOT NULL
-            GROUP BY f.application_id, a.app_name, a.app_code
-            ORDER BY COUNT(f.id) DESC
-            LIMIT $1 OFFSET $2
-            "#,
-        )
-        .bind(pagination.limit())
-        .bind(pagination.offset())
-        .fetch_all(pool)
-        .await?
-    };
-
-    // Step 3: For each application, fetch tool coverage and correlation counts
-    let mut summaries = Vec::with_capacity(app_rows.len());
-    for row in app_rows {
-        let tools = sqlx::query_as::<_, ToolCoverageRow>(
-            "SELECT DISTINCT source_tool FROM findings WHERE application_id = $1",
         )
-        .bind(row.application_id)
-        .fetch_all(pool)
-        .await?;
-
-        let tool_coverage: Vec<String> = tools.into_iter().map(|t| t.source_tool).collect();
-
-        let corr = sqlx::query_as::<_, CorrelationCountRow>(
-            r#"
+        SELECT
+            apps.*,
+            COALESCE(tools.tool_coverage, ARRAY[]::text[]) AS tool_coverage,
+            corr.correlated_findings,
+            corr.group_count
+        FROM apps
+        CROSS JOIN LATERAL (
+            SELECT ARRAY_AGG(DISTINCT t.source_tool::text ORDER BY t.source_tool::text) AS tool_coverage
+            FROM findings t
+            WHERE t.application_id = apps.application_id
+        ) tools
+        CROSS JOIN LATERAL (
             SELECT
-                COUNT(DISTINCT f.id) AS correlated_findings,
+                COUNT(DISTINCT c.id) AS correlated_findings,
                 COUNT(DISTINCT fr.source_finding_id) AS group_count
-            FROM findings f
+            FROM findings c
             JOIN finding_relationships fr
-              ON (fr.source_finding_id = f.id OR fr.target_finding_id = f.id)
-            WHERE f.application_id = $1
+              ON (fr.source_finding_id = c.id OR fr.target_finding_id = c.id)
+            WHERE c.application_id = apps.application_id
               AND fr.relationship_type::text IN ('correlated_with', 'grouped_under')
-            "#,
-        )
-        .bind(row.application_id)
-        .fetch_one(pool)
-        .await?;
-
-        let uncorrelated = row.total_findings - corr.correlated_findings;
+        ) corr
+        ORDER BY apps.total_findings DESC
+        "#,
+    )
+    .bind(filters.branch.as_deref())
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .bind(filters.visible_to)
+    .fetch_all(pool)
+    .await?;
 
-        summaries.push(AppAttackChainSummary {
+    let mut summaries: Vec<AppAttackChainSummary> = app_rows
+        .into_iter()
+        .map(|row| AppAttackChainSummary {
             application_id: row.application_id,
             app_name: row.app_name,
             app_code: row.app_code,
-            correlation_group_count: corr.group_count,
+            correlation_group_count: row.group_count,
             total_findings: row.total_findings,
-            correlated_findings: corr.correlated_findings,
-            uncorrelated_findings: uncorrelated.max(0),
-            tool_coverage,
+            correlated_findings: row.correlated_findings,
+            uncorrelated_findings: (row.total_findings - row.correlated_findings).max(0),
+            tool_coverage: row.tool_coverage,
             severity_breakdown: SeverityBreakdown {
                 critical: row.critical,
                 high: row.high,
@@ -303,8 +310,8 @@ pub async fn list_summaries(
                 info: row.info,
             },
             risk_score: row.avg_risk_score,
-        });
-    }
+        })
+        .collect();
 
     // Re-sort by correlation group count descending, then total findings
     summaries.sort_by(|a, b| {
@@ -341,7 +348,8 @@ pub async fn get_by_app(
                 f.source_tool,
                 f.finding_category::text AS finding_category,
                 f.normalized_severity::text AS normalized_severity,
-                f.status::text AS status
+                f.status::text AS status,
+                ARRAY(SELECT jsonb_array_elements_text(f.attack_technique_ids)) AS attack_technique_ids
             FROM findings f
             JOIN finding_sast fs ON fs.finding_id = f.id
             WHERE f.application_id = $1
@@ -361,7 +369,8 @@ pub async fn get_by_app(
                 f.source_tool,
                 f.finding_category::text AS finding_category,
                 f.normalized_severity::text AS normalized_severity,
-                f.status::text AS status
+                f.status::text AS status,
+                ARRAY(SELECT jsonb_array_elements_text(f.attack_technique_ids)) AS attack_technique_ids
             FROM findings f
             WHERE f.application_id = $1
             "#,
@@ -430,6 +439,7 @@ pub async fn get_by_app(
                 finding_category: f.finding_category.clone(),
                 normalized_severity: f.normalized_severity.clone(),
                 status: f.status.clone(),
+                attack_technique_ids: f.attack_technique_ids.clone(),
             });
         } else {
             let group_id = chain_findings[0].id;
@@ -442,10 +452,10 @@ pub async fn get_by_app(
 
             let max_severity = chain_findings
                 .iter()
-                .map(|f| severity_rank(&f.normalized_severity))
-                .max()
-                .map(|rank| severity_label(rank).to_string())
-                .unwrap_or_else(|| "Info".to_string());
+                .filter_map(|f| SeverityLevel::from_label(&f.normalized_severity))
+                .max_by_key(SeverityLevel::rank)
+                .map_or("Info", |s| s.label())
+                .to_string();
 
             // Collect relationships belonging to this chain
             let chain_ids: std::collections::HashSet<Uuid> =
@@ -477,6 +487,7 @@ pub async fn get_by_app(
                     finding_category: f.finding_category.clone(),
                     normalized_severity: f.normalized_severity.clone(),
                     status: f.status.clone(),
+                    attack_technique_ids: f.attack_technique_ids.clone(),
                 })
                 .collect();
 
@@ -507,10 +518,139 @@ pub async fn get_by_app(
     })
 }
 
+/// Get the attack chains of one application grouped by ATT&CK tactic.
+///
+/// Uncorrelated findings with techniques appear as single-finding chains.
+pub async fn get_tactics_by_app(
+    pool: &PgPool,
+    app_id: Uuid,
+    filters: &AttackChainFilters,
+) -> Result<AppAttackTactics, AppError> {
+    let detail = get_by_app(pool, app_id, filters).await?;
+
+    let mut chains: Vec<ChainTechniques> = detail
+        .chains
+        .iter()
+        .map(|chain| ChainTechniques {
+            group_id: chain.group_id,
+            max_severity: chain.max_severity.clone(),
+            finding_ids: chain.findings.iter().map(|f| f.id).collect(),
+            technique_ids: chain
+                .findings
+                .iter()
+                .flat_map(|f| f.attack_technique_ids.iter().cloned())
+                .collect(),
+        })
+        .collect();
+    chains.extend(
+        detail
+            .uncorrelated_findings
+            .iter()
+            .map(|f| ChainTechniques {
+                group_id: f.id,
+                max_severity: f.normalized_severity.clone(),
+                finding_ids: vec![f.id],
+                technique_ids: f.attack_technique_ids.iter().cloned().collect(),
+            }),
+    );
+    chains.retain(|c| !c.technique_ids.is_empty());
+
+    let ids: Vec<String> = chains
+        .iter()
+        .flat_map(|c| c.technique_ids.iter().cloned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let catalog = attack::techniques(pool, &ids).await?;
+
+    Ok(AppAttackTactics {
+        application_id: detail.application_id,
+        app_name: detail.app_name,
+        app_code: detail.app_code,
+        tactics: group_by_tactic(chains, &catalog),
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Internal helpers
 // ---------------------------------------------------------------------------
 
+/// A chain's techniques, before grouping by tactic.
+struct ChainTechniques {
+    group_id: Uuid,
+    max_severity: String,
+    finding_ids: Vec<Uuid>,
+    technique_ids: BTreeSet<String>,
+}
+
+/// Position of a tactic in the kill chain; unknown tactics sort after the
+/// known ones and `unclassified` last.
+fn tactic_rank(tactic: &str) -> usize {
+    match TACTIC_ORDER.iter().position(|t| *t == tactic) {
+        Some(rank) => rank,
+        None if tactic == UNCLASSIFIED_TACTIC => TACTIC_ORDER.len() + 1,
+        None => TACTIC_ORDER.len(),
+    }
+}
+
+/// Place each chain under every tactic its techniques belong to.
+fn group_by_tactic(
+    chains: Vec<ChainTechniques>,
+    catalog: &HashMap<String, AttackTechnique>,
+) -> Vec<TacticGroup> {
+    let mut groups: BTreeMap<String, (BTreeMap<String, AttackTechnique>, Vec<TacticChain>)> =
+        BTreeMap::new();
+    for chain in chains {
+        let mut by_tactic: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for id in &chain.technique_ids {
+            let tactics = catalog
+                .get(id)
+                .map(|t| t.tactics.iter().map(String::as_str).collect::<Vec<_>>())
+                .filter(|tactics| !tactics.is_empty())
+                .unwrap_or_else(|| vec![UNCLASSIFIED_TACTIC]);
+            for tactic in tactics {
+                by_tactic.entry(tactic).or_default().push(id.clone());
+            }
+        }
+        for (tactic, technique_ids) in by_tactic {
+            let (techniques, tactic_chains) = groups.entry(tactic.to_string()).or_default();
+            for id in &technique_ids {
+                techniques.entry(id.clone()).or_insert_with(|| {
+                    catalog.get(id).cloned().unwrap_or_else(|| AttackTechnique {
+                        technique_id: id.clone(),
+                        name: id.clone(),
+                        tactics: Vec::new(),
+                    })
+                });
+            }
+            tactic_chains.push(TacticChain {
+                group_id: chain.group_id,
+                max_severity: chain.max_severity.clone(),
+                finding_ids: chain.finding_ids.clone(),
+                technique_ids,
+            });
+        }
+    }
+
+    let mut tactics: Vec<TacticGroup> = groups
+        .into_iter()
+        .map(|(tactic, (techniques, mut chains))| {
+            chains.sort_by(|a, b| {
+                severity_rank(&b.max_severity)
+                    .cmp(&severity_rank(&a.max_severity))
+                    .then_with(|| b.finding_ids.len().cmp(&a.finding_ids.len()))
+            });
+            TacticGroup {
+                tactic,
+                techniques: techniques.into_values().collect(),
+                chains,
+            }
+        })
+        .collect();
+    tactics.sort_by_key(|g| tactic_rank(&g.tactic));
+    tactics
+}
+
 /// Build connected components from findings and relationship edges.
 ///
 /// Returns groups of findings where each group represents one chain.
@@ -574,47 +714,46 @@ fn build_chains<'a>(
     groups.into_values().collect()
 }
 
-/// Rank severity for sorting (higher = more severe).
-fn severity_rank(severity: &str) -> u8 {
-    match severity {
-        "Critical" => 5,
-        "High" => 4,
-        "Medium" => 3,
-        "Low" => 2,
-        "Info" => 1,
-        _ => 0,
-    }
-}
-
-/// Convert severity rank back to label.
-fn severity_label(rank: u8) -> &'static str {
-    match rank {
-        5 => "Critical",
-        4 => "High",
-        3 => "Medium",
-        2 => "Low",
-        1 => "Info",
-        _ => "Info",
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn severity_rank_ordering() {
-        assert!(severity_rank("Critical") > severity_rank("High"));
-        assert!(severity_rank("High") > severity_rank("Medium"));
-        assert!(severity_rank("Medium") > severity_rank("Low"));
-        assert!(severity_rank("Low") > severity_rank("Info"));
-    }
+    fn chains_are_grouped_by_tactic_in_kill_chain_order() {
+        let technique = |id: &str, tactics: &[&str]| AttackTechnique {
+            technique_id: id.to_string(),
+            name: id.to_string(),
+            tactics: tactics.iter().map(|t| t.to_string()).collect(),
+        };
+        let catalog: HashMap<String, AttackTechnique> = [
+            technique("T1110", &["credential-access"]),
+            technique("T1190", &["initial-access"]),
+        ]
+        .into_iter()
+        .map(|t| (t.technique_id.clone(), t))
+        .collect();
+        let chain = |severity: &str, ids: &[&str]| ChainTechniques {
+            group_id: Uuid::new_v4(),
+            max_severity: severity.to_string(),
+            finding_ids: vec![Uuid::new_v4()],
+            technique_ids: ids.iter().map(|id| id.to_string()).collect(),
+        };
 
-    #[test]
-    fn severity_label_round_trip() {
-        for label in &["Critical", "High", "Medium", "Low", "Info"] {
-            assert_eq!(severity_label(severity_rank(label)), *label);
-        }
+        let groups = group_by_tactic(
+            vec![
+                chain("Medium", &["T1110", "T9999"]),
+                chain("Critical", &["T1110", "T1190"]),
+            ],
+            &catalog,
+        );
+        let tactics: Vec<&str> = groups.iter().map(|g| g.tactic.as_str()).collect();
+        assert_eq!(
+            tactics,
+            ["initial-access", "credential-access", "unclassified"]
+        );
+        assert_eq!(groups[1].chains.len(), 2);
+        assert_eq!(groups[1].chains[0].max_severity, "Critical");
+        assert_eq!(groups[2].techniques[0].technique_id, "T9999");
     }
 
     #[test]
@@ -627,6 +766,7 @@ mod tests {
                 finding_category: "SAST".to_string(),
                 normalized_severity: "High".to_string(),
                 status: "New".to_string(),
+                attack_technique_ids: Vec::new(),
             },
             FindingRow {
                 id: Uuid::new_v4(),
@@ -635,6 +775,7 @@ mod tests {
                 finding_category: "SCA".to_string(),
                 normalized_severity: "Medium".to_string(),
                 status: "New".to_string(),
+                attack_technique_ids: Vec::new(),
             },
         ];
 
@@ -660,6 +801,7 @@ mod tests {
                 finding_category: "SAST".to_string(),
                 normalized_severity: "High".to_string(),
                 status: "New".to_string(),
+                attack_technique_ids: Vec::new(),
             },
             FindingRow {
                 id: id2,
@@ -668,6 +810,7 @@ mod tests {
                 finding_category: "SCA".to_string(),
                 normalized_severity: "Critical".to_string(),
                 status: "New".to_string(),
+                attack_technique_ids: Vec::new(),
             },
             FindingRow {
                 id: id3,
@@ -676,6 +819,7 @@ mod tests {
                 finding_category: "DAST".to_string(),
                 normalized_severity: "Low".to_string(),
                 status: "Confirmed".to_string(),
+                attack_technique_ids: Vec::new(),
             },
         ];
 
@@ -713,6 +857,7 @@ mod tests {
                 finding_category: "SAST".to_string(),
                 normalized_severity: "High".to_string(),
                 status: "New".to_string(),
+                attack_technique_ids: Vec::new(),
             },
             FindingRow {
                 id: id2,
@@ -721,6 +866,7 @@ mod tests {
                 finding_category: "SCA".to_string(),
                 normalized_severity: "Medium".to_string(),
                 status: "New".to_string(),
+                attack_technique_ids: Vec::new(),
             },
             FindingRow {
                 id: id3,
@@ -729,6 +875,7 @@ mod tests {
                 finding_category: "DAST".to_string(),
                 normalized_severity: "Critical".to_string(),
                 status: "New".to_string(),
+                attack_technique_ids: Vec::new(),
             },
         ];
 
@@ -0,0 +1,291 @@
+//! Audit log recording, paginated listing, and evidence export.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::audit::{AuditLog, CreateAuditLog};
+use crate::models::pagination::{PagedResult, Pagination};
+
+/// Filters for selecting audit log entries.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditLogFilters {
+    /// Inclusive lower bound on `created_at`.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`.
+    pub to: Option<DateTime<Utc>>,
+    pub actor_id: Option<Uuid>,
+    /// Case-insensitive substring match on the actor's name.
+    pub actor_name: Option<String>,
+    pub entity_type: Option<String>,
+    /// Exact action, e.g. `status_change` or an HTTP method for API requests.
+    pub action: Option<String>,
+}
+
+impl AuditLogFilters {
+    /// Reject ranges whose end precedes their start.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if to <= from {
+                return Err(AppError::Validation(
+                    "'to' must be later than 'from'".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the WHERE clause with positional parameters in bind order.
+    fn where_clause(&self) -> String {
+        let mut conditions: Vec<String> = Vec::new();
+        let mut param_index = 0u32;
+
+        if self.from.is_some() {
+            param_index += 1;
+            conditions.push(format!("created_at >= ${param_index}"));
+        }
+        if self.to.is_some() {
+            param_index += 1;
+            conditions.push(format!("created_at < ${param_index}"));
+        }
+        if self.actor_id.is_some() {
+            param_index += 1;
+            conditions.push(format!("actor_id = ${param_index}"));
+        }
+        if self.actor_name.is_some() {
+            param_index += 1;
+            conditions.push(format!("actor_name ILIKE ${param_index}"));
+        }
+        if self.entity_type.is_some() {
+            param_index += 1;
+            conditions.push(format!("entity_type = ${param_index}"));
+        }
+        if self.action.is_some() {
+            param_index += 1;
+            conditions.push(format!("action = ${param_index}"));
+        }
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        }
+    }
+}
+
+/// List all audit entries matching the filters, oldest first.
+pub async fn list_for_export(
+    pool: &PgPool,
+    filters: &AuditLogFilters,
+) -> Result<Vec<AuditLog>, AppError> {
+    filters.validate()?;
+
+    let sql = format!(
+        "SELECT id, entity_type, entity_id, action, actor_id, actor_name, details, ip_address, created_at \
+         FROM audit_log {} ORDER BY created_at ASC, id ASC",
+        filters.where_clause()
+    );
+
+    let mut query = sqlx::query_as::<_, AuditLog>(&sql);
+    if let Some(from) = filters.from {
+        query = query.bind(from);
+    }
+    if let Some(to) = filters.to {
+        query = query.bind(to);
+    }
+    if let Some(actor_id) = filters.actor_id {
+        query = query.bind(actor_id);
+    }
+    if let Some(ref actor_name) = filters.actor_name {
+        query = query.bind(format!("%{actor_name}%"));
+    }
+    if let Some(ref entity_type) = filters.entity_type {
+        query = query.bind(entity_type);
+    }
+    if let Some(ref action) = filters.action {
+        query = query.bind(action);
+    }
+
+    Ok(query.fetch_all(pool).await?)
+}
+
+/// List audit entries matching the filters, newest first.
+pub async fn list(
+    pool: &PgPool,
+    filters: &AuditLogFilters,
+    pagination: &Pagination,
+) -> Result<PagedResult<AuditLog>, AppError> {
+    filters.validate()?;
+
+    let where_clause = filters.where_clause();
+    let count_sql = format!("SELECT COUNT(*) FROM audit_log {where_clause}");
+    let data_sql = format!(
+        "SELECT id, entity_type, entity_id, action, actor_id, actor_name, details, ip_address, created_at \
+         FROM audit_log {where_clause} ORDER BY created_at DESC, id DESC LIMIT {} OFFSET {}",
+        pagination.limit(),
+        pagination.offset()
+    );
+
+    let mut count_query = sqlx::query_scalar::<_, i64>(&count_sql);
+    let mut data_query = sqlx::query_as::<_, AuditLog>(&data_sql);
+
+    macro_rules! bind_both {
+        ($val:expr) => {
+            count_query = count_query.bind($val);
+            data_query = data_query.bind($val);
+        };
+    }
+
+    let actor_pattern = filters.actor_name.as_ref().map(|n| format!("%{n}%"));
+    if let Some(from) = filters.from {
+        bind_both!(from);
+    }
+    if let Some(to) = filters.to {
+        bind_both!(to);
+    }
+    if let Some(actor_id) = filters.actor_id {
+        bind_both!(actor_id);
+    }
+    if let Some(ref pattern) = actor_pattern {
+        bind_both!(pattern);
+    }
+    if let Some(ref entity_type) = filters.entity_type {
+        bind_both!(entity_type);
+    }
+    if let Some(ref action) = filters.action {
+        bind_both!(action);
+    }
+
+    let total = count_query.fetch_one(pool).await?;
+    let items = data_query.fetch_all(pool).await?;
+    Ok(PagedResult::new(items, total, pagination))
+}
+
+/// Append an entry to the audit log.
+pub async fn record(pool: &PgPool, entry: &CreateAuditLog) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details, ip_address)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(&entry.entity_type)
+    .bind(entry.entity_id)
+    .bind(&entry.action)
+    .bind(entry.actor_id)
+    .bind(&entry.actor_name)
+    .bind(&entry.details)
+    .bind(&entry.ip_address)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Flat CSV row for an audit entry; `details` is kept as compact JSON.
+#[derive(Debug, Serialize)]
+struct AuditCsvRow<'a> {
+    id: String,
+    created_at: String,
+    actor_id: String,
+    actor_name: &'a str,
+    action: &'a str,
+    entity_type: &'a str,
+    entity_id: String,
+    ip_address: &'a str,
+    details: String,
+}
+
+impl<'a> From<&'a AuditLog> for AuditCsvRow<'a> {
+    fn from(entry: &'a AuditLog) -> Self {
+        Self {
+            id: entry.id.to_string(),
+            created_at: entry.created_at.to_rfc3339(),
+            actor_id: entry.actor_id.map_or_else(String::new, |id| id.to_string()),
+            actor_name: &entry.actor_name,
+            action: &entry.action,
+            entity_type: &entry.entity_type,
+            entity_id: entry
+                .entity_id
+                .map_or_else(String::new, |id| id.to_string()),
+            ip_address: entry.ip_address.as_deref().unwrap_or(""),
+            details: entry
+                .details
+                .as_ref()
+                .map_or_else(String::new, |d| d.to_string()),
+        }
+    }
+}
+
+/// Encode audit entries as a CSV document with a header row.
+pub fn encode_csv(entries: &[AuditLog]) -> Result<Vec<u8>, AppError> {
+    let mut wtr = csv::Writer::from_writer(Vec::new());
+    for entry in entries {
+        wtr.serialize(AuditCsvRow::from(entry))
+            .map_err(|e| AppError::Internal(format!("CSV serialization failed: {e}")))?;
+    }
+    wtr.into_inner()
+        .map_err(|e| AppError::Internal(format!("CSV flush failed: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(details: Option<serde_json::Value>) -> AuditLog {
+        AuditLog {
+            id: Uuid::new_v4(),
+            entity_type: "finding".to_string(),
+            entity_id: Some(Uuid::new_v4()),
+            action: "status_change".to_string(),
+            actor_id: None,
+            actor_name: "analyst, one".to_string(),
+            details,
+            ip_address: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn where_clause_numbers_params_in_bind_order() {
+        let filters = AuditLogFilters {
+            from: Some(Utc::now()),
+            actor_name: Some("admin".to_string()),
+            entity_type: Some("finding".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filters.where_clause(),
+            "WHERE created_at >= $1 AND actor_name ILIKE $2 AND entity_type = $3"
+        );
+        assert_eq!(AuditLogFilters::default().where_clause(), "");
+    }
+
+    #[test]
+    fn rejects_inverted_range() {
+        let now = Utc::now();
+        let filters = AuditLogFilters {
+            from: Some(now),
+            to: Some(now - chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(filters.validate().is_err());
+    }
+
+    #[test]
+    fn csv_has_header_and_quotes_fields() {
+        let csv = encode_csv(&[entry(Some(serde_json::json!({"new_status": "Closed"})))]).unwrap();
+        let text = String::from_utf8(csv).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "id,created_at,actor_id,actor_name,action,entity_type,entity_id,ip_address,details"
+        );
+        let row = lines.next().unwrap();
+        assert!(row.contains("\"analyst, one\""));
+        assert!(row.contains(r#""{""new_status"":""Closed""}""#));
+    }
+}
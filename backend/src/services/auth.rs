@@ -8,10 +8,12 @@ use chrono::{Duration, Utc};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::models::user::{CreateUser, User};
+use crate::services::redis_store::RedisStore;
 
 /// Maximum failed login attempts before account lockout.
 const MAX_FAILED_ATTEMPTS: i32 = 3;
@@ -28,10 +30,14 @@ pub struct Claims {
     pub token_type: String,
     pub exp: i64,
     pub iat: i64,
+    /// Unique token ID, the key under which a refresh token is revoked.
+    /// Empty for tokens issued before IDs were added.
+    #[serde(default)]
+    pub jti: String,
 }
 
 /// Token pair returned on successful login.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct TokenPair {
     pub access_token: String,
     pub refresh_token: String,
@@ -78,6 +84,7 @@ pub fn generate_tokens(
         token_type: "access".to_string(),
         exp: (now + Duration::seconds(access_expiry_secs)).timestamp(),
         iat: now.timestamp(),
+        jti: Uuid::new_v4().to_string(),
     };
 
     let refresh_claims = Claims {
@@ -87,6 +94,7 @@ pub fn generate_tokens(
         token_type: "refresh".to_string(),
         exp: (now + Duration::seconds(refresh_expiry_secs)).timestamp(),
         iat: now.timestamp(),
+        jti: Uuid::new_v4().to_string(),
     };
 
     let access_token = jsonwebtoken::encode(&Header::default(), &access_claims, &encoding_key)
@@ -202,16 +210,95 @@ pub async fn login(
     generate_tokens(&user, jwt_secret, access_expiry_secs, refresh_expiry_secs)
 }
 
+/// Redis key marking a refresh token as revoked.
+fn revoked_key(jti: &str) -> String {
+    format!("auth:revoked:{jti}")
+}
+
+/// Revoke a refresh token until it expires. A no-op without Redis or for
+/// tokens without an ID.
+pub async fn revoke_refresh_token(redis: Option<&RedisStore>, claims: &Claims) {
+    let Some(redis) = redis else { return };
+    if claims.jti.is_empty() {
+        return;
+    }
+    let remaining = claims.exp - Utc::now().timestamp();
+    if remaining > 0 {
+        redis
+            .set_marker(
+                &revoked_key(&claims.jti),
+                std::time::Duration::from_secs(remaining as u64),
+            )
+            .await;
+    }
+}
+
+/// Whether a refresh token was revoked. Without Redis, or while it is
+/// unreachable, revocation cannot be checked and the token is accepted; this
+/// only matters for logout, since `refresh_token()` claims the token
+/// atomically and fails closed.
+async fn is_revoked(redis: Option<&RedisStore>, claims: &Claims) -> bool {
+    match redis {
+        Some(redis) if !claims.jti.is_empty() => redis
+            .exists(&revoked_key(&claims.jti))
+            .await
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Validate a refresh token and return its claims, rejecting access tokens
+/// and revoked refresh tokens.
+pub async fn validate_refresh_token(
+    redis: Option<&RedisStore>,
+    token: &str,
+    jwt_secret: &str,
+) -> Result<Claims, AppError> {
+    let claims = validate_token(token, jwt_secret)?;
+    if claims.token_type != "refresh" || is_revoked(redis, &claims).await {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(claims)
+}
+
+/// Claim a refresh token for one rotation by setting its revocation marker
+/// only if it is not set yet, so concurrent refreshes with the same token
+/// cannot both succeed.
+///
+/// Fails closed: while Redis is unreachable the refresh is refused with a
+/// server error, leaving the token usable for a retry, rather than letting a
+/// possibly replayed token through. Without Redis at all (an invalid
+/// `REDIS_URL`) single use cannot be enforced and the claim succeeds.
+async fn claim_refresh_token(redis: Option<&RedisStore>, claims: &Claims) -> Result<(), AppError> {
+    let Some(redis) = redis else { return Ok(()) };
+    let remaining = claims.exp - Utc::now().timestamp();
+    if claims.jti.is_empty() || remaining <= 0 {
+        return Err(AppError::Unauthorized);
+    }
+    let ttl = std::time::Duration::from_secs(remaining as u64);
+    match redis.set_marker_if_absent(&revoked_key(&claims.jti), ttl).await {
+        Some(true) => Ok(()),
+        Some(false) => Err(AppError::Unauthorized),
+        None => Err(AppError::Internal(
+            "Refresh token store unavailable".to_string(),
+        )),
+    }
+}
+
 /// Refresh an access token using a valid refresh token.
+///
+/// Refresh tokens are single use: the presented token is claimed (and so
+/// revoked) before the new pair is issued, and a token already claimed is
+/// rejected.
 pub async fn refresh_token(
     pool: &PgPool,
+    redis: Option<&RedisStore>,
     refresh_token_str: &str,
     jwt_secret: &str,
     access_expiry_secs: i64,
     refresh_expiry_secs: i64,
 ) -> Result<TokenPair, AppError> {
     let claims = validate_token(refresh_token_str, jwt_secret)?;
-
     if claims.token_type != "refresh" {
         return Err(AppError::Unauthorized);
     }
@@ -227,6 +314,7 @@ pub async fn refresh_token(
         .await?
         .ok_or(AppError::Unauthorized)?;
 
+    claim_refresh_token(redis, &claims).await?;
     generate_tokens(&user, jwt_secret, access_expiry_secs, refresh_expiry_secs)
 }
 
@@ -284,6 +372,8 @@ mod tests {
         // Validate refresh token
         let refresh_claims = validate_token(&tokens.refresh_token, secret).unwrap();
         assert_eq!(refresh_claims.token_type, "refresh");
+        assert!(!refresh_claims.jti.is_empty());
+        assert_ne!(refresh_claims.jti, claims.jti);
     }
 
     #[test]
@@ -315,4 +405,40 @@ mod tests {
         let result = validate_token(&tokens.access_token, secret);
         assert!(result.is_err());
     }
+
+    fn refresh_claims(jti: &str, ttl_secs: i64) -> Claims {
+        let now = Utc::now().timestamp();
+        Claims {
+            sub: "test".to_string(),
+            user_id: Uuid::new_v4().to_string(),
+            role: "Developer".to_string(),
+            token_type: "refresh".to_string(),
+            exp: now + ttl_secs,
+            iat: now,
+            jti: jti.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_claim_fails_closed_when_redis_is_unreachable() {
+        let redis = RedisStore::new(redis::Client::open("redis://127.0.0.1:1").unwrap());
+        let claims = refresh_claims("jti-1", 600);
+        assert!(matches!(
+            claim_refresh_token(Some(&redis), &claims).await,
+            Err(AppError::Internal(_))
+        ));
+        // Without Redis single use cannot be enforced
+        assert!(claim_refresh_token(None, &claims).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn refresh_claim_rejects_tokens_without_id_or_expired() {
+        let redis = RedisStore::new(redis::Client::open("redis://127.0.0.1:1").unwrap());
+        for claims in [refresh_claims("", 600), refresh_claims("jti-2", -1)] {
+            assert!(matches!(
+                claim_refresh_token(Some(&redis), &claims).await,
+                Err(AppError::Unauthorized)
+            ));
+        }
+    }
 }
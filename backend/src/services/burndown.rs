@@ -0,0 +1,191 @@
+//! Remediation burn-down for one application or tag: open findings over time
+//! against the SLA target line, for tracking remediation campaigns.
+//!
+//! The target line is the number of findings that would still be open if
+//! every finding seen by then were closed exactly on its SLA due date.
+
+use chrono::{Duration, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::services::finding_trends::{TrendInterval, TrendParams};
+
+/// Query parameters for the burn-down endpoint. Exactly one of
+/// `application_id` and `tag` is required.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BurndownParams {
+    pub application_id: Option<Uuid>,
+    /// Finding tag identifying the campaign.
+    pub tag: Option<String>,
+    /// Spacing of points (defaults to `week`).
+    #[serde(default)]
+    pub interval: TrendInterval,
+    /// First day of the series (defaults to one default window before `to`).
+    pub from: Option<NaiveDate>,
+    /// Last day of the series (defaults to today, UTC).
+    pub to: Option<NaiveDate>,
+}
+
+/// Finding counts at the end of one day of the series.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    /// Findings open at the end of the day.
+    pub open: i64,
+    /// Open findings past their SLA due date.
+    pub overdue: i64,
+    /// Findings that would be open if each were closed on its SLA due date.
+    pub sla_target: i64,
+}
+
+/// Burn-down series for the requested scope.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Burndown {
+    pub application_id: Option<Uuid>,
+    pub tag: Option<String>,
+    pub interval: TrendInterval,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub points: Vec<BurndownPoint>,
+}
+
+/// Build the burn-down series for an application or tag.
+pub async fn get_burndown(pool: &PgPool, params: &BurndownParams) -> Result<Burndown, AppError> {
+    match (params.application_id, params.tag.as_deref()) {
+        (Some(_), None) | (None, Some(_)) => {}
+        _ => {
+            return Err(AppError::Validation(
+                "Exactly one of 'application_id' or 'tag' is required".to_string(),
+            ))
+        }
+    }
+
+    let (from, to) = TrendParams {
+        interval: params.interval,
+        from: params.from,
+        to: params.to,
+    }
+    .window(Utc::now().date_naive())?;
+
+    if let Some(app_id) = params.application_id {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM applications WHERE id = $1)")
+                .bind(app_id)
+                .fetch_one(pool)
+                .await?;
+        if !exists {
+            return Err(AppError::NotFound(format!(
+                "Application {app_id} not found"
+            )));
+        }
+    }
+
+    // Each point is measured at the end of its day. Open findings follow the
+    // executive report rule: first seen by then, and either still open or
+    // left the open states afterwards.
+    let points = sqlx::query_as::<_, BurndownPoint>(
+        r#"
+        SELECT
+            d.date,
+            COUNT(f.id) FILTER (
+                WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+                   OR f.status_changed_at > d.date + 1
+            ) AS open,
+            COUNT(f.id) FILTER (
+                WHERE (f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+                       OR f.status_changed_at > d.date + 1)
+                  AND f.sla_due_date < d.date + 1
+            ) AS overdue,
+            COUNT(f.id) FILTER (
+                WHERE f.status NOT IN ('Invalidated', 'False_Positive')
+                  AND (f.sla_due_date IS NULL OR f.sla_due_date >= d.date + 1)
+            ) AS sla_target
+        FROM UNNEST($1::date[]) AS d(date)
+        LEFT JOIN findings f
+          ON f.first_seen < d.date + 1
+         AND ($2::uuid IS NULL OR f.application_id = $2)
+         AND ($3::text IS NULL OR f.tags @> jsonb_build_array($3::text))
+        GROUP BY d.date
+        ORDER BY d.date
+        "#,
+    )
+    .bind(series_dates(from, to, params.interval))
+    .bind(params.application_id)
+    .bind(params.tag.as_deref())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Burndown {
+        application_id: params.application_id,
+        tag: params.tag.clone(),
+        interval: params.interval,
+        from,
+        to,
+        points,
+    })
+}
+
+/// Dates of the series: `from`, then every `interval`, always ending on `to`.
+fn series_dates(from: NaiveDate, to: NaiveDate, interval: TrendInterval) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    let mut date = from;
+    while date < to {
+        dates.push(date);
+        let next = match interval {
+            TrendInterval::Day => date.checked_add_signed(Duration::days(1)),
+            TrendInterval::Week => date.checked_add_signed(Duration::weeks(1)),
+            TrendInterval::Month => date.checked_add_months(Months::new(1)),
+        };
+        match next {
+            Some(next) => date = next,
+            None => break,
+        }
+    }
+    dates.push(to);
+    dates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn series_steps_by_interval_and_ends_on_to() {
+        assert_eq!(
+            series_dates(date(2026, 3, 1), date(2026, 3, 3), TrendInterval::Day),
+            [date(2026, 3, 1), date(2026, 3, 2), date(2026, 3, 3)]
+        );
+        assert_eq!(
+            series_dates(date(2026, 3, 1), date(2026, 3, 20), TrendInterval::Week),
+            [
+                date(2026, 3, 1),
+                date(2026, 3, 8),
+                date(2026, 3, 15),
+                date(2026, 3, 20)
+            ]
+        );
+        assert_eq!(
+            series_dates(date(2026, 1, 31), date(2026, 3, 31), TrendInterval::Month),
+            [
+                date(2026, 1, 31),
+                date(2026, 2, 28),
+                date(2026, 3, 28),
+                date(2026, 3, 31)
+            ]
+        );
+    }
+
+    #[test]
+    fn single_day_window_has_one_point() {
+        let day = date(2026, 6, 1);
+        assert_eq!(series_dates(day, day, TrendInterval::Week), [day]);
+    }
+}
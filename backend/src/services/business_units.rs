@@ -0,0 +1,261 @@
+//! Business-unit rollup: open findings, risk, SLA compliance, and scanner
+//! coverage per business unit or SSA code, so office owners can be compared.
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::errors::AppError;
+use crate::services::dashboard::{SeverityCounts, SlaSummary};
+
+/// Group label for applications without a business unit or SSA code.
+const UNASSIGNED: &str = "Unassigned";
+
+/// Application attribute the rollup groups by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupGroup {
+    #[default]
+    BusinessUnit,
+    SsaCode,
+}
+
+impl RollupGroup {
+    fn column(self) -> &'static str {
+        match self {
+            Self::BusinessUnit => "business_unit",
+            Self::SsaCode => "ssa_code",
+        }
+    }
+}
+
+/// Query parameters for the business-unit rollup.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RollupParams {
+    /// Defaults to `business_unit`.
+    #[serde(default)]
+    pub group_by: RollupGroup,
+}
+
+/// Applications with at least one finding from each testing category.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryCoverage {
+    pub sast: i64,
+    pub sca: i64,
+    pub dast: i64,
+}
+
+/// Aggregates for one business unit or SSA code.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BusinessUnitRollup {
+    /// Business unit or SSA code; `Unassigned` when the application has none.
+    pub key: String,
+    /// Distinct effective office owners of the group's applications.
+    pub office_owners: Vec<String>,
+    pub applications: i64,
+    pub open_findings: i64,
+    /// Open findings by normalized severity.
+    pub severity_counts: SeverityCounts,
+    /// Summed composite risk of open findings.
+    pub total_risk_score: f64,
+    /// Mean composite risk per application.
+    pub avg_app_risk_score: f64,
+    /// SLA status of open findings.
+    pub sla: SlaSummary,
+    /// Share of open findings with an SLA that are not breached, in percent.
+    pub sla_compliance_pct: Option<f64>,
+    pub coverage: CategoryCoverage,
+}
+
+/// Rollup for every group, riskiest first.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BusinessUnitReport {
+    pub group_by: RollupGroup,
+    pub groups: Vec<BusinessUnitRollup>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct RollupRow {
+    key: Option<String>,
+    office_owners: Vec<String>,
+    applications: i64,
+    open_findings: i64,
+    critical: i64,
+    high: i64,
+    medium: i64,
+    low: i64,
+    info: i64,
+    total_risk_score: f64,
+    on_track: i64,
+    at_risk: i64,
+    breached: i64,
+    sast_apps: i64,
+    sca_apps: i64,
+    dast_apps: i64,
+}
+
+/// Aggregate applications and their open findings per group.
+pub async fn get_rollup(
+    pool: &PgPool,
+    params: &RollupParams,
+) -> Result<BusinessUnitReport, AppError> {
+    // The grouping column comes from a fixed enum, never from user input.
+    let query = format!(
+        r#"
+        WITH app_stats AS (
+            SELECT
+                a.id,
+                a.{column} AS key,
+                a.effective_office_owner,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')) AS open_findings,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.effective_severity = 'Critical') AS critical,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.effective_severity = 'High') AS high,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.effective_severity = 'Medium') AS medium,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.effective_severity = 'Low') AS low,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.effective_severity = 'Info') AS info,
+                COALESCE(SUM(f.composite_risk_score::double precision) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')), 0) AS risk_score,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.sla_status = 'On_Track') AS on_track,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.sla_status = 'At_Risk') AS at_risk,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.sla_status = 'Breached') AS breached,
+                COALESCE(BOOL_OR(f.finding_category = 'SAST'), false) AS has_sast,
+                COALESCE(BOOL_OR(f.finding_category = 'SCA'), false) AS has_sca,
+                COALESCE(BOOL_OR(f.finding_category = 'DAST'), false) AS has_dast
+            FROM applications a
+            LEFT JOIN findings f ON f.application_id = a.id
+            GROUP BY a.id
+        )
+        SELECT
+            key,
+            ARRAY_REMOVE(ARRAY_AGG(DISTINCT effective_office_owner), NULL) AS office_owners,
+            COUNT(*) AS applications,
+            SUM(open_findings)::bigint AS open_findings,
+            SUM(critical)::bigint AS critical,
+            SUM(high)::bigint AS high,
+            SUM(medium)::bigint AS medium,
+            SUM(low)::bigint AS low,
+            SUM(info)::bigint AS info,
+            SUM(risk_score) AS total_risk_score,
+            SUM(on_track)::bigint AS on_track,
+            SUM(at_risk)::bigint AS at_risk,
+            SUM(breached)::bigint AS breached,
+            COUNT(*) FILTER (WHERE has_sast) AS sast_apps,
+            COUNT(*) FILTER (WHERE has_sca) AS sca_apps,
+            COUNT(*) FILTER (WHERE has_dast) AS dast_apps
+        FROM app_stats
+        GROUP BY key
+        ORDER BY total_risk_score DESC, open_findings DESC, key
+        "#,
+        column = params.group_by.column(),
+    );
+
+    let rows = sqlx::query_as::<_, RollupRow>(&query)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(BusinessUnitReport {
+        group_by: params.group_by,
+        groups: rows.into_iter().map(BusinessUnitRollup::from).collect(),
+    })
+}
+
+impl From<RollupRow> for BusinessUnitRollup {
+    fn from(row: RollupRow) -> Self {
+        let avg_app_risk_score = if row.applications > 0 {
+            row.total_risk_score / row.applications as f64
+        } else {
+            0.0
+        };
+        Self {
+            key: row.key.unwrap_or_else(|| UNASSIGNED.to_string()),
+            office_owners: row.office_owners,
+            applications: row.applications,
+            open_findings: row.open_findings,
+            severity_counts: SeverityCounts {
+                critical: row.critical,
+                high: row.high,
+                medium: row.medium,
+                low: row.low,
+                info: row.info,
+            },
+            total_risk_score: row.total_risk_score,
+            avg_app_risk_score,
+            sla_compliance_pct: sla_compliance(row.on_track, row.at_risk, row.breached),
+            sla: SlaSummary {
+                on_track: row.on_track,
+                at_risk: row.at_risk,
+                breached: row.breached,
+            },
+            coverage: CategoryCoverage {
+                sast: row.sast_apps,
+                sca: row.sca_apps,
+                dast: row.dast_apps,
+            },
+        }
+    }
+}
+
+/// Percentage of SLA-tracked findings that have not breached, or `None` when
+/// no open finding carries an SLA.
+fn sla_compliance(on_track: i64, at_risk: i64, breached: i64) -> Option<f64> {
+    let tracked = on_track + at_risk + breached;
+    if tracked == 0 {
+        return None;
+    }
+    Some((on_track + at_risk) as f64 * 100.0 / tracked as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(key: Option<&str>) -> RollupRow {
+        RollupRow {
+            key: key.map(str::to_string),
+            office_owners: vec!["Jane Roe".to_string()],
+            applications: 4,
+            open_findings: 10,
+            critical: 1,
+            high: 2,
+            medium: 3,
+            low: 4,
+            info: 0,
+            total_risk_score: 30.0,
+            on_track: 6,
+            at_risk: 1,
+            breached: 1,
+            sast_apps: 4,
+            sca_apps: 2,
+            dast_apps: 0,
+        }
+    }
+
+    #[test]
+    fn sla_compliance_excludes_breached_findings() {
+        assert_eq!(sla_compliance(3, 1, 0), Some(100.0));
+        assert_eq!(sla_compliance(1, 0, 3), Some(25.0));
+        assert_eq!(sla_compliance(0, 0, 0), None);
+    }
+
+    #[test]
+    fn rollup_from_row() {
+        let rollup = BusinessUnitRollup::from(row(Some("Retail")));
+        assert_eq!(rollup.key, "Retail");
+        assert_eq!(rollup.avg_app_risk_score, 7.5);
+        assert_eq!(rollup.sla_compliance_pct, Some(87.5));
+        assert_eq!(rollup.severity_counts.medium, 3);
+        assert_eq!(rollup.coverage.sca, 2);
+    }
+
+    #[test]
+    fn missing_key_is_unassigned() {
+        assert_eq!(BusinessUnitRollup::from(row(None)).key, UNASSIGNED);
+    }
+
+    #[test]
+    fn group_by_accepts_snake_case() {
+        let params: RollupParams = serde_json::from_str(r#"{"group_by":"ssa_code"}"#).unwrap();
+        assert_eq!(params.group_by, RollupGroup::SsaCode);
+        assert_eq!(params.group_by.column(), "ssa_code");
+    }
+}
@@ -0,0 +1,584 @@
+//! Configuration bundle export and import.
+//!
+//! Export collects applications, correlation rules, app code patterns, triage
+//! rules, and `system_config` settings (SLA matrix, tier mapping, risk
+//! weights) into one JSON document. Import validates the whole bundle first,
+//! then upserts every entity by natural key in a single transaction, so a
+//! bundle is applied completely or not at all. Entities missing from the
+//! bundle are left untouched.
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::errors::{AppError, FieldError};
+use crate::models::config_bundle::{
+    BundleAppCodePattern, BundleApplication, BundleCorrelationRule, BundleImportResult,
+    BundleSetting, BundleTriageRule, ConfigBundle, ImportCounts, BUNDLE_FORMAT_VERSION,
+};
+use crate::services::config_cache;
+
+/// Export the current configuration.
+pub async fn export(pool: &PgPool) -> Result<ConfigBundle, AppError> {
+    let applications = sqlx::query_as::<_, BundleApplication>(
+        r#"
+        SELECT app_name, app_code, description, criticality, tier, business_unit,
+               business_owner, technical_owner, security_champion, technology_stack,
+               deployment_environment, exposure, data_classification, regulatory_scope,
+               repository_urls, scanner_project_ids, status, is_verified, ssa_code,
+               ssa_name, functional_reference_email, technical_reference_email,
+               effective_office_owner, effective_office_name, confidentiality_level,
+               integrity_level, availability_level, is_dora_fei, is_gdpr_subject,
+               has_pci_data, is_psd2_relevant, apm_metadata
+        FROM applications
+        ORDER BY app_code
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let correlation_rules = sqlx::query_as::<_, BundleCorrelationRule>(
+        r#"
+        SELECT name, description, rule_type, conditions, confidence, is_active, priority
+        FROM correlation_rules
+        ORDER BY priority DESC, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let app_code_patterns = sqlx::query_as::<_, BundleAppCodePattern>(
+        r#"
+        SELECT source_tool, field_name, regex_pattern, priority, description, is_active
+        FROM app_code_patterns
+        ORDER BY source_tool, priority DESC, field_name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let triage_rules = sqlx::query_as::<_, BundleTriageRule>(
+        r#"
+        SELECT name, description, conditions, is_active, priority
+        FROM triage_rules
+        ORDER BY priority DESC, name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let settings = sqlx::query_as::<_, BundleSetting>(
+        "SELECT key, value, description FROM system_config ORDER BY key",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ConfigBundle {
+        format_version: BUNDLE_FORMAT_VERSION,
+        exported_at: Utc::now(),
+        applications,
+        correlation_rules,
+        app_code_patterns,
+        triage_rules,
+        settings,
+    })
+}
+
+/// Record an error when `value` is empty or longer than `max` characters.
+fn check_length(errors: &mut Vec<FieldError>, field: String, value: &str, max: usize) {
+    let len = value.trim().chars().count();
+    if len == 0 || len > max {
+        errors.push(FieldError {
+            field,
+            message: format!("must be 1 to {max} characters"),
+        });
+    }
+}
+
+/// Record an error when `key` was already seen in the same list.
+fn check_unique(
+    errors: &mut Vec<FieldError>,
+    seen: &mut HashSet<String>,
+    field: String,
+    key: String,
+) {
+    if !seen.insert(key) {
+        errors.push(FieldError {
+            field,
+            message: "duplicates an earlier entry".to_string(),
+        });
+    }
+}
+
+/// Every problem that would make the import fail or ambiguous.
+fn validate(bundle: &ConfigBundle) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if bundle.format_version != BUNDLE_FORMAT_VERSION {
+        errors.push(FieldError {
+            field: "format_version".to_string(),
+            message: format!("unsupported version; expected {BUNDLE_FORMAT_VERSION}"),
+        });
+    }
+
+    let mut seen = HashSet::new();
+    for (i, app) in bundle.applications.iter().enumerate() {
+        check_length(
+            &mut errors,
+            format!("applications[{i}].app_name"),
+            &app.app_name,
+            255,
+        );
+        check_length(
+            &mut errors,
+            format!("applications[{i}].app_code"),
+            &app.app_code,
+            10,
+        );
+        check_unique(
+            &mut errors,
+            &mut seen,
+            format!("applications[{i}].app_code"),
+            app.app_code.clone(),
+        );
+    }
+
+    let mut seen = HashSet::new();
+    for (i, rule) in bundle.correlation_rules.iter().enumerate() {
+        check_length(
+            &mut errors,
+            format!("correlation_rules[{i}].name"),
+            &rule.name,
+            255,
+        );
+        check_length(
+            &mut errors,
+            format!("correlation_rules[{i}].rule_type"),
+            &rule.rule_type,
+            50,
+        );
+        check_unique(
+            &mut errors,
+            &mut seen,
+            format!("correlation_rules[{i}].name"),
+            rule.name.clone(),
+        );
+    }
+
+    let mut seen = HashSet::new();
+    for (i, pattern) in bundle.app_code_patterns.iter().enumerate() {
+        check_length(
+            &mut errors,
+            format!("app_code_patterns[{i}].source_tool"),
+            &pattern.source_tool,
+            100,
+        );
+        check_length(
+            &mut errors,
+            format!("app_code_patterns[{i}].field_name"),
+            &pattern.field_name,
+            100,
+        );
+        if let Err(e) = regex::Regex::new(&pattern.regex_pattern) {
+            errors.push(FieldError {
+                field: format!("app_code_patterns[{i}].regex_pattern"),
+                message: format!("invalid regex: {e}"),
+            });
+        }
+        check_unique(
+            &mut errors,
+            &mut seen,
+            format!("app_code_patterns[{i}]"),
+            format!(
+                "{}\u{0}{}\u{0}{}",
+                pattern.source_tool, pattern.field_name, pattern.regex_pattern
+            ),
+        );
+    }
+
+    let mut seen = HashSet::new();
+    for (i, rule) in bundle.triage_rules.iter().enumerate() {
+        check_length(
+            &mut errors,
+            format!("triage_rules[{i}].name"),
+            &rule.name,
+            255,
+        );
+        check_unique(
+            &mut errors,
+            &mut seen,
+            format!("triage_rules[{i}].name"),
+            rule.name.clone(),
+        );
+    }
+
+    let mut seen = HashSet::new();
+    for (i, setting) in bundle.settings.iter().enumerate() {
+        check_length(&mut errors, format!("settings[{i}].key"), &setting.key, 255);
+        check_unique(
+            &mut errors,
+            &mut seen,
+            format!("settings[{i}].key"),
+            setting.key.clone(),
+        );
+    }
+    errors
+}
+
+fn count(counts: &mut ImportCounts, created: bool) {
+    if created {
+        counts.created += 1;
+    } else {
+        counts.updated += 1;
+    }
+}
+
+async fn upsert_application(
+    tx: &mut Transaction<'_, Postgres>,
+    app: &BundleApplication,
+) -> Result<bool, AppError> {
+    let created = sqlx::query_scalar::<_, bool>(
+        r#"
+        INSERT INTO applications (
+            app_name, app_code, description, criticality, tier, business_unit,
+            business_owner, technical_owner, security_champion, technology_stack,
+            deployment_environment, exposure, data_classification, regulatory_scope,
+            repository_urls, scanner_project_ids, status, is_verified, ssa_code,
+            ssa_name, functional_reference_email, technical_reference_email,
+            effective_office_owner, effective_office_name, confidentiality_level,
+            integrity_level, availability_level, is_dora_fei, is_gdpr_subject,
+            has_pci_data, is_psd2_relevant, apm_metadata
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17,
+            $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32
+        )
+        ON CONFLICT (app_code) DO UPDATE SET
+            app_name = EXCLUDED.app_name,
+            description = EXCLUDED.description,
+            criticality = EXCLUDED.criticality,
+            tier = EXCLUDED.tier,
+            business_unit = EXCLUDED.business_unit,
+            business_owner = EXCLUDED.business_owner,
+            technical_owner = EXCLUDED.technical_owner,
+            security_champion = EXCLUDED.security_champion,
+            technology_stack = EXCLUDED.technology_stack,
+            deployment_environment = EXCLUDED.deployment_environment,
+            exposure = EXCLUDED.exposure,
+            data_classification = EXCLUDED.data_classification,
+            regulatory_scope = EXCLUDED.regulatory_scope,
+            repository_urls = EXCLUDED.repository_urls,
+            scanner_project_ids = EXCLUDED.scanner_project_ids,
+            status = EXCLUDED.status,
+            is_verified = EXCLUDED.is_verified,
+            ssa_code = EXCLUDED.ssa_code,
+            ssa_name = EXCLUDED.ssa_name,
+            functional_reference_email = EXCLUDED.functional_reference_email,
+            technical_reference_email = EXCLUDED.technical_reference_email,
+            effective_office_owner = EXCLUDED.effective_office_owner,
+            effective_office_name = EXCLUDED.effective_office_name,
+            confidentiality_level = EXCLUDED.confidentiality_level,
+            integrity_level = EXCLUDED.integrity_level,
+            availability_level = EXCLUDED.availability_level,
+            is_dora_fei = EXCLUDED.is_dora_fei,
+            is_gdpr_subject = EXCLUDED.is_gdpr_subject,
+            has_pci_data = EXCLUDED.has_pci_data,
+            is_psd2_relevant = EXCLUDED.is_psd2_relevant,
+            apm_metadata = EXCLUDED.apm_metadata
+        RETURNING (xmax = 0) AS created
+        "#,
+    )
+    .bind(&app.app_name)
+    .bind(&app.app_code)
+    .bind(&app.description)
+    .bind(&app.criticality)
+    .bind(&app.tier)
+    .bind(&app.business_unit)
+    .bind(&app.business_owner)
+    .bind(&app.technical_owner)
+    .bind(&app.security_champion)
+    .bind(&app.technology_stack)
+    .bind(&app.deployment_environment)
+    .bind(&app.exposure)
+    .bind(&app.data_classification)
+    .bind(&app.regulatory_scope)
+    .bind(&app.repository_urls)
+    .bind(&app.scanner_project_ids)
+    .bind(&app.status)
+    .bind(app.is_verified)
+    .bind(&app.ssa_code)
+    .bind(&app.ssa_name)
+    .bind(&app.functional_reference_email)
+    .bind(&app.technical_reference_email)
+    .bind(&app.effective_office_owner)
+    .bind(&app.effective_office_name)
+    .bind(&app.confidentiality_level)
+    .bind(&app.integrity_level)
+    .bind(&app.availability_level)
+    .bind(app.is_dora_fei)
+    .bind(app.is_gdpr_subject)
+    .bind(app.has_pci_data)
+    .bind(app.is_psd2_relevant)
+    .bind(&app.apm_metadata)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(created)
+}
+
+async fn upsert_correlation_rule(
+    tx: &mut Transaction<'_, Postgres>,
+    rule: &BundleCorrelationRule,
+) -> Result<bool, AppError> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE correlation_rules
+        SET description = $2, rule_type = $3, conditions = $4, confidence = $5,
+            is_active = $6, priority = $7
+        WHERE name = $1
+        "#,
+    )
+    .bind(&rule.name)
+    .bind(&rule.description)
+    .bind(&rule.rule_type)
+    .bind(&rule.conditions)
+    .bind(&rule.confidence)
+    .bind(rule.is_active)
+    .bind(rule.priority)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+    if updated > 0 {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO correlation_rules
+            (name, description, rule_type, conditions, confidence, is_active, priority)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(&rule.name)
+    .bind(&rule.description)
+    .bind(&rule.rule_type)
+    .bind(&rule.conditions)
+    .bind(&rule.confidence)
+    .bind(rule.is_active)
+    .bind(rule.priority)
+    .execute(&mut **tx)
+    .await?;
+    Ok(true)
+}
+
+async fn upsert_app_code_pattern(
+    tx: &mut Transaction<'_, Postgres>,
+    pattern: &BundleAppCodePattern,
+) -> Result<bool, AppError> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE app_code_patterns
+        SET priority = $4, description = $5, is_active = $6
+        WHERE source_tool = $1 AND field_name = $2 AND regex_pattern = $3
+        "#,
+    )
+    .bind(&pattern.source_tool)
+    .bind(&pattern.field_name)
+    .bind(&pattern.regex_pattern)
+    .bind(pattern.priority)
+    .bind(&pattern.description)
+    .bind(pattern.is_active)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+    if updated > 0 {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO app_code_patterns
+            (source_tool, field_name, regex_pattern, priority, description, is_active)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(&pattern.source_tool)
+    .bind(&pattern.field_name)
+    .bind(&pattern.regex_pattern)
+    .bind(pattern.priority)
+    .bind(&pattern.description)
+    .bind(pattern.is_active)
+    .execute(&mut **tx)
+    .await?;
+    Ok(true)
+}
+
+async fn upsert_triage_rule(
+    tx: &mut Transaction<'_, Postgres>,
+    rule: &BundleTriageRule,
+    actor_id: Uuid,
+) -> Result<bool, AppError> {
+    let updated = sqlx::query(
+        r#"
+        UPDATE triage_rules
+        SET description = $2, conditions = $3, is_active = $4, priority = $5, updated_at = NOW()
+        WHERE name = $1
+        "#,
+    )
+    .bind(&rule.name)
+    .bind(&rule.description)
+    .bind(&rule.conditions)
+    .bind(rule.is_active)
+    .bind(rule.priority)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+    if updated > 0 {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO triage_rules (name, description, conditions, is_active, priority, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(&rule.name)
+    .bind(&rule.description)
+    .bind(&rule.conditions)
+    .bind(rule.is_active)
+    .bind(rule.priority)
+    .bind(actor_id)
+    .execute(&mut **tx)
+    .await?;
+    Ok(true)
+}
+
+async fn upsert_setting(
+    tx: &mut Transaction<'_, Postgres>,
+    setting: &BundleSetting,
+    actor_id: Uuid,
+) -> Result<bool, AppError> {
+    let created = sqlx::query_scalar::<_, bool>(
+        r#"
+        INSERT INTO system_config (key, value, description, updated_by)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (key) DO UPDATE SET
+            value = EXCLUDED.value,
+            description = COALESCE(EXCLUDED.description, system_config.description),
+            updated_by = EXCLUDED.updated_by,
+            updated_at = NOW()
+        RETURNING (xmax = 0) AS created
+        "#,
+    )
+    .bind(&setting.key)
+    .bind(&setting.value)
+    .bind(&setting.description)
+    .bind(actor_id)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(created)
+}
+
+/// Validate and apply a bundle atomically.
+pub async fn import(
+    pool: &PgPool,
+    bundle: &ConfigBundle,
+    actor_id: Uuid,
+) -> Result<BundleImportResult, AppError> {
+    let errors = validate(bundle);
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    let mut result = BundleImportResult::default();
+    let mut tx = pool.begin().await?;
+    for app in &bundle.applications {
+        let created = upsert_application(&mut tx, app).await?;
+        count(&mut result.applications, created);
+    }
+    for rule in &bundle.correlation_rules {
+        let created = upsert_correlation_rule(&mut tx, rule).await?;
+        count(&mut result.correlation_rules, created);
+    }
+    for pattern in &bundle.app_code_patterns {
+        let created = upsert_app_code_pattern(&mut tx, pattern).await?;
+        count(&mut result.app_code_patterns, created);
+    }
+    for rule in &bundle.triage_rules {
+        let created = upsert_triage_rule(&mut tx, rule, actor_id).await?;
+        count(&mut result.triage_rules, created);
+    }
+    for setting in &bundle.settings {
+        let created = upsert_setting(&mut tx, setting, actor_id).await?;
+        count(&mut result.settings, created);
+    }
+    tx.commit().await?;
+
+    let cache = config_cache::global();
+    cache.invalidate_patterns();
+    cache.invalidate_settings().await;
+
+    tracing::info!(
+        applications = bundle.applications.len(),
+        correlation_rules = bundle.correlation_rules.len(),
+        app_code_patterns = bundle.app_code_patterns.len(),
+        triage_rules = bundle.triage_rules.len(),
+        settings = bundle.settings.len(),
+        "Imported configuration bundle"
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> ConfigBundle {
+        serde_json::from_value(serde_json::json!({
+            "format_version": 1,
+            "exported_at": "2026-01-01T00:00:00Z",
+            "correlation_rules": [
+                {"name": "CR-1", "description": null, "rule_type": "cross_tool",
+                 "conditions": {}, "confidence": "High", "is_active": true, "priority": 1}
+            ],
+            "app_code_patterns": [
+                {"source_tool": "SonarQube", "field_name": "project", "regex_pattern": "^([A-Z0-9]{5})",
+                 "priority": 10, "description": null, "is_active": true}
+            ],
+            "settings": [
+                {"key": "sla_matrix", "value": {"P1": {"Tier_1": 72}}, "description": null}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn partial_bundles_are_valid() {
+        let bundle = bundle();
+        assert!(bundle.applications.is_empty());
+        assert!(validate(&bundle).is_empty());
+    }
+
+    #[test]
+    fn validation_reports_every_problem() {
+        let mut bundle = bundle();
+        bundle.format_version = 2;
+        bundle
+            .correlation_rules
+            .push(bundle.correlation_rules[0].clone());
+        bundle.app_code_patterns[0].regex_pattern = "([A-Z".to_string();
+        bundle.settings[0].key = " ".to_string();
+
+        let fields: Vec<String> = validate(&bundle).into_iter().map(|e| e.field).collect();
+        assert_eq!(
+            fields,
+            [
+                "format_version",
+                "correlation_rules[1].name",
+                "app_code_patterns[0].regex_pattern",
+                "settings[0].key",
+            ]
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! In-process cache for configuration read on every ingested finding.
+//!
+//! App code patterns (per source tool), the asset map, DNS mappings, and
+//! `system_config` settings change rarely but are consulted once per finding, so they are
+//! served from a process-wide cache with a short TTL. Code that changes them
+//! should call the matching `invalidate_*` method so this process picks up
+//! the change immediately. With Redis available the invalidation is also
+//! announced to the other backend instances (see
+//! [`spawn_invalidation_listener`]); otherwise they see it once the TTL
+//! expires.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use moka::future::Cache;
+use sqlx::PgPool;
+
+use crate::errors::AppError;
+use crate::services::app_code_resolver::PatternEntry;
+use crate::services::asset::{self, AssetMap};
+use crate::services::cross_dedup::DedupHeuristics;
+use crate::services::dns_mapping::{self, DnsMappingSet};
+use crate::services::fingerprint::FingerprintProfiles;
+use crate::services::redis_store::RedisStore;
+use crate::services::risk_score::{RiskWeights, SlaMatrix};
+use crate::services::similarity::SimilaritySettings;
+
+/// TTL used when [`configure`] was not called (e.g. in the seed binary).
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// Source tools with cached pattern lists.
+const MAX_CACHED_TOOLS: u64 = 64;
+
+/// Redis channel carrying invalidations between instances.
+const INVALIDATION_CHANNEL: &str = "synapsec:config-cache";
+
+/// Wait before resubscribing after the invalidation channel drops.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+static CACHE: OnceLock<ConfigCache> = OnceLock::new();
+
+/// Where invalidations are announced; unset without Redis.
+static ANNOUNCER: OnceLock<RedisStore> = OnceLock::new();
+
+/// Set the TTL of the process-wide cache. Call once at startup, before
+/// the first [`global`] access; later calls are ignored.
+pub fn configure(ttl: Duration) {
+    let _ = CACHE.set(ConfigCache::new(ttl));
+}
+
+/// The process-wide configuration cache.
+pub fn global() -> &'static ConfigCache {
+    CACHE.get_or_init(|| ConfigCache::new(DEFAULT_TTL))
+}
+
+/// Part of the cache dropped by an invalidation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Patterns,
+    Assets,
+    DnsMappings,
+    Settings,
+}
+
+impl Scope {
+    const ALL: [Scope; 4] = [
+        Self::Patterns,
+        Self::Assets,
+        Self::DnsMappings,
+        Self::Settings,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Patterns => "patterns",
+            Self::Assets => "assets",
+            Self::DnsMappings => "dns_mappings",
+            Self::Settings => "settings",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scope| scope.name() == name)
+    }
+}
+
+/// Tell the other instances to drop `scope`. Fire and forget: a lost
+/// announcement only delays the change until the TTL expires.
+fn announce(scope: Scope) {
+    if let Some(redis) = ANNOUNCER.get() {
+        let redis = redis.clone();
+        tokio::spawn(async move {
+            redis.publish(INVALIDATION_CHANNEL, scope.name()).await;
+        });
+    }
+}
+
+/// Announce this process's invalidations through Redis and apply those of
+/// the other instances. The subscription is re-established when Redis drops
+/// it; since announcements may have been missed meanwhile, the whole cache
+/// is dropped on every (re)subscribe.
+pub fn spawn_invalidation_listener(redis: RedisStore) {
+    let _ = ANNOUNCER.set(redis.clone());
+    tokio::spawn(async move {
+        loop {
+            match listen(&redis).await {
+                Ok(()) => tracing::warn!("Config cache invalidation channel closed"),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Config cache invalidation channel unavailable")
+                }
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    });
+}
+
+async fn listen(redis: &RedisStore) -> redis::RedisResult<()> {
+    let mut pubsub = redis.client().get_async_pubsub().await?;
+    pubsub.subscribe(INVALIDATION_CHANNEL).await?;
+    for scope in Scope::ALL {
+        global().drop_scope(scope).await;
+    }
+
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let payload: String = message.get_payload()?;
+        match Scope::parse(&payload) {
+            Some(scope) => global().drop_scope(scope).await,
+            None => tracing::warn!(payload, "Ignoring unknown config cache invalidation"),
+        }
+    }
+    Ok(())
+}
+
+/// `system_config` rows keyed by `key`.
+#[derive(Debug, Clone, Default)]
+pub struct SystemSettings {
+    values: HashMap<String, serde_json::Value>,
+}
+
+impl SystemSettings {
+    pub fn from_rows(rows: impl IntoIterator<Item = (String, serde_json::Value)>) -> Self {
+        Self {
+            values: rows.into_iter().collect(),
+        }
+    }
+
+    /// Raw value of a setting, if present.
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.values.get(key)
+    }
+
+    /// Consecutive scans a finding must be missing from before it is eligible
+    /// for auto-close (`auto_close_missed_scans`). Defaults to 3 when unset or
+    /// not a positive integer.
+    pub fn auto_close_missed_scans(&self) -> u32 {
+        self.get("auto_close_missed_scans")
+            .and_then(|v| v.as_u64())
+            .filter(|&n| n > 0)
+            .and_then(|n| u32::try_from(n).ok())
+            .unwrap_or(3)
+    }
+
+    /// Whether ingested findings are auto-confirmed (`auto_confirm_enabled`).
+    /// Defaults to `true` when unset or not a boolean.
+    pub fn auto_confirm_enabled(&self) -> bool {
+        self.get("auto_confirm_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true)
+    }
+
+    /// Cross-tool dedup heuristics (`cross_dedup_heuristics`), falling back to
+    /// the defaults when unset or malformed.
+    pub fn cross_dedup_heuristics(&self) -> DedupHeuristics {
+        self.get("cross_dedup_heuristics")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Fingerprint components per source tool (`fingerprint_components`);
+    /// empty when unset or malformed.
+    pub fn fingerprint_profiles(&self) -> FingerprintProfiles {
+        self.get("fingerprint_components")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Risk score factor weights (`risk_score_weights`), falling back to the
+    /// defaults when unset or malformed.
+    pub fn risk_weights(&self) -> RiskWeights {
+        self.get("risk_score_weights")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Remediation SLA hours by priority and tier (`sla_matrix`); empty, so
+    /// no finding gets an SLA, when unset or malformed.
+    pub fn sla_matrix(&self) -> SlaMatrix {
+        self.get("sla_matrix")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Near-duplicate scoring (`finding_similarity`), falling back to the
+    /// defaults when unset or malformed.
+    pub fn similarity_settings(&self) -> SimilaritySettings {
+        self.get("finding_similarity")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Cached app code patterns, asset map, DNS mappings, and system settings.
+///
+/// Cheap to clone; all clones share the same entries.
+#[derive(Clone)]
+pub struct ConfigCache {
+    patterns: Cache<String, Arc<Vec<PatternEntry>>>,
+    assets: Cache<(), Arc<AssetMap>>,
+    dns_mappings: Cache<(), Arc<DnsMappingSet>>,
+    settings: Cache<(), Arc<SystemSettings>>,
+}
+
+impl std::fmt::Debug for ConfigCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigCache")
+            .field("pattern_tools", &self.patterns.entry_count())
+            .field("assets_cached", &self.assets.contains_key(&()))
+            .field("dns_mappings_cached", &self.dns_mappings.contains_key(&()))
+            .field("settings_cached", &self.settings.contains_key(&()))
+            .finish()
+    }
+}
+
+impl ConfigCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            patterns: Cache::builder()
+                .max_capacity(MAX_CACHED_TOOLS)
+                .time_to_live(ttl)
+                .build(),
+            assets: Cache::builder().max_capacity(1).time_to_live(ttl).build(),
+            dns_mappings: Cache::builder().max_capacity(1).time_to_live(ttl).build(),
+            settings: Cache::builder().max_capacity(1).time_to_live(ttl).build(),
+        }
+    }
+
+    /// Active app code patterns for a source tool, highest priority first.
+    pub async fn app_code_patterns(
+        &self,
+        pool: &PgPool,
+        source_tool: &str,
+    ) -> Result<Arc<Vec<PatternEntry>>, AppError> {
+        self.patterns
+            .try_get_with(source_tool.to_string(), async {
+                fetch_patterns(pool, source_tool).await.map(Arc::new)
+            })
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to load app code patterns: {e}")))
+    }
+
+    /// Assets linked to applications, indexed for app code lookups.
+    pub async fn asset_map(&self, pool: &PgPool) -> Result<Arc<AssetMap>, AppError> {
+        self.assets
+            .try_get_with((), async { asset::load_map(pool).await.map(Arc::new) })
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to load asset map: {e}")))
+    }
+
+    /// DNS name and URL prefix mappings, indexed for app code lookups.
+    pub async fn dns_mappings(&self, pool: &PgPool) -> Result<Arc<DnsMappingSet>, AppError> {
+        self.dns_mappings
+            .try_get_with((), async {
+                dns_mapping::load_set(pool).await.map(Arc::new)
+            })
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to load DNS mappings: {e}")))
+    }
+
+    /// All `system_config` settings.
+    pub async fn settings(&self, pool: &PgPool) -> Result<Arc<SystemSettings>, AppError> {
+        self.settings
+            .try_get_with((), async { fetch_settings(pool).await.map(Arc::new) })
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to load system settings: {e}")))
+    }
+
+    /// Drop cached patterns after app code patterns change.
+    pub fn invalidate_patterns(&self) {
+        self.patterns.invalidate_all();
+        announce(Scope::Patterns);
+    }
+
+    /// Drop the cached asset map after assets or their links change.
+    pub async fn invalidate_assets(&self) {
+        self.drop_scope(Scope::Assets).await;
+        announce(Scope::Assets);
+    }
+
+    /// Drop the cached DNS mappings after they change.
+    pub async fn invalidate_dns_mappings(&self) {
+        self.drop_scope(Scope::DnsMappings).await;
+        announce(Scope::DnsMappings);
+    }
+
+    /// Drop cached settings after `system_config` changes.
+    pub async fn invalidate_settings(&self) {
+        self.drop_scope(Scope::Settings).await;
+        announce(Scope::Settings);
+    }
+
+    /// Drop one part of this process's cache without announcing it.
+    async fn drop_scope(&self, scope: Scope) {
+        match scope {
+            Scope::Patterns => self.patterns.invalidate_all(),
+            Scope::Assets => self.assets.invalidate(&()).await,
+            Scope::DnsMappings => self.dns_mappings.invalidate(&()).await,
+            Scope::Settings => self.settings.invalidate(&()).await,
+        }
+    }
+}
+
+async fn fetch_patterns(pool: &PgPool, source_tool: &str) -> Result<Vec<PatternEntry>, AppError> {
+    let rows = sqlx::query_as::<_, (String, String, i32)>(
+        r#"
+        SELECT field_name, regex_pattern, priority
+        FROM app_code_patterns
+        WHERE source_tool = $1 AND is_active = true
+        ORDER BY priority DESC
+        "#,
+    )
+    .bind(source_tool)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(field_name, regex_pattern, priority)| PatternEntry {
+            field_name,
+            regex_pattern,
+            priority,
+        })
+        .collect())
+}
+
+async fn fetch_settings(pool: &PgPool) -> Result<SystemSettings, AppError> {
+    let rows =
+        sqlx::query_as::<_, (String, serde_json::Value)>("SELECT key, value FROM system_config")
+            .fetch_all(pool)
+            .await?;
+    Ok(SystemSettings::from_rows(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn invalidation_scopes_round_trip_through_their_names() {
+        for scope in Scope::ALL {
+            assert_eq!(Scope::parse(scope.name()), Some(scope));
+        }
+        assert_eq!(Scope::parse("everything"), None);
+    }
+
+    #[test]
+    fn settings_fall_back_to_defaults() {
+        let settings = SystemSettings::default();
+        assert_eq!(settings.auto_close_missed_scans(), 3);
+        assert!(settings.auto_confirm_enabled());
+        assert_eq!(settings.risk_weights().normalized_severity, 0.30);
+
+        let malformed = SystemSettings::from_rows([
+            ("auto_close_missed_scans".to_string(), json!(0)),
+            ("auto_confirm_enabled".to_string(), json!("yes")),
+            (
+                "risk_score_weights".to_string(),
+                json!({ "normalized_severity": 1.0 }),
+            ),
+        ]);
+        assert_eq!(malformed.auto_close_missed_scans(), 3);
+        assert!(malformed.auto_confirm_enabled());
+        assert_eq!(malformed.risk_weights().asset_criticality, 0.25);
+    }
+
+    #[test]
+    fn settings_read_stored_values() {
+        let settings = SystemSettings::from_rows([
+            ("auto_close_missed_scans".to_string(), json!(5)),
+            ("auto_confirm_enabled".to_string(), json!(false)),
+            (
+                "risk_score_weights".to_string(),
+                json!({
+                    "normalized_severity": 0.4,
+                    "asset_criticality": 0.2,
+                    "exploitability": 0.2,
+                    "finding_age": 0.1,
+                    "correlation_density": 0.1
+                }),
+            ),
+        ]);
+        assert_eq!(settings.auto_close_missed_scans(), 5);
+        assert!(!settings.auto_confirm_enabled());
+        assert_eq!(settings.risk_weights().normalized_severity, 0.4);
+    }
+}
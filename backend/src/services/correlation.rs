@@ -8,6 +8,8 @@
 //! This module contains no database access — the caller is responsible for
 //! fetching candidates and persisting the resulting relationships.
 
+use std::collections::HashMap;
+
 use uuid::Uuid;
 
 use crate::models::finding::{ConfidenceLevel, FindingCategory, RelationshipType};
@@ -39,6 +41,12 @@ pub struct CorrelationMatch {
     pub match_reason: String,
 }
 
+/// A correlation rule applied to a (new, existing) pair.
+type Rule = fn(&CorrelationCandidate, &CorrelationCandidate) -> Option<CorrelationMatch>;
+
+/// CR-1 through CR-6, in the order their matches are reported.
+const RULES: &[Rule] = &[try_cr1, try_cr2, try_cr3, try_cr4, try_cr5, try_cr6];
+
 /// Correlate a new finding against a list of existing findings.
 ///
 /// Applies the 6 correlation rules (CR-1 through CR-6) and returns
@@ -48,25 +56,118 @@ pub fn correlate_finding(
     new_finding: &CorrelationCandidate,
     existing_findings: &[CorrelationCandidate],
 ) -> Vec<CorrelationMatch> {
-    let rules: &[fn(&CorrelationCandidate, &CorrelationCandidate) -> Option<CorrelationMatch>] = &[
-        try_cr1,
-        try_cr2,
-        try_cr3,
-        try_cr4,
-        try_cr5,
-        try_cr6,
-    ];
-
     existing_findings
         .iter()
-        .flat_map(|existing| {
-            rules
-                .iter()
-                .filter_map(|rule| rule(new_finding, existing))
-        })
+        .flat_map(|existing| RULES.iter().filter_map(|rule| rule(new_finding, existing)))
         .collect()
 }
 
+/// Correlate every candidate against every other candidate.
+///
+/// Produces the same matches as calling [`correlate_finding`] for each
+/// candidate against all the others, but only compares pairs that share a
+/// key some rule requires (CVE, CWE or SAST rule), plus the SCA/SAST pairs
+/// that CR-3 matches by substring. Returns `(source_finding_id, match)`
+/// pairs in candidate order.
+pub fn correlate_all(candidates: &[CorrelationCandidate]) -> Vec<(Uuid, CorrelationMatch)> {
+    let index = CandidateIndex::new(candidates);
+    let mut related = Vec::new();
+    let mut matches = Vec::new();
+
+    for (i, new) in candidates.iter().enumerate() {
+        index.related_to(i, &mut related);
+        for &j in &related {
+            let existing = &candidates[j];
+            matches.extend(
+                RULES
+                    .iter()
+                    .filter_map(|rule| rule(new, existing))
+                    .map(|m| (new.id, m)),
+            );
+        }
+    }
+
+    matches
+}
+
+/// Candidate positions keyed by the fields the correlation rules compare.
+struct CandidateIndex<'a> {
+    candidates: &'a [CorrelationCandidate],
+    /// CR-1.
+    by_cve: HashMap<&'a str, Vec<usize>>,
+    /// CR-2, CR-4 and CR-6.
+    by_cwe: HashMap<&'a str, Vec<usize>>,
+    /// CR-5.
+    by_rule: HashMap<&'a str, Vec<usize>>,
+    /// CR-3: SCA findings with a package name.
+    sca_packages: Vec<usize>,
+    /// CR-3: SAST findings on the production branch.
+    production_sast: Vec<usize>,
+}
+
+impl<'a> CandidateIndex<'a> {
+    fn new(candidates: &'a [CorrelationCandidate]) -> Self {
+        let mut index = Self {
+            candidates,
+            by_cve: HashMap::new(),
+            by_cwe: HashMap::new(),
+            by_rule: HashMap::new(),
+            sca_packages: Vec::new(),
+            production_sast: Vec::new(),
+        };
+
+        for (i, c) in candidates.iter().enumerate() {
+            for cve in &c.cve_ids {
+                index.by_cve.entry(cve.as_str()).or_default().push(i);
+            }
+            for cwe in &c.cwe_ids {
+                index.by_cwe.entry(cwe.as_str()).or_default().push(i);
+            }
+            if let Some(rule) = c.rule_id.as_deref() {
+                index.by_rule.entry(rule).or_default().push(i);
+            }
+            if c.category == FindingCategory::Sca && c.package_name.is_some() {
+                index.sca_packages.push(i);
+            }
+            if c.category == FindingCategory::Sast && is_production_branch(c) {
+                index.production_sast.push(i);
+            }
+        }
+
+        index
+    }
+
+    /// Fill `out` with the positions of candidates that could match
+    /// candidate `i` under any rule, ascending and excluding `i` itself.
+    fn related_to(&self, i: usize, out: &mut Vec<usize>) {
+        out.clear();
+        let c = &self.candidates[i];
+
+        for cve in &c.cve_ids {
+            out.extend(self.by_cve.get(cve.as_str()).into_iter().flatten());
+        }
+        for cwe in &c.cwe_ids {
+            out.extend(self.by_cwe.get(cwe.as_str()).into_iter().flatten());
+        }
+        if let Some(rule) = c.rule_id.as_deref() {
+            out.extend(self.by_rule.get(rule).into_iter().flatten());
+        }
+        match c.category {
+            FindingCategory::Sca if c.package_name.is_some() => {
+                out.extend(&self.production_sast);
+            }
+            FindingCategory::Sast if is_production_branch(c) => {
+                out.extend(&self.sca_packages);
+            }
+            _ => {}
+        }
+
+        out.sort_unstable();
+        out.dedup();
+        out.retain(|&j| j != i);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -459,7 +560,7 @@ mod tests {
             ..Default::default()
         });
 
-        let matches = correlate_finding(&new, &[existing.clone()]);
+        let matches = correlate_finding(&new, std::slice::from_ref(&existing));
         assert_eq!(matches.len(), 1);
         assert_eq!(matches[0].rule_name, "CR-1");
         assert_eq!(matches[0].relationship_type, RelationshipType::CorrelatedWith);
@@ -526,7 +627,7 @@ mod tests {
             ..Default::default()
         });
 
-        let matches = correlate_finding(&new, &[existing.clone()]);
+        let matches = correlate_finding(&new, std::slice::from_ref(&existing));
         let cr2_matches: Vec<_> = matches.iter().filter(|m| m.rule_name == "CR-2").collect();
         assert_eq!(cr2_matches.len(), 1);
         assert_eq!(cr2_matches[0].relationship_type, RelationshipType::CorrelatedWith);
@@ -576,7 +677,7 @@ mod tests {
             ..Default::default()
         });
 
-        let matches = correlate_finding(&new, &[existing.clone()]);
+        let matches = correlate_finding(&new, std::slice::from_ref(&existing));
         let cr3_matches: Vec<_> = matches.iter().filter(|m| m.rule_name == "CR-3").collect();
         assert_eq!(cr3_matches.len(), 1);
         assert_eq!(cr3_matches[0].confidence, ConfidenceLevel::Medium);
@@ -622,7 +723,7 @@ mod tests {
             ..Default::default()
         });
 
-        let matches = correlate_finding(&new, &[existing.clone()]);
+        let matches = correlate_finding(&new, std::slice::from_ref(&existing));
         let cr4_matches: Vec<_> = matches.iter().filter(|m| m.rule_name == "CR-4").collect();
         assert_eq!(cr4_matches.len(), 1);
         assert_eq!(cr4_matches[0].confidence, ConfidenceLevel::Medium);
@@ -650,7 +751,7 @@ mod tests {
             ..Default::default()
         });
 
-        let matches = correlate_finding(&new, &[existing.clone()]);
+        let matches = correlate_finding(&new, std::slice::from_ref(&existing));
         let cr5_matches: Vec<_> = matches.iter().filter(|m| m.rule_name == "CR-5").collect();
         assert_eq!(cr5_matches.len(), 1);
         assert_eq!(cr5_matches[0].relationship_type, RelationshipType::GroupedUnder);
@@ -725,7 +826,7 @@ mod tests {
             ..Default::default()
         });
 
-        let matches = correlate_finding(&new, &[existing.clone()]);
+        let matches = correlate_finding(&new, std::slice::from_ref(&existing));
         let cr6_matches: Vec<_> = matches.iter().filter(|m| m.rule_name == "CR-6").collect();
         assert_eq!(cr6_matches.len(), 1);
         assert_eq!(cr6_matches[0].relationship_type, RelationshipType::GroupedUnder);
@@ -802,4 +903,80 @@ mod tests {
         let matches = correlate_finding(&new, &[existing]);
         assert!(matches.is_empty(), "Unrelated findings should produce no matches");
     }
+
+    #[test]
+    fn correlate_all_matches_pairwise_comparison() {
+        let app = Some(Uuid::new_v4());
+        let s = |v: &str| Some(v.to_string());
+        let candidates = vec![
+            make_candidate(CandidateOverrides {
+                category: Some(FindingCategory::Sca),
+                application_id: Some(app),
+                cve_ids: Some(vec!["CVE-2021-44228".to_string()]),
+                package_name: Some(s("log4j")),
+                ..Default::default()
+            }),
+            make_candidate(CandidateOverrides {
+                category: Some(FindingCategory::Dast),
+                application_id: Some(app),
+                cve_ids: Some(vec!["CVE-2021-44228".to_string()]),
+                cwe_ids: Some(vec!["CWE-79".to_string()]),
+                target_url: Some(s("https://app.example.com/search")),
+                ..Default::default()
+            }),
+            make_candidate(CandidateOverrides {
+                application_id: Some(app),
+                cwe_ids: Some(vec!["CWE-79".to_string()]),
+                rule_id: Some(s("java:S5131")),
+                file_path: Some(s("src/main/java/log4j/Search.java")),
+                branch: Some(s("main")),
+                ..Default::default()
+            }),
+            make_candidate(CandidateOverrides {
+                application_id: Some(app),
+                cwe_ids: Some(vec!["CWE-79".to_string(), "CWE-89".to_string()]),
+                rule_id: Some(s("java:S5131")),
+                file_path: Some(s("src/main/java/log4j/Search.java")),
+                branch: Some(s("main")),
+                ..Default::default()
+            }),
+            make_candidate(CandidateOverrides {
+                application_id: Some(app),
+                rule_id: Some(s("java:S5131")),
+                file_path: Some(s("src/main/java/Other.java")),
+                branch: Some(s("main")),
+                ..Default::default()
+            }),
+            make_candidate(CandidateOverrides {
+                category: Some(FindingCategory::Sca),
+                application_id: Some(app),
+                package_name: Some(s("jackson")),
+                ..Default::default()
+            }),
+        ];
+
+        let pairwise: Vec<(Uuid, Uuid, String)> = candidates
+            .iter()
+            .enumerate()
+            .flat_map(|(i, new)| {
+                let others: Vec<_> = candidates
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, c)| c.clone())
+                    .collect();
+                correlate_finding(new, &others)
+                    .into_iter()
+                    .map(|m| (new.id, m.existing_finding_id, m.rule_name))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let indexed: Vec<(Uuid, Uuid, String)> = correlate_all(&candidates)
+            .into_iter()
+            .map(|(source, m)| (source, m.existing_finding_id, m.rule_name))
+            .collect();
+
+        assert!(!pairwise.is_empty());
+        assert_eq!(indexed, pairwise);
+    }
 }
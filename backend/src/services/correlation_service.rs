@@ -4,10 +4,14 @@
 //! [`crate::services::correlation`]. Handles CRUD for correlation rules,
 //! relationship management, and orchestrating correlation runs.
 
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::errors::AppError;
 use crate::models::correlation_rule::{CorrelationRule, CreateCorrelationRule, UpdateCorrelationRule};
@@ -22,7 +26,7 @@ use crate::services::correlation::{self, CorrelationCandidate};
 // ---------------------------------------------------------------------------
 
 /// Summary of a correlation group (related findings clustered together).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct CorrelationGroup {
     pub id: Uuid,
     pub primary_finding_id: Uuid,
@@ -32,31 +36,33 @@ pub struct CorrelationGroup {
 }
 
 /// A correlation group with its full member finding list.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct CorrelationGroupDetail {
     pub group: CorrelationGroup,
     pub members: Vec<FindingSummary>,
 }
 
 /// Result of a correlation run for an application.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct CorrelationRunResult {
     pub new_relationships: usize,
     pub total_findings_analyzed: usize,
 }
 
 /// Request body for manually creating a finding relationship.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema, Validate)]
 pub struct CreateRelationshipRequest {
     pub source_finding_id: Uuid,
     pub target_finding_id: Uuid,
     pub relationship_type: RelationshipType,
     pub confidence: Option<ConfidenceLevel>,
+    #[validate(length(max = 2000))]
     pub notes: Option<String>,
 }
 
 /// Filters for listing correlation groups.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CorrelationGroupFilters {
     pub application_id: Option<Uuid>,
 }
@@ -224,7 +230,7 @@ pub async fn get_group(pool: &PgPool, group_id: Uuid) -> Result<CorrelationGroup
         r#"
         SELECT
             id, source_tool, finding_category, title,
-            normalized_severity, status, composite_risk_score,
+            normalized_severity, severity_override, status, composite_risk_score,
             fingerprint, application_id, first_seen, last_seen,
             sla_status
         FROM findings
@@ -354,8 +360,9 @@ pub async fn update_rule(
 
 /// Run correlation engine for all findings in an application.
 ///
-/// Loads findings, converts to candidates, runs the pure correlation logic,
-/// and inserts any new relationships that do not already exist.
+/// Loads findings, converts to candidates, runs the pure correlation logic
+/// over an index of the candidates, and batch-inserts any new relationships
+/// that do not already exist.
 pub async fn run_for_application(
     pool: &PgPool,
     app_id: Uuid,
@@ -391,42 +398,8 @@ pub async fn run_for_application(
     let total_findings_analyzed = rows.len();
     let candidates: Vec<CorrelationCandidate> = rows.iter().map(row_to_candidate).collect();
 
-    let mut new_relationships = 0usize;
-
-    for (i, candidate) in candidates.iter().enumerate() {
-        let others: Vec<CorrelationCandidate> = candidates
-            .iter()
-            .enumerate()
-            .filter(|(j, _)| *j != i)
-            .map(|(_, c)| c.clone())
-            .collect();
-
-        let matches = correlation::correlate_finding(candidate, &others);
-
-        for m in matches {
-            // Insert only if the relationship does not already exist
-            let inserted = sqlx::query_scalar::<_, bool>(
-                r#"
-                INSERT INTO finding_relationships (source_finding_id, target_finding_id, relationship_type, confidence, created_by, notes)
-                VALUES ($1, $2, $3, $4, $5, $6)
-                ON CONFLICT (source_finding_id, target_finding_id, relationship_type) DO NOTHING
-                RETURNING true
-                "#,
-            )
-            .bind(candidate.id)
-            .bind(m.existing_finding_id)
-            .bind(&m.relationship_type)
-            .bind(&m.confidence)
-            .bind(user_id)
-            .bind(&m.match_reason)
-            .fetch_optional(pool)
-            .await?;
-
-            if inserted.is_some() {
-                new_relationships += 1;
-            }
-        }
-    }
+    let matches = correlation::correlate_all(&candidates);
+    let new_relationships = insert_relationships(pool, &matches, user_id).await?;
 
     Ok(CorrelationRunResult {
         new_relationships,
@@ -434,6 +407,55 @@ pub async fn run_for_application(
     })
 }
 
+/// Relationships written per `INSERT` statement during a correlation run.
+const RELATIONSHIP_INSERT_BATCH: usize = 1_000;
+
+/// Insert correlation matches that do not already exist as relationships.
+///
+/// When several rules link the same pair with the same relationship type,
+/// the first match's reason is kept. All batches are written in one
+/// transaction. Returns the number of relationships inserted.
+async fn insert_relationships(
+    pool: &PgPool,
+    matches: &[(Uuid, correlation::CorrelationMatch)],
+    user_id: Uuid,
+) -> Result<usize, AppError> {
+    let mut seen = HashSet::new();
+    let unique: Vec<_> = matches
+        .iter()
+        .filter(|(source, m)| {
+            seen.insert((*source, m.existing_finding_id, m.relationship_type.clone()))
+        })
+        .collect();
+
+    let mut tx = pool.begin().await?;
+    let mut inserted = 0u64;
+
+    for batch in unique.chunks(RELATIONSHIP_INSERT_BATCH) {
+        inserted += sqlx::query(
+            r#"
+            INSERT INTO finding_relationships (source_finding_id, target_finding_id, relationship_type, confidence, created_by, notes)
+            SELECT source, target, rel_type, confidence, $5, notes
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::relationship_type[], $4::confidence_level[], $6::text[])
+                AS m(source, target, rel_type, confidence, notes)
+            ON CONFLICT (source_finding_id, target_finding_id, relationship_type) DO NOTHING
+            "#,
+        )
+        .bind(batch.iter().map(|(source, _)| *source).collect::<Vec<_>>())
+        .bind(batch.iter().map(|(_, m)| m.existing_finding_id).collect::<Vec<_>>())
+        .bind(batch.iter().map(|(_, m)| m.relationship_type.clone()).collect::<Vec<_>>())
+        .bind(batch.iter().map(|(_, m)| m.confidence.clone()).collect::<Vec<_>>())
+        .bind(user_id)
+        .bind(batch.iter().map(|(_, m)| m.match_reason.clone()).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    tx.commit().await?;
+    Ok(inserted as usize)
+}
+
 // ---------------------------------------------------------------------------
 // Manual relationship management
 // ---------------------------------------------------------------------------
@@ -498,6 +520,24 @@ pub async fn create_relationship(
     Ok(relationship)
 }
 
+/// List relationships where the finding is either source or target, newest first.
+pub async fn list_relationships_for_finding(
+    pool: &PgPool,
+    finding_id: Uuid,
+) -> Result<Vec<FindingRelationship>, AppError> {
+    let relationships = sqlx::query_as::<_, FindingRelationship>(
+        r#"
+        SELECT * FROM finding_relationships
+        WHERE source_finding_id = $1 OR target_finding_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(finding_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(relationships)
+}
+
 /// Delete a finding relationship by ID.
 pub async fn delete_relationship(pool: &PgPool, relationship_id: Uuid) -> Result<(), AppError> {
     let result = sqlx::query("DELETE FROM finding_relationships WHERE id = $1")
@@ -528,7 +568,7 @@ pub async fn delete_relationship(pool: &PgPool, relationship_id: Uuid) -> Result
 /// When the finding has no relationships, returns a standalone input
 /// (distinct_tool_count = 1, correlated_finding_count = 0).
 pub async fn correlation_density_for_finding(
-    pool: &PgPool,
+    conn: &mut PgConnection,
     finding_id: Uuid,
 ) -> Result<crate::services::risk_score::CorrelationInput, AppError> {
     // Single query: collect distinct source_tool values across the finding
@@ -557,7 +597,7 @@ pub async fn correlation_density_for_finding(
         "#,
     )
     .bind(finding_id)
-    .fetch_one(pool)
+    .fetch_one(conn)
     .await?;
 
     Ok(density_row_to_input(&row))
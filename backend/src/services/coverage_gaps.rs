@@ -0,0 +1,285 @@
+//! Scanner coverage gap analysis: active applications that have not been
+//! reported on by a testing category they are expected to have.
+//!
+//! Expectations come from the application profile: SAST and SCA for every
+//! application with source repositories, DAST for Tier 1 and internet-facing
+//! applications. A category counts as covered when one of its scanners has
+//! reported a finding for the application within the window.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::application::{AssetTier, ExposureLevel};
+use crate::models::finding::FindingCategory;
+
+/// Default look-back window in days.
+const DEFAULT_WINDOW_DAYS: i64 = 90;
+
+fn default_days() -> i64 {
+    DEFAULT_WINDOW_DAYS
+}
+
+/// Query parameters for the coverage gap report.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CoverageGapParams {
+    /// Look-back window in days (defaults to 90).
+    #[serde(default = "default_days")]
+    pub days: i64,
+    /// Only report gaps in this category.
+    pub category: Option<FindingCategory>,
+}
+
+/// One expected category without recent findings.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct MissingCoverage {
+    pub category: FindingCategory,
+    /// Why the category is expected for the application.
+    pub reason: String,
+    /// Most recent finding from the category, if any was ever reported.
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+/// Application with at least one coverage gap.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CoverageGap {
+    pub application_id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    pub tier: AssetTier,
+    pub exposure: Option<ExposureLevel>,
+    pub business_unit: Option<String>,
+    pub missing: Vec<MissingCoverage>,
+}
+
+/// Most recent completed ingestion of one scanner.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct ToolIngestion {
+    pub source_tool: String,
+    pub last_completed_at: Option<DateTime<Utc>>,
+    /// True when the scanner has no completed ingestion within the window.
+    pub stale: bool,
+}
+
+/// Coverage gaps across active applications.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CoverageGapReport {
+    pub window_days: i64,
+    pub since: DateTime<Utc>,
+    pub applications_checked: usize,
+    pub gaps: Vec<CoverageGap>,
+    pub tool_ingestions: Vec<ToolIngestion>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AppCoverageRow {
+    id: Uuid,
+    app_name: String,
+    app_code: String,
+    tier: AssetTier,
+    exposure: Option<ExposureLevel>,
+    business_unit: Option<String>,
+    has_repositories: bool,
+    sast_last_seen: Option<DateTime<Utc>>,
+    sca_last_seen: Option<DateTime<Utc>>,
+    dast_last_seen: Option<DateTime<Utc>>,
+}
+
+/// List active applications missing expected scanner coverage.
+pub async fn get_gaps(
+    pool: &PgPool,
+    params: &CoverageGapParams,
+) -> Result<CoverageGapReport, AppError> {
+    if params.days < 1 {
+        return Err(AppError::Validation(
+            "'days' must be at least 1".to_string(),
+        ));
+    }
+    let since = Utc::now() - Duration::days(params.days);
+
+    let (apps, tool_ingestions) =
+        tokio::try_join!(fetch_app_coverage(pool), fetch_tool_ingestions(pool, since))?;
+
+    let applications_checked = apps.len();
+    let gaps = apps
+        .into_iter()
+        .filter_map(|app| find_gap(app, since, params.category.as_ref()))
+        .collect();
+
+    Ok(CoverageGapReport {
+        window_days: params.days,
+        since,
+        applications_checked,
+        gaps,
+        tool_ingestions,
+    })
+}
+
+async fn fetch_app_coverage(pool: &PgPool) -> Result<Vec<AppCoverageRow>, AppError> {
+    let rows = sqlx::query_as::<_, AppCoverageRow>(
+        r#"
+        SELECT
+            a.id,
+            a.app_name,
+            a.app_code,
+            a.tier,
+            a.exposure,
+            a.business_unit,
+            COALESCE(jsonb_array_length(a.repository_urls), 0) > 0 AS has_repositories,
+            MAX(f.last_seen) FILTER (WHERE f.finding_category = 'SAST') AS sast_last_seen,
+            MAX(f.last_seen) FILTER (WHERE f.finding_category = 'SCA') AS sca_last_seen,
+            MAX(f.last_seen) FILTER (WHERE f.finding_category = 'DAST') AS dast_last_seen
+        FROM applications a
+        LEFT JOIN findings f ON f.application_id = a.id
+        WHERE a.status = 'Active'
+        GROUP BY a.id
+        ORDER BY a.tier, a.app_code
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+async fn fetch_tool_ingestions(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+) -> Result<Vec<ToolIngestion>, AppError> {
+    let rows = sqlx::query_as::<_, ToolIngestion>(
+        r#"
+        SELECT
+            source_tool,
+            MAX(completed_at) AS last_completed_at,
+            COALESCE(MAX(completed_at) < $1, true) AS stale
+        FROM ingestion_logs
+        GROUP BY source_tool
+        ORDER BY source_tool
+        "#,
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Categories the application is expected to be scanned by, with the reason.
+fn expected_categories(app: &AppCoverageRow) -> Vec<(FindingCategory, &'static str)> {
+    let mut expected = Vec::new();
+    if app.has_repositories {
+        expected.push((FindingCategory::Sast, "Application has source repositories"));
+        expected.push((FindingCategory::Sca, "Application has source repositories"));
+    }
+    if app.tier == AssetTier::Tier1 {
+        expected.push((FindingCategory::Dast, "Tier 1 application"));
+    } else if app.exposure == Some(ExposureLevel::InternetFacing) {
+        expected.push((FindingCategory::Dast, "Internet-facing application"));
+    }
+    expected
+}
+
+/// Build the gap entry for an application, or `None` when it is covered.
+fn find_gap(
+    app: AppCoverageRow,
+    since: DateTime<Utc>,
+    only: Option<&FindingCategory>,
+) -> Option<CoverageGap> {
+    let missing: Vec<MissingCoverage> = expected_categories(&app)
+        .into_iter()
+        .filter(|(category, _)| only.map_or(true, |c| c == category))
+        .filter_map(|(category, reason)| {
+            let last_seen = match category {
+                FindingCategory::Sast => app.sast_last_seen,
+                FindingCategory::Sca => app.sca_last_seen,
+                FindingCategory::Dast => app.dast_last_seen,
+                // Never expected: pentests are not scanner coverage
+                FindingCategory::Manual => None,
+            };
+            match last_seen {
+                Some(seen) if seen >= since => None,
+                _ => Some(MissingCoverage {
+                    category,
+                    reason: reason.to_string(),
+                    last_seen,
+                }),
+            }
+        })
+        .collect();
+
+    if missing.is_empty() {
+        return None;
+    }
+    Some(CoverageGap {
+        application_id: app.id,
+        app_name: app.app_name,
+        app_code: app.app_code,
+        tier: app.tier,
+        exposure: app.exposure,
+        business_unit: app.business_unit,
+        missing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(tier: AssetTier, exposure: Option<ExposureLevel>, repos: bool) -> AppCoverageRow {
+        AppCoverageRow {
+            id: Uuid::new_v4(),
+            app_name: "Payments".to_string(),
+            app_code: "PAY01".to_string(),
+            tier,
+            exposure,
+            business_unit: None,
+            has_repositories: repos,
+            sast_last_seen: None,
+            sca_last_seen: None,
+            dast_last_seen: None,
+        }
+    }
+
+    fn categories(gap: &CoverageGap) -> Vec<FindingCategory> {
+        gap.missing.iter().map(|m| m.category.clone()).collect()
+    }
+
+    #[test]
+    fn tier_one_app_without_dast_is_a_gap() {
+        let since = Utc::now() - Duration::days(90);
+        let gap = find_gap(app(AssetTier::Tier1, None, false), since, None).unwrap();
+        assert_eq!(categories(&gap), [FindingCategory::Dast]);
+        assert_eq!(gap.missing[0].reason, "Tier 1 application");
+    }
+
+    #[test]
+    fn stale_findings_do_not_count_as_coverage() {
+        let since = Utc::now() - Duration::days(90);
+        let mut row = app(AssetTier::Tier2, None, true);
+        row.sast_last_seen = Some(Utc::now() - Duration::days(10));
+        row.sca_last_seen = Some(Utc::now() - Duration::days(120));
+        let gap = find_gap(row, since, None).unwrap();
+        assert_eq!(categories(&gap), [FindingCategory::Sca]);
+        assert!(gap.missing[0].last_seen.is_some());
+    }
+
+    #[test]
+    fn covered_or_unexpected_apps_have_no_gap() {
+        let since = Utc::now() - Duration::days(90);
+        assert!(find_gap(app(AssetTier::Tier3, None, false), since, None).is_none());
+
+        let mut row = app(AssetTier::Tier2, Some(ExposureLevel::InternetFacing), false);
+        row.dast_last_seen = Some(Utc::now());
+        assert!(find_gap(row, since, None).is_none());
+    }
+
+    #[test]
+    fn category_filter_limits_reported_gaps() {
+        let since = Utc::now() - Duration::days(90);
+        let row = app(AssetTier::Tier1, None, true);
+        let gap = find_gap(row, since, Some(&FindingCategory::Sast)).unwrap();
+        assert_eq!(categories(&gap), [FindingCategory::Sast]);
+    }
+}
@@ -4,7 +4,12 @@
 //! category to determine if they represent the same vulnerability.
 //! This is pure logic with no database access — the caller is responsible
 //! for fetching candidates and persisting match results.
+//!
+//! [`apply_heuristics`] runs the configurable heuristics of the
+//! `cross_dedup_heuristics` setting, each of which either merges matched
+//! pairs automatically or queues them for analyst review.
 
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::models::finding::{ConfidenceLevel, FindingCategory};
@@ -21,6 +26,8 @@ pub struct CrossDedupCandidate {
     pub package_name: Option<String>,
     pub file_path: Option<String>,
     pub line_number: Option<i32>,
+    /// Last line of the reported range; `line_number` alone is a single line.
+    pub line_end: Option<i32>,
     pub branch: Option<String>,
     pub target_url: Option<String>,
     pub parameter: Option<String>,
@@ -59,6 +66,8 @@ pub fn check_cross_dedup(a: &CrossDedupCandidate, b: &CrossDedupCandidate) -> Op
         FindingCategory::Sca => check_sca(a, b),
         FindingCategory::Sast => check_sast(a, b),
         FindingCategory::Dast => check_dast(a, b),
+        // Hand-entered findings are linked by analysts, not by heuristics
+        FindingCategory::Manual => None,
     }
 }
 
@@ -179,6 +188,183 @@ fn check_dast(a: &CrossDedupCandidate, b: &CrossDedupCandidate) -> Option<CrossD
     })
 }
 
+// ---------------------------------------------------------------------------
+// Configurable heuristics
+// ---------------------------------------------------------------------------
+
+/// What to do with a pair matched by a heuristic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupAction {
+    /// Record the pair as a confirmed duplicate.
+    AutoMerge,
+    /// Queue the pair for analyst review.
+    Review,
+}
+
+impl DedupAction {
+    /// Confidence of the `duplicate_of` relationship recorded for the pair;
+    /// the review queue holds the Low and Medium ones.
+    pub fn confidence(self) -> ConfidenceLevel {
+        match self {
+            Self::AutoMerge => ConfidenceLevel::High,
+            Self::Review => ConfidenceLevel::Medium,
+        }
+    }
+}
+
+/// SCA findings with a common CVE in the same package.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CvePackageHeuristic {
+    pub enabled: bool,
+    pub action: DedupAction,
+}
+
+impl Default for CvePackageHeuristic {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: DedupAction::AutoMerge,
+        }
+    }
+}
+
+/// SAST findings with a common CWE in the same file and overlapping lines.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CweFileHeuristic {
+    pub enabled: bool,
+    pub action: DedupAction,
+    /// Lines of slack allowed between the two ranges.
+    pub line_tolerance: i32,
+}
+
+impl Default for CweFileHeuristic {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            action: DedupAction::Review,
+            line_tolerance: 0,
+        }
+    }
+}
+
+/// Heuristics configured in the `cross_dedup_heuristics` setting.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DedupHeuristics {
+    pub same_cve_package: CvePackageHeuristic,
+    pub same_cwe_file_lines: CweFileHeuristic,
+}
+
+/// A pair of findings matched by a heuristic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DedupSuggestion {
+    pub finding_a_id: Uuid,
+    pub finding_b_id: Uuid,
+    pub action: DedupAction,
+    /// Key of the matching heuristic in the settings, recorded as the
+    /// relationship's match source.
+    pub heuristic: &'static str,
+    pub reason: &'static str,
+}
+
+/// Run the enabled heuristics on two findings from different tools in the
+/// same application and category.
+pub fn apply_heuristics(
+    a: &CrossDedupCandidate,
+    b: &CrossDedupCandidate,
+    heuristics: &DedupHeuristics,
+) -> Option<DedupSuggestion> {
+    if a.category != b.category || a.source_tool == b.source_tool {
+        return None;
+    }
+    match (&a.application_id, &b.application_id) {
+        (Some(app_a), Some(app_b)) if app_a == app_b => {}
+        _ => return None,
+    }
+
+    let cve_package = &heuristics.same_cve_package;
+    let cwe_file = &heuristics.same_cwe_file_lines;
+    let (action, heuristic, reason) = match a.category {
+        FindingCategory::Sca if cve_package.enabled && same_cve_and_package(a, b) => (
+            cve_package.action,
+            "same_cve_package",
+            "Same CVE and package in the application across tools",
+        ),
+        FindingCategory::Sast
+            if cwe_file.enabled && same_cwe_file_and_lines(a, b, cwe_file.line_tolerance) =>
+        {
+            (
+                cwe_file.action,
+                "same_cwe_file_lines",
+                "Same CWE and file with overlapping lines across tools",
+            )
+        }
+        _ => return None,
+    };
+
+    Some(DedupSuggestion {
+        finding_a_id: a.id,
+        finding_b_id: b.id,
+        action,
+        heuristic,
+        reason,
+    })
+}
+
+fn same_cve_and_package(a: &CrossDedupCandidate, b: &CrossDedupCandidate) -> bool {
+    has_common_id(&a.cve_ids, &b.cve_ids)
+        && matches!(
+            (&a.package_name, &b.package_name),
+            (Some(pa), Some(pb)) if pa.eq_ignore_ascii_case(pb)
+        )
+}
+
+fn same_cwe_file_and_lines(
+    a: &CrossDedupCandidate,
+    b: &CrossDedupCandidate,
+    tolerance: i32,
+) -> bool {
+    if !has_common_id(&a.cwe_ids, &b.cwe_ids) {
+        return false;
+    }
+    // Branches only rule a pair out when both tools report one
+    if let (Some(branch_a), Some(branch_b)) = (&a.branch, &b.branch) {
+        if branch_a != branch_b {
+            return false;
+        }
+    }
+    match (&a.file_path, &b.file_path) {
+        (Some(fa), Some(fb)) if normalize_path(fa) == normalize_path(fb) => {}
+        _ => return false,
+    }
+    match (line_range(a), line_range(b)) {
+        (Some((start_a, end_a)), Some((start_b, end_b))) => {
+            start_a <= end_b + tolerance && start_b <= end_a + tolerance
+        }
+        _ => false,
+    }
+}
+
+/// Reported line range, a single line when there is no end line.
+fn line_range(candidate: &CrossDedupCandidate) -> Option<(i32, i32)> {
+    let start = candidate.line_number?;
+    Some((start, candidate.line_end.unwrap_or(start).max(start)))
+}
+
+/// File path as compared across tools: forward slashes, relative to the
+/// repository root.
+pub(crate) fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut path = path.as_str();
+    while let Some(rest) = path.strip_prefix("./") {
+        path = rest;
+    }
+    path.trim_start_matches('/').to_string()
+}
+
 /// Check whether two ID lists share at least one common element.
 fn has_common_id(a: &[String], b: &[String]) -> bool {
     a.iter().any(|id| b.contains(id))
@@ -201,6 +387,7 @@ mod tests {
             package_name: overrides.package_name.unwrap_or(None),
             file_path: overrides.file_path.unwrap_or(None),
             line_number: overrides.line_number.unwrap_or(None),
+            line_end: overrides.line_end.unwrap_or(None),
             branch: overrides.branch.unwrap_or(None),
             target_url: overrides.target_url.unwrap_or(None),
             parameter: overrides.parameter.unwrap_or(None),
@@ -218,6 +405,7 @@ mod tests {
         package_name: Option<Option<String>>,
         file_path: Option<Option<String>>,
         line_number: Option<Option<i32>>,
+        line_end: Option<Option<i32>>,
         branch: Option<Option<String>>,
         target_url: Option<Option<String>>,
         parameter: Option<Option<String>>,
@@ -339,4 +527,77 @@ mod tests {
         let result = check_cross_dedup(&a, &b);
         assert!(result.is_none(), "Different branches should not match for SAST");
     }
+
+    #[test]
+    fn sca_heuristic_merges_same_cve_and_package() {
+        let a = make_candidate(CandidateOverrides {
+            source_tool: Some("JFrog Xray".to_string()),
+            cve_ids: Some(vec!["CVE-2021-44228".to_string()]),
+            package_name: Some(Some("log4j-core".to_string())),
+            ..Default::default()
+        });
+        let b = make_candidate(CandidateOverrides {
+            source_tool: Some("Snyk".to_string()),
+            cve_ids: Some(vec!["CVE-2021-44228".to_string()]),
+            package_name: Some(Some("Log4j-Core".to_string())),
+            ..Default::default()
+        });
+        let mut heuristics = DedupHeuristics::default();
+
+        let suggestion = apply_heuristics(&a, &b, &heuristics).unwrap();
+        assert_eq!(suggestion.action, DedupAction::AutoMerge);
+        assert_eq!(suggestion.action.confidence(), ConfidenceLevel::High);
+
+        heuristics.same_cve_package.action = DedupAction::Review;
+        let suggestion = apply_heuristics(&a, &b, &heuristics).unwrap();
+        assert_eq!(suggestion.action, DedupAction::Review);
+
+        heuristics.same_cve_package.enabled = false;
+        assert!(apply_heuristics(&a, &b, &heuristics).is_none());
+    }
+
+    #[test]
+    fn sast_heuristic_needs_overlapping_lines_in_the_same_file() {
+        let a = make_candidate(CandidateOverrides {
+            category: Some(FindingCategory::Sast),
+            source_tool: Some("SonarQube".to_string()),
+            cwe_ids: Some(vec!["CWE-89".to_string()]),
+            file_path: Some(Some("./src/Dao.java".to_string())),
+            line_number: Some(Some(40)),
+            line_end: Some(Some(44)),
+            ..Default::default()
+        });
+        let mut b = make_candidate(CandidateOverrides {
+            category: Some(FindingCategory::Sast),
+            source_tool: Some("Checkmarx".to_string()),
+            cwe_ids: Some(vec!["CWE-89".to_string()]),
+            file_path: Some(Some("src\\Dao.java".to_string())),
+            line_number: Some(Some(44)),
+            ..Default::default()
+        });
+        let mut heuristics = DedupHeuristics::default();
+
+        let suggestion = apply_heuristics(&a, &b, &heuristics).unwrap();
+        assert_eq!(suggestion.action, DedupAction::Review);
+
+        b.line_number = Some(46);
+        assert!(apply_heuristics(&a, &b, &heuristics).is_none());
+        heuristics.same_cwe_file_lines.line_tolerance = 2;
+        assert!(apply_heuristics(&a, &b, &heuristics).is_some());
+
+        b.file_path = Some("src/OtherDao.java".to_string());
+        assert!(apply_heuristics(&a, &b, &heuristics).is_none());
+    }
+
+    #[test]
+    fn heuristics_setting_fills_in_defaults() {
+        let heuristics: DedupHeuristics = serde_json::from_value(serde_json::json!({
+            "same_cwe_file_lines": { "action": "auto_merge" }
+        }))
+        .unwrap();
+        assert_eq!(heuristics.same_cve_package, CvePackageHeuristic::default());
+        assert!(heuristics.same_cwe_file_lines.enabled);
+        assert_eq!(heuristics.same_cwe_file_lines.action, DedupAction::AutoMerge);
+        assert_eq!(heuristics.same_cwe_file_lines.line_tolerance, 0);
+    }
 }
@@ -0,0 +1,302 @@
+//! Database-backed cross-tool deduplication runs.
+//!
+//! Loads an application's open SCA and SAST findings, runs the heuristics of
+//! the `cross_dedup_heuristics` setting (see [`crate::services::cross_dedup`])
+//! and records each matched pair as a `duplicate_of` relationship from the
+//! newer finding to the older one: High confidence for automatic merges,
+//! Medium for pairs that go to the review queue. The heuristic's key is kept
+//! as the relationship's match source. Pairs already linked as
+//! duplicates or rejected by an analyst, in either direction, are skipped.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{ConfidenceLevel, FindingCategory};
+use crate::services::config_cache;
+use crate::services::cross_dedup::{
+    self, normalize_path, CrossDedupCandidate, DedupAction, DedupHeuristics, DedupSuggestion,
+};
+use crate::services::json_value::json_strings;
+
+/// Outcome of a cross-tool deduplication run for an application.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct CrossDedupRunResult {
+    pub findings_analyzed: usize,
+    /// Pairs recorded as confirmed duplicates.
+    pub auto_merged: usize,
+    /// Pairs added to the deduplication review queue.
+    pub queued_for_review: usize,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CandidateRow {
+    id: Uuid,
+    finding_category: FindingCategory,
+    application_id: Option<Uuid>,
+    source_tool: String,
+    cve_ids: serde_json::Value,
+    cwe_ids: serde_json::Value,
+    package_name: Option<String>,
+    file_path: Option<String>,
+    line_number_start: Option<i32>,
+    line_number_end: Option<i32>,
+    branch: Option<String>,
+}
+
+impl CandidateRow {
+    fn into_candidate(self) -> CrossDedupCandidate {
+        CrossDedupCandidate {
+            id: self.id,
+            category: self.finding_category,
+            application_id: self.application_id,
+            source_tool: self.source_tool,
+            cve_ids: json_strings(&self.cve_ids),
+            cwe_ids: json_strings(&self.cwe_ids),
+            package_name: self.package_name,
+            file_path: self.file_path,
+            line_number: self.line_number_start,
+            line_end: self.line_number_end,
+            branch: self.branch,
+            target_url: None,
+            parameter: None,
+        }
+    }
+}
+
+/// Run the configured heuristics over an application's open findings.
+pub async fn run_for_application(
+    pool: &PgPool,
+    app_id: Uuid,
+    user_id: Uuid,
+) -> Result<CrossDedupRunResult, AppError> {
+    let exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM applications WHERE id = $1)")
+            .bind(app_id)
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Application {app_id} not found"
+        )));
+    }
+
+    // Oldest first, so the later finding of a pair is the duplicate
+    let rows = sqlx::query_as::<_, CandidateRow>(
+        r#"
+        SELECT
+            f.id,
+            f.finding_category,
+            f.application_id,
+            f.source_tool,
+            f.cve_ids,
+            f.cwe_ids,
+            fc.package_name,
+            fs.file_path,
+            fs.line_number_start,
+            fs.line_number_end,
+            fs.branch
+        FROM findings f
+        LEFT JOIN finding_sca fc ON fc.finding_id = f.id
+        LEFT JOIN finding_sast fs ON fs.finding_id = f.id
+        WHERE f.application_id = $1
+          AND f.finding_category IN ('SCA', 'SAST')
+          AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND f.archived_at IS NULL
+        ORDER BY f.first_seen, f.id
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    let candidates: Vec<CrossDedupCandidate> =
+        rows.into_iter().map(CandidateRow::into_candidate).collect();
+
+    let known = known_pairs(pool, app_id).await?;
+    let heuristics = config_cache::global()
+        .settings(pool)
+        .await?
+        .cross_dedup_heuristics();
+    let suggestions = find_duplicates(&candidates, &heuristics, &known);
+
+    let mut result = CrossDedupRunResult {
+        findings_analyzed: candidates.len(),
+        ..Default::default()
+    };
+    if suggestions.is_empty() {
+        return Ok(result);
+    }
+
+    // Suggestions hold (older, newer); the newer finding is the duplicate
+    let inserted: Vec<ConfidenceLevel> = sqlx::query_scalar(
+        r#"
+        INSERT INTO finding_relationships (source_finding_id, target_finding_id, relationship_type, confidence, created_by, notes, match_source)
+        SELECT source, target, 'duplicate_of', confidence, $4, notes, match_source
+        FROM UNNEST($1::uuid[], $2::uuid[], $3::confidence_level[], $5::text[], $6::text[])
+            AS s(source, target, confidence, notes, match_source)
+        ON CONFLICT (source_finding_id, target_finding_id, relationship_type) DO NOTHING
+        RETURNING confidence
+        "#,
+    )
+    .bind(suggestions.iter().map(|s| s.finding_b_id).collect::<Vec<_>>())
+    .bind(suggestions.iter().map(|s| s.finding_a_id).collect::<Vec<_>>())
+    .bind(
+        suggestions
+            .iter()
+            .map(|s| s.action.confidence())
+            .collect::<Vec<_>>(),
+    )
+    .bind(user_id)
+    .bind(suggestions.iter().map(|s| s.reason).collect::<Vec<_>>())
+    .bind(suggestions.iter().map(|s| s.heuristic).collect::<Vec<_>>())
+    .fetch_all(pool)
+    .await?;
+
+    for confidence in inserted {
+        if confidence == DedupAction::AutoMerge.confidence() {
+            result.auto_merged += 1;
+        } else {
+            result.queued_for_review += 1;
+        }
+    }
+    tracing::info!(
+        application_id = %app_id,
+        auto_merged = result.auto_merged,
+        queued_for_review = result.queued_for_review,
+        "Cross-tool deduplication run"
+    );
+    Ok(result)
+}
+
+/// Unordered pairs of the application's findings already linked as
+/// duplicates or rejected as such.
+pub(crate) async fn known_pairs(pool: &PgPool, app_id: Uuid) -> Result<HashSet<(Uuid, Uuid)>, AppError> {
+    let rows = sqlx::query_as::<_, (Uuid, Uuid)>(
+        r#"
+        SELECT fr.source_finding_id, fr.target_finding_id
+        FROM finding_relationships fr
+        JOIN findings f ON f.id = fr.source_finding_id
+        WHERE fr.relationship_type = 'duplicate_of' AND f.application_id = $1
+        UNION
+        SELECT dr.source_finding_id, dr.target_finding_id
+        FROM dedup_rejections dr
+        JOIN findings f ON f.id = dr.source_finding_id
+        WHERE f.application_id = $1
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|(a, b)| pair_key(a, b)).collect())
+}
+
+pub(crate) fn pair_key(a: Uuid, b: Uuid) -> (Uuid, Uuid) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Matched pairs among `candidates` (oldest first), each as (older, newer).
+///
+/// Only findings sharing a CVE (SCA) or a file (SAST) are compared, which
+/// keeps runs over large applications far from quadratic.
+fn find_duplicates(
+    candidates: &[CrossDedupCandidate],
+    heuristics: &DedupHeuristics,
+    known: &HashSet<(Uuid, Uuid)>,
+) -> Vec<DedupSuggestion> {
+    let mut buckets: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, candidate) in candidates.iter().enumerate() {
+        match candidate.category {
+            FindingCategory::Sca => {
+                for cve in &candidate.cve_ids {
+                    buckets.entry(format!("cve:{cve}")).or_default().push(i);
+                }
+            }
+            FindingCategory::Sast => {
+                if let Some(path) = &candidate.file_path {
+                    buckets
+                        .entry(format!("file:{}", normalize_path(path)))
+                        .or_default()
+                        .push(i);
+                }
+            }
+            FindingCategory::Dast | FindingCategory::Manual => {}
+        }
+    }
+
+    let mut compared = HashSet::new();
+    let mut suggestions = Vec::new();
+    let mut keys: Vec<&String> = buckets.keys().collect();
+    keys.sort();
+    for key in keys {
+        let members = &buckets[key];
+        for (n, &i) in members.iter().enumerate() {
+            for &j in &members[n + 1..] {
+                let (a, b) = (&candidates[i], &candidates[j]);
+                let pair = pair_key(a.id, b.id);
+                if known.contains(&pair) || !compared.insert(pair) {
+                    continue;
+                }
+                if let Some(suggestion) = cross_dedup::apply_heuristics(a, b, heuristics) {
+                    suggestions.push(suggestion);
+                }
+            }
+        }
+    }
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sca(tool: &str, cve: &str, package: &str) -> CrossDedupCandidate {
+        CrossDedupCandidate {
+            id: Uuid::new_v4(),
+            category: FindingCategory::Sca,
+            application_id: Some(Uuid::nil()),
+            source_tool: tool.to_string(),
+            cve_ids: vec![cve.to_string()],
+            cwe_ids: vec![],
+            package_name: Some(package.to_string()),
+            file_path: None,
+            line_number: None,
+            line_end: None,
+            branch: None,
+            target_url: None,
+            parameter: None,
+        }
+    }
+
+    #[test]
+    fn pairs_are_matched_once_with_the_older_finding_first() {
+        let older = sca("JFrog Xray", "CVE-2021-44228", "log4j-core");
+        let newer = sca("Snyk", "CVE-2021-44228", "log4j-core");
+        let unrelated = sca("Snyk", "CVE-2022-22965", "spring-beans");
+        let candidates = vec![older.clone(), newer.clone(), unrelated];
+
+        let suggestions =
+            find_duplicates(&candidates, &DedupHeuristics::default(), &HashSet::new());
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].finding_a_id, older.id);
+        assert_eq!(suggestions[0].finding_b_id, newer.id);
+        assert_eq!(suggestions[0].action, DedupAction::AutoMerge);
+    }
+
+    #[test]
+    fn known_pairs_are_skipped_in_either_direction() {
+        let older = sca("JFrog Xray", "CVE-2021-44228", "log4j-core");
+        let newer = sca("Snyk", "CVE-2021-44228", "log4j-core");
+        let known = HashSet::from([pair_key(newer.id, older.id)]);
+
+        let suggestions = find_duplicates(&[older, newer], &DedupHeuristics::default(), &known);
+        assert!(suggestions.is_empty());
+    }
+}
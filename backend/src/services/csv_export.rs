@@ -0,0 +1,158 @@
+//! Flat CSV encoding of findings for export and scheduled reports.
+
+use crate::models::finding::FindingSummaryWithCategory;
+
+/// Flat CSV row for export. Flattens `FindingSummaryWithCategory` into a
+/// single record suitable for the `csv` crate's `Serializer`.
+#[derive(Debug, serde::Serialize)]
+pub struct CsvExportRow {
+    id: String,
+    source_tool: String,
+    finding_category: String,
+    title: String,
+    normalized_severity: String,
+    status: String,
+    composite_risk_score: Option<f32>,
+    fingerprint: String,
+    application_id: String,
+    first_seen: String,
+    last_seen: String,
+    sla_status: String,
+    // SAST fields
+    file_path: String,
+    line_number: String,
+    rule_id: String,
+    project: String,
+    language: String,
+    branch: String,
+    // SCA fields
+    package_name: String,
+    package_version: String,
+    fixed_version: String,
+    dependency_type: String,
+    known_exploited: String,
+    // DAST fields
+    target_url: String,
+    parameter: String,
+    web_application_name: String,
+}
+
+impl CsvExportRow {
+    /// Convert a `FindingSummaryWithCategory` to a flat CSV row.
+    pub fn from_finding(f: &FindingSummaryWithCategory) -> Self {
+        let s = &f.summary;
+        let cat = f.category_data.as_ref();
+
+        Self {
+            id: s.id.to_string(),
+            source_tool: s.source_tool.clone(),
+            finding_category: serde_json::to_string(&s.finding_category)
+                .unwrap_or_default()
+                .trim_matches('"')
+                .to_string(),
+            title: s.title.clone(),
+            normalized_severity: serde_json::to_string(&s.normalized_severity)
+                .unwrap_or_default()
+                .trim_matches('"')
+                .to_string(),
+            status: serde_json::to_string(&s.status)
+                .unwrap_or_default()
+                .trim_matches('"')
+                .to_string(),
+            composite_risk_score: s.composite_risk_score,
+            fingerprint: s.fingerprint.clone(),
+            application_id: s
+                .application_id
+                .map_or_else(String::new, |id| id.to_string()),
+            first_seen: s.first_seen.to_rfc3339(),
+            last_seen: s.last_seen.to_rfc3339(),
+            sla_status: s
+                .sla_status
+                .as_ref()
+                .map(|v| {
+                    serde_json::to_string(v)
+                        .unwrap_or_default()
+                        .trim_matches('"')
+                        .to_string()
+                })
+                .unwrap_or_default(),
+            // SAST
+            file_path: cat
+                .and_then(|c| c.file_path.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            line_number: cat
+                .and_then(|c| c.line_number)
+                .map_or_else(String::new, |n| n.to_string()),
+            rule_id: cat
+                .and_then(|c| c.rule_id.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            project: cat
+                .and_then(|c| c.project.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            language: cat
+                .and_then(|c| c.language.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            branch: cat
+                .and_then(|c| c.branch.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            // SCA
+            package_name: cat
+                .and_then(|c| c.package_name.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            package_version: cat
+                .and_then(|c| c.package_version.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            fixed_version: cat
+                .and_then(|c| c.fixed_version.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            dependency_type: cat
+                .and_then(|c| c.dependency_type.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            known_exploited: cat
+                .and_then(|c| c.known_exploited)
+                .map_or_else(String::new, |v| v.to_string()),
+            // DAST
+            target_url: cat
+                .and_then(|c| c.target_url.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            parameter: cat
+                .and_then(|c| c.parameter.as_deref())
+                .unwrap_or("")
+                .to_string(),
+            web_application_name: cat
+                .and_then(|c| c.web_application_name.as_deref())
+                .unwrap_or("")
+                .to_string(),
+        }
+    }
+}
+
+/// Serialize rows as CSV, preceded by the header row when `with_header` is
+/// set and there is at least one row.
+pub fn encode_rows(
+    rows: &[FindingSummaryWithCategory],
+    with_header: bool,
+) -> Result<Vec<u8>, csv::Error> {
+    let mut wtr = csv::WriterBuilder::new()
+        .has_headers(with_header)
+        .from_writer(Vec::new());
+    for finding in rows {
+        wtr.serialize(CsvExportRow::from_finding(finding))?;
+    }
+    wtr.into_inner().map_err(|e| e.into_error().into())
+}
+
+/// Encode findings as a complete CSV document with a header row.
+pub fn encode_all(rows: &[FindingSummaryWithCategory]) -> Result<Vec<u8>, csv::Error> {
+    encode_rows(rows, true)
+}
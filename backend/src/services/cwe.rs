@@ -0,0 +1,410 @@
+//! CWE catalog: dictionary import, lookup, and roll-up by CWE family.
+//!
+//! The dictionary is MITRE's Research Concepts CSV (view 1000). A CWE's family
+//! is the top of its primary-parent chain, normally one of the pillars.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::cwe::{CweDetail, CweEntry, CweFamilyCount, CweImportResult, CweSummary};
+use crate::models::pagination::{PagedResult, Pagination};
+
+/// Research Concepts view, the hierarchy used for parents and families.
+const RESEARCH_VIEW: &str = "1000";
+
+/// Longest primary-parent chain followed; guards against cycles.
+const MAX_DEPTH: i32 = 25;
+
+/// Summary columns with the display label.
+const SELECT_SUMMARY: &str =
+    "SELECT cwe_id, name, 'CWE-' || cwe_id || ': ' || name AS label FROM cwe_catalog";
+
+/// Query parameters for listing the catalog.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CweQuery {
+    /// Matches the id (`89`, `CWE-89`) or part of the name.
+    pub q: Option<String>,
+}
+
+/// Scope of a CWE family roll-up.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CweRollupQuery {
+    pub application_id: Option<Uuid>,
+}
+
+/// Numeric id from `CWE-89`, `cwe-89`, or `89`.
+pub fn parse_cwe_id(value: &str) -> Option<i32> {
+    let value = value.trim();
+    let digits = match value.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("CWE-") => &value[4..],
+        _ => value,
+    };
+    digits.parse().ok().filter(|id| *id > 0)
+}
+
+/// A dictionary row ready to store.
+#[derive(Debug, PartialEq)]
+struct ImportRow {
+    cwe_id: i32,
+    name: String,
+    abstraction: Option<String>,
+    status: Option<String>,
+    description: Option<String>,
+    parent_ids: Vec<i32>,
+}
+
+/// ChildOf parents in view 1000 from a `Related Weaknesses` cell such as
+/// `::NATURE:ChildOf:CWE ID:74:VIEW ID:1000:ORDINAL:Primary::`, primary first.
+fn research_parents(related: &str) -> Vec<i32> {
+    let mut parents: Vec<(bool, i32)> = related
+        .split("::")
+        .filter_map(|entry| {
+            let tokens: Vec<&str> = entry.split(':').collect();
+            let fields: HashMap<&str, &str> = tokens
+                .chunks(2)
+                .filter_map(|pair| Some((pair[0], *pair.get(1)?)))
+                .collect();
+            if fields.get("NATURE") != Some(&"ChildOf")
+                || fields.get("VIEW ID") != Some(&RESEARCH_VIEW)
+            {
+                return None;
+            }
+            let id = fields.get("CWE ID")?.parse().ok()?;
+            Some((fields.get("ORDINAL") == Some(&"Primary"), id))
+        })
+        .collect();
+    parents.sort_by_key(|(primary, _)| !primary);
+    let mut ids: Vec<i32> = Vec::with_capacity(parents.len());
+    for (_, id) in parents {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// Parse the CWE CSV export. Returns the rows and the number skipped.
+fn parse_dictionary(data: &[u8]) -> Result<(Vec<ImportRow>, usize), AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(data);
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::Validation(format!("Invalid CSV headers: {e}")))?
+        .clone();
+    let column = |name: &str| headers.iter().position(|h| h.trim() == name);
+    let (Some(id_col), Some(name_col)) = (column("CWE-ID"), column("Name")) else {
+        return Err(AppError::Validation(
+            "CWE dictionary must have 'CWE-ID' and 'Name' columns".to_string(),
+        ));
+    };
+    let abstraction_col = column("Weakness Abstraction");
+    let status_col = column("Status");
+    let description_col = column("Description");
+    let related_col = column("Related Weaknesses");
+
+    let mut rows = Vec::new();
+    let mut skipped = 0;
+    for result in reader.records() {
+        let record = result.map_err(|e| AppError::Validation(format!("CSV parse error: {e}")))?;
+        let cell = |col: Option<usize>| {
+            col.and_then(|c| record.get(c))
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        let cwe_id = cell(Some(id_col)).as_deref().and_then(parse_cwe_id);
+        let (Some(cwe_id), Some(name)) = (cwe_id, cell(Some(name_col))) else {
+            skipped += 1;
+            continue;
+        };
+        rows.push(ImportRow {
+            cwe_id,
+            name,
+            abstraction: cell(abstraction_col),
+            status: cell(status_col),
+            description: cell(description_col),
+            parent_ids: cell(related_col)
+                .map(|r| research_parents(&r))
+                .unwrap_or_default(),
+        });
+    }
+    Ok((rows, skipped))
+}
+
+/// Insert or update catalog entries from a CWE CSV export.
+pub async fn import(pool: &PgPool, data: &[u8]) -> Result<CweImportResult, AppError> {
+    let (rows, skipped) = parse_dictionary(data)?;
+    if rows.is_empty() {
+        return Err(AppError::Validation(
+            "CWE dictionary has no entries".to_string(),
+        ));
+    }
+
+    // Parent lists are ragged, so they travel as comma-separated text
+    let ids: Vec<i32> = rows.iter().map(|r| r.cwe_id).collect();
+    let names: Vec<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+    let abstractions: Vec<Option<&str>> = rows.iter().map(|r| r.abstraction.as_deref()).collect();
+    let statuses: Vec<Option<&str>> = rows.iter().map(|r| r.status.as_deref()).collect();
+    let descriptions: Vec<Option<&str>> = rows.iter().map(|r| r.description.as_deref()).collect();
+    let parents: Vec<String> = rows
+        .iter()
+        .map(|r| {
+            r.parent_ids
+                .iter()
+                .map(i32::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect();
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO cwe_catalog (cwe_id, name, abstraction, status, description, parent_ids)
+        SELECT DISTINCT ON (cwe_id) cwe_id, name, abstraction, status, description,
+               COALESCE(string_to_array(NULLIF(parents, ''), ',')::int[], '{}')
+        FROM UNNEST($1::int[], $2::text[], $3::text[], $4::text[], $5::text[], $6::text[])
+            AS r(cwe_id, name, abstraction, status, description, parents)
+        ON CONFLICT (cwe_id) DO UPDATE SET
+            name = EXCLUDED.name,
+            abstraction = EXCLUDED.abstraction,
+            status = EXCLUDED.status,
+            description = EXCLUDED.description,
+            parent_ids = EXCLUDED.parent_ids,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(&ids)
+    .bind(&names)
+    .bind(&abstractions)
+    .bind(&statuses)
+    .bind(&descriptions)
+    .bind(&parents)
+    .execute(pool)
+    .await?;
+
+    Ok(CweImportResult {
+        imported: result.rows_affected(),
+        skipped,
+    })
+}
+
+/// Catalog entries matching an optional id or name search, by id.
+pub async fn list(
+    pool: &PgPool,
+    query: &CweQuery,
+    pagination: &Pagination,
+) -> Result<PagedResult<CweSummary>, AppError> {
+    let q = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+    let id = q.and_then(parse_cwe_id);
+    let pattern = q.map(|q| {
+        format!(
+            "%{}%",
+            q.replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        )
+    });
+    let filter = "($1::int IS NULL AND $2::text IS NULL) OR cwe_id = $1 OR name ILIKE $2";
+
+    let total =
+        sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM cwe_catalog WHERE {filter}"))
+            .bind(id)
+            .bind(&pattern)
+            .fetch_one(pool)
+            .await?;
+
+    let items = sqlx::query_as::<_, CweSummary>(&format!(
+        "{SELECT_SUMMARY} WHERE {filter} ORDER BY cwe_id LIMIT $3 OFFSET $4"
+    ))
+    .bind(id)
+    .bind(&pattern)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(items, total, pagination))
+}
+
+/// A CWE with its parents, children, and primary ancestor chain.
+pub async fn get(pool: &PgPool, id: &str) -> Result<CweDetail, AppError> {
+    let cwe_id =
+        parse_cwe_id(id).ok_or_else(|| AppError::Validation(format!("Invalid CWE id '{id}'")))?;
+    let entry = sqlx::query_as::<_, CweEntry>("SELECT * FROM cwe_catalog WHERE cwe_id = $1")
+        .bind(cwe_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("CWE-{cwe_id} is not in the catalog")))?;
+
+    let parents = summaries(pool, &entry.parent_ids).await?;
+    let children = sqlx::query_as::<_, CweSummary>(&format!(
+        "{SELECT_SUMMARY} WHERE $1 = ANY(parent_ids) ORDER BY cwe_id"
+    ))
+    .bind(cwe_id)
+    .fetch_all(pool)
+    .await?;
+    let ancestors = sqlx::query_as::<_, CweSummary>(
+        r#"
+        WITH RECURSIVE chain AS (
+            SELECT parent_ids[1] AS cwe_id, 1 AS depth FROM cwe_catalog WHERE cwe_id = $1
+            UNION ALL
+            SELECT c.parent_ids[1], chain.depth + 1
+            FROM chain JOIN cwe_catalog c ON c.cwe_id = chain.cwe_id
+            WHERE chain.depth < $2
+        )
+        SELECT c.cwe_id, c.name, 'CWE-' || c.cwe_id || ': ' || c.name AS label
+        FROM chain JOIN cwe_catalog c ON c.cwe_id = chain.cwe_id
+        ORDER BY chain.depth
+        "#,
+    )
+    .bind(cwe_id)
+    .bind(MAX_DEPTH)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(CweDetail {
+        label: format!("CWE-{}: {}", entry.cwe_id, entry.name),
+        entry,
+        parents,
+        children,
+        ancestors,
+    })
+}
+
+/// Labels for the given CWE ids that are in the catalog, in the given order.
+pub async fn summaries(pool: &PgPool, ids: &[i32]) -> Result<Vec<CweSummary>, AppError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let found =
+        sqlx::query_as::<_, CweSummary>(&format!("{SELECT_SUMMARY} WHERE cwe_id = ANY($1)"))
+            .bind(ids)
+            .fetch_all(pool)
+            .await?;
+    let mut by_id: HashMap<i32, CweSummary> = found.into_iter().map(|s| (s.cwe_id, s)).collect();
+    Ok(ids.iter().filter_map(|id| by_id.remove(id)).collect())
+}
+
+/// CWE ids of a finding's `cwe_ids` JSON array, skipping malformed entries.
+pub fn ids_from_json(cwe_ids: &serde_json::Value) -> Vec<i32> {
+    let mut ids: Vec<i32> = cwe_ids
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| {
+            v.as_str()
+                .and_then(parse_cwe_id)
+                .or_else(|| v.as_i64()?.try_into().ok())
+        })
+        .collect();
+    ids.dedup();
+    ids
+}
+
+/// Open findings per CWE family, largest first. A finding with several CWEs
+/// in one family counts once there.
+pub async fn family_rollup(
+    pool: &PgPool,
+    query: &CweRollupQuery,
+) -> Result<Vec<CweFamilyCount>, AppError> {
+    let rows = sqlx::query_as::<_, CweFamilyCount>(
+        r#"
+        WITH RECURSIVE finding_cwes AS (
+            SELECT DISTINCT f.id AS finding_id,
+                   substring(c.value FROM '[0-9]+')::int AS cwe_id
+            FROM findings f
+            CROSS JOIN LATERAL jsonb_array_elements_text(f.cwe_ids) AS c(value)
+            WHERE f.archived_at IS NULL
+              AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+              AND ($1::uuid IS NULL OR f.application_id = $1)
+              AND c.value ~ '[0-9]'
+        ),
+        chain AS (
+            SELECT cwe_id AS leaf_id, cwe_id, 0 AS depth
+            FROM (SELECT DISTINCT cwe_id FROM finding_cwes) leaves
+            UNION ALL
+            SELECT chain.leaf_id, c.parent_ids[1], chain.depth + 1
+            FROM chain JOIN cwe_catalog c ON c.cwe_id = chain.cwe_id
+            WHERE cardinality(c.parent_ids) > 0 AND chain.depth < $2
+        ),
+        family AS (
+            SELECT DISTINCT ON (leaf_id) leaf_id, cwe_id AS family_id
+            FROM chain
+            ORDER BY leaf_id, depth DESC
+        )
+        SELECT fam.family_id,
+               c.name AS family_name,
+               COUNT(DISTINCT fc.finding_id) AS findings,
+               array_agg(DISTINCT fc.cwe_id) AS cwe_ids
+        FROM finding_cwes fc
+        JOIN family fam ON fam.leaf_id = fc.cwe_id
+        LEFT JOIN cwe_catalog c ON c.cwe_id = fam.family_id
+        GROUP BY fam.family_id, c.name
+        ORDER BY findings DESC, fam.family_id
+        "#,
+    )
+    .bind(query.application_id)
+    .bind(MAX_DEPTH)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cwe_ids_parse_with_or_without_prefix() {
+        assert_eq!(parse_cwe_id("CWE-89"), Some(89));
+        assert_eq!(parse_cwe_id(" cwe-79 "), Some(79));
+        assert_eq!(parse_cwe_id("352"), Some(352));
+        assert_eq!(parse_cwe_id("CWE-"), None);
+        assert_eq!(parse_cwe_id("NVD-CWE-noinfo"), None);
+        assert_eq!(
+            ids_from_json(&serde_json::json!(["CWE-89", "bogus", 79])),
+            [89, 79]
+        );
+    }
+
+    #[test]
+    fn research_view_parents_put_primary_first() {
+        let related = "::NATURE:ChildOf:CWE ID:74:VIEW ID:1003:ORDINAL:Primary\
+                       ::NATURE:ChildOf:CWE ID:913:VIEW ID:1000\
+                       ::NATURE:ChildOf:CWE ID:74:VIEW ID:1000:ORDINAL:Primary\
+                       ::NATURE:CanFollow:CWE ID:20:VIEW ID:1000::";
+        assert_eq!(research_parents(related), [74, 913]);
+        assert!(research_parents("").is_empty());
+    }
+
+    #[test]
+    fn dictionary_csv_is_parsed_by_header() {
+        let csv = "CWE-ID,Name,Weakness Abstraction,Status,Description,Related Weaknesses,\n\
+                   89,Improper Neutralization of Special Elements used in an SQL Command ('SQL Injection'),Base,Stable,\"The product constructs SQL, badly.\",::NATURE:ChildOf:CWE ID:943:VIEW ID:1000:ORDINAL:Primary::,\n\
+                   ,Missing id,Base,Stable,,,\n";
+        let (rows, skipped) = parse_dictionary(csv.as_bytes()).unwrap();
+        assert_eq!(skipped, 1);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].cwe_id, 89);
+        assert_eq!(rows[0].abstraction.as_deref(), Some("Base"));
+        assert_eq!(
+            rows[0].description.as_deref(),
+            Some("The product constructs SQL, badly.")
+        );
+        assert_eq!(rows[0].parent_ids, [943]);
+    }
+
+    #[test]
+    fn dictionary_without_id_column_is_rejected() {
+        assert!(parse_dictionary(b"Name,Status\nfoo,Stable\n").is_err());
+    }
+}
@@ -1,14 +1,23 @@
 //! Dashboard statistics aggregation queries.
+//!
+//! Finding counts are read from the `mv_finding_counts` and `mv_sla_buckets`
+//! materialized views rather than scanning `findings`. The views are
+//! refreshed by a background job and after each ingestion.
+
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use moka::future::Cache;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::services::redis_store::RedisStore;
 
 /// Aggregated dashboard statistics for the main overview page.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DashboardStats {
     pub triage_count: i64,
     pub unmapped_apps_count: i64,
@@ -17,10 +26,11 @@ pub struct DashboardStats {
     pub recent_ingestions: Vec<RecentIngestion>,
     pub top_risky_apps: Vec<TopRiskyApp>,
     pub findings_by_source: Vec<SourceToolCount>,
+    pub findings_by_owasp: Vec<OwaspCategoryCount>,
 }
 
 /// Open finding counts grouped by normalized severity.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SeverityCounts {
     pub critical: i64,
     pub high: i64,
@@ -30,7 +40,7 @@ pub struct SeverityCounts {
 }
 
 /// Finding counts grouped by SLA status.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SlaSummary {
     pub on_track: i64,
     pub at_risk: i64,
@@ -38,7 +48,7 @@ pub struct SlaSummary {
 }
 
 /// Recent ingestion log entry for the dashboard feed.
-#[derive(Debug, Serialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct RecentIngestion {
     pub id: Uuid,
     pub source_tool: String,
@@ -50,14 +60,24 @@ pub struct RecentIngestion {
 }
 
 /// Open finding count for a single source tool (scanner).
-#[derive(Debug, Serialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct SourceToolCount {
     pub source_tool: String,
     pub count: i64,
 }
 
+/// Open finding count for a single OWASP Top 10 category.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct OwaspCategoryCount {
+    /// Category code, e.g. `A03`.
+    pub category: String,
+    /// 2021 category name; `None` for codes outside the Top 10.
+    pub name: Option<String>,
+    pub count: i64,
+}
+
 /// Application with highest open finding counts.
-#[derive(Debug, Serialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct TopRiskyApp {
     pub id: Uuid,
     pub app_name: String,
@@ -67,9 +87,74 @@ pub struct TopRiskyApp {
     pub high_count: i64,
 }
 
+/// Redis key of the statistics shared between instances.
+const STATS_REDIS_KEY: &str = "dashboard:stats";
+
+/// Short-lived cache for [`get_stats`], so repeated dashboard loads do not
+/// rerun the aggregate queries. Cleared whenever the views are refreshed.
+///
+/// With Redis available, computed statistics are also shared with the other
+/// backend instances for the same TTL.
+///
+/// Cheap to clone; all clones share the same entry.
+#[derive(Clone)]
+pub struct StatsCache {
+    inner: Cache<(), DashboardStats>,
+    redis: Option<RedisStore>,
+    ttl: Duration,
+}
+
+impl std::fmt::Debug for StatsCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatsCache")
+            .field("cached", &self.inner.contains_key(&()))
+            .finish()
+    }
+}
+
+impl StatsCache {
+    pub fn new(ttl: Duration, redis: Option<RedisStore>) -> Self {
+        Self {
+            inner: Cache::builder().max_capacity(1).time_to_live(ttl).build(),
+            redis,
+            ttl,
+        }
+    }
+
+    /// Return cached statistics, computing them on a miss. Concurrent misses
+    /// share a single computation.
+    pub async fn get_or_load(&self, pool: &PgPool) -> Result<DashboardStats, AppError> {
+        self.inner
+            .try_get_with((), self.load(pool))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to load dashboard statistics: {e}")))
+    }
+
+    /// Read the shared copy from Redis, or compute and share it.
+    async fn load(&self, pool: &PgPool) -> Result<DashboardStats, AppError> {
+        let Some(redis) = &self.redis else {
+            return get_stats(pool).await;
+        };
+        if let Some(stats) = redis.get_json(STATS_REDIS_KEY).await {
+            return Ok(stats);
+        }
+        let stats = get_stats(pool).await?;
+        redis.set_json(STATS_REDIS_KEY, &stats, self.ttl).await;
+        Ok(stats)
+    }
+
+    /// Drop the cached statistics so the next request recomputes them.
+    pub async fn invalidate(&self) {
+        self.inner.invalidate(&()).await;
+        if let Some(redis) = &self.redis {
+            redis.delete(STATS_REDIS_KEY).await;
+        }
+    }
+}
+
 /// Fetch all dashboard statistics in parallel queries.
 pub async fn get_stats(pool: &PgPool) -> Result<DashboardStats, AppError> {
-    let (triage_count, unmapped_apps_count, severity_counts, sla_summary, recent_ingestions, top_risky_apps, findings_by_source) = tokio::try_join!(
+    let (triage_count, unmapped_apps_count, severity_counts, sla_summary, recent_ingestions, top_risky_apps, findings_by_source, findings_by_owasp) = tokio::try_join!(
         fetch_triage_count(pool),
         fetch_unmapped_apps_count(pool),
         fetch_severity_counts(pool),
@@ -77,6 +162,7 @@ pub async fn get_stats(pool: &PgPool) -> Result<DashboardStats, AppError> {
         fetch_recent_ingestions(pool),
         fetch_top_risky_apps(pool),
         fetch_findings_by_source(pool),
+        fetch_findings_by_owasp(pool),
     )?;
 
     Ok(DashboardStats {
@@ -87,13 +173,14 @@ pub async fn get_stats(pool: &PgPool) -> Result<DashboardStats, AppError> {
         recent_ingestions,
         top_risky_apps,
         findings_by_source,
+        findings_by_owasp,
     })
 }
 
 /// Count findings awaiting triage (status = 'New').
 async fn fetch_triage_count(pool: &PgPool) -> Result<i64, AppError> {
     let row = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM findings WHERE status = 'New'",
+        "SELECT COALESCE(SUM(finding_count), 0)::bigint FROM mv_finding_counts WHERE status = 'New'",
     )
     .fetch_one(pool)
     .await?;
@@ -110,18 +197,18 @@ async fn fetch_unmapped_apps_count(pool: &PgPool) -> Result<i64, AppError> {
     Ok(row)
 }
 
-/// Count open findings grouped by normalized severity.
+/// Count open findings grouped by effective severity.
 async fn fetch_severity_counts(pool: &PgPool) -> Result<SeverityCounts, AppError> {
     // Use conditional aggregation in a single query for efficiency.
     let row = sqlx::query_as::<_, SeverityRow>(
         r#"
         SELECT
-            COALESCE(SUM(CASE WHEN normalized_severity = 'Critical' THEN 1 ELSE 0 END), 0) AS critical,
-            COALESCE(SUM(CASE WHEN normalized_severity = 'High'     THEN 1 ELSE 0 END), 0) AS high,
-            COALESCE(SUM(CASE WHEN normalized_severity = 'Medium'   THEN 1 ELSE 0 END), 0) AS medium,
-            COALESCE(SUM(CASE WHEN normalized_severity = 'Low'      THEN 1 ELSE 0 END), 0) AS low,
-            COALESCE(SUM(CASE WHEN normalized_severity = 'Info'     THEN 1 ELSE 0 END), 0) AS info
-        FROM findings
+            COALESCE(SUM(finding_count) FILTER (WHERE effective_severity = 'Critical'), 0)::bigint AS critical,
+            COALESCE(SUM(finding_count) FILTER (WHERE effective_severity = 'High'),     0)::bigint AS high,
+            COALESCE(SUM(finding_count) FILTER (WHERE effective_severity = 'Medium'),   0)::bigint AS medium,
+            COALESCE(SUM(finding_count) FILTER (WHERE effective_severity = 'Low'),      0)::bigint AS low,
+            COALESCE(SUM(finding_count) FILTER (WHERE effective_severity = 'Info'),     0)::bigint AS info
+        FROM mv_finding_counts
         WHERE status NOT IN ('Closed', 'Invalidated', 'False_Positive')
         "#,
     )
@@ -152,11 +239,10 @@ async fn fetch_sla_summary(pool: &PgPool) -> Result<SlaSummary, AppError> {
     let row = sqlx::query_as::<_, SlaRow>(
         r#"
         SELECT
-            COALESCE(SUM(CASE WHEN sla_status = 'On_Track' THEN 1 ELSE 0 END), 0) AS on_track,
-            COALESCE(SUM(CASE WHEN sla_status = 'At_Risk'  THEN 1 ELSE 0 END), 0) AS at_risk,
-            COALESCE(SUM(CASE WHEN sla_status = 'Breached'  THEN 1 ELSE 0 END), 0) AS breached
-        FROM findings
-        WHERE sla_status IS NOT NULL
+            COALESCE(SUM(finding_count) FILTER (WHERE sla_status = 'On_Track'), 0)::bigint AS on_track,
+            COALESCE(SUM(finding_count) FILTER (WHERE sla_status = 'At_Risk'),  0)::bigint AS at_risk,
+            COALESCE(SUM(finding_count) FILTER (WHERE sla_status = 'Breached'), 0)::bigint AS breached
+        FROM mv_sla_buckets
         "#,
     )
     .fetch_one(pool)
@@ -200,14 +286,14 @@ async fn fetch_top_risky_apps(pool: &PgPool) -> Result<Vec<TopRiskyApp>, AppErro
             a.id,
             a.app_name,
             a.app_code,
-            COUNT(f.id) AS finding_count,
-            COALESCE(SUM(CASE WHEN f.normalized_severity = 'Critical' THEN 1 ELSE 0 END), 0) AS critical_count,
-            COALESCE(SUM(CASE WHEN f.normalized_severity = 'High'     THEN 1 ELSE 0 END), 0) AS high_count
+            SUM(c.finding_count)::bigint AS finding_count,
+            COALESCE(SUM(c.finding_count) FILTER (WHERE c.effective_severity = 'Critical'), 0)::bigint AS critical_count,
+            COALESCE(SUM(c.finding_count) FILTER (WHERE c.effective_severity = 'High'),     0)::bigint AS high_count
         FROM applications a
-        INNER JOIN findings f ON f.application_id = a.id
-        WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+        INNER JOIN mv_finding_counts c ON c.application_id = a.id
+        WHERE c.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
         GROUP BY a.id, a.app_name, a.app_code
-        ORDER BY COUNT(f.id) DESC
+        ORDER BY SUM(c.finding_count) DESC
         LIMIT 5
         "#,
     )
@@ -220,8 +306,8 @@ async fn fetch_top_risky_apps(pool: &PgPool) -> Result<Vec<TopRiskyApp>, AppErro
 async fn fetch_findings_by_source(pool: &PgPool) -> Result<Vec<SourceToolCount>, AppError> {
     let rows = sqlx::query_as::<_, SourceToolCount>(
         r#"
-        SELECT source_tool, COUNT(*) AS count
-        FROM findings
+        SELECT source_tool, SUM(finding_count)::bigint AS count
+        FROM mv_finding_counts
         WHERE status NOT IN ('Closed', 'False_Positive', 'Invalidated')
         GROUP BY source_tool
         ORDER BY count DESC
@@ -231,3 +317,117 @@ async fn fetch_findings_by_source(pool: &PgPool) -> Result<Vec<SourceToolCount>,
     .await?;
     Ok(rows)
 }
+
+/// Count open findings grouped by OWASP Top 10 category, in category order.
+async fn fetch_findings_by_owasp(pool: &PgPool) -> Result<Vec<OwaspCategoryCount>, AppError> {
+    let rows = sqlx::query_as::<_, OwaspCategoryCount>(
+        r#"
+        SELECT v.category, c.name, SUM(v.finding_count)::bigint AS count
+        FROM mv_owasp_counts v
+        LEFT JOIN owasp_categories c ON owasp_code(c.code) = v.category
+        WHERE v.status NOT IN ('Closed', 'False_Positive', 'Invalidated')
+        GROUP BY v.category, c.name
+        ORDER BY v.category
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Refresh the dashboard materialized views without blocking readers.
+pub async fn refresh_views(pool: &PgPool) -> Result<(), AppError> {
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY mv_finding_counts")
+        .execute(pool)
+        .await?;
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY mv_sla_buckets")
+        .execute(pool)
+        .await?;
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY mv_owasp_counts")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Refresh the views and then drop the cached statistics built from them.
+pub async fn refresh(pool: &PgPool, cache: &StatsCache) {
+    if let Err(e) = refresh_views(pool).await {
+        tracing::error!(error = %e, "Dashboard view refresh failed");
+    }
+    cache.invalidate().await;
+}
+
+/// Job kind that refreshes the views and clears the statistics cache.
+pub const REFRESH_JOB: &str = "dashboard.refresh";
+
+/// Handler for [`REFRESH_JOB`]; a failed refresh is returned so the job retries.
+pub async fn run_refresh_job(pool: PgPool, cache: StatsCache) -> Result<(), AppError> {
+    refresh_views(&pool).await?;
+    cache.invalidate().await;
+    Ok(())
+}
+
+/// Start the background task that refreshes the dashboard views every `interval`.
+pub fn spawn_view_refresher(pool: PgPool, cache: StatsCache, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            refresh(&pool, &cache).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(triage_count: i64) -> DashboardStats {
+        DashboardStats {
+            triage_count,
+            unmapped_apps_count: 0,
+            severity_counts: SeverityCounts {
+                critical: 0,
+                high: 0,
+                medium: 0,
+                low: 0,
+                info: 0,
+            },
+            sla_summary: SlaSummary {
+                on_track: 0,
+                at_risk: 0,
+                breached: 0,
+            },
+            recent_ingestions: Vec::new(),
+            top_risky_apps: Vec::new(),
+            findings_by_source: Vec::new(),
+            findings_by_owasp: Vec::new(),
+        }
+    }
+
+    /// A pool that is never connected: any query through it fails.
+    fn unreachable_pool() -> PgPool {
+        sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_lazy("postgres://synapsec@127.0.0.1:1/unreachable")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn cached_stats_are_served_without_querying() {
+        let cache = StatsCache::new(Duration::from_secs(60), None);
+        cache.inner.insert((), stats(7)).await;
+
+        let cached = cache.get_or_load(&unreachable_pool()).await.unwrap();
+        assert_eq!(cached.triage_count, 7);
+    }
+
+    #[tokio::test]
+    async fn invalidate_forces_a_reload() {
+        let cache = StatsCache::new(Duration::from_secs(60), None);
+        cache.inner.insert((), stats(7)).await;
+        cache.invalidate().await;
+
+        assert!(cache.get_or_load(&unreachable_pool()).await.is_err());
+    }
+}
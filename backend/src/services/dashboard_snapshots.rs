@@ -0,0 +1,448 @@
+//! Nightly snapshots of open-finding counts by severity, status, and
+//! application, kept in `dashboard_snapshots` for month-over-month
+//! comparisons that cannot be reconstructed from live data.
+//!
+//! A background task tries to take today's snapshot every interval; only the
+//! first run after midnight UTC writes rows.
+
+use std::cmp::Reverse;
+
+use chrono::{Duration, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::severity_rank;
+
+/// Series window when no start date is given.
+const DEFAULT_WINDOW_DAYS: i64 = 90;
+
+/// Dimension the snapshot series is broken down by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotGroupBy {
+    #[default]
+    Severity,
+    Status,
+    /// Application code; unmapped findings are grouped under `Unassigned`.
+    Application,
+}
+
+impl SnapshotGroupBy {
+    /// SQL expression producing the group key.
+    fn key_expr(self) -> &'static str {
+        match self {
+            Self::Severity => "s.normalized_severity::text",
+            Self::Status => "s.status::text",
+            Self::Application => "COALESCE(a.app_code, 'Unassigned')",
+        }
+    }
+}
+
+/// Query parameters for the snapshot series.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SnapshotParams {
+    /// Defaults to `severity`.
+    #[serde(default)]
+    pub group_by: SnapshotGroupBy,
+    /// Restrict to one application.
+    pub application_id: Option<Uuid>,
+    /// First day included (defaults to 90 days before `to`).
+    pub from: Option<NaiveDate>,
+    /// Last day included (defaults to today, UTC).
+    pub to: Option<NaiveDate>,
+}
+
+/// Open findings for one group on one day.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SnapshotGroupCount {
+    pub key: String,
+    pub count: i64,
+}
+
+/// All groups for one snapshot day.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotPoint {
+    pub snapshot_date: NaiveDate,
+    pub total: i64,
+    pub groups: Vec<SnapshotGroupCount>,
+}
+
+/// Snapshot series over the requested window.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotSeries {
+    pub group_by: SnapshotGroupBy,
+    pub application_id: Option<Uuid>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub points: Vec<SnapshotPoint>,
+}
+
+/// Query parameters for comparing two snapshots.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CompareParams {
+    /// Restrict to one application.
+    pub application_id: Option<Uuid>,
+    /// Latest snapshot on or before this day is compared against (defaults to today).
+    pub target: Option<NaiveDate>,
+    /// Latest snapshot on or before this day is the baseline (defaults to one
+    /// month before the target snapshot).
+    pub base: Option<NaiveDate>,
+}
+
+/// Open findings for one key in both snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CountChange {
+    pub key: String,
+    pub base: i64,
+    pub target: i64,
+    /// `target - base`.
+    pub change: i64,
+}
+
+/// Differences between two snapshots.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SnapshotComparison {
+    pub application_id: Option<Uuid>,
+    pub base_date: NaiveDate,
+    pub target_date: NaiveDate,
+    pub total: CountChange,
+    pub by_severity: Vec<CountChange>,
+    pub by_status: Vec<CountChange>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SeriesRow {
+    snapshot_date: NaiveDate,
+    key: String,
+    count: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CompareRow {
+    snapshot_date: NaiveDate,
+    severity: String,
+    status: String,
+    count: i64,
+}
+
+/// Load the snapshot series for the requested window.
+pub async fn get_series(
+    pool: &PgPool,
+    params: &SnapshotParams,
+) -> Result<SnapshotSeries, AppError> {
+    let to = params.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = params
+        .from
+        .unwrap_or_else(|| to - Duration::days(DEFAULT_WINDOW_DAYS));
+    if to < from {
+        return Err(AppError::Validation(
+            "'to' must not be earlier than 'from'".to_string(),
+        ));
+    }
+
+    // The key expression comes from a fixed enum, never from user input.
+    let query = format!(
+        r#"
+        SELECT s.snapshot_date, {key} AS key, SUM(s.finding_count)::bigint AS count
+        FROM dashboard_snapshots s
+        LEFT JOIN applications a ON a.id = s.application_id
+        WHERE s.snapshot_date BETWEEN $1 AND $2
+          AND ($3::uuid IS NULL OR s.application_id = $3)
+        GROUP BY 1, 2
+        ORDER BY 1, 3 DESC, 2
+        "#,
+        key = params.group_by.key_expr(),
+    );
+    let rows = sqlx::query_as::<_, SeriesRow>(&query)
+        .bind(from)
+        .bind(to)
+        .bind(params.application_id)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(SnapshotSeries {
+        group_by: params.group_by,
+        application_id: params.application_id,
+        from,
+        to,
+        points: fold_series(rows, params.group_by),
+    })
+}
+
+/// Compare the snapshots nearest to the requested base and target days.
+pub async fn compare(
+    pool: &PgPool,
+    params: &CompareParams,
+) -> Result<SnapshotComparison, AppError> {
+    let target_date = latest_on_or_before(
+        pool,
+        params.target.unwrap_or_else(|| Utc::now().date_naive()),
+    )
+    .await?;
+    let base_day = params.base.unwrap_or_else(|| {
+        target_date
+            .checked_sub_months(Months::new(1))
+            .unwrap_or(target_date)
+    });
+    let base_date = latest_on_or_before(pool, base_day).await?;
+
+    let rows = sqlx::query_as::<_, CompareRow>(
+        r#"
+        SELECT
+            snapshot_date,
+            normalized_severity::text AS severity,
+            status::text AS status,
+            SUM(finding_count)::bigint AS count
+        FROM dashboard_snapshots
+        WHERE snapshot_date IN ($1, $2)
+          AND ($3::uuid IS NULL OR application_id = $3)
+        GROUP BY 1, 2, 3
+        "#,
+    )
+    .bind(base_date)
+    .bind(target_date)
+    .bind(params.application_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(build_comparison(
+        params.application_id,
+        base_date,
+        target_date,
+        &rows,
+    ))
+}
+
+/// Date of the latest snapshot taken on or before `day`.
+async fn latest_on_or_before(pool: &PgPool, day: NaiveDate) -> Result<NaiveDate, AppError> {
+    sqlx::query_scalar::<_, Option<NaiveDate>>(
+        "SELECT MAX(snapshot_date) FROM dashboard_snapshots WHERE snapshot_date <= $1",
+    )
+    .bind(day)
+    .fetch_one(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("No dashboard snapshot on or before {day}")))
+}
+
+/// Group rows (ordered by date) into one point per snapshot day.
+fn fold_series(rows: Vec<SeriesRow>, group_by: SnapshotGroupBy) -> Vec<SnapshotPoint> {
+    let mut points: Vec<SnapshotPoint> = Vec::new();
+    for row in rows {
+        if points.last().map(|p| p.snapshot_date) != Some(row.snapshot_date) {
+            points.push(SnapshotPoint {
+                snapshot_date: row.snapshot_date,
+                total: 0,
+                groups: Vec::new(),
+            });
+        }
+        let point = points.last_mut().expect("point pushed above");
+        point.total += row.count;
+        point.groups.push(SnapshotGroupCount {
+            key: row.key,
+            count: row.count,
+        });
+    }
+    if group_by == SnapshotGroupBy::Severity {
+        for point in &mut points {
+            point.groups.sort_by_key(|g| Reverse(severity_rank(&g.key)));
+        }
+    }
+    points
+}
+
+/// Sum counts per severity and status for each date and pair them up.
+fn build_comparison(
+    application_id: Option<Uuid>,
+    base_date: NaiveDate,
+    target_date: NaiveDate,
+    rows: &[CompareRow],
+) -> SnapshotComparison {
+    let mut total = change("total");
+    let mut by_severity: Vec<CountChange> = Vec::new();
+    let mut by_status: Vec<CountChange> = Vec::new();
+
+    for row in rows {
+        let counts = [
+            &mut total,
+            entry(&mut by_severity, &row.severity),
+            entry(&mut by_status, &row.status),
+        ];
+        for c in counts {
+            // When both dates resolve to the same snapshot, it is both sides.
+            if row.snapshot_date == base_date {
+                c.base += row.count;
+            }
+            if row.snapshot_date == target_date {
+                c.target += row.count;
+            }
+            c.change = c.target - c.base;
+        }
+    }
+
+    by_severity.sort_by_key(|c| Reverse(severity_rank(&c.key)));
+    by_status.sort_by(|a, b| b.target.cmp(&a.target).then_with(|| a.key.cmp(&b.key)));
+
+    SnapshotComparison {
+        application_id,
+        base_date,
+        target_date,
+        total,
+        by_severity,
+        by_status,
+    }
+}
+
+fn change(key: &str) -> CountChange {
+    CountChange {
+        key: key.to_string(),
+        base: 0,
+        target: 0,
+        change: 0,
+    }
+}
+
+fn entry<'a>(changes: &'a mut Vec<CountChange>, key: &str) -> &'a mut CountChange {
+    match changes.iter().position(|c| c.key == key) {
+        Some(i) => &mut changes[i],
+        None => {
+            changes.push(change(key));
+            changes.last_mut().expect("entry pushed above")
+        }
+    }
+}
+
+/// Record open-finding counts for `date` unless that day already has a snapshot.
+/// Counts are bucketed by effective severity, overrides included.
+pub async fn capture_snapshot(pool: &PgPool, date: NaiveDate) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO dashboard_snapshots
+            (snapshot_date, normalized_severity, status, application_id, finding_count)
+        SELECT $1, effective_severity, status, application_id, COUNT(*)
+        FROM findings
+        WHERE status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND NOT EXISTS (SELECT 1 FROM dashboard_snapshots WHERE snapshot_date = $1)
+        GROUP BY effective_severity, status, application_id
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(date)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Spawn the background task that takes today's snapshot once it is due.
+pub fn spawn_snapshotter(pool: PgPool, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let today = Utc::now().date_naive();
+            match capture_snapshot(&pool, today).await {
+                Ok(0) => {}
+                Ok(rows) => tracing::info!(%today, rows, "Dashboard snapshot captured"),
+                Err(e) => tracing::error!(error = %e, "Dashboard snapshot failed"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn series_row(day: NaiveDate, key: &str, count: i64) -> SeriesRow {
+        SeriesRow {
+            snapshot_date: day,
+            key: key.to_string(),
+            count,
+        }
+    }
+
+    fn compare_row(day: NaiveDate, severity: &str, status: &str, count: i64) -> CompareRow {
+        CompareRow {
+            snapshot_date: day,
+            severity: severity.to_string(),
+            status: status.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn folds_series_by_day_in_severity_order() {
+        let points = fold_series(
+            vec![
+                series_row(date(2026, 9, 1), "Low", 9),
+                series_row(date(2026, 9, 1), "Critical", 2),
+                series_row(date(2026, 9, 2), "High", 4),
+            ],
+            SnapshotGroupBy::Severity,
+        );
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].total, 11);
+        assert_eq!(points[0].groups[0].key, "Critical");
+        assert_eq!(
+            points[1].groups,
+            [SnapshotGroupCount {
+                key: "High".to_string(),
+                count: 4
+            }]
+        );
+    }
+
+    #[test]
+    fn compares_month_over_month() {
+        let base = date(2026, 8, 15);
+        let target = date(2026, 9, 15);
+        let comparison = build_comparison(
+            None,
+            base,
+            target,
+            &[
+                compare_row(base, "High", "New", 10),
+                compare_row(base, "Critical", "Confirmed", 3),
+                compare_row(target, "High", "New", 6),
+                compare_row(target, "High", "Confirmed", 2),
+            ],
+        );
+
+        assert_eq!(comparison.total.base, 13);
+        assert_eq!(comparison.total.target, 8);
+        assert_eq!(comparison.total.change, -5);
+
+        let keys: Vec<&str> = comparison
+            .by_severity
+            .iter()
+            .map(|c| c.key.as_str())
+            .collect();
+        assert_eq!(keys, ["Critical", "High"]);
+        assert_eq!(comparison.by_severity[0].change, -3);
+        assert_eq!(comparison.by_severity[1].change, -2);
+
+        let confirmed = comparison
+            .by_status
+            .iter()
+            .find(|c| c.key == "Confirmed")
+            .unwrap();
+        assert_eq!((confirmed.base, confirmed.target), (3, 2));
+    }
+
+    #[test]
+    fn same_snapshot_on_both_sides_has_no_change() {
+        let day = date(2026, 9, 1);
+        let comparison = build_comparison(None, day, day, &[compare_row(day, "Low", "New", 5)]);
+        assert_eq!(comparison.total.base, 5);
+        assert_eq!(comparison.total.target, 5);
+        assert_eq!(comparison.total.change, 0);
+    }
+}
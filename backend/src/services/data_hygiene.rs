@@ -0,0 +1,370 @@
+//! Referential hygiene checks.
+//!
+//! Foreign keys keep rows from pointing at rows that no longer exist, but
+//! bulk operations (application merges and decommissioning, finding merges,
+//! splits and reassignments, hard deletes) can still leave data nothing
+//! points at meaningfully: open findings of decommissioned applications,
+//! relationships nobody can see, split records listing deleted findings,
+//! category rows in the wrong category table. The report lists each kind of
+//! issue with the affected ids; the fix repairs the kinds that have a safe
+//! repair and records it in the audit log.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Ids listed per issue in a report.
+pub const SAMPLE_SIZE: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HygieneIssueKind {
+    /// Open findings of a decommissioned application; fixed by archiving them.
+    DecommissionedApplication,
+    /// Relationships of a finding with itself; fixed by deleting them.
+    SelfRelationship,
+    /// Relationships between two archived findings, outside an active merge;
+    /// fixed by deleting them.
+    ArchivedRelationship,
+    /// Split records listing deleted findings; fixed by dropping those ids.
+    MissingSplitPart,
+    /// Findings with a row in another category's table; fixed by deleting
+    /// the stray rows.
+    MismatchedCategoryRow,
+    /// Findings without a row in their category's table. Report only: the
+    /// data can only come back from a re-scan.
+    MissingCategoryRow,
+}
+
+impl HygieneIssueKind {
+    pub const ALL: [Self; 6] = [
+        Self::DecommissionedApplication,
+        Self::SelfRelationship,
+        Self::ArchivedRelationship,
+        Self::MissingSplitPart,
+        Self::MismatchedCategoryRow,
+        Self::MissingCategoryRow,
+    ];
+
+    pub fn fixable(self) -> bool {
+        !matches!(self, Self::MissingCategoryRow)
+    }
+
+    /// What the ids of the issue identify.
+    fn entity(self) -> &'static str {
+        match self {
+            Self::SelfRelationship | Self::ArchivedRelationship => "relationship",
+            Self::MissingSplitPart => "finding_split",
+            _ => "finding",
+        }
+    }
+
+    /// Query returning the affected ids.
+    fn detect_sql(self) -> &'static str {
+        match self {
+            Self::DecommissionedApplication => {
+                r#"
+                SELECT f.id FROM findings f
+                JOIN applications a ON a.id = f.application_id
+                WHERE a.status = 'Decommissioned'
+                  AND f.archived_at IS NULL
+                  AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+                ORDER BY f.id
+                "#
+            }
+            Self::SelfRelationship => {
+                r#"
+                SELECT id FROM finding_relationships
+                WHERE source_finding_id = target_finding_id
+                ORDER BY id
+                "#
+            }
+            Self::ArchivedRelationship => {
+                r#"
+                SELECT r.id FROM finding_relationships r
+                JOIN findings s ON s.id = r.source_finding_id
+                JOIN findings t ON t.id = r.target_finding_id
+                WHERE s.archived_at IS NOT NULL AND t.archived_at IS NOT NULL
+                  AND NOT EXISTS (
+                      SELECT 1 FROM finding_merges m
+                      WHERE m.unmerged_at IS NULL
+                        AND ARRAY[m.source_finding_id, m.target_finding_id]
+                            @> ARRAY[r.source_finding_id, r.target_finding_id]
+                  )
+                ORDER BY r.id
+                "#
+            }
+            Self::MissingSplitPart => {
+                r#"
+                SELECT sp.id FROM finding_splits sp
+                WHERE EXISTS (
+                    SELECT 1 FROM UNNEST(sp.split_finding_ids) AS part(id)
+                    WHERE NOT EXISTS (SELECT 1 FROM findings f WHERE f.id = part.id)
+                )
+                ORDER BY sp.id
+                "#
+            }
+            Self::MismatchedCategoryRow => {
+                r#"
+                SELECT f.id FROM findings f
+                WHERE (f.finding_category <> 'SAST'
+                       AND EXISTS (SELECT 1 FROM finding_sast c WHERE c.finding_id = f.id))
+                   OR (f.finding_category <> 'SCA'
+                       AND EXISTS (SELECT 1 FROM finding_sca c WHERE c.finding_id = f.id))
+                   OR (f.finding_category <> 'DAST'
+                       AND EXISTS (SELECT 1 FROM finding_dast c WHERE c.finding_id = f.id))
+                   OR (f.finding_category <> 'MANUAL'
+                       AND EXISTS (SELECT 1 FROM finding_manual c WHERE c.finding_id = f.id))
+                ORDER BY f.id
+                "#
+            }
+            Self::MissingCategoryRow => {
+                r#"
+                SELECT f.id FROM findings f
+                WHERE NOT CASE f.finding_category
+                    WHEN 'SAST' THEN EXISTS (SELECT 1 FROM finding_sast c WHERE c.finding_id = f.id)
+                    WHEN 'SCA' THEN EXISTS (SELECT 1 FROM finding_sca c WHERE c.finding_id = f.id)
+                    WHEN 'DAST' THEN EXISTS (SELECT 1 FROM finding_dast c WHERE c.finding_id = f.id)
+                    ELSE EXISTS (SELECT 1 FROM finding_manual c WHERE c.finding_id = f.id)
+                END
+                ORDER BY f.id
+                "#
+            }
+        }
+    }
+}
+
+/// One kind of issue found by a hygiene check.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HygieneIssue {
+    pub kind: HygieneIssueKind,
+    /// `finding`, `relationship` or `finding_split`.
+    pub entity: String,
+    pub count: usize,
+    pub fixable: bool,
+    /// Up to [`SAMPLE_SIZE`] affected ids.
+    pub sample_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HygieneReport {
+    pub generated_at: DateTime<Utc>,
+    /// Every kind of issue checked, including those with no occurrence.
+    pub issues: Vec<HygieneIssue>,
+}
+
+/// Issues to repair.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct HygieneFixRequest {
+    /// Kinds to fix; empty fixes every fixable kind.
+    #[serde(default)]
+    pub kinds: Vec<HygieneIssueKind>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HygieneFix {
+    pub kind: HygieneIssueKind,
+    /// Rows repaired.
+    pub fixed: u64,
+}
+
+/// The kinds a fix request covers, in check order.
+fn kinds_to_fix(requested: &[HygieneIssueKind]) -> Result<Vec<HygieneIssueKind>, AppError> {
+    if let Some(kind) = requested.iter().find(|k| !k.fixable()) {
+        return Err(AppError::Validation(format!(
+            "{} issues cannot be fixed automatically",
+            serde_json::to_value(kind)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default()
+        )));
+    }
+    Ok(HygieneIssueKind::ALL
+        .into_iter()
+        .filter(|k| k.fixable() && (requested.is_empty() || requested.contains(k)))
+        .collect())
+}
+
+async fn detect(conn: &mut PgConnection, kind: HygieneIssueKind) -> Result<Vec<Uuid>, AppError> {
+    Ok(sqlx::query_scalar::<_, Uuid>(kind.detect_sql())
+        .fetch_all(conn)
+        .await?)
+}
+
+/// Check every kind of issue.
+pub async fn report(pool: &PgPool) -> Result<HygieneReport, AppError> {
+    let mut conn = pool.acquire().await?;
+    let mut issues = Vec::with_capacity(HygieneIssueKind::ALL.len());
+    for kind in HygieneIssueKind::ALL {
+        let ids = detect(&mut conn, kind).await?;
+        issues.push(HygieneIssue {
+            kind,
+            entity: kind.entity().to_string(),
+            count: ids.len(),
+            fixable: kind.fixable(),
+            sample_ids: ids.into_iter().take(SAMPLE_SIZE).collect(),
+        });
+    }
+    Ok(HygieneReport {
+        generated_at: Utc::now(),
+        issues,
+    })
+}
+
+/// Repair the rows `ids` affected by `kind`. Returns the number repaired.
+async fn repair(
+    conn: &mut PgConnection,
+    kind: HygieneIssueKind,
+    ids: &[Uuid],
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<u64, AppError> {
+    let fixed = match kind {
+        HygieneIssueKind::DecommissionedApplication => {
+            let archived = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                UPDATE findings SET archived_at = NOW(), updated_at = NOW()
+                WHERE id = ANY($1) AND archived_at IS NULL
+                RETURNING id
+                "#,
+            )
+            .bind(ids)
+            .fetch_all(&mut *conn)
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name, justification)
+                SELECT id, 'archived', 'archived_at', NULL, NOW()::text, $2, $3, 'Application decommissioned'
+                FROM UNNEST($1::uuid[]) AS id
+                "#,
+            )
+            .bind(&archived)
+            .bind(actor_id)
+            .bind(actor_name)
+            .execute(&mut *conn)
+            .await?;
+            archived.len() as u64
+        }
+        HygieneIssueKind::SelfRelationship | HygieneIssueKind::ArchivedRelationship => {
+            sqlx::query("DELETE FROM finding_relationships WHERE id = ANY($1)")
+                .bind(ids)
+                .execute(&mut *conn)
+                .await?
+                .rows_affected()
+        }
+        HygieneIssueKind::MissingSplitPart => sqlx::query(
+            r#"
+            UPDATE finding_splits sp SET split_finding_ids = ARRAY(
+                SELECT part.id FROM UNNEST(sp.split_finding_ids) WITH ORDINALITY AS part(id, n)
+                WHERE EXISTS (SELECT 1 FROM findings f WHERE f.id = part.id)
+                ORDER BY part.n
+            )
+            WHERE sp.id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected(),
+        HygieneIssueKind::MismatchedCategoryRow => {
+            let mut deleted = 0;
+            for (table, category) in [
+                ("finding_sast", "SAST"),
+                ("finding_sca", "SCA"),
+                ("finding_dast", "DAST"),
+                ("finding_manual", "MANUAL"),
+            ] {
+                deleted += sqlx::query(&format!(
+                    "DELETE FROM {table} c USING findings f \
+                     WHERE f.id = c.finding_id AND c.finding_id = ANY($1) \
+                       AND f.finding_category <> '{category}'"
+                ))
+                .bind(ids)
+                .execute(&mut *conn)
+                .await?
+                .rows_affected();
+            }
+            deleted
+        }
+        HygieneIssueKind::MissingCategoryRow => 0,
+    };
+    Ok(fixed)
+}
+
+/// Repair the requested kinds of issue (every fixable kind when none is
+/// given) in one transaction, recording what was done in the audit log.
+pub async fn fix(
+    pool: &PgPool,
+    input: &HygieneFixRequest,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<Vec<HygieneFix>, AppError> {
+    let kinds = kinds_to_fix(&input.kinds)?;
+    let mut tx = pool.begin().await?;
+
+    let mut fixes = Vec::with_capacity(kinds.len());
+    for kind in kinds {
+        let ids = detect(&mut tx, kind).await?;
+        let fixed = if ids.is_empty() {
+            0
+        } else {
+            repair(&mut tx, kind, &ids, actor_id, actor_name).await?
+        };
+        fixes.push(HygieneFix { kind, fixed });
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('data_hygiene', NULL, 'data_hygiene_fix', $1, $2, $3)
+        "#,
+    )
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({ "fixes": fixes }))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(fixes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_request_fixes_every_fixable_kind() {
+        let kinds = kinds_to_fix(&[]).unwrap();
+        assert_eq!(kinds.len(), HygieneIssueKind::ALL.len() - 1);
+        assert!(!kinds.contains(&HygieneIssueKind::MissingCategoryRow));
+    }
+
+    #[test]
+    fn requested_kinds_are_fixed_in_check_order() {
+        let kinds = kinds_to_fix(&[
+            HygieneIssueKind::MismatchedCategoryRow,
+            HygieneIssueKind::SelfRelationship,
+        ])
+        .unwrap();
+        assert_eq!(
+            kinds,
+            vec![
+                HygieneIssueKind::SelfRelationship,
+                HygieneIssueKind::MismatchedCategoryRow
+            ]
+        );
+    }
+
+    #[test]
+    fn report_only_kinds_cannot_be_fixed() {
+        let err = kinds_to_fix(&[HygieneIssueKind::MissingCategoryRow]).unwrap_err();
+        assert!(
+            matches!(err, AppError::Validation(ref m) if m.starts_with("missing_category_row"))
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Data subject pseudonymization.
+//!
+//! An erasure request cannot delete the findings, comments, history and audit
+//! entries a person appears in without breaking the record of what happened
+//! to each finding. Instead every name and email the person appears under is
+//! replaced by a pseudonym: whole-value columns (owners, actor and author
+//! names) when they match an identifier, free text (comments, history
+//! values and justifications, notification texts, audit details) where it
+//! mentions one. The same pseudonym is used
+//! everywhere, and kept for later requests about the same person, so the
+//! trail still shows one actor doing the same things. Only the pseudonym is
+//! stored; nothing maps it back to the person.
+
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Shortest identifier accepted: shorter ones would match unrelated text.
+const MIN_IDENTIFIER_LEN: usize = 3;
+
+/// Columns holding a single name or email, replaced when the whole value
+/// matches an identifier.
+const EXACT_COLUMNS: [(&str, &str); 14] = [
+    ("applications", "business_owner"),
+    ("applications", "technical_owner"),
+    ("applications", "functional_reference_email"),
+    ("applications", "technical_reference_email"),
+    ("applications", "effective_office_owner"),
+    ("findings", "remediation_owner"),
+    ("findings", "office_owner"),
+    ("findings", "office_manager"),
+    ("finding_manual", "reporter"),
+    ("finding_history", "actor_name"),
+    ("finding_comments", "author_name"),
+    ("audit_log", "actor_name"),
+    ("notifications", "actor_name"),
+    ("notification_emails", "actor_name"),
+];
+
+/// Free text columns, where every mention of an identifier is replaced.
+/// History values hold whole comment bodies for comment edits and
+/// deletions, so they are rewritten as text; a value that is just a name is
+/// still replaced whole.
+const TEXT_COLUMNS: [(&str, &str); 8] = [
+    ("finding_comments", "content"),
+    ("finding_history", "old_value"),
+    ("finding_history", "new_value"),
+    ("finding_history", "justification"),
+    ("notifications", "title"),
+    ("notifications", "body"),
+    ("notification_emails", "title"),
+    ("notification_emails", "body"),
+];
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PseudonymizeRequest {
+    /// Names, usernames and emails the person appears under, matched
+    /// case-insensitively.
+    #[serde(default)]
+    pub identifiers: Vec<String>,
+    /// The person's account: its username, email and display name are added
+    /// to the identifiers, and the account is renamed and deactivated.
+    pub user_id: Option<Uuid>,
+    /// Pseudonym given by an earlier request for the same person, to extend
+    /// it to identifiers missed the first time.
+    pub pseudonym: Option<String>,
+    /// Reference of the erasure request. Recorded in the audit log, so it
+    /// must not name the person.
+    pub justification: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PseudonymizedColumn {
+    pub table: String,
+    pub column: String,
+    pub rows: u64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PseudonymizeResult {
+    pub data_subject_id: Uuid,
+    pub pseudonym: String,
+    /// Distinct identifiers replaced.
+    pub identifiers: usize,
+    /// Rows changed per column, for columns where anything matched.
+    pub replaced: Vec<PseudonymizedColumn>,
+}
+
+/// The normalized form of an identifier, or why it cannot be matched safely.
+fn check_identifier(value: &str) -> Result<String, AppError> {
+    let value = value.trim().to_lowercase();
+    if value.chars().count() < MIN_IDENTIFIER_LEN {
+        return Err(AppError::Validation(format!(
+            "Identifiers must be at least {MIN_IDENTIFIER_LEN} characters long"
+        )));
+    }
+    if value.chars().count() > 255 {
+        return Err(AppError::Validation(
+            "Identifiers must be at most 255 characters long".to_string(),
+        ));
+    }
+    // Audit details are rewritten as JSON text: quotes and backslashes are
+    // escaped there and would not match, or would break the document.
+    if value.contains(['"', '\\']) {
+        return Err(AppError::Validation(
+            "Identifiers must not contain quotes or backslashes".to_string(),
+        ));
+    }
+    Ok(value)
+}
+
+/// Add `value` to `identifiers` unless already present.
+fn push_unique(identifiers: &mut Vec<String>, value: String) {
+    if !identifiers.contains(&value) {
+        identifiers.push(value);
+    }
+}
+
+/// Case-insensitive Postgres regular expression matching any identifier as
+/// a whole word, longest first so an email wins over the name inside it.
+fn mention_pattern(identifiers: &[String]) -> String {
+    let mut sorted: Vec<&String> = identifiers.iter().collect();
+    sorted.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    let alternatives: Vec<String> = sorted
+        .iter()
+        .map(|identifier| {
+            identifier
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() || c == ' ' {
+                        c.to_string()
+                    } else {
+                        format!("\\{c}")
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    format!(
+        "(?<![[:alnum:]_])(?:{})(?![[:alnum:]_])",
+        alternatives.join("|")
+    )
+}
+
+fn new_pseudonym() -> String {
+    let id = Uuid::new_v4().simple().to_string();
+    format!("subject-{}", &id[..12])
+}
+
+/// The data subject the request is about: the one named by its pseudonym or
+/// its account, or a new one.
+async fn resolve_subject(
+    conn: &mut PgConnection,
+    input: &PseudonymizeRequest,
+    actor_id: Uuid,
+) -> Result<(Uuid, String), AppError> {
+    let by_pseudonym = match input.pseudonym.as_deref().map(str::trim) {
+        Some(pseudonym) => Some(
+            sqlx::query_as::<_, (Uuid, String, Option<Uuid>)>(
+                "SELECT id, pseudonym, user_id FROM data_subjects WHERE pseudonym = $1",
+            )
+            .bind(pseudonym)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Pseudonym {pseudonym} not found")))?,
+        ),
+        None => None,
+    };
+    let by_user = match input.user_id {
+        Some(user_id) => {
+            sqlx::query_as::<_, (Uuid, String)>(
+                "SELECT id, pseudonym FROM data_subjects WHERE user_id = $1",
+            )
+            .bind(user_id)
+            .fetch_optional(&mut *conn)
+            .await?
+        }
+        None => None,
+    };
+
+    match (by_pseudonym, by_user) {
+        (Some((id, _, _)), Some((user_subject, _))) if id != user_subject => Err(
+            AppError::Conflict("The account was pseudonymized under another pseudonym".to_string()),
+        ),
+        (Some((_, _, Some(linked_user))), _) if input.user_id.is_some_and(|u| u != linked_user) => {
+            Err(AppError::Conflict(
+                "The pseudonym belongs to another account".to_string(),
+            ))
+        }
+        (Some((id, pseudonym, linked_user)), _) => {
+            if linked_user.is_none() && input.user_id.is_some() {
+                sqlx::query("UPDATE data_subjects SET user_id = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(input.user_id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            Ok((id, pseudonym))
+        }
+        (None, Some(subject)) => Ok(subject),
+        (None, None) => {
+            let pseudonym = new_pseudonym();
+            let id = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                INSERT INTO data_subjects (pseudonym, user_id, created_by)
+                VALUES ($1, $2, $3)
+                RETURNING id
+                "#,
+            )
+            .bind(&pseudonym)
+            .bind(input.user_id)
+            .bind(actor_id)
+            .fetch_one(&mut *conn)
+            .await?;
+            Ok((id, pseudonym))
+        }
+    }
+}
+
+/// Replace every name and email of a person by a stable pseudonym, in one
+/// transaction. The operation is recorded in the audit log under the
+/// pseudonym only.
+pub async fn pseudonymize(
+    pool: &PgPool,
+    input: &PseudonymizeRequest,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<PseudonymizeResult, AppError> {
+    if input.justification.trim().is_empty() {
+        return Err(AppError::Validation(
+            "justification must not be blank".to_string(),
+        ));
+    }
+    let mut identifiers = Vec::new();
+    for value in &input.identifiers {
+        push_unique(&mut identifiers, check_identifier(value)?);
+    }
+    if identifiers.is_empty() && input.user_id.is_none() {
+        return Err(AppError::Validation(
+            "Give the person's identifiers or account".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+    let (data_subject_id, pseudonym) = resolve_subject(&mut tx, input, actor_id).await?;
+
+    if let Some(user_id) = input.user_id {
+        let (username, email, display_name) = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT username, email, display_name FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User {user_id} not found")))?;
+        // Account values too short to match safely are still renamed below.
+        for value in [username, email, display_name] {
+            if let Ok(value) = check_identifier(&value) {
+                push_unique(&mut identifiers, value);
+            }
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET username = $2, email = $2 || '@pseudonymized.invalid',
+                display_name = $2, is_active = false
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(&pseudonym)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // An account pseudonymized before already carries the pseudonym.
+    let own_email = format!("{pseudonym}@pseudonymized.invalid");
+    identifiers.retain(|value| *value != pseudonym && *value != own_email);
+
+    let mut replaced = Vec::new();
+    if !identifiers.is_empty() {
+        let pattern = mention_pattern(&identifiers);
+        for (table, column) in EXACT_COLUMNS {
+            let rows = sqlx::query(&format!(
+                "UPDATE {table} SET {column} = $2 WHERE lower(trim({column})) = ANY($1)"
+            ))
+            .bind(&identifiers)
+            .bind(&pseudonym)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            push_count(&mut replaced, table, column, rows);
+        }
+        for (table, column) in TEXT_COLUMNS {
+            let rows = sqlx::query(&format!(
+                "UPDATE {table} SET {column} = regexp_replace({column}, $1, $2, 'gi') \
+                 WHERE {column} ~* $1"
+            ))
+            .bind(&pattern)
+            .bind(&pseudonym)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            push_count(&mut replaced, table, column, rows);
+        }
+        let rows = sqlx::query(
+            r#"
+            UPDATE audit_log
+            SET details = regexp_replace(details::text, $1, $2, 'gi')::jsonb
+            WHERE details::text ~* $1
+            "#,
+        )
+        .bind(&pattern)
+        .bind(&pseudonym)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        push_count(&mut replaced, "audit_log", "details", rows);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('data_subject', $1, 'data_subject_pseudonymized', $2, $3, $4)
+        "#,
+    )
+    .bind(data_subject_id)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({
+        "pseudonym": pseudonym,
+        "justification": input.justification.trim(),
+        "account": input.user_id.is_some(),
+        "identifiers": identifiers.len(),
+        "replaced": replaced,
+    }))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(PseudonymizeResult {
+        data_subject_id,
+        pseudonym,
+        identifiers: identifiers.len(),
+        replaced,
+    })
+}
+
+fn push_count(replaced: &mut Vec<PseudonymizedColumn>, table: &str, column: &str, rows: u64) {
+    if rows > 0 {
+        replaced.push(PseudonymizedColumn {
+            table: table.to_string(),
+            column: column.to_string(),
+            rows,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_are_normalized_and_checked() {
+        assert_eq!(
+            check_identifier("  Jane.Doe@Example.com ").unwrap(),
+            "jane.doe@example.com"
+        );
+        assert!(matches!(
+            check_identifier("jd"),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            check_identifier("jane \"jd\" doe"),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn pattern_escapes_identifiers_and_prefers_longer_ones() {
+        let identifiers = vec!["jane doe".to_string(), "jane.doe@example.com".to_string()];
+        assert_eq!(
+            mention_pattern(&identifiers),
+            "(?<![[:alnum:]_])(?:jane\\.doe\\@example\\.com|jane doe)(?![[:alnum:]_])"
+        );
+    }
+
+    #[test]
+    fn pseudonyms_are_opaque_and_distinct() {
+        let pseudonym = new_pseudonym();
+        assert!(pseudonym.starts_with("subject-"));
+        assert_eq!(pseudonym.len(), "subject-".len() + 12);
+        assert_ne!(pseudonym, new_pseudonym());
+    }
+}
@@ -1,19 +1,25 @@
 //! Deduplication dashboard service for reviewing cross-tool duplicate pairs.
 //!
 //! Provides statistics, pending-review listings, decision history, and
-//! confirm/reject actions for finding relationships flagged as duplicates.
+//! confirm/reject actions for finding relationships flagged as duplicates,
+//! plus effectiveness metrics drawn from ingestion logs and the
+//! `finding_occurrences` table. Every decision is kept with the matcher and
+//! score that suggested the pair, so review metrics show how often each
+//! matcher's suggestions are accepted and where thresholds should sit.
 //! Kept separate from `deduplication.rs` which handles intra-tool dedup.
 
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::similarity;
 
 /// Aggregate statistics for the deduplication dashboard.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct DedupStats {
     pub total_duplicate_relationships: i64,
     pub pending_review: i64,
@@ -23,8 +29,59 @@ pub struct DedupStats {
     pub last_ingestion_at: Option<DateTime<Utc>>,
 }
 
+/// Duplicate rate of one scanner across all its ingestions.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ToolDuplicateRate {
+    pub source_tool: String,
+    pub ingestions: i64,
+    pub total_records: i64,
+    /// Records that matched an existing open finding by fingerprint.
+    pub duplicates: i64,
+    /// Records that matched a closed finding and reopened it.
+    pub reopened: i64,
+    pub duplicate_rate_pct: Option<f64>,
+}
+
+/// How often the same CVE is reported by more than one scanner.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CveOverlap {
+    pub distinct_cves: i64,
+    pub found_by_multiple_tools: i64,
+    pub overlap_pct: Option<f64>,
+    /// Number of CVEs per count of distinct tools reporting them.
+    pub by_tool_count: Vec<ToolCountBucket>,
+}
+
+/// CVEs reported by exactly `tool_count` distinct tools.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ToolCountBucket {
+    pub tool_count: i64,
+    pub cves: i64,
+}
+
+/// Fingerprint collision statistics.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct FingerprintCollisions {
+    pub total_occurrences: i64,
+    /// Extra records in a single ingestion that shared a fingerprint with an
+    /// earlier record of the same file.
+    pub intra_ingestion_collisions: i64,
+    /// Fingerprints produced by more than one scanner.
+    pub cross_tool_fingerprints: i64,
+    /// Fingerprints held by more than one finding.
+    pub shared_by_multiple_findings: i64,
+}
+
+/// Deduplication effectiveness metrics.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DedupEffectiveness {
+    pub tools: Vec<ToolDuplicateRate>,
+    pub cve_overlap: CveOverlap,
+    pub collisions: FingerprintCollisions,
+}
+
 /// A duplicate-pair awaiting analyst review.
-#[derive(Debug, Serialize, FromRow)]
+#[derive(Debug, Serialize, FromRow, ToSchema)]
 pub struct PendingReview {
     pub relationship_id: Uuid,
     pub source_finding_id: Uuid,
@@ -34,11 +91,47 @@ pub struct PendingReview {
     pub target_title: String,
     pub target_tool: String,
     pub confidence: Option<String>,
+    /// Matcher that suggested the pair: a cross-tool heuristic key or
+    /// `similarity`.
+    pub match_source: Option<String>,
+    /// Similarity score of the pair, for fuzzy matches.
+    pub match_score: Option<f32>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Reviewer decisions on the pairs suggested by one matcher.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MatchSourceDecisions {
+    /// Heuristic key, `similarity`, or `unspecified` for pairs recorded
+    /// before matchers were tracked.
+    pub match_source: String,
+    pub pending: i64,
+    pub accepted: i64,
+    pub rejected: i64,
+    pub acceptance_rate_pct: Option<f64>,
+}
+
+/// Reviewer decisions on similarity matches scoring within a band.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScoreBandDecisions {
+    pub min_score: f64,
+    pub max_score: f64,
+    pub accepted: i64,
+    pub rejected: i64,
+    pub acceptance_rate_pct: Option<f64>,
+}
+
+/// Review queue outcomes, for tuning heuristic actions and similarity
+/// thresholds.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewMetrics {
+    pub by_source: Vec<MatchSourceDecisions>,
+    /// Similarity decisions in bands of 0.1, lowest first.
+    pub similarity_score_bands: Vec<ScoreBandDecisions>,
+}
+
 /// Audit trail entry for a confirm or reject decision.
-#[derive(Debug, Serialize, FromRow)]
+#[derive(Debug, Serialize, FromRow, ToSchema)]
 pub struct DedupDecision {
     pub id: Uuid,
     pub finding_id: Uuid,
@@ -71,6 +164,22 @@ pub async fn get_stats(pool: &PgPool) -> Result<DedupStats, AppError> {
     })
 }
 
+/// Fetch per-tool duplicate rates, cross-tool CVE overlap, and fingerprint
+/// collision statistics.
+pub async fn get_effectiveness(pool: &PgPool) -> Result<DedupEffectiveness, AppError> {
+    let (tools, by_tool_count, collisions) = tokio::try_join!(
+        fetch_tool_duplicate_rates(pool),
+        fetch_cve_tool_counts(pool),
+        fetch_fingerprint_collisions(pool),
+    )?;
+
+    Ok(DedupEffectiveness {
+        tools,
+        cve_overlap: cve_overlap(by_tool_count),
+        collisions,
+    })
+}
+
 /// List duplicate pairs pending analyst review, paginated.
 pub async fn list_pending(
     pool: &PgPool,
@@ -98,6 +207,8 @@ pub async fn list_pending(
             tf.title        AS target_title,
             tf.source_tool  AS target_tool,
             fr.confidence::text AS confidence,
+            fr.match_source,
+            fr.match_score,
             fr.created_at
         FROM finding_relationships fr
         INNER JOIN findings sf ON sf.id = fr.source_finding_id
@@ -148,6 +259,37 @@ pub async fn list_history(
     Ok(PagedResult::new(items, total, pagination))
 }
 
+/// Fetch reviewer decision counts per matcher and per similarity score band.
+pub async fn get_review_metrics(pool: &PgPool) -> Result<ReviewMetrics, AppError> {
+    let (sources, bands) = tokio::try_join!(
+        fetch_source_decisions(pool),
+        fetch_score_band_decisions(pool),
+    )?;
+
+    Ok(ReviewMetrics {
+        by_source: sources
+            .into_iter()
+            .map(|row| MatchSourceDecisions {
+                acceptance_rate_pct: acceptance_rate(row.accepted, row.rejected),
+                match_source: row.match_source,
+                pending: row.pending,
+                accepted: row.accepted,
+                rejected: row.rejected,
+            })
+            .collect(),
+        similarity_score_bands: bands
+            .into_iter()
+            .map(|row| ScoreBandDecisions {
+                min_score: row.band as f64 / 10.0,
+                max_score: (row.band + 1) as f64 / 10.0,
+                acceptance_rate_pct: acceptance_rate(row.accepted, row.rejected),
+                accepted: row.accepted,
+                rejected: row.rejected,
+            })
+            .collect(),
+    })
+}
+
 /// Confirm a duplicate relationship by promoting confidence to High.
 ///
 /// Wraps the update and audit-trail insert in a single transaction.
@@ -161,7 +303,8 @@ pub async fn confirm(
     // Fetch the relationship to record audit info.
     let rel = sqlx::query_as::<_, RelRow>(
         r#"
-        SELECT id, source_finding_id, confidence::text AS confidence
+        SELECT id, source_finding_id, target_finding_id, confidence::text AS confidence,
+               match_source, match_score
         FROM finding_relationships
         WHERE id = $1 AND relationship_type = 'duplicate_of'
         "#,
@@ -195,11 +338,14 @@ pub async fn confirm(
     .execute(&mut *tx)
     .await?;
 
+    record_decision(&mut tx, &rel, "accepted", user_id).await?;
+
     tx.commit().await?;
     Ok(())
 }
 
-/// Reject a duplicate relationship by deleting it.
+/// Reject a duplicate relationship by deleting it. The pair is remembered so
+/// cross-tool heuristics do not suggest it again.
 ///
 /// Wraps the delete and audit-trail insert in a single transaction.
 pub async fn reject(
@@ -212,7 +358,8 @@ pub async fn reject(
     // Fetch the relationship before deletion for audit info.
     let rel = sqlx::query_as::<_, RelRow>(
         r#"
-        SELECT id, source_finding_id, confidence::text AS confidence
+        SELECT id, source_finding_id, target_finding_id, confidence::text AS confidence,
+               match_source, match_score
         FROM finding_relationships
         WHERE id = $1 AND relationship_type = 'duplicate_of'
         "#,
@@ -227,6 +374,19 @@ pub async fn reject(
         .execute(&mut *tx)
         .await?;
 
+    sqlx::query(
+        r#"
+        INSERT INTO dedup_rejections (source_finding_id, target_finding_id, rejected_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(rel.source_finding_id)
+    .bind(rel.target_finding_id)
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+
     let actor_name = fetch_actor_name(&mut tx, user_id).await?;
 
     sqlx::query(
@@ -243,6 +403,8 @@ pub async fn reject(
     .execute(&mut *tx)
     .await?;
 
+    record_decision(&mut tx, &rel, "rejected", user_id).await?;
+
     tx.commit().await?;
     Ok(())
 }
@@ -252,10 +414,40 @@ pub async fn reject(
 /// Minimal row for reading a relationship before mutation.
 #[derive(Debug, FromRow)]
 struct RelRow {
-    #[expect(dead_code, reason = "selected for completeness but only source_finding_id / confidence are used")]
     id: Uuid,
     source_finding_id: Uuid,
+    target_finding_id: Uuid,
     confidence: Option<String>,
+    match_source: Option<String>,
+    match_score: Option<f32>,
+}
+
+/// Keep a reviewer decision with the matcher and score of the pair.
+async fn record_decision(
+    tx: &mut sqlx::PgConnection,
+    rel: &RelRow,
+    decision: &str,
+    user_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO dedup_review_decisions
+            (relationship_id, source_finding_id, target_finding_id, match_source, match_score,
+             confidence_before, decision, decided_by)
+        VALUES ($1, $2, $3, $4, $5, $6::confidence_level, $7, $8)
+        "#,
+    )
+    .bind(rel.id)
+    .bind(rel.source_finding_id)
+    .bind(rel.target_finding_id)
+    .bind(&rel.match_source)
+    .bind(rel.match_score)
+    .bind(&rel.confidence)
+    .bind(decision)
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
 }
 
 /// Resolve a user ID to their username for audit trail entries.
@@ -323,6 +515,75 @@ async fn fetch_rejected_count(pool: &PgPool) -> Result<i64, AppError> {
     Ok(count)
 }
 
+#[derive(Debug, FromRow)]
+struct SourceDecisionRow {
+    match_source: String,
+    pending: i64,
+    accepted: i64,
+    rejected: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct ScoreBandRow {
+    band: i32,
+    accepted: i64,
+    rejected: i64,
+}
+
+/// Pending pairs and decisions per matcher.
+async fn fetch_source_decisions(pool: &PgPool) -> Result<Vec<SourceDecisionRow>, AppError> {
+    let rows = sqlx::query_as::<_, SourceDecisionRow>(
+        r#"
+        WITH pending AS (
+            SELECT COALESCE(match_source, 'unspecified') AS match_source, COUNT(*) AS pending
+            FROM finding_relationships
+            WHERE relationship_type = 'duplicate_of'
+              AND (confidence::text IN ('Low', 'Medium') OR confidence IS NULL)
+            GROUP BY 1
+        ),
+        decided AS (
+            SELECT
+                COALESCE(match_source, 'unspecified') AS match_source,
+                COUNT(*) FILTER (WHERE decision = 'accepted') AS accepted,
+                COUNT(*) FILTER (WHERE decision = 'rejected') AS rejected
+            FROM dedup_review_decisions
+            GROUP BY 1
+        )
+        SELECT
+            COALESCE(p.match_source, d.match_source) AS match_source,
+            COALESCE(p.pending, 0) AS pending,
+            COALESCE(d.accepted, 0) AS accepted,
+            COALESCE(d.rejected, 0) AS rejected
+        FROM pending p
+        FULL JOIN decided d ON d.match_source = p.match_source
+        ORDER BY 1
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Similarity decisions per 0.1 score band.
+async fn fetch_score_band_decisions(pool: &PgPool) -> Result<Vec<ScoreBandRow>, AppError> {
+    let rows = sqlx::query_as::<_, ScoreBandRow>(
+        r#"
+        SELECT
+            LEAST(FLOOR(match_score * 10), 9)::int AS band,
+            COUNT(*) FILTER (WHERE decision = 'accepted') AS accepted,
+            COUNT(*) FILTER (WHERE decision = 'rejected') AS rejected
+        FROM dedup_review_decisions
+        WHERE match_source = $1 AND match_score IS NOT NULL
+        GROUP BY 1
+        ORDER BY 1
+        "#,
+    )
+    .bind(similarity::MATCH_SOURCE)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
 /// Count all ingestion log entries.
 async fn fetch_total_ingestions(pool: &PgPool) -> Result<i64, AppError> {
     let count = sqlx::query_scalar::<_, i64>(
@@ -343,6 +604,132 @@ async fn fetch_last_ingestion_at(pool: &PgPool) -> Result<Option<DateTime<Utc>>,
     Ok(ts)
 }
 
+/// Duplicate and reopen counts per scanner from ingestion logs and occurrences.
+async fn fetch_tool_duplicate_rates(pool: &PgPool) -> Result<Vec<ToolDuplicateRate>, AppError> {
+    let rows = sqlx::query_as::<_, ToolDuplicateRate>(
+        r#"
+        WITH logs AS (
+            SELECT
+                source_tool,
+                COUNT(*) AS ingestions,
+                COALESCE(SUM(total_records), 0)::bigint AS total_records,
+                COALESCE(SUM(duplicates), 0)::bigint AS duplicates
+            FROM ingestion_logs
+            GROUP BY source_tool
+        ),
+        reopens AS (
+            SELECT source_tool, COUNT(*) AS reopened
+            FROM finding_occurrences
+            WHERE outcome = 'reopened'
+            GROUP BY source_tool
+        )
+        SELECT
+            l.source_tool,
+            l.ingestions,
+            l.total_records,
+            l.duplicates,
+            COALESCE(r.reopened, 0) AS reopened,
+            ROUND(100.0 * l.duplicates / NULLIF(l.total_records, 0), 2)::float8 AS duplicate_rate_pct
+        FROM logs l
+        LEFT JOIN reopens r ON r.source_tool = l.source_tool
+        ORDER BY duplicate_rate_pct DESC NULLS LAST, l.source_tool
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Count CVEs by the number of distinct tools reporting them.
+async fn fetch_cve_tool_counts(pool: &PgPool) -> Result<Vec<ToolCountBucket>, AppError> {
+    let rows = sqlx::query_as::<_, ToolCountBucket>(
+        r#"
+        WITH cve_tools AS (
+            SELECT cve.value AS cve_id, COUNT(DISTINCT f.source_tool) AS tool_count
+            FROM findings f
+            CROSS JOIN LATERAL jsonb_array_elements_text(COALESCE(f.cve_ids, '[]'::jsonb)) AS cve(value)
+            WHERE f.status <> 'Invalidated'
+            GROUP BY cve.value
+        )
+        SELECT tool_count, COUNT(*) AS cves
+        FROM cve_tools
+        GROUP BY tool_count
+        ORDER BY tool_count
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Collision counts from occurrences and the findings table.
+async fn fetch_fingerprint_collisions(pool: &PgPool) -> Result<FingerprintCollisions, AppError> {
+    let row = sqlx::query_as::<_, FingerprintCollisions>(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM finding_occurrences) AS total_occurrences,
+            (
+                SELECT COALESCE(SUM(n - 1), 0)::bigint FROM (
+                    SELECT COUNT(*) AS n
+                    FROM finding_occurrences
+                    GROUP BY ingestion_log_id, fingerprint
+                    HAVING COUNT(*) > 1
+                ) s
+            ) AS intra_ingestion_collisions,
+            (
+                SELECT COUNT(*) FROM (
+                    SELECT fingerprint
+                    FROM finding_occurrences
+                    GROUP BY fingerprint
+                    HAVING COUNT(DISTINCT source_tool) > 1
+                ) s
+            ) AS cross_tool_fingerprints,
+            (
+                SELECT COUNT(*) FROM (
+                    SELECT fingerprint
+                    FROM findings
+                    GROUP BY fingerprint
+                    HAVING COUNT(*) > 1
+                ) s
+            ) AS shared_by_multiple_findings
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(row)
+}
+
+/// Summarize per-tool-count buckets into the CVE overlap figures.
+fn cve_overlap(by_tool_count: Vec<ToolCountBucket>) -> CveOverlap {
+    let distinct_cves: i64 = by_tool_count.iter().map(|b| b.cves).sum();
+    let found_by_multiple_tools: i64 = by_tool_count
+        .iter()
+        .filter(|b| b.tool_count >= 2)
+        .map(|b| b.cves)
+        .sum();
+    let overlap_pct = if distinct_cves > 0 {
+        Some(found_by_multiple_tools as f64 * 100.0 / distinct_cves as f64)
+    } else {
+        None
+    };
+    CveOverlap {
+        distinct_cves,
+        found_by_multiple_tools,
+        overlap_pct,
+        by_tool_count,
+    }
+}
+
+/// Share of decisions that accepted the pair, if any were made.
+fn acceptance_rate(accepted: i64, rejected: i64) -> Option<f64> {
+    let decided = accepted + rejected;
+    if decided > 0 {
+        Some(accepted as f64 * 100.0 / decided as f64)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -381,6 +768,25 @@ mod tests {
         assert!(json["last_ingestion_at"].is_string());
     }
 
+    #[test]
+    fn cve_overlap_counts_multi_tool_cves() {
+        let overlap = cve_overlap(vec![
+            ToolCountBucket { tool_count: 1, cves: 6 },
+            ToolCountBucket { tool_count: 2, cves: 3 },
+            ToolCountBucket { tool_count: 3, cves: 1 },
+        ]);
+        assert_eq!(overlap.distinct_cves, 10);
+        assert_eq!(overlap.found_by_multiple_tools, 4);
+        assert_eq!(overlap.overlap_pct, Some(40.0));
+    }
+
+    #[test]
+    fn cve_overlap_without_cves() {
+        let overlap = cve_overlap(Vec::new());
+        assert_eq!(overlap.distinct_cves, 0);
+        assert!(overlap.overlap_pct.is_none());
+    }
+
     #[test]
     fn dedup_decision_serialization() {
         let decision = DedupDecision {
@@ -409,11 +815,21 @@ mod tests {
             target_title: "SQL Injection in login".to_string(),
             target_tool: "semgrep".to_string(),
             confidence: Some("Medium".to_string()),
+            match_source: Some("same_cwe_file_lines".to_string()),
+            match_score: None,
             created_at: Utc::now(),
         };
         let json = serde_json::to_value(&review).unwrap();
         assert_eq!(json["source_title"], "SQL Injection");
         assert_eq!(json["target_tool"], "semgrep");
         assert_eq!(json["confidence"], "Medium");
+        assert_eq!(json["match_source"], "same_cwe_file_lines");
+    }
+
+    #[test]
+    fn acceptance_rate_over_decided_pairs() {
+        assert_eq!(acceptance_rate(3, 1), Some(75.0));
+        assert_eq!(acceptance_rate(0, 2), Some(0.0));
+        assert!(acceptance_rate(0, 0).is_none());
     }
 }
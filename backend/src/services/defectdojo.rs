@@ -0,0 +1,347 @@
+//! DefectDojo-compatible export of an application's findings.
+//!
+//! Produces the "Generic Findings Import" JSON document accepted by
+//! DefectDojo's `import-scan` API, mapping lifecycle status onto DefectDojo's
+//! boolean state flags and category data onto file, component, and endpoint
+//! fields.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{FindingCategory, FindingStatus, SeverityLevel};
+use crate::services::json_value::json_strings;
+
+/// Top-level Generic Findings Import document.
+#[derive(Debug, Serialize)]
+pub struct DojoExport {
+    pub findings: Vec<DojoFinding>,
+}
+
+/// A single finding in DefectDojo's generic import format.
+#[derive(Debug, Serialize)]
+pub struct DojoFinding {
+    pub title: String,
+    pub description: String,
+    pub severity: &'static str,
+    pub date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwe: Option<i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub vulnerability_ids: Vec<DojoVulnerabilityId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvssv3: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cvssv3_score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mitigation: Option<String>,
+    pub active: bool,
+    pub verified: bool,
+    pub false_p: bool,
+    pub risk_accepted: bool,
+    pub is_mitigated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mitigated: Option<String>,
+    pub static_finding: bool,
+    pub dynamic_finding: bool,
+    pub unique_id_from_tool: String,
+    pub vuln_id_from_tool: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_version: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+/// Vulnerability identifier entry (CVE, GHSA, ...).
+#[derive(Debug, Serialize)]
+pub struct DojoVulnerabilityId {
+    pub vulnerability_id: String,
+}
+
+/// Finding row joined with the category fields DefectDojo understands.
+#[derive(Debug, FromRow)]
+struct DojoRow {
+    id: Uuid,
+    source_tool: String,
+    source_finding_id: String,
+    finding_category: FindingCategory,
+    title: String,
+    description: String,
+    normalized_severity: SeverityLevel,
+    cvss_score: Option<f32>,
+    cvss_vector: Option<String>,
+    cwe_ids: serde_json::Value,
+    cve_ids: serde_json::Value,
+    status: FindingStatus,
+    first_seen: DateTime<Utc>,
+    status_changed_at: DateTime<Utc>,
+    tags: serde_json::Value,
+    remediation_guidance: Option<String>,
+    file_path: Option<String>,
+    line_number_start: Option<i32>,
+    rule_id: Option<String>,
+    package_name: Option<String>,
+    package_version: Option<String>,
+    fixed_version: Option<String>,
+    target_url: Option<String>,
+}
+
+/// Build the DefectDojo export for all non-invalidated findings of an application.
+pub async fn export_for_application(pool: &PgPool, app_id: Uuid) -> Result<DojoExport, AppError> {
+    let exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM applications WHERE id = $1)")
+            .bind(app_id)
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Application {app_id} not found"
+        )));
+    }
+
+    let rows = sqlx::query_as::<_, DojoRow>(
+        r#"
+        SELECT
+            f.id, f.source_tool, f.source_finding_id, f.finding_category,
+            f.title, f.description, f.normalized_severity,
+            f.cvss_score, f.cvss_vector, f.cwe_ids, f.cve_ids,
+            f.status, f.first_seen, f.status_changed_at, f.tags,
+            f.remediation_guidance,
+            fs.file_path, fs.line_number_start, fs.rule_id,
+            fc.package_name, fc.package_version, fc.fixed_version,
+            fd.target_url
+        FROM findings f
+        LEFT JOIN finding_sast fs ON fs.finding_id = f.id
+        LEFT JOIN finding_sca fc ON fc.finding_id = f.id
+        LEFT JOIN finding_dast fd ON fd.finding_id = f.id
+        WHERE f.application_id = $1
+          AND f.status <> 'Invalidated'
+        ORDER BY f.first_seen ASC
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(DojoExport {
+        findings: rows.iter().map(to_dojo).collect(),
+    })
+}
+
+/// Convert a joined row into a DefectDojo finding.
+fn to_dojo(row: &DojoRow) -> DojoFinding {
+    let flags = status_flags(&row.status);
+    let cve_ids = json_strings(&row.cve_ids);
+
+    let mitigation = match (&row.remediation_guidance, &row.fixed_version) {
+        (Some(g), _) => Some(g.clone()),
+        (None, Some(v)) => Some(format!(
+            "Upgrade {} to version {v} or later",
+            row.package_name.as_deref().unwrap_or("the component")
+        )),
+        (None, None) => None,
+    };
+
+    DojoFinding {
+        title: row.title.clone(),
+        description: if row.description.trim().is_empty() {
+            row.title.clone()
+        } else {
+            row.description.clone()
+        },
+        severity: dojo_severity(&row.normalized_severity),
+        date: row.first_seen.format("%Y-%m-%d").to_string(),
+        cwe: json_strings(&row.cwe_ids)
+            .iter()
+            .find_map(|c| parse_cwe_number(c)),
+        vulnerability_ids: cve_ids
+            .into_iter()
+            .map(|vulnerability_id| DojoVulnerabilityId { vulnerability_id })
+            .collect(),
+        cvssv3: row.cvss_vector.clone().filter(|v| v.starts_with("CVSS:3")),
+        cvssv3_score: row.cvss_score,
+        mitigation,
+        active: flags.active,
+        verified: flags.verified,
+        false_p: flags.false_p,
+        risk_accepted: flags.risk_accepted,
+        is_mitigated: flags.is_mitigated,
+        mitigated: flags
+            .is_mitigated
+            .then(|| row.status_changed_at.to_rfc3339()),
+        static_finding: matches!(
+            row.finding_category,
+            FindingCategory::Sast | FindingCategory::Sca
+        ),
+        dynamic_finding: row.finding_category == FindingCategory::Dast,
+        unique_id_from_tool: row.id.to_string(),
+        vuln_id_from_tool: row
+            .rule_id
+            .clone()
+            .unwrap_or_else(|| row.source_finding_id.clone()),
+        file_path: row.file_path.clone(),
+        line: row.line_number_start,
+        component_name: row.package_name.clone(),
+        component_version: row.package_version.clone(),
+        endpoints: row.target_url.iter().cloned().collect(),
+        tags: std::iter::once(row.source_tool.clone())
+            .chain(json_strings(&row.tags))
+            .collect(),
+    }
+}
+
+/// DefectDojo boolean state derived from a lifecycle status.
+#[derive(Debug, Default, PartialEq)]
+struct StatusFlags {
+    active: bool,
+    verified: bool,
+    false_p: bool,
+    risk_accepted: bool,
+    is_mitigated: bool,
+}
+
+/// Map a lifecycle status onto DefectDojo's state flags.
+fn status_flags(status: &FindingStatus) -> StatusFlags {
+    match status {
+        FindingStatus::New | FindingStatus::FalsePositiveRequested => StatusFlags {
+            active: true,
+            ..Default::default()
+        },
+        FindingStatus::Confirmed
+        | FindingStatus::InRemediation
+        | FindingStatus::DeferredRemediation => StatusFlags {
+            active: true,
+            verified: true,
+            ..Default::default()
+        },
+        FindingStatus::FalsePositive => StatusFlags {
+            false_p: true,
+            verified: true,
+            ..Default::default()
+        },
+        FindingStatus::RiskAccepted => StatusFlags {
+            risk_accepted: true,
+            verified: true,
+            ..Default::default()
+        },
+        FindingStatus::Mitigated | FindingStatus::Verified | FindingStatus::Closed => StatusFlags {
+            verified: true,
+            is_mitigated: true,
+            ..Default::default()
+        },
+        FindingStatus::Invalidated => StatusFlags::default(),
+    }
+}
+
+/// DefectDojo severity label; it uses `Info` for informational findings.
+fn dojo_severity(severity: &SeverityLevel) -> &'static str {
+    match severity {
+        SeverityLevel::Critical => "Critical",
+        SeverityLevel::High => "High",
+        SeverityLevel::Medium => "Medium",
+        SeverityLevel::Low => "Low",
+        SeverityLevel::Info => "Info",
+    }
+}
+
+/// Parse the numeric part of a CWE identifier such as `CWE-89` or `89`.
+fn parse_cwe_number(cwe: &str) -> Option<i32> {
+    cwe.trim()
+        .trim_start_matches("CWE-")
+        .trim_start_matches("cwe-")
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sca_row(status: FindingStatus) -> DojoRow {
+        DojoRow {
+            id: Uuid::nil(),
+            source_tool: "JFrog Xray".to_string(),
+            source_finding_id: "XRAY-1".to_string(),
+            finding_category: FindingCategory::Sca,
+            title: "CVE-2024-38816: spring-webmvc".to_string(),
+            description: String::new(),
+            normalized_severity: SeverityLevel::High,
+            cvss_score: Some(7.5),
+            cvss_vector: Some("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:N/A:N".to_string()),
+            cwe_ids: serde_json::json!(["CWE-22"]),
+            cve_ids: serde_json::json!(["CVE-2024-38816"]),
+            status,
+            first_seen: DateTime::from_timestamp(1_726_000_000, 0).unwrap(),
+            status_changed_at: Utc::now(),
+            tags: serde_json::json!(["payments"]),
+            remediation_guidance: None,
+            file_path: None,
+            line_number_start: None,
+            rule_id: None,
+            package_name: Some("spring-webmvc".to_string()),
+            package_version: Some("6.1.6".to_string()),
+            fixed_version: Some("6.1.12".to_string()),
+            target_url: None,
+        }
+    }
+
+    #[test]
+    fn cwe_number_parsing() {
+        assert_eq!(parse_cwe_number("CWE-89"), Some(89));
+        assert_eq!(parse_cwe_number("79"), Some(79));
+        assert_eq!(parse_cwe_number("NVD-CWE-Other"), None);
+    }
+
+    #[test]
+    fn status_flag_mapping() {
+        assert!(status_flags(&FindingStatus::New).active);
+        assert!(!status_flags(&FindingStatus::New).verified);
+        assert!(status_flags(&FindingStatus::FalsePositive).false_p);
+        assert!(status_flags(&FindingStatus::RiskAccepted).risk_accepted);
+        assert!(status_flags(&FindingStatus::Closed).is_mitigated);
+        assert!(!status_flags(&FindingStatus::Closed).active);
+    }
+
+    #[test]
+    fn sca_row_maps_component_and_cve() {
+        let f = to_dojo(&sca_row(FindingStatus::Confirmed));
+        assert_eq!(f.severity, "High");
+        assert_eq!(f.date, "2024-09-10");
+        assert_eq!(f.cwe, Some(22));
+        assert_eq!(f.vulnerability_ids[0].vulnerability_id, "CVE-2024-38816");
+        assert_eq!(f.component_name.as_deref(), Some("spring-webmvc"));
+        assert!(f.mitigation.unwrap().contains("6.1.12"));
+        assert!(f.static_finding);
+        assert!(!f.dynamic_finding);
+        // Empty description falls back to the title
+        assert_eq!(f.description, f.title);
+        assert_eq!(f.tags, vec!["JFrog Xray", "payments"]);
+    }
+
+    #[test]
+    fn mitigated_date_only_when_mitigated() {
+        assert!(to_dojo(&sca_row(FindingStatus::Confirmed))
+            .mitigated
+            .is_none());
+        assert!(to_dojo(&sca_row(FindingStatus::Closed)).mitigated.is_some());
+    }
+
+    #[test]
+    fn export_serializes_findings_key() {
+        let export = DojoExport {
+            findings: vec![to_dojo(&sca_row(FindingStatus::New))],
+        };
+        let json = serde_json::to_value(&export).unwrap();
+        assert_eq!(json["findings"][0]["severity"], "High");
+        assert!(json["findings"][0].get("file_path").is_none());
+    }
+}
@@ -0,0 +1,348 @@
+//! DNS name and URL prefix to application mappings for DAST findings.
+//!
+//! DAST hostnames frequently do not contain the app code, so regex patterns
+//! cannot resolve them. Mappings name the application directly: a DNS name
+//! (exact, or `*.example.com` for every subdomain) or a URL prefix. Ingestion
+//! consults them after the asset inventory and before the regex patterns.
+
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::dns_mapping::{CreateDnsMapping, DnsMapping, DnsMappingKind, UpdateDnsMapping};
+use crate::services::{application, config_cache};
+
+/// Mapping columns plus the application's app code.
+const SELECT_MAPPING: &str = r#"
+    SELECT m.id, m.kind, m.pattern, m.application_id, a.app_code,
+           m.description, m.created_at, m.updated_at
+    FROM dns_mappings m
+    JOIN applications a ON a.id = m.application_id
+"#;
+
+// ---------------------------------------------------------------------------
+// Normalization and matching
+// ---------------------------------------------------------------------------
+
+/// Lowercase a host name and drop any trailing root dot.
+fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Normalize a pattern for storage and check it is well formed: DNS names
+/// may only use `*` as a leading `*.` label, and URL prefixes need an HTTP
+/// scheme and a host.
+fn normalize_pattern(kind: DnsMappingKind, pattern: &str) -> Result<String, AppError> {
+    match kind {
+        DnsMappingKind::DnsName => {
+            let pattern = normalize_host(pattern);
+            let name = pattern.strip_prefix("*.").unwrap_or(&pattern);
+            let valid = !name.is_empty()
+                && !name.contains(|c: char| c == '*' || c == '/' || c.is_whitespace())
+                && !name.split('.').any(str::is_empty);
+            if !valid {
+                return Err(AppError::Validation(format!(
+                    "'{pattern}' is not a DNS name or '*.' wildcard"
+                )));
+            }
+            Ok(pattern)
+        }
+        DnsMappingKind::UrlPrefix => {
+            let pattern = pattern.trim().to_ascii_lowercase();
+            let has_host = reqwest::Url::parse(&pattern)
+                .ok()
+                .filter(|url| matches!(url.scheme(), "http" | "https"))
+                .and_then(|url| url.host_str().map(|host| !host.is_empty()))
+                .unwrap_or(false);
+            if !has_host || pattern.contains('*') {
+                return Err(AppError::Validation(format!(
+                    "'{pattern}' is not an http(s) URL prefix"
+                )));
+            }
+            Ok(pattern)
+        }
+    }
+}
+
+/// Mappings indexed for app code lookups.
+#[derive(Debug, Default)]
+pub struct DnsMappingSet {
+    exact: HashMap<String, String>,
+    /// `(suffix, app_code)` for `*.` wildcards, the suffix keeping its
+    /// leading dot; longest (most specific) first.
+    wildcards: Vec<(String, String)>,
+    /// `(prefix, app_code)`, longest first.
+    url_prefixes: Vec<(String, String)>,
+}
+
+impl DnsMappingSet {
+    /// Build the set from `(kind, pattern, app_code)` entries.
+    pub fn from_entries(
+        entries: impl IntoIterator<Item = (DnsMappingKind, String, String)>,
+    ) -> Self {
+        let mut set = Self::default();
+        for (kind, pattern, app_code) in entries {
+            match kind {
+                DnsMappingKind::UrlPrefix => set.url_prefixes.push((pattern, app_code)),
+                DnsMappingKind::DnsName => match pattern.strip_prefix('*') {
+                    Some(suffix) => set.wildcards.push((suffix.to_string(), app_code)),
+                    None => {
+                        set.exact.insert(pattern, app_code);
+                    }
+                },
+            }
+        }
+        let longest_first = |a: &(String, String), b: &(String, String)| {
+            b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0))
+        };
+        set.wildcards.sort_by(longest_first);
+        set.url_prefixes.sort_by(longest_first);
+        set
+    }
+
+    /// App code for a finding's `url` and `dns_name` fields.
+    ///
+    /// The longest matching URL prefix wins, then an exact DNS name, then
+    /// the most specific wildcard. When `dns_name` is missing, the host of
+    /// `url` is used.
+    pub fn resolve(&self, fields: &[(String, String)]) -> Option<String> {
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(field_name, _)| field_name == name)
+                .map(|(_, value)| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+        let url = field("url").map(str::to_ascii_lowercase);
+
+        if let Some(url) = &url {
+            let matched = self
+                .url_prefixes
+                .iter()
+                .find(|(prefix, _)| url.starts_with(prefix.as_str()));
+            if let Some((_, app_code)) = matched {
+                return Some(app_code.clone());
+            }
+        }
+
+        let host = match field("dns_name") {
+            Some(dns_name) => normalize_host(dns_name),
+            None => url
+                .as_deref()
+                .and_then(|url| reqwest::Url::parse(url).ok())
+                .and_then(|url| url.host_str().map(normalize_host))?,
+        };
+        if let Some(app_code) = self.exact.get(&host) {
+            return Some(app_code.clone());
+        }
+        self.wildcards
+            .iter()
+            .find(|(suffix, _)| host.ends_with(suffix.as_str()))
+            .map(|(_, app_code)| app_code.clone())
+    }
+}
+
+/// Load every mapping into a [`DnsMappingSet`].
+pub async fn load_set(pool: &PgPool) -> Result<DnsMappingSet, AppError> {
+    let rows = sqlx::query_as::<_, (DnsMappingKind, String, String)>(
+        r#"
+        SELECT m.kind, m.pattern, a.app_code
+        FROM dns_mappings m
+        JOIN applications a ON a.id = m.application_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(DnsMappingSet::from_entries(rows))
+}
+
+/// Map the `(kind, pattern)` unique violation to a readable conflict.
+fn pattern_conflict(e: sqlx::Error, pattern: &str) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            AppError::Conflict(format!("A mapping for '{pattern}' already exists"))
+        }
+        _ => AppError::Database(e),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CRUD
+// ---------------------------------------------------------------------------
+
+/// List all mappings, by kind and pattern.
+pub async fn list(pool: &PgPool) -> Result<Vec<DnsMapping>, AppError> {
+    let mappings =
+        sqlx::query_as::<_, DnsMapping>(&format!("{SELECT_MAPPING} ORDER BY m.kind, m.pattern"))
+            .fetch_all(pool)
+            .await?;
+    Ok(mappings)
+}
+
+/// Fetch a mapping by ID.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<DnsMapping, AppError> {
+    sqlx::query_as::<_, DnsMapping>(&format!("{SELECT_MAPPING} WHERE m.id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("DNS mapping {id} not found")))
+}
+
+/// Create a mapping.
+pub async fn create(pool: &PgPool, input: &CreateDnsMapping) -> Result<DnsMapping, AppError> {
+    let pattern = normalize_pattern(input.kind, &input.pattern)?;
+    application::find_by_id(pool, input.application_id).await?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO dns_mappings (kind, pattern, application_id, description)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+    )
+    .bind(input.kind)
+    .bind(&pattern)
+    .bind(input.application_id)
+    .bind(&input.description)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| pattern_conflict(e, &pattern))?;
+
+    config_cache::global().invalidate_dns_mappings().await;
+    find_by_id(pool, id).await
+}
+
+/// Update a mapping; omitted fields keep their values.
+pub async fn update(
+    pool: &PgPool,
+    id: Uuid,
+    input: &UpdateDnsMapping,
+) -> Result<DnsMapping, AppError> {
+    let existing = find_by_id(pool, id).await?;
+    let pattern = match &input.pattern {
+        Some(pattern) => normalize_pattern(existing.kind, pattern)?,
+        None => existing.pattern,
+    };
+    if let Some(application_id) = input.application_id {
+        application::find_by_id(pool, application_id).await?;
+    }
+
+    sqlx::query(
+        "UPDATE dns_mappings SET pattern = $1, application_id = $2, description = $3 WHERE id = $4",
+    )
+    .bind(&pattern)
+    .bind(input.application_id.unwrap_or(existing.application_id))
+    .bind(input.description.as_ref().or(existing.description.as_ref()))
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| pattern_conflict(e, &pattern))?;
+
+    config_cache::global().invalidate_dns_mappings().await;
+    find_by_id(pool, id).await
+}
+
+/// Delete a mapping.
+pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM dns_mappings WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("DNS mapping {id} not found")));
+    }
+    config_cache::global().invalidate_dns_mappings().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn set() -> DnsMappingSet {
+        DnsMappingSet::from_entries([
+            (
+                DnsMappingKind::DnsName,
+                "portal.example.com".to_string(),
+                "PRT".to_string(),
+            ),
+            (
+                DnsMappingKind::DnsName,
+                "*.example.com".to_string(),
+                "WEB".to_string(),
+            ),
+            (
+                DnsMappingKind::DnsName,
+                "*.pay.example.com".to_string(),
+                "PAY".to_string(),
+            ),
+            (
+                DnsMappingKind::UrlPrefix,
+                "https://portal.example.com/admin".to_string(),
+                "ADM".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn patterns_are_normalized_and_validated() {
+        assert_eq!(
+            normalize_pattern(DnsMappingKind::DnsName, " *.Example.COM. ").unwrap(),
+            "*.example.com"
+        );
+        assert!(normalize_pattern(DnsMappingKind::DnsName, "*").is_err());
+        assert!(normalize_pattern(DnsMappingKind::DnsName, "app.*.example.com").is_err());
+        assert!(normalize_pattern(DnsMappingKind::DnsName, "a..example.com").is_err());
+        assert_eq!(
+            normalize_pattern(DnsMappingKind::UrlPrefix, "HTTPS://Shop.example.com/API").unwrap(),
+            "https://shop.example.com/api"
+        );
+        assert!(normalize_pattern(DnsMappingKind::UrlPrefix, "shop.example.com/api").is_err());
+        assert!(normalize_pattern(DnsMappingKind::UrlPrefix, "ftp://shop.example.com").is_err());
+    }
+
+    #[test]
+    fn exact_names_beat_wildcards_and_specific_wildcards_win() {
+        let set = set();
+        assert_eq!(
+            set.resolve(&fields(&[("dns_name", "Portal.example.com")])),
+            Some("PRT".to_string())
+        );
+        assert_eq!(
+            set.resolve(&fields(&[("dns_name", "api.pay.example.com")])),
+            Some("PAY".to_string())
+        );
+        assert_eq!(
+            set.resolve(&fields(&[("dns_name", "s1xq7.example.com")])),
+            Some("WEB".to_string())
+        );
+        // A wildcard does not match its own apex
+        assert_eq!(set.resolve(&fields(&[("dns_name", "example.com")])), None);
+    }
+
+    #[test]
+    fn url_prefixes_win_and_url_host_is_a_fallback() {
+        let set = set();
+        assert_eq!(
+            set.resolve(&fields(&[
+                ("dns_name", "portal.example.com"),
+                ("url", "https://portal.example.com/admin/users"),
+            ])),
+            Some("ADM".to_string())
+        );
+        assert_eq!(
+            set.resolve(&fields(&[("url", "https://portal.example.com/home")])),
+            Some("PRT".to_string())
+        );
+        assert_eq!(set.resolve(&fields(&[("url", "not a url")])), None);
+    }
+}
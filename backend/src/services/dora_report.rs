@@ -0,0 +1,409 @@
+//! DORA-oriented compliance report for applications supporting critical or
+//! important functions (`is_dora_fei`).
+//!
+//! Sections follow the ICT risk management obligations of Regulation (EU)
+//! 2022/2554 and its RTS on the ICT risk management framework: the in-scope
+//! asset inventory (Art. 8), vulnerability exposure (Art. 9), and timeliness
+//! of vulnerability remediation (RTS 2024/1774 Art. 10). Figures are broken
+//! down by asset criticality.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::application::AssetCriticality;
+use crate::services::executive_report::ReportPeriod;
+use crate::services::pdf_report::{ReportBlock, ReportDocument};
+
+/// Finding metrics for one in-scope application.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct DoraApplicationMetrics {
+    pub application_id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    pub criticality: Option<AssetCriticality>,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub metrics: DoraMetrics,
+}
+
+/// Counters shared by application rows and criticality groups.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, sqlx::FromRow, ToSchema)]
+pub struct DoraMetrics {
+    pub open_total: i64,
+    pub open_critical: i64,
+    pub open_high: i64,
+    pub open_medium: i64,
+    pub open_low: i64,
+    pub sla_at_risk: i64,
+    pub sla_breached: i64,
+    /// Findings fixed (Mitigated, Verified, Closed) during the period.
+    pub remediated_in_period: i64,
+    /// Remediated findings that carried an SLA due date.
+    pub remediated_with_sla: i64,
+    /// Of those, remediated on or before the due date.
+    pub remediated_within_sla: i64,
+    /// Sum of first-seen-to-fix durations, in days, for MTTR.
+    pub remediation_days_total: f64,
+}
+
+impl DoraMetrics {
+    /// Accumulate another set of counters into this one.
+    fn add(&mut self, other: &DoraMetrics) {
+        self.open_total += other.open_total;
+        self.open_critical += other.open_critical;
+        self.open_high += other.open_high;
+        self.open_medium += other.open_medium;
+        self.open_low += other.open_low;
+        self.sla_at_risk += other.sla_at_risk;
+        self.sla_breached += other.sla_breached;
+        self.remediated_in_period += other.remediated_in_period;
+        self.remediated_with_sla += other.remediated_with_sla;
+        self.remediated_within_sla += other.remediated_within_sla;
+        self.remediation_days_total += other.remediation_days_total;
+    }
+
+    /// Percentage of SLA-bound remediations completed in time.
+    pub fn sla_adherence_pct(&self) -> Option<f64> {
+        (self.remediated_with_sla > 0)
+            .then(|| self.remediated_within_sla as f64 * 100.0 / self.remediated_with_sla as f64)
+    }
+
+    /// Mean time to remediate, in days, for findings fixed in the period.
+    pub fn mttr_days(&self) -> Option<f64> {
+        (self.remediated_in_period > 0)
+            .then(|| self.remediation_days_total / self.remediated_in_period as f64)
+    }
+}
+
+/// Metrics for all in-scope applications of one criticality level.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DoraCriticalityGroup {
+    /// `None` groups applications without a recorded criticality.
+    pub criticality: Option<AssetCriticality>,
+    pub application_count: usize,
+    #[serde(flatten)]
+    pub metrics: DoraMetrics,
+    pub sla_adherence_pct: Option<f64>,
+    pub mttr_days: Option<f64>,
+}
+
+/// Complete DORA report.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DoraReport {
+    pub period: ReportPeriod,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub application_count: usize,
+    pub totals: DoraCriticalityGroup,
+    pub by_criticality: Vec<DoraCriticalityGroup>,
+    pub applications: Vec<DoraApplicationMetrics>,
+}
+
+/// Build the DORA report for active `is_dora_fei` applications.
+pub async fn build(pool: &PgPool, period: ReportPeriod) -> Result<DoraReport, AppError> {
+    let period_end = Utc::now();
+    let period_start = period_end - period.duration();
+
+    let applications = sqlx::query_as::<_, DoraApplicationMetrics>(
+        r#"
+        SELECT
+            a.id AS application_id,
+            a.app_name,
+            a.app_code,
+            a.criticality,
+            COUNT(f.id) FILTER (WHERE f.open) AS open_total,
+            COUNT(f.id) FILTER (WHERE f.open AND f.effective_severity = 'Critical') AS open_critical,
+            COUNT(f.id) FILTER (WHERE f.open AND f.effective_severity = 'High') AS open_high,
+            COUNT(f.id) FILTER (WHERE f.open AND f.effective_severity = 'Medium') AS open_medium,
+            COUNT(f.id) FILTER (WHERE f.open AND f.effective_severity = 'Low') AS open_low,
+            COUNT(f.id) FILTER (WHERE f.open AND f.sla_status = 'At_Risk') AS sla_at_risk,
+            COUNT(f.id) FILTER (WHERE f.open AND f.sla_status = 'Breached') AS sla_breached,
+            COUNT(f.id) FILTER (WHERE f.remediated) AS remediated_in_period,
+            COUNT(f.id) FILTER (WHERE f.remediated AND f.sla_due_date IS NOT NULL) AS remediated_with_sla,
+            COUNT(f.id) FILTER (WHERE f.remediated AND f.status_changed_at <= f.sla_due_date) AS remediated_within_sla,
+            COALESCE(SUM(EXTRACT(EPOCH FROM (f.status_changed_at - f.first_seen)) / 86400.0)
+                FILTER (WHERE f.remediated), 0)::double precision AS remediation_days_total
+        FROM applications a
+        LEFT JOIN (
+            SELECT
+                id, application_id, effective_severity, sla_status, sla_due_date,
+                first_seen, status_changed_at,
+                status NOT IN ('Closed', 'Invalidated', 'False_Positive') AS open,
+                status IN ('Mitigated', 'Verified', 'Closed')
+                    AND status_changed_at BETWEEN $1 AND $2 AS remediated
+            FROM findings
+        ) f ON f.application_id = a.id
+        WHERE a.is_dora_fei = true
+          AND a.status = 'Active'
+        GROUP BY a.id, a.app_name, a.app_code, a.criticality
+        ORDER BY a.criticality NULLS LAST, a.app_name
+        "#,
+    )
+    .bind(period_start)
+    .bind(period_end)
+    .fetch_all(pool)
+    .await?;
+
+    let by_criticality = group_by_criticality(&applications);
+    let totals = summarize(None, applications.iter().map(|a| &a.metrics));
+
+    Ok(DoraReport {
+        period,
+        period_start,
+        period_end,
+        application_count: applications.len(),
+        totals,
+        by_criticality,
+        applications,
+    })
+}
+
+/// Group application metrics by criticality, preserving criticality order.
+///
+/// Input rows are ordered by criticality, so consecutive rows share a group.
+fn group_by_criticality(apps: &[DoraApplicationMetrics]) -> Vec<DoraCriticalityGroup> {
+    let mut groups: Vec<DoraCriticalityGroup> = Vec::new();
+    for app in apps {
+        match groups.last_mut() {
+            Some(group) if group.criticality == app.criticality => {
+                group.application_count += 1;
+                group.metrics.add(&app.metrics);
+            }
+            _ => groups.push(summarize(
+                app.criticality.clone(),
+                std::iter::once(&app.metrics),
+            )),
+        }
+    }
+    for group in &mut groups {
+        group.sla_adherence_pct = group.metrics.sla_adherence_pct();
+        group.mttr_days = group.metrics.mttr_days();
+    }
+    groups
+}
+
+/// Sum metrics into a group with derived percentages.
+fn summarize<'a>(
+    criticality: Option<AssetCriticality>,
+    metrics: impl Iterator<Item = &'a DoraMetrics>,
+) -> DoraCriticalityGroup {
+    let mut total = DoraMetrics::default();
+    let mut count = 0;
+    for m in metrics {
+        total.add(m);
+        count += 1;
+    }
+    DoraCriticalityGroup {
+        criticality,
+        application_count: count,
+        sla_adherence_pct: total.sla_adherence_pct(),
+        mttr_days: total.mttr_days(),
+        metrics: total,
+    }
+}
+
+/// Display label for a criticality group.
+fn criticality_label(criticality: Option<&AssetCriticality>) -> &'static str {
+    match criticality {
+        Some(AssetCriticality::VeryHigh) => "Very High",
+        Some(AssetCriticality::High) => "High",
+        Some(AssetCriticality::MediumHigh) => "Medium-High",
+        Some(AssetCriticality::Medium) => "Medium",
+        Some(AssetCriticality::MediumLow) => "Medium-Low",
+        Some(AssetCriticality::Low) => "Low",
+        None => "Unclassified",
+    }
+}
+
+fn pct(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |v| format!("{v:.1}%"))
+}
+
+fn days(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_string(), |v| format!("{v:.1}"))
+}
+
+/// Lay out the DORA report as a printable document.
+pub fn to_document(report: &DoraReport) -> ReportDocument {
+    let groups_with_total = report
+        .by_criticality
+        .iter()
+        .map(|g| (criticality_label(g.criticality.as_ref()), g))
+        .chain(std::iter::once(("Total", &report.totals)));
+
+    let mut exposure_rows = Vec::new();
+    let mut remediation_rows = Vec::new();
+    for (label, g) in groups_with_total {
+        let m = &g.metrics;
+        exposure_rows.push(vec![
+            label.to_string(),
+            g.application_count.to_string(),
+            m.open_total.to_string(),
+            m.open_critical.to_string(),
+            m.open_high.to_string(),
+            m.sla_at_risk.to_string(),
+            m.sla_breached.to_string(),
+        ]);
+        remediation_rows.push(vec![
+            label.to_string(),
+            m.remediated_in_period.to_string(),
+            pct(g.sla_adherence_pct),
+            days(g.mttr_days),
+        ]);
+    }
+
+    let blocks = vec![
+        ReportBlock::Heading(
+            "1. ICT assets supporting critical or important functions (Art. 8)".to_string(),
+        ),
+        ReportBlock::Paragraph(format!(
+            "{} active applications are flagged as supporting critical or important functions \
+             and are in scope of this report.",
+            report.application_count
+        )),
+        ReportBlock::Table {
+            columns: vec![
+                "Application".to_string(),
+                "Code".to_string(),
+                "Criticality".to_string(),
+                "Open".to_string(),
+                "Breached".to_string(),
+            ],
+            rows: report
+                .applications
+                .iter()
+                .map(|a| {
+                    vec![
+                        a.app_name.clone(),
+                        a.app_code.clone(),
+                        criticality_label(a.criticality.as_ref()).to_string(),
+                        a.metrics.open_total.to_string(),
+                        a.metrics.sla_breached.to_string(),
+                    ]
+                })
+                .collect(),
+        },
+        ReportBlock::Heading("2. Open vulnerability exposure by criticality (Art. 9)".to_string()),
+        ReportBlock::Table {
+            columns: vec![
+                "Criticality".to_string(),
+                "Apps".to_string(),
+                "Open".to_string(),
+                "Critical".to_string(),
+                "High".to_string(),
+                "SLA at risk".to_string(),
+                "SLA breached".to_string(),
+            ],
+            rows: exposure_rows,
+        },
+        ReportBlock::Heading(
+            "3. Vulnerability remediation timeliness (RTS 2024/1774 Art. 10)".to_string(),
+        ),
+        ReportBlock::Table {
+            columns: vec![
+                "Criticality".to_string(),
+                "Remediated".to_string(),
+                "Within SLA".to_string(),
+                "MTTR (days)".to_string(),
+            ],
+            rows: remediation_rows,
+        },
+    ];
+
+    ReportDocument {
+        title: "DORA ICT Risk - Application Security Report".to_string(),
+        subtitle: Some(format!(
+            "{} period {} to {}",
+            report.period.label(),
+            report.period_start.format("%Y-%m-%d"),
+            report.period_end.format("%Y-%m-%d"),
+        )),
+        blocks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(
+        criticality: Option<AssetCriticality>,
+        open: i64,
+        fixed: i64,
+        days: f64,
+    ) -> DoraApplicationMetrics {
+        DoraApplicationMetrics {
+            application_id: Uuid::new_v4(),
+            app_name: "app".to_string(),
+            app_code: "APP".to_string(),
+            criticality,
+            metrics: DoraMetrics {
+                open_total: open,
+                remediated_in_period: fixed,
+                remediated_with_sla: fixed,
+                remediated_within_sla: fixed / 2,
+                remediation_days_total: days,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn groups_consecutive_rows_by_criticality() {
+        let apps = vec![
+            app(Some(AssetCriticality::VeryHigh), 3, 2, 20.0),
+            app(Some(AssetCriticality::VeryHigh), 1, 2, 40.0),
+            app(Some(AssetCriticality::Medium), 5, 0, 0.0),
+            app(None, 2, 0, 0.0),
+        ];
+        let groups = group_by_criticality(&apps);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].application_count, 2);
+        assert_eq!(groups[0].metrics.open_total, 4);
+        assert_eq!(groups[0].mttr_days, Some(15.0));
+        assert_eq!(groups[0].sla_adherence_pct, Some(50.0));
+        assert_eq!(groups[1].mttr_days, None);
+        assert_eq!(groups[2].criticality, None);
+    }
+
+    #[test]
+    fn totals_sum_all_applications() {
+        let apps = [
+            app(Some(AssetCriticality::High), 3, 1, 10.0),
+            app(None, 2, 1, 30.0),
+        ];
+        let totals = summarize(None, apps.iter().map(|a| &a.metrics));
+        assert_eq!(totals.application_count, 2);
+        assert_eq!(totals.metrics.open_total, 5);
+        assert_eq!(totals.mttr_days, Some(20.0));
+    }
+
+    #[test]
+    fn document_has_article_sections() {
+        let apps = vec![app(Some(AssetCriticality::High), 3, 1, 10.0)];
+        let report = DoraReport {
+            period: ReportPeriod::Quarter,
+            period_start: Utc::now(),
+            period_end: Utc::now(),
+            application_count: 1,
+            totals: summarize(None, apps.iter().map(|a| &a.metrics)),
+            by_criticality: group_by_criticality(&apps),
+            applications: apps,
+        };
+        let doc = to_document(&report);
+        let headings: Vec<&str> = doc
+            .blocks
+            .iter()
+            .filter_map(|b| match b {
+                ReportBlock::Heading(h) => Some(h.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(headings.len(), 3);
+        assert!(headings[1].contains("Art. 9"));
+    }
+}
@@ -0,0 +1,634 @@
+//! End-of-life detection from the endoflife.date dataset.
+//!
+//! A background task fetches the release cycles of the frameworks and
+//! runtimes listed in [`PRODUCTS`], then looks for packages in a cycle past
+//! its end of support: those of open SCA findings and those of each
+//! application's latest SBOM. Each one gets a low-severity SCA finding from
+//! source tool `endoflife.date`, even when no CVE affects it yet, since no
+//! fix will be published for the next one. The portfolio report lists the
+//! same packages by product cycle, along with those reaching end of support
+//! soon.
+
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::models::finding::{CreateFinding, FindingCategory, SeverityLevel};
+use crate::models::finding_sca::CreateFindingSca;
+use crate::services::finding::{self, CategoryData};
+use crate::services::fingerprint;
+
+/// Timeout for a single endoflife.date request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Source tool of the findings raised for end-of-life packages.
+pub const SOURCE_TOOL: &str = "endoflife.date";
+
+/// Default window, in days, for cycles reaching end of support soon.
+const DEFAULT_HORIZON_DAYS: i64 = 90;
+
+/// endoflife.date products and the package names (artifact part, lower case)
+/// they are released as.
+pub const PRODUCTS: &[(&str, &[&str])] = &[
+    ("spring-boot", &["spring-boot", "spring-boot-autoconfigure"]),
+    (
+        "spring-framework",
+        &[
+            "spring-core",
+            "spring-context",
+            "spring-web",
+            "spring-webmvc",
+        ],
+    ),
+    ("log4j", &["log4j-core", "log4j"]),
+    ("tomcat", &["tomcat-embed-core", "tomcat-catalina"]),
+    ("angular", &["@angular/core"]),
+    ("angularjs", &["angular"]),
+    ("react", &["react"]),
+    ("vue", &["vue"]),
+    ("jquery", &["jquery"]),
+    ("bootstrap", &["bootstrap"]),
+    ("django", &["django"]),
+    ("rails", &["rails", "railties"]),
+    ("laravel", &["laravel/framework"]),
+    ("symfony", &["symfony/http-kernel", "symfony/symfony"]),
+    ("drupal", &["drupal/core"]),
+    ("nodejs", &["node", "nodejs"]),
+    ("python", &["python", "cpython"]),
+    (
+        "dotnet",
+        &["microsoft.netcore.app", "microsoft.aspnetcore.app"],
+    ),
+    ("go", &["stdlib"]),
+];
+
+/// endoflife.date connection settings derived from [`AppConfig`].
+#[derive(Debug, Clone)]
+pub struct EolSettings {
+    pub url: String,
+    pub interval: Duration,
+}
+
+impl EolSettings {
+    /// Build settings from config, returning `None` when detection is disabled.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        if !config.eol_detection_enabled {
+            return None;
+        }
+        Some(Self {
+            url: config.eol_api_url.trim_end_matches('/').to_string(),
+            interval: Duration::from_secs(config.eol_detection_interval_secs.max(1)),
+        })
+    }
+}
+
+/// A release cycle as endoflife.date returns it. `cycle` is a string or a
+/// number, `eol` a date or a boolean.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiCycle {
+    cycle: serde_json::Value,
+    release_date: Option<String>,
+    #[serde(default)]
+    eol: serde_json::Value,
+    latest: Option<serde_json::Value>,
+}
+
+/// A stored release cycle.
+#[derive(Debug, Clone, FromRow)]
+struct Cycle {
+    product: String,
+    cycle: String,
+    release_date: Option<NaiveDate>,
+    eol_date: Option<NaiveDate>,
+    eol_reached: bool,
+    latest: Option<String>,
+}
+
+impl Cycle {
+    /// Whether support ended on or before `today`.
+    fn is_eol(&self, today: NaiveDate) -> bool {
+        self.eol_date.map_or(self.eol_reached, |date| date <= today)
+    }
+}
+
+fn value_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_cycles(product: &str, data: &[u8]) -> Result<Vec<Cycle>, AppError> {
+    let cycles: Vec<ApiCycle> = serde_json::from_slice(data).map_err(|e| {
+        AppError::Validation(format!(
+            "Invalid endoflife.date response for {product}: {e}"
+        ))
+    })?;
+    let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok();
+    Ok(cycles
+        .into_iter()
+        .filter_map(|c| {
+            Some(Cycle {
+                product: product.to_string(),
+                cycle: value_text(&c.cycle)?,
+                release_date: c.release_date.as_deref().and_then(date),
+                eol_date: c.eol.as_str().and_then(date),
+                eol_reached: c.eol.as_bool().unwrap_or(false),
+                latest: c.latest.as_ref().and_then(value_text),
+            })
+        })
+        .collect())
+}
+
+/// The product a package belongs to, from its name (Maven `group:artifact`
+/// names are matched on the artifact).
+fn product_for(package_name: &str) -> Option<&'static str> {
+    let name = package_name.trim().to_lowercase();
+    let artifact = name.rsplit(':').next().unwrap_or(&name);
+    PRODUCTS
+        .iter()
+        .find(|(_, packages)| packages.contains(&artifact))
+        .map(|(product, _)| *product)
+}
+
+/// The cycle of `cycles` that `version` belongs to: the longest cycle equal
+/// to the version or a dotted prefix of it.
+fn cycle_for<'a>(version: &str, cycles: impl IntoIterator<Item = &'a Cycle>) -> Option<&'a Cycle> {
+    let version = version.trim();
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let version = version.strip_prefix("go").unwrap_or(version);
+    cycles
+        .into_iter()
+        .filter(|c| {
+            version == c.cycle
+                || version
+                    .strip_prefix(c.cycle.as_str())
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+        .max_by_key(|c| c.cycle.len())
+}
+
+/// Latest release of the newest supported cycle of a product.
+fn upgrade_target(cycles: &[Cycle], product: &str, today: NaiveDate) -> Option<String> {
+    cycles
+        .iter()
+        .filter(|c| c.product == product && !c.is_eol(today))
+        .max_by_key(|c| c.release_date)
+        .and_then(|c| c.latest.clone().or_else(|| Some(c.cycle.clone())))
+}
+
+// ---------------------------------------------------------------------------
+// Dataset refresh
+// ---------------------------------------------------------------------------
+
+/// Replace the stored cycles of one product.
+async fn replace_product(pool: &PgPool, product: &str, cycles: &[Cycle]) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM eol_cycles WHERE product = $1")
+        .bind(product)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO eol_cycles (product, cycle, release_date, eol_date, eol_reached, latest)
+        SELECT $1, cycle, release_date, eol_date, eol_reached, latest
+        FROM UNNEST($2::text[], $3::date[], $4::date[], $5::bool[], $6::text[])
+            AS c(cycle, release_date, eol_date, eol_reached, latest)
+        ON CONFLICT (product, cycle) DO NOTHING
+        "#,
+    )
+    .bind(product)
+    .bind(cycles.iter().map(|c| c.cycle.as_str()).collect::<Vec<_>>())
+    .bind(cycles.iter().map(|c| c.release_date).collect::<Vec<_>>())
+    .bind(cycles.iter().map(|c| c.eol_date).collect::<Vec<_>>())
+    .bind(cycles.iter().map(|c| c.eol_reached).collect::<Vec<_>>())
+    .bind(
+        cycles
+            .iter()
+            .map(|c| c.latest.as_deref())
+            .collect::<Vec<_>>(),
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn fetch_product(
+    client: &reqwest::Client,
+    url: &str,
+    product: &str,
+) -> Result<Vec<Cycle>, AppError> {
+    let resp = client
+        .get(format!("{url}/{product}.json"))
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("endoflife.date request failed: {e}")))?;
+    if !resp.status().is_success() {
+        return Err(AppError::Internal(format!(
+            "endoflife.date returned {} for {product}",
+            resp.status()
+        )));
+    }
+    let body = resp
+        .bytes()
+        .await
+        .map_err(|e| AppError::Internal(format!("endoflife.date request failed: {e}")))?;
+    parse_cycles(product, &body)
+}
+
+/// Fetch and store the cycles of every tracked product; a product that
+/// fails keeps its previously stored cycles.
+async fn refresh(pool: &PgPool, client: &reqwest::Client, url: &str) {
+    for (product, _) in PRODUCTS {
+        let stored = match fetch_product(client, url, product).await {
+            Ok(cycles) if cycles.is_empty() => Err(AppError::Internal(
+                "endoflife.date returned no cycles".to_string(),
+            )),
+            Ok(cycles) => replace_product(pool, product, &cycles).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = stored {
+            tracing::warn!(product, error = %e, "End-of-life cycle refresh failed");
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Detection
+// ---------------------------------------------------------------------------
+
+/// A package used by an application, from an SCA finding or its latest SBOM.
+#[derive(Debug, FromRow)]
+struct PackageUse {
+    application_id: Uuid,
+    app_code: String,
+    package_name: String,
+    package_version: String,
+    package_type: Option<String>,
+}
+
+/// A package in a cycle past, or near, its end of support.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EolPackage {
+    pub application_id: Uuid,
+    pub app_code: String,
+    pub package_name: String,
+    pub package_version: String,
+}
+
+/// Applications using one product cycle past, or near, its end of support.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EolCycleUsage {
+    pub product: String,
+    pub cycle: String,
+    /// End of support; `None` when the dataset gives no date.
+    pub eol_date: Option<NaiveDate>,
+    /// False for cycles still supported but ending within the horizon.
+    pub past_eol: bool,
+    /// Latest release of the newest supported cycle.
+    pub upgrade_to: Option<String>,
+    pub application_count: usize,
+    pub packages: Vec<EolPackage>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EolReport {
+    pub as_of: NaiveDate,
+    pub horizon_days: i64,
+    /// Past end of support first, then by end date.
+    pub cycles: Vec<EolCycleUsage>,
+}
+
+/// Query parameters for the portfolio report.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EolReportParams {
+    /// Also list cycles whose support ends within this many days (default 90).
+    pub horizon_days: Option<i64>,
+}
+
+async fn load_cycles(pool: &PgPool) -> Result<Vec<Cycle>, AppError> {
+    let cycles = sqlx::query_as::<_, Cycle>(
+        "SELECT product, cycle, release_date, eol_date, eol_reached, latest FROM eol_cycles",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(cycles)
+}
+
+/// Packages of open SCA findings and of each application's latest SBOM.
+async fn load_packages(pool: &PgPool) -> Result<Vec<PackageUse>, AppError> {
+    let packages = sqlx::query_as::<_, PackageUse>(
+        r#"
+        SELECT f.application_id, a.app_code, s.package_name, s.package_version,
+               s.package_type
+        FROM findings f
+        JOIN finding_sca s ON s.finding_id = f.id
+        JOIN applications a ON a.id = f.application_id
+        WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND f.archived_at IS NULL
+          AND f.source_tool <> $1
+          AND a.status <> 'Decommissioned'
+        UNION
+        SELECT latest.application_id, a.app_code, c.name, c.version, c.package_type
+        FROM (
+            SELECT DISTINCT ON (application_id) id, application_id
+            FROM sboms
+            ORDER BY application_id, created_at DESC
+        ) latest
+        JOIN sbom_components c ON c.sbom_id = latest.id
+        JOIN applications a ON a.id = latest.application_id
+        WHERE a.status <> 'Decommissioned'
+        "#,
+    )
+    .bind(SOURCE_TOOL)
+    .fetch_all(pool)
+    .await?;
+    Ok(packages)
+}
+
+/// Package uses in a cycle whose support ends before `until`, with the cycle.
+fn match_packages<'a>(
+    packages: &'a [PackageUse],
+    cycles: &'a [Cycle],
+    until: NaiveDate,
+) -> Vec<(&'a PackageUse, &'a Cycle)> {
+    let mut seen = HashSet::new();
+    packages
+        .iter()
+        .filter_map(|package| {
+            let product = product_for(&package.package_name)?;
+            let cycle = cycle_for(
+                &package.package_version,
+                cycles.iter().filter(|c| c.product == product),
+            )?;
+            let key = (
+                package.application_id,
+                package.package_name.to_lowercase(),
+                package.package_version.clone(),
+            );
+            (cycle.is_eol(until) && seen.insert(key)).then_some((package, cycle))
+        })
+        .collect()
+}
+
+/// The finding raised for a package in an end-of-life cycle.
+fn eol_finding(
+    package: &PackageUse,
+    cycle: &Cycle,
+    upgrade_to: Option<String>,
+) -> (CreateFinding, CategoryData) {
+    let ended = cycle
+        .eol_date
+        .map(|d| format!("on {d}"))
+        .unwrap_or_else(|| "already".to_string());
+    let upgrade = upgrade_to
+        .as_deref()
+        .map(|v| format!(" Upgrade to a supported release such as {v}."))
+        .unwrap_or_default();
+    let core = CreateFinding {
+        source_tool: SOURCE_TOOL.to_string(),
+        source_tool_version: None,
+        source_finding_id: format!("{}:{}", cycle.product, cycle.cycle),
+        finding_category: FindingCategory::Sca,
+        title: format!(
+            "{} {} is past end of life",
+            package.package_name, package.package_version
+        ),
+        description: format!(
+            "{} {} reached end of support {ended}. Vulnerabilities found in it from now on \
+             will not be fixed.{upgrade}",
+            cycle.product, cycle.cycle
+        ),
+        normalized_severity: SeverityLevel::Low,
+        original_severity: "EOL".to_string(),
+        cvss_score: None,
+        cvss_vector: None,
+        cwe_ids: vec!["CWE-1104".to_string()],
+        cve_ids: Vec::new(),
+        owasp_category: None,
+        confidence: None,
+        fingerprint: fingerprint::compute_sca(
+            &package.app_code,
+            &package.package_name,
+            &package.package_version,
+            &format!("EOL:{}:{}", cycle.product, cycle.cycle),
+        ),
+        application_id: Some(package.application_id),
+        tags: vec!["eol".to_string()],
+        remediation_guidance: upgrade_to
+            .as_deref()
+            .map(|v| format!("Upgrade {} to {v}", cycle.product)),
+        raw_finding: serde_json::json!({
+            "product": cycle.product,
+            "cycle": cycle.cycle,
+            "eol": cycle.eol_date,
+            "latest": cycle.latest,
+        }),
+        metadata: serde_json::json!({
+            "eol_product": cycle.product,
+            "eol_cycle": cycle.cycle,
+            "eol_date": cycle.eol_date,
+        }),
+    };
+    let sca = CategoryData::Sca(CreateFindingSca {
+        package_name: package.package_name.clone(),
+        package_version: package.package_version.clone(),
+        package_type: package.package_type.clone(),
+        fixed_version: upgrade_to,
+        dependency_type: None,
+        dependency_path: None,
+        license: None,
+        license_risk: None,
+        sbom_reference: None,
+        epss_score: None,
+        known_exploited: None,
+        exploit_maturity: None,
+        affected_artifact: None,
+        build_project: None,
+    });
+    (core, sca)
+}
+
+/// Raise a finding for every package past end of life that has none yet.
+/// A finding closed or dismissed earlier is not raised again. Returns the
+/// number of findings created.
+pub async fn detect(pool: &PgPool) -> Result<usize, AppError> {
+    let today = Utc::now().date_naive();
+    let cycles = load_cycles(pool).await?;
+    let packages = load_packages(pool).await?;
+
+    let mut created = 0;
+    for (package, cycle) in match_packages(&packages, &cycles, today) {
+        let upgrade_to = upgrade_target(&cycles, &cycle.product, today);
+        let (core, sca) = eol_finding(package, cycle, upgrade_to);
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM findings WHERE fingerprint = $1)",
+        )
+        .bind(&core.fingerprint)
+        .fetch_one(pool)
+        .await?;
+        if !exists {
+            finding::create(pool, &core, &sca).await?;
+            created += 1;
+        }
+    }
+    Ok(created)
+}
+
+/// Spawn the background task that refreshes the dataset and raises findings.
+pub fn spawn_detector(pool: PgPool, settings: EolSettings) {
+    tokio::spawn(async move {
+        let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to build endoflife.date client; end-of-life detection disabled");
+                return;
+            }
+        };
+        let mut ticker = tokio::time::interval(settings.interval);
+        loop {
+            ticker.tick().await;
+            refresh(&pool, &client, &settings.url).await;
+            match detect(&pool).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "Raised end-of-life package findings"),
+                Err(e) => tracing::error!(error = %e, "End-of-life detection failed"),
+            }
+        }
+    });
+}
+
+/// Product cycles in use across the portfolio that are past end of support,
+/// or reach it within the horizon.
+pub async fn report(pool: &PgPool, params: &EolReportParams) -> Result<EolReport, AppError> {
+    let horizon_days = params.horizon_days.unwrap_or(DEFAULT_HORIZON_DAYS);
+    if !(0..=3650).contains(&horizon_days) {
+        return Err(AppError::Validation(
+            "horizon_days must be between 0 and 3650".to_string(),
+        ));
+    }
+    let today = Utc::now().date_naive();
+    let until = today + chrono::Duration::days(horizon_days);
+    let cycles = load_cycles(pool).await?;
+    let packages = load_packages(pool).await?;
+
+    let mut usage: BTreeMap<(String, String), EolCycleUsage> = BTreeMap::new();
+    for (package, cycle) in match_packages(&packages, &cycles, until) {
+        let entry = usage
+            .entry((cycle.product.clone(), cycle.cycle.clone()))
+            .or_insert_with(|| EolCycleUsage {
+                product: cycle.product.clone(),
+                cycle: cycle.cycle.clone(),
+                eol_date: cycle.eol_date,
+                past_eol: cycle.is_eol(today),
+                upgrade_to: upgrade_target(&cycles, &cycle.product, today),
+                application_count: 0,
+                packages: Vec::new(),
+            });
+        entry.packages.push(EolPackage {
+            application_id: package.application_id,
+            app_code: package.app_code.clone(),
+            package_name: package.package_name.clone(),
+            package_version: package.package_version.clone(),
+        });
+    }
+
+    let mut cycles: Vec<EolCycleUsage> = usage.into_values().collect();
+    for usage in &mut cycles {
+        usage.packages.sort_by(|a, b| a.app_code.cmp(&b.app_code));
+        let apps: HashSet<Uuid> = usage.packages.iter().map(|p| p.application_id).collect();
+        usage.application_count = apps.len();
+    }
+    cycles.sort_by(|a, b| {
+        b.past_eol
+            .cmp(&a.past_eol)
+            .then(a.eol_date.cmp(&b.eol_date))
+    });
+
+    Ok(EolReport {
+        as_of: today,
+        horizon_days,
+        cycles,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(product: &str, cycle: &str, eol: Option<&str>) -> Cycle {
+        Cycle {
+            product: product.to_string(),
+            cycle: cycle.to_string(),
+            release_date: None,
+            eol_date: eol.map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap()),
+            eol_reached: false,
+            latest: None,
+        }
+    }
+
+    #[test]
+    fn cycles_accept_dates_booleans_and_numbers() {
+        let data = br#"[
+            {"cycle": "3.2", "releaseDate": "2023-11-23", "eol": "2025-05-22", "latest": "3.2.12"},
+            {"cycle": 2.7, "releaseDate": "2022-05-19", "eol": true, "latest": "2.7.18"},
+            {"cycle": "3.4", "eol": false}
+        ]"#;
+        let cycles = parse_cycles("spring-boot", data).unwrap();
+        assert_eq!(cycles.len(), 3);
+        assert_eq!(cycles[0].eol_date, NaiveDate::from_ymd_opt(2025, 5, 22));
+        assert_eq!(cycles[1].cycle, "2.7");
+        assert!(cycles[1].eol_reached);
+        assert!(!cycles[2].eol_reached && cycles[2].eol_date.is_none());
+    }
+
+    #[test]
+    fn packages_map_to_products_by_artifact() {
+        assert_eq!(
+            product_for("org.springframework.boot:spring-boot"),
+            Some("spring-boot")
+        );
+        assert_eq!(product_for("@angular/core"), Some("angular"));
+        assert_eq!(product_for("Django"), Some("django"));
+        assert_eq!(product_for("left-pad"), None);
+    }
+
+    #[test]
+    fn versions_match_the_longest_cycle_prefix() {
+        let cycles = vec![
+            cycle("python", "3", None),
+            cycle("python", "3.8", None),
+            cycle("python", "3.11", None),
+        ];
+        assert_eq!(cycle_for("3.8.18", &cycles).unwrap().cycle, "3.8");
+        assert_eq!(cycle_for("v3.11", &cycles).unwrap().cycle, "3.11");
+        assert_eq!(cycle_for("3.80.1", &cycles).unwrap().cycle, "3");
+        assert!(cycle_for("2.7.18", &cycles).is_none());
+    }
+
+    #[test]
+    fn upgrade_target_is_the_newest_supported_cycle() {
+        let today = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let mut old = cycle("django", "3.2", Some("2024-04-01"));
+        old.release_date = NaiveDate::from_ymd_opt(2021, 4, 6);
+        let mut lts = cycle("django", "4.2", Some("2026-04-30"));
+        lts.release_date = NaiveDate::from_ymd_opt(2023, 4, 3);
+        lts.latest = Some("4.2.20".to_string());
+        assert!(old.is_eol(today) && !lts.is_eol(today));
+        assert_eq!(
+            upgrade_target(&[old, lts], "django", today),
+            Some("4.2.20".to_string())
+        );
+    }
+}
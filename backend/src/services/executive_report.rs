@@ -0,0 +1,480 @@
+//! Executive summary report: portfolio risk trend, top risky applications,
+//! SLA compliance, and attack chain highlights for a reporting period.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::SeverityLevel;
+use crate::services::pdf_report::{ReportBlock, ReportDocument};
+
+/// Number of applications listed in the top-risk table.
+const TOP_APPS_LIMIT: i64 = 10;
+
+/// Number of applications listed in the attack chain highlights.
+const ATTACK_CHAIN_LIMIT: i64 = 5;
+
+/// Reporting window for executive reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPeriod {
+    Month,
+    #[default]
+    Quarter,
+    Year,
+}
+
+impl ReportPeriod {
+    /// Length of the window ending now.
+    pub fn duration(self) -> Duration {
+        match self {
+            Self::Month => Duration::days(30),
+            Self::Quarter => Duration::days(91),
+            Self::Year => Duration::days(365),
+        }
+    }
+
+    /// Spacing of risk trend data points (a PostgreSQL interval literal).
+    /// Weekly points for month/quarter, monthly points for a year.
+    pub(crate) fn trend_interval(self) -> &'static str {
+        match self {
+            Self::Month | Self::Quarter => "1 week",
+            Self::Year => "1 month",
+        }
+    }
+
+    /// Human-readable label used in report titles.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Month => "Monthly",
+            Self::Quarter => "Quarterly",
+            Self::Year => "Annual",
+        }
+    }
+}
+
+/// All data shown in the executive summary.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExecutiveSummary {
+    pub period: ReportPeriod,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub risk_trend: Vec<RiskTrendPoint>,
+    pub top_risky_apps: Vec<RiskyApplication>,
+    pub sla: SlaCompliance,
+    pub attack_chains: Vec<AttackChainHighlight>,
+}
+
+/// Portfolio-wide open findings and risk at one point in time.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct RiskTrendPoint {
+    pub at: DateTime<Utc>,
+    pub open_findings: i64,
+    pub open_critical_high: i64,
+    pub total_risk_score: f64,
+}
+
+/// Application ranked by the summed risk of its open findings.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct RiskyApplication {
+    pub id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    pub open_findings: i64,
+    pub critical_count: i64,
+    pub high_count: i64,
+    pub total_risk_score: f64,
+}
+
+/// SLA adherence for the period plus the current SLA status of open findings.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct SlaCompliance {
+    /// Findings with an SLA that were resolved during the period.
+    pub resolved_with_sla: i64,
+    /// Of those, the ones resolved on or before their due date.
+    pub resolved_within_sla: i64,
+    pub open_on_track: i64,
+    pub open_at_risk: i64,
+    pub open_breached: i64,
+}
+
+impl SlaCompliance {
+    /// Share of SLA-bound findings resolved in time, as a percentage.
+    /// `None` when nothing SLA-bound was resolved in the period.
+    pub fn compliance_pct(&self) -> Option<f64> {
+        (self.resolved_with_sla > 0)
+            .then(|| self.resolved_within_sla as f64 * 100.0 / self.resolved_with_sla as f64)
+    }
+}
+
+/// Application with the most open cross-tool correlated findings.
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct AttackChainHighlight {
+    pub application_id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    pub correlated_findings: i64,
+    pub tool_count: i64,
+    pub max_severity: SeverityLevel,
+}
+
+/// Gather the executive summary for the period ending now.
+pub async fn build(pool: &PgPool, period: ReportPeriod) -> Result<ExecutiveSummary, AppError> {
+    let period_end = Utc::now();
+    let period_start = period_end - period.duration();
+
+    let (risk_trend, top_risky_apps, sla, attack_chains) = tokio::try_join!(
+        fetch_risk_trend(pool, period, period_start, period_end),
+        fetch_top_risky_apps(pool),
+        fetch_sla_compliance(pool, period_start, period_end),
+        fetch_attack_chain_highlights(pool),
+    )?;
+
+    Ok(ExecutiveSummary {
+        period,
+        period_start,
+        period_end,
+        risk_trend,
+        top_risky_apps,
+        sla,
+        attack_chains,
+    })
+}
+
+/// Reconstruct open findings and risk at regular points across the period.
+///
+/// A finding counts as open at time `t` if it was first seen by `t` and
+/// either is still open or left the open states after `t`.
+async fn fetch_risk_trend(
+    pool: &PgPool,
+    period: ReportPeriod,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<RiskTrendPoint>, AppError> {
+    let rows = sqlx::query_as::<_, RiskTrendPoint>(
+        r#"
+        SELECT
+            b.at,
+            COUNT(f.id) AS open_findings,
+            COALESCE(SUM(CASE WHEN f.effective_severity IN ('Critical', 'High') THEN 1 ELSE 0 END), 0) AS open_critical_high,
+            COALESCE(SUM(f.composite_risk_score::double precision), 0) AS total_risk_score
+        FROM generate_series($1::timestamptz, $2::timestamptz, $3::interval) AS b(at)
+        LEFT JOIN findings f
+          ON f.first_seen <= b.at
+         AND (f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+              OR f.status_changed_at > b.at)
+        GROUP BY b.at
+        ORDER BY b.at
+        "#,
+    )
+    .bind(start)
+    .bind(end)
+    .bind(period.trend_interval())
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Top applications by summed composite risk of open findings.
+async fn fetch_top_risky_apps(pool: &PgPool) -> Result<Vec<RiskyApplication>, AppError> {
+    let rows = sqlx::query_as::<_, RiskyApplication>(
+        r#"
+        SELECT
+            a.id,
+            a.app_name,
+            a.app_code,
+            COUNT(f.id) AS open_findings,
+            COALESCE(SUM(CASE WHEN f.effective_severity = 'Critical' THEN 1 ELSE 0 END), 0) AS critical_count,
+            COALESCE(SUM(CASE WHEN f.effective_severity = 'High'     THEN 1 ELSE 0 END), 0) AS high_count,
+            COALESCE(SUM(f.composite_risk_score::double precision), 0) AS total_risk_score
+        FROM applications a
+        INNER JOIN findings f ON f.application_id = a.id
+        WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+        GROUP BY a.id, a.app_name, a.app_code
+        ORDER BY total_risk_score DESC, open_findings DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(TOP_APPS_LIMIT)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// SLA adherence of findings resolved in the period, plus current SLA status.
+async fn fetch_sla_compliance(
+    pool: &PgPool,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<SlaCompliance, AppError> {
+    let row = sqlx::query_as::<_, SlaCompliance>(
+        r#"
+        SELECT
+            COALESCE(SUM(CASE WHEN resolved THEN 1 ELSE 0 END), 0) AS resolved_with_sla,
+            COALESCE(SUM(CASE WHEN resolved AND status_changed_at <= sla_due_date THEN 1 ELSE 0 END), 0) AS resolved_within_sla,
+            COALESCE(SUM(CASE WHEN NOT resolved AND sla_status = 'On_Track' THEN 1 ELSE 0 END), 0) AS open_on_track,
+            COALESCE(SUM(CASE WHEN NOT resolved AND sla_status = 'At_Risk'  THEN 1 ELSE 0 END), 0) AS open_at_risk,
+            COALESCE(SUM(CASE WHEN NOT resolved AND sla_status = 'Breached' THEN 1 ELSE 0 END), 0) AS open_breached
+        FROM (
+            SELECT
+                status_changed_at,
+                sla_due_date,
+                sla_status,
+                status IN ('Closed', 'Invalidated', 'False_Positive') AS resolved
+            FROM findings
+            WHERE sla_due_date IS NOT NULL
+        ) f
+        WHERE NOT resolved OR status_changed_at BETWEEN $1 AND $2
+        "#,
+    )
+    .bind(start)
+    .bind(end)
+    .fetch_one(pool)
+    .await?;
+    Ok(row)
+}
+
+/// Applications with the most open findings in correlation groups.
+async fn fetch_attack_chain_highlights(
+    pool: &PgPool,
+) -> Result<Vec<AttackChainHighlight>, AppError> {
+    // Severity enum values are declared most-severe first, so MIN() yields
+    // the highest severity in the chain.
+    let rows = sqlx::query_as::<_, AttackChainHighlight>(
+        r#"
+        SELECT
+            a.id AS application_id,
+            a.app_name,
+            a.app_code,
+            COUNT(DISTINCT f.id) AS correlated_findings,
+            COUNT(DISTINCT f.source_tool) AS tool_count,
+            MIN(f.effective_severity) AS max_severity
+        FROM finding_relationships fr
+        JOIN findings f
+          ON f.id = fr.source_finding_id OR f.id = fr.target_finding_id
+        JOIN applications a ON a.id = f.application_id
+        WHERE fr.relationship_type IN ('correlated_with', 'grouped_under')
+          AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+        GROUP BY a.id, a.app_name, a.app_code
+        ORDER BY correlated_findings DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(ATTACK_CHAIN_LIMIT)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Lay out the executive summary as a printable report.
+pub fn to_document(summary: &ExecutiveSummary) -> ReportDocument {
+    let mut blocks = Vec::new();
+
+    blocks.push(ReportBlock::Heading("Portfolio risk trend".to_string()));
+    if let (Some(first), Some(last)) = (summary.risk_trend.first(), summary.risk_trend.last()) {
+        blocks.push(ReportBlock::Paragraph(format!(
+            "Open findings moved from {} to {} ({}) over the period; open critical and high \
+             findings moved from {} to {}.",
+            first.open_findings,
+            last.open_findings,
+            signed(last.open_findings - first.open_findings),
+            first.open_critical_high,
+            last.open_critical_high,
+        )));
+    }
+    blocks.push(ReportBlock::Table {
+        columns: vec![
+            "Date".to_string(),
+            "Open findings".to_string(),
+            "Critical + High".to_string(),
+            "Total risk".to_string(),
+        ],
+        rows: summary
+            .risk_trend
+            .iter()
+            .map(|p| {
+                vec![
+                    p.at.format("%Y-%m-%d").to_string(),
+                    p.open_findings.to_string(),
+                    p.open_critical_high.to_string(),
+                    format!("{:.0}", p.total_risk_score),
+                ]
+            })
+            .collect(),
+    });
+
+    blocks.push(ReportBlock::Heading("Top risky applications".to_string()));
+    if summary.top_risky_apps.is_empty() {
+        blocks.push(ReportBlock::Paragraph("No applications with open findings.".to_string()));
+    } else {
+        blocks.push(ReportBlock::Table {
+            columns: vec![
+                "Application".to_string(),
+                "Code".to_string(),
+                "Open".to_string(),
+                "Critical".to_string(),
+                "High".to_string(),
+                "Total risk".to_string(),
+            ],
+            rows: summary
+                .top_risky_apps
+                .iter()
+                .map(|a| {
+                    vec![
+                        a.app_name.clone(),
+                        a.app_code.clone(),
+                        a.open_findings.to_string(),
+                        a.critical_count.to_string(),
+                        a.high_count.to_string(),
+                        format!("{:.0}", a.total_risk_score),
+                    ]
+                })
+                .collect(),
+        });
+    }
+
+    let sla = &summary.sla;
+    blocks.push(ReportBlock::Heading("SLA compliance".to_string()));
+    blocks.push(ReportBlock::KeyValues(vec![
+        (
+            "Resolved within SLA".to_string(),
+            match sla.compliance_pct() {
+                Some(pct) => format!(
+                    "{pct:.1}% ({} of {})",
+                    sla.resolved_within_sla, sla.resolved_with_sla
+                ),
+                None => "n/a (no SLA-bound findings resolved)".to_string(),
+            },
+        ),
+        ("Open, on track".to_string(), sla.open_on_track.to_string()),
+        ("Open, at risk".to_string(), sla.open_at_risk.to_string()),
+        ("Open, breached".to_string(), sla.open_breached.to_string()),
+    ]));
+
+    blocks.push(ReportBlock::Heading("Attack chain highlights".to_string()));
+    if summary.attack_chains.is_empty() {
+        blocks.push(ReportBlock::Paragraph(
+            "No open cross-tool correlated findings.".to_string(),
+        ));
+    } else {
+        blocks.push(ReportBlock::Table {
+            columns: vec![
+                "Application".to_string(),
+                "Code".to_string(),
+                "Correlated findings".to_string(),
+                "Tools".to_string(),
+                "Max severity".to_string(),
+            ],
+            rows: summary
+                .attack_chains
+                .iter()
+                .map(|c| {
+                    vec![
+                        c.app_name.clone(),
+                        c.app_code.clone(),
+                        c.correlated_findings.to_string(),
+                        c.tool_count.to_string(),
+                        format!("{:?}", c.max_severity),
+                    ]
+                })
+                .collect(),
+        });
+    }
+
+    ReportDocument {
+        title: format!("{} Security Executive Summary", summary.period.label()),
+        subtitle: Some(format!(
+            "Period {} to {} (generated {} UTC)",
+            summary.period_start.format("%Y-%m-%d"),
+            summary.period_end.format("%Y-%m-%d"),
+            summary.period_end.format("%Y-%m-%d %H:%M"),
+        )),
+        blocks,
+    }
+}
+
+/// Format a delta with an explicit sign.
+fn signed(n: i64) -> String {
+    if n > 0 {
+        format!("+{n}")
+    } else {
+        n.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> ExecutiveSummary {
+        let end = Utc::now();
+        ExecutiveSummary {
+            period: ReportPeriod::Quarter,
+            period_start: end - ReportPeriod::Quarter.duration(),
+            period_end: end,
+            risk_trend: vec![
+                RiskTrendPoint {
+                    at: end - Duration::days(7),
+                    open_findings: 120,
+                    open_critical_high: 30,
+                    total_risk_score: 5400.0,
+                },
+                RiskTrendPoint {
+                    at: end,
+                    open_findings: 100,
+                    open_critical_high: 22,
+                    total_risk_score: 4300.0,
+                },
+            ],
+            top_risky_apps: vec![],
+            sla: SlaCompliance {
+                resolved_with_sla: 40,
+                resolved_within_sla: 30,
+                open_on_track: 50,
+                open_at_risk: 10,
+                open_breached: 5,
+            },
+            attack_chains: vec![],
+        }
+    }
+
+    #[test]
+    fn period_deserializes_lowercase() {
+        let p: ReportPeriod = serde_json::from_str("\"year\"").unwrap();
+        assert_eq!(p, ReportPeriod::Year);
+        assert_eq!(ReportPeriod::default(), ReportPeriod::Quarter);
+    }
+
+    #[test]
+    fn sla_compliance_percentage() {
+        assert_eq!(summary().sla.compliance_pct(), Some(75.0));
+        let empty = SlaCompliance {
+            resolved_with_sla: 0,
+            resolved_within_sla: 0,
+            open_on_track: 0,
+            open_at_risk: 0,
+            open_breached: 0,
+        };
+        assert_eq!(empty.compliance_pct(), None);
+    }
+
+    #[test]
+    fn document_summarizes_trend_delta() {
+        let doc = to_document(&summary());
+        assert_eq!(doc.title, "Quarterly Security Executive Summary");
+        let has_delta = doc.blocks.iter().any(|b| {
+            matches!(b, ReportBlock::Paragraph(text) if text.contains("from 120 to 100 (-20)"))
+        });
+        assert!(has_delta);
+    }
+
+    #[test]
+    fn signed_formats_positive_with_plus() {
+        assert_eq!(signed(5), "+5");
+        assert_eq!(signed(-3), "-3");
+        assert_eq!(signed(0), "0");
+    }
+}
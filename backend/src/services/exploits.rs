@@ -0,0 +1,378 @@
+//! Exploit maturity from the Exploit-DB and Metasploit module indexes.
+//!
+//! A background task downloads both indexes, replaces each source's
+//! references, and raises the `exploit_maturity` of SCA findings to the best
+//! exploit known for any of their CVEs, so the risk score's exploitability
+//! factor reflects weaponization rather than scanner defaults. A maturity
+//! reported by the scanner is never lowered.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::models::exploit::{ExploitReference, ExploitSource};
+use crate::models::finding_sca::ExploitMaturity;
+use crate::services::nvd::normalize_cve_id;
+
+/// Timeout for an index download; the Metasploit index is tens of MB.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Exploit index settings derived from [`AppConfig`].
+#[derive(Debug, Clone)]
+pub struct ExploitSettings {
+    pub exploitdb_url: String,
+    pub metasploit_url: String,
+    pub interval: Duration,
+}
+
+impl ExploitSettings {
+    /// Build settings from config, returning `None` when enrichment is disabled.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        if !config.exploit_enrichment_enabled {
+            return None;
+        }
+        Some(Self {
+            exploitdb_url: config.exploitdb_index_url.clone(),
+            metasploit_url: config.metasploit_index_url.clone(),
+            interval: Duration::from_secs(config.exploit_enrichment_interval_secs.max(1)),
+        })
+    }
+}
+
+fn parse_date(value: Option<&str>) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value?.trim(), "%Y-%m-%d").ok()
+}
+
+/// Entries of Exploit-DB's `files_exploits.csv` with a CVE in `codes`
+/// (`CVE-2021-44228;OSVDB-...`), one per CVE.
+fn parse_exploitdb(data: &[u8]) -> Result<Vec<ExploitReference>, AppError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(data);
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::Validation(format!("Invalid Exploit-DB index: {e}")))?
+        .clone();
+    let column = |name: &str| headers.iter().position(|h| h.trim() == name);
+    let (Some(id_col), Some(title_col), Some(codes_col)) =
+        (column("id"), column("description"), column("codes"))
+    else {
+        return Err(AppError::Validation(
+            "Exploit-DB index must have 'id', 'description', and 'codes' columns".to_string(),
+        ));
+    };
+    let verified_col = column("verified");
+    let published_col = column("date_published");
+
+    let mut references = Vec::new();
+    for result in reader.records() {
+        let Ok(record) = result else {
+            continue;
+        };
+        let cell = |col: Option<usize>| col.and_then(|c| record.get(c)).map(str::trim);
+        let (Some(id), Some(title)) = (cell(Some(id_col)), cell(Some(title_col))) else {
+            continue;
+        };
+        let maturity = if cell(verified_col) == Some("1") {
+            ExploitMaturity::Functional
+        } else {
+            ExploitMaturity::ProofOfConcept
+        };
+        let published_on = parse_date(cell(published_col));
+        for code in cell(Some(codes_col)).unwrap_or("").split(';') {
+            let Ok(cve_id) = normalize_cve_id(code) else {
+                continue;
+            };
+            references.push(ExploitReference {
+                source: ExploitSource::ExploitDb,
+                reference: id.to_string(),
+                cve_id,
+                title: title.to_string(),
+                maturity: maturity.clone(),
+                url: Some(format!("https://www.exploit-db.com/exploits/{id}")),
+                published_on,
+            });
+        }
+    }
+    Ok(references)
+}
+
+#[derive(Debug, Deserialize)]
+struct MetasploitModule {
+    name: String,
+    fullname: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    references: Vec<String>,
+    disclosure_date: Option<String>,
+}
+
+/// Exploit and auxiliary modules of Metasploit's `modules_metadata_base.json`
+/// with a CVE reference, one per CVE.
+fn parse_metasploit(data: &[u8]) -> Result<Vec<ExploitReference>, AppError> {
+    let modules: HashMap<String, MetasploitModule> = serde_json::from_slice(data)
+        .map_err(|e| AppError::Validation(format!("Invalid Metasploit index: {e}")))?;
+
+    let mut references = Vec::new();
+    for module in modules.into_values() {
+        let maturity = match module.kind.as_str() {
+            "exploit" => ExploitMaturity::Weaponized,
+            "auxiliary" => ExploitMaturity::Functional,
+            _ => continue,
+        };
+        for reference in &module.references {
+            let Ok(cve_id) = normalize_cve_id(reference) else {
+                continue;
+            };
+            references.push(ExploitReference {
+                source: ExploitSource::Metasploit,
+                reference: module.fullname.clone(),
+                cve_id,
+                title: module.name.clone(),
+                maturity: maturity.clone(),
+                url: Some(format!(
+                    "https://www.rapid7.com/db/modules/{}/",
+                    module.fullname
+                )),
+                published_on: parse_date(module.disclosure_date.as_deref()),
+            });
+        }
+    }
+    Ok(references)
+}
+
+/// Replace all references of one source.
+async fn replace_source(
+    pool: &PgPool,
+    source: ExploitSource,
+    references: &[ExploitReference],
+) -> Result<u64, AppError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM exploit_references WHERE source = $1")
+        .bind(source)
+        .execute(&mut *tx)
+        .await?;
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO exploit_references
+            (source, reference, cve_id, title, maturity, url, published_on)
+        SELECT $1, reference, cve_id, title, maturity, url, published_on
+        FROM UNNEST($2::text[], $3::text[], $4::text[], $5::exploit_maturity[], $6::text[], $7::date[])
+            AS r(reference, cve_id, title, maturity, url, published_on)
+        ON CONFLICT (source, reference, cve_id) DO NOTHING
+        "#,
+    )
+    .bind(source)
+    .bind(references.iter().map(|r| r.reference.as_str()).collect::<Vec<_>>())
+    .bind(references.iter().map(|r| r.cve_id.as_str()).collect::<Vec<_>>())
+    .bind(references.iter().map(|r| r.title.as_str()).collect::<Vec<_>>())
+    .bind(references.iter().map(|r| r.maturity.clone()).collect::<Vec<_>>())
+    .bind(references.iter().map(|r| r.url.as_deref()).collect::<Vec<_>>())
+    .bind(references.iter().map(|r| r.published_on).collect::<Vec<_>>())
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    tx.commit().await?;
+    Ok(inserted)
+}
+
+/// Raise the exploit maturity of SCA findings to the best exploit for any of
+/// their CVEs. Returns the number of findings changed.
+pub async fn apply_maturity(pool: &PgPool) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        WITH best AS (
+            SELECT f.id AS finding_id,
+                   MAX(CASE e.maturity
+                           WHEN 'Weaponized' THEN 3
+                           WHEN 'Functional' THEN 2
+                           WHEN 'Proof_of_Concept' THEN 1
+                           ELSE 0
+                       END) AS rank
+            FROM findings f
+            CROSS JOIN LATERAL jsonb_array_elements_text(f.cve_ids) AS cve(id)
+            JOIN exploit_references e ON e.cve_id = UPPER(cve.id)
+            WHERE f.finding_category = 'SCA'
+            GROUP BY f.id
+        )
+        UPDATE finding_sca s
+        SET exploit_maturity = (CASE best.rank
+                                    WHEN 3 THEN 'Weaponized'
+                                    WHEN 2 THEN 'Functional'
+                                    ELSE 'Proof_of_Concept'
+                                END)::exploit_maturity
+        FROM best
+        WHERE s.finding_id = best.finding_id
+          AND best.rank > CASE s.exploit_maturity
+                              WHEN 'Weaponized' THEN 3
+                              WHEN 'Functional' THEN 2
+                              WHEN 'Proof_of_Concept' THEN 1
+                              ELSE 0
+                          END
+        "#,
+    )
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, AppError> {
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("Exploit index download failed: {e}")))?;
+    if !resp.status().is_success() {
+        return Err(AppError::Internal(format!(
+            "Exploit index download returned {}",
+            resp.status()
+        )));
+    }
+    let body = resp
+        .bytes()
+        .await
+        .map_err(|e| AppError::Internal(format!("Exploit index download failed: {e}")))?;
+    Ok(body.to_vec())
+}
+
+/// Download and store one index; failures are logged and leave the
+/// previously stored references in place.
+async fn refresh_source(pool: &PgPool, client: &reqwest::Client, source: ExploitSource, url: &str) {
+    let parsed = match download(client, url).await {
+        Ok(data) => match source {
+            ExploitSource::ExploitDb => parse_exploitdb(&data),
+            ExploitSource::Metasploit => parse_metasploit(&data),
+        },
+        Err(e) => Err(e),
+    };
+    let stored = match parsed {
+        Ok(references) if references.is_empty() => Err(AppError::Internal(
+            "Exploit index has no CVE references".to_string(),
+        )),
+        Ok(references) => replace_source(pool, source, &references).await,
+        Err(e) => Err(e),
+    };
+    match stored {
+        Ok(count) => tracing::info!(source = ?source, count, "Stored exploit references"),
+        Err(e) => tracing::error!(source = ?source, error = %e, "Exploit index refresh failed"),
+    }
+}
+
+/// Spawn the background task that refreshes both indexes and applies them.
+pub fn spawn_enricher(pool: PgPool, settings: ExploitSettings) {
+    tokio::spawn(async move {
+        let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to build exploit index client; exploit enrichment disabled");
+                return;
+            }
+        };
+        let mut ticker = tokio::time::interval(settings.interval);
+        loop {
+            ticker.tick().await;
+            refresh_source(
+                &pool,
+                &client,
+                ExploitSource::ExploitDb,
+                &settings.exploitdb_url,
+            )
+            .await;
+            refresh_source(
+                &pool,
+                &client,
+                ExploitSource::Metasploit,
+                &settings.metasploit_url,
+            )
+            .await;
+            match apply_maturity(&pool).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "Raised exploit maturity of SCA findings"),
+                Err(e) => tracing::error!(error = %e, "Applying exploit maturity failed"),
+            }
+        }
+    });
+}
+
+/// Known public exploits for a CVE, most mature first.
+pub async fn for_cve(pool: &PgPool, cve_id: &str) -> Result<Vec<ExploitReference>, AppError> {
+    let references = sqlx::query_as::<_, ExploitReference>(
+        r#"
+        SELECT * FROM exploit_references
+        WHERE cve_id = $1
+        ORDER BY CASE maturity
+                     WHEN 'Weaponized' THEN 0
+                     WHEN 'Functional' THEN 1
+                     ELSE 2
+                 END,
+                 source, reference
+        "#,
+    )
+    .bind(cve_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(references)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exploitdb_entries_are_split_per_cve() {
+        let csv = "id,file,description,date_published,author,type,platform,port,date_added,date_updated,verified,codes,tags\n\
+                   50592,exploits/java/remote/50592.py,Apache Log4j 2 - Remote Code Execution (RCE),2021-12-14,kozmer,remote,java,,2021-12-14,2021-12-14,0,CVE-2021-45046;CVE-2021-44228,\n\
+                   12345,exploits/php/webapps/12345.txt,Old bug,2010-01-01,someone,webapps,php,,2010-01-01,2010-01-01,1,OSVDB-1,\n\
+                   40000,exploits/linux/local/40000.c,Kernel LPE,2016-06-01,someone,local,linux,,2016-06-01,2016-06-01,1,cve-2016-5195,\n";
+        let refs = parse_exploitdb(csv.as_bytes()).unwrap();
+        let cves: Vec<&str> = refs.iter().map(|r| r.cve_id.as_str()).collect();
+        assert_eq!(cves, ["CVE-2021-45046", "CVE-2021-44228", "CVE-2016-5195"]);
+        assert_eq!(refs[0].maturity, ExploitMaturity::ProofOfConcept);
+        assert_eq!(refs[2].maturity, ExploitMaturity::Functional);
+        assert_eq!(
+            refs[0].url.as_deref(),
+            Some("https://www.exploit-db.com/exploits/50592")
+        );
+        assert_eq!(refs[0].published_on, NaiveDate::from_ymd_opt(2021, 12, 14));
+    }
+
+    #[test]
+    fn metasploit_exploit_modules_are_weaponized() {
+        let index = serde_json::json!({
+            "exploit_multi/http/log4shell_header_injection": {
+                "name": "Log4Shell HTTP Header Injection",
+                "fullname": "exploit/multi/http/log4shell_header_injection",
+                "type": "exploit",
+                "references": ["CVE-2021-44228", "URL-https://example.com"],
+                "disclosure_date": "2021-12-09"
+            },
+            "auxiliary_scanner/http/log4shell_scanner": {
+                "name": "Log4Shell HTTP Scanner",
+                "fullname": "auxiliary/scanner/http/log4shell_scanner",
+                "type": "auxiliary",
+                "references": ["CVE-2021-44228"],
+                "disclosure_date": null
+            },
+            "post_multi/gather/env": {
+                "name": "Env",
+                "fullname": "post/multi/gather/env",
+                "type": "post",
+                "references": ["CVE-2000-0001"]
+            }
+        });
+        let mut refs = parse_metasploit(index.to_string().as_bytes()).unwrap();
+        refs.sort_by(|a, b| a.reference.cmp(&b.reference));
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].maturity, ExploitMaturity::Functional);
+        assert_eq!(refs[1].maturity, ExploitMaturity::Weaponized);
+        assert_eq!(refs[1].cve_id, "CVE-2021-44228");
+        assert_eq!(refs[1].published_on, NaiveDate::from_ymd_opt(2021, 12, 9));
+    }
+}
@@ -0,0 +1,348 @@
+//! False-positive rates per tool, scanner rule, and application from
+//! `finding_history` status transitions.
+//!
+//! A finding counts as triaged in the period when it moved to Confirmed or
+//! False_Positive within it, and as a false positive when it moved to
+//! False_Positive within it. Rules come from the scanner rule catalog, so the
+//! noisiest ones can be taken back to the scanner teams.
+
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::services::executive_report::ReportPeriod;
+
+/// Triaged findings a rule or application needs before it is listed.
+const DEFAULT_MIN_TRIAGED: i64 = 5;
+
+/// Query parameters for the false-positive report.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FalsePositiveParams {
+    /// Window ending now (defaults to `quarter`).
+    #[serde(default)]
+    pub period: ReportPeriod,
+    /// Restrict the report to one source tool.
+    pub source_tool: Option<String>,
+    /// Triaged findings a rule or application needs to be listed (defaults to 5).
+    pub min_triaged: Option<i64>,
+}
+
+/// Triage outcomes for one group of findings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema, sqlx::FromRow)]
+pub struct FalsePositiveStats {
+    /// Findings confirmed or marked false positive in the period.
+    pub triaged: i64,
+    /// Findings marked false positive in the period.
+    pub false_positives: i64,
+    /// Findings for which a false positive was requested in the period.
+    pub false_positive_requests: i64,
+    /// `false_positives / triaged`; `None` when nothing was triaged.
+    #[sqlx(skip)]
+    pub false_positive_rate: Option<f64>,
+}
+
+impl FalsePositiveStats {
+    fn with_rate(mut self) -> Self {
+        self.false_positive_rate =
+            (self.triaged > 0).then(|| self.false_positives as f64 / self.triaged as f64);
+        self
+    }
+}
+
+/// False-positive rate of one source tool.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ToolFalsePositives {
+    pub source_tool: String,
+    #[serde(flatten)]
+    pub stats: FalsePositiveStats,
+}
+
+/// False-positive rate of one scanner rule.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct RuleFalsePositives {
+    pub scanner_rule_id: Uuid,
+    pub source_tool: String,
+    pub rule_id: String,
+    pub rule_name: Option<String>,
+    #[serde(flatten)]
+    pub stats: FalsePositiveStats,
+}
+
+/// False-positive rate of one application; `None` ids group unassigned findings.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ApplicationFalsePositives {
+    pub application_id: Option<Uuid>,
+    pub app_code: Option<String>,
+    #[serde(flatten)]
+    pub stats: FalsePositiveStats,
+}
+
+/// False-positive rates for the period, noisiest first.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FalsePositiveReport {
+    pub period: ReportPeriod,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub source_tool: Option<String>,
+    /// Rules and applications with fewer triaged findings are left out.
+    pub min_triaged: i64,
+    pub overall: FalsePositiveStats,
+    pub by_tool: Vec<ToolFalsePositives>,
+    pub by_rule: Vec<RuleFalsePositives>,
+    pub by_application: Vec<ApplicationFalsePositives>,
+}
+
+/// One grouping-set row: `dimension` is `overall`, `tool`, `rule`, or
+/// `application`.
+#[derive(Debug, sqlx::FromRow)]
+struct FalsePositiveRow {
+    dimension: String,
+    source_tool: Option<String>,
+    scanner_rule_id: Option<Uuid>,
+    rule_id: Option<String>,
+    rule_name: Option<String>,
+    application_id: Option<Uuid>,
+    app_code: Option<String>,
+    #[sqlx(flatten)]
+    stats: FalsePositiveStats,
+}
+
+/// Compute false-positive rates for the period ending now.
+pub async fn build(
+    pool: &PgPool,
+    params: &FalsePositiveParams,
+) -> Result<FalsePositiveReport, AppError> {
+    let period_end = Utc::now();
+    let period_start = period_end - params.period.duration();
+
+    let rows = sqlx::query_as::<_, FalsePositiveRow>(
+        r#"
+        WITH verdicts AS (
+            SELECT
+                f.id,
+                f.source_tool,
+                f.scanner_rule_id,
+                f.application_id,
+                bool_or(h.new_value IN ('Confirmed', 'False_Positive')) AS triaged,
+                bool_or(h.new_value = 'False_Positive') AS false_positive,
+                bool_or(h.new_value = 'False_Positive_Requested') AS requested
+            FROM findings f
+            JOIN finding_history h
+              ON h.finding_id = f.id AND h.field_changed = 'status'
+             AND h.created_at >= $1 AND h.created_at < $2
+            WHERE ($3::text IS NULL OR f.source_tool = $3)
+            GROUP BY f.id
+        )
+        SELECT
+            CASE
+                WHEN GROUPING(r.id) = 0 THEN 'rule'
+                WHEN GROUPING(a.id) = 0 THEN 'application'
+                WHEN GROUPING(v.source_tool) = 0 THEN 'tool'
+                ELSE 'overall'
+            END AS dimension,
+            v.source_tool,
+            r.id AS scanner_rule_id,
+            r.rule_id,
+            r.rule_name,
+            a.id AS application_id,
+            a.app_code,
+            COUNT(*) FILTER (WHERE v.triaged) AS triaged,
+            COUNT(*) FILTER (WHERE v.false_positive) AS false_positives,
+            COUNT(*) FILTER (WHERE v.requested) AS false_positive_requests
+        FROM verdicts v
+        LEFT JOIN scanner_rules r ON r.id = v.scanner_rule_id
+        LEFT JOIN applications a ON a.id = v.application_id
+        GROUP BY GROUPING SETS (
+            (),
+            (v.source_tool),
+            (v.source_tool, r.id, r.rule_id, r.rule_name),
+            (a.id, a.app_code)
+        )
+        "#,
+    )
+    .bind(period_start)
+    .bind(period_end)
+    .bind(&params.source_tool)
+    .fetch_all(pool)
+    .await?;
+
+    let min_triaged = params.min_triaged.unwrap_or(DEFAULT_MIN_TRIAGED).max(1);
+    let mut report = assemble(params.period, period_start, period_end, min_triaged, rows);
+    report.source_tool = params.source_tool.clone();
+    Ok(report)
+}
+
+/// Split grouping-set rows into the overall figures and per-dimension lists,
+/// dropping small rule and application groups and sorting noisiest first.
+fn assemble(
+    period: ReportPeriod,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    min_triaged: i64,
+    rows: Vec<FalsePositiveRow>,
+) -> FalsePositiveReport {
+    let mut report = FalsePositiveReport {
+        period,
+        period_start,
+        period_end,
+        source_tool: None,
+        min_triaged,
+        overall: FalsePositiveStats::default(),
+        by_tool: Vec::new(),
+        by_rule: Vec::new(),
+        by_application: Vec::new(),
+    };
+
+    for row in rows {
+        let stats = row.stats.with_rate();
+        match row.dimension.as_str() {
+            "overall" => report.overall = stats,
+            "tool" => report.by_tool.push(ToolFalsePositives {
+                source_tool: row.source_tool.unwrap_or_default(),
+                stats,
+            }),
+            "rule" if stats.triaged >= min_triaged => {
+                // Findings without a catalog rule form a group of their own
+                let (Some(scanner_rule_id), Some(rule_id)) = (row.scanner_rule_id, row.rule_id)
+                else {
+                    continue;
+                };
+                report.by_rule.push(RuleFalsePositives {
+                    scanner_rule_id,
+                    source_tool: row.source_tool.unwrap_or_default(),
+                    rule_id,
+                    rule_name: row.rule_name,
+                    stats,
+                });
+            }
+            "application" if stats.triaged >= min_triaged => {
+                report.by_application.push(ApplicationFalsePositives {
+                    application_id: row.application_id,
+                    app_code: row.app_code,
+                    stats,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    report
+        .by_tool
+        .sort_by(|a, b| noisiest_first(&a.stats, &b.stats));
+    report
+        .by_rule
+        .sort_by(|a, b| noisiest_first(&a.stats, &b.stats));
+    report
+        .by_application
+        .sort_by(|a, b| noisiest_first(&a.stats, &b.stats));
+    report
+}
+
+/// Highest rate first, larger samples breaking ties.
+fn noisiest_first(a: &FalsePositiveStats, b: &FalsePositiveStats) -> Ordering {
+    let rate = |s: &FalsePositiveStats| s.false_positive_rate.unwrap_or(-1.0);
+    rate(b)
+        .total_cmp(&rate(a))
+        .then_with(|| b.triaged.cmp(&a.triaged))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(dimension: &str, triaged: i64, false_positives: i64) -> FalsePositiveRow {
+        FalsePositiveRow {
+            dimension: dimension.to_string(),
+            source_tool: Some("SonarQube".to_string()),
+            scanner_rule_id: None,
+            rule_id: None,
+            rule_name: None,
+            application_id: None,
+            app_code: None,
+            stats: FalsePositiveStats {
+                triaged,
+                false_positives,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn rule_row(rule_id: &str, triaged: i64, false_positives: i64) -> FalsePositiveRow {
+        FalsePositiveRow {
+            scanner_rule_id: Some(Uuid::new_v4()),
+            rule_id: Some(rule_id.to_string()),
+            ..row("rule", triaged, false_positives)
+        }
+    }
+
+    fn assemble_rows(rows: Vec<FalsePositiveRow>) -> FalsePositiveReport {
+        let end = Utc::now();
+        let start = end - ReportPeriod::Quarter.duration();
+        assemble(ReportPeriod::Quarter, start, end, 5, rows)
+    }
+
+    #[test]
+    fn rates_are_computed_and_sorted_noisiest_first() {
+        let report = assemble_rows(vec![
+            row("overall", 40, 10),
+            rule_row("java:S100", 10, 1),
+            rule_row("java:S3649", 10, 8),
+            rule_row("java:S2077", 20, 16),
+            FalsePositiveRow {
+                source_tool: Some("JFrog Xray".to_string()),
+                ..row("tool", 0, 0)
+            },
+            row("tool", 40, 10),
+        ]);
+
+        assert_eq!(report.overall.false_positive_rate, Some(0.25));
+        let rules: Vec<&str> = report.by_rule.iter().map(|r| r.rule_id.as_str()).collect();
+        assert_eq!(rules, ["java:S2077", "java:S3649", "java:S100"]);
+        assert_eq!(report.by_tool[0].source_tool, "SonarQube");
+        assert_eq!(report.by_tool[1].stats.false_positive_rate, None);
+    }
+
+    #[test]
+    fn small_and_uncatalogued_groups_are_left_out() {
+        let report = assemble_rows(vec![
+            rule_row("rare", 2, 2),
+            row("rule", 30, 3),
+            row("application", 4, 4),
+            FalsePositiveRow {
+                app_code: Some("gpe30".to_string()),
+                ..row("application", 6, 3)
+            },
+        ]);
+
+        assert!(report.by_rule.is_empty());
+        assert_eq!(report.by_application.len(), 1);
+        assert_eq!(report.by_application[0].app_code.as_deref(), Some("gpe30"));
+        assert_eq!(
+            report.by_application[0].stats.false_positive_rate,
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn stats_serialize_inline() {
+        let json = serde_json::to_value(ToolFalsePositives {
+            source_tool: "SonarQube".to_string(),
+            stats: FalsePositiveStats {
+                triaged: 4,
+                false_positives: 1,
+                false_positive_requests: 2,
+                false_positive_rate: Some(0.25),
+            },
+        })
+        .unwrap();
+        assert_eq!(json["source_tool"], "SonarQube");
+        assert_eq!(json["false_positive_rate"], 0.25);
+        assert_eq!(json["false_positive_requests"], 2);
+    }
+}
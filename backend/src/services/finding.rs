@@ -1,42 +1,120 @@
 //! Finding service: CRUD, search, status transitions, comments, and history.
 
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
 use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use sqlx::{PgPool, Row};
+use sqlx::postgres::PgRow;
+use sqlx::{PgConnection, PgPool, Row};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::errors::AppError;
+use crate::middleware::auth::CurrentUser;
+use crate::models::application::{AssetCriticality, AssetTier};
 use crate::models::finding::{
-    CreateComment, CreateFinding, Finding, FindingCategory, FindingCategoryData, FindingComment,
-    FindingHistory, FindingStatus, FindingSummary, FindingSummaryWithCategory, SeverityLevel,
-    SlaStatus, UpdateFinding,
+    ConfidenceLevel, CreateComment, CreateFinding, Finding, FindingCategory, FindingCategoryData,
+    FindingComment, FindingHistory, FindingStatus, FindingSummary, FindingSummaryWithCategory,
+    SeverityLevel, SlaStatus, UpdateComment, UpdateFinding,
 };
 use crate::models::finding_dast::CreateFindingDast;
+use crate::models::finding_manual::CreateFindingManual;
 use crate::models::finding_sast::CreateFindingSast;
-use crate::models::finding_sca::CreateFindingSca;
-use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::finding_sca::{CreateFindingSca, ExploitMaturity};
+use crate::models::notification::NotificationKind;
+use crate::models::pagination::{PagedResult, Pagination, SortDirection};
+use crate::models::user::UserRole;
+use crate::services::config_cache::{self, SystemSettings};
+use crate::services::correlation_service;
+use crate::services::mentions;
+use crate::services::notification::{self, NewNotification};
+use crate::services::owasp;
+use crate::services::risk_score::{
+    self, ExploitabilityInput, FindingAgeInput, RiskFactors, SastTaintConfidence,
+};
+use crate::services::splunk_hec::{self, HecSink, PlatformEvent};
+use crate::services::watchlist;
 
 /// Category-specific data for finding creation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "category")]
 pub enum CategoryData {
     Sast(CreateFindingSast),
     Sca(CreateFindingSca),
     Dast(CreateFindingDast),
+    Manual(CreateFindingManual),
 }
 
 /// Combined finding with category-specific details for detail views.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct FindingWithDetails {
     #[serde(flatten)]
     pub finding: Finding,
     pub sast: Option<crate::models::finding_sast::FindingSast>,
     pub sca: Option<crate::models::finding_sca::FindingSca>,
     pub dast: Option<crate::models::finding_dast::FindingDast>,
+    pub manual: Option<crate::models::finding_manual::FindingManual>,
+    /// Catalog labels for the finding's CWE ids; ids not in the catalog are omitted.
+    pub cwes: Vec<crate::models::cwe::CweSummary>,
+}
+
+/// What the scanner reported for a finding, as stored at ingestion.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct RawFindingDocument {
+    pub finding_id: Uuid,
+    pub source_tool: String,
+    pub source_tool_version: Option<String>,
+    pub source_finding_id: String,
+    /// Severity in the tool's own scale.
+    pub original_severity: String,
+    pub normalized_severity: SeverityLevel,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// The scanner record, unmodified.
+    pub raw_finding: serde_json::Value,
+}
+
+impl RawFindingDocument {
+    /// Download file name, e.g. `sonarqube_<id>.raw.json`.
+    pub fn file_name(&self) -> String {
+        let tool: String = self
+            .source_tool
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{tool}_{}.raw.json", self.finding_id)
+    }
+}
+
+/// Maximum number of IDs accepted by a batch get.
+pub const MAX_BATCH_GET: usize = 200;
+
+/// Request for fetching several findings at once.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchGetRequest {
+    pub ids: Vec<Uuid>,
+}
+
+/// Findings found for a batch get, in request order, plus the IDs that
+/// matched nothing.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchGetResult {
+    pub items: Vec<FindingWithDetails>,
+    pub missing: Vec<Uuid>,
 }
 
 /// Filters for listing findings.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct FindingFilters {
     pub severity: Option<SeverityLevel>,
     pub status: Option<FindingStatus>,
@@ -44,10 +122,15 @@ pub struct FindingFilters {
     pub application_id: Option<Uuid>,
     pub source_tool: Option<String>,
     pub sla_status: Option<SlaStatus>,
+    /// OWASP Top 10 category, e.g. `A03` or `A03:2021`.
+    pub owasp_category: Option<String>,
     pub search: Option<String>,
     /// When true, LEFT JOINs category tables to include category-specific fields.
     #[serde(default)]
     pub include_category_data: Option<bool>,
+    /// When true, archived findings are listed too.
+    #[serde(default)]
+    pub include_archived: Option<bool>,
 
     // SAST-specific filters
     pub branch: Option<String>,
@@ -73,6 +156,12 @@ pub struct FindingFilters {
     pub dns_name: Option<String>,
     pub discovered_from: Option<DateTime<Utc>>,
     pub discovered_to: Option<DateTime<Utc>>,
+
+    /// Restrict to applications this user is assigned to. Set by the routes
+    /// from the caller's ownership scope, never from the query string.
+    #[serde(skip)]
+    #[param(ignore)]
+    pub visible_to: Option<Uuid>,
 }
 
 impl FindingFilters {
@@ -108,15 +197,187 @@ impl FindingFilters {
     }
 }
 
+/// How the finding `search` filter is parsed into a full-text query.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchSyntax {
+    /// `plainto_tsquery`: every word must match; operators are ignored.
+    #[default]
+    Plain,
+    /// `websearch_to_tsquery`: supports `"quoted phrases"`, `or`, and `-exclusion`.
+    Websearch,
+}
+
+impl SearchSyntax {
+    fn tsquery_function(self) -> &'static str {
+        match self {
+            Self::Plain => "plainto_tsquery",
+            Self::Websearch => "websearch_to_tsquery",
+        }
+    }
+}
+
+impl std::str::FromStr for SearchSyntax {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "plain" => Ok(Self::Plain),
+            "websearch" => Ok(Self::Websearch),
+            other => Err(format!(
+                "unknown search syntax '{other}' (expected 'plain' or 'websearch')"
+            )),
+        }
+    }
+}
+
+static SEARCH_SYNTAX: OnceLock<SearchSyntax> = OnceLock::new();
+
+/// Set the search syntax used by every finding list and export. Called once
+/// at startup; later calls are ignored.
+pub fn set_search_syntax(syntax: SearchSyntax) {
+    let _ = SEARCH_SYNTAX.set(syntax);
+}
+
+fn search_syntax() -> SearchSyntax {
+    SEARCH_SYNTAX.get().copied().unwrap_or_default()
+}
+
+/// Sortable columns for finding lists.
+///
+/// Enum columns sort in declaration order, so `asc` on `severity` lists
+/// Critical first. SAST, SCA, and DAST columns come from the category table;
+/// findings of other categories sort last.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSortField {
+    #[default]
+    RiskScore,
+    Severity,
+    Status,
+    Title,
+    SourceTool,
+    FirstSeen,
+    LastSeen,
+    SlaDueDate,
+    RuleId,
+    FilePath,
+    PackageName,
+    TargetUrl,
+}
+
+impl FindingSortField {
+    /// SQL expression against the `findings f` alias. Built only from this
+    /// fixed set of variants, never from user input.
+    fn expression(self) -> &'static str {
+        match self {
+            Self::RiskScore => "f.composite_risk_score",
+            Self::Severity => "f.effective_severity",
+            Self::Status => "f.status",
+            Self::Title => "f.title",
+            Self::SourceTool => "f.source_tool",
+            Self::FirstSeen => "f.first_seen",
+            Self::LastSeen => "f.last_seen",
+            Self::SlaDueDate => "f.sla_due_date",
+            Self::RuleId => "(SELECT s.rule_id FROM finding_sast s WHERE s.finding_id = f.id)",
+            Self::FilePath => "(SELECT s.file_path FROM finding_sast s WHERE s.finding_id = f.id)",
+            Self::PackageName => {
+                "(SELECT sc.package_name FROM finding_sca sc WHERE sc.finding_id = f.id)"
+            }
+            Self::TargetUrl => "(SELECT d.target_url FROM finding_dast d WHERE d.finding_id = f.id)",
+        }
+    }
+
+    /// Newest and riskiest first; everything else ascending.
+    fn default_direction(self) -> SortDirection {
+        match self {
+            Self::RiskScore | Self::FirstSeen | Self::LastSeen => SortDirection::Desc,
+            _ => SortDirection::Asc,
+        }
+    }
+}
+
+/// Sort parameters for finding lists.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FindingSort {
+    /// Column to sort by (defaults to risk score, then severity and first seen).
+    pub sort_by: Option<FindingSortField>,
+    /// Sort direction (defaults to descending for risk score and dates,
+    /// ascending otherwise).
+    pub sort_dir: Option<SortDirection>,
+}
+
+impl FindingSort {
+    /// ORDER BY clause body. Ties fall back to the finding ID so pages are stable.
+    pub fn order_by(&self) -> String {
+        let field = self.sort_by.unwrap_or_default();
+        let dir = self
+            .sort_dir
+            .unwrap_or_else(|| field.default_direction())
+            .as_sql();
+        match field {
+            FindingSortField::RiskScore => format!(
+                "f.composite_risk_score {dir} NULLS LAST, f.effective_severity ASC, f.first_seen DESC, f.id"
+            ),
+            _ => format!("{} {dir} NULLS LAST, f.id", field.expression()),
+        }
+    }
+}
+
+/// Filters and ordering for a finding's comments.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CommentListParams {
+    /// Order by creation time (defaults to oldest first).
+    pub sort_dir: Option<SortDirection>,
+    /// Whether deleted comments, kept with empty content so replies stay in
+    /// their thread, are listed (defaults to true).
+    pub include_deleted: Option<bool>,
+}
+
+/// Filters and ordering for a finding's change history.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HistoryListParams {
+    /// Order by time of the change (defaults to newest first).
+    pub sort_dir: Option<SortDirection>,
+    /// Comma-separated actions to include, e.g. `status_change,severity_override`.
+    pub action: Option<String>,
+}
+
+impl HistoryListParams {
+    /// The requested actions; `None` when every action is listed.
+    fn actions(&self) -> Option<Vec<String>> {
+        let actions: Vec<String> = self
+            .action
+            .as_deref()?
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(String::from)
+            .collect();
+        (!actions.is_empty()).then_some(actions)
+    }
+}
+
 /// Request body for status update.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct StatusUpdateRequest {
     pub status: FindingStatus,
     pub justification: Option<String>,
 }
 
+/// Request body for setting or clearing a severity override.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct SeverityOverrideRequest {
+    /// Severity replacing the tool's; `null` clears the override.
+    pub severity: Option<SeverityLevel>,
+    #[validate(length(min = 1, max = 2000))]
+    pub justification: String,
+}
+
 /// Request for bulk status update.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BulkStatusUpdate {
     pub finding_ids: Vec<Uuid>,
     pub status: FindingStatus,
@@ -124,32 +385,86 @@ pub struct BulkStatusUpdate {
 }
 
 /// Request for bulk assignment.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BulkAssign {
     pub finding_ids: Vec<Uuid>,
     pub remediation_owner: String,
 }
 
 /// Request for bulk tagging.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BulkTag {
     pub finding_ids: Vec<Uuid>,
     pub tags: Vec<String>,
 }
 
 /// Result of a bulk operation.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BulkResult {
     pub updated: usize,
     pub total: usize,
 }
 
+/// Findings targeted by bulk archive and delete: explicit IDs, every finding
+/// of the given applications, or both.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct BulkSelection {
+    #[serde(default)]
+    pub finding_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub application_ids: Vec<Uuid>,
+}
+
+impl BulkSelection {
+    fn validate(&self) -> Result<(), AppError> {
+        if self.finding_ids.is_empty() && self.application_ids.is_empty() {
+            return Err(AppError::Validation(
+                "Select findings by finding_ids or application_ids".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Request for bulk archive.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkArchive {
+    #[serde(flatten)]
+    pub selection: BulkSelection,
+    pub justification: Option<String>,
+}
+
+/// Request for bulk hard delete.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkDelete {
+    #[serde(flatten)]
+    pub selection: BulkSelection,
+    /// When true, only count the findings that would be deleted.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Result of a bulk hard delete.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkDeleteResult {
+    /// Findings matched by the selection.
+    pub matched: i64,
+    /// Findings removed; zero on a dry run.
+    pub deleted: u64,
+    pub dry_run: bool,
+}
+
 /// Create a finding with category-specific data in a transaction.
 pub async fn create(
     pool: &PgPool,
     input: &CreateFinding,
     category_data: &CategoryData,
 ) -> Result<Finding, AppError> {
+    let owasp_category = match &input.owasp_category {
+        Some(category) => Some(category.clone()),
+        None => owasp::category_for(pool, &input.cwe_ids).await?,
+    };
+
     let mut tx = pool.begin().await?;
 
     let finding = sqlx::query_as::<_, Finding>(
@@ -160,9 +475,10 @@ pub async fn create(
             normalized_severity, original_severity,
             cvss_score, cvss_vector, cwe_ids, cve_ids, owasp_category,
             confidence, fingerprint, application_id,
-            tags, remediation_guidance, raw_finding, metadata
+            tags, remediation_guidance, raw_finding, metadata, attack_technique_ids
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+                attack_techniques_for($11, $17))
         RETURNING *
         "#,
     )
@@ -178,7 +494,7 @@ pub async fn create(
     .bind(&input.cvss_vector)
     .bind(serde_json::to_value(&input.cwe_ids).unwrap_or_default())
     .bind(serde_json::to_value(&input.cve_ids).unwrap_or_default())
-    .bind(&input.owasp_category)
+    .bind(&owasp_category)
     .bind(&input.confidence)
     .bind(&input.fingerprint)
     .bind(input.application_id)
@@ -285,12 +601,279 @@ pub async fn create(
             .execute(&mut *tx)
             .await?;
         }
+        CategoryData::Manual(manual) => {
+            sqlx::query(
+                r#"
+                INSERT INTO finding_manual (
+                    finding_id, source, affected_asset, evidence,
+                    reporter, engagement, reported_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(finding.id)
+            .bind(manual.source)
+            .bind(&manual.affected_asset)
+            .bind(&manual.evidence)
+            .bind(&manual.reporter)
+            .bind(&manual.engagement)
+            .bind(manual.reported_at)
+            .execute(&mut *tx)
+            .await?;
+        }
     }
 
     tx.commit().await?;
     Ok(finding)
 }
 
+/// Create many findings with their category data in one transaction.
+///
+/// Each table is written with a single multi-row `UNNEST` insert, so the
+/// number of round trips does not grow with the batch. IDs are generated up
+/// front to link category rows; findings are returned in input order.
+pub async fn create_many(
+    pool: &PgPool,
+    items: &[(CreateFinding, CategoryData)],
+) -> Result<Vec<Finding>, AppError> {
+    if items.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<Uuid> = items.iter().map(|_| Uuid::new_v4()).collect();
+    let rows: Vec<(Uuid, &CreateFinding)> = ids
+        .iter()
+        .copied()
+        .zip(items.iter().map(|(f, _)| f))
+        .collect();
+
+    let mut tx = pool.begin().await?;
+
+    let findings = sqlx::query_as::<_, Finding>(
+        r#"
+        INSERT INTO findings (
+            id, source_tool, source_tool_version, source_finding_id,
+            finding_category, title, description,
+            normalized_severity, original_severity,
+            cvss_score, cvss_vector, cwe_ids, cve_ids, owasp_category,
+            confidence, fingerprint, application_id,
+            tags, remediation_guidance, raw_finding, metadata, attack_technique_ids
+        )
+        SELECT r.*, attack_techniques_for(r.cwe_ids, r.tags) FROM UNNEST(
+            $1::uuid[], $2::text[], $3::text[], $4::text[],
+            $5::finding_category[], $6::text[], $7::text[],
+            $8::severity_level[], $9::text[],
+            $10::real[], $11::text[], $12::jsonb[], $13::jsonb[], $14::text[],
+            $15::confidence_level[], $16::text[], $17::uuid[],
+            $18::jsonb[], $19::text[], $20::jsonb[], $21::jsonb[]
+        ) AS r(
+            id, source_tool, source_tool_version, source_finding_id,
+            finding_category, title, description,
+            normalized_severity, original_severity,
+            cvss_score, cvss_vector, cwe_ids, cve_ids, owasp_category,
+            confidence, fingerprint, application_id,
+            tags, remediation_guidance, raw_finding, metadata
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(&ids)
+    .bind(column(&rows, |(_, f)| f.source_tool.clone()))
+    .bind(column(&rows, |(_, f)| f.source_tool_version.clone()))
+    .bind(column(&rows, |(_, f)| f.source_finding_id.clone()))
+    .bind(column(&rows, |(_, f)| f.finding_category.clone()))
+    .bind(column(&rows, |(_, f)| f.title.clone()))
+    .bind(column(&rows, |(_, f)| f.description.clone()))
+    .bind(column(&rows, |(_, f)| f.normalized_severity.clone()))
+    .bind(column(&rows, |(_, f)| f.original_severity.clone()))
+    .bind(column(&rows, |(_, f)| f.cvss_score))
+    .bind(column(&rows, |(_, f)| f.cvss_vector.clone()))
+    .bind(column(&rows, |(_, f)| {
+        serde_json::to_value(&f.cwe_ids).unwrap_or_default()
+    }))
+    .bind(column(&rows, |(_, f)| {
+        serde_json::to_value(&f.cve_ids).unwrap_or_default()
+    }))
+    .bind(column(&rows, |(_, f)| f.owasp_category.clone()))
+    .bind(column(&rows, |(_, f)| f.confidence.clone()))
+    .bind(column(&rows, |(_, f)| f.fingerprint.clone()))
+    .bind(column(&rows, |(_, f)| f.application_id))
+    .bind(column(&rows, |(_, f)| {
+        serde_json::to_value(&f.tags).unwrap_or_default()
+    }))
+    .bind(column(&rows, |(_, f)| f.remediation_guidance.clone()))
+    .bind(column(&rows, |(_, f)| f.raw_finding.clone()))
+    .bind(column(&rows, |(_, f)| f.metadata.clone()))
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut sast = Vec::new();
+    let mut sca = Vec::new();
+    let mut dast = Vec::new();
+    let mut manual = Vec::new();
+    for (id, (_, category_data)) in ids.iter().copied().zip(items) {
+        match category_data {
+            CategoryData::Sast(data) => sast.push((id, data)),
+            CategoryData::Sca(data) => sca.push((id, data)),
+            CategoryData::Dast(data) => dast.push((id, data)),
+            CategoryData::Manual(data) => manual.push((id, data)),
+        }
+    }
+
+    if !sast.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO finding_sast (
+                finding_id, file_path, line_number_start, line_number_end,
+                project, rule_name, rule_id, issue_type, branch, source_url,
+                scanner_creation_date, baseline_date, last_analysis_date,
+                code_snippet, taint_source, taint_sink, language, framework,
+                scanner_description, scanner_tags, quality_gate
+            )
+            SELECT * FROM UNNEST(
+                $1::uuid[], $2::text[], $3::int4[], $4::int4[],
+                $5::text[], $6::text[], $7::text[], $8::text[], $9::text[], $10::text[],
+                $11::timestamptz[], $12::timestamptz[], $13::timestamptz[],
+                $14::text[], $15::text[], $16::text[], $17::text[], $18::text[],
+                $19::text[], $20::jsonb[], $21::text[]
+            )
+            "#,
+        )
+        .bind(column(&sast, |(id, _)| *id))
+        .bind(column(&sast, |(_, s)| s.file_path.clone()))
+        .bind(column(&sast, |(_, s)| s.line_number_start))
+        .bind(column(&sast, |(_, s)| s.line_number_end))
+        .bind(column(&sast, |(_, s)| s.project.clone()))
+        .bind(column(&sast, |(_, s)| s.rule_name.clone()))
+        .bind(column(&sast, |(_, s)| s.rule_id.clone()))
+        .bind(column(&sast, |(_, s)| s.issue_type.clone()))
+        .bind(column(&sast, |(_, s)| s.branch.clone()))
+        .bind(column(&sast, |(_, s)| s.source_url.clone()))
+        .bind(column(&sast, |(_, s)| s.scanner_creation_date))
+        .bind(column(&sast, |(_, s)| s.baseline_date))
+        .bind(column(&sast, |(_, s)| s.last_analysis_date))
+        .bind(column(&sast, |(_, s)| s.code_snippet.clone()))
+        .bind(column(&sast, |(_, s)| s.taint_source.clone()))
+        .bind(column(&sast, |(_, s)| s.taint_sink.clone()))
+        .bind(column(&sast, |(_, s)| s.language.clone()))
+        .bind(column(&sast, |(_, s)| s.framework.clone()))
+        .bind(column(&sast, |(_, s)| s.scanner_description.clone()))
+        .bind(column(&sast, |(_, s)| {
+            serde_json::to_value(&s.scanner_tags).unwrap_or_default()
+        }))
+        .bind(column(&sast, |(_, s)| s.quality_gate.clone()))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if !sca.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO finding_sca (
+                finding_id, package_name, package_version, package_type,
+                fixed_version, dependency_type, dependency_path, license,
+                license_risk, sbom_reference, epss_score, known_exploited,
+                exploit_maturity, affected_artifact, build_project
+            )
+            SELECT * FROM UNNEST(
+                $1::uuid[], $2::text[], $3::text[], $4::text[],
+                $5::text[], $6::dependency_type[], $7::text[], $8::text[],
+                $9::text[], $10::text[], $11::real[], $12::bool[],
+                $13::exploit_maturity[], $14::text[], $15::text[]
+            )
+            "#,
+        )
+        .bind(column(&sca, |(id, _)| *id))
+        .bind(column(&sca, |(_, s)| s.package_name.clone()))
+        .bind(column(&sca, |(_, s)| s.package_version.clone()))
+        .bind(column(&sca, |(_, s)| s.package_type.clone()))
+        .bind(column(&sca, |(_, s)| s.fixed_version.clone()))
+        .bind(column(&sca, |(_, s)| s.dependency_type.clone()))
+        .bind(column(&sca, |(_, s)| s.dependency_path.clone()))
+        .bind(column(&sca, |(_, s)| s.license.clone()))
+        .bind(column(&sca, |(_, s)| s.license_risk.clone()))
+        .bind(column(&sca, |(_, s)| s.sbom_reference.clone()))
+        .bind(column(&sca, |(_, s)| s.epss_score))
+        .bind(column(&sca, |(_, s)| s.known_exploited))
+        .bind(column(&sca, |(_, s)| s.exploit_maturity.clone()))
+        .bind(column(&sca, |(_, s)| s.affected_artifact.clone()))
+        .bind(column(&sca, |(_, s)| s.build_project.clone()))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if !dast.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO finding_dast (
+                finding_id, target_url, http_method, parameter,
+                attack_vector, request_evidence, response_evidence,
+                authentication_required, authentication_context,
+                web_application_name, scan_policy
+            )
+            SELECT * FROM UNNEST(
+                $1::uuid[], $2::text[], $3::text[], $4::text[],
+                $5::text[], $6::text[], $7::text[],
+                $8::bool[], $9::text[],
+                $10::text[], $11::text[]
+            )
+            "#,
+        )
+        .bind(column(&dast, |(id, _)| *id))
+        .bind(column(&dast, |(_, d)| d.target_url.clone()))
+        .bind(column(&dast, |(_, d)| d.http_method.clone()))
+        .bind(column(&dast, |(_, d)| d.parameter.clone()))
+        .bind(column(&dast, |(_, d)| d.attack_vector.clone()))
+        .bind(column(&dast, |(_, d)| d.request_evidence.clone()))
+        .bind(column(&dast, |(_, d)| d.response_evidence.clone()))
+        .bind(column(&dast, |(_, d)| d.authentication_required))
+        .bind(column(&dast, |(_, d)| d.authentication_context.clone()))
+        .bind(column(&dast, |(_, d)| d.web_application_name.clone()))
+        .bind(column(&dast, |(_, d)| d.scan_policy.clone()))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    if !manual.is_empty() {
+        sqlx::query(
+            r#"
+            INSERT INTO finding_manual (
+                finding_id, source, affected_asset, evidence,
+                reporter, engagement, reported_at
+            )
+            SELECT * FROM UNNEST(
+                $1::uuid[], $2::manual_finding_source[], $3::text[], $4::text[],
+                $5::text[], $6::text[], $7::timestamptz[]
+            )
+            "#,
+        )
+        .bind(column(&manual, |(id, _)| *id))
+        .bind(column(&manual, |(_, m)| m.source))
+        .bind(column(&manual, |(_, m)| m.affected_asset.clone()))
+        .bind(column(&manual, |(_, m)| m.evidence.clone()))
+        .bind(column(&manual, |(_, m)| m.reporter.clone()))
+        .bind(column(&manual, |(_, m)| m.engagement.clone()))
+        .bind(column(&manual, |(_, m)| m.reported_at))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(in_input_order(&ids, findings))
+}
+
+/// Gather one column of a multi-row insert as an array bind.
+fn column<T, U>(rows: &[T], value: impl Fn(&T) -> U) -> Vec<U> {
+    rows.iter().map(value).collect()
+}
+
+/// Reorder `RETURNING` rows, which Postgres does not order, to match `ids`.
+fn in_input_order(ids: &[Uuid], mut findings: Vec<Finding>) -> Vec<Finding> {
+    let position: HashMap<Uuid, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    findings.sort_by_key(|f| position.get(&f.id).copied().unwrap_or(usize::MAX));
+    findings
+}
+
 /// Find a finding by ID with category-specific details.
 pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<FindingWithDetails, AppError> {
     let finding = sqlx::query_as::<_, Finding>("SELECT * FROM findings WHERE id = $1")
@@ -335,14 +918,146 @@ pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<FindingWithDetails, A
         _ => None,
     };
 
+    let manual = match finding.finding_category {
+        FindingCategory::Manual => {
+            sqlx::query_as::<_, crate::models::finding_manual::FindingManual>(
+                "SELECT * FROM finding_manual WHERE finding_id = $1",
+            )
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+        }
+        _ => None,
+    };
+
+    let cwes = crate::services::cwe::summaries(
+        pool,
+        &crate::services::cwe::ids_from_json(&finding.cwe_ids),
+    )
+    .await?;
+
     Ok(FindingWithDetails {
         finding,
         sast,
         sca,
         dast,
+        manual,
+        cwes,
     })
 }
 
+/// The raw scanner record of a finding, with the tool's own severity.
+pub async fn raw_document(pool: &PgPool, id: Uuid) -> Result<RawFindingDocument, AppError> {
+    sqlx::query_as::<_, RawFindingDocument>(
+        r#"
+        SELECT id AS finding_id, source_tool, source_tool_version, source_finding_id,
+               original_severity, normalized_severity, first_seen, last_seen, raw_finding
+        FROM findings
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))
+}
+
+/// Fetch up to `MAX_BATCH_GET` findings with category details in four queries.
+///
+/// Duplicate IDs are returned once; unknown IDs are listed in `missing`.
+pub async fn find_many(pool: &PgPool, ids: &[Uuid]) -> Result<BatchGetResult, AppError> {
+    if ids.len() > MAX_BATCH_GET {
+        return Err(AppError::Validation(format!(
+            "At most {MAX_BATCH_GET} findings can be fetched at once"
+        )));
+    }
+    let mut unique = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !unique.contains(id) {
+            unique.push(*id);
+        }
+    }
+
+    let (findings, sast, sca, dast, manual) = tokio::try_join!(
+        sqlx::query_as::<_, Finding>("SELECT * FROM findings WHERE id = ANY($1)")
+            .bind(&unique)
+            .fetch_all(pool),
+        sqlx::query_as::<_, crate::models::finding_sast::FindingSast>(
+            "SELECT * FROM finding_sast WHERE finding_id = ANY($1)",
+        )
+        .bind(&unique)
+        .fetch_all(pool),
+        sqlx::query_as::<_, crate::models::finding_sca::FindingSca>(
+            "SELECT * FROM finding_sca WHERE finding_id = ANY($1)",
+        )
+        .bind(&unique)
+        .fetch_all(pool),
+        sqlx::query_as::<_, crate::models::finding_dast::FindingDast>(
+            "SELECT * FROM finding_dast WHERE finding_id = ANY($1)",
+        )
+        .bind(&unique)
+        .fetch_all(pool),
+        sqlx::query_as::<_, crate::models::finding_manual::FindingManual>(
+            "SELECT * FROM finding_manual WHERE finding_id = ANY($1)",
+        )
+        .bind(&unique)
+        .fetch_all(pool),
+    )?;
+
+    let mut result = assemble_batch(&unique, findings, sast, sca, dast, manual);
+    let cwe_ids: Vec<i32> = result
+        .items
+        .iter()
+        .flat_map(|item| crate::services::cwe::ids_from_json(&item.finding.cwe_ids))
+        .collect();
+    let labels: HashMap<i32, crate::models::cwe::CweSummary> =
+        crate::services::cwe::summaries(pool, &cwe_ids)
+            .await?
+            .into_iter()
+            .map(|s| (s.cwe_id, s))
+            .collect();
+    for item in &mut result.items {
+        item.cwes = crate::services::cwe::ids_from_json(&item.finding.cwe_ids)
+            .iter()
+            .filter_map(|id| labels.get(id).cloned())
+            .collect();
+    }
+    Ok(result)
+}
+
+/// Attach category rows to their findings and order results like `ids`.
+fn assemble_batch(
+    ids: &[Uuid],
+    findings: Vec<Finding>,
+    sast: Vec<crate::models::finding_sast::FindingSast>,
+    sca: Vec<crate::models::finding_sca::FindingSca>,
+    dast: Vec<crate::models::finding_dast::FindingDast>,
+    manual: Vec<crate::models::finding_manual::FindingManual>,
+) -> BatchGetResult {
+    let mut findings: HashMap<Uuid, Finding> = findings.into_iter().map(|f| (f.id, f)).collect();
+    let mut sast: HashMap<Uuid, _> = sast.into_iter().map(|s| (s.finding_id, s)).collect();
+    let mut sca: HashMap<Uuid, _> = sca.into_iter().map(|s| (s.finding_id, s)).collect();
+    let mut dast: HashMap<Uuid, _> = dast.into_iter().map(|d| (d.finding_id, d)).collect();
+    let mut manual: HashMap<Uuid, _> = manual.into_iter().map(|m| (m.finding_id, m)).collect();
+
+    let mut items = Vec::with_capacity(findings.len());
+    let mut missing = Vec::new();
+    for id in ids {
+        match findings.remove(id) {
+            Some(finding) => items.push(FindingWithDetails {
+                finding,
+                sast: sast.remove(id),
+                sca: sca.remove(id),
+                dast: dast.remove(id),
+                manual: manual.remove(id),
+                cwes: Vec::new(),
+            }),
+            None => missing.push(*id),
+        }
+    }
+    BatchGetResult { items, missing }
+}
+
 /// Find a finding by fingerprint (for deduplication).
 pub async fn find_by_fingerprint(
     pool: &PgPool,
@@ -356,10 +1071,12 @@ pub async fn find_by_fingerprint(
     Ok(finding)
 }
 
-/// List findings with filters, pagination, and optional full-text search.
+/// List findings with filters, sorting, pagination, and optional full-text search
+/// (parsed according to [`set_search_syntax`]).
 pub async fn list(
     pool: &PgPool,
     filters: &FindingFilters,
+    sort: &FindingSort,
     pagination: &Pagination,
 ) -> Result<PagedResult<FindingSummary>, AppError> {
     let mut conditions: Vec<String> = Vec::new();
@@ -367,7 +1084,7 @@ pub async fn list(
 
     if filters.severity.is_some() {
         param_index += 1;
-        conditions.push(format!("normalized_severity = ${param_index}"));
+        conditions.push(format!("effective_severity = ${param_index}"));
     }
     if filters.status.is_some() {
         param_index += 1;
@@ -381,6 +1098,12 @@ pub async fn list(
         param_index += 1;
         conditions.push(format!("application_id = ${param_index}"));
     }
+    if filters.visible_to.is_some() {
+        param_index += 1;
+        conditions.push(format!(
+            "application_id IN (SELECT application_id FROM application_owners WHERE user_id = ${param_index})"
+        ));
+    }
     if filters.source_tool.is_some() {
         param_index += 1;
         conditions.push(format!("source_tool = ${param_index}"));
@@ -389,12 +1112,20 @@ pub async fn list(
         param_index += 1;
         conditions.push(format!("sla_status = ${param_index}"));
     }
+    if filters.owasp_category.is_some() {
+        param_index += 1;
+        conditions.push(format!("owasp_code(owasp_category) = ${param_index}"));
+    }
     if filters.search.is_some() {
         param_index += 1;
         conditions.push(format!(
-            "search_vector @@ plainto_tsquery('english', ${param_index})"
+            "search_vector @@ {}('english', ${param_index})",
+            search_syntax().tsquery_function()
         ));
     }
+    if !filters.include_archived.unwrap_or(false) {
+        conditions.push("archived_at IS NULL".to_string());
+    }
 
     let where_clause = if conditions.is_empty() {
         String::new()
@@ -404,11 +1135,12 @@ pub async fn list(
 
     let count_sql = format!("SELECT COUNT(*) FROM findings {where_clause}");
     let data_sql = format!(
-        "SELECT id, source_tool, finding_category, title, normalized_severity, status, \
-         composite_risk_score, fingerprint, application_id, first_seen, last_seen, sla_status \
-         FROM findings {where_clause} \
-         ORDER BY composite_risk_score DESC NULLS LAST, normalized_severity ASC, first_seen DESC \
+        "SELECT id, source_tool, finding_category, title, normalized_severity, severity_override, \
+         status, composite_risk_score, fingerprint, application_id, first_seen, last_seen, sla_status \
+         FROM findings f {where_clause} \
+         ORDER BY {} \
          LIMIT {} OFFSET {}",
+        sort.order_by(),
         pagination.limit(),
         pagination.offset()
     );
@@ -435,12 +1167,18 @@ pub async fn list(
     if let Some(ref app_id) = filters.application_id {
         bind_both!(app_id);
     }
+    if let Some(ref user_id) = filters.visible_to {
+        bind_both!(user_id);
+    }
     if let Some(ref tool) = filters.source_tool {
         bind_both!(tool);
     }
     if let Some(ref sla) = filters.sla_status {
         bind_both!(sla);
     }
+    if let Some(ref owasp_category) = filters.owasp_category {
+        bind_both!(owasp::category_code(owasp_category));
+    }
     if let Some(ref search) = filters.search {
         bind_both!(search);
     }
@@ -459,6 +1197,7 @@ pub async fn list(
 pub async fn list_with_category(
     pool: &PgPool,
     filters: &FindingFilters,
+    sort: &FindingSort,
     pagination: &Pagination,
 ) -> Result<PagedResult<FindingSummaryWithCategory>, AppError> {
     // Build WHERE conditions on the findings table (aliased as "f")
@@ -467,7 +1206,7 @@ pub async fn list_with_category(
 
     if filters.severity.is_some() {
         param_index += 1;
-        conditions.push(format!("f.normalized_severity = ${param_index}"));
+        conditions.push(format!("f.effective_severity = ${param_index}"));
     }
     if filters.status.is_some() {
         param_index += 1;
@@ -481,6 +1220,12 @@ pub async fn list_with_category(
         param_index += 1;
         conditions.push(format!("f.application_id = ${param_index}"));
     }
+    if filters.visible_to.is_some() {
+        param_index += 1;
+        conditions.push(format!(
+            "f.application_id IN (SELECT application_id FROM application_owners WHERE user_id = ${param_index})"
+        ));
+    }
     if filters.source_tool.is_some() {
         param_index += 1;
         conditions.push(format!("f.source_tool = ${param_index}"));
@@ -489,12 +1234,20 @@ pub async fn list_with_category(
         param_index += 1;
         conditions.push(format!("f.sla_status = ${param_index}"));
     }
+    if filters.owasp_category.is_some() {
+        param_index += 1;
+        conditions.push(format!("owasp_code(f.owasp_category) = ${param_index}"));
+    }
     if filters.search.is_some() {
         param_index += 1;
         conditions.push(format!(
-            "f.search_vector @@ plainto_tsquery('english', ${param_index})"
+            "f.search_vector @@ {}('english', ${param_index})",
+            search_syntax().tsquery_function()
         ));
     }
+    if !filters.include_archived.unwrap_or(false) {
+        conditions.push("f.archived_at IS NULL".to_string());
+    }
 
     // SAST-specific conditions (table alias: s)
     if filters.branch.is_some() {
@@ -602,6 +1355,7 @@ pub async fn list_with_category(
         || filters.has_sca_filters();
     let join_dast = matches!(filters.category, None | Some(FindingCategory::Dast))
         || filters.has_dast_filters();
+    let join_manual = matches!(filters.category, None | Some(FindingCategory::Manual));
 
     // Build JOIN clauses
     let mut joins = String::new();
@@ -614,6 +1368,9 @@ pub async fn list_with_category(
     if join_dast {
         joins.push_str(" LEFT JOIN finding_dast d ON d.finding_id = f.id");
     }
+    if join_manual {
+        joins.push_str(" LEFT JOIN finding_manual m ON m.finding_id = f.id");
+    }
 
     // Build SELECT columns for category data
     let mut extra_columns = String::new();
@@ -638,15 +1395,22 @@ pub async fn list_with_category(
              d.web_application_name AS dast_web_application_name",
         );
     }
+    if join_manual {
+        extra_columns.push_str(
+            ", m.affected_asset AS manual_affected_asset, \
+             m.reporter AS manual_reporter",
+        );
+    }
 
     let count_sql = format!("SELECT COUNT(*) FROM findings f {joins} {where_clause}");
     let data_sql = format!(
         "SELECT f.id, f.source_tool, f.finding_category, f.title, f.normalized_severity, \
-         f.status, f.composite_risk_score, f.fingerprint, f.application_id, \
+         f.severity_override, f.status, f.composite_risk_score, f.fingerprint, f.application_id, \
          f.first_seen, f.last_seen, f.sla_status{extra_columns} \
          FROM findings f {joins} {where_clause} \
-         ORDER BY f.composite_risk_score DESC NULLS LAST, f.normalized_severity ASC, f.first_seen DESC \
+         ORDER BY {} \
          LIMIT {} OFFSET {}",
+        sort.order_by(),
         pagination.limit(),
         pagination.offset()
     );
@@ -680,12 +1444,18 @@ pub async fn list_with_category(
     if let Some(ref app_id) = filters.application_id {
         bind_both_cat!(app_id);
     }
+    if let Some(ref user_id) = filters.visible_to {
+        bind_both_cat!(user_id);
+    }
     if let Some(ref tool) = filters.source_tool {
         bind_both_cat!(tool);
     }
     if let Some(ref sla) = filters.sla_status {
         bind_both_cat!(sla);
     }
+    if let Some(ref owasp_category) = filters.owasp_category {
+        bind_both_cat!(owasp::category_code(owasp_category));
+    }
     if let Some(ref search) = filters.search {
         bind_both_cat!(search);
     }
@@ -761,6 +1531,7 @@ pub async fn list_with_category(
                 finding_category: finding_category.clone(),
                 title: row.get("title"),
                 normalized_severity: row.get("normalized_severity"),
+                severity_override: row.get("severity_override"),
                 status: row.get("status"),
                 composite_risk_score: row.get("composite_risk_score"),
                 fingerprint: row.get("fingerprint"),
@@ -816,6 +1587,18 @@ pub async fn list_with_category(
                         None
                     }
                 }
+                FindingCategory::Manual if join_manual => {
+                    let affected_asset: Option<String> = row.get("manual_affected_asset");
+                    if affected_asset.is_some() {
+                        Some(FindingCategoryData {
+                            affected_asset,
+                            reporter: row.get("manual_reporter"),
+                            ..Default::default()
+                        })
+                    } else {
+                        None
+                    }
+                }
                 _ => None,
             };
 
@@ -837,6 +1620,7 @@ pub async fn update_status(
     actor_id: Option<Uuid>,
     actor_name: &str,
     justification: Option<&str>,
+    events: Option<&HecSink>,
 ) -> Result<Finding, AppError> {
     let existing = sqlx::query_as::<_, Finding>("SELECT * FROM findings WHERE id = $1")
         .bind(id)
@@ -873,39 +1657,239 @@ pub async fn update_status(
     .await?;
 
     tx.commit().await?;
+
+    if let Some(application_id) = finding.application_id {
+        notification::send_to_application(
+            pool,
+            application_id,
+            actor_id,
+            &NewNotification {
+                kind: NotificationKind::StatusChange,
+                title: format!(
+                    "Finding moved to {}",
+                    serde_json::to_string(new_status)
+                        .unwrap_or_default()
+                        .trim_matches('"')
+                ),
+                body: Some(finding.title.clone()),
+                finding_id: Some(id),
+                application_id: Some(application_id),
+                actor_name: Some(actor_name.to_string()),
+            },
+        )
+        .await;
+    }
+
+    watchlist::record_status_change(pool, &finding, &existing.status, actor_id, actor_name).await;
+
+    splunk_hec::emit(
+        events,
+        PlatformEvent::StatusChanged {
+            finding_id: id,
+            previous_status: existing.status.clone(),
+            new_status: new_status.clone(),
+            actor_name: actor_name.to_string(),
+            justification: justification.map(str::to_string),
+        },
+    );
+
     Ok(finding)
 }
 
-/// Update general finding fields.
-pub async fn update(pool: &PgPool, id: Uuid, input: &UpdateFinding) -> Result<Finding, AppError> {
-    // Verify finding exists
-    let _ = sqlx::query_as::<_, Finding>("SELECT id FROM findings WHERE id = $1")
-        .bind(id)
-        .fetch_optional(pool)
-        .await?
-        .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))?;
+/// Set or clear the analyst severity override of a finding, recording the
+/// change and its justification in the finding's history.
+pub async fn set_severity_override(
+    pool: &PgPool,
+    id: Uuid,
+    severity: Option<&SeverityLevel>,
+    justification: &str,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<Finding, AppError> {
+    let justification = justification.trim();
+    if justification.is_empty() {
+        return Err(AppError::Validation(
+            "A justification is required to override severity".to_string(),
+        ));
+    }
 
-    let finding = sqlx::query_as::<_, Finding>(
-        r#"
-        UPDATE findings SET
-            normalized_severity = COALESCE($2, normalized_severity),
-            status = COALESCE($3, status),
-            application_id = COALESCE($4, application_id),
-            remediation_owner = COALESCE($5, remediation_owner),
-            office_owner = COALESCE($6, office_owner),
-            office_manager = COALESCE($7, office_manager),
-            sla_due_date = COALESCE($8, sla_due_date),
-            sla_status = COALESCE($9, sla_status),
-            tags = COALESCE($10, tags),
-            remediation_guidance = COALESCE($11, remediation_guidance),
-            metadata = COALESCE($12, metadata),
-            updated_at = NOW()
-        WHERE id = $1
-        RETURNING *
-        "#,
+    let settings = config_cache::global().settings(pool).await?;
+    let mut tx = pool.begin().await?;
+
+    let previous = sqlx::query_scalar::<_, Option<SeverityLevel>>(
+        "SELECT severity_override FROM findings WHERE id = $1 FOR UPDATE",
     )
     .bind(id)
-    .bind(&input.normalized_severity)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))?;
+    if previous.is_none() && severity.is_none() {
+        return Err(AppError::Validation(
+            "Finding has no severity override to clear".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        "UPDATE findings SET severity_override = $2, \
+         severity_override_justification = $3, severity_override_by = $4, \
+         severity_override_at = NOW(), updated_at = NOW() \
+         WHERE id = $1",
+    )
+    .bind(id)
+    .bind(severity)
+    .bind(justification)
+    .bind(actor_id)
+    .execute(&mut *tx)
+    .await?;
+    let finding = recompute_risk(&mut tx, id, &settings).await?;
+
+    sqlx::query(
+        "INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, \
+         actor_id, actor_name, justification) \
+         VALUES ($1, 'severity_override', 'severity_override', $2, $3, $4, $5, $6)",
+    )
+    .bind(id)
+    .bind(previous.as_ref().map(SeverityLevel::label))
+    .bind(severity.map(SeverityLevel::label))
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(justification)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(finding)
+}
+
+/// Risk inputs of a finding beyond its own row.
+#[derive(Debug, sqlx::FromRow)]
+struct RiskInputRow {
+    effective_severity: SeverityLevel,
+    confidence: Option<ConfidenceLevel>,
+    first_seen: DateTime<Utc>,
+    criticality: Option<AssetCriticality>,
+    tier: Option<AssetTier>,
+    known_exploited: Option<bool>,
+    epss_score: Option<f32>,
+    exploit_maturity: Option<ExploitMaturity>,
+    dast_attack_vector: Option<String>,
+}
+
+fn exploitability_input(row: &RiskInputRow) -> ExploitabilityInput {
+    if row.known_exploited == Some(true) {
+        ExploitabilityInput::KnownExploited
+    } else if row.dast_attack_vector.is_some() {
+        ExploitabilityInput::DastConfirmed
+    } else if let Some(epss) = row.epss_score {
+        ExploitabilityInput::EpssScore(epss)
+    } else if let Some(maturity) = &row.exploit_maturity {
+        ExploitabilityInput::ScaMaturity(maturity.clone())
+    } else {
+        match row.confidence {
+            Some(ConfidenceLevel::High) => {
+                ExploitabilityInput::SastConfidence(SastTaintConfidence::High)
+            }
+            Some(ConfidenceLevel::Medium) => {
+                ExploitabilityInput::SastConfidence(SastTaintConfidence::Medium)
+            }
+            Some(ConfidenceLevel::Low) => {
+                ExploitabilityInput::SastConfidence(SastTaintConfidence::Low)
+            }
+            None => ExploitabilityInput::Unknown,
+        }
+    }
+}
+
+/// Recompute a finding's composite risk score and SLA from its effective
+/// severity, within the caller's transaction.
+///
+/// The SLA window comes from the priority before the finding's age is
+/// scored, so ageing escalates the score without shortening the deadline.
+/// Findings without an application are treated as the default tier.
+async fn recompute_risk(
+    conn: &mut PgConnection,
+    id: Uuid,
+    settings: &SystemSettings,
+) -> Result<Finding, AppError> {
+    let row = sqlx::query_as::<_, RiskInputRow>(
+        r#"
+        SELECT f.effective_severity, f.confidence, f.first_seen,
+               a.criticality, a.tier,
+               s.known_exploited, s.epss_score, s.exploit_maturity,
+               d.attack_vector AS dast_attack_vector
+        FROM findings f
+        LEFT JOIN applications a ON a.id = f.application_id
+        LEFT JOIN finding_sca s ON s.finding_id = f.id
+        LEFT JOIN finding_dast d ON d.finding_id = f.id
+        WHERE f.id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_one(&mut *conn)
+    .await?;
+    let correlation = correlation_service::correlation_density_for_finding(conn, id).await?;
+
+    let weights = settings.risk_weights();
+    let mut factors = RiskFactors {
+        severity: row.effective_severity.clone(),
+        asset_criticality: row.criticality.clone(),
+        exploitability: exploitability_input(&row),
+        finding_age: FindingAgeInput { sla_ratio: None },
+        correlation_density: correlation,
+    };
+    let base = risk_score::compute(&factors, &weights);
+    let sla_hours = settings
+        .sla_matrix()
+        .hours(&base.priority, row.tier.as_ref().unwrap_or(&AssetTier::Tier2));
+    let sla_due_date = sla_hours.map(|h| row.first_seen + chrono::Duration::hours(i64::from(h)));
+    let elapsed_hours = (Utc::now() - row.first_seen).num_seconds() as f32 / 3600.0;
+    let sla_ratio = sla_hours.map(|h| elapsed_hours / h as f32);
+    factors.finding_age = FindingAgeInput { sla_ratio };
+    let score = risk_score::compute(&factors, &weights);
+
+    let finding = sqlx::query_as::<_, Finding>(
+        "UPDATE findings SET composite_risk_score = $2, sla_due_date = $3, sla_status = $4 \
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .bind(score.composite_score)
+    .bind(sla_due_date)
+    .bind(sla_ratio.map(risk_score::sla_status))
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(finding)
+}
+
+/// Update general finding fields.
+pub async fn update(pool: &PgPool, id: Uuid, input: &UpdateFinding) -> Result<Finding, AppError> {
+    // Verify finding exists
+    let _ = sqlx::query_as::<_, Finding>("SELECT id FROM findings WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))?;
+
+    let finding = sqlx::query_as::<_, Finding>(
+        r#"
+        UPDATE findings SET
+            normalized_severity = COALESCE($2, normalized_severity),
+            status = COALESCE($3, status),
+            application_id = COALESCE($4, application_id),
+            remediation_owner = COALESCE($5, remediation_owner),
+            office_owner = COALESCE($6, office_owner),
+            office_manager = COALESCE($7, office_manager),
+            sla_due_date = COALESCE($8, sla_due_date),
+            sla_status = COALESCE($9, sla_status),
+            tags = COALESCE($10, tags),
+            remediation_guidance = COALESCE($11, remediation_guidance),
+            metadata = COALESCE($12, metadata),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(&input.normalized_severity)
     .bind(&input.status)
     .bind(input.application_id)
     .bind(&input.remediation_owner)
@@ -922,7 +1906,8 @@ pub async fn update(pool: &PgPool, id: Uuid, input: &UpdateFinding) -> Result<Fi
     Ok(finding)
 }
 
-/// Add a comment to a finding.
+/// Add a comment to a finding, optionally as a reply to another of its
+/// comments. Users mentioned as `@username` are recorded on the comment.
 pub async fn add_comment(
     pool: &PgPool,
     finding_id: Uuid,
@@ -937,21 +1922,184 @@ pub async fn add_comment(
         .await?
         .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))?;
 
+    if let Some(parent_id) = input.parent_id {
+        let parent_finding =
+            sqlx::query_scalar::<_, Uuid>("SELECT finding_id FROM finding_comments WHERE id = $1")
+                .bind(parent_id)
+                .fetch_optional(pool)
+                .await?;
+        if parent_finding != Some(finding_id) {
+            return Err(AppError::Validation(format!(
+                "Comment {parent_id} is not a comment on this finding"
+            )));
+        }
+    }
+
+    let mentioned = mentions::resolve(pool, &input.content, author_id).await?;
+
     let comment = sqlx::query_as::<_, FindingComment>(
-        "INSERT INTO finding_comments (finding_id, author_id, author_name, content) \
-         VALUES ($1, $2, $3, $4) RETURNING *",
+        "INSERT INTO finding_comments \
+         (finding_id, author_id, author_name, content, parent_id, content_format, mentioned_user_ids) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *",
     )
     .bind(finding_id)
     .bind(author_id)
     .bind(author_name)
     .bind(&input.content)
+    .bind(input.parent_id)
+    .bind(input.content_format)
+    .bind(&mentioned)
     .fetch_one(pool)
     .await?;
 
+    notify_mentions(pool, &comment, &mentioned).await;
+    Ok(comment)
+}
+
+/// Notify users newly mentioned in a comment.
+async fn notify_mentions(pool: &PgPool, comment: &FindingComment, user_ids: &[Uuid]) {
+    notification::send(
+        pool,
+        user_ids,
+        &NewNotification {
+            kind: NotificationKind::Mention,
+            title: format!("{} mentioned you in a comment", comment.author_name),
+            body: Some(comment.content.clone()),
+            finding_id: Some(comment.finding_id),
+            application_id: None,
+            actor_name: Some(comment.author_name.clone()),
+        },
+    )
+    .await;
+}
+
+/// Whether a user may edit or delete a comment: its author or a platform admin.
+fn can_modify_comment(comment: &FindingComment, user: &CurrentUser) -> bool {
+    comment.author_id == user.id || user.role == UserRole::PlatformAdmin
+}
+
+/// Lock a live comment on a finding that the user may modify.
+async fn lock_modifiable_comment(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    finding_id: Uuid,
+    comment_id: Uuid,
+    user: &CurrentUser,
+) -> Result<FindingComment, AppError> {
+    let comment = sqlx::query_as::<_, FindingComment>(
+        "SELECT * FROM finding_comments \
+         WHERE id = $1 AND finding_id = $2 AND deleted_at IS NULL FOR UPDATE",
+    )
+    .bind(comment_id)
+    .bind(finding_id)
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Comment not found".to_string()))?;
+    if !can_modify_comment(&comment, user) {
+        return Err(AppError::Forbidden(
+            "Only the author or an admin can change a comment".to_string(),
+        ));
+    }
+    Ok(comment)
+}
+
+/// Record a comment's previous content in the finding history.
+async fn record_comment_change(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    comment: &FindingComment,
+    action: &str,
+    new_content: Option<&str>,
+    user: &CurrentUser,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO finding_history \
+         (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(comment.finding_id)
+    .bind(action)
+    .bind(format!("comment:{}", comment.id))
+    .bind(&comment.content)
+    .bind(new_content)
+    .bind(user.id)
+    .bind(&user.username)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Edit a comment (author or admin). The previous content goes to the
+/// finding history and mentions are re-resolved from the new content.
+pub async fn update_comment(
+    pool: &PgPool,
+    finding_id: Uuid,
+    comment_id: Uuid,
+    user: &CurrentUser,
+    input: &UpdateComment,
+) -> Result<FindingComment, AppError> {
+    let mut tx = pool.begin().await?;
+    let existing = lock_modifiable_comment(&mut tx, finding_id, comment_id, user).await?;
+    if existing.content == input.content {
+        return Ok(existing);
+    }
+
+    let mentioned = mentions::resolve(pool, &input.content, existing.author_id).await?;
+    let comment = sqlx::query_as::<_, FindingComment>(
+        "UPDATE finding_comments \
+         SET content = $2, mentioned_user_ids = $3, edited_at = NOW(), updated_at = NOW() \
+         WHERE id = $1 RETURNING *",
+    )
+    .bind(comment_id)
+    .bind(&input.content)
+    .bind(&mentioned)
+    .fetch_one(&mut *tx)
+    .await?;
+    record_comment_change(
+        &mut tx,
+        &existing,
+        "comment_edited",
+        Some(&input.content),
+        user,
+    )
+    .await?;
+    tx.commit().await?;
+
+    let added: Vec<Uuid> = mentioned
+        .into_iter()
+        .filter(|id| !existing.mentioned_user_ids.contains(id))
+        .collect();
+    notify_mentions(pool, &comment, &added).await;
     Ok(comment)
 }
 
-/// List comments for a finding.
+/// Delete a comment (author or admin). The comment stays as an empty
+/// placeholder so replies keep their thread; its content goes to the
+/// finding history.
+pub async fn delete_comment(
+    pool: &PgPool,
+    finding_id: Uuid,
+    comment_id: Uuid,
+    user: &CurrentUser,
+) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    let existing = lock_modifiable_comment(&mut tx, finding_id, comment_id, user).await?;
+
+    sqlx::query(
+        "UPDATE finding_comments \
+         SET content = '', mentioned_user_ids = '{}', deleted_at = NOW(), updated_at = NOW() \
+         WHERE id = $1",
+    )
+    .bind(comment_id)
+    .execute(&mut *tx)
+    .await?;
+    record_comment_change(&mut tx, &existing, "comment_deleted", None, user).await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// List comments for a finding in posting order. Replies carry their
+/// parent's ID, so clients build threads without a second query; deleted
+/// comments are kept as placeholders.
 pub async fn list_comments(
     pool: &PgPool,
     finding_id: Uuid,
@@ -965,6 +2113,65 @@ pub async fn list_comments(
     Ok(comments)
 }
 
+/// A page of a finding's comments.
+pub async fn list_comments_page(
+    pool: &PgPool,
+    finding_id: Uuid,
+    params: &CommentListParams,
+    pagination: &Pagination,
+) -> Result<PagedResult<FindingComment>, AppError> {
+    let include_deleted = params.include_deleted.unwrap_or(true);
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM finding_comments \
+         WHERE finding_id = $1 AND ($2 OR deleted_at IS NULL)",
+    )
+    .bind(finding_id)
+    .bind(include_deleted)
+    .fetch_one(pool)
+    .await?;
+
+    let dir = params.sort_dir.unwrap_or(SortDirection::Asc).as_sql();
+    let comments = sqlx::query_as::<_, FindingComment>(&format!(
+        "SELECT * FROM finding_comments \
+         WHERE finding_id = $1 AND ($2 OR deleted_at IS NULL) \
+         ORDER BY created_at {dir}, id {dir} LIMIT $3 OFFSET $4"
+    ))
+    .bind(finding_id)
+    .bind(include_deleted)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(comments, total, pagination))
+}
+
+/// Comments mentioning a user, newest first.
+pub async fn list_mentions(
+    pool: &PgPool,
+    user_id: Uuid,
+    pagination: &Pagination,
+) -> Result<PagedResult<FindingComment>, AppError> {
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM finding_comments WHERE $1 = ANY(mentioned_user_ids)",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    let comments = sqlx::query_as::<_, FindingComment>(
+        "SELECT * FROM finding_comments WHERE $1 = ANY(mentioned_user_ids) \
+         ORDER BY created_at DESC LIMIT $2 OFFSET $3",
+    )
+    .bind(user_id)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(comments, total, pagination))
+}
+
 /// Get the history for a finding.
 pub async fn get_history(
     pool: &PgPool,
@@ -979,12 +2186,46 @@ pub async fn get_history(
     Ok(history)
 }
 
+/// A page of a finding's history, optionally limited to some actions.
+pub async fn get_history_page(
+    pool: &PgPool,
+    finding_id: Uuid,
+    params: &HistoryListParams,
+    pagination: &Pagination,
+) -> Result<PagedResult<FindingHistory>, AppError> {
+    let actions = params.actions();
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM finding_history \
+         WHERE finding_id = $1 AND ($2::text[] IS NULL OR action = ANY($2))",
+    )
+    .bind(finding_id)
+    .bind(&actions)
+    .fetch_one(pool)
+    .await?;
+
+    let dir = params.sort_dir.unwrap_or(SortDirection::Desc).as_sql();
+    let history = sqlx::query_as::<_, FindingHistory>(&format!(
+        "SELECT * FROM finding_history \
+         WHERE finding_id = $1 AND ($2::text[] IS NULL OR action = ANY($2)) \
+         ORDER BY created_at {dir}, id {dir} LIMIT $3 OFFSET $4"
+    ))
+    .bind(finding_id)
+    .bind(&actions)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(history, total, pagination))
+}
+
 /// Bulk update status for multiple findings.
 pub async fn bulk_update_status(
     pool: &PgPool,
     input: &BulkStatusUpdate,
     actor_id: Option<Uuid>,
     actor_name: &str,
+    events: Option<&HecSink>,
 ) -> Result<BulkResult, AppError> {
     let mut updated = 0usize;
     for &id in &input.finding_ids {
@@ -995,6 +2236,7 @@ pub async fn bulk_update_status(
             actor_id,
             actor_name,
             input.justification.as_deref(),
+            events,
         )
         .await
         {
@@ -1010,7 +2252,11 @@ pub async fn bulk_update_status(
 }
 
 /// Bulk assign remediation owner for multiple findings.
-pub async fn bulk_assign(pool: &PgPool, input: &BulkAssign) -> Result<BulkResult, AppError> {
+pub async fn bulk_assign(
+    pool: &PgPool,
+    input: &BulkAssign,
+    actor_name: &str,
+) -> Result<BulkResult, AppError> {
     let result = sqlx::query(
         "UPDATE findings SET remediation_owner = $1, updated_at = NOW() WHERE id = ANY($2)",
     )
@@ -1019,6 +2265,35 @@ pub async fn bulk_assign(pool: &PgPool, input: &BulkAssign) -> Result<BulkResult
     .execute(pool)
     .await?;
 
+    // Remediation owners are free text; notify only when one names a user
+    let assignee = sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM users WHERE lower(username) = lower($1) AND is_active",
+    )
+    .bind(input.remediation_owner.trim())
+    .fetch_optional(pool)
+    .await?;
+    if let Some(assignee) = assignee.filter(|_| result.rows_affected() > 0) {
+        notification::send(
+            pool,
+            &[assignee],
+            &NewNotification {
+                kind: NotificationKind::Assignment,
+                title: format!(
+                    "{} finding(s) assigned to you for remediation",
+                    result.rows_affected()
+                ),
+                body: None,
+                finding_id: match input.finding_ids.as_slice() {
+                    [id] => Some(*id),
+                    _ => None,
+                },
+                application_id: None,
+                actor_name: Some(actor_name.to_string()),
+            },
+        )
+        .await;
+    }
+
     Ok(BulkResult {
         updated: result.rows_affected() as usize,
         total: input.finding_ids.len(),
@@ -1042,13 +2317,204 @@ pub async fn bulk_tag(pool: &PgPool, input: &BulkTag) -> Result<BulkResult, AppE
     })
 }
 
+/// Archive the selected findings, hiding them from lists without deleting them.
+///
+/// Already archived findings are left untouched. Each archived finding gets
+/// a history entry and the run is recorded in the audit log.
+pub async fn bulk_archive(
+    pool: &PgPool,
+    input: &BulkArchive,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<BulkResult, AppError> {
+    input.selection.validate()?;
+    let mut tx = pool.begin().await?;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM findings WHERE id = ANY($1) OR application_id = ANY($2)",
+    )
+    .bind(&input.selection.finding_ids)
+    .bind(&input.selection.application_ids)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let archived = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        UPDATE findings SET archived_at = NOW(), updated_at = NOW()
+        WHERE (id = ANY($1) OR application_id = ANY($2)) AND archived_at IS NULL
+        RETURNING id
+        "#,
+    )
+    .bind(&input.selection.finding_ids)
+    .bind(&input.selection.application_ids)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name, justification)
+        SELECT id, 'archived', 'archived_at', NULL, NOW()::text, $2, $3, $4
+        FROM UNNEST($1::uuid[]) AS id
+        "#,
+    )
+    .bind(&archived)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(&input.justification)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('finding', NULL, 'bulk_archive', $1, $2, $3)
+        "#,
+    )
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({
+        "finding_ids": input.selection.finding_ids,
+        "application_ids": input.selection.application_ids,
+        "archived": archived.len(),
+        "justification": input.justification,
+    }))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(BulkResult {
+        updated: archived.len(),
+        total: total as usize,
+    })
+}
+
+/// Permanently delete the selected findings, or only count them on a dry run.
+///
+/// Category data, comments, history, and relationships go with the findings
+/// through their cascading foreign keys; the audit log keeps a record of the run.
+pub async fn bulk_delete(
+    pool: &PgPool,
+    input: &BulkDelete,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<BulkDeleteResult, AppError> {
+    input.selection.validate()?;
+    let mut tx = pool.begin().await?;
+
+    let matched = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM findings WHERE id = ANY($1) OR application_id = ANY($2)",
+    )
+    .bind(&input.selection.finding_ids)
+    .bind(&input.selection.application_ids)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if input.dry_run {
+        return Ok(BulkDeleteResult {
+            matched,
+            deleted: 0,
+            dry_run: true,
+        });
+    }
+
+    let deleted = sqlx::query("DELETE FROM findings WHERE id = ANY($1) OR application_id = ANY($2)")
+        .bind(&input.selection.finding_ids)
+        .bind(&input.selection.application_ids)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('finding', NULL, 'bulk_delete', $1, $2, $3)
+        "#,
+    )
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({
+        "finding_ids": input.selection.finding_ids,
+        "application_ids": input.selection.application_ids,
+        "deleted": deleted,
+    }))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(BulkDeleteResult {
+        matched,
+        deleted,
+        dry_run: false,
+    })
+}
+
+/// Findings fetched per export query.
+const EXPORT_BATCH_SIZE: usize = 1_000;
+
+/// Sort key of the last exported row, used to fetch the next batch.
+#[derive(Debug, Clone, Copy)]
+struct ExportCursor {
+    first_seen: DateTime<Utc>,
+    id: Uuid,
+}
+
+impl ExportCursor {
+    fn after(item: &FindingSummaryWithCategory) -> Self {
+        Self {
+            first_seen: item.summary.first_seen,
+            id: item.summary.id,
+        }
+    }
+}
+
 /// List all findings matching filters for export (no pagination).
 ///
-/// Uses the same query logic as `list_with_category()` but omits LIMIT/OFFSET
-/// and the COUNT query, returning all matching rows in a single `Vec`.
+/// Collects every batch of `export_batches()` into a single `Vec`; use the
+/// stream directly for responses that may be large.
 pub async fn list_all_for_export(
     pool: &PgPool,
     filters: &FindingFilters,
+) -> Result<Vec<FindingSummaryWithCategory>, AppError> {
+    export_batches(pool.clone(), filters.clone())
+        .try_concat()
+        .await
+}
+
+/// Stream all findings matching filters for export, in batches.
+///
+/// Each batch is a separate keyset-paginated query, so no connection or
+/// snapshot is held while the client reads, and memory stays flat regardless
+/// of result size. Rows changed between batches may be missed or repeated,
+/// as with any paginated read. Dropping the stream stops further queries.
+pub fn export_batches(
+    pool: PgPool,
+    filters: FindingFilters,
+) -> impl Stream<Item = Result<Vec<FindingSummaryWithCategory>, AppError>> + Send + 'static {
+    stream::try_unfold(Some((pool, filters, None)), |state| async move {
+        let Some((pool, filters, after)) = state else {
+            return Ok(None);
+        };
+        let batch = fetch_export_batch(&pool, &filters, after.as_ref()).await?;
+        if batch.is_empty() {
+            return Ok(None);
+        }
+        let next = match batch.last() {
+            Some(last) if batch.len() == EXPORT_BATCH_SIZE => {
+                Some((pool, filters, Some(ExportCursor::after(last))))
+            }
+            _ => None,
+        };
+        Ok(Some((batch, next)))
+    })
+}
+
+/// Run one export query: up to `EXPORT_BATCH_SIZE` rows following `after`.
+async fn fetch_export_batch(
+    pool: &PgPool,
+    filters: &FindingFilters,
+    after: Option<&ExportCursor>,
 ) -> Result<Vec<FindingSummaryWithCategory>, AppError> {
     // Build WHERE conditions on the findings table (aliased as "f")
     let mut conditions: Vec<String> = Vec::new();
@@ -1056,7 +2522,7 @@ pub async fn list_all_for_export(
 
     if filters.severity.is_some() {
         param_index += 1;
-        conditions.push(format!("f.normalized_severity = ${param_index}"));
+        conditions.push(format!("f.effective_severity = ${param_index}"));
     }
     if filters.status.is_some() {
         param_index += 1;
@@ -1070,6 +2536,12 @@ pub async fn list_all_for_export(
         param_index += 1;
         conditions.push(format!("f.application_id = ${param_index}"));
     }
+    if filters.visible_to.is_some() {
+        param_index += 1;
+        conditions.push(format!(
+            "f.application_id IN (SELECT application_id FROM application_owners WHERE user_id = ${param_index})"
+        ));
+    }
     if filters.source_tool.is_some() {
         param_index += 1;
         conditions.push(format!("f.source_tool = ${param_index}"));
@@ -1078,12 +2550,20 @@ pub async fn list_all_for_export(
         param_index += 1;
         conditions.push(format!("f.sla_status = ${param_index}"));
     }
+    if filters.owasp_category.is_some() {
+        param_index += 1;
+        conditions.push(format!("owasp_code(f.owasp_category) = ${param_index}"));
+    }
     if filters.search.is_some() {
         param_index += 1;
         conditions.push(format!(
-            "f.search_vector @@ plainto_tsquery('english', ${param_index})"
+            "f.search_vector @@ {}('english', ${param_index})",
+            search_syntax().tsquery_function()
         ));
     }
+    if !filters.include_archived.unwrap_or(false) {
+        conditions.push("f.archived_at IS NULL".to_string());
+    }
 
     // SAST-specific conditions (table alias: s)
     if filters.branch.is_some() {
@@ -1177,6 +2657,19 @@ pub async fn list_all_for_export(
         conditions.push(format!("f.first_seen <= ${param_index}"));
     }
 
+    // Keyset condition on the ORDER BY columns, bound after the filters.
+    // Newest first on (first_seen, id) so each batch is an index range scan
+    // rather than a sort of every remaining row. The separate bound on
+    // first_seen lets a partitioned table skip the months already exported;
+    // the row comparison alone does not prune.
+    if after.is_some() {
+        conditions.push(format!(
+            "f.first_seen <= ${0} AND (f.first_seen, f.id) < (${0}, ${1})",
+            param_index + 1,
+            param_index + 2
+        ));
+    }
+
     let where_clause = if conditions.is_empty() {
         String::new()
     } else {
@@ -1190,6 +2683,7 @@ pub async fn list_all_for_export(
         || filters.has_sca_filters();
     let join_dast = matches!(filters.category, None | Some(FindingCategory::Dast))
         || filters.has_dast_filters();
+    let join_manual = matches!(filters.category, None | Some(FindingCategory::Manual));
 
     let mut joins = String::new();
     if join_sast {
@@ -1201,6 +2695,9 @@ pub async fn list_all_for_export(
     if join_dast {
         joins.push_str(" LEFT JOIN finding_dast d ON d.finding_id = f.id");
     }
+    if join_manual {
+        joins.push_str(" LEFT JOIN finding_manual m ON m.finding_id = f.id");
+    }
 
     let mut extra_columns = String::new();
     if join_sast {
@@ -1224,14 +2721,20 @@ pub async fn list_all_for_export(
              d.web_application_name AS dast_web_application_name",
         );
     }
+    if join_manual {
+        extra_columns.push_str(
+            ", m.affected_asset AS manual_affected_asset, \
+             m.reporter AS manual_reporter",
+        );
+    }
 
-    // No LIMIT/OFFSET — export returns all matching rows
     let data_sql = format!(
         "SELECT f.id, f.source_tool, f.finding_category, f.title, f.normalized_severity, \
-         f.status, f.composite_risk_score, f.fingerprint, f.application_id, \
+         f.severity_override, f.status, f.composite_risk_score, f.fingerprint, f.application_id, \
          f.first_seen, f.last_seen, f.sla_status{extra_columns} \
          FROM findings f {joins} {where_clause} \
-         ORDER BY f.composite_risk_score DESC NULLS LAST, f.normalized_severity ASC, f.first_seen DESC"
+         ORDER BY f.first_seen DESC, f.id DESC \
+         LIMIT {EXPORT_BATCH_SIZE}"
     );
 
     // Pre-compute ILIKE patterns so they outlive the query bindings.
@@ -1261,12 +2764,18 @@ pub async fn list_all_for_export(
     if let Some(ref app_id) = filters.application_id {
         bind_export!(app_id);
     }
+    if let Some(ref user_id) = filters.visible_to {
+        bind_export!(user_id);
+    }
     if let Some(ref tool) = filters.source_tool {
         bind_export!(tool);
     }
     if let Some(ref sla) = filters.sla_status {
         bind_export!(sla);
     }
+    if let Some(ref owasp_category) = filters.owasp_category {
+        bind_export!(owasp::category_code(owasp_category));
+    }
     if let Some(ref search) = filters.search {
         bind_export!(search);
     }
@@ -1328,84 +2837,108 @@ pub async fn list_all_for_export(
         bind_export!(to);
     }
 
-    let rows = data_query.fetch_all(pool).await?;
-
-    let items: Vec<FindingSummaryWithCategory> = rows
-        .into_iter()
-        .map(|row| {
-            let finding_category: FindingCategory = row.get("finding_category");
+    if let Some(cursor) = after {
+        bind_export!(cursor.first_seen);
+        bind_export!(cursor.id);
+    }
 
-            let summary = FindingSummary {
-                id: row.get("id"),
-                source_tool: row.get("source_tool"),
-                finding_category: finding_category.clone(),
-                title: row.get("title"),
-                normalized_severity: row.get("normalized_severity"),
-                status: row.get("status"),
-                composite_risk_score: row.get("composite_risk_score"),
-                fingerprint: row.get("fingerprint"),
-                application_id: row.get("application_id"),
-                first_seen: row.get("first_seen"),
-                last_seen: row.get("last_seen"),
-                sla_status: row.get("sla_status"),
-            };
+    let rows = data_query.fetch_all(pool).await?;
+    Ok(rows
+        .iter()
+        .map(|row| export_item_from_row(row, join_sast, join_sca, join_dast, join_manual))
+        .collect())
+}
 
-            let category_data = match finding_category {
-                FindingCategory::Sast if join_sast => {
-                    let file_path: Option<String> = row.get("sast_file_path");
-                    if file_path.is_some() {
-                        Some(FindingCategoryData {
-                            file_path,
-                            line_number: row.get("sast_line_number"),
-                            rule_id: row.get("sast_rule_id"),
-                            project: row.get("sast_project"),
-                            language: row.get("sast_language"),
-                            branch: row.get("sast_branch"),
-                            ..Default::default()
-                        })
-                    } else {
-                        None
-                    }
-                }
-                FindingCategory::Sca if join_sca => {
-                    let package_name: Option<String> = row.get("sca_package_name");
-                    if package_name.is_some() {
-                        Some(FindingCategoryData {
-                            package_name,
-                            package_version: row.get("sca_package_version"),
-                            fixed_version: row.get("sca_fixed_version"),
-                            dependency_type: row.get("sca_dependency_type"),
-                            known_exploited: row.get("sca_known_exploited"),
-                            ..Default::default()
-                        })
-                    } else {
-                        None
-                    }
-                }
-                FindingCategory::Dast if join_dast => {
-                    let target_url: Option<String> = row.get("dast_target_url");
-                    if target_url.is_some() {
-                        Some(FindingCategoryData {
-                            target_url,
-                            parameter: row.get("dast_parameter"),
-                            web_application_name: row.get("dast_web_application_name"),
-                            ..Default::default()
-                        })
-                    } else {
-                        None
-                    }
-                }
-                _ => None,
-            };
+/// Map an export query row to a summary with its category data.
+fn export_item_from_row(
+    row: &PgRow,
+    join_sast: bool,
+    join_sca: bool,
+    join_dast: bool,
+    join_manual: bool,
+) -> FindingSummaryWithCategory {
+    let finding_category: FindingCategory = row.get("finding_category");
+
+    let summary = FindingSummary {
+        id: row.get("id"),
+        source_tool: row.get("source_tool"),
+        finding_category: finding_category.clone(),
+        title: row.get("title"),
+        normalized_severity: row.get("normalized_severity"),
+        severity_override: row.get("severity_override"),
+        status: row.get("status"),
+        composite_risk_score: row.get("composite_risk_score"),
+        fingerprint: row.get("fingerprint"),
+        application_id: row.get("application_id"),
+        first_seen: row.get("first_seen"),
+        last_seen: row.get("last_seen"),
+        sla_status: row.get("sla_status"),
+    };
 
-            FindingSummaryWithCategory {
-                summary,
-                category_data,
+    let category_data = match finding_category {
+        FindingCategory::Sast if join_sast => {
+            let file_path: Option<String> = row.get("sast_file_path");
+            if file_path.is_some() {
+                Some(FindingCategoryData {
+                    file_path,
+                    line_number: row.get("sast_line_number"),
+                    rule_id: row.get("sast_rule_id"),
+                    project: row.get("sast_project"),
+                    language: row.get("sast_language"),
+                    branch: row.get("sast_branch"),
+                    ..Default::default()
+                })
+            } else {
+                None
             }
-        })
-        .collect();
+        }
+        FindingCategory::Sca if join_sca => {
+            let package_name: Option<String> = row.get("sca_package_name");
+            if package_name.is_some() {
+                Some(FindingCategoryData {
+                    package_name,
+                    package_version: row.get("sca_package_version"),
+                    fixed_version: row.get("sca_fixed_version"),
+                    dependency_type: row.get("sca_dependency_type"),
+                    known_exploited: row.get("sca_known_exploited"),
+                    ..Default::default()
+                })
+            } else {
+                None
+            }
+        }
+        FindingCategory::Dast if join_dast => {
+            let target_url: Option<String> = row.get("dast_target_url");
+            if target_url.is_some() {
+                Some(FindingCategoryData {
+                    target_url,
+                    parameter: row.get("dast_parameter"),
+                    web_application_name: row.get("dast_web_application_name"),
+                    ..Default::default()
+                })
+            } else {
+                None
+            }
+        }
+        FindingCategory::Manual if join_manual => {
+            let affected_asset: Option<String> = row.get("manual_affected_asset");
+            if affected_asset.is_some() {
+                Some(FindingCategoryData {
+                    affected_asset,
+                    reporter: row.get("manual_reporter"),
+                    ..Default::default()
+                })
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
 
-    Ok(items)
+    FindingSummaryWithCategory {
+        summary,
+        category_data,
+    }
 }
 
 /// Update last_seen timestamp for a finding (used during re-ingestion).
@@ -1416,3 +2949,201 @@ pub async fn touch_last_seen(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
         .await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(id: Uuid) -> Finding {
+        let now = Utc::now();
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "source_tool": "semgrep",
+            "source_finding_id": "rule-1",
+            "finding_category": "SAST",
+            "title": "SQL injection",
+            "description": "Unsanitized input reaches a query",
+            "normalized_severity": "High",
+            "original_severity": "ERROR",
+            "effective_severity": "High",
+            "cwe_ids": [],
+            "cve_ids": [],
+            "status": "New",
+            "fingerprint": "fp",
+            "first_seen": now,
+            "last_seen": now,
+            "status_changed_at": now,
+            "created_at": now,
+            "updated_at": now,
+            "tags": [],
+            "raw_finding": {},
+            "metadata": {},
+            "attack_technique_ids": [],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn severity_override_requires_justification() {
+        let request = |body| serde_json::from_value::<SeverityOverrideRequest>(body).unwrap();
+        let set =
+            request(serde_json::json!({ "severity": "Low", "justification": "Not reachable" }));
+        assert!(set.validate().is_ok());
+        assert_eq!(set.severity, Some(SeverityLevel::Low));
+
+        let clear = request(serde_json::json!({ "severity": null, "justification": "" }));
+        assert!(clear.validate().is_err());
+        assert_eq!(clear.severity, None);
+    }
+
+    #[test]
+    fn comments_are_modifiable_by_author_or_admin() {
+        let author = Uuid::new_v4();
+        let now = Utc::now();
+        let comment: FindingComment = serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "finding_id": Uuid::new_v4(),
+            "author_id": author,
+            "author_name": "alice",
+            "content": "Looks exploitable",
+            "created_at": now,
+            "updated_at": now,
+            "content_format": "markdown",
+            "mentioned_user_ids": [],
+        }))
+        .unwrap();
+        let user = |id, role| CurrentUser {
+            id,
+            username: "someone".to_string(),
+            role,
+        };
+
+        assert!(can_modify_comment(
+            &comment,
+            &user(author, UserRole::AppSecAnalyst)
+        ));
+        assert!(can_modify_comment(
+            &comment,
+            &user(Uuid::new_v4(), UserRole::PlatformAdmin)
+        ));
+        assert!(!can_modify_comment(
+            &comment,
+            &user(Uuid::new_v4(), UserRole::AppSecManager)
+        ));
+    }
+
+    #[test]
+    fn search_syntax_parses_config_values() {
+        assert_eq!("websearch".parse(), Ok(SearchSyntax::Websearch));
+        assert_eq!(" Plain ".parse(), Ok(SearchSyntax::Plain));
+        assert!("phrase".parse::<SearchSyntax>().is_err());
+        assert_eq!(
+            SearchSyntax::Websearch.tsquery_function(),
+            "websearch_to_tsquery"
+        );
+    }
+
+    #[test]
+    fn bulk_insert_rows_follow_input_order() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let returned = vec![finding(ids[2]), finding(ids[0]), finding(ids[1])];
+        let ordered: Vec<Uuid> = in_input_order(&ids, returned).iter().map(|f| f.id).collect();
+        assert_eq!(ordered, ids);
+    }
+
+    #[test]
+    fn batch_keeps_request_order_and_reports_missing() {
+        let (a, b, unknown) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let result = assemble_batch(
+            &[b, unknown, a],
+            vec![finding(a), finding(b)],
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        );
+        let ids: Vec<Uuid> = result.items.iter().map(|i| i.finding.id).collect();
+        assert_eq!(ids, vec![b, a]);
+        assert_eq!(result.missing, vec![unknown]);
+        assert!(result.items[0].sast.is_none());
+    }
+
+    #[test]
+    fn bulk_selection_requires_findings_or_applications() {
+        assert!(BulkSelection::default().validate().is_err());
+
+        let delete: BulkDelete = serde_json::from_str(
+            r#"{"application_ids": ["00000000-0000-0000-0000-000000000000"], "dry_run": true}"#,
+        )
+        .unwrap();
+        assert!(delete.selection.validate().is_ok());
+        assert!(delete.selection.finding_ids.is_empty());
+        assert!(delete.dry_run);
+    }
+
+    #[test]
+    fn default_sort_keeps_risk_ordering() {
+        let order = FindingSort::default().order_by();
+        assert!(order.starts_with("f.composite_risk_score DESC NULLS LAST"));
+        assert!(order.ends_with("f.id"));
+    }
+
+    #[test]
+    fn sort_uses_column_default_direction() {
+        let sort = FindingSort {
+            sort_by: Some(FindingSortField::SlaDueDate),
+            sort_dir: None,
+        };
+        assert_eq!(sort.order_by(), "f.sla_due_date ASC NULLS LAST, f.id");
+
+        let sort = FindingSort {
+            sort_by: Some(FindingSortField::LastSeen),
+            sort_dir: Some(SortDirection::Asc),
+        };
+        assert_eq!(sort.order_by(), "f.last_seen ASC NULLS LAST, f.id");
+    }
+
+    #[test]
+    fn category_sort_reads_category_table() {
+        let sort: FindingSort =
+            serde_json::from_str(r#"{"sort_by": "package_name", "sort_dir": "desc"}"#).unwrap();
+        let order = sort.order_by();
+        assert!(order.contains("FROM finding_sca sc WHERE sc.finding_id = f.id"));
+        assert!(order.contains("DESC NULLS LAST"));
+    }
+
+    #[test]
+    fn raw_document_file_names_are_safe() {
+        let id = Uuid::nil();
+        let doc = RawFindingDocument {
+            finding_id: id,
+            source_tool: "JFrog Xray/\"x\"".to_string(),
+            source_tool_version: None,
+            source_finding_id: "XRAY-1".to_string(),
+            original_severity: "High".to_string(),
+            normalized_severity: SeverityLevel::High,
+            first_seen: Utc::now(),
+            last_seen: Utc::now(),
+            raw_finding: serde_json::json!({}),
+        };
+        assert_eq!(doc.file_name(), format!("jfrog_xray__x__{id}.raw.json"));
+    }
+
+    #[test]
+    fn history_action_filter_splits_and_trims() {
+        let params = HistoryListParams {
+            sort_dir: None,
+            action: Some(" status_change, ,severity_override ".to_string()),
+        };
+        assert_eq!(
+            params.actions(),
+            Some(vec!["status_change".to_string(), "severity_override".to_string()])
+        );
+        let blank = HistoryListParams {
+            sort_dir: None,
+            action: Some(" , ".to_string()),
+        };
+        assert_eq!(blank.actions(), None);
+        assert_eq!(HistoryListParams::default().actions(), None);
+    }
+}
@@ -0,0 +1,697 @@
+//! Manual merging of duplicate findings, and undoing merges.
+//!
+//! Merging finding B (source) into finding A (target) moves B's comments,
+//! history, occurrences, attachments and relationships onto A, keeps the
+//! richer of the two category rows on A, adds B's tags to A, archives B and
+//! records B as a confirmed duplicate of A. The `finding_merges` row lists
+//! everything the merge changed, so unmerging puts it all back; rows created
+//! on A after the merge stay on A.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{ConfidenceLevel, FindingCategory};
+
+/// Request to merge a duplicate into the finding in the path.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeRequest {
+    /// The duplicate finding, archived by the merge.
+    pub source_finding_id: Uuid,
+    pub justification: Option<String>,
+}
+
+/// A merge of `source_finding_id` into `target_finding_id`.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct FindingMerge {
+    pub id: Uuid,
+    pub target_finding_id: Uuid,
+    pub source_finding_id: Uuid,
+    pub justification: Option<String>,
+    pub comments_moved: i32,
+    pub history_moved: i32,
+    pub occurrences_moved: i32,
+    pub attachments_moved: i32,
+    pub relationships_moved: i32,
+    /// Whether the source's category data replaced the target's.
+    pub category_data_replaced: bool,
+    /// Source tags added to the target.
+    pub added_tags: serde_json::Value,
+    pub merged_by: Option<Uuid>,
+    pub merged_at: DateTime<Utc>,
+    pub unmerged_by: Option<Uuid>,
+    pub unmerged_at: Option<DateTime<Utc>>,
+}
+
+const MERGE_COLUMNS: &str = r#"
+    id, target_finding_id, source_finding_id, justification,
+    cardinality(moved_comment_ids) AS comments_moved,
+    cardinality(moved_history_ids) AS history_moved,
+    cardinality(moved_occurrence_ids) AS occurrences_moved,
+    cardinality(moved_attachment_ids) AS attachments_moved,
+    cardinality(moved_outgoing_relationship_ids)
+        + cardinality(moved_incoming_relationship_ids) AS relationships_moved,
+    category_data_replaced, added_tags, merged_by, merged_at, unmerged_by, unmerged_at
+"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct MergeFinding {
+    id: Uuid,
+    finding_category: FindingCategory,
+    tags: serde_json::Value,
+    archived_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct MergeRecord {
+    target_finding_id: Uuid,
+    source_finding_id: Uuid,
+    moved_comment_ids: Vec<Uuid>,
+    moved_history_ids: Vec<Uuid>,
+    moved_occurrence_ids: Vec<Uuid>,
+    moved_attachment_ids: Vec<Uuid>,
+    moved_outgoing_relationship_ids: Vec<Uuid>,
+    moved_incoming_relationship_ids: Vec<Uuid>,
+    category_data_replaced: bool,
+    replaced_category_data: Option<serde_json::Value>,
+    added_tags: serde_json::Value,
+    source_archived_at_before: Option<DateTime<Utc>>,
+    duplicate_relationship_created: bool,
+    duplicate_confidence_before: Option<ConfidenceLevel>,
+    merged_at: DateTime<Utc>,
+    unmerged_at: Option<DateTime<Utc>>,
+}
+
+/// Category-specific table of a finding category.
+pub(crate) fn category_table(category: &FindingCategory) -> &'static str {
+    match category {
+        FindingCategory::Sast => "finding_sast",
+        FindingCategory::Sca => "finding_sca",
+        FindingCategory::Dast => "finding_dast",
+        FindingCategory::Manual => "finding_manual",
+    }
+}
+
+/// Number of populated fields in a category row, the measure of "richer".
+fn richness(row: Option<&serde_json::Value>) -> usize {
+    let Some(fields) = row.and_then(|r| r.as_object()) else {
+        return 0;
+    };
+    fields
+        .iter()
+        .filter(|(name, _)| name.as_str() != "finding_id")
+        .filter(|(_, value)| match value {
+            serde_json::Value::Null => false,
+            serde_json::Value::String(s) => !s.is_empty(),
+            serde_json::Value::Array(a) => !a.is_empty(),
+            _ => true,
+        })
+        .count()
+}
+
+fn tag_names(tags: &serde_json::Value) -> Vec<String> {
+    serde_json::from_value(tags.clone()).unwrap_or_default()
+}
+
+/// Tags of `source` missing from `target`, in source order.
+fn tags_to_add(target: &[String], source: &[String]) -> Vec<String> {
+    let mut added: Vec<String> = Vec::new();
+    for tag in source {
+        if !target.contains(tag) && !added.contains(tag) {
+            added.push(tag.clone());
+        }
+    }
+    added
+}
+
+/// Move the rows of `table` listed in `ids` from finding `from` to `to`,
+/// returning the ids moved. Without `ids`, every row of `from` moves.
+async fn move_rows(
+    conn: &mut PgConnection,
+    table: &str,
+    from: Uuid,
+    to: Uuid,
+    ids: Option<&[Uuid]>,
+) -> Result<Vec<Uuid>, AppError> {
+    let moved = sqlx::query_scalar::<_, Uuid>(&format!(
+        "UPDATE {table} SET finding_id = $2 \
+         WHERE finding_id = $1 AND ($3::uuid[] IS NULL OR id = ANY($3)) \
+         RETURNING id"
+    ))
+    .bind(from)
+    .bind(to)
+    .bind(ids)
+    .fetch_all(&mut *conn)
+    .await?;
+    Ok(moved)
+}
+
+/// Category row of a finding as JSON.
+pub(crate) async fn category_row(
+    conn: &mut PgConnection,
+    table: &str,
+    finding_id: Uuid,
+) -> Result<Option<serde_json::Value>, AppError> {
+    let row = sqlx::query_scalar::<_, serde_json::Value>(&format!(
+        "SELECT to_jsonb(t) FROM {table} t WHERE finding_id = $1"
+    ))
+    .bind(finding_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    Ok(row)
+}
+
+/// Replace a finding's category row with `row` (re-keyed to the finding), or
+/// remove it when `row` is `None`.
+pub(crate) async fn replace_category_row(
+    conn: &mut PgConnection,
+    table: &str,
+    finding_id: Uuid,
+    row: Option<&serde_json::Value>,
+) -> Result<(), AppError> {
+    sqlx::query(&format!("DELETE FROM {table} WHERE finding_id = $1"))
+        .bind(finding_id)
+        .execute(&mut *conn)
+        .await?;
+    if let Some(row) = row {
+        sqlx::query(&format!(
+            "INSERT INTO {table} \
+             SELECT (jsonb_populate_record(NULL::{table}, $2 || jsonb_build_object('finding_id', $1::uuid))).*"
+        ))
+        .bind(finding_id)
+        .bind(row)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Record a merge action on both findings' history.
+async fn record_history(
+    conn: &mut PgConnection,
+    action: &str,
+    target_id: Uuid,
+    source_id: Uuid,
+    actor_id: Uuid,
+    actor_name: &str,
+    justification: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name, justification)
+        VALUES ($2, $1, 'merged_from', NULL, $3::text, $4, $5, $6),
+               ($3, $1, 'merged_into', NULL, $2::text, $4, $5, $6)
+        "#,
+    )
+    .bind(action)
+    .bind(target_id)
+    .bind(source_id)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(justification)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn record_audit(
+    conn: &mut PgConnection,
+    target_finding_id: Uuid,
+    action: &str,
+    actor_id: Uuid,
+    actor_name: &str,
+    details: serde_json::Value,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('finding', $1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(target_finding_id)
+    .bind(action)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(details)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn fetch_merge(conn: &mut PgConnection, merge_id: Uuid) -> Result<FindingMerge, AppError> {
+    let merge = sqlx::query_as::<_, FindingMerge>(&format!(
+        "SELECT {MERGE_COLUMNS} FROM finding_merges WHERE id = $1"
+    ))
+    .bind(merge_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(merge)
+}
+
+/// Merge `input.source_finding_id` into `target_id`.
+///
+/// Both findings must share a category. A finding merged into another
+/// cannot take part in further merges until it is unmerged.
+pub async fn merge(
+    pool: &PgPool,
+    target_id: Uuid,
+    input: &MergeRequest,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<FindingMerge, AppError> {
+    let source_id = input.source_finding_id;
+    if source_id == target_id {
+        return Err(AppError::Validation(
+            "A finding cannot be merged into itself".to_string(),
+        ));
+    }
+    let mut tx = pool.begin().await?;
+
+    let findings = sqlx::query_as::<_, MergeFinding>(
+        "SELECT id, finding_category, tags, archived_at FROM findings WHERE id = ANY($1) FOR UPDATE",
+    )
+    .bind(vec![target_id, source_id])
+    .fetch_all(&mut *tx)
+    .await?;
+    let find = |id: Uuid| {
+        findings
+            .iter()
+            .find(|f| f.id == id)
+            .ok_or_else(|| AppError::NotFound(format!("Finding {id} not found")))
+    };
+    let target = find(target_id)?;
+    let source = find(source_id)?;
+    if target.finding_category != source.finding_category {
+        return Err(AppError::Validation(
+            "Only findings of the same category can be merged".to_string(),
+        ));
+    }
+
+    let merged_away = sqlx::query_scalar::<_, Uuid>(
+        "SELECT source_finding_id FROM finding_merges WHERE source_finding_id = ANY($1) AND unmerged_at IS NULL",
+    )
+    .bind(vec![target_id, source_id])
+    .fetch_all(&mut *tx)
+    .await?;
+    if let Some(id) = merged_away.first() {
+        return Err(AppError::Conflict(format!(
+            "Finding {id} is already merged into another finding"
+        )));
+    }
+
+    let comments = move_rows(&mut tx, "finding_comments", source_id, target_id, None).await?;
+    let history = move_rows(&mut tx, "finding_history", source_id, target_id, None).await?;
+    let occurrences = move_rows(&mut tx, "finding_occurrences", source_id, target_id, None).await?;
+    let attachments = move_rows(&mut tx, "finding_attachments", source_id, target_id, None).await?;
+
+    // Relationships between the pair stay put, as do those the target
+    // already has with the same finding and type.
+    let outgoing = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        UPDATE finding_relationships r SET source_finding_id = $2
+        WHERE r.source_finding_id = $1 AND r.target_finding_id <> $2
+          AND NOT EXISTS (
+              SELECT 1 FROM finding_relationships x
+              WHERE x.source_finding_id = $2 AND x.target_finding_id = r.target_finding_id
+                AND x.relationship_type = r.relationship_type
+          )
+        RETURNING r.id
+        "#,
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let incoming = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        UPDATE finding_relationships r SET target_finding_id = $2
+        WHERE r.target_finding_id = $1 AND r.source_finding_id <> $2
+          AND NOT EXISTS (
+              SELECT 1 FROM finding_relationships x
+              WHERE x.target_finding_id = $2 AND x.source_finding_id = r.source_finding_id
+                AND x.relationship_type = r.relationship_type
+          )
+        RETURNING r.id
+        "#,
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let table = category_table(&target.finding_category);
+    let target_row = category_row(&mut tx, table, target_id).await?;
+    let source_row = category_row(&mut tx, table, source_id).await?;
+    let category_data_replaced = richness(source_row.as_ref()) > richness(target_row.as_ref());
+    if category_data_replaced {
+        replace_category_row(&mut tx, table, target_id, source_row.as_ref()).await?;
+    }
+
+    let added_tags = tags_to_add(&tag_names(&target.tags), &tag_names(&source.tags));
+    if !added_tags.is_empty() {
+        sqlx::query("UPDATE findings SET tags = COALESCE(tags, '[]') || $2, updated_at = NOW() WHERE id = $1")
+            .bind(target_id)
+            .bind(serde_json::json!(added_tags))
+            .execute(&mut *tx)
+            .await?;
+    }
+    sqlx::query(
+        "UPDATE findings SET archived_at = COALESCE(archived_at, NOW()), updated_at = NOW() WHERE id = $1",
+    )
+    .bind(source_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let existing_duplicate = sqlx::query_scalar::<_, Option<ConfidenceLevel>>(
+        r#"
+        SELECT confidence FROM finding_relationships
+        WHERE source_finding_id = $1 AND target_finding_id = $2 AND relationship_type = 'duplicate_of'
+        "#,
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO finding_relationships (source_finding_id, target_finding_id, relationship_type, confidence, created_by, notes)
+        VALUES ($1, $2, 'duplicate_of', 'High', $3, 'Merged')
+        ON CONFLICT (source_finding_id, target_finding_id, relationship_type)
+        DO UPDATE SET confidence = 'High'
+        "#,
+    )
+    .bind(source_id)
+    .bind(target_id)
+    .bind(actor_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let merge_id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO finding_merges (
+            target_finding_id, source_finding_id, justification,
+            moved_comment_ids, moved_history_ids, moved_occurrence_ids, moved_attachment_ids,
+            moved_outgoing_relationship_ids, moved_incoming_relationship_ids,
+            category_data_replaced, replaced_category_data, added_tags, source_archived_at_before,
+            duplicate_relationship_created, duplicate_confidence_before, merged_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+        RETURNING id
+        "#,
+    )
+    .bind(target_id)
+    .bind(source_id)
+    .bind(&input.justification)
+    .bind(&comments)
+    .bind(&history)
+    .bind(&occurrences)
+    .bind(&attachments)
+    .bind(&outgoing)
+    .bind(&incoming)
+    .bind(category_data_replaced)
+    .bind(if category_data_replaced {
+        target_row.as_ref()
+    } else {
+        None
+    })
+    .bind(serde_json::json!(added_tags))
+    .bind(source.archived_at)
+    .bind(existing_duplicate.is_none())
+    .bind(existing_duplicate.flatten())
+    .bind(actor_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let justification = input.justification.as_deref();
+    record_history(
+        &mut tx,
+        "merged",
+        target_id,
+        source_id,
+        actor_id,
+        actor_name,
+        justification,
+    )
+    .await?;
+    let merge = fetch_merge(&mut tx, merge_id).await?;
+    record_audit(
+        &mut tx,
+        target_id,
+        "finding_merge",
+        actor_id,
+        actor_name,
+        serde_json::json!({
+            "merge_id": merge_id,
+            "source_finding_id": source_id,
+            "comments_moved": merge.comments_moved,
+            "history_moved": merge.history_moved,
+            "occurrences_moved": merge.occurrences_moved,
+            "attachments_moved": merge.attachments_moved,
+            "relationships_moved": merge.relationships_moved,
+            "category_data_replaced": category_data_replaced,
+            "justification": justification,
+        }),
+    )
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(%merge_id, %target_id, %source_id, "Findings merged");
+    Ok(merge)
+}
+
+/// Undo a merge, moving the source's rows back and restoring both findings.
+///
+/// Merges are undone newest first: a merge cannot be undone while a later
+/// merge involving either finding is still in place.
+pub async fn unmerge(
+    pool: &PgPool,
+    merge_id: Uuid,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<FindingMerge, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let record = sqlx::query_as::<_, MergeRecord>(
+        r#"
+        SELECT target_finding_id, source_finding_id,
+               moved_comment_ids, moved_history_ids, moved_occurrence_ids, moved_attachment_ids,
+               moved_outgoing_relationship_ids, moved_incoming_relationship_ids,
+               category_data_replaced, replaced_category_data, added_tags, source_archived_at_before,
+               duplicate_relationship_created, duplicate_confidence_before, merged_at, unmerged_at
+        FROM finding_merges WHERE id = $1 FOR UPDATE
+        "#,
+    )
+    .bind(merge_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Merge {merge_id} not found")))?;
+    if record.unmerged_at.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Merge {merge_id} was already undone"
+        )));
+    }
+    let (target_id, source_id) = (record.target_finding_id, record.source_finding_id);
+
+    let later = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM finding_merges
+            WHERE id <> $1 AND unmerged_at IS NULL AND merged_at > $2
+              AND (target_finding_id = ANY($3) OR source_finding_id = ANY($3))
+        )
+        "#,
+    )
+    .bind(merge_id)
+    .bind(record.merged_at)
+    .bind(vec![target_id, source_id])
+    .fetch_one(&mut *tx)
+    .await?;
+    if later {
+        return Err(AppError::Conflict(
+            "A later merge involving these findings must be undone first".to_string(),
+        ));
+    }
+
+    let category = sqlx::query_scalar::<_, FindingCategory>(
+        "SELECT finding_category FROM findings WHERE id = $1 FOR UPDATE",
+    )
+    .bind(target_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    for (table, ids) in [
+        ("finding_comments", &record.moved_comment_ids),
+        ("finding_history", &record.moved_history_ids),
+        ("finding_occurrences", &record.moved_occurrence_ids),
+        ("finding_attachments", &record.moved_attachment_ids),
+    ] {
+        move_rows(&mut tx, table, target_id, source_id, Some(ids.as_slice())).await?;
+    }
+    sqlx::query(
+        "UPDATE finding_relationships SET source_finding_id = $2 WHERE id = ANY($3) AND source_finding_id = $1",
+    )
+    .bind(target_id)
+    .bind(source_id)
+    .bind(&record.moved_outgoing_relationship_ids)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "UPDATE finding_relationships SET target_finding_id = $2 WHERE id = ANY($3) AND target_finding_id = $1",
+    )
+    .bind(target_id)
+    .bind(source_id)
+    .bind(&record.moved_incoming_relationship_ids)
+    .execute(&mut *tx)
+    .await?;
+
+    if record.category_data_replaced {
+        replace_category_row(
+            &mut tx,
+            category_table(&category),
+            target_id,
+            record.replaced_category_data.as_ref(),
+        )
+        .await?;
+    }
+
+    let added_tags = tag_names(&record.added_tags);
+    if !added_tags.is_empty() {
+        sqlx::query(
+            r#"
+            UPDATE findings
+            SET tags = COALESCE(
+                    (SELECT jsonb_agg(t) FROM jsonb_array_elements_text(tags) AS t WHERE t <> ALL($2)),
+                    '[]'::jsonb
+                ),
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(target_id)
+        .bind(&added_tags)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query("UPDATE findings SET archived_at = $2, updated_at = NOW() WHERE id = $1")
+        .bind(source_id)
+        .bind(record.source_archived_at_before)
+        .execute(&mut *tx)
+        .await?;
+
+    if record.duplicate_relationship_created {
+        sqlx::query(
+            r#"
+            DELETE FROM finding_relationships
+            WHERE source_finding_id = $1 AND target_finding_id = $2 AND relationship_type = 'duplicate_of'
+            "#,
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .execute(&mut *tx)
+        .await?;
+    } else {
+        sqlx::query(
+            r#"
+            UPDATE finding_relationships SET confidence = $3
+            WHERE source_finding_id = $1 AND target_finding_id = $2 AND relationship_type = 'duplicate_of'
+            "#,
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .bind(&record.duplicate_confidence_before)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query("UPDATE finding_merges SET unmerged_by = $2, unmerged_at = NOW() WHERE id = $1")
+        .bind(merge_id)
+        .bind(actor_id)
+        .execute(&mut *tx)
+        .await?;
+
+    record_history(
+        &mut tx, "unmerged", target_id, source_id, actor_id, actor_name, None,
+    )
+    .await?;
+    record_audit(
+        &mut tx,
+        target_id,
+        "finding_unmerge",
+        actor_id,
+        actor_name,
+        serde_json::json!({ "merge_id": merge_id, "source_finding_id": source_id }),
+    )
+    .await?;
+    let merge = fetch_merge(&mut tx, merge_id).await?;
+
+    tx.commit().await?;
+    tracing::info!(%merge_id, %target_id, %source_id, "Finding merge undone");
+    Ok(merge)
+}
+
+/// Merges the finding took part in, as target or source, newest first.
+pub async fn list_for_finding(
+    pool: &PgPool,
+    finding_id: Uuid,
+) -> Result<Vec<FindingMerge>, AppError> {
+    let merges = sqlx::query_as::<_, FindingMerge>(&format!(
+        r#"
+        SELECT {MERGE_COLUMNS} FROM finding_merges
+        WHERE target_finding_id = $1 OR source_finding_id = $1
+        ORDER BY merged_at DESC
+        "#
+    ))
+    .bind(finding_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(merges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn richness_counts_populated_fields_except_the_key() {
+        let sparse = serde_json::json!({
+            "finding_id": Uuid::nil(),
+            "file_path": "src/db.rs",
+            "line_number_start": null,
+            "code_snippet": "",
+            "scanner_tags": [],
+        });
+        let rich = serde_json::json!({
+            "finding_id": Uuid::nil(),
+            "file_path": "src/db.rs",
+            "line_number_start": 42,
+            "code_snippet": "query(&sql)",
+            "scanner_tags": ["injection"],
+        });
+        assert_eq!(richness(None), 0);
+        assert_eq!(richness(Some(&sparse)), 1);
+        assert_eq!(richness(Some(&rich)), 4);
+    }
+
+    #[test]
+    fn only_missing_tags_are_added() {
+        let target = vec!["pci".to_string(), "payments".to_string()];
+        let source = vec![
+            "payments".to_string(),
+            "external".to_string(),
+            "external".to_string(),
+        ];
+        assert_eq!(tags_to_add(&target, &source), vec!["external".to_string()]);
+        assert!(tags_to_add(&target, &[]).is_empty());
+    }
+
+    #[test]
+    fn category_tables() {
+        assert_eq!(category_table(&FindingCategory::Sast), "finding_sast");
+        assert_eq!(category_table(&FindingCategory::Sca), "finding_sca");
+        assert_eq!(category_table(&FindingCategory::Dast), "finding_dast");
+        assert_eq!(category_table(&FindingCategory::Manual), "finding_manual");
+    }
+}
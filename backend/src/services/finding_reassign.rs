@@ -0,0 +1,641 @@
+//! Moving findings between applications: splitting a finding that covers
+//! several applications, and reassigning findings in bulk after an app-code
+//! correction.
+//!
+//! A split keeps its first part on the original finding and creates a
+//! finding per further part, copying the original's fields, category data
+//! and history. Parts get their own fingerprint, computed from their
+//! application and category data, so re-scans keep landing on the original.
+//!
+//! A reassignment moves findings from one application to another, recording
+//! the change in each finding's history. Findings of tools whose configured
+//! fingerprint includes the app code are re-fingerprinted with the new one,
+//! the same way ingestion would fingerprint them now.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::errors::AppError;
+use crate::models::finding::FindingCategory;
+use crate::services::config_cache;
+use crate::services::finding::BulkResult;
+use crate::services::finding_merge::{category_row, category_table, replace_category_row};
+use crate::services::fingerprint::{
+    self, FingerprintComponent, FingerprintInputs, FingerprintProfiles,
+};
+
+/// Most findings a split can produce.
+pub const MAX_SPLIT_PARTS: usize = 20;
+
+/// Finding columns a split part copies from the original. Identity,
+/// fingerprint, application, title and timestamps of the row are set anew.
+const COPIED_COLUMNS: &str = r#"
+    source_tool, source_tool_version, source_finding_id, finding_category, description,
+    normalized_severity, original_severity, cvss_score, cvss_vector, cwe_ids, cve_ids,
+    owasp_category, status, composite_risk_score, confidence, remediation_owner,
+    office_owner, office_manager, first_seen, last_seen, status_changed_at, sla_due_date,
+    sla_status, tags, remediation_guidance, raw_finding, metadata, introduced_in_release_id,
+    fixed_in_release_id, attack_technique_ids, scanner_rule_id, severity_override,
+    severity_override_justification, severity_override_by, severity_override_at
+"#;
+
+/// One finding produced by a split.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SplitPart {
+    /// Application the part belongs to.
+    pub application_id: Uuid,
+    /// Title of the part; defaults to the original's.
+    pub title: Option<String>,
+    /// Category fields to change on the part, e.g. `{"target_url": ...}` for
+    /// a DAST finding.
+    #[serde(default)]
+    pub category_data: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Request to split the finding in the path.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct SplitRequest {
+    /// The first part stays on the original finding; each further part
+    /// becomes a new finding.
+    pub parts: Vec<SplitPart>,
+    #[validate(length(min = 1, max = 2000))]
+    pub justification: String,
+}
+
+/// A split of `original_finding_id` into itself and `split_finding_ids`.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct FindingSplit {
+    pub id: Uuid,
+    pub original_finding_id: Uuid,
+    /// Findings created by the split, in part order.
+    pub split_finding_ids: Vec<Uuid>,
+    /// Application of the original finding before the split.
+    pub original_application_id: Option<Uuid>,
+    pub justification: String,
+    pub split_by: Option<Uuid>,
+    pub split_at: DateTime<Utc>,
+}
+
+/// Request to move findings from one application to another.
+#[derive(Debug, Deserialize, ToSchema, Validate)]
+pub struct BulkReassign {
+    pub from_application_id: Uuid,
+    pub to_application_id: Uuid,
+    /// Findings of `from_application_id` to move; empty moves all of them.
+    #[serde(default)]
+    pub finding_ids: Vec<Uuid>,
+    #[validate(length(min = 1, max = 2000))]
+    pub justification: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SplitFinding {
+    finding_category: FindingCategory,
+    application_id: Option<Uuid>,
+    title: String,
+    archived_at: Option<DateTime<Utc>>,
+}
+
+/// Stored fields a fingerprint is computed from.
+#[derive(Debug, sqlx::FromRow)]
+struct StoredInputs {
+    id: Uuid,
+    source_tool: String,
+    finding_category: FindingCategory,
+    app_code: String,
+    file_path: String,
+    rule_id: String,
+    branch: String,
+    line_number: String,
+    package_name: String,
+    package_version: String,
+    cve_id: String,
+    target_url: String,
+    http_method: String,
+    parameter: String,
+    cwe_id: String,
+    affected_asset: String,
+    title: String,
+}
+
+impl From<StoredInputs> for FingerprintInputs {
+    fn from(row: StoredInputs) -> Self {
+        Self {
+            app_code: row.app_code,
+            file_path: row.file_path,
+            rule_id: row.rule_id,
+            branch: row.branch,
+            line_number: row.line_number,
+            package_name: row.package_name,
+            package_version: row.package_version,
+            cve_id: row.cve_id,
+            target_url: row.target_url,
+            http_method: row.http_method,
+            parameter: row.parameter,
+            cwe_id: row.cwe_id,
+            affected_asset: row.affected_asset,
+            title: row.title,
+        }
+    }
+}
+
+/// Check a part's category changes against the original's category row:
+/// the row must exist and every field must be one of its columns.
+fn apply_category_changes(
+    table: &str,
+    row: Option<&serde_json::Value>,
+    changes: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Option<serde_json::Value>, AppError> {
+    if changes.is_empty() {
+        return Ok(row.cloned());
+    }
+    let Some(mut fields) = row.and_then(|r| r.as_object()).cloned() else {
+        return Err(AppError::Validation(
+            "The finding has no category data to change".to_string(),
+        ));
+    };
+    for (name, value) in changes {
+        if name == "finding_id" || !fields.contains_key(name) {
+            return Err(AppError::Validation(format!(
+                "Unknown {table} field '{name}'"
+            )));
+        }
+        fields.insert(name.clone(), value.clone());
+    }
+    Ok(Some(serde_json::Value::Object(fields)))
+}
+
+/// Whether a reassignment changes the fingerprints of the tool's findings.
+fn keyed_by_app_code(components: &[FingerprintComponent]) -> bool {
+    components.contains(&FingerprintComponent::AppCode)
+}
+
+/// Recompute the fingerprints of `ids` with the components `components_for`
+/// gives for their tool and category; findings without components keep
+/// theirs. Returns the number of fingerprints changed.
+async fn refingerprint<'a>(
+    conn: &mut PgConnection,
+    ids: &[Uuid],
+    components_for: impl Fn(&str, &FindingCategory) -> Option<&'a [FingerprintComponent]>,
+) -> Result<usize, AppError> {
+    let rows = sqlx::query_as::<_, StoredInputs>(
+        r#"
+        SELECT f.id, f.source_tool, f.finding_category,
+               COALESCE(a.app_code, '') AS app_code,
+               COALESCE(s.file_path, '') AS file_path,
+               COALESCE(s.rule_id, '') AS rule_id,
+               COALESCE(s.branch, '') AS branch,
+               COALESCE(s.line_number_start::text, '') AS line_number,
+               COALESCE(sc.package_name, '') AS package_name,
+               COALESCE(sc.package_version, '') AS package_version,
+               COALESCE(f.cve_ids->>0, '') AS cve_id,
+               COALESCE(d.target_url, '') AS target_url,
+               COALESCE(d.http_method, '') AS http_method,
+               COALESCE(d.parameter, '') AS parameter,
+               COALESCE(f.cwe_ids->>0, '') AS cwe_id,
+               COALESCE(m.affected_asset, '') AS affected_asset,
+               f.title
+        FROM findings f
+        LEFT JOIN applications a ON a.id = f.application_id
+        LEFT JOIN finding_sast s ON s.finding_id = f.id
+        LEFT JOIN finding_sca sc ON sc.finding_id = f.id
+        LEFT JOIN finding_dast d ON d.finding_id = f.id
+        LEFT JOIN finding_manual m ON m.finding_id = f.id
+        WHERE f.id = ANY($1)
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut changed_ids = Vec::new();
+    let mut fingerprints = Vec::new();
+    for row in rows {
+        let Some(components) = components_for(&row.source_tool, &row.finding_category) else {
+            continue;
+        };
+        let (id, category) = (row.id, row.finding_category.clone());
+        changed_ids.push(id);
+        fingerprints.push(fingerprint::compute_with(
+            &category,
+            components,
+            &row.into(),
+        ));
+    }
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE findings f SET fingerprint = u.fingerprint, updated_at = NOW()
+        FROM UNNEST($1::uuid[], $2::text[]) AS u(id, fingerprint)
+        WHERE f.id = u.id AND f.fingerprint <> u.fingerprint
+        "#,
+    )
+    .bind(&changed_ids)
+    .bind(&fingerprints)
+    .execute(&mut *conn)
+    .await?;
+    Ok(updated.rows_affected() as usize)
+}
+
+async fn fingerprint_profiles(pool: &PgPool) -> Result<FingerprintProfiles, AppError> {
+    Ok(config_cache::global()
+        .settings(pool)
+        .await?
+        .fingerprint_profiles())
+}
+
+/// Split a finding into one finding per part.
+///
+/// The original takes the first part's application, title and category
+/// changes; each further part becomes a new finding.
+pub async fn split(
+    pool: &PgPool,
+    finding_id: Uuid,
+    input: &SplitRequest,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<FindingSplit, AppError> {
+    let justification = input.justification.trim();
+    if justification.is_empty() {
+        return Err(AppError::Validation(
+            "A justification is required to split a finding".to_string(),
+        ));
+    }
+    if !(2..=MAX_SPLIT_PARTS).contains(&input.parts.len()) {
+        return Err(AppError::Validation(format!(
+            "A split needs between 2 and {MAX_SPLIT_PARTS} parts"
+        )));
+    }
+    if input
+        .parts
+        .iter()
+        .any(|p| p.title.as_deref().is_some_and(|t| t.trim().is_empty()))
+    {
+        return Err(AppError::Validation(
+            "Part titles cannot be empty".to_string(),
+        ));
+    }
+    let profiles = fingerprint_profiles(pool).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let original = sqlx::query_as::<_, SplitFinding>(
+        "SELECT finding_category, application_id, title, archived_at FROM findings WHERE id = $1 FOR UPDATE",
+    )
+    .bind(finding_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Finding {finding_id} not found")))?;
+    if original.archived_at.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Finding {finding_id} is archived"
+        )));
+    }
+
+    let application_ids: Vec<Uuid> = input.parts.iter().map(|p| p.application_id).collect();
+    let known = sqlx::query_scalar::<_, Uuid>("SELECT id FROM applications WHERE id = ANY($1)")
+        .bind(&application_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+    if let Some(missing) = application_ids.iter().find(|id| !known.contains(id)) {
+        return Err(AppError::Validation(format!(
+            "Application {missing} not found"
+        )));
+    }
+
+    let table = category_table(&original.finding_category);
+    let original_row = category_row(&mut tx, table, finding_id).await?;
+    let part_rows = input
+        .parts
+        .iter()
+        .map(|p| apply_category_changes(table, original_row.as_ref(), &p.category_data))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Further parts, copied from the original before it changes
+    let mut split_ids = Vec::with_capacity(input.parts.len() - 1);
+    for (part, row) in input.parts.iter().zip(&part_rows).skip(1) {
+        let id = sqlx::query_scalar::<_, Uuid>(&format!(
+            r#"
+            INSERT INTO findings ({COPIED_COLUMNS}, title, application_id, fingerprint)
+            SELECT {COPIED_COLUMNS}, COALESCE($2, title), $3, fingerprint
+            FROM findings WHERE id = $1
+            RETURNING id
+            "#
+        ))
+        .bind(finding_id)
+        .bind(part.title.as_deref().map(str::trim))
+        .bind(part.application_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        replace_category_row(&mut tx, table, id, row.as_ref()).await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name, justification, created_at)
+            SELECT $2, action, field_changed, old_value, new_value, actor_id, actor_name, justification, created_at
+            FROM finding_history WHERE finding_id = $1
+            "#,
+        )
+        .bind(finding_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        split_ids.push(id);
+    }
+    refingerprint(&mut tx, &split_ids, |tool, category| {
+        Some(
+            profiles
+                .for_tool(tool)
+                .unwrap_or_else(|| fingerprint::default_components(category)),
+        )
+    })
+    .await?;
+
+    // The original takes the first part
+    let first = &input.parts[0];
+    let title = first.title.as_deref().map(str::trim);
+    sqlx::query(
+        "UPDATE findings SET application_id = $2, title = COALESCE($3, title), updated_at = NOW() WHERE id = $1",
+    )
+    .bind(finding_id)
+    .bind(first.application_id)
+    .bind(title)
+    .execute(&mut *tx)
+    .await?;
+    if !first.category_data.is_empty() {
+        replace_category_row(&mut tx, table, finding_id, part_rows[0].as_ref()).await?;
+    }
+
+    let mut changes = vec![(
+        "application_id",
+        original.application_id.map(|id| id.to_string()),
+        Some(first.application_id.to_string()),
+    )];
+    if let Some(title) = title.filter(|t| *t != original.title) {
+        changes.push((
+            "title",
+            Some(original.title.clone()),
+            Some(title.to_string()),
+        ));
+    }
+    for (field, old_value, new_value) in changes.into_iter().filter(|(_, old, new)| old != new) {
+        sqlx::query(
+            r#"
+            INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name, justification)
+            VALUES ($1, 'split', $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(finding_id)
+        .bind(field)
+        .bind(old_value)
+        .bind(new_value)
+        .bind(actor_id)
+        .bind(actor_name)
+        .bind(justification)
+        .execute(&mut *tx)
+        .await?;
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name, justification)
+        SELECT $1, 'split', 'split_into', NULL, id::text, $3, $4, $5 FROM UNNEST($2::uuid[]) AS id
+        UNION ALL
+        SELECT id, 'split', 'split_from', NULL, $1::text, $3, $4, $5 FROM UNNEST($2::uuid[]) AS id
+        "#,
+    )
+    .bind(finding_id)
+    .bind(&split_ids)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(justification)
+    .execute(&mut *tx)
+    .await?;
+
+    let split = sqlx::query_as::<_, FindingSplit>(
+        r#"
+        INSERT INTO finding_splits (original_finding_id, split_finding_ids, original_application_id, justification, split_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(finding_id)
+    .bind(&split_ids)
+    .bind(original.application_id)
+    .bind(justification)
+    .bind(actor_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('finding', $1, 'finding_split', $2, $3, $4)
+        "#,
+    )
+    .bind(finding_id)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({
+        "split_id": split.id,
+        "split_finding_ids": split_ids,
+        "application_ids": application_ids,
+        "justification": justification,
+    }))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    tracing::info!(split_id = %split.id, %finding_id, parts = input.parts.len(), "Finding split");
+    Ok(split)
+}
+
+/// Splits the finding took part in, as original or part, newest first.
+pub async fn list_splits(pool: &PgPool, finding_id: Uuid) -> Result<Vec<FindingSplit>, AppError> {
+    let splits = sqlx::query_as::<_, FindingSplit>(
+        r#"
+        SELECT * FROM finding_splits
+        WHERE original_finding_id = $1 OR split_finding_ids @> ARRAY[$1]
+        ORDER BY split_at DESC
+        "#,
+    )
+    .bind(finding_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(splits)
+}
+
+/// Move findings of one application to another.
+pub async fn bulk_reassign(
+    pool: &PgPool,
+    input: &BulkReassign,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<BulkResult, AppError> {
+    let justification = input.justification.trim();
+    if justification.is_empty() {
+        return Err(AppError::Validation(
+            "A justification is required to reassign findings".to_string(),
+        ));
+    }
+    let (from, to) = (input.from_application_id, input.to_application_id);
+    if from == to {
+        return Err(AppError::Validation(
+            "Findings are already in that application".to_string(),
+        ));
+    }
+    let app_codes: HashMap<Uuid, String> = sqlx::query_as::<_, (Uuid, String)>(
+        "SELECT id, app_code FROM applications WHERE id = ANY($1)",
+    )
+    .bind(vec![from, to])
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+    for id in [from, to] {
+        if !app_codes.contains_key(&id) {
+            return Err(AppError::NotFound(format!("Application {id} not found")));
+        }
+    }
+    let profiles = fingerprint_profiles(pool).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let moved = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        UPDATE findings SET application_id = $2, updated_at = NOW()
+        WHERE application_id = $1 AND (cardinality($3::uuid[]) = 0 OR id = ANY($3))
+        RETURNING id
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .bind(&input.finding_ids)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO finding_history (finding_id, action, field_changed, old_value, new_value, actor_id, actor_name, justification)
+        SELECT id, 'reassigned', 'application_id', $2::text, $3::text, $4, $5, $6
+        FROM UNNEST($1::uuid[]) AS id
+        "#,
+    )
+    .bind(&moved)
+    .bind(from)
+    .bind(to)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(justification)
+    .execute(&mut *tx)
+    .await?;
+
+    let refingerprinted = refingerprint(&mut tx, &moved, |tool, _| {
+        profiles.for_tool(tool).filter(|c| keyed_by_app_code(c))
+    })
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('finding', NULL, 'bulk_reassign', $1, $2, $3)
+        "#,
+    )
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({
+        "from_application": app_codes[&from],
+        "to_application": app_codes[&to],
+        "finding_ids": input.finding_ids,
+        "reassigned": moved.len(),
+        "refingerprinted": refingerprinted,
+        "justification": justification,
+    }))
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(BulkResult {
+        updated: moved.len(),
+        total: if input.finding_ids.is_empty() {
+            moved.len()
+        } else {
+            input.finding_ids.len()
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dast_row() -> serde_json::Value {
+        serde_json::json!({
+            "finding_id": Uuid::nil(),
+            "target_url": "https://shared.example.com/login",
+            "http_method": "POST",
+            "parameter": "user",
+        })
+    }
+
+    fn changes(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn category_changes_apply_to_known_fields_only() {
+        let row = dast_row();
+        let changed = apply_category_changes(
+            "finding_dast",
+            Some(&row),
+            &changes(serde_json::json!({ "target_url": "https://app2.example.com/login" })),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(changed["target_url"], "https://app2.example.com/login");
+        assert_eq!(changed["parameter"], "user");
+
+        for bad in [
+            serde_json::json!({ "package_name": "log4j-core" }),
+            serde_json::json!({ "finding_id": Uuid::new_v4() }),
+        ] {
+            assert!(matches!(
+                apply_category_changes("finding_dast", Some(&row), &changes(bad)),
+                Err(AppError::Validation(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parts_without_changes_copy_the_original_row() {
+        let row = dast_row();
+        let empty = serde_json::Map::new();
+        assert_eq!(
+            apply_category_changes("finding_dast", Some(&row), &empty).unwrap(),
+            Some(row)
+        );
+        assert_eq!(
+            apply_category_changes("finding_dast", None, &empty).unwrap(),
+            None
+        );
+        assert!(apply_category_changes(
+            "finding_dast",
+            None,
+            &changes(serde_json::json!({ "parameter": "id" }))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn only_app_code_profiles_are_refingerprinted_on_reassign() {
+        use FingerprintComponent::*;
+        assert!(keyed_by_app_code(&[AppCode, TargetUrl]));
+        assert!(!keyed_by_app_code(&[TargetUrl, HttpMethod, Parameter]));
+    }
+}
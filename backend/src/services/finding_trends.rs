@@ -0,0 +1,405 @@
+//! Findings trend series for the dashboard.
+//!
+//! A background task records one row per day, severity, and category in
+//! `finding_daily_snapshots`: findings opened and closed that day plus the
+//! open total at capture time. The trends endpoint rolls those rows up by
+//! day, week, or month so the dashboard can show direction over time.
+
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::errors::AppError;
+use crate::models::finding::{FindingCategory, SeverityLevel};
+
+/// Severities in display order.
+const SEVERITIES: [SeverityLevel; 5] = [
+    SeverityLevel::Critical,
+    SeverityLevel::High,
+    SeverityLevel::Medium,
+    SeverityLevel::Low,
+    SeverityLevel::Info,
+];
+
+/// Categories in display order.
+const CATEGORIES: [FindingCategory; 4] = [
+    FindingCategory::Sast,
+    FindingCategory::Sca,
+    FindingCategory::Dast,
+    FindingCategory::Manual,
+];
+
+/// Bucket size for trend points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TrendInterval {
+    Day,
+    #[default]
+    Week,
+    Month,
+}
+
+impl TrendInterval {
+    /// Field name passed to PostgreSQL `date_trunc`.
+    fn trunc_field(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    /// Window shown when no start date is given.
+    pub fn default_window(self) -> Duration {
+        match self {
+            Self::Day => Duration::days(30),
+            Self::Week => Duration::weeks(12),
+            Self::Month => Duration::days(365),
+        }
+    }
+}
+
+/// Query parameters for the trends endpoint.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TrendParams {
+    /// Bucket size (defaults to `week`).
+    #[serde(default)]
+    pub interval: TrendInterval,
+    /// First day included (defaults to one default window before `to`).
+    pub from: Option<NaiveDate>,
+    /// Last day included (defaults to today, UTC).
+    pub to: Option<NaiveDate>,
+}
+
+impl TrendParams {
+    /// Resolve the inclusive date range, filling in defaults relative to `today`.
+    pub fn window(&self, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), AppError> {
+        let to = self.to.unwrap_or(today);
+        let from = self
+            .from
+            .unwrap_or_else(|| to - self.interval.default_window());
+        if to < from {
+            return Err(AppError::Validation(
+                "'to' must not be earlier than 'from'".to_string(),
+            ));
+        }
+        Ok((from, to))
+    }
+}
+
+/// Opened, closed, and open counts for one bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, ToSchema)]
+pub struct TrendCounts {
+    /// Findings first seen during the bucket.
+    pub opened: i64,
+    /// Findings closed, invalidated, or marked false positive during the bucket.
+    pub closed: i64,
+    /// `opened - closed`.
+    pub net_change: i64,
+    /// Findings open at the last snapshot in the bucket.
+    pub open_total: i64,
+}
+
+impl TrendCounts {
+    fn add(&mut self, row: &TrendRow) {
+        self.opened += row.opened;
+        self.closed += row.closed;
+        self.net_change = self.opened - self.closed;
+        self.open_total += row.open_total;
+    }
+}
+
+/// Counts for one severity within a bucket.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SeverityTrend {
+    pub severity: SeverityLevel,
+    #[serde(flatten)]
+    pub counts: TrendCounts,
+}
+
+/// Counts for one category within a bucket.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryTrend {
+    pub category: FindingCategory,
+    #[serde(flatten)]
+    pub counts: TrendCounts,
+}
+
+/// One bucket of the series.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrendPoint {
+    /// First day of the bucket; weeks start on Monday.
+    pub period_start: NaiveDate,
+    #[serde(flatten)]
+    pub totals: TrendCounts,
+    pub by_severity: Vec<SeverityTrend>,
+    pub by_category: Vec<CategoryTrend>,
+}
+
+/// Trend series over the requested window.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FindingTrends {
+    pub interval: TrendInterval,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub points: Vec<TrendPoint>,
+}
+
+/// Snapshot rows rolled up to one bucket, severity, and category.
+#[derive(Debug, sqlx::FromRow)]
+struct TrendRow {
+    period_start: NaiveDate,
+    normalized_severity: SeverityLevel,
+    finding_category: FindingCategory,
+    opened: i64,
+    closed: i64,
+    open_total: i64,
+}
+
+/// Load the trend series for the requested window.
+pub async fn get_trends(pool: &PgPool, params: &TrendParams) -> Result<FindingTrends, AppError> {
+    let (from, to) = params.window(Utc::now().date_naive())?;
+
+    let rows = sqlx::query_as::<_, TrendRow>(
+        r#"
+        SELECT
+            date_trunc($1, snapshot_date::timestamp)::date AS period_start,
+            normalized_severity,
+            finding_category,
+            SUM(opened)::bigint AS opened,
+            SUM(closed)::bigint AS closed,
+            (ARRAY_AGG(open_total ORDER BY snapshot_date DESC))[1]::bigint AS open_total
+        FROM finding_daily_snapshots
+        WHERE snapshot_date BETWEEN $2 AND $3
+        GROUP BY 1, 2, 3
+        ORDER BY 1
+        "#,
+    )
+    .bind(params.interval.trunc_field())
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(FindingTrends {
+        interval: params.interval,
+        from,
+        to,
+        points: fold_points(&rows),
+    })
+}
+
+/// Group rows (ordered by bucket) into points with per-severity and
+/// per-category breakdowns.
+fn fold_points(rows: &[TrendRow]) -> Vec<TrendPoint> {
+    let mut points: Vec<TrendPoint> = Vec::new();
+    for row in rows {
+        if points.last().map(|p| p.period_start) != Some(row.period_start) {
+            points.push(empty_point(row.period_start));
+        }
+        let point = points.last_mut().expect("point pushed above");
+
+        point.totals.add(row);
+        if let Some(s) = point
+            .by_severity
+            .iter_mut()
+            .find(|s| s.severity == row.normalized_severity)
+        {
+            s.counts.add(row);
+        }
+        if let Some(c) = point
+            .by_category
+            .iter_mut()
+            .find(|c| c.category == row.finding_category)
+        {
+            c.counts.add(row);
+        }
+    }
+    points
+}
+
+/// A bucket with zeroed counts for every severity and category, so each
+/// point carries the same series.
+fn empty_point(period_start: NaiveDate) -> TrendPoint {
+    TrendPoint {
+        period_start,
+        totals: TrendCounts::default(),
+        by_severity: SEVERITIES
+            .into_iter()
+            .map(|severity| SeverityTrend {
+                severity,
+                counts: TrendCounts::default(),
+            })
+            .collect(),
+        by_category: CATEGORIES
+            .into_iter()
+            .map(|category| CategoryTrend {
+                category,
+                counts: TrendCounts::default(),
+            })
+            .collect(),
+    }
+}
+
+/// Record the snapshot for `date`, replacing any earlier capture of that day.
+pub async fn capture_snapshot(pool: &PgPool, date: NaiveDate) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO finding_daily_snapshots
+            (snapshot_date, normalized_severity, finding_category, opened, closed, open_total, captured_at)
+        SELECT
+            $1,
+            s.severity,
+            c.category,
+            COUNT(f.id) FILTER (WHERE f.first_seen >= $1::date AND f.first_seen < $1::date + 1),
+            COUNT(f.id) FILTER (
+                WHERE f.status IN ('Closed', 'Invalidated', 'False_Positive')
+                  AND f.status_changed_at >= $1::date AND f.status_changed_at < $1::date + 1
+            ),
+            COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')),
+            NOW()
+        FROM unnest(enum_range(NULL::severity_level)) AS s(severity)
+        CROSS JOIN unnest(enum_range(NULL::finding_category)) AS c(category)
+        LEFT JOIN findings f
+            ON f.normalized_severity = s.severity AND f.finding_category = c.category
+        GROUP BY s.severity, c.category
+        ON CONFLICT (snapshot_date, normalized_severity, finding_category) DO UPDATE SET
+            opened = EXCLUDED.opened,
+            closed = EXCLUDED.closed,
+            open_total = EXCLUDED.open_total,
+            captured_at = EXCLUDED.captured_at
+        "#,
+    )
+    .bind(date)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Spawn the background task that refreshes today's snapshot every `interval`.
+pub fn spawn_snapshotter(pool: PgPool, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = capture_snapshot(&pool, Utc::now().date_naive()).await {
+                tracing::error!(error = %e, "Finding trend snapshot failed");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn row(
+        period_start: NaiveDate,
+        severity: SeverityLevel,
+        category: FindingCategory,
+        opened: i64,
+        closed: i64,
+        open_total: i64,
+    ) -> TrendRow {
+        TrendRow {
+            period_start,
+            normalized_severity: severity,
+            finding_category: category,
+            opened,
+            closed,
+            open_total,
+        }
+    }
+
+    #[test]
+    fn window_defaults_to_interval_length_ending_today() {
+        let today = date(2026, 3, 31);
+        let params = TrendParams::default();
+        assert_eq!(params.window(today).unwrap(), (date(2026, 1, 6), today));
+
+        let monthly = TrendParams {
+            interval: TrendInterval::Month,
+            to: Some(date(2025, 12, 31)),
+            ..Default::default()
+        };
+        assert_eq!(
+            monthly.window(today).unwrap(),
+            (date(2024, 12, 31), date(2025, 12, 31))
+        );
+    }
+
+    #[test]
+    fn window_rejects_inverted_range() {
+        let params = TrendParams {
+            from: Some(date(2026, 3, 2)),
+            to: Some(date(2026, 3, 1)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            params.window(date(2026, 3, 31)),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn folds_rows_into_points_with_breakdowns() {
+        let w1 = date(2026, 3, 2);
+        let w2 = date(2026, 3, 9);
+        let rows = vec![
+            row(w1, SeverityLevel::Critical, FindingCategory::Sast, 3, 1, 5),
+            row(w1, SeverityLevel::Critical, FindingCategory::Sca, 2, 0, 4),
+            row(w1, SeverityLevel::Low, FindingCategory::Sca, 1, 4, 2),
+            row(w2, SeverityLevel::High, FindingCategory::Dast, 0, 2, 7),
+        ];
+
+        let points = fold_points(&rows);
+        assert_eq!(points.len(), 2);
+
+        let first = &points[0];
+        assert_eq!(first.period_start, w1);
+        assert_eq!(
+            first.totals,
+            TrendCounts {
+                opened: 6,
+                closed: 5,
+                net_change: 1,
+                open_total: 11,
+            }
+        );
+        assert_eq!(first.by_severity.len(), 5);
+        assert_eq!(first.by_severity[0].severity, SeverityLevel::Critical);
+        assert_eq!(first.by_severity[0].counts.opened, 5);
+        assert_eq!(first.by_severity[0].counts.open_total, 9);
+        assert_eq!(first.by_severity[1].counts, TrendCounts::default());
+        assert_eq!(first.by_category[1].category, FindingCategory::Sca);
+        assert_eq!(first.by_category[1].counts.net_change, -1);
+
+        let second = &points[1];
+        assert_eq!(second.totals.net_change, -2);
+        assert_eq!(second.by_category[2].counts.open_total, 7);
+    }
+
+    #[test]
+    fn point_serializes_counts_inline() {
+        let rows = vec![row(
+            date(2026, 3, 1),
+            SeverityLevel::Medium,
+            FindingCategory::Dast,
+            1,
+            0,
+            1,
+        )];
+        let json = serde_json::to_value(&fold_points(&rows)[0]).unwrap();
+        assert_eq!(json["period_start"], "2026-03-01");
+        assert_eq!(json["opened"], 1);
+        assert_eq!(json["by_severity"][2]["severity"], "Medium");
+        assert_eq!(json["by_severity"][2]["open_total"], 1);
+        assert_eq!(json["by_category"][2]["category"], "DAST");
+    }
+}
@@ -3,8 +3,22 @@
 //! Each category uses a deterministic hash of identifying fields that remain
 //! stable across re-scans, excluding volatile fields like line numbers (SAST)
 //! or CWE IDs (DAST) that may change without the underlying issue changing.
+//!
+//! Platform admins can override the components per source tool
+//! (`fingerprint_components` setting, see [`FingerprintProfiles`]). A profile
+//! listing exactly the default components of a category reproduces the
+//! default fingerprint; any other profile changes the fingerprints of the
+//! tool's findings from the next ingestion on.
+
+use std::collections::BTreeMap;
 
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use crate::errors::AppError;
+use crate::models::finding::{CreateFinding, FindingCategory};
+use crate::services::finding::CategoryData;
 
 /// Compute a SAST finding fingerprint.
 ///
@@ -44,6 +58,208 @@ pub fn compute_dast(
     ))
 }
 
+/// Compute a manual (pentest or bug bounty) finding fingerprint.
+///
+/// Inputs: app_code, affected_asset, title.
+/// Reports carry no stable rule id, so the title stands in for one: the same
+/// issue reported again against the same asset matches the earlier entry.
+pub fn compute_manual(app_code: &str, affected_asset: &str, title: &str) -> String {
+    hash(&format!("MANUAL:{app_code}:{affected_asset}:{title}"))
+}
+
+/// A field that can make up a fingerprint.
+///
+/// Declaration order is the order components are hashed in, whatever the
+/// order of a profile's list.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FingerprintComponent {
+    /// Resolved application code; applies to every category.
+    AppCode,
+    FilePath,
+    RuleId,
+    Branch,
+    /// First line of a SAST finding.
+    LineNumber,
+    PackageName,
+    PackageVersion,
+    /// First CVE of an SCA finding.
+    CveId,
+    TargetUrl,
+    HttpMethod,
+    Parameter,
+    /// First CWE of a DAST finding.
+    CweId,
+    AffectedAsset,
+    /// Title of a manual finding.
+    Title,
+}
+
+impl FingerprintComponent {
+    /// Category the component belongs to; `None` for components of every
+    /// category.
+    pub fn category(self) -> Option<FindingCategory> {
+        match self {
+            Self::AppCode => None,
+            Self::FilePath | Self::RuleId | Self::Branch | Self::LineNumber => {
+                Some(FindingCategory::Sast)
+            }
+            Self::PackageName | Self::PackageVersion | Self::CveId => Some(FindingCategory::Sca),
+            Self::TargetUrl | Self::HttpMethod | Self::Parameter | Self::CweId => {
+                Some(FindingCategory::Dast)
+            }
+            Self::AffectedAsset | Self::Title => Some(FindingCategory::Manual),
+        }
+    }
+}
+
+/// Fingerprint components per source tool; tools without an entry use the
+/// built-in fingerprints.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FingerprintProfiles(pub BTreeMap<String, Vec<FingerprintComponent>>);
+
+impl FingerprintProfiles {
+    /// Components configured for `source_tool`.
+    pub fn for_tool(&self, source_tool: &str) -> Option<&[FingerprintComponent]> {
+        self.0.get(source_tool).map(Vec::as_slice)
+    }
+}
+
+/// Check that a profile is usable: not empty, and not mixing components of
+/// different categories.
+pub fn validate_components(components: &[FingerprintComponent]) -> Result<(), AppError> {
+    if components.is_empty() {
+        return Err(AppError::Validation(
+            "A fingerprint needs at least one component".to_string(),
+        ));
+    }
+    let mut categories = components.iter().filter_map(|c| c.category());
+    if let Some(first) = categories.next() {
+        if categories.any(|c| c != first) {
+            return Err(AppError::Validation(
+                "Fingerprint components must belong to a single finding category".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Field values a fingerprint can be built from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FingerprintInputs {
+    pub app_code: String,
+    pub file_path: String,
+    pub rule_id: String,
+    pub branch: String,
+    pub line_number: String,
+    pub package_name: String,
+    pub package_version: String,
+    pub cve_id: String,
+    pub target_url: String,
+    pub http_method: String,
+    pub parameter: String,
+    pub cwe_id: String,
+    pub affected_asset: String,
+    pub title: String,
+}
+
+impl FingerprintInputs {
+    /// Inputs of a parsed finding resolved to `app_code`.
+    pub fn from_finding(app_code: &str, core: &CreateFinding, category: &CategoryData) -> Self {
+        let mut inputs = Self {
+            app_code: app_code.to_string(),
+            ..Default::default()
+        };
+        match category {
+            CategoryData::Sast(sast) => {
+                inputs.file_path = sast.file_path.clone();
+                inputs.rule_id = sast.rule_id.clone();
+                inputs.branch = sast.branch.clone().unwrap_or_default();
+                inputs.line_number = sast
+                    .line_number_start
+                    .map(|n| n.to_string())
+                    .unwrap_or_default();
+            }
+            CategoryData::Sca(sca) => {
+                inputs.package_name = sca.package_name.clone();
+                inputs.package_version = sca.package_version.clone();
+                inputs.cve_id = core.cve_ids.first().cloned().unwrap_or_default();
+            }
+            CategoryData::Dast(dast) => {
+                inputs.target_url = dast.target_url.clone();
+                inputs.http_method = dast.http_method.clone().unwrap_or_default();
+                inputs.parameter = dast.parameter.clone().unwrap_or_default();
+                inputs.cwe_id = core.cwe_ids.first().cloned().unwrap_or_default();
+            }
+            CategoryData::Manual(manual) => {
+                inputs.affected_asset = manual.affected_asset.clone();
+                inputs.title = core.title.clone();
+            }
+        }
+        inputs
+    }
+
+    fn value(&self, component: FingerprintComponent) -> &str {
+        match component {
+            FingerprintComponent::AppCode => &self.app_code,
+            FingerprintComponent::FilePath => &self.file_path,
+            FingerprintComponent::RuleId => &self.rule_id,
+            FingerprintComponent::Branch => &self.branch,
+            FingerprintComponent::LineNumber => &self.line_number,
+            FingerprintComponent::PackageName => &self.package_name,
+            FingerprintComponent::PackageVersion => &self.package_version,
+            FingerprintComponent::CveId => &self.cve_id,
+            FingerprintComponent::TargetUrl => &self.target_url,
+            FingerprintComponent::HttpMethod => &self.http_method,
+            FingerprintComponent::Parameter => &self.parameter,
+            FingerprintComponent::CweId => &self.cwe_id,
+            FingerprintComponent::AffectedAsset => &self.affected_asset,
+            FingerprintComponent::Title => &self.title,
+        }
+    }
+}
+
+/// Components of the built-in fingerprint of `category`.
+pub fn default_components(category: &FindingCategory) -> &'static [FingerprintComponent] {
+    use FingerprintComponent::*;
+    match category {
+        FindingCategory::Sast => &[AppCode, FilePath, RuleId, Branch],
+        FindingCategory::Sca => &[AppCode, PackageName, PackageVersion, CveId],
+        FindingCategory::Dast => &[AppCode, TargetUrl, HttpMethod, Parameter],
+        FindingCategory::Manual => &[AppCode, AffectedAsset, Title],
+    }
+}
+
+/// Compute a fingerprint from the configured `components` of a finding of
+/// `category`.
+///
+/// Uses the same layout as the built-in fingerprints, so the default
+/// components give the default fingerprint.
+pub fn compute_with(
+    category: &FindingCategory,
+    components: &[FingerprintComponent],
+    inputs: &FingerprintInputs,
+) -> String {
+    let mut components = components.to_vec();
+    components.sort();
+    components.dedup();
+    let prefix = match category {
+        FindingCategory::Sast => "SAST",
+        FindingCategory::Sca => "SCA",
+        FindingCategory::Dast => "DAST",
+        FindingCategory::Manual => "MANUAL",
+    };
+    let mut input = prefix.to_string();
+    for component in components {
+        input.push(':');
+        input.push_str(inputs.value(component));
+    }
+    hash(&input)
+}
+
 /// SHA-256 hash a string and return hex-encoded digest.
 fn hash(input: &str) -> String {
     let mut hasher = Sha256::new();
@@ -111,6 +327,109 @@ mod tests {
         assert!(fp.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    fn sast_inputs(line: i32) -> FingerprintInputs {
+        FingerprintInputs {
+            app_code: "APP1".to_string(),
+            file_path: "src/main.rs".to_string(),
+            rule_id: "sqli-rule".to_string(),
+            branch: "main".to_string(),
+            line_number: line.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_components_reproduce_default_fingerprint() {
+        use FingerprintComponent::*;
+        let components = [RuleId, AppCode, Branch, FilePath];
+        assert_eq!(
+            compute_with(&FindingCategory::Sast, &components, &sast_inputs(10)),
+            compute_sast("APP1", "src/main.rs", "sqli-rule", "main")
+        );
+    }
+
+    #[test]
+    fn default_components_of_every_category_reproduce_built_in_fingerprints() {
+        let sca = FingerprintInputs {
+            app_code: "APP1".to_string(),
+            package_name: "log4j-core".to_string(),
+            package_version: "2.14.1".to_string(),
+            cve_id: "CVE-2021-44228".to_string(),
+            ..Default::default()
+        };
+        let dast = FingerprintInputs {
+            app_code: "APP1".to_string(),
+            target_url: "https://app1.example.com/login".to_string(),
+            http_method: "POST".to_string(),
+            parameter: "user".to_string(),
+            ..Default::default()
+        };
+        let manual = FingerprintInputs {
+            app_code: "APP1".to_string(),
+            affected_asset: "vpn.example.com".to_string(),
+            title: "Weak TLS configuration".to_string(),
+            ..Default::default()
+        };
+        let (sast_cat, sca_cat, dast_cat, manual_cat) = (
+            FindingCategory::Sast,
+            FindingCategory::Sca,
+            FindingCategory::Dast,
+            FindingCategory::Manual,
+        );
+
+        assert_eq!(
+            compute_with(&sast_cat, default_components(&sast_cat), &sast_inputs(10)),
+            compute_sast("APP1", "src/main.rs", "sqli-rule", "main")
+        );
+        assert_eq!(
+            compute_with(&sca_cat, default_components(&sca_cat), &sca),
+            compute_sca("APP1", "log4j-core", "2.14.1", "CVE-2021-44228")
+        );
+        assert_eq!(
+            compute_with(&dast_cat, default_components(&dast_cat), &dast),
+            compute_dast("APP1", "https://app1.example.com/login", "POST", "user")
+        );
+        assert_eq!(
+            compute_with(&manual_cat, default_components(&manual_cat), &manual),
+            compute_manual("APP1", "vpn.example.com", "Weak TLS configuration")
+        );
+    }
+
+    #[test]
+    fn line_number_component_separates_findings_by_line() {
+        use FingerprintComponent::*;
+        let without = [AppCode, FilePath, RuleId];
+        let with = [AppCode, FilePath, RuleId, LineNumber];
+        let sast = FindingCategory::Sast;
+        assert_eq!(
+            compute_with(&sast, &without, &sast_inputs(10)),
+            compute_with(&sast, &without, &sast_inputs(42))
+        );
+        assert_ne!(
+            compute_with(&sast, &with, &sast_inputs(10)),
+            compute_with(&sast, &with, &sast_inputs(42))
+        );
+    }
+
+    #[test]
+    fn profiles_must_be_non_empty_and_single_category() {
+        use FingerprintComponent::*;
+        assert!(validate_components(&[]).is_err());
+        assert!(validate_components(&[AppCode]).is_ok());
+        assert!(validate_components(&[AppCode, TargetUrl, Parameter]).is_ok());
+        assert!(validate_components(&[FilePath, PackageName]).is_err());
+    }
+
+    #[test]
+    fn profiles_deserialize_from_setting() {
+        let profiles: FingerprintProfiles = serde_json::from_value(serde_json::json!({
+            "SonarQube": ["app_code", "file_path", "rule_id", "line_number"]
+        }))
+        .unwrap();
+        assert_eq!(profiles.for_tool("SonarQube").map(<[_]>::len), Some(4));
+        assert!(profiles.for_tool("Tenable WAS").is_none());
+    }
+
     #[test]
     fn cross_category_no_collision() {
         // Same identifying fields but different category prefix
@@ -0,0 +1,131 @@
+//! Administration of per-tool fingerprint components.
+//!
+//! Profiles are stored in the `fingerprint_components` setting as a map from
+//! source tool to component list and read by ingestion through the
+//! configuration cache. Changing a profile changes the fingerprints of that
+//! tool's findings from the next ingestion on, so findings ingested before
+//! the change may be reported again as new.
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::services::config_cache;
+use crate::services::fingerprint::{self, FingerprintComponent, FingerprintProfiles};
+
+const SETTING_KEY: &str = "fingerprint_components";
+
+/// Fingerprint components configured for a source tool.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FingerprintProfile {
+    pub source_tool: String,
+    pub components: Vec<FingerprintComponent>,
+}
+
+/// Request body replacing a tool's fingerprint components.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetFingerprintProfile {
+    pub components: Vec<FingerprintComponent>,
+}
+
+async fn load(conn: &mut sqlx::PgConnection, lock: bool) -> Result<FingerprintProfiles, AppError> {
+    let sql = if lock {
+        "SELECT value FROM system_config WHERE key = $1 FOR UPDATE"
+    } else {
+        "SELECT value FROM system_config WHERE key = $1"
+    };
+    let value = sqlx::query_scalar::<_, serde_json::Value>(sql)
+        .bind(SETTING_KEY)
+        .fetch_optional(&mut *conn)
+        .await?;
+    Ok(value
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+async fn store(
+    conn: &mut sqlx::PgConnection,
+    profiles: &FingerprintProfiles,
+    actor_id: Uuid,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO system_config (key, value, description, updated_by)
+        VALUES ($1, $2, 'Fingerprint components per source tool', $3)
+        ON CONFLICT (key) DO UPDATE SET
+            value = EXCLUDED.value,
+            updated_by = EXCLUDED.updated_by,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(SETTING_KEY)
+    .bind(serde_json::to_value(profiles).unwrap_or_default())
+    .bind(actor_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// All configured profiles, by source tool.
+pub async fn list(pool: &PgPool) -> Result<Vec<FingerprintProfile>, AppError> {
+    let mut conn = pool.acquire().await?;
+    let profiles = load(&mut conn, false).await?;
+    Ok(profiles
+        .0
+        .into_iter()
+        .map(|(source_tool, components)| FingerprintProfile {
+            source_tool,
+            components,
+        })
+        .collect())
+}
+
+/// Replace the fingerprint components of `source_tool`.
+pub async fn set(
+    pool: &PgPool,
+    source_tool: &str,
+    input: &SetFingerprintProfile,
+    actor_id: Uuid,
+) -> Result<FingerprintProfile, AppError> {
+    if source_tool.trim().is_empty() {
+        return Err(AppError::Validation("Source tool is required".to_string()));
+    }
+    fingerprint::validate_components(&input.components)?;
+    let mut components = input.components.clone();
+    components.sort();
+    components.dedup();
+
+    let mut tx = pool.begin().await?;
+    let mut profiles = load(&mut tx, true).await?;
+    profiles
+        .0
+        .insert(source_tool.to_string(), components.clone());
+    store(&mut tx, &profiles, actor_id).await?;
+    tx.commit().await?;
+
+    config_cache::global().invalidate_settings().await;
+    tracing::info!(source_tool, ?components, "Fingerprint profile updated");
+    Ok(FingerprintProfile {
+        source_tool: source_tool.to_string(),
+        components,
+    })
+}
+
+/// Remove the profile of `source_tool`, restoring the built-in fingerprint.
+pub async fn remove(pool: &PgPool, source_tool: &str, actor_id: Uuid) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    let mut profiles = load(&mut tx, true).await?;
+    if profiles.0.remove(source_tool).is_none() {
+        return Err(AppError::NotFound(format!(
+            "No fingerprint profile for {source_tool}"
+        )));
+    }
+    store(&mut tx, &profiles, actor_id).await?;
+    tx.commit().await?;
+
+    config_cache::global().invalidate_settings().await;
+    tracing::info!(source_tool, "Fingerprint profile removed");
+    Ok(())
+}
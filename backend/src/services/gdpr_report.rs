@@ -0,0 +1,451 @@
+//! GDPR report for the DPO: findings on `is_gdpr_subject` applications whose
+//! weaknesses can expose or tamper with personal data, open risk
+//! acceptances, and remediation status per application.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{FindingStatus, SeverityLevel, SlaStatus};
+use crate::services::pdf_report::{ReportBlock, ReportDocument};
+
+/// CWEs describing disclosure of data to unauthorized parties.
+const EXPOSURE_CWES: &[&str] = &[
+    "CWE-200", "CWE-201", "CWE-209", "CWE-311", "CWE-312", "CWE-319", "CWE-327", "CWE-359",
+    "CWE-532", "CWE-538", "CWE-639", "CWE-862", "CWE-863",
+];
+
+/// CWEs describing injection flaws that can read or alter stored data.
+const INJECTION_CWES: &[&str] = &[
+    "CWE-22", "CWE-77", "CWE-78", "CWE-79", "CWE-89", "CWE-90", "CWE-91", "CWE-611", "CWE-643",
+    "CWE-917", "CWE-918", "CWE-943",
+];
+
+/// How a finding's weakness relates to personal-data processing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PersonalDataRisk {
+    Exposure,
+    Injection,
+}
+
+/// Classify a finding's CWEs into personal-data risk categories.
+pub fn classify_cwes(cwe_ids: &[String]) -> Vec<PersonalDataRisk> {
+    let mut risks = Vec::new();
+    if cwe_ids.iter().any(|c| EXPOSURE_CWES.contains(&c.as_str())) {
+        risks.push(PersonalDataRisk::Exposure);
+    }
+    if cwe_ids.iter().any(|c| INJECTION_CWES.contains(&c.as_str())) {
+        risks.push(PersonalDataRisk::Injection);
+    }
+    risks
+}
+
+/// Raw finding row returned by the report query.
+#[derive(Debug, sqlx::FromRow)]
+struct GdprFindingRow {
+    id: Uuid,
+    application_id: Uuid,
+    app_code: String,
+    title: String,
+    normalized_severity: SeverityLevel,
+    status: FindingStatus,
+    cwe_ids: serde_json::Value,
+    first_seen: DateTime<Utc>,
+    sla_due_date: Option<DateTime<Utc>>,
+    sla_status: Option<SlaStatus>,
+}
+
+/// A non-closed finding touching personal-data flows.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GdprFinding {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    pub app_code: String,
+    pub title: String,
+    pub normalized_severity: SeverityLevel,
+    pub status: FindingStatus,
+    pub cwe_ids: Vec<String>,
+    pub risks: Vec<PersonalDataRisk>,
+    pub first_seen: DateTime<Utc>,
+    pub sla_due_date: Option<DateTime<Utc>>,
+    pub sla_status: Option<SlaStatus>,
+}
+
+/// A finding currently in Risk_Accepted on an in-scope application.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct GdprRiskAcceptance {
+    pub finding_id: Uuid,
+    pub app_code: String,
+    pub title: String,
+    pub normalized_severity: SeverityLevel,
+    pub accepted_by: Option<String>,
+    pub accepted_at: DateTime<Utc>,
+    pub justification: Option<String>,
+}
+
+/// In-scope application row as loaded from the database.
+#[derive(Debug, sqlx::FromRow)]
+struct GdprApplicationRow {
+    id: Uuid,
+    app_name: String,
+    app_code: String,
+}
+
+/// Remediation status of personal-data findings for one application.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct GdprApplicationSummary {
+    pub application_id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    pub exposure_findings: i64,
+    pub injection_findings: i64,
+    /// New or Confirmed: not yet being worked on.
+    pub awaiting_remediation: i64,
+    /// In_Remediation or Deferred_Remediation.
+    pub in_remediation: i64,
+    /// Mitigated, pending verification.
+    pub mitigated: i64,
+    pub risk_accepted: i64,
+    pub sla_breached: i64,
+}
+
+/// Complete GDPR report.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GdprReport {
+    pub generated_at: DateTime<Utc>,
+    pub application_count: usize,
+    pub applications: Vec<GdprApplicationSummary>,
+    pub findings: Vec<GdprFinding>,
+    pub risk_acceptances: Vec<GdprRiskAcceptance>,
+}
+
+/// Build the GDPR report for active `is_gdpr_subject` applications.
+pub async fn build(pool: &PgPool) -> Result<GdprReport, AppError> {
+    let applications = sqlx::query_as::<_, GdprApplicationRow>(
+        r#"
+        SELECT id, app_name, app_code
+        FROM applications
+        WHERE is_gdpr_subject = true AND status = 'Active'
+        ORDER BY app_name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let personal_data_cwes: Vec<String> = EXPOSURE_CWES
+        .iter()
+        .chain(INJECTION_CWES)
+        .map(|c| c.to_string())
+        .collect();
+
+    let rows = sqlx::query_as::<_, GdprFindingRow>(
+        r#"
+        SELECT f.id, f.application_id, a.app_code, f.title, f.normalized_severity,
+               f.status, f.cwe_ids, f.first_seen, f.sla_due_date, f.sla_status
+        FROM findings f
+        JOIN applications a ON a.id = f.application_id
+        WHERE a.is_gdpr_subject = true
+          AND a.status = 'Active'
+          AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND f.cwe_ids ?| $1
+        ORDER BY f.normalized_severity, f.first_seen
+        "#,
+    )
+    .bind(&personal_data_cwes)
+    .fetch_all(pool)
+    .await?;
+
+    let risk_acceptances = sqlx::query_as::<_, GdprRiskAcceptance>(
+        r#"
+        SELECT f.id AS finding_id, a.app_code, f.title, f.normalized_severity,
+               h.actor_name AS accepted_by,
+               f.status_changed_at AS accepted_at,
+               h.justification
+        FROM findings f
+        JOIN applications a ON a.id = f.application_id
+        LEFT JOIN LATERAL (
+            SELECT actor_name, justification
+            FROM finding_history
+            WHERE finding_id = f.id
+              AND action = 'status_change'
+              AND new_value = 'RiskAccepted'
+            ORDER BY created_at DESC
+            LIMIT 1
+        ) h ON true
+        WHERE a.is_gdpr_subject = true
+          AND a.status = 'Active'
+          AND f.status = 'Risk_Accepted'
+        ORDER BY f.normalized_severity, f.status_changed_at
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let findings: Vec<GdprFinding> = rows.into_iter().map(GdprFinding::from).collect();
+    let applications = summarize_applications(applications, &findings);
+
+    Ok(GdprReport {
+        generated_at: Utc::now(),
+        application_count: applications.len(),
+        applications,
+        findings,
+        risk_acceptances,
+    })
+}
+
+impl From<GdprFindingRow> for GdprFinding {
+    fn from(row: GdprFindingRow) -> Self {
+        let cwe_ids: Vec<String> = match row.cwe_ids {
+            serde_json::Value::Array(values) => values
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        };
+        Self {
+            id: row.id,
+            application_id: row.application_id,
+            app_code: row.app_code,
+            title: row.title,
+            normalized_severity: row.normalized_severity,
+            status: row.status,
+            risks: classify_cwes(&cwe_ids),
+            cwe_ids,
+            first_seen: row.first_seen,
+            sla_due_date: row.sla_due_date,
+            sla_status: row.sla_status,
+        }
+    }
+}
+
+/// Tally personal-data findings per application, keeping applications
+/// without findings so the DPO sees the full in-scope inventory.
+fn summarize_applications(
+    applications: Vec<GdprApplicationRow>,
+    findings: &[GdprFinding],
+) -> Vec<GdprApplicationSummary> {
+    applications
+        .into_iter()
+        .map(|app| {
+            let mut summary = GdprApplicationSummary {
+                application_id: app.id,
+                app_name: app.app_name,
+                app_code: app.app_code,
+                ..Default::default()
+            };
+            for f in findings.iter().filter(|f| f.application_id == app.id) {
+                if f.risks.contains(&PersonalDataRisk::Exposure) {
+                    summary.exposure_findings += 1;
+                }
+                if f.risks.contains(&PersonalDataRisk::Injection) {
+                    summary.injection_findings += 1;
+                }
+                match f.status {
+                    FindingStatus::New | FindingStatus::Confirmed => {
+                        summary.awaiting_remediation += 1
+                    }
+                    FindingStatus::InRemediation | FindingStatus::DeferredRemediation => {
+                        summary.in_remediation += 1
+                    }
+                    FindingStatus::Mitigated => summary.mitigated += 1,
+                    FindingStatus::RiskAccepted => summary.risk_accepted += 1,
+                    _ => {}
+                }
+                if f.sla_status == Some(SlaStatus::Breached) {
+                    summary.sla_breached += 1;
+                }
+            }
+            summary
+        })
+        .collect()
+}
+
+fn risk_labels(risks: &[PersonalDataRisk]) -> String {
+    risks
+        .iter()
+        .map(|r| match r {
+            PersonalDataRisk::Exposure => "Exposure",
+            PersonalDataRisk::Injection => "Injection",
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Lay out the GDPR report as a printable document.
+pub fn to_document(report: &GdprReport) -> ReportDocument {
+    let blocks = vec![
+        ReportBlock::Heading("In-scope applications".to_string()),
+        ReportBlock::Paragraph(format!(
+            "{} active applications process personal data. Counts cover open findings whose \
+             CWE indicates personal-data exposure or injection.",
+            report.application_count
+        )),
+        ReportBlock::Table {
+            columns: vec![
+                "Application".to_string(),
+                "Exposure".to_string(),
+                "Injection".to_string(),
+                "Awaiting".to_string(),
+                "In remediation".to_string(),
+                "Mitigated".to_string(),
+                "Accepted".to_string(),
+                "SLA breached".to_string(),
+            ],
+            rows: report
+                .applications
+                .iter()
+                .map(|a| {
+                    vec![
+                        format!("{} ({})", a.app_name, a.app_code),
+                        a.exposure_findings.to_string(),
+                        a.injection_findings.to_string(),
+                        a.awaiting_remediation.to_string(),
+                        a.in_remediation.to_string(),
+                        a.mitigated.to_string(),
+                        a.risk_accepted.to_string(),
+                        a.sla_breached.to_string(),
+                    ]
+                })
+                .collect(),
+        },
+        ReportBlock::Heading("Findings touching personal-data flows".to_string()),
+        ReportBlock::Table {
+            columns: vec![
+                "App".to_string(),
+                "Severity".to_string(),
+                "Title".to_string(),
+                "Risk".to_string(),
+                "Status".to_string(),
+                "SLA due".to_string(),
+            ],
+            rows: report
+                .findings
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.app_code.clone(),
+                        format!("{:?}", f.normalized_severity),
+                        f.title.clone(),
+                        risk_labels(&f.risks),
+                        format!("{:?}", f.status),
+                        f.sla_due_date
+                            .map_or_else(|| "-".to_string(), |d| d.format("%Y-%m-%d").to_string()),
+                    ]
+                })
+                .collect(),
+        },
+        ReportBlock::Heading("Open risk acceptances".to_string()),
+        ReportBlock::Table {
+            columns: vec![
+                "App".to_string(),
+                "Severity".to_string(),
+                "Title".to_string(),
+                "Accepted by".to_string(),
+                "Accepted on".to_string(),
+                "Justification".to_string(),
+            ],
+            rows: report
+                .risk_acceptances
+                .iter()
+                .map(|r| {
+                    vec![
+                        r.app_code.clone(),
+                        format!("{:?}", r.normalized_severity),
+                        r.title.clone(),
+                        r.accepted_by.clone().unwrap_or_else(|| "-".to_string()),
+                        r.accepted_at.format("%Y-%m-%d").to_string(),
+                        r.justification.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+        },
+    ];
+
+    ReportDocument {
+        title: "GDPR - Personal Data Security Findings".to_string(),
+        subtitle: Some(format!(
+            "Generated {}",
+            report.generated_at.format("%Y-%m-%d %H:%M UTC")
+        )),
+        blocks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(application_id: Uuid, cwe: &str, status: FindingStatus) -> GdprFinding {
+        let cwe_ids = vec![cwe.to_string()];
+        GdprFinding {
+            id: Uuid::new_v4(),
+            application_id,
+            app_code: "APP".to_string(),
+            title: "finding".to_string(),
+            normalized_severity: SeverityLevel::High,
+            status,
+            risks: classify_cwes(&cwe_ids),
+            cwe_ids,
+            first_seen: Utc::now(),
+            sla_due_date: None,
+            sla_status: None,
+        }
+    }
+
+    #[test]
+    fn classifies_exposure_and_injection() {
+        assert_eq!(
+            classify_cwes(&["CWE-89".to_string()]),
+            vec![PersonalDataRisk::Injection]
+        );
+        assert_eq!(
+            classify_cwes(&["CWE-312".to_string(), "CWE-79".to_string()]),
+            vec![PersonalDataRisk::Exposure, PersonalDataRisk::Injection]
+        );
+        assert!(classify_cwes(&["CWE-400".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn summarizes_per_application_including_empty() {
+        let app_a = Uuid::new_v4();
+        let app_b = Uuid::new_v4();
+        let apps = vec![
+            GdprApplicationRow {
+                id: app_a,
+                app_name: "Payments".to_string(),
+                app_code: "PAY".to_string(),
+            },
+            GdprApplicationRow {
+                id: app_b,
+                app_name: "Intranet".to_string(),
+                app_code: "INT".to_string(),
+            },
+        ];
+        let findings = vec![
+            finding(app_a, "CWE-89", FindingStatus::Confirmed),
+            finding(app_a, "CWE-200", FindingStatus::InRemediation),
+            finding(app_a, "CWE-532", FindingStatus::RiskAccepted),
+        ];
+
+        let summaries = summarize_applications(apps, &findings);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].injection_findings, 1);
+        assert_eq!(summaries[0].exposure_findings, 2);
+        assert_eq!(summaries[0].awaiting_remediation, 1);
+        assert_eq!(summaries[0].in_remediation, 1);
+        assert_eq!(summaries[0].risk_accepted, 1);
+        assert_eq!(summaries[1].exposure_findings, 0);
+    }
+
+    #[test]
+    fn risk_labels_join() {
+        assert_eq!(
+            risk_labels(&[PersonalDataRisk::Exposure, PersonalDataRisk::Injection]),
+            "Exposure, Injection"
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! GitHub Security Advisory (GHSA) resolution.
+//!
+//! Some scanners report only a GHSA id where others report the CVE. Advisories
+//! are fetched from the GitHub advisory database and cached in
+//! `ghsa_advisories`; the CVE aliases are then added to `cve_ids` of every
+//! finding reporting the GHSA id, so CVE-based correlation and search match
+//! across tools. Ingestion applies cached aliases to incoming findings; a
+//! background task fetches advisories not yet cached and backfills findings.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::models::ghsa::GhsaAdvisory;
+use crate::parsers::ParsedFinding;
+
+/// Timeout for a single GitHub request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// GitHub API connection settings derived from [`AppConfig`].
+#[derive(Clone)]
+pub struct GhsaSettings {
+    pub url: String,
+    pub token: Option<String>,
+    pub interval: Duration,
+}
+
+impl std::fmt::Debug for GhsaSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GhsaSettings")
+            .field("url", &self.url)
+            .field("token", &self.token.as_ref().map(|_| "[redacted]"))
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl GhsaSettings {
+    /// Build settings from config, returning `None` when resolution is disabled.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        if !config.ghsa_resolution_enabled {
+            return None;
+        }
+        Some(Self {
+            url: config.github_api_url.trim_end_matches('/').to_string(),
+            token: config.github_token.clone(),
+            interval: Duration::from_secs(config.ghsa_resolution_interval_secs.max(1)),
+        })
+    }
+
+    /// Advisories fetched per run: GitHub allows 60 unauthenticated requests
+    /// per hour and 5,000 with a token.
+    fn batch_size(&self) -> i64 {
+        if self.token.is_some() {
+            1000
+        } else {
+            50
+        }
+    }
+}
+
+/// Canonical form of a GHSA id (`GHSA-xxxx-xxxx-xxxx`, lowercase groups), or
+/// `None` if `id` is not one.
+pub fn normalize_ghsa_id(id: &str) -> Option<String> {
+    let id = id.trim();
+    let rest = id
+        .get(..5)
+        .filter(|p| p.eq_ignore_ascii_case("GHSA-"))
+        .map(|_| &id[5..])?;
+    let groups: Vec<&str> = rest.split('-').collect();
+    let valid = groups.len() == 3
+        && groups
+            .iter()
+            .all(|g| g.len() == 4 && g.bytes().all(|b| b.is_ascii_alphanumeric()));
+    valid.then(|| format!("GHSA-{}", rest.to_ascii_lowercase()))
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAdvisory {
+    cve_id: Option<String>,
+    #[serde(default)]
+    identifiers: Vec<GithubIdentifier>,
+    summary: Option<String>,
+    severity: Option<String>,
+    cvss: Option<GithubCvss>,
+    #[serde(default)]
+    cwes: Vec<GithubCwe>,
+    html_url: Option<String>,
+    published_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    withdrawn_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubIdentifier {
+    #[serde(rename = "type")]
+    kind: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubCvss {
+    vector_string: Option<String>,
+    score: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubCwe {
+    cwe_id: String,
+}
+
+/// CVE ids of an advisory from `cve_id` and its identifier list.
+fn cve_aliases(advisory: &GithubAdvisory) -> Vec<String> {
+    let mut cves: Vec<String> = advisory
+        .cve_id
+        .iter()
+        .chain(
+            advisory
+                .identifiers
+                .iter()
+                .filter(|i| i.kind.eq_ignore_ascii_case("CVE"))
+                .map(|i| &i.value),
+        )
+        .map(|c| c.trim().to_ascii_uppercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+    cves.sort();
+    cves.dedup();
+    cves
+}
+
+/// Fetch one advisory; `Ok(None)` when GitHub has no advisory for the id.
+async fn fetch(
+    client: &reqwest::Client,
+    settings: &GhsaSettings,
+    ghsa_id: &str,
+) -> Result<Option<GithubAdvisory>, AppError> {
+    let mut request = client
+        .get(format!("{}/advisories/{ghsa_id}", settings.url))
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+    if let Some(token) = &settings.token {
+        request = request.bearer_auth(token);
+    }
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("GitHub advisory request failed: {e}")))?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !resp.status().is_success() {
+        return Err(AppError::Internal(format!(
+            "GitHub advisory API responded with status {}",
+            resp.status()
+        )));
+    }
+    let advisory = resp
+        .json::<GithubAdvisory>()
+        .await
+        .map_err(|e| AppError::Internal(format!("Invalid GitHub advisory response: {e}")))?;
+    Ok(Some(advisory))
+}
+
+/// Cache a lookup result and return the stored advisory.
+async fn upsert(
+    pool: &PgPool,
+    ghsa_id: &str,
+    advisory: Option<&GithubAdvisory>,
+) -> Result<GhsaAdvisory, AppError> {
+    let cve_ids = advisory.map(cve_aliases).unwrap_or_default();
+    let cwe_ids: Vec<String> = advisory
+        .map(|a| a.cwes.iter().map(|c| c.cwe_id.clone()).collect())
+        .unwrap_or_default();
+    let stored = sqlx::query_as::<_, GhsaAdvisory>(
+        r#"
+        INSERT INTO ghsa_advisories (
+            ghsa_id, found, cve_ids, summary, severity, cvss_vector, cvss_score, cwe_ids,
+            html_url, published_at, advisory_updated_at, withdrawn_at, fetched_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW())
+        ON CONFLICT (ghsa_id) DO UPDATE SET
+            found = EXCLUDED.found,
+            cve_ids = EXCLUDED.cve_ids,
+            summary = EXCLUDED.summary,
+            severity = EXCLUDED.severity,
+            cvss_vector = EXCLUDED.cvss_vector,
+            cvss_score = EXCLUDED.cvss_score,
+            cwe_ids = EXCLUDED.cwe_ids,
+            html_url = EXCLUDED.html_url,
+            published_at = EXCLUDED.published_at,
+            advisory_updated_at = EXCLUDED.advisory_updated_at,
+            withdrawn_at = EXCLUDED.withdrawn_at,
+            fetched_at = NOW()
+        RETURNING *
+        "#,
+    )
+    .bind(ghsa_id)
+    .bind(advisory.is_some())
+    .bind(&cve_ids)
+    .bind(advisory.and_then(|a| a.summary.clone()))
+    .bind(advisory.and_then(|a| a.severity.clone()))
+    .bind(advisory.and_then(|a| a.cvss.as_ref()?.vector_string.clone()))
+    .bind(advisory.and_then(|a| a.cvss.as_ref()?.score))
+    .bind(&cwe_ids)
+    .bind(advisory.and_then(|a| a.html_url.clone()))
+    .bind(advisory.and_then(|a| a.published_at))
+    .bind(advisory.and_then(|a| a.updated_at))
+    .bind(advisory.and_then(|a| a.withdrawn_at))
+    .fetch_one(pool)
+    .await?;
+    Ok(stored)
+}
+
+/// Add an advisory's CVE aliases to every finding reporting its GHSA id.
+/// Returns the number of findings changed.
+async fn backfill_findings(pool: &PgPool, advisory: &GhsaAdvisory) -> Result<u64, AppError> {
+    if advisory.cve_ids.is_empty() {
+        return Ok(0);
+    }
+    let result = sqlx::query(
+        r#"
+        UPDATE findings f
+        SET cve_ids = f.cve_ids || (
+                SELECT COALESCE(jsonb_agg(cve), '[]'::jsonb)
+                FROM UNNEST($2::text[]) AS cve
+                WHERE NOT f.cve_ids @> jsonb_build_array(cve)
+            ),
+            updated_at = NOW()
+        WHERE EXISTS (
+                SELECT 1 FROM jsonb_array_elements_text(f.cve_ids) AS id(value)
+                WHERE LOWER(id.value) = LOWER($1)
+            )
+          AND EXISTS (
+                SELECT 1 FROM UNNEST($2::text[]) AS cve
+                WHERE NOT f.cve_ids @> jsonb_build_array(cve)
+            )
+        "#,
+    )
+    .bind(&advisory.ghsa_id)
+    .bind(&advisory.cve_ids)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Append cached CVE aliases to parsed findings that report GHSA ids, so new
+/// findings are stored with both identifiers. Uncached ids are left for the
+/// background resolver.
+pub async fn apply_cached_aliases(
+    pool: &PgPool,
+    findings: &mut [ParsedFinding],
+) -> Result<(), AppError> {
+    let ghsa_ids: Vec<String> = findings
+        .iter()
+        .flat_map(|f| &f.core.cve_ids)
+        .filter_map(|id| normalize_ghsa_id(id))
+        .collect();
+    if ghsa_ids.is_empty() {
+        return Ok(());
+    }
+
+    let rows = sqlx::query_as::<_, (String, Vec<String>)>(
+        "SELECT ghsa_id, cve_ids FROM ghsa_advisories WHERE ghsa_id = ANY($1) AND found",
+    )
+    .bind(&ghsa_ids)
+    .fetch_all(pool)
+    .await?;
+    let aliases: HashMap<String, Vec<String>> = rows.into_iter().collect();
+    for finding in findings {
+        add_aliases(&mut finding.core.cve_ids, &aliases);
+    }
+    Ok(())
+}
+
+/// Append the CVE aliases of any GHSA ids in `ids` that are not already present.
+fn add_aliases(ids: &mut Vec<String>, aliases: &HashMap<String, Vec<String>>) {
+    let cves: Vec<String> = ids
+        .iter()
+        .filter_map(|id| aliases.get(&normalize_ghsa_id(id)?))
+        .flatten()
+        .cloned()
+        .collect();
+    for cve in cves {
+        if !ids.iter().any(|id| id.eq_ignore_ascii_case(&cve)) {
+            ids.push(cve);
+        }
+    }
+}
+
+/// GHSA ids reported by findings that are not cached yet.
+async fn pending_ids(pool: &PgPool, limit: i64) -> Result<Vec<String>, AppError> {
+    let ids = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT DISTINCT id.value
+        FROM findings f
+        CROSS JOIN LATERAL jsonb_array_elements_text(f.cve_ids) AS id(value)
+        WHERE id.value ILIKE 'GHSA-%'
+          AND NOT EXISTS (
+                SELECT 1 FROM ghsa_advisories g
+                WHERE g.ghsa_id = 'GHSA-' || LOWER(SUBSTRING(id.value FROM 6))
+            )
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(ids)
+}
+
+/// Fetch, cache, and apply advisories for uncached GHSA ids. Returns the
+/// number of findings that gained CVE aliases.
+pub async fn resolve_pending(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    settings: &GhsaSettings,
+) -> Result<u64, AppError> {
+    let mut updated = 0;
+    for raw_id in pending_ids(pool, settings.batch_size()).await? {
+        let Some(ghsa_id) = normalize_ghsa_id(&raw_id) else {
+            continue;
+        };
+        match fetch(client, settings, &ghsa_id).await {
+            Ok(advisory) => {
+                let stored = upsert(pool, &ghsa_id, advisory.as_ref()).await?;
+                updated += backfill_findings(pool, &stored).await?;
+            }
+            Err(e) => tracing::warn!(ghsa_id = %ghsa_id, error = %e, "GHSA lookup failed"),
+        }
+    }
+    Ok(updated)
+}
+
+/// Spawn the background GHSA resolver.
+pub fn spawn_resolver(pool: PgPool, settings: GhsaSettings) {
+    tokio::spawn(async move {
+        let client = match reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent("synapsec")
+            .build()
+        {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to build GitHub client; GHSA resolution disabled");
+                return;
+            }
+        };
+        let mut ticker = tokio::time::interval(settings.interval);
+        loop {
+            ticker.tick().await;
+            match resolve_pending(&pool, &client, &settings).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "Added CVE aliases from GHSA advisories"),
+                Err(e) => tracing::error!(error = %e, "GHSA resolution failed"),
+            }
+        }
+    });
+}
+
+/// A cached advisory by GHSA id.
+pub async fn get_advisory(pool: &PgPool, id: &str) -> Result<GhsaAdvisory, AppError> {
+    let ghsa_id = normalize_ghsa_id(id)
+        .ok_or_else(|| AppError::Validation(format!("Invalid GHSA id '{id}'")))?;
+    sqlx::query_as::<_, GhsaAdvisory>("SELECT * FROM ghsa_advisories WHERE ghsa_id = $1")
+        .bind(&ghsa_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Advisory {ghsa_id} not resolved")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ghsa_ids_are_normalized() {
+        assert_eq!(
+            normalize_ghsa_id(" ghsa-JFH8-C2JP-5V3Q ").as_deref(),
+            Some("GHSA-jfh8-c2jp-5v3q")
+        );
+        assert_eq!(normalize_ghsa_id("CVE-2021-44228"), None);
+        assert_eq!(normalize_ghsa_id("GHSA-jfh8-c2jp"), None);
+    }
+
+    #[test]
+    fn cve_aliases_come_from_cve_id_and_identifiers() {
+        let advisory: GithubAdvisory = serde_json::from_value(serde_json::json!({
+            "ghsa_id": "GHSA-jfh8-c2jp-5v3q",
+            "cve_id": "CVE-2021-44228",
+            "identifiers": [
+                {"type": "GHSA", "value": "GHSA-jfh8-c2jp-5v3q"},
+                {"type": "CVE", "value": "cve-2021-44228"}
+            ],
+            "summary": "Remote code injection in Log4j",
+            "severity": "critical",
+            "cvss": {"vector_string": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H", "score": 10.0},
+            "cwes": [{"cwe_id": "CWE-502", "name": "Deserialization of Untrusted Data"}],
+            "published_at": "2021-12-10T00:40:56Z"
+        }))
+        .unwrap();
+        assert_eq!(cve_aliases(&advisory), ["CVE-2021-44228"]);
+    }
+
+    #[test]
+    fn cached_aliases_are_appended_once() {
+        let aliases = HashMap::from([(
+            "GHSA-jfh8-c2jp-5v3q".to_string(),
+            vec!["CVE-2021-44228".to_string()],
+        )]);
+        let mut ids = vec!["GHSA-JFH8-C2JP-5V3Q".to_string()];
+        add_aliases(&mut ids, &aliases);
+        add_aliases(&mut ids, &aliases);
+        assert_eq!(ids, ["GHSA-JFH8-C2JP-5V3Q", "CVE-2021-44228"]);
+    }
+}
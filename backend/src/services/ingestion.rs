@@ -2,21 +2,33 @@
 //!
 //! Accepts scanner output files, selects the appropriate parser, normalizes
 //! findings, resolves applications, applies deduplication, creates findings,
-//! and logs the ingestion event.
+//! and logs the ingestion event. New findings are written with one bulk
+//! insert per run rather than one round trip per record.
+
+use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::models::finding::{CreateFinding, SeverityLevel};
 use crate::parsers::sarif::SarifParser;
 use crate::parsers::sonarqube::SonarQubeParser;
 use crate::parsers::{InputFormat, Parser};
-use crate::services::{app_code_resolver, application, deduplication, finding};
+use crate::services::splunk_hec::{self, HecSink, PlatformEvent};
+use crate::services::finding::CategoryData;
+use crate::services::fingerprint::{self, FingerprintInputs, FingerprintProfiles};
+use crate::services::{
+    app_code_resolver, application, config_cache, deduplication, finding, ghsa, license_policy,
+    owasp, scanner_rule, watchlist,
+};
+use crate::services::upload_token::UploadScope;
 
 /// Summary of an ingestion run.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct IngestionResult {
     #[serde(rename = "ingestion_log_id")]
     pub ingestion_id: Uuid,
@@ -28,13 +40,15 @@ pub struct IngestionResult {
     pub reopened_findings: usize,
     pub duplicates: usize,
     pub quarantined: usize,
+    /// Records skipped because their scanner rule is suppressed.
+    pub suppressed: usize,
     #[serde(rename = "errors")]
     pub error_count: usize,
     pub error_details: Vec<IngestionError>,
 }
 
 /// Error during ingestion of a single record.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct IngestionError {
     pub record_index: usize,
     pub stage: String,
@@ -49,7 +63,7 @@ pub struct IngestionUpload {
 }
 
 /// Supported parser types.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ParserType {
     Sonarqube,
@@ -58,6 +72,7 @@ pub enum ParserType {
     JfrogXray,
     #[serde(rename = "tenable_was")]
     TenableWas,
+    Semgrep,
 }
 
 impl std::fmt::Display for ParserType {
@@ -67,6 +82,7 @@ impl std::fmt::Display for ParserType {
             Self::Sarif => write!(f, "sarif"),
             Self::JfrogXray => write!(f, "jfrog_xray"),
             Self::TenableWas => write!(f, "tenable_was"),
+            Self::Semgrep => write!(f, "semgrep"),
         }
     }
 }
@@ -74,7 +90,7 @@ impl std::fmt::Display for ParserType {
 /// Ingestion log entry stored in the database.
 ///
 /// Maps to the `ingestion_logs` table in the initial schema.
-#[derive(Debug, Clone, Serialize, FromRow)]
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
 pub struct IngestionLog {
     pub id: Uuid,
     pub source_tool: String,
@@ -91,10 +107,12 @@ pub struct IngestionLog {
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub initiated_by: Option<Uuid>,
+    /// Upload token the file was uploaded with, if any.
+    pub upload_token_id: Option<Uuid>,
 }
 
 /// Ingestion log summary for history listing.
-#[derive(Debug, Clone, Serialize, FromRow)]
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
 pub struct IngestionLogSummary {
     pub id: Uuid,
     pub source_tool: String,
@@ -112,6 +130,9 @@ pub struct IngestionLogSummary {
 }
 
 /// Run the full ingestion pipeline for an uploaded file.
+///
+/// When `events` is set, each created finding and the final run summary are
+/// forwarded to Splunk HEC.
 pub async fn ingest_file(
     pool: &PgPool,
     file_data: &[u8],
@@ -119,6 +140,30 @@ pub async fn ingest_file(
     parser_type: &ParserType,
     format: &InputFormat,
     initiated_by: Uuid,
+    events: Option<&HecSink>,
+) -> Result<IngestionResult, AppError> {
+    ingest_file_scoped(pool, file_data, file_name, parser_type, format, initiated_by, None, events)
+        .await
+}
+
+/// Run the ingestion pipeline for a file uploaded with an upload token.
+///
+/// The whole file is rejected, before anything is written, when it reports a
+/// source tool the token does not allow or when any finding resolves to an
+/// application outside the token's applications.
+#[expect(
+    clippy::too_many_arguments,
+    reason = "ingest_file's arguments plus the upload scope; callers pass them straight from the upload"
+)]
+pub async fn ingest_file_scoped(
+    pool: &PgPool,
+    file_data: &[u8],
+    file_name: &str,
+    parser_type: &ParserType,
+    format: &InputFormat,
+    initiated_by: Uuid,
+    scope: Option<&UploadScope>,
+    events: Option<&HecSink>,
 ) -> Result<IngestionResult, AppError> {
     // 1. Select parser
     let parser: Box<dyn Parser> = match parser_type {
@@ -126,16 +171,32 @@ pub async fn ingest_file(
         ParserType::Sarif => Box::new(SarifParser::new()),
         ParserType::JfrogXray => Box::new(crate::parsers::jfrog_xray::JfrogXrayParser::new()),
         ParserType::TenableWas => Box::new(crate::parsers::tenable_was::TenableWasParser::new()),
+        ParserType::Semgrep => Box::new(crate::parsers::semgrep::SemgrepParser::new()),
     };
 
     // 2. Parse raw data
-    let parse_result = parser.parse(file_data, format.clone()).map_err(|e| {
+    let mut parse_result = parser.parse(file_data, format.clone()).map_err(|e| {
         AppError::Validation(format!("Failed to parse file: {e}"))
     })?;
+    if let Some(scope) = scope {
+        check_upload_scope(pool, scope, parser_type, &parse_result).await?;
+    }
+
+    // Tools reporting only a GHSA id get its cached CVE aliases, so findings
+    // correlate by CVE with other tools
+    if let Err(e) = ghsa::apply_cached_aliases(pool, &mut parse_result.findings).await {
+        tracing::warn!(error = %e, "Failed to apply GHSA CVE aliases");
+    }
+    // Findings without a scanner-reported OWASP category get one from their CWEs
+    let parsed = parse_result.findings.iter_mut().map(|f| &mut f.core);
+    if let Err(e) = owasp::fill_categories(pool, parsed).await {
+        tracing::warn!(error = %e, "Failed to derive OWASP categories");
+    }
 
     let mut new_findings = 0usize;
     let mut updated_findings = 0usize;
     let mut reopened_findings = 0usize;
+    let mut suppressed = 0usize;
     let mut errors: Vec<IngestionError> = Vec::new();
 
     // Collect parse errors
@@ -148,15 +209,70 @@ pub async fn ingest_file(
     }
 
     let total_parsed = parse_result.findings.len();
-
-    // 3. Process each parsed finding through the pipeline
+    let mut occurrences = Occurrences::default();
+
+    // New findings queued for the bulk insert, with their record indexes.
+    // A fingerprint repeated within the file dedups against its queued row.
+    let mut pending: Vec<(CreateFinding, CategoryData)> = Vec::new();
+    let mut pending_records: Vec<usize> = Vec::new();
+    let mut pending_rules: Vec<Option<scanner_rule::RuleKey>> = Vec::new();
+    let mut pending_by_fingerprint: HashMap<String, usize> = HashMap::new();
+    let mut repeats: Vec<(usize, usize)> = Vec::new();
+
+    // 3. Resolve each parsed finding against existing findings
+    let profiles = config_cache::global()
+        .settings(pool)
+        .await?
+        .fingerprint_profiles();
+    let policies = scanner_rule::policies(pool, &parse_result.source_tool).await?;
+    let mut sightings = scanner_rule::Sightings::default();
     for (i, parsed) in parse_result.findings.iter().enumerate() {
-        match process_finding(pool, parsed, initiated_by).await {
-            Ok(outcome) => match outcome {
-                ProcessOutcome::Created => new_findings += 1,
-                ProcessOutcome::Deduplicated => updated_findings += 1,
-                ProcessOutcome::Reopened => reopened_findings += 1,
-            },
+        // Catalog the record's rule; records of suppressed rules go no further
+        let rule = scanner_rule::rule_key(&parsed.core, &parsed.category_data);
+        if let Some(rule) = &rule {
+            sightings.see(rule);
+            if policies.is_suppressed(rule) {
+                suppressed += 1;
+                continue;
+            }
+        }
+
+        match resolve_finding(pool, parsed, &profiles, initiated_by).await {
+            Ok(Resolution::Existing {
+                fingerprint,
+                outcome,
+            }) => {
+                if let Some(rule) = &rule {
+                    sightings.link(outcome.finding_id(), rule);
+                }
+                match outcome {
+                    ProcessOutcome::Created(_) => new_findings += 1,
+                    ProcessOutcome::Deduplicated(_) => updated_findings += 1,
+                    ProcessOutcome::Reopened(_) => reopened_findings += 1,
+                }
+                occurrences.push(
+                    &fingerprint,
+                    &outcome,
+                    &parsed.core.normalized_severity,
+                    &parsed.category_data,
+                );
+            }
+            Ok(Resolution::New(mut core)) => {
+                if let Some(&slot) = pending_by_fingerprint.get(&core.fingerprint) {
+                    repeats.push((i, slot));
+                } else {
+                    // New findings of a rule with a default severity get it
+                    // in place of the tool's
+                    let default_severity = rule.as_ref().and_then(|r| policies.default_severity(r));
+                    if let Some(severity) = default_severity {
+                        core.normalized_severity = severity;
+                    }
+                    pending_by_fingerprint.insert(core.fingerprint.clone(), pending.len());
+                    pending_records.push(i);
+                    pending_rules.push(rule);
+                    pending.push((*core, parsed.category_data.clone()));
+                }
+            }
             Err(e) => {
                 errors.push(IngestionError {
                     record_index: i,
@@ -167,7 +283,50 @@ pub async fn ingest_file(
         }
     }
 
-    // 4. Log ingestion event
+    // 4. Create new findings in one transaction
+    match finding::create_many(pool, &pending).await {
+        Ok(created) => {
+            let records = created.iter().zip(&pending_records).zip(&pending_rules);
+            for ((f, &i), rule) in records {
+                splunk_hec::emit(events, PlatformEvent::finding_created(f));
+                // The severity the tool reported, before any rule default
+                let record = &parse_result.findings[i];
+                occurrences.push(
+                    &f.fingerprint,
+                    &ProcessOutcome::Created(f.id),
+                    &record.core.normalized_severity,
+                    &record.category_data,
+                );
+                if let Some(rule) = rule {
+                    sightings.link(f.id, rule);
+                }
+            }
+            new_findings += created.len();
+            for &(i, slot) in &repeats {
+                let f = &created[slot];
+                updated_findings += 1;
+                let record = &parse_result.findings[i];
+                occurrences.push(
+                    &f.fingerprint,
+                    &ProcessOutcome::Deduplicated(f.id),
+                    &record.core.normalized_severity,
+                    &record.category_data,
+                );
+            }
+        }
+        Err(e) => {
+            let message = e.to_string();
+            for &record_index in pending_records.iter().chain(repeats.iter().map(|(i, _)| i)) {
+                errors.push(IngestionError {
+                    record_index,
+                    stage: "ingest".to_string(),
+                    message: message.clone(),
+                });
+            }
+        }
+    }
+
+    // 5. Log ingestion event
     let ingestion_id = log_ingestion(
         pool,
         &IngestionLogInput {
@@ -180,13 +339,49 @@ pub async fn ingest_file(
             duplicates: updated_findings,
             errors: &errors,
             initiated_by,
+            upload_token_id: scope.map(|s| s.token_id),
         },
     )
     .await?;
 
+    // 6. Record occurrences, dated by the scan when the report says when it ran
+    let scan_date = parse_result.scanned_at.unwrap_or_else(Utc::now);
+    record_occurrences(
+        pool,
+        ingestion_id,
+        &parse_result.source_tool,
+        scan_date,
+        &occurrences,
+    )
+    .await?;
+
+    // 7. Add the run's rules to the scanner rule catalog
+    scanner_rule::record(pool, &parse_result.source_tool, &sightings).await?;
+
+    // 8. Record watchlist hits for the run's findings
+    watchlist::record_ingestion(pool, ingestion_id, &parse_result.source_tool).await;
+
+    // 9. Flag license policy violations of the run's SCA findings
+    license_policy::record_ingestion(pool, ingestion_id).await;
+
     let error_count = errors.len();
     let duplicates = updated_findings;
 
+    splunk_hec::emit(
+        events,
+        PlatformEvent::IngestionCompleted {
+            ingestion_id,
+            source_tool: parse_result.source_tool.clone(),
+            file_name: file_name.to_string(),
+            total_parsed,
+            new_findings,
+            updated_findings,
+            reopened_findings,
+            errors: error_count,
+            initiated_by,
+        },
+    );
+
     Ok(IngestionResult {
         ingestion_id,
         source_tool: parse_result.source_tool,
@@ -197,22 +392,76 @@ pub async fn ingest_file(
         reopened_findings,
         duplicates,
         quarantined: 0,
+        suppressed,
         error_count,
         error_details: errors,
     })
 }
 
+/// Result of processing one record, with the finding it resolved to.
 enum ProcessOutcome {
-    Created,
-    Deduplicated,
-    Reopened,
+    Created(Uuid),
+    Deduplicated(Uuid),
+    Reopened(Uuid),
+}
+
+impl ProcessOutcome {
+    fn finding_id(&self) -> Uuid {
+        match self {
+            Self::Created(id) | Self::Deduplicated(id) | Self::Reopened(id) => *id,
+        }
+    }
+
+    /// Label stored in `finding_occurrences.outcome`.
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "new",
+            Self::Deduplicated(_) => "updated",
+            Self::Reopened(_) => "reopened",
+        }
+    }
+}
+
+/// Occurrences collected during a run, as parallel arrays for a single
+/// `UNNEST` insert once the ingestion log exists.
+#[derive(Debug, Default)]
+struct Occurrences {
+    finding_ids: Vec<Uuid>,
+    fingerprints: Vec<String>,
+    outcomes: Vec<&'static str>,
+    severities: Vec<SeverityLevel>,
+    branches: Vec<Option<String>>,
+    target_urls: Vec<Option<String>>,
+}
+
+impl Occurrences {
+    fn push(
+        &mut self,
+        fingerprint: &str,
+        outcome: &ProcessOutcome,
+        severity: &SeverityLevel,
+        data: &CategoryData,
+    ) {
+        let (branch, target_url) = match data {
+            CategoryData::Sast(sast) => (sast.branch.clone(), None),
+            CategoryData::Sca(_) => (None, None),
+            CategoryData::Dast(dast) => (None, Some(dast.target_url.clone())),
+            CategoryData::Manual(_) => (None, None),
+        };
+        self.finding_ids.push(outcome.finding_id());
+        self.fingerprints.push(fingerprint.to_string());
+        self.outcomes.push(outcome.label());
+        self.severities.push(severity.clone());
+        self.branches.push(branch);
+        self.target_urls.push(target_url);
+    }
 }
 
 /// Extract all string-valued fields from metadata as `(field_name, field_value)` pairs.
 ///
 /// Non-string and null values are skipped. Returns an empty vec for non-object
 /// metadata (e.g. `null`, arrays).
-fn extract_resolver_fields(metadata: &serde_json::Value) -> Vec<(String, String)> {
+pub(crate) fn extract_resolver_fields(metadata: &serde_json::Value) -> Vec<(String, String)> {
     let Some(obj) = metadata.as_object() else {
         return Vec::new();
     };
@@ -221,81 +470,117 @@ fn extract_resolver_fields(metadata: &serde_json::Value) -> Vec<(String, String)
         .collect()
 }
 
-/// Load active app code patterns for a source tool, ordered by priority descending.
-async fn load_patterns(
-    pool: &PgPool,
-    source_tool: &str,
-) -> Result<Vec<app_code_resolver::PatternEntry>, AppError> {
-    let rows = sqlx::query_as::<_, (String, String, i32)>(
-        r#"
-        SELECT field_name, regex_pattern, priority
-        FROM app_code_patterns
-        WHERE source_tool = $1 AND is_active = true
-        ORDER BY priority DESC
-        "#,
-    )
-    .bind(source_tool)
-    .fetch_all(pool)
-    .await?;
-
-    Ok(rows
-        .into_iter()
-        .map(|(field_name, regex_pattern, priority)| app_code_resolver::PatternEntry {
-            field_name,
-            regex_pattern,
-            priority,
-        })
-        .collect())
+/// How a parsed record resolved before new findings are inserted.
+enum Resolution {
+    /// No finding has this fingerprint; the finding to queue for insertion.
+    New(Box<CreateFinding>),
+    /// An existing finding with this fingerprint was updated or reopened.
+    Existing {
+        fingerprint: String,
+        outcome: ProcessOutcome,
+    },
 }
 
-/// Process a single parsed finding: resolve app, check dedup, create if new.
-async fn process_finding(
-    pool: &PgPool,
-    parsed: &crate::parsers::ParsedFinding,
-    initiated_by: Uuid,
-) -> Result<ProcessOutcome, AppError> {
-    // a. Resolve application: try explicit app_code first, then pattern resolver
-    let explicit_app_code = parsed
-        .core
+/// The app code of a parsed finding: its explicit app_code first, then the
+/// asset inventory, then DNS mappings, then the pattern resolver.
+async fn resolve_app_code(pool: &PgPool, core: &CreateFinding) -> Result<Option<String>, AppError> {
+    let explicit_app_code = core
         .metadata
         .get("app_code")
         .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string();
+        .unwrap_or("");
+    if !explicit_app_code.is_empty() {
+        return Ok(Some(explicit_app_code.to_string()));
+    }
 
-    let mut core = parsed.core.clone();
+    let cache = config_cache::global();
+    let fields = extract_resolver_fields(&core.metadata);
+    let mut resolved = cache.asset_map(pool).await?.resolve(&fields);
+    if resolved.is_none() {
+        resolved = cache.dns_mappings(pool).await?.resolve(&fields);
+    }
+    if resolved.is_none() {
+        let patterns = cache.app_code_patterns(pool, &core.source_tool).await?;
+        resolved = app_code_resolver::resolve(&patterns, &fields);
+    }
+    Ok(resolved)
+}
 
-    let resolved_app_code = if !explicit_app_code.is_empty() {
-        Some(explicit_app_code)
-    } else {
-        let patterns = load_patterns(pool, &core.source_tool).await?;
-        if patterns.is_empty() {
-            None
-        } else {
-            let fields = extract_resolver_fields(&core.metadata);
-            app_code_resolver::resolve(&patterns, &fields)
+/// Reject a file uploaded with a token it is outside the scope of: another
+/// parser or source tool, or findings of applications the token may not
+/// write to. Findings resolving to no application are rejected too when the
+/// token is limited to applications.
+async fn check_upload_scope(
+    pool: &PgPool,
+    scope: &UploadScope,
+    parser_type: &ParserType,
+    parse_result: &crate::parsers::ParseResult,
+) -> Result<(), AppError> {
+    scope.check_source(parser_type, &parse_result.source_tool)?;
+    if scope.application_ids.is_empty() {
+        return Ok(());
+    }
+
+    let allowed: Vec<String> =
+        sqlx::query_scalar("SELECT app_code FROM applications WHERE id = ANY($1)")
+            .bind(&scope.application_ids)
+            .fetch_all(pool)
+            .await?;
+    for (i, parsed) in parse_result.findings.iter().enumerate() {
+        match resolve_app_code(pool, &parsed.core).await? {
+            Some(code) if allowed.contains(&code) => {}
+            Some(code) => {
+                return Err(AppError::Forbidden(format!(
+                    "Record {i} belongs to application {code}, which the upload token may not write to"
+                )));
+            }
+            None => {
+                return Err(AppError::Forbidden(format!(
+                    "Record {i} resolves to no application; the upload token is limited to specific applications"
+                )));
+            }
         }
-    };
+    }
+    Ok(())
+}
 
+/// Resolve a single parsed finding: resolve app, then check dedup.
+async fn resolve_finding(
+    pool: &PgPool,
+    parsed: &crate::parsers::ParsedFinding,
+    profiles: &FingerprintProfiles,
+    initiated_by: Uuid,
+) -> Result<Resolution, AppError> {
+    let mut core = parsed.core.clone();
+
+    // a. Resolve the application, creating a stub for unknown app codes
+    let resolved_app_code = resolve_app_code(pool, &core).await?;
     if let Some(app_code) = &resolved_app_code {
         let app =
             application::find_or_create_stub(pool, app_code, &core.source_tool).await?;
         core.application_id = Some(app.id);
     }
 
-    // b. Check deduplication by fingerprint
+    // b. Recompute the fingerprint from the tool's configured components
+    if let Some(components) = profiles.for_tool(&core.source_tool) {
+        let app_code = resolved_app_code.as_deref().unwrap_or_default();
+        let inputs = FingerprintInputs::from_finding(app_code, &core, &parsed.category_data);
+        core.fingerprint = fingerprint::compute_with(&core.finding_category, components, &inputs);
+    }
+
+    // c. Check deduplication by fingerprint
     let dedup_result =
         deduplication::check_and_apply(pool, &core.fingerprint, initiated_by).await?;
 
-    match dedup_result {
-        deduplication::DedupResult::New => {
-            // c. Create finding
-            let _created = finding::create(pool, &core, &parsed.category_data).await?;
-            Ok(ProcessOutcome::Created)
-        }
-        deduplication::DedupResult::Updated(_) => Ok(ProcessOutcome::Deduplicated),
-        deduplication::DedupResult::Reopened(_) => Ok(ProcessOutcome::Reopened),
-    }
+    let outcome = match dedup_result {
+        deduplication::DedupResult::New => return Ok(Resolution::New(Box::new(core))),
+        deduplication::DedupResult::Updated(id) => ProcessOutcome::Deduplicated(id),
+        deduplication::DedupResult::Reopened(id) => ProcessOutcome::Reopened(id),
+    };
+    Ok(Resolution::Existing {
+        fingerprint: core.fingerprint,
+        outcome,
+    })
 }
 
 /// Data needed to insert an ingestion log entry.
@@ -309,6 +594,7 @@ struct IngestionLogInput<'a> {
     duplicates: usize,
     errors: &'a [IngestionError],
     initiated_by: Uuid,
+    upload_token_id: Option<Uuid>,
 }
 
 /// Insert an ingestion log entry matching the `ingestion_logs` table schema.
@@ -321,9 +607,9 @@ async fn log_ingestion(pool: &PgPool, input: &IngestionLogInput<'_>) -> Result<U
             source_tool, ingestion_type, file_name,
             total_records, new_findings, updated_findings, duplicates,
             errors, quarantined, status, error_details,
-            started_at, completed_at, initiated_by
+            started_at, completed_at, initiated_by, upload_token_id
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 0, 'Completed', $9, NOW(), NOW(), $10)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 0, 'Completed', $9, NOW(), NOW(), $10, $11)
         RETURNING id
         "#,
     )
@@ -337,12 +623,51 @@ async fn log_ingestion(pool: &PgPool, input: &IngestionLogInput<'_>) -> Result<U
     .bind(input.errors.len() as i32)
     .bind(&errors_json)
     .bind(input.initiated_by)
+    .bind(input.upload_token_id)
     .fetch_one(pool)
     .await?;
 
     Ok(row)
 }
 
+/// Insert the run's finding occurrences against its ingestion log, scoped to
+/// each finding's application as resolved by the run.
+async fn record_occurrences(
+    pool: &PgPool,
+    ingestion_id: Uuid,
+    source_tool: &str,
+    scan_date: DateTime<Utc>,
+    occurrences: &Occurrences,
+) -> Result<(), AppError> {
+    if occurrences.finding_ids.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO finding_occurrences
+            (finding_id, ingestion_log_id, fingerprint, source_tool, outcome,
+             application_id, scan_date, branch, target_url, normalized_severity)
+        SELECT o.finding_id, $1, o.fingerprint, $2, o.outcome,
+               f.application_id, $3, o.branch, o.target_url, o.normalized_severity
+        FROM UNNEST($4::uuid[], $5::text[], $6::text[], $7::text[], $8::text[], $9::severity_level[])
+            AS o(finding_id, fingerprint, outcome, branch, target_url, normalized_severity)
+        JOIN findings f ON f.id = o.finding_id
+        "#,
+    )
+    .bind(ingestion_id)
+    .bind(source_tool)
+    .bind(scan_date)
+    .bind(&occurrences.finding_ids)
+    .bind(&occurrences.fingerprints)
+    .bind(&occurrences.outcomes)
+    .bind(&occurrences.branches)
+    .bind(&occurrences.target_urls)
+    .bind(&occurrences.severities)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 /// Count total ingestion log entries.
 pub async fn count_history(pool: &PgPool) -> Result<i64, AppError> {
     let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM ingestion_logs")
@@ -419,6 +744,13 @@ mod tests {
         assert_eq!(pt.to_string(), "tenable_was");
     }
 
+    #[test]
+    fn parser_type_semgrep() {
+        let pt: ParserType = serde_json::from_str("\"semgrep\"").unwrap();
+        assert_eq!(pt, ParserType::Semgrep);
+        assert_eq!(pt.to_string(), "semgrep");
+    }
+
     #[test]
     fn ingestion_error_serialization() {
         let err = IngestionError {
@@ -444,6 +776,7 @@ mod tests {
             reopened_findings: 1,
             duplicates: 3,
             quarantined: 0,
+            suppressed: 2,
             error_count: 0,
             error_details: vec![],
         };
@@ -455,6 +788,7 @@ mod tests {
         assert_eq!(json["reopened_findings"], 1);
         assert_eq!(json["duplicates"], 3);
         assert_eq!(json["quarantined"], 0);
+        assert_eq!(json["suppressed"], 2);
         assert_eq!(json["errors"], 0);
     }
 
@@ -514,6 +848,58 @@ mod tests {
         assert!(!field_map.contains_key("last_observed"));
     }
 
+    #[test]
+    fn occurrences_record_outcome_labels() {
+        let id = Uuid::nil();
+        let data = dast("https://app.example.com/login");
+        let mut occurrences = Occurrences::default();
+        let high = SeverityLevel::High;
+        occurrences.push("fp-1", &ProcessOutcome::Created(id), &high, &data);
+        occurrences.push("fp-2", &ProcessOutcome::Deduplicated(id), &high, &data);
+        let low = SeverityLevel::Low;
+        occurrences.push("fp-3", &ProcessOutcome::Reopened(id), &low, &data);
+        assert_eq!(occurrences.outcomes, vec!["new", "updated", "reopened"]);
+        assert_eq!(occurrences.severities[2], SeverityLevel::Low);
+        assert_eq!(occurrences.fingerprints[1], "fp-2");
+        assert_eq!(occurrences.finding_ids.len(), 3);
+    }
+
+    #[test]
+    fn occurrences_record_the_scanned_branch_or_target() {
+        let sast = CategoryData::Sast(
+            serde_json::from_value(serde_json::json!({
+                "file_path": "src/Login.java",
+                "project": "portal",
+                "rule_name": "SQL injection",
+                "rule_id": "java:S3649",
+                "branch": "main",
+                "scanner_tags": []
+            }))
+            .unwrap(),
+        );
+        let mut occurrences = Occurrences::default();
+        let medium = SeverityLevel::Medium;
+        let created = ProcessOutcome::Created(Uuid::nil());
+        occurrences.push("fp-1", &created, &medium, &sast);
+        occurrences.push(
+            "fp-2",
+            &ProcessOutcome::Created(Uuid::nil()),
+            &medium,
+            &dast("https://app.example.com/login"),
+        );
+        assert_eq!(occurrences.branches, vec![Some("main".to_string()), None]);
+        assert_eq!(
+            occurrences.target_urls,
+            vec![None, Some("https://app.example.com/login".to_string())]
+        );
+    }
+
+    fn dast(target_url: &str) -> CategoryData {
+        CategoryData::Dast(
+            serde_json::from_value(serde_json::json!({ "target_url": target_url })).unwrap(),
+        )
+    }
+
     #[test]
     fn resolver_fields_handles_empty_metadata() {
         let metadata = serde_json::json!({});
@@ -0,0 +1,482 @@
+//! Persistent background job queue.
+//!
+//! Jobs are rows in the `jobs` table. Workers claim due jobs with
+//! `FOR UPDATE SKIP LOCKED`, so any number of workers and instances can share
+//! the queue. Failed attempts are retried with exponential backoff until
+//! `max_attempts`; jobs held by a worker that died are requeued once their
+//! lock is older than the configured timeout.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::models::job::{Job, JobStatus, NewJob};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::shutdown::ShutdownListener;
+
+/// Attempts per job when the caller does not say otherwise.
+const DEFAULT_MAX_ATTEMPTS: i32 = 3;
+
+/// Delay before the first retry; doubled for each further attempt.
+const RETRY_BASE_DELAY_SECS: i64 = 30;
+
+/// Longest delay between attempts.
+const RETRY_MAX_DELAY_SECS: i64 = 3600;
+
+/// Stored `last_error` is truncated to this many characters.
+const MAX_ERROR_CHARS: usize = 2000;
+
+/// Filters for the job list.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JobFilters {
+    pub status: Option<JobStatus>,
+    pub kind: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Queue operations
+// ---------------------------------------------------------------------------
+
+/// Requested attempts, or the default.
+fn max_attempts(input: &NewJob) -> Result<i32, AppError> {
+    let max_attempts = input.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+    if max_attempts < 1 {
+        return Err(AppError::Validation(
+            "max_attempts must be at least 1".to_string(),
+        ));
+    }
+    Ok(max_attempts)
+}
+
+/// Add a job to the queue.
+pub async fn enqueue(pool: &PgPool, input: &NewJob) -> Result<Job, AppError> {
+    let max_attempts = max_attempts(input)?;
+
+    let job = sqlx::query_as::<_, Job>(
+        r#"
+        INSERT INTO jobs (kind, payload, run_at, max_attempts, created_by)
+        VALUES ($1, $2, COALESCE($3, NOW()), $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(&input.kind)
+    .bind(&input.payload)
+    .bind(input.run_at)
+    .bind(max_attempts)
+    .bind(input.created_by)
+    .fetch_one(pool)
+    .await?;
+    Ok(job)
+}
+
+/// Enqueue a job unless one of the same kind is already waiting to run.
+///
+/// For idempotent work such as refreshes, where one pending run covers every
+/// request made before it starts. Returns `None` when a job was already queued.
+pub async fn enqueue_once(pool: &PgPool, input: &NewJob) -> Result<Option<Job>, AppError> {
+    let max_attempts = max_attempts(input)?;
+    let mut tx = pool.begin().await?;
+    // Serialize concurrent callers for the same kind
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+        .bind(&input.kind)
+        .execute(&mut *tx)
+        .await?;
+
+    let pending = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM jobs WHERE kind = $1 AND status = 'queued')",
+    )
+    .bind(&input.kind)
+    .fetch_one(&mut *tx)
+    .await?;
+    if pending {
+        return Ok(None);
+    }
+
+    let job = sqlx::query_as::<_, Job>(
+        r#"
+        INSERT INTO jobs (kind, payload, run_at, max_attempts, created_by)
+        VALUES ($1, $2, COALESCE($3, NOW()), $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(&input.kind)
+    .bind(&input.payload)
+    .bind(input.run_at)
+    .bind(max_attempts)
+    .bind(input.created_by)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(Some(job))
+}
+
+/// List jobs, newest first.
+pub async fn list(
+    pool: &PgPool,
+    filters: &JobFilters,
+    pagination: &Pagination,
+) -> Result<PagedResult<Job>, AppError> {
+    let total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*) FROM jobs
+        WHERE ($1::job_status IS NULL OR status = $1)
+          AND ($2::text IS NULL OR kind = $2)
+        "#,
+    )
+    .bind(filters.status)
+    .bind(filters.kind.as_deref())
+    .fetch_one(pool)
+    .await?;
+
+    let jobs = sqlx::query_as::<_, Job>(
+        r#"
+        SELECT * FROM jobs
+        WHERE ($1::job_status IS NULL OR status = $1)
+          AND ($2::text IS NULL OR kind = $2)
+        ORDER BY created_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(filters.status)
+    .bind(filters.kind.as_deref())
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(jobs, total, pagination))
+}
+
+/// Fetch a single job.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Job, AppError> {
+    sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Job {id} not found")))
+}
+
+/// Requeue a failed job with a fresh set of attempts.
+pub async fn retry(pool: &PgPool, id: Uuid) -> Result<Job, AppError> {
+    let job = find_by_id(pool, id).await?;
+    if job.status != JobStatus::Failed {
+        return Err(AppError::Conflict(format!(
+            "Only failed jobs can be retried; job {id} is {:?}",
+            job.status
+        )));
+    }
+
+    let job = sqlx::query_as::<_, Job>(
+        r#"
+        UPDATE jobs
+        SET status = 'queued', attempts = 0, run_at = NOW(), completed_at = NULL
+        WHERE id = $1 AND status = 'failed'
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::Conflict(format!("Job {id} changed while retrying")))?;
+    Ok(job)
+}
+
+/// Claim the next due job of a registered kind, marking it running.
+async fn claim_next(pool: &PgPool, kinds: &[String]) -> Result<Option<Job>, AppError> {
+    let job = sqlx::query_as::<_, Job>(
+        r#"
+        UPDATE jobs
+        SET status = 'running', attempts = attempts + 1, locked_at = NOW(), started_at = NOW()
+        WHERE id = (
+            SELECT id FROM jobs
+            WHERE status = 'queued' AND run_at <= NOW() AND kind = ANY($1)
+            ORDER BY run_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(kinds)
+    .fetch_optional(pool)
+    .await?;
+    Ok(job)
+}
+
+/// Record a successful attempt.
+async fn mark_succeeded(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        UPDATE jobs
+        SET status = 'succeeded', locked_at = NULL, last_error = NULL, completed_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Record a failed attempt: requeue with backoff, or fail once attempts run out.
+async fn mark_failed(pool: &PgPool, job: &Job, error: &str) -> Result<(), AppError> {
+    let error: String = error.chars().take(MAX_ERROR_CHARS).collect();
+    if job.attempts >= job.max_attempts {
+        sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'failed', locked_at = NULL, last_error = $2, completed_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(job.id)
+        .bind(&error)
+        .execute(pool)
+        .await?;
+    } else {
+        sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'queued', locked_at = NULL, last_error = $2,
+                run_at = NOW() + make_interval(secs => $3)
+            WHERE id = $1
+            "#,
+        )
+        .bind(job.id)
+        .bind(&error)
+        .bind(retry_delay_secs(job.attempts) as f64)
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Requeue (or fail, when out of attempts) jobs whose worker stopped
+/// reporting before `lock_timeout` elapsed.
+async fn recover_stale(pool: &PgPool, lock_timeout: Duration) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE jobs
+        SET status = CASE WHEN attempts >= max_attempts THEN 'failed' ELSE 'queued' END::job_status,
+            locked_at = NULL,
+            last_error = 'Worker stopped before the job finished',
+            completed_at = CASE WHEN attempts >= max_attempts THEN NOW() END
+        WHERE status = 'running' AND locked_at < NOW() - make_interval(secs => $1)
+        "#,
+    )
+    .bind(lock_timeout.as_secs_f64())
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Seconds to wait after the given (1-based) failed attempt.
+fn retry_delay_secs(attempt: i32) -> i64 {
+    let exponent = attempt.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE_DELAY_SECS
+        .saturating_mul(1 << exponent)
+        .min(RETRY_MAX_DELAY_SECS)
+}
+
+// ---------------------------------------------------------------------------
+// Handlers and workers
+// ---------------------------------------------------------------------------
+
+/// Future returned by a job handler.
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<(), AppError>> + Send>>;
+
+type Handler = Arc<dyn Fn(PgPool, serde_json::Value) -> JobFuture + Send + Sync>;
+
+/// Handlers by job kind. Workers only claim kinds registered here, so
+/// instances running different versions can share one queue.
+#[derive(Clone, Default)]
+pub struct JobRegistry {
+    handlers: HashMap<String, Handler>,
+}
+
+impl std::fmt::Debug for JobRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobRegistry")
+            .field("kinds", &self.kinds())
+            .finish()
+    }
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the handler for `kind`, called with the job's payload.
+    pub fn register<F, Fut>(mut self, kind: &str, handler: F) -> Self
+    where
+        F: Fn(PgPool, serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), AppError>> + Send + 'static,
+    {
+        let handler: Handler = Arc::new(move |pool, payload| Box::pin(handler(pool, payload)));
+        self.handlers.insert(kind.to_string(), handler);
+        self
+    }
+
+    /// Registered kinds, sorted.
+    pub fn kinds(&self) -> Vec<String> {
+        let mut kinds: Vec<String> = self.handlers.keys().cloned().collect();
+        kinds.sort();
+        kinds
+    }
+}
+
+/// Worker pool settings.
+#[derive(Debug, Clone)]
+pub struct WorkerSettings {
+    pub workers: usize,
+    /// Idle wait between polls when the queue is empty.
+    pub poll_interval: Duration,
+    /// Running jobs locked for longer than this are recovered.
+    pub lock_timeout: Duration,
+}
+
+impl WorkerSettings {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            workers: config.job_workers,
+            poll_interval: Duration::from_secs(config.job_poll_interval_secs.max(1)),
+            lock_timeout: Duration::from_secs(config.job_lock_timeout_secs.max(1)),
+        }
+    }
+}
+
+/// Run one claimed job to completion and record the outcome. A panicking
+/// handler counts as a failed attempt.
+async fn run_job(pool: &PgPool, handler: &Handler, job: Job) -> Result<(), AppError> {
+    let outcome = tokio::spawn(handler(pool.clone(), job.payload.clone())).await;
+    match outcome {
+        Ok(Ok(())) => {
+            tracing::info!(job_id = %job.id, kind = %job.kind, attempt = job.attempts, "Job succeeded");
+            mark_succeeded(pool, job.id).await
+        }
+        Ok(Err(e)) => {
+            tracing::warn!(job_id = %job.id, kind = %job.kind, attempt = job.attempts, error = %e, "Job failed");
+            mark_failed(pool, &job, &e.to_string()).await
+        }
+        Err(e) => {
+            tracing::error!(job_id = %job.id, kind = %job.kind, attempt = job.attempts, error = %e, "Job panicked");
+            mark_failed(pool, &job, "Job handler panicked").await
+        }
+    }
+}
+
+/// Claim and run jobs until none are due or shutdown starts. Returns the
+/// number processed.
+async fn drain(
+    pool: &PgPool,
+    registry: &JobRegistry,
+    kinds: &[String],
+    shutdown: &ShutdownListener,
+) -> Result<usize, AppError> {
+    let mut processed = 0;
+    while !shutdown.is_triggered() {
+        let Some(job) = claim_next(pool, kinds).await? else {
+            break;
+        };
+        let Some(handler) = registry.handlers.get(&job.kind) else {
+            // Unreachable while claims are limited to registered kinds
+            mark_failed(pool, &job, "No handler registered").await?;
+            continue;
+        };
+        run_job(pool, handler, job).await?;
+        processed += 1;
+    }
+    Ok(processed)
+}
+
+/// Start `settings.workers` workers processing the registered job kinds.
+///
+/// Workers stop claiming jobs once `shutdown` triggers and exit after the job
+/// they are running; await the returned handles to drain them. Does nothing
+/// when no workers or no handlers are configured. Must be called from within
+/// a Tokio runtime.
+pub fn spawn_workers(
+    pool: PgPool,
+    registry: JobRegistry,
+    settings: WorkerSettings,
+    shutdown: ShutdownListener,
+) -> Vec<JoinHandle<()>> {
+    let kinds = registry.kinds();
+    if settings.workers == 0 || kinds.is_empty() {
+        return Vec::new();
+    }
+
+    (0..settings.workers)
+        .map(|worker| {
+            let (pool, registry, kinds, settings, mut shutdown) = (
+                pool.clone(),
+                registry.clone(),
+                kinds.clone(),
+                settings.clone(),
+                shutdown.clone(),
+            );
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(settings.poll_interval);
+                loop {
+                    tokio::select! {
+                        _ = ticker.tick() => {}
+                        _ = shutdown.triggered() => break,
+                    }
+                    // One worker is enough to sweep stale locks
+                    if worker == 0 {
+                        match recover_stale(&pool, settings.lock_timeout).await {
+                            Ok(0) => {}
+                            Ok(n) => tracing::warn!(recovered = n, "Recovered stale jobs"),
+                            Err(e) => tracing::error!(error = %e, "Stale job recovery failed"),
+                        }
+                    }
+                    if let Err(e) = drain(&pool, &registry, &kinds, &shutdown).await {
+                        tracing::error!(worker, error = %e, "Job worker tick failed");
+                    }
+                }
+                tracing::debug!(worker, "Job worker stopped");
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay_secs(1), 30);
+        assert_eq!(retry_delay_secs(2), 60);
+        assert_eq!(retry_delay_secs(3), 120);
+        assert_eq!(retry_delay_secs(10), RETRY_MAX_DELAY_SECS);
+        assert_eq!(retry_delay_secs(i32::MAX), RETRY_MAX_DELAY_SECS);
+    }
+
+    #[test]
+    fn registry_lists_registered_kinds() {
+        let registry = JobRegistry::new()
+            .register("b.second", |_, _| async { Ok(()) })
+            .register("a.first", |_, _| async { Ok(()) });
+        assert_eq!(registry.kinds(), ["a.first", "b.second"]);
+    }
+
+    #[test]
+    fn job_status_uses_snake_case() {
+        let status: JobStatus = serde_json::from_str("\"succeeded\"").unwrap();
+        assert_eq!(status, JobStatus::Succeeded);
+    }
+}
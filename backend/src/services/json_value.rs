@@ -0,0 +1,52 @@
+//! Helpers for reading loosely typed JSON from scanners, SBOMs, and JSONB
+//! columns.
+
+use serde_json::Value;
+
+/// The string elements of a JSON array; empty when `value` is not an array.
+/// Non-string elements are skipped.
+pub fn json_strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A string field of a JSON object, trimmed; `None` when missing, not a
+/// string, or blank.
+pub fn str_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn json_strings_skips_non_strings() {
+        assert_eq!(
+            json_strings(&json!(["CWE-79", 89, null, "CWE-89"])),
+            ["CWE-79", "CWE-89"]
+        );
+        assert!(json_strings(&json!({"CWE-79": true})).is_empty());
+    }
+
+    #[test]
+    fn str_field_ignores_blank_and_non_string_values() {
+        let doc = json!({"name": " log4j-core ", "blank": "  ", "version": 2});
+        assert_eq!(str_field(&doc, "name").as_deref(), Some("log4j-core"));
+        assert_eq!(str_field(&doc, "blank"), None);
+        assert_eq!(str_field(&doc, "version"), None);
+        assert_eq!(str_field(&doc, "missing"), None);
+    }
+}
@@ -0,0 +1,490 @@
+//! License compliance policies for SCA findings.
+//!
+//! SCA scanners report the license of each vulnerable package. A deny policy
+//! flags packages under the licenses it lists, in every application; a
+//! copyleft policy flags packages under a copyleft license in applications
+//! marked as distributed. Violations are stored as annotations on the
+//! finding, recomputed for every open SCA finding when a policy changes and
+//! for the run's findings after each ingestion.
+//!
+//! License expressions are read the SPDX way: `OR` (or `/`) separates
+//! alternatives, of which the user may pick any, while `AND` (or `,`) joins
+//! licenses that all apply. A package violates a policy only when every
+//! alternative contains a license the policy flags.
+
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::license_policy::{
+    ApplicationLicenseReport, LicensePolicy, LicensePolicyRule, LicenseUsage, LicenseViolation,
+    UpsertLicensePolicy,
+};
+
+/// Copyleft licenses flagged by a copyleft policy listing none, in matched form.
+pub const COPYLEFT_LICENSES: &[&str] = &[
+    "gpl",
+    "gpl-1.0",
+    "gpl-2.0",
+    "gpl-3.0",
+    "agpl",
+    "agpl-1.0",
+    "agpl-3.0",
+    "lgpl",
+    "lgpl-2.0",
+    "lgpl-2.1",
+    "lgpl-3.0",
+    "mpl-1.0",
+    "mpl-1.1",
+    "mpl-2.0",
+    "epl-1.0",
+    "epl-2.0",
+    "cddl-1.0",
+    "cddl-1.1",
+    "eupl-1.1",
+    "eupl-1.2",
+    "osl-3.0",
+    "sspl-1.0",
+    "cc-by-sa-4.0",
+];
+
+/// Open findings, as a condition on `f`.
+const OPEN: &str = "f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') \
+                    AND f.archived_at IS NULL";
+
+/// A license id in matched form: lower case, without the `-only`,
+/// `-or-later` and `+` version qualifiers.
+fn matched_form(id: &str) -> String {
+    let id = id.trim().to_lowercase();
+    let id = id.trim_end_matches('+');
+    let id = id
+        .strip_suffix("-only")
+        .or_else(|| id.strip_suffix("-or-later"))
+        .unwrap_or(id);
+    id.to_string()
+}
+
+/// The alternatives of a license expression, each as the licenses that all
+/// apply, in matched form.
+fn alternatives(expression: &str) -> Vec<Vec<String>> {
+    let spaced: String = expression
+        .chars()
+        .map(|c| match c {
+            '(' | ')' => " ".to_string(),
+            ',' => " and ".to_string(),
+            '/' => " or ".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+
+    let mut alternatives = vec![Vec::new()];
+    let mut tokens = spaced.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token.to_lowercase().as_str() {
+            "or" => alternatives.push(Vec::new()),
+            "and" => {}
+            // The exception only relaxes the license it applies to.
+            "with" => {
+                tokens.next();
+            }
+            _ => alternatives
+                .last_mut()
+                .expect("alternatives is never empty")
+                .push(matched_form(token)),
+        }
+    }
+    alternatives.retain(|alternative| !alternative.is_empty());
+    alternatives
+}
+
+/// The license of `expression` that violates a policy flagging `flagged`,
+/// if every alternative contains one.
+fn violating_license(flagged: &[String], expression: &str) -> Option<String> {
+    let alternatives = alternatives(expression);
+    let mut first = None;
+    for alternative in &alternatives {
+        let hit = alternative.iter().find(|id| flagged.contains(id))?;
+        first.get_or_insert_with(|| hit.clone());
+    }
+    first
+}
+
+/// The licenses a policy flags, in matched form.
+fn flagged_licenses(rule: LicensePolicyRule, licenses: &[String]) -> Vec<String> {
+    if rule == LicensePolicyRule::CopyleftDistributed && licenses.is_empty() {
+        COPYLEFT_LICENSES.iter().map(|id| id.to_string()).collect()
+    } else {
+        licenses.to_vec()
+    }
+}
+
+/// The policy's licenses in matched form, checked for its rule.
+fn normalize_licenses(input: &UpsertLicensePolicy) -> Result<Vec<String>, AppError> {
+    let mut licenses = Vec::new();
+    for license in &input.licenses {
+        let license = matched_form(license);
+        if license.is_empty() || license.len() > 100 {
+            return Err(AppError::Validation(
+                "License ids must be 1 to 100 characters long".to_string(),
+            ));
+        }
+        if !licenses.contains(&license) {
+            licenses.push(license);
+        }
+    }
+    if input.rule == LicensePolicyRule::Deny && licenses.is_empty() {
+        return Err(AppError::Validation(
+            "A deny policy must list at least one license".to_string(),
+        ));
+    }
+    Ok(licenses)
+}
+
+// ---------------------------------------------------------------------------
+// Evaluation
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, FromRow)]
+struct PolicyRow {
+    id: Uuid,
+    rule: LicensePolicyRule,
+    licenses: Vec<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct Candidate {
+    finding_id: Uuid,
+    license: String,
+    is_distributed: bool,
+}
+
+/// Recompute the violations of open SCA findings: those seen by ingestion
+/// run `ingestion_log_id`, or all of them. Returns the number of violations
+/// in scope afterwards.
+pub async fn evaluate(pool: &PgPool, ingestion_log_id: Option<Uuid>) -> Result<usize, AppError> {
+    let policies = sqlx::query_as::<_, PolicyRow>(
+        "SELECT id, rule, licenses FROM license_policies WHERE enabled",
+    )
+    .fetch_all(pool)
+    .await?;
+    let candidates = sqlx::query_as::<_, Candidate>(&format!(
+        r#"
+        SELECT f.id AS finding_id, s.license, COALESCE(a.is_distributed, false) AS is_distributed
+        FROM findings f
+        JOIN finding_sca s ON s.finding_id = f.id
+        LEFT JOIN applications a ON a.id = f.application_id
+        WHERE {OPEN}
+          AND s.license IS NOT NULL
+          AND ($1::uuid IS NULL OR f.id IN (
+              SELECT finding_id FROM finding_occurrences WHERE ingestion_log_id = $1
+          ))
+        "#
+    ))
+    .bind(ingestion_log_id)
+    .fetch_all(pool)
+    .await?;
+
+    let flagged: Vec<(&PolicyRow, Vec<String>)> = policies
+        .iter()
+        .map(|p| (p, flagged_licenses(p.rule, &p.licenses)))
+        .collect();
+    let mut policy_ids = Vec::new();
+    let mut finding_ids = Vec::new();
+    let mut licenses = Vec::new();
+    for candidate in &candidates {
+        for (policy, flagged) in &flagged {
+            if policy.rule == LicensePolicyRule::CopyleftDistributed && !candidate.is_distributed {
+                continue;
+            }
+            if let Some(license) = violating_license(flagged, &candidate.license) {
+                policy_ids.push(policy.id);
+                finding_ids.push(candidate.finding_id);
+                licenses.push(license);
+            }
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+        DELETE FROM license_violations v
+        WHERE ($3::uuid IS NULL OR v.finding_id IN (
+                  SELECT finding_id FROM finding_occurrences WHERE ingestion_log_id = $3
+              ))
+          AND NOT EXISTS (
+              SELECT 1 FROM UNNEST($1::uuid[], $2::uuid[]) AS k(policy_id, finding_id)
+              WHERE k.policy_id = v.policy_id AND k.finding_id = v.finding_id
+          )
+        "#,
+    )
+    .bind(&policy_ids)
+    .bind(&finding_ids)
+    .bind(ingestion_log_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO license_violations (policy_id, finding_id, license)
+        SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::text[])
+        ON CONFLICT (policy_id, finding_id) DO UPDATE SET license = EXCLUDED.license
+        "#,
+    )
+    .bind(&policy_ids)
+    .bind(&finding_ids)
+    .bind(&licenses)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(policy_ids.len())
+}
+
+/// Recompute the violations of an ingestion run's findings. Best effort: a
+/// failure is logged and never fails the ingestion.
+pub async fn record_ingestion(pool: &PgPool, ingestion_log_id: Uuid) {
+    if let Err(e) = evaluate(pool, Some(ingestion_log_id)).await {
+        tracing::warn!(%ingestion_log_id, error = %e, "Failed to evaluate license policies");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Policies
+// ---------------------------------------------------------------------------
+
+/// Policy columns plus the open findings violating it.
+fn select_policy() -> String {
+    format!(
+        r#"
+        SELECT p.*,
+               (
+                   SELECT COUNT(*) FROM license_violations v
+                   JOIN findings f ON f.id = v.finding_id
+                   WHERE v.policy_id = p.id AND {OPEN}
+               ) AS violation_count
+        FROM license_policies p
+        "#
+    )
+}
+
+pub async fn list(pool: &PgPool) -> Result<Vec<LicensePolicy>, AppError> {
+    let policies =
+        sqlx::query_as::<_, LicensePolicy>(&format!("{} ORDER BY p.name", select_policy()))
+            .fetch_all(pool)
+            .await?;
+    Ok(policies)
+}
+
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<LicensePolicy, AppError> {
+    sqlx::query_as::<_, LicensePolicy>(&format!("{} WHERE p.id = $1", select_policy()))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("License policy {id} not found")))
+}
+
+/// Create a policy and flag the open findings violating it.
+pub async fn create(
+    pool: &PgPool,
+    input: &UpsertLicensePolicy,
+    created_by: Uuid,
+) -> Result<LicensePolicy, AppError> {
+    let licenses = normalize_licenses(input)?;
+    let name = input.name.trim();
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO license_policies (name, rule, licenses, severity, enabled, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id
+        "#,
+    )
+    .bind(name)
+    .bind(input.rule)
+    .bind(&licenses)
+    .bind(&input.severity)
+    .bind(input.enabled)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| AppError::name_conflict(e, "license policy", name))?;
+
+    evaluate(pool, None).await?;
+    find_by_id(pool, id).await
+}
+
+/// Replace a policy and recompute violations.
+pub async fn update(
+    pool: &PgPool,
+    id: Uuid,
+    input: &UpsertLicensePolicy,
+) -> Result<LicensePolicy, AppError> {
+    let licenses = normalize_licenses(input)?;
+    let name = input.name.trim();
+    let updated = sqlx::query(
+        r#"
+        UPDATE license_policies
+        SET name = $2, rule = $3, licenses = $4, severity = $5, enabled = $6
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(name)
+    .bind(input.rule)
+    .bind(&licenses)
+    .bind(&input.severity)
+    .bind(input.enabled)
+    .execute(pool)
+    .await
+    .map_err(|e| AppError::name_conflict(e, "license policy", name))?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("License policy {id} not found")));
+    }
+
+    evaluate(pool, None).await?;
+    find_by_id(pool, id).await
+}
+
+/// Delete a policy and its violations.
+pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    let deleted = sqlx::query("DELETE FROM license_policies WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("License policy {id} not found")));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Application report
+// ---------------------------------------------------------------------------
+
+/// Licenses of an application's open SCA findings, and their violations.
+pub async fn application_report(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<ApplicationLicenseReport, AppError> {
+    let (app_code, is_distributed) = sqlx::query_as::<_, (String, bool)>(
+        "SELECT app_code, is_distributed FROM applications WHERE id = $1",
+    )
+    .bind(application_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Application {application_id} not found")))?;
+
+    let licenses = sqlx::query_as::<_, LicenseUsage>(&format!(
+        r#"
+        SELECT s.license,
+               COUNT(DISTINCT (lower(s.package_name), s.package_version)) AS packages,
+               COUNT(*) AS findings
+        FROM findings f
+        JOIN finding_sca s ON s.finding_id = f.id
+        WHERE f.application_id = $1 AND {OPEN}
+        GROUP BY s.license
+        ORDER BY packages DESC, s.license NULLS LAST
+        "#
+    ))
+    .bind(application_id)
+    .fetch_all(pool)
+    .await?;
+
+    let violations = sqlx::query_as::<_, LicenseViolation>(&format!(
+        r#"
+        SELECT v.policy_id, p.name AS policy_name, p.rule, p.severity, v.finding_id,
+               s.package_name, s.package_version, v.license, v.detected_at
+        FROM license_violations v
+        JOIN license_policies p ON p.id = v.policy_id
+        JOIN findings f ON f.id = v.finding_id
+        JOIN finding_sca s ON s.finding_id = f.id
+        WHERE f.application_id = $1 AND {OPEN}
+        ORDER BY p.severity, p.name, s.package_name
+        "#
+    ))
+    .bind(application_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ApplicationLicenseReport {
+        application_id,
+        app_code,
+        is_distributed,
+        licenses,
+        violations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::SeverityLevel;
+
+    fn flagged(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn version_qualifiers_are_ignored() {
+        assert_eq!(matched_form(" GPL-3.0-only "), "gpl-3.0");
+        assert_eq!(matched_form("GPL-2.0-or-later"), "gpl-2.0");
+        assert_eq!(matched_form("LGPL-2.1+"), "lgpl-2.1");
+        assert_eq!(matched_form("MIT"), "mit");
+    }
+
+    #[test]
+    fn expressions_split_into_alternatives() {
+        assert_eq!(
+            alternatives("(MIT OR GPL-2.0-only) AND BSD-3-Clause"),
+            vec![vec!["mit"], vec!["gpl-2.0", "bsd-3-clause"]]
+        );
+        assert_eq!(
+            alternatives("Apache-2.0, GPL-2.0 WITH Classpath-exception-2.0"),
+            vec![vec!["apache-2.0", "gpl-2.0"]]
+        );
+        assert_eq!(
+            alternatives("MIT/Apache-2.0"),
+            vec![vec!["mit"], vec!["apache-2.0"]]
+        );
+    }
+
+    #[test]
+    fn a_license_choice_avoids_the_violation() {
+        let gpl = flagged(&["gpl-3.0"]);
+        assert_eq!(
+            violating_license(&gpl, "GPL-3.0-only"),
+            Some("gpl-3.0".to_string())
+        );
+        assert_eq!(violating_license(&gpl, "MIT OR GPL-3.0-only"), None);
+        assert_eq!(
+            violating_license(&gpl, "MIT, GPL-3.0-or-later"),
+            Some("gpl-3.0".to_string())
+        );
+        assert_eq!(violating_license(&gpl, ""), None);
+    }
+
+    #[test]
+    fn copyleft_rule_defaults_to_builtin_list() {
+        let licenses = flagged_licenses(LicensePolicyRule::CopyleftDistributed, &[]);
+        assert!(licenses.contains(&"agpl-3.0".to_string()));
+        assert_eq!(
+            flagged_licenses(LicensePolicyRule::Deny, &flagged(&["sspl-1.0"])),
+            flagged(&["sspl-1.0"])
+        );
+    }
+
+    #[test]
+    fn deny_policy_requires_licenses() {
+        let mut input = UpsertLicensePolicy {
+            name: "No AGPL".to_string(),
+            rule: LicensePolicyRule::Deny,
+            licenses: Vec::new(),
+            severity: SeverityLevel::High,
+            enabled: true,
+        };
+        assert!(matches!(
+            normalize_licenses(&input),
+            Err(AppError::Validation(_))
+        ));
+        input.licenses = vec!["AGPL-3.0-only".to_string(), "agpl-3.0".to_string()];
+        assert_eq!(normalize_licenses(&input).unwrap(), flagged(&["agpl-3.0"]));
+    }
+}
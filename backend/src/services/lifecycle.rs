@@ -255,13 +255,10 @@ pub async fn should_hold_for_triage(
     _application: Option<&crate::models::application::Application>,
 ) -> Result<bool, AppError> {
     // Check if auto-confirm is enabled
-    let auto_confirm = sqlx::query_scalar::<_, serde_json::Value>(
-        "SELECT value FROM system_config WHERE key = 'auto_confirm_enabled'",
-    )
-    .fetch_optional(pool)
-    .await?
-    .and_then(|v| v.as_bool())
-    .unwrap_or(true);
+    let auto_confirm = crate::services::config_cache::global()
+        .settings(pool)
+        .await?
+        .auto_confirm_enabled();
 
     if !auto_confirm {
         return Ok(true); // Hold for triage when auto-confirm disabled
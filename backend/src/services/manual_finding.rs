@@ -0,0 +1,223 @@
+//! Manual finding entry.
+//!
+//! Penetration test and bug bounty results have no report format to parse:
+//! analysts enter them one at a time. Each becomes a regular finding of
+//! category `MANUAL`, with the pentest or bug bounty program as its source
+//! tool, so it is listed, scored and tracked against SLA alongside scanner
+//! findings. Its fingerprint is built from the application, the affected
+//! asset and the title, which catches the same issue being entered twice.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::errors::AppError;
+use crate::models::finding::{CreateFinding, Finding, FindingCategory, SeverityLevel};
+use crate::models::finding_manual::{CreateFindingManual, ManualFindingSource};
+use crate::services::finding::{self, CategoryData};
+use crate::services::fingerprint;
+
+/// A penetration test or bug bounty finding entered by hand.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CreateManualFinding {
+    pub source: ManualFindingSource,
+    pub application_id: Uuid,
+    #[validate(length(min = 1, max = 1000))]
+    pub title: String,
+    #[validate(length(min = 1))]
+    pub description: String,
+    pub severity: SeverityLevel,
+    #[validate(range(min = 0.0, max = 10.0))]
+    pub cvss_score: Option<f32>,
+    #[validate(length(max = 255))]
+    pub cvss_vector: Option<String>,
+    /// Host, URL, component or anything else the finding was reported against.
+    #[validate(length(min = 1, max = 2000))]
+    pub affected_asset: String,
+    /// Reproduction steps, requests and responses, screenshots references.
+    pub evidence: Option<String>,
+    /// Tester or researcher who reported the finding.
+    #[validate(length(min = 1, max = 255))]
+    pub reporter: String,
+    /// Pentest engagement or bug bounty program.
+    #[validate(length(max = 255))]
+    pub engagement: Option<String>,
+    /// When the finding was reported; defaults to entry time.
+    pub reported_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cwe_ids: Vec<String>,
+    #[serde(default)]
+    pub cve_ids: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub remediation_guidance: Option<String>,
+}
+
+/// Trimmed required text, or a validation error naming `field`.
+fn required<'a>(value: &'a str, field: &str) -> Result<&'a str, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(AppError::Validation(format!("{field} must not be blank")));
+    }
+    Ok(value)
+}
+
+/// The finding and category rows to store for an entry of `app_code`.
+fn build(
+    input: &CreateManualFinding,
+    app_code: &str,
+    actor_name: &str,
+) -> Result<(CreateFinding, CategoryData), AppError> {
+    let title = required(&input.title, "title")?;
+    let affected_asset = required(&input.affected_asset, "affected_asset")?;
+    let reporter = required(&input.reporter, "reporter")?;
+
+    let core = CreateFinding {
+        source_tool: input.source.source_tool().to_string(),
+        source_tool_version: None,
+        source_finding_id: Uuid::new_v4().to_string(),
+        finding_category: FindingCategory::Manual,
+        title: title.to_string(),
+        description: input.description.clone(),
+        normalized_severity: input.severity.clone(),
+        original_severity: format!("{:?}", input.severity),
+        cvss_score: input.cvss_score,
+        cvss_vector: input.cvss_vector.clone(),
+        cwe_ids: input.cwe_ids.clone(),
+        cve_ids: input.cve_ids.clone(),
+        owasp_category: None,
+        confidence: None,
+        fingerprint: fingerprint::compute_manual(app_code, affected_asset, title),
+        application_id: Some(input.application_id),
+        tags: input.tags.clone(),
+        remediation_guidance: input.remediation_guidance.clone(),
+        raw_finding: serde_json::to_value(input).unwrap_or_default(),
+        metadata: serde_json::json!({ "entered_by": actor_name }),
+    };
+    let manual = CategoryData::Manual(CreateFindingManual {
+        source: input.source,
+        affected_asset: affected_asset.to_string(),
+        evidence: input.evidence.clone(),
+        reporter: reporter.to_string(),
+        engagement: input.engagement.clone(),
+        reported_at: Some(input.reported_at.unwrap_or_else(Utc::now)),
+    });
+    Ok((core, manual))
+}
+
+/// Create a manual finding. Fails with a conflict when an open finding
+/// already tracks the same issue on the same asset.
+pub async fn create(
+    pool: &PgPool,
+    input: &CreateManualFinding,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<Finding, AppError> {
+    let app_code =
+        sqlx::query_scalar::<_, String>("SELECT app_code FROM applications WHERE id = $1")
+            .bind(input.application_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Application {} not found", input.application_id))
+            })?;
+
+    let (core, manual) = build(input, &app_code, actor_name)?;
+
+    let existing = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT id FROM findings
+        WHERE fingerprint = $1
+          AND status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND archived_at IS NULL
+        LIMIT 1
+        "#,
+    )
+    .bind(&core.fingerprint)
+    .fetch_optional(pool)
+    .await?;
+    if let Some(id) = existing {
+        return Err(AppError::Conflict(format!(
+            "Finding {id} already tracks this issue on {}",
+            input.affected_asset.trim()
+        )));
+    }
+
+    let created = finding::create(pool, &core, &manual).await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('finding', $1, 'manual_finding_created', $2, $3, $4)
+        "#,
+    )
+    .bind(created.id)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({
+        "source": input.source,
+        "reporter": input.reporter.trim(),
+        "engagement": input.engagement,
+    }))
+    .execute(pool)
+    .await?;
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> CreateManualFinding {
+        CreateManualFinding {
+            source: ManualFindingSource::Pentest,
+            application_id: Uuid::nil(),
+            title: " Weak TLS configuration ".to_string(),
+            description: "TLS 1.0 is accepted".to_string(),
+            severity: SeverityLevel::Medium,
+            cvss_score: Some(5.3),
+            cvss_vector: None,
+            affected_asset: "vpn.example.com".to_string(),
+            evidence: Some("sslscan output".to_string()),
+            reporter: "External tester".to_string(),
+            engagement: Some("2026 Q3 external pentest".to_string()),
+            reported_at: None,
+            cwe_ids: vec!["326".to_string()],
+            cve_ids: Vec::new(),
+            tags: Vec::new(),
+            remediation_guidance: None,
+        }
+    }
+
+    #[test]
+    fn entry_becomes_a_manual_finding_keyed_by_asset_and_title() {
+        let (core, manual) = build(&entry(), "APP1", "alice").unwrap();
+        assert_eq!(core.finding_category, FindingCategory::Manual);
+        assert_eq!(core.source_tool, "Pentest");
+        assert_eq!(core.title, "Weak TLS configuration");
+        assert_eq!(core.original_severity, "Medium");
+        assert_eq!(
+            core.fingerprint,
+            fingerprint::compute_manual("APP1", "vpn.example.com", "Weak TLS configuration")
+        );
+        let CategoryData::Manual(manual) = manual else {
+            panic!("expected manual category data");
+        };
+        assert_eq!(manual.reporter, "External tester");
+        assert!(manual.reported_at.is_some());
+    }
+
+    #[test]
+    fn blank_required_fields_are_rejected() {
+        let mut input = entry();
+        input.affected_asset = "   ".to_string();
+        assert!(matches!(
+            build(&input, "APP1", "alice"),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+//! `@username` mentions in finding comments.
+//!
+//! Mentions are extracted server-side so clients cannot notify arbitrary
+//! users: only names written in the comment text and matching an active
+//! user are recorded. Code spans and fenced code blocks are skipped, as are
+//! `@` signs inside words such as email addresses.
+
+use std::collections::HashSet;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Longest username considered, matching the `users.username` column.
+const MAX_USERNAME_LEN: usize = 100;
+
+/// Whether `c` can appear in a mentioned username.
+fn is_username_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')
+}
+
+/// Usernames mentioned in markdown `content`, lowercased and in order of
+/// first appearance.
+pub fn extract(content: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut mentions = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut in_code = false;
+        let mut prev: Option<char> = None;
+        let mut chars = line.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c == '`' {
+                in_code = !in_code;
+            } else if c == '@'
+                && !in_code
+                && !prev.is_some_and(|p| is_username_char(p) || matches!(p, '@' | '/'))
+            {
+                let rest = &line[i + 1..];
+                let end = rest
+                    .find(|c: char| !is_username_char(c))
+                    .unwrap_or(rest.len());
+                // Sentence punctuation after a name is not part of it
+                let name = rest[..end].trim_end_matches(['.', '-']);
+                if !name.is_empty() && name.len() <= MAX_USERNAME_LEN {
+                    let name = name.to_ascii_lowercase();
+                    if seen.insert(name.clone()) {
+                        mentions.push(name);
+                    }
+                }
+                for _ in 0..end {
+                    chars.next();
+                }
+                prev = rest[..end].chars().last().or(Some(c));
+                continue;
+            }
+            prev = Some(c);
+        }
+    }
+    mentions
+}
+
+/// IDs of the active users mentioned in `content`, excluding `author_id`.
+pub async fn resolve(pool: &PgPool, content: &str, author_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+    let names = extract(content);
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids = sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM users WHERE lower(username) = ANY($1) AND is_active AND id <> $2",
+    )
+    .bind(&names)
+    .bind(author_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_mentions_in_order_without_duplicates() {
+        assert_eq!(
+            extract("@alice please check with @Bob.Smith and @alice."),
+            vec!["alice", "bob.smith"]
+        );
+        assert_eq!(extract("(cc @dev_ops-team)"), vec!["dev_ops-team"]);
+    }
+
+    #[test]
+    fn ignores_emails_paths_and_bare_signs() {
+        assert!(extract("mail alice@example.com or see /users/@me").is_empty());
+        assert!(extract("costs @ 5 units, @@double").is_empty());
+    }
+
+    #[test]
+    fn skips_code() {
+        let content = "Use `@Override` here, @carol\n```java\n@Test\nvoid t() {}\n```\n@dave";
+        assert_eq!(extract(content), vec!["carol", "dave"]);
+    }
+}
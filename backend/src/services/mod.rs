@@ -1,17 +1,90 @@
 //! Business logic services.
 
+pub mod apm_preview;
+pub mod app_code_pattern;
 pub mod app_code_resolver;
+pub mod app_posture;
+pub mod app_stats;
 pub mod application;
+pub mod asset;
+pub mod attachment;
+pub mod attachment_storage;
+pub mod attack;
 pub mod attack_chains;
+pub mod audit_log;
 pub mod auth;
+pub mod burndown;
+pub mod business_units;
+pub mod config_bundle;
+pub mod config_cache;
 pub mod correlation;
 pub mod correlation_service;
+pub mod coverage_gaps;
 pub mod cross_dedup;
+pub mod cross_dedup_service;
+pub mod csv_export;
+pub mod cwe;
 pub mod dashboard;
+pub mod dashboard_snapshots;
+pub mod data_hygiene;
+pub mod data_subject;
 pub mod dedup_dashboard;
 pub mod deduplication;
+pub mod defectdojo;
+pub mod dns_mapping;
+pub mod dora_report;
+pub mod eol;
+pub mod executive_report;
+pub mod exploits;
+pub mod false_positive_report;
 pub mod finding;
+pub mod finding_merge;
+pub mod finding_reassign;
+pub mod finding_trends;
+pub mod gdpr_report;
+pub mod license_policy;
 pub mod lifecycle;
+pub mod manual_finding;
+pub mod mentions;
+pub mod mttr;
+pub mod notification;
+pub mod notification_mailer;
+pub mod notification_preferences;
+pub mod nvd;
+pub mod occurrence;
+pub mod osv;
+pub mod owasp;
+pub mod ownership;
+pub mod partitions;
 pub mod fingerprint;
+pub mod fingerprint_profile;
+pub mod ghsa;
 pub mod ingestion;
+pub mod job;
+pub mod json_value;
+pub mod pdf_report;
+pub mod redis_store;
+pub mod release;
+pub mod remediation;
+pub mod report_delivery;
+pub mod report_schedule;
+pub mod report_template;
 pub mod risk_score;
+pub mod saved_dashboard;
+pub mod saved_filter;
+pub mod sbom;
+pub mod scanner_rule;
+pub mod search;
+pub mod sarif_export;
+pub mod similarity;
+pub mod sla_monitor;
+pub mod splunk_hec;
+pub mod tag;
+pub mod text;
+pub mod top_apps;
+pub mod triage;
+pub mod upload_token;
+pub mod vex;
+pub mod watchlist;
+pub mod workload;
+pub mod xlsx_export;
@@ -0,0 +1,264 @@
+//! Mean and median time-to-remediate from `finding_history` status
+//! transitions, with breakdowns by severity, category, tool, and business unit.
+//!
+//! Durations run from a finding's first transition to Confirmed until its
+//! first transition to Mitigated (time to mitigate) and to Closed (time to
+//! close). A finding counts towards the period in which it was mitigated or
+//! closed.
+
+use std::cmp::Reverse;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::errors::AppError;
+use crate::models::finding::severity_rank;
+use crate::services::executive_report::ReportPeriod;
+
+/// Query parameters for the MTTR endpoint.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MttrParams {
+    /// Window ending now (defaults to `quarter`).
+    #[serde(default)]
+    pub period: ReportPeriod,
+}
+
+/// Remediation times for one group of findings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema, sqlx::FromRow)]
+pub struct RemediationTimes {
+    /// Findings mitigated or closed in the period.
+    pub remediated: i64,
+    /// Findings that went from Confirmed to Mitigated in the period.
+    pub mitigated: i64,
+    pub mean_days_to_mitigate: Option<f64>,
+    pub median_days_to_mitigate: Option<f64>,
+    /// Findings that went from Confirmed to Closed in the period.
+    pub closed: i64,
+    pub mean_days_to_close: Option<f64>,
+    pub median_days_to_close: Option<f64>,
+}
+
+/// Remediation times for one value of a breakdown dimension.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct MttrBreakdown {
+    pub key: String,
+    #[serde(flatten)]
+    pub times: RemediationTimes,
+}
+
+/// MTTR and remediation velocity for the period.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MttrReport {
+    pub period: ReportPeriod,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub overall: RemediationTimes,
+    /// Findings remediated per week over the period.
+    pub remediated_per_week: f64,
+    pub by_severity: Vec<MttrBreakdown>,
+    pub by_category: Vec<MttrBreakdown>,
+    pub by_tool: Vec<MttrBreakdown>,
+    /// Findings without an application are grouped under `Unassigned`.
+    pub by_business_unit: Vec<MttrBreakdown>,
+}
+
+/// One grouping-set row: `dimension` is `overall`, `severity`, `category`,
+/// `tool`, or `business_unit`.
+#[derive(Debug, sqlx::FromRow)]
+struct MttrRow {
+    dimension: String,
+    key: Option<String>,
+    #[sqlx(flatten)]
+    times: RemediationTimes,
+}
+
+/// Compute MTTR statistics for the period ending now.
+pub async fn get_mttr(pool: &PgPool, params: &MttrParams) -> Result<MttrReport, AppError> {
+    let period_end = Utc::now();
+    let period_start = period_end - params.period.duration();
+
+    let rows = sqlx::query_as::<_, MttrRow>(
+        r#"
+        WITH transitions AS (
+            SELECT
+                f.id,
+                f.normalized_severity::text AS severity,
+                f.finding_category::text AS category,
+                f.source_tool,
+                COALESCE(a.business_unit, 'Unassigned') AS business_unit,
+                MIN(h.created_at) FILTER (WHERE h.new_value = 'Confirmed') AS confirmed_at,
+                MIN(h.created_at) FILTER (WHERE h.new_value = 'Mitigated') AS mitigated_at,
+                MIN(h.created_at) FILTER (WHERE h.new_value = 'Closed') AS closed_at
+            FROM findings f
+            JOIN finding_history h
+              ON h.finding_id = f.id AND h.field_changed = 'status' AND h.created_at < $2
+            LEFT JOIN applications a ON a.id = f.application_id
+            GROUP BY f.id, a.business_unit
+        ),
+        durations AS (
+            SELECT
+                severity,
+                category,
+                source_tool,
+                business_unit,
+                CASE WHEN mitigated_at >= $1 AND mitigated_at < $2 AND mitigated_at >= confirmed_at
+                    THEN EXTRACT(EPOCH FROM (mitigated_at - confirmed_at)) / 86400.0
+                END AS days_to_mitigate,
+                CASE WHEN closed_at >= $1 AND closed_at < $2 AND closed_at >= confirmed_at
+                    THEN EXTRACT(EPOCH FROM (closed_at - confirmed_at)) / 86400.0
+                END AS days_to_close
+            FROM transitions
+            WHERE confirmed_at IS NOT NULL
+        )
+        SELECT
+            CASE
+                WHEN GROUPING(severity) = 0 THEN 'severity'
+                WHEN GROUPING(category) = 0 THEN 'category'
+                WHEN GROUPING(source_tool) = 0 THEN 'tool'
+                WHEN GROUPING(business_unit) = 0 THEN 'business_unit'
+                ELSE 'overall'
+            END AS dimension,
+            COALESCE(severity, category, source_tool, business_unit) AS key,
+            COUNT(*) AS remediated,
+            COUNT(days_to_mitigate) AS mitigated,
+            AVG(days_to_mitigate)::double precision AS mean_days_to_mitigate,
+            percentile_cont(0.5) WITHIN GROUP (ORDER BY days_to_mitigate) AS median_days_to_mitigate,
+            COUNT(days_to_close) AS closed,
+            AVG(days_to_close)::double precision AS mean_days_to_close,
+            percentile_cont(0.5) WITHIN GROUP (ORDER BY days_to_close) AS median_days_to_close
+        FROM durations
+        WHERE days_to_mitigate IS NOT NULL OR days_to_close IS NOT NULL
+        GROUP BY GROUPING SETS ((), (severity), (category), (source_tool), (business_unit))
+        ORDER BY dimension, key
+        "#,
+    )
+    .bind(period_start)
+    .bind(period_end)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(assemble(params.period, period_start, period_end, rows))
+}
+
+/// Split grouping-set rows into the overall figures and per-dimension lists.
+fn assemble(
+    period: ReportPeriod,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    rows: Vec<MttrRow>,
+) -> MttrReport {
+    let mut report = MttrReport {
+        period,
+        period_start,
+        period_end,
+        overall: RemediationTimes::default(),
+        remediated_per_week: 0.0,
+        by_severity: Vec::new(),
+        by_category: Vec::new(),
+        by_tool: Vec::new(),
+        by_business_unit: Vec::new(),
+    };
+
+    for row in rows {
+        let breakdown = |times| MttrBreakdown {
+            key: row.key.clone().unwrap_or_default(),
+            times,
+        };
+        match row.dimension.as_str() {
+            "overall" => report.overall = row.times,
+            "severity" => report.by_severity.push(breakdown(row.times)),
+            "category" => report.by_category.push(breakdown(row.times)),
+            "tool" => report.by_tool.push(breakdown(row.times)),
+            "business_unit" => report.by_business_unit.push(breakdown(row.times)),
+            _ => {}
+        }
+    }
+
+    report
+        .by_severity
+        .sort_by_key(|b| Reverse(severity_rank(&b.key)));
+
+    let weeks = (period_end - period_start).num_days() as f64 / 7.0;
+    if weeks > 0.0 {
+        report.remediated_per_week = report.overall.remediated as f64 / weeks;
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(dimension: &str, key: Option<&str>, remediated: i64) -> MttrRow {
+        MttrRow {
+            dimension: dimension.to_string(),
+            key: key.map(str::to_string),
+            times: RemediationTimes {
+                remediated,
+                mitigated: remediated,
+                mean_days_to_mitigate: Some(10.0),
+                median_days_to_mitigate: Some(8.0),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn assembles_dimensions_and_velocity() {
+        let end = Utc::now();
+        let start = end - ReportPeriod::Month.duration();
+        let report = assemble(
+            ReportPeriod::Month,
+            start,
+            end,
+            vec![
+                row("business_unit", Some("Payments"), 4),
+                row("category", Some("SAST"), 6),
+                row("overall", None, 60),
+                row("severity", Some("Low"), 1),
+                row("severity", Some("Critical"), 2),
+                row("severity", Some("High"), 3),
+                row("tool", Some("sonarqube"), 6),
+            ],
+        );
+
+        assert_eq!(report.overall.remediated, 60);
+        assert_eq!(report.overall.median_days_to_mitigate, Some(8.0));
+        let severities: Vec<&str> = report.by_severity.iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(severities, ["Critical", "High", "Low"]);
+        assert_eq!(report.by_category[0].key, "SAST");
+        assert_eq!(report.by_tool[0].times.remediated, 6);
+        assert_eq!(report.by_business_unit[0].key, "Payments");
+        assert_eq!(report.remediated_per_week, 14.0);
+    }
+
+    #[test]
+    fn empty_period_has_no_velocity() {
+        let end = Utc::now();
+        let report = assemble(ReportPeriod::Quarter, end, end, Vec::new());
+        assert_eq!(report.overall, RemediationTimes::default());
+        assert_eq!(report.remediated_per_week, 0.0);
+        assert!(report.by_severity.is_empty());
+    }
+
+    #[test]
+    fn breakdown_serializes_times_inline() {
+        let json = serde_json::to_value(MttrBreakdown {
+            key: "High".to_string(),
+            times: RemediationTimes {
+                remediated: 1,
+                closed: 1,
+                median_days_to_close: Some(3.5),
+                ..Default::default()
+            },
+        })
+        .unwrap();
+        assert_eq!(json["key"], "High");
+        assert_eq!(json["median_days_to_close"], 3.5);
+        assert!(json["mean_days_to_mitigate"].is_null());
+    }
+}
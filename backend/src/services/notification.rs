@@ -0,0 +1,232 @@
+//! Notifications: preference-aware dispatch and the per-user inbox.
+//!
+//! Services call [`send`] after their own transaction commits. Each
+//! recipient's preferences decide whether the notification lands in their
+//! inbox and whether it is emailed now or in the daily digest (queued for
+//! [`notification_mailer`](crate::services::notification_mailer)). Delivery is
+//! best effort: a failed insert is logged and never fails the action that
+//! caused it. Notifications about an application's findings go to its owners
+//! and champions (see [`ownership::notification_recipients`]); the acting
+//! user is never notified of their own action.
+
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::notification::{Notification, NotificationFrequency, NotificationKind};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::notification_preferences::{self, Deliveries};
+use crate::services::ownership;
+
+/// Query parameters for the notification inbox.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationFilters {
+    /// Only unread notifications.
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+/// A notification to deliver to one or more users.
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: Option<String>,
+    pub finding_id: Option<Uuid>,
+    pub application_id: Option<Uuid>,
+    pub actor_name: Option<String>,
+}
+
+async fn insert(
+    pool: &PgPool,
+    recipients: &[Uuid],
+    notification: &NewNotification,
+) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO notifications (user_id, kind, title, body, finding_id, application_id, actor_name)
+        SELECT DISTINCT user_id, $2, $3, $4, $5, $6, $7
+        FROM UNNEST($1::uuid[]) AS user_id
+        "#,
+    )
+    .bind(recipients)
+    .bind(notification.kind)
+    .bind(&notification.title)
+    .bind(&notification.body)
+    .bind(notification.finding_id)
+    .bind(notification.application_id)
+    .bind(&notification.actor_name)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+async fn queue_email(
+    pool: &PgPool,
+    recipients: &[Uuid],
+    frequency: NotificationFrequency,
+    notification: &NewNotification,
+) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO notification_emails
+            (user_id, kind, frequency, title, body, finding_id, application_id, actor_name)
+        SELECT DISTINCT user_id, $2, $3, $4, $5, $6, $7, $8
+        FROM UNNEST($1::uuid[]) AS user_id
+        "#,
+    )
+    .bind(recipients)
+    .bind(notification.kind)
+    .bind(frequency)
+    .bind(&notification.title)
+    .bind(&notification.body)
+    .bind(notification.finding_id)
+    .bind(notification.application_id)
+    .bind(&notification.actor_name)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+async fn deliver(
+    pool: &PgPool,
+    deliveries: &Deliveries,
+    notification: &NewNotification,
+) -> Result<(), AppError> {
+    if !deliveries.in_app.is_empty() {
+        insert(pool, &deliveries.in_app, notification).await?;
+    }
+    if !deliveries.email_immediate.is_empty() {
+        queue_email(
+            pool,
+            &deliveries.email_immediate,
+            NotificationFrequency::Immediate,
+            notification,
+        )
+        .await?;
+    }
+    if !deliveries.email_digest.is_empty() {
+        queue_email(
+            pool,
+            &deliveries.email_digest,
+            NotificationFrequency::DailyDigest,
+            notification,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Deliver a notification to `recipients` according to their preferences,
+/// logging rather than returning failures.
+pub async fn send(pool: &PgPool, recipients: &[Uuid], notification: &NewNotification) {
+    if recipients.is_empty() {
+        return;
+    }
+    let result =
+        match notification_preferences::deliveries(pool, notification.kind, recipients).await {
+            Ok(deliveries) => deliver(pool, &deliveries, notification).await,
+            Err(e) => Err(e),
+        };
+    if let Err(e) = result {
+        tracing::warn!(
+            kind = ?notification.kind,
+            recipients = recipients.len(),
+            error = %e,
+            "Failed to store notification"
+        );
+    }
+}
+
+/// Notify an application's owners and champions, except `actor_id`.
+pub async fn send_to_application(
+    pool: &PgPool,
+    application_id: Uuid,
+    actor_id: Option<Uuid>,
+    notification: &NewNotification,
+) {
+    match ownership::notification_recipients(pool, application_id).await {
+        Ok(mut recipients) => {
+            recipients.retain(|id| Some(*id) != actor_id);
+            send(pool, &recipients, notification).await;
+        }
+        Err(e) => {
+            tracing::warn!(
+                application_id = %application_id,
+                error = %e,
+                "Failed to load notification recipients"
+            );
+        }
+    }
+}
+
+/// A user's notifications, newest first.
+pub async fn list_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    filters: &NotificationFilters,
+    pagination: &Pagination,
+) -> Result<PagedResult<Notification>, AppError> {
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)",
+    )
+    .bind(user_id)
+    .bind(filters.unread_only)
+    .fetch_one(pool)
+    .await?;
+
+    let items = sqlx::query_as::<_, Notification>(
+        r#"
+        SELECT * FROM notifications
+        WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+        ORDER BY created_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(user_id)
+    .bind(filters.unread_only)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(items, total, pagination))
+}
+
+/// Number of unread notifications, for the inbox badge.
+pub async fn unread_count(pool: &PgPool, user_id: Uuid) -> Result<i64, AppError> {
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND read_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
+/// Mark some of a user's notifications as read; other users' IDs are ignored.
+pub async fn mark_read(pool: &PgPool, user_id: Uuid, ids: &[Uuid]) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        "UPDATE notifications SET read_at = NOW() \
+         WHERE user_id = $1 AND id = ANY($2) AND read_at IS NULL",
+    )
+    .bind(user_id)
+    .bind(ids)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Mark all of a user's notifications as read.
+pub async fn mark_all_read(pool: &PgPool, user_id: Uuid) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        "UPDATE notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL",
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
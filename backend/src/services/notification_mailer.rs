@@ -0,0 +1,209 @@
+//! Email delivery for queued notifications.
+//!
+//! [`notification::send`](crate::services::notification::send) queues emails
+//! in `notification_emails`. The mailer sends immediate rows on every tick,
+//! one email per user, and batches each user's digest rows into a single
+//! email once a day after the configured UTC hour. Without an SMTP relay the
+//! queue is discarded instead.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{NaiveDate, Timelike, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::notification::NotificationFrequency;
+use crate::services::report_delivery::ReportDelivery;
+
+/// Queued emails handled per frequency and tick.
+const BATCH_SIZE: i64 = 1000;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct QueuedEmail {
+    id: Uuid,
+    user_id: Uuid,
+    email: String,
+    title: String,
+    body: Option<String>,
+    actor_name: Option<String>,
+}
+
+/// Subject and plain-text body for one user's queued emails.
+fn render(frequency: NotificationFrequency, items: &[QueuedEmail]) -> (String, String) {
+    let subject = match (frequency, items) {
+        (NotificationFrequency::DailyDigest, _) => {
+            format!("SynApSec daily digest: {} notification(s)", items.len())
+        }
+        (_, [only]) => format!("SynApSec: {}", only.title),
+        _ => format!("SynApSec: {} new notifications", items.len()),
+    };
+
+    let mut body = String::new();
+    for item in items {
+        body.push_str("- ");
+        body.push_str(&item.title);
+        if let Some(actor) = &item.actor_name {
+            body.push_str(&format!(" (by {actor})"));
+        }
+        body.push('\n');
+        if let Some(detail) = &item.body {
+            for line in detail.lines() {
+                body.push_str("    ");
+                body.push_str(line);
+                body.push('\n');
+            }
+        }
+    }
+    body.push_str("\nManage email preferences under notification settings in SynApSec.\n");
+    (subject, body)
+}
+
+/// Email every user their pending notifications of `frequency`. Returns the
+/// number of emails sent; a user whose email fails keeps their rows queued
+/// for the next run.
+pub async fn send_pending(
+    pool: &PgPool,
+    delivery: &ReportDelivery,
+    frequency: NotificationFrequency,
+) -> Result<usize, AppError> {
+    let mut sent = 0;
+    let mut failed: Vec<Uuid> = Vec::new();
+    loop {
+        let queued = sqlx::query_as::<_, QueuedEmail>(
+            r#"
+            SELECT e.id, e.user_id, u.email, e.title, e.body, e.actor_name
+            FROM notification_emails e
+            JOIN users u ON u.id = e.user_id AND u.is_active
+            WHERE e.sent_at IS NULL AND e.frequency = $1 AND e.user_id <> ALL($2)
+            ORDER BY e.user_id, e.created_at
+            LIMIT $3
+            "#,
+        )
+        .bind(frequency)
+        .bind(&failed)
+        .bind(BATCH_SIZE)
+        .fetch_all(pool)
+        .await?;
+        let exhausted = (queued.len() as i64) < BATCH_SIZE;
+
+        let mut by_user: BTreeMap<Uuid, Vec<QueuedEmail>> = BTreeMap::new();
+        for email in queued {
+            by_user.entry(email.user_id).or_default().push(email);
+        }
+
+        for (user_id, items) in by_user {
+            let (subject, body) = render(frequency, &items);
+            if let Err(e) = delivery
+                .send_text_email(&items[0].email, &subject, body)
+                .await
+            {
+                tracing::warn!(user_id = %user_id, error = %e, "Failed to email notifications");
+                failed.push(user_id);
+                continue;
+            }
+            let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+            sqlx::query("UPDATE notification_emails SET sent_at = NOW() WHERE id = ANY($1)")
+                .bind(&ids)
+                .execute(pool)
+                .await?;
+            sent += 1;
+        }
+
+        if exhausted {
+            return Ok(sent);
+        }
+    }
+}
+
+/// Whether the daily digest is due at `hour` on `today`, given the day it last went out.
+fn digest_due(today: NaiveDate, hour: u32, digest_hour: u32, last_sent: Option<NaiveDate>) -> bool {
+    hour >= digest_hour && last_sent != Some(today)
+}
+
+/// Spawn the background mailer, checking the queue every `interval`.
+pub fn spawn_mailer(
+    pool: PgPool,
+    delivery: ReportDelivery,
+    interval: Duration,
+    digest_hour_utc: u32,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        let mut last_digest: Option<NaiveDate> = None;
+        loop {
+            ticker.tick().await;
+            if !delivery.email_enabled() {
+                if let Err(e) = sqlx::query("DELETE FROM notification_emails WHERE sent_at IS NULL")
+                    .execute(&pool)
+                    .await
+                {
+                    tracing::error!(error = %e, "Failed to discard queued notification emails");
+                }
+                continue;
+            }
+
+            if let Err(e) = send_pending(&pool, &delivery, NotificationFrequency::Immediate).await {
+                tracing::error!(error = %e, "Notification email delivery failed");
+            }
+
+            let now = Utc::now();
+            let today = now.date_naive();
+            if digest_due(today, now.hour(), digest_hour_utc, last_digest) {
+                match send_pending(&pool, &delivery, NotificationFrequency::DailyDigest).await {
+                    Ok(sent) => {
+                        tracing::info!(sent, "Sent daily notification digests");
+                        last_digest = Some(today);
+                    }
+                    Err(e) => tracing::error!(error = %e, "Daily notification digest failed"),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queued(title: &str, body: Option<&str>) -> QueuedEmail {
+        QueuedEmail {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            email: "dev@example.com".to_string(),
+            title: title.to_string(),
+            body: body.map(str::to_string),
+            actor_name: Some("alice".to_string()),
+        }
+    }
+
+    #[test]
+    fn single_immediate_email_uses_its_title() {
+        let (subject, body) = render(
+            NotificationFrequency::Immediate,
+            &[queued("SLA breached: XSS", Some("line one\nline two"))],
+        );
+        assert_eq!(subject, "SynApSec: SLA breached: XSS");
+        assert!(body.starts_with("- SLA breached: XSS (by alice)\n    line one\n    line two\n"));
+    }
+
+    #[test]
+    fn digest_subject_counts_items() {
+        let (subject, _) = render(
+            NotificationFrequency::DailyDigest,
+            &[queued("a", None), queued("b", None)],
+        );
+        assert_eq!(subject, "SynApSec daily digest: 2 notification(s)");
+    }
+
+    #[test]
+    fn digest_goes_out_once_per_day_after_the_hour() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let yesterday = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        assert!(!digest_due(today, 6, 7, Some(yesterday)));
+        assert!(digest_due(today, 7, 7, Some(yesterday)));
+        assert!(digest_due(today, 9, 7, None));
+        assert!(!digest_due(today, 9, 7, Some(today)));
+    }
+}
@@ -0,0 +1,247 @@
+//! Per-user notification preferences.
+//!
+//! A preference sets how often one kind of notification reaches a user on one
+//! channel. Users only store the settings they changed; everything else falls
+//! back to [`default_frequency`].
+
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::notification::{
+    NotificationChannel, NotificationFrequency, NotificationKind, NotificationPreference,
+};
+
+/// Frequency used when a user has not set a preference. Every kind shows up
+/// in-app; SLA breaches, assignments and watchlist hits are emailed right
+/// away and the lower-priority kinds go into the daily digest.
+pub fn default_frequency(
+    kind: NotificationKind,
+    channel: NotificationChannel,
+) -> NotificationFrequency {
+    match (channel, kind) {
+        (NotificationChannel::InApp, _) => NotificationFrequency::Immediate,
+        (
+            NotificationChannel::Email,
+            NotificationKind::SlaBreach
+            | NotificationKind::Assignment
+            | NotificationKind::WatchlistHit,
+        ) => NotificationFrequency::Immediate,
+        (
+            NotificationChannel::Email,
+            NotificationKind::StatusChange | NotificationKind::Mention,
+        ) => NotificationFrequency::DailyDigest,
+    }
+}
+
+/// Reject settings the dispatcher cannot honour: the in-app inbox has no digest.
+fn validate(preference: &NotificationPreference) -> Result<(), AppError> {
+    if preference.channel == NotificationChannel::InApp
+        && preference.frequency == NotificationFrequency::DailyDigest
+    {
+        return Err(AppError::Validation(
+            "In-app notifications support only 'immediate' or 'off'".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Complete preference matrix from stored overrides, in kind then channel order.
+fn resolve_matrix(stored: &[NotificationPreference]) -> Vec<NotificationPreference> {
+    let overrides: HashMap<_, _> = stored
+        .iter()
+        .map(|p| ((p.kind, p.channel), p.frequency))
+        .collect();
+    NotificationKind::ALL
+        .iter()
+        .flat_map(|&kind| {
+            NotificationChannel::ALL
+                .iter()
+                .map(move |&channel| (kind, channel))
+        })
+        .map(|(kind, channel)| NotificationPreference {
+            kind,
+            channel,
+            frequency: overrides
+                .get(&(kind, channel))
+                .copied()
+                .unwrap_or_else(|| default_frequency(kind, channel)),
+        })
+        .collect()
+}
+
+/// A user's effective preferences for every kind and channel.
+pub async fn list_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<NotificationPreference>, AppError> {
+    let stored = sqlx::query_as::<_, NotificationPreference>(
+        "SELECT kind, channel, frequency FROM notification_preferences WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(resolve_matrix(&stored))
+}
+
+/// Store the given preferences and return the user's effective preferences.
+pub async fn update(
+    pool: &PgPool,
+    user_id: Uuid,
+    preferences: &[NotificationPreference],
+) -> Result<Vec<NotificationPreference>, AppError> {
+    for preference in preferences {
+        validate(preference)?;
+    }
+    let kinds: Vec<NotificationKind> = preferences.iter().map(|p| p.kind).collect();
+    let channels: Vec<NotificationChannel> = preferences.iter().map(|p| p.channel).collect();
+    let frequencies: Vec<NotificationFrequency> = preferences.iter().map(|p| p.frequency).collect();
+
+    // Later entries for the same kind and channel win
+    sqlx::query(
+        r#"
+        INSERT INTO notification_preferences (user_id, kind, channel, frequency)
+        SELECT DISTINCT ON (kind, channel) $1, kind, channel, frequency
+        FROM UNNEST($2::notification_kind[], $3::notification_channel[], $4::notification_frequency[])
+            WITH ORDINALITY AS p(kind, channel, frequency, ord)
+        ORDER BY kind, channel, ord DESC
+        ON CONFLICT (user_id, kind, channel)
+        DO UPDATE SET frequency = EXCLUDED.frequency, updated_at = NOW()
+        "#,
+    )
+    .bind(user_id)
+    .bind(&kinds)
+    .bind(&channels)
+    .bind(&frequencies)
+    .execute(pool)
+    .await?;
+
+    list_for_user(pool, user_id).await
+}
+
+/// How each recipient should receive one notification.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Deliveries {
+    pub in_app: Vec<Uuid>,
+    pub email_immediate: Vec<Uuid>,
+    pub email_digest: Vec<Uuid>,
+}
+
+fn route_recipients(
+    kind: NotificationKind,
+    recipients: &[Uuid],
+    stored: &HashMap<(Uuid, NotificationChannel), NotificationFrequency>,
+) -> Deliveries {
+    let mut deliveries = Deliveries::default();
+    for &user_id in recipients {
+        for channel in NotificationChannel::ALL {
+            let frequency = stored
+                .get(&(user_id, channel))
+                .copied()
+                .unwrap_or_else(|| default_frequency(kind, channel));
+            let target = match (channel, frequency) {
+                (_, NotificationFrequency::Off) => continue,
+                (NotificationChannel::InApp, _) => &mut deliveries.in_app,
+                (NotificationChannel::Email, NotificationFrequency::Immediate) => {
+                    &mut deliveries.email_immediate
+                }
+                (NotificationChannel::Email, NotificationFrequency::DailyDigest) => {
+                    &mut deliveries.email_digest
+                }
+            };
+            target.push(user_id);
+        }
+    }
+    deliveries
+}
+
+/// Split `recipients` of a `kind` notification by channel and frequency.
+pub async fn deliveries(
+    pool: &PgPool,
+    kind: NotificationKind,
+    recipients: &[Uuid],
+) -> Result<Deliveries, AppError> {
+    let rows = sqlx::query_as::<_, (Uuid, NotificationChannel, NotificationFrequency)>(
+        "SELECT user_id, channel, frequency FROM notification_preferences \
+         WHERE user_id = ANY($1) AND kind = $2",
+    )
+    .bind(recipients)
+    .bind(kind)
+    .fetch_all(pool)
+    .await?;
+    let stored = rows
+        .into_iter()
+        .map(|(user_id, channel, frequency)| ((user_id, channel), frequency))
+        .collect();
+    Ok(route_recipients(kind, recipients, &stored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_covers_every_kind_and_channel_with_overrides_applied() {
+        let matrix = resolve_matrix(&[NotificationPreference {
+            kind: NotificationKind::Mention,
+            channel: NotificationChannel::Email,
+            frequency: NotificationFrequency::Immediate,
+        }]);
+        assert_eq!(
+            matrix.len(),
+            NotificationKind::ALL.len() * NotificationChannel::ALL.len()
+        );
+        let mention_email = matrix
+            .iter()
+            .find(|p| {
+                p.kind == NotificationKind::Mention && p.channel == NotificationChannel::Email
+            })
+            .unwrap();
+        assert_eq!(mention_email.frequency, NotificationFrequency::Immediate);
+        let status_email = matrix
+            .iter()
+            .find(|p| {
+                p.kind == NotificationKind::StatusChange && p.channel == NotificationChannel::Email
+            })
+            .unwrap();
+        assert_eq!(status_email.frequency, NotificationFrequency::DailyDigest);
+    }
+
+    #[test]
+    fn in_app_digest_is_rejected() {
+        let preference = NotificationPreference {
+            kind: NotificationKind::SlaBreach,
+            channel: NotificationChannel::InApp,
+            frequency: NotificationFrequency::DailyDigest,
+        };
+        assert!(validate(&preference).is_err());
+    }
+
+    #[test]
+    fn recipients_are_routed_by_preference_or_default() {
+        let quiet = Uuid::new_v4();
+        let defaults = Uuid::new_v4();
+        let stored = HashMap::from([
+            (
+                (quiet, NotificationChannel::InApp),
+                NotificationFrequency::Off,
+            ),
+            (
+                (quiet, NotificationChannel::Email),
+                NotificationFrequency::Off,
+            ),
+        ]);
+        let deliveries =
+            route_recipients(NotificationKind::StatusChange, &[quiet, defaults], &stored);
+        assert_eq!(
+            deliveries,
+            Deliveries {
+                in_app: vec![defaults],
+                email_immediate: vec![],
+                email_digest: vec![defaults],
+            }
+        );
+    }
+}
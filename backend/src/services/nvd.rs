@@ -0,0 +1,484 @@
+//! CVE enrichment from the NVD 2.0 API.
+//!
+//! A background task looks up CVEs referenced by findings that are missing
+//! from `cve_catalog` or older than the refresh window, one request at a time
+//! to stay within NVD's public rate limits, and caches description, CVSS
+//! vectors, CWEs, and references.
+
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::models::cve::{CveDetail, CveFinding, CveRecord, CveReference};
+use crate::services::exploits;
+
+/// Timeout for a single NVD request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// CVEs looked up per enrichment run.
+const BATCH_SIZE: i64 = 200;
+
+/// NVD connection settings derived from [`AppConfig`].
+#[derive(Clone)]
+pub struct NvdSettings {
+    pub url: String,
+    pub api_key: Option<String>,
+    pub interval: Duration,
+    pub refresh_after_days: i32,
+}
+
+impl std::fmt::Debug for NvdSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NvdSettings")
+            .field("url", &self.url)
+            .field("api_key", &self.api_key.as_ref().map(|_| "[redacted]"))
+            .field("interval", &self.interval)
+            .field("refresh_after_days", &self.refresh_after_days)
+            .finish()
+    }
+}
+
+impl NvdSettings {
+    /// Build settings from config, returning `None` when enrichment is disabled.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        if !config.nvd_enrichment_enabled {
+            return None;
+        }
+        Some(Self {
+            url: config.nvd_api_url.trim_end_matches('/').to_string(),
+            api_key: config.nvd_api_key.clone(),
+            interval: Duration::from_secs(config.nvd_enrichment_interval_secs.max(1)),
+            refresh_after_days: config.nvd_refresh_after_days.max(1),
+        })
+    }
+
+    /// Pause between requests: NVD allows 5 requests per 30 seconds without
+    /// an API key and 50 with one.
+    fn request_delay(&self) -> Duration {
+        if self.api_key.is_some() {
+            Duration::from_millis(700)
+        } else {
+            Duration::from_secs(6)
+        }
+    }
+}
+
+/// Uppercase a CVE id and check its `CVE-YYYY-NNNN+` shape.
+pub fn normalize_cve_id(id: &str) -> Result<String, AppError> {
+    let id = id.trim().to_ascii_uppercase();
+    let valid = id
+        .strip_prefix("CVE-")
+        .and_then(|rest| rest.split_once('-'))
+        .is_some_and(|(year, number)| {
+            year.len() == 4
+                && number.len() >= 4
+                && year.bytes().all(|b| b.is_ascii_digit())
+                && number.bytes().all(|b| b.is_ascii_digit())
+        });
+    if valid {
+        Ok(id)
+    } else {
+        Err(AppError::Validation(format!("Invalid CVE id '{id}'")))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NvdResponse {
+    #[serde(default)]
+    vulnerabilities: Vec<NvdVulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NvdVulnerability {
+    cve: NvdCve,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdCve {
+    published: Option<String>,
+    last_modified: Option<String>,
+    #[serde(default)]
+    descriptions: Vec<NvdLangString>,
+    #[serde(default)]
+    metrics: NvdMetrics,
+    #[serde(default)]
+    weaknesses: Vec<NvdWeakness>,
+    #[serde(default)]
+    references: Vec<CveReference>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NvdLangString {
+    lang: String,
+    value: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdMetrics {
+    #[serde(default)]
+    cvss_metric_v31: Vec<NvdMetric>,
+    #[serde(default)]
+    cvss_metric_v30: Vec<NvdMetric>,
+    #[serde(default)]
+    cvss_metric_v2: Vec<NvdMetric>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdMetric {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    cvss_data: NvdCvssData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NvdCvssData {
+    vector_string: Option<String>,
+    base_score: Option<f32>,
+    base_severity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NvdWeakness {
+    #[serde(default)]
+    description: Vec<NvdLangString>,
+}
+
+/// CVE details extracted from an NVD record.
+#[derive(Debug, Default, PartialEq)]
+struct ParsedCve {
+    description: Option<String>,
+    cvss_v3_vector: Option<String>,
+    cvss_v3_score: Option<f32>,
+    cvss_v3_severity: Option<String>,
+    cvss_v2_vector: Option<String>,
+    cvss_v2_score: Option<f32>,
+    cwe_ids: Vec<String>,
+    references: Vec<CveReference>,
+    published_at: Option<DateTime<Utc>>,
+    last_modified_at: Option<DateTime<Utc>>,
+}
+
+/// NVD timestamps are UTC without an offset, e.g. `2021-12-10T10:15:09.143`.
+fn parse_nvd_time(value: Option<&str>) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value?, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// The primary (NVD-scored) metric if present, else the first.
+fn primary_metric(metrics: &[NvdMetric]) -> Option<&NvdMetric> {
+    metrics
+        .iter()
+        .find(|m| m.kind.as_deref() == Some("Primary"))
+        .or_else(|| metrics.first())
+}
+
+/// Extract the details of the first CVE in an NVD response, if any.
+fn parse_response(response: NvdResponse) -> Option<ParsedCve> {
+    let cve = response.vulnerabilities.into_iter().next()?.cve;
+    let description = cve
+        .descriptions
+        .iter()
+        .find(|d| d.lang == "en")
+        .or_else(|| cve.descriptions.first())
+        .map(|d| d.value.clone());
+    let v3 = primary_metric(&cve.metrics.cvss_metric_v31)
+        .or_else(|| primary_metric(&cve.metrics.cvss_metric_v30));
+    let v2 = primary_metric(&cve.metrics.cvss_metric_v2);
+
+    // NVD uses placeholder values such as NVD-CWE-noinfo when no CWE applies
+    let mut cwe_ids: Vec<String> = cve
+        .weaknesses
+        .iter()
+        .flat_map(|w| &w.description)
+        .map(|d| d.value.clone())
+        .filter(|v| v.starts_with("CWE-"))
+        .collect();
+    cwe_ids.sort();
+    cwe_ids.dedup();
+
+    Some(ParsedCve {
+        description,
+        cvss_v3_vector: v3.and_then(|m| m.cvss_data.vector_string.clone()),
+        cvss_v3_score: v3.and_then(|m| m.cvss_data.base_score),
+        cvss_v3_severity: v3.and_then(|m| m.cvss_data.base_severity.clone()),
+        cvss_v2_vector: v2.and_then(|m| m.cvss_data.vector_string.clone()),
+        cvss_v2_score: v2.and_then(|m| m.cvss_data.base_score),
+        cwe_ids,
+        references: cve.references,
+        published_at: parse_nvd_time(cve.published.as_deref()),
+        last_modified_at: parse_nvd_time(cve.last_modified.as_deref()),
+    })
+}
+
+/// Look up one CVE; `Ok(None)` when NVD has no record for it.
+async fn fetch(
+    client: &reqwest::Client,
+    settings: &NvdSettings,
+    cve_id: &str,
+) -> Result<Option<ParsedCve>, AppError> {
+    let mut request = client.get(&settings.url).query(&[("cveId", cve_id)]);
+    if let Some(key) = &settings.api_key {
+        request = request.header("apiKey", key);
+    }
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("NVD request failed: {e}")))?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !resp.status().is_success() {
+        return Err(AppError::Internal(format!(
+            "NVD responded with status {}",
+            resp.status()
+        )));
+    }
+    let body = resp
+        .json::<NvdResponse>()
+        .await
+        .map_err(|e| AppError::Internal(format!("Invalid NVD response: {e}")))?;
+    Ok(parse_response(body))
+}
+
+/// Store the result of a lookup, marking ids NVD does not know as not found.
+async fn upsert(pool: &PgPool, cve_id: &str, parsed: Option<ParsedCve>) -> Result<(), AppError> {
+    let found = parsed.is_some();
+    let parsed = parsed.unwrap_or_default();
+    sqlx::query(
+        r#"
+        INSERT INTO cve_catalog (
+            cve_id, found, description, cvss_v3_vector, cvss_v3_score, cvss_v3_severity,
+            cvss_v2_vector, cvss_v2_score, cwe_ids, reference_links, published_at,
+            last_modified_at, fetched_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW())
+        ON CONFLICT (cve_id) DO UPDATE SET
+            found = EXCLUDED.found,
+            description = EXCLUDED.description,
+            cvss_v3_vector = EXCLUDED.cvss_v3_vector,
+            cvss_v3_score = EXCLUDED.cvss_v3_score,
+            cvss_v3_severity = EXCLUDED.cvss_v3_severity,
+            cvss_v2_vector = EXCLUDED.cvss_v2_vector,
+            cvss_v2_score = EXCLUDED.cvss_v2_score,
+            cwe_ids = EXCLUDED.cwe_ids,
+            reference_links = EXCLUDED.reference_links,
+            published_at = EXCLUDED.published_at,
+            last_modified_at = EXCLUDED.last_modified_at,
+            fetched_at = NOW()
+        "#,
+    )
+    .bind(cve_id)
+    .bind(found)
+    .bind(&parsed.description)
+    .bind(&parsed.cvss_v3_vector)
+    .bind(parsed.cvss_v3_score)
+    .bind(&parsed.cvss_v3_severity)
+    .bind(&parsed.cvss_v2_vector)
+    .bind(parsed.cvss_v2_score)
+    .bind(&parsed.cwe_ids)
+    .bind(serde_json::to_value(&parsed.references).unwrap_or_default())
+    .bind(parsed.published_at)
+    .bind(parsed.last_modified_at)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// CVE ids seen in findings that are not cached or are older than the refresh window.
+async fn pending_cves(pool: &PgPool, refresh_after_days: i32) -> Result<Vec<String>, AppError> {
+    let ids = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT DISTINCT UPPER(cve.id)
+        FROM findings f
+        CROSS JOIN LATERAL jsonb_array_elements_text(f.cve_ids) AS cve(id)
+        LEFT JOIN cve_catalog c ON c.cve_id = UPPER(cve.id)
+        WHERE UPPER(cve.id) LIKE 'CVE-%'
+          AND (c.cve_id IS NULL OR c.fetched_at < NOW() - make_interval(days => $1))
+        LIMIT $2
+        "#,
+    )
+    .bind(refresh_after_days)
+    .bind(BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+    Ok(ids)
+}
+
+/// Fetch and cache pending CVEs. Returns the number of CVEs stored; a failed
+/// lookup is logged and retried on the next run.
+pub async fn enrich_pending(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    settings: &NvdSettings,
+) -> Result<usize, AppError> {
+    let mut stored = 0;
+    for raw_id in pending_cves(pool, settings.refresh_after_days).await? {
+        let Ok(cve_id) = normalize_cve_id(&raw_id) else {
+            continue;
+        };
+        match fetch(client, settings, &cve_id).await {
+            Ok(parsed) => {
+                upsert(pool, &cve_id, parsed).await?;
+                stored += 1;
+            }
+            Err(e) => tracing::warn!(cve_id = %cve_id, error = %e, "NVD lookup failed"),
+        }
+        tokio::time::sleep(settings.request_delay()).await;
+    }
+    Ok(stored)
+}
+
+/// Spawn the background enrichment task.
+pub fn spawn_enricher(pool: PgPool, settings: NvdSettings) {
+    tokio::spawn(async move {
+        let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to build NVD client; CVE enrichment disabled");
+                return;
+            }
+        };
+        let mut ticker = tokio::time::interval(settings.interval);
+        loop {
+            ticker.tick().await;
+            match enrich_pending(&pool, &client, &settings).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "Enriched CVEs from NVD"),
+                Err(e) => tracing::error!(error = %e, "NVD CVE enrichment failed"),
+            }
+        }
+    });
+}
+
+/// A CVE's cached details and the findings reporting it.
+pub async fn get_cve(pool: &PgPool, id: &str) -> Result<CveDetail, AppError> {
+    let cve_id = normalize_cve_id(id)?;
+    let details = sqlx::query_as::<_, CveRecord>("SELECT * FROM cve_catalog WHERE cve_id = $1")
+        .bind(&cve_id)
+        .fetch_optional(pool)
+        .await?;
+
+    let findings = sqlx::query_as::<_, CveFinding>(
+        r#"
+        SELECT f.id, f.title, f.finding_category, f.normalized_severity, f.status,
+               f.application_id, a.app_code, f.first_seen
+        FROM findings f
+        LEFT JOIN applications a ON a.id = f.application_id
+        WHERE (f.cve_ids @> jsonb_build_array($1::text)
+               OR f.cve_ids @> jsonb_build_array(LOWER($1)))
+          AND f.archived_at IS NULL
+        ORDER BY f.first_seen DESC
+        "#,
+    )
+    .bind(&cve_id)
+    .fetch_all(pool)
+    .await?;
+
+    let exploits = exploits::for_cve(pool, &cve_id).await?;
+
+    if details.is_none() && findings.is_empty() && exploits.is_empty() {
+        return Err(AppError::NotFound(format!("CVE {cve_id} not found")));
+    }
+    Ok(CveDetail {
+        cve_id,
+        details,
+        exploits,
+        findings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cve_ids_are_normalized_and_validated() {
+        assert_eq!(
+            normalize_cve_id(" cve-2021-44228 ").unwrap(),
+            "CVE-2021-44228"
+        );
+        assert_eq!(
+            normalize_cve_id("CVE-2024-123456").unwrap(),
+            "CVE-2024-123456"
+        );
+        assert!(normalize_cve_id("CVE-21-44228").is_err());
+        assert!(normalize_cve_id("GHSA-jfh8-c2jp-5v3q").is_err());
+        assert!(normalize_cve_id("CVE-2021-12").is_err());
+    }
+
+    #[test]
+    fn parses_nvd_record_preferring_primary_v31_metric() {
+        let body: NvdResponse = serde_json::from_value(serde_json::json!({
+            "vulnerabilities": [{
+                "cve": {
+                    "id": "CVE-2021-44228",
+                    "published": "2021-12-10T10:15:09.143",
+                    "lastModified": "2024-07-24T17:08:24.167",
+                    "descriptions": [
+                        {"lang": "es", "value": "Apache Log4j2 ..."},
+                        {"lang": "en", "value": "Apache Log4j2 JNDI features ..."}
+                    ],
+                    "metrics": {
+                        "cvssMetricV31": [
+                            {"source": "cna", "type": "Secondary", "cvssData": {
+                                "vectorString": "CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:H/I:H/A:H",
+                                "baseScore": 8.1, "baseSeverity": "HIGH"}},
+                            {"source": "nvd@nist.gov", "type": "Primary", "cvssData": {
+                                "vectorString": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H",
+                                "baseScore": 10.0, "baseSeverity": "CRITICAL"}}
+                        ],
+                        "cvssMetricV2": [
+                            {"type": "Primary", "cvssData": {
+                                "vectorString": "AV:N/AC:M/Au:N/C:C/I:C/A:C", "baseScore": 9.3},
+                             "baseSeverity": "HIGH"}
+                        ]
+                    },
+                    "weaknesses": [
+                        {"description": [{"lang": "en", "value": "CWE-917"}]},
+                        {"description": [{"lang": "en", "value": "CWE-502"},
+                                         {"lang": "en", "value": "NVD-CWE-noinfo"}]}
+                    ],
+                    "references": [
+                        {"url": "https://logging.apache.org/log4j/2.x/security.html",
+                         "source": "security@apache.org", "tags": ["Vendor Advisory"]}
+                    ]
+                }
+            }]
+        }))
+        .unwrap();
+
+        let parsed = parse_response(body).unwrap();
+        assert_eq!(
+            parsed.description.as_deref(),
+            Some("Apache Log4j2 JNDI features ...")
+        );
+        assert_eq!(parsed.cvss_v3_score, Some(10.0));
+        assert_eq!(parsed.cvss_v3_severity.as_deref(), Some("CRITICAL"));
+        assert_eq!(
+            parsed.cvss_v2_vector.as_deref(),
+            Some("AV:N/AC:M/Au:N/C:C/I:C/A:C")
+        );
+        assert_eq!(parsed.cwe_ids, ["CWE-502", "CWE-917"]);
+        assert_eq!(parsed.references[0].tags, ["Vendor Advisory"]);
+        assert_eq!(
+            parsed.published_at.unwrap().to_rfc3339(),
+            "2021-12-10T10:15:09.143+00:00"
+        );
+    }
+
+    #[test]
+    fn empty_response_means_not_found() {
+        let body: NvdResponse =
+            serde_json::from_str(r#"{"totalResults": 0, "vulnerabilities": []}"#).unwrap();
+        assert!(parse_response(body).is_none());
+    }
+}
@@ -0,0 +1,673 @@
+//! Finding occurrence tracking.
+//!
+//! Every scanner record resolved to a finding during ingestion is kept in
+//! `finding_occurrences` with the scan date and what the scan covered
+//! (application, SAST branch, DAST target). Occurrences of the same tool,
+//! application and branch form a scope: ordered by scan date, its ingestions
+//! are the successive scans in which a finding was seen or missing. That
+//! gives a finding's "seen in N consecutive scans" context, its auto-close
+//! eligibility once it has been missing from enough scans
+//! (`auto_close_missed_scans` setting), and the diff of an ingestion against
+//! the previous scan of each scope it covered, or against another ingestion
+//! of the same tool.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{FindingStatus, SeverityLevel};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::services::config_cache;
+
+/// Scans of a scope read to compute a finding's streak, newest first.
+const SCAN_HISTORY_LIMIT: i64 = 1000;
+
+/// One sighting of a finding by a scan.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Occurrence {
+    pub id: Uuid,
+    pub ingestion_log_id: Uuid,
+    pub source_tool: String,
+    /// `new`, `updated` or `reopened`.
+    pub outcome: String,
+    pub application_id: Option<Uuid>,
+    /// When the scan ran, or when it was ingested if the report does not say.
+    pub scan_date: DateTime<Utc>,
+    pub branch: Option<String>,
+    pub target_url: Option<String>,
+    pub seen_at: DateTime<Utc>,
+}
+
+/// A finding's presence across the scans of the scope it was last seen in.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OccurrenceSummary {
+    pub finding_id: Uuid,
+    pub total_occurrences: i64,
+    pub first_scan_date: Option<DateTime<Utc>>,
+    pub last_scan_date: Option<DateTime<Utc>>,
+    /// Tool of the latest sighting.
+    pub source_tool: Option<String>,
+    /// Branch of the latest sighting.
+    pub branch: Option<String>,
+    /// Consecutive scans the finding was seen in, up to its latest sighting.
+    pub consecutive_scans: u32,
+    /// Scans of the scope since the latest sighting.
+    pub missed_scans: u32,
+    /// Open and missing from at least `auto_close_missed_scans` scans.
+    pub auto_close_eligible: bool,
+}
+
+/// An open finding missing from enough scans of its scope to be auto-closed.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AutoCloseCandidate {
+    pub finding_id: Uuid,
+    pub title: String,
+    pub normalized_severity: SeverityLevel,
+    pub status: FindingStatus,
+    pub source_tool: String,
+    pub branch: Option<String>,
+    pub last_scan_date: DateTime<Utc>,
+    pub missed_scans: i32,
+}
+
+/// A finding in an ingestion diff.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DiffFinding {
+    pub finding_id: Uuid,
+    pub title: String,
+    pub normalized_severity: SeverityLevel,
+    pub status: FindingStatus,
+    pub application_id: Option<Uuid>,
+    pub branch: Option<String>,
+}
+
+/// A finding both compared scans saw, reported at different severities.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct SeverityChange {
+    pub finding_id: Uuid,
+    pub title: String,
+    pub application_id: Option<Uuid>,
+    pub branch: Option<String>,
+    pub previous_severity: SeverityLevel,
+    pub current_severity: SeverityLevel,
+}
+
+/// What an ingestion changed compared with the previous scan of each
+/// application and branch it covered, or with an explicitly chosen ingestion.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IngestionDiff {
+    pub ingestion_id: Uuid,
+    /// Ingestion compared against; `None` compares each scope with its
+    /// previous scan.
+    pub against_ingestion_id: Option<Uuid>,
+    pub source_tool: String,
+    pub scan_date: Option<DateTime<Utc>>,
+    pub new_findings: Vec<DiffFinding>,
+    pub reopened: Vec<DiffFinding>,
+    /// Findings that were already open and were seen again.
+    pub persisting: i64,
+    /// Findings seen by this scan of a scope but not by the compared one.
+    pub appeared: Vec<DiffFinding>,
+    /// Findings seen by the compared scan of a scope but not by this one.
+    pub absent: Vec<DiffFinding>,
+    /// Findings seen by both scans whose reported severity changed. Only
+    /// occurrences recorded with their severity are compared.
+    pub severity_changed: Vec<SeverityChange>,
+}
+
+/// Query parameters of the ingestion diff.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IngestionDiffParams {
+    /// Ingestion of the same tool to compare against, instead of the
+    /// previous scan of each scope.
+    pub against: Option<Uuid>,
+}
+
+/// List a finding's occurrences, latest scan first, paginated.
+pub async fn list_for_finding(
+    pool: &PgPool,
+    finding_id: Uuid,
+    pagination: &Pagination,
+) -> Result<PagedResult<Occurrence>, AppError> {
+    ensure_finding(pool, finding_id).await?;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM finding_occurrences WHERE finding_id = $1",
+    )
+    .bind(finding_id)
+    .fetch_one(pool)
+    .await?;
+
+    let items = sqlx::query_as::<_, Occurrence>(
+        r#"
+        SELECT id, ingestion_log_id, source_tool, outcome, application_id,
+               scan_date, branch, target_url, seen_at
+        FROM finding_occurrences
+        WHERE finding_id = $1
+        ORDER BY scan_date DESC, seen_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(finding_id)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(items, total, pagination))
+}
+
+/// Summarize a finding's presence across the scans of its latest scope.
+pub async fn summary_for_finding(
+    pool: &PgPool,
+    finding_id: Uuid,
+) -> Result<OccurrenceSummary, AppError> {
+    let open = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND archived_at IS NULL
+        FROM findings
+        WHERE id = $1
+        "#,
+    )
+    .bind(finding_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Finding {finding_id} not found")))?;
+
+    let (total_occurrences, first_scan_date, last_scan_date) =
+        sqlx::query_as::<_, (i64, Option<DateTime<Utc>>, Option<DateTime<Utc>>)>(
+            r#"
+            SELECT COUNT(*), MIN(scan_date), MAX(scan_date)
+            FROM finding_occurrences
+            WHERE finding_id = $1
+            "#,
+        )
+        .bind(finding_id)
+        .fetch_one(pool)
+        .await?;
+
+    let latest = sqlx::query_as::<_, (String, Option<Uuid>, Option<String>)>(
+        r#"
+        SELECT source_tool, application_id, branch
+        FROM finding_occurrences
+        WHERE finding_id = $1
+        ORDER BY scan_date DESC, seen_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(finding_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let mut summary = OccurrenceSummary {
+        finding_id,
+        total_occurrences,
+        first_scan_date,
+        last_scan_date,
+        source_tool: None,
+        branch: None,
+        consecutive_scans: 0,
+        missed_scans: 0,
+        auto_close_eligible: false,
+    };
+    let Some((source_tool, application_id, branch)) = latest else {
+        return Ok(summary);
+    };
+
+    let seen = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT BOOL_OR(finding_id = $1)
+        FROM finding_occurrences
+        WHERE source_tool = $2
+          AND application_id IS NOT DISTINCT FROM $3
+          AND branch IS NOT DISTINCT FROM $4
+        GROUP BY ingestion_log_id
+        ORDER BY MAX(scan_date) DESC, ingestion_log_id
+        LIMIT $5
+        "#,
+    )
+    .bind(finding_id)
+    .bind(&source_tool)
+    .bind(application_id)
+    .bind(&branch)
+    .bind(SCAN_HISTORY_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    let threshold = config_cache::global()
+        .settings(pool)
+        .await?
+        .auto_close_missed_scans();
+    let streak = ScanStreak::from_newest_first(&seen);
+    summary.source_tool = Some(source_tool);
+    summary.branch = branch;
+    summary.consecutive_scans = streak.consecutive_scans;
+    summary.missed_scans = streak.missed_scans;
+    summary.auto_close_eligible = open && streak.missed_scans >= threshold;
+    Ok(summary)
+}
+
+/// Open findings of an application missing from at least
+/// `auto_close_missed_scans` scans of the scope they were last seen in,
+/// longest missing first.
+pub async fn auto_close_candidates(
+    pool: &PgPool,
+    app_id: Uuid,
+) -> Result<Vec<AutoCloseCandidate>, AppError> {
+    let exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM applications WHERE id = $1)")
+            .bind(app_id)
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Application {app_id} not found"
+        )));
+    }
+
+    let threshold = config_cache::global()
+        .settings(pool)
+        .await?
+        .auto_close_missed_scans();
+
+    // A scan's recency is 1 for the latest scan of its scope, so a finding
+    // last seen in the scan of recency n has missed n - 1 scans
+    let rows = sqlx::query_as::<_, AutoCloseCandidate>(
+        r#"
+        WITH scans AS (
+            SELECT
+                source_tool,
+                branch,
+                ingestion_log_id,
+                ROW_NUMBER() OVER (
+                    PARTITION BY source_tool, branch
+                    ORDER BY MAX(scan_date) DESC, ingestion_log_id
+                ) AS recency
+            FROM finding_occurrences
+            WHERE application_id = $1
+            GROUP BY source_tool, branch, ingestion_log_id
+        ),
+        last_seen AS (
+            SELECT DISTINCT ON (finding_id)
+                finding_id, source_tool, branch, ingestion_log_id, scan_date
+            FROM finding_occurrences
+            WHERE application_id = $1
+            ORDER BY finding_id, scan_date DESC, seen_at DESC
+        )
+        SELECT
+            f.id AS finding_id,
+            f.title,
+            f.normalized_severity,
+            f.status,
+            ls.source_tool,
+            ls.branch,
+            ls.scan_date AS last_scan_date,
+            (s.recency - 1)::int AS missed_scans
+        FROM last_seen ls
+        JOIN scans s
+          ON s.ingestion_log_id = ls.ingestion_log_id
+         AND s.source_tool = ls.source_tool
+         AND s.branch IS NOT DISTINCT FROM ls.branch
+        JOIN findings f ON f.id = ls.finding_id
+        WHERE s.recency > $2
+          AND f.application_id = $1
+          AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND f.archived_at IS NULL
+        ORDER BY missed_scans DESC, f.id
+        "#,
+    )
+    .bind(app_id)
+    .bind(i64::from(threshold))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Scopes of an ingestion paired with the ingestion they are compared
+/// against: `$3` when given, else the previous scan of the scope. Only scopes
+/// the compared ingestion also covered are kept. `$1` is the ingestion,
+/// `$2` its source tool.
+const COMPARED_SCANS: &str = r#"
+    this_scan AS (
+        SELECT finding_id, application_id, branch, scan_date, normalized_severity
+        FROM finding_occurrences
+        WHERE ingestion_log_id = $1
+    ),
+    scopes AS (
+        SELECT DISTINCT application_id, branch, scan_date FROM this_scan
+    ),
+    previous AS (
+        SELECT
+            s.application_id,
+            s.branch,
+            COALESCE($3::uuid, (
+                SELECT o.ingestion_log_id
+                FROM finding_occurrences o
+                WHERE o.source_tool = $2
+                  AND o.application_id IS NOT DISTINCT FROM s.application_id
+                  AND o.branch IS NOT DISTINCT FROM s.branch
+                  AND o.ingestion_log_id <> $1
+                  AND o.scan_date <= s.scan_date
+                ORDER BY o.scan_date DESC, o.ingestion_log_id
+                LIMIT 1
+            )) AS ingestion_log_id
+        FROM scopes s
+    ),
+    previous_scan AS (
+        SELECT o.finding_id, o.application_id, o.branch, o.normalized_severity
+        FROM previous p
+        JOIN finding_occurrences o
+          ON o.ingestion_log_id = p.ingestion_log_id
+         AND o.application_id IS NOT DISTINCT FROM p.application_id
+         AND o.branch IS NOT DISTINCT FROM p.branch
+    ),
+    compared_scan AS (
+        SELECT c.*
+        FROM this_scan c
+        WHERE EXISTS (
+            SELECT 1 FROM previous_scan p
+            WHERE p.application_id IS NOT DISTINCT FROM c.application_id
+              AND p.branch IS NOT DISTINCT FROM c.branch
+        )
+    )
+"#;
+
+/// Diff an ingestion against the previous scan of each application and
+/// branch it covered, or against `against`, an ingestion of the same tool
+/// sharing at least one scope.
+pub async fn ingestion_diff(
+    pool: &PgPool,
+    ingestion_id: Uuid,
+    against: Option<Uuid>,
+) -> Result<IngestionDiff, AppError> {
+    let source_tool = ingestion_source_tool(pool, ingestion_id).await?;
+    if let Some(against_id) = against {
+        let against_tool = ingestion_source_tool(pool, against_id).await?;
+        check_comparable(ingestion_id, &source_tool, against_id, &against_tool)?;
+        let shares_scope = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM finding_occurrences c
+                JOIN finding_occurrences p
+                  ON p.ingestion_log_id = $2
+                 AND p.application_id IS NOT DISTINCT FROM c.application_id
+                 AND p.branch IS NOT DISTINCT FROM c.branch
+                WHERE c.ingestion_log_id = $1
+            )
+            "#,
+        )
+        .bind(ingestion_id)
+        .bind(against_id)
+        .fetch_one(pool)
+        .await?;
+        if !shares_scope {
+            return Err(AppError::Validation(format!(
+                "Ingestion {against_id} covered none of the applications and branches of ingestion {ingestion_id}"
+            )));
+        }
+    }
+
+    // A finding matched by several records of the file counts once, by its
+    // most significant outcome
+    let seen = sqlx::query_as::<_, SeenRow>(
+        r#"
+        SELECT DISTINCT ON (o.finding_id)
+            o.finding_id,
+            f.title,
+            f.normalized_severity,
+            f.status,
+            o.application_id,
+            o.branch,
+            o.outcome,
+            o.scan_date
+        FROM finding_occurrences o
+        JOIN findings f ON f.id = o.finding_id
+        WHERE o.ingestion_log_id = $1
+        ORDER BY o.finding_id,
+                 CASE o.outcome WHEN 'new' THEN 0 WHEN 'reopened' THEN 1 ELSE 2 END
+        "#,
+    )
+    .bind(ingestion_id)
+    .fetch_all(pool)
+    .await?;
+
+    let absent = sqlx::query_as::<_, DiffFinding>(&format!(
+        r#"
+        WITH {COMPARED_SCANS}
+        SELECT DISTINCT ON (f.id)
+            f.id AS finding_id,
+            f.title,
+            f.normalized_severity,
+            f.status,
+            p.application_id,
+            p.branch
+        FROM previous_scan p
+        JOIN findings f ON f.id = p.finding_id
+        WHERE NOT EXISTS (SELECT 1 FROM this_scan c WHERE c.finding_id = p.finding_id)
+        ORDER BY f.id
+        "#
+    ))
+    .bind(ingestion_id)
+    .bind(&source_tool)
+    .bind(against)
+    .fetch_all(pool)
+    .await?;
+
+    let appeared = sqlx::query_as::<_, DiffFinding>(&format!(
+        r#"
+        WITH {COMPARED_SCANS}
+        SELECT DISTINCT ON (f.id)
+            f.id AS finding_id,
+            f.title,
+            f.normalized_severity,
+            f.status,
+            c.application_id,
+            c.branch
+        FROM compared_scan c
+        JOIN findings f ON f.id = c.finding_id
+        WHERE NOT EXISTS (SELECT 1 FROM previous_scan p WHERE p.finding_id = c.finding_id)
+        ORDER BY f.id
+        "#
+    ))
+    .bind(ingestion_id)
+    .bind(&source_tool)
+    .bind(against)
+    .fetch_all(pool)
+    .await?;
+
+    let severity_changed = sqlx::query_as::<_, SeverityChange>(&format!(
+        r#"
+        WITH {COMPARED_SCANS}
+        SELECT DISTINCT ON (f.id)
+            f.id AS finding_id,
+            f.title,
+            c.application_id,
+            c.branch,
+            p.normalized_severity AS previous_severity,
+            c.normalized_severity AS current_severity
+        FROM compared_scan c
+        JOIN previous_scan p ON p.finding_id = c.finding_id
+        JOIN findings f ON f.id = c.finding_id
+        WHERE c.normalized_severity IS NOT NULL
+          AND p.normalized_severity IS NOT NULL
+          AND c.normalized_severity <> p.normalized_severity
+        ORDER BY f.id
+        "#
+    ))
+    .bind(ingestion_id)
+    .bind(&source_tool)
+    .bind(against)
+    .fetch_all(pool)
+    .await?;
+
+    let scan_date = seen.iter().map(|row| row.scan_date).max();
+    let mut diff = IngestionDiff {
+        ingestion_id,
+        against_ingestion_id: against,
+        source_tool,
+        scan_date,
+        new_findings: Vec::new(),
+        reopened: Vec::new(),
+        persisting: 0,
+        appeared,
+        absent,
+        severity_changed,
+    };
+    for row in seen {
+        match row.outcome.as_str() {
+            "new" => diff.new_findings.push(row.into_diff_finding()),
+            "reopened" => diff.reopened.push(row.into_diff_finding()),
+            _ => diff.persisting += 1,
+        }
+    }
+    Ok(diff)
+}
+
+// -- Private helpers ----------------------------------------------------------
+
+/// Where a finding stands in the scans of its scope.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ScanStreak {
+    missed_scans: u32,
+    consecutive_scans: u32,
+}
+
+impl ScanStreak {
+    /// From whether each scan of the scope saw the finding, newest first.
+    fn from_newest_first(seen: &[bool]) -> Self {
+        let missed = seen.iter().take_while(|s| !**s).count();
+        let consecutive = seen[missed..].iter().take_while(|s| **s).count();
+        Self {
+            missed_scans: missed as u32,
+            consecutive_scans: consecutive as u32,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SeenRow {
+    finding_id: Uuid,
+    title: String,
+    normalized_severity: SeverityLevel,
+    status: FindingStatus,
+    application_id: Option<Uuid>,
+    branch: Option<String>,
+    outcome: String,
+    scan_date: DateTime<Utc>,
+}
+
+impl SeenRow {
+    fn into_diff_finding(self) -> DiffFinding {
+        DiffFinding {
+            finding_id: self.finding_id,
+            title: self.title,
+            normalized_severity: self.normalized_severity,
+            status: self.status,
+            application_id: self.application_id,
+            branch: self.branch,
+        }
+    }
+}
+
+/// Applications the ingestion's records were matched to, with `None` for
+/// records not mapped to an application.
+pub async fn ingestion_applications(
+    pool: &PgPool,
+    ingestion_id: Uuid,
+) -> Result<Vec<Option<Uuid>>, AppError> {
+    let applications = sqlx::query_scalar::<_, Option<Uuid>>(
+        "SELECT DISTINCT application_id FROM finding_occurrences WHERE ingestion_log_id = $1",
+    )
+    .bind(ingestion_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(applications)
+}
+
+async fn ingestion_source_tool(pool: &PgPool, ingestion_id: Uuid) -> Result<String, AppError> {
+    sqlx::query_scalar::<_, String>("SELECT source_tool FROM ingestion_logs WHERE id = $1")
+        .bind(ingestion_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Ingestion log {ingestion_id} not found")))
+}
+
+/// Two ingestions can be compared when they are distinct runs of the same tool.
+fn check_comparable(
+    ingestion_id: Uuid,
+    source_tool: &str,
+    against_id: Uuid,
+    against_tool: &str,
+) -> Result<(), AppError> {
+    if ingestion_id == against_id {
+        return Err(AppError::Validation(
+            "Cannot compare an ingestion with itself".to_string(),
+        ));
+    }
+    if source_tool != against_tool {
+        return Err(AppError::Validation(format!(
+            "Cannot compare a {source_tool} ingestion with a {against_tool} one"
+        )));
+    }
+    Ok(())
+}
+
+async fn ensure_finding(pool: &PgPool, finding_id: Uuid) -> Result<(), AppError> {
+    let exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM findings WHERE id = $1)")
+            .bind(finding_id)
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Finding {finding_id} not found"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streak_counts_consecutive_sightings_up_to_the_latest() {
+        let streak = ScanStreak::from_newest_first(&[true, true, true, false, true]);
+        assert_eq!(streak.missed_scans, 0);
+        assert_eq!(streak.consecutive_scans, 3);
+    }
+
+    #[test]
+    fn streak_counts_scans_missed_since_the_latest_sighting() {
+        let streak = ScanStreak::from_newest_first(&[false, false, true, true, false]);
+        assert_eq!(streak.missed_scans, 2);
+        assert_eq!(streak.consecutive_scans, 2);
+    }
+
+    #[test]
+    fn only_distinct_runs_of_the_same_tool_are_comparable() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(check_comparable(a, "Semgrep", b, "Semgrep").is_ok());
+        assert!(matches!(
+            check_comparable(a, "Semgrep", a, "Semgrep"),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            check_comparable(a, "Semgrep", b, "Trivy"),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn streak_of_a_finding_never_seen_in_the_scope() {
+        assert_eq!(ScanStreak::from_newest_first(&[]), ScanStreak::default());
+        let streak = ScanStreak::from_newest_first(&[false, false]);
+        assert_eq!(streak.missed_scans, 2);
+        assert_eq!(streak.consecutive_scans, 0);
+    }
+}
@@ -0,0 +1,536 @@
+//! OSV.dev enrichment for SCA findings.
+//!
+//! Findings whose scanner reported no fixed version are looked up in OSV by
+//! package and version. The affected ranges and advisory aliases OSV returns
+//! are stored on the SCA layer, and `fixed_version` is backfilled with the
+//! lowest version that fixes every matching advisory. Runs on a schedule and
+//! on demand for a single finding.
+
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::models::finding_sca::{AffectedRange, FindingSca};
+
+/// Timeout for a single OSV request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// SCA findings looked up per scheduled run.
+const BATCH_SIZE: i64 = 500;
+
+/// OSV connection settings derived from [`AppConfig`].
+#[derive(Debug, Clone)]
+pub struct OsvSettings {
+    pub url: String,
+    pub interval: Duration,
+    pub recheck_after_days: i32,
+}
+
+impl OsvSettings {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            url: config.osv_api_url.trim_end_matches('/').to_string(),
+            interval: Duration::from_secs(config.osv_enrichment_interval_secs.max(1)),
+            recheck_after_days: config.osv_recheck_after_days.max(1),
+        }
+    }
+}
+
+/// Build the HTTP client used for OSV lookups.
+pub fn client() -> Result<reqwest::Client, AppError> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::Internal(format!("Failed to build OSV client: {e}")))
+}
+
+/// OSV ecosystem for a package URL type or scanner package type.
+fn ecosystem(package_type: &str) -> Option<&'static str> {
+    let ecosystem = match package_type.to_ascii_lowercase().as_str() {
+        "maven" => "Maven",
+        "npm" => "npm",
+        "pypi" => "PyPI",
+        "golang" | "go" => "Go",
+        "nuget" => "NuGet",
+        "gem" | "rubygems" => "RubyGems",
+        "cargo" | "crates.io" => "crates.io",
+        "composer" | "packagist" => "Packagist",
+        "hex" => "Hex",
+        "pub" => "Pub",
+        "swift" => "SwiftURL",
+        _ => return None,
+    };
+    Some(ecosystem)
+}
+
+/// How a version with one extra trailing segment compares to the version
+/// without it: `1.0.0` equals `1.0`, `1.0.1` is newer, and a pre-release
+/// suffix such as `1.0-rc1` is older.
+fn trailing_segment(segment: &str) -> Ordering {
+    match segment.parse::<u64>() {
+        Ok(0) => Ordering::Equal,
+        Ok(_) => Ordering::Greater,
+        Err(_) => Ordering::Less,
+    }
+}
+
+/// Compare versions segment by segment, numerically where both segments are
+/// numbers. Approximates most ecosystems' ordering without per-ecosystem rules.
+pub(crate) fn compare_versions(a: &str, b: &str) -> Ordering {
+    let split = |v: &str| -> Vec<String> {
+        v.trim_start_matches('v')
+            .split(['.', '-', '+', '_'])
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (split(a), split(b));
+    for i in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                _ => x.cmp(y),
+            },
+            (Some(x), None) => trailing_segment(x),
+            (None, Some(y)) => trailing_segment(y).reverse(),
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+#[derive(Debug, Serialize)]
+struct OsvQuery<'a> {
+    package: OsvPackage<'a>,
+    version: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct OsvPackage<'a> {
+    name: &'a str,
+    ecosystem: &'a str,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OsvResponse {
+    #[serde(default)]
+    vulns: Vec<OsvVulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvVulnerability {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvAffected {
+    package: Option<OsvAffectedPackage>,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvAffectedPackage {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    range_type: String,
+    #[serde(default)]
+    events: Vec<OsvEvent>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OsvEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+/// What OSV says about one package version.
+#[derive(Debug, Default, PartialEq)]
+struct Enrichment {
+    fixed_version: Option<String>,
+    ranges: Vec<AffectedRange>,
+    aliases: Vec<String>,
+}
+
+/// Pair OSV range events into `[introduced, fixed)` or
+/// `[introduced, last_affected]` intervals.
+fn range_intervals(vulnerability_id: &str, range: &OsvRange) -> Vec<AffectedRange> {
+    let mut intervals = Vec::new();
+    let mut open: Option<String> = None;
+    for event in &range.events {
+        if let Some(introduced) = &event.introduced {
+            open = Some(introduced.clone());
+        }
+        if event.fixed.is_some() || event.last_affected.is_some() {
+            intervals.push(AffectedRange {
+                vulnerability_id: vulnerability_id.to_string(),
+                range_type: range.range_type.clone(),
+                introduced: open.take(),
+                fixed: event.fixed.clone(),
+                last_affected: event.last_affected.clone(),
+            });
+        }
+    }
+    if let Some(introduced) = open {
+        intervals.push(AffectedRange {
+            vulnerability_id: vulnerability_id.to_string(),
+            range_type: range.range_type.clone(),
+            introduced: Some(introduced),
+            fixed: None,
+            last_affected: None,
+        });
+    }
+    intervals
+}
+
+/// Whether `version` falls within a range interval.
+pub(crate) fn contains(range: &AffectedRange, version: &str) -> bool {
+    let after_start = match range.introduced.as_deref() {
+        None | Some("0") => true,
+        Some(introduced) => compare_versions(version, introduced) != Ordering::Less,
+    };
+    let before_end = match (&range.fixed, &range.last_affected) {
+        (Some(fixed), _) => compare_versions(version, fixed) == Ordering::Less,
+        (None, Some(last)) => compare_versions(version, last) != Ordering::Greater,
+        (None, None) => true,
+    };
+    after_start && before_end
+}
+
+/// Summarize the advisories OSV returned for `package` at `version`.
+///
+/// When the finding names CVEs, only advisories matching one of them by id or
+/// alias are used; otherwise every advisory is. The backfilled fixed version
+/// is the highest of the per-advisory fixes containing `version`, so it
+/// resolves all of them. GIT ranges hold commit hashes and are skipped.
+fn summarize(
+    response: OsvResponse,
+    package: &str,
+    version: &str,
+    cve_ids: &[String],
+) -> Enrichment {
+    let relevant: Vec<OsvVulnerability> = if cve_ids.is_empty() {
+        response.vulns
+    } else {
+        response
+            .vulns
+            .into_iter()
+            .filter(|v| {
+                std::iter::once(&v.id)
+                    .chain(&v.aliases)
+                    .any(|id| cve_ids.iter().any(|cve| cve.eq_ignore_ascii_case(id)))
+            })
+            .collect()
+    };
+
+    let mut enrichment = Enrichment::default();
+    for vuln in &relevant {
+        enrichment.aliases.push(vuln.id.clone());
+        enrichment.aliases.extend(vuln.aliases.iter().cloned());
+
+        let mut fix: Option<String> = None;
+        for affected in &vuln.affected {
+            let same_package = affected
+                .package
+                .as_ref()
+                .map_or(true, |p| p.name.eq_ignore_ascii_case(package));
+            if !same_package {
+                continue;
+            }
+            for range in affected.ranges.iter().filter(|r| r.range_type != "GIT") {
+                for interval in range_intervals(&vuln.id, range) {
+                    if contains(&interval, version) {
+                        if let Some(fixed) = &interval.fixed {
+                            if fix
+                                .as_deref()
+                                .map_or(true, |f| compare_versions(fixed, f) == Ordering::Less)
+                            {
+                                fix = Some(fixed.clone());
+                            }
+                        }
+                    }
+                    enrichment.ranges.push(interval);
+                }
+            }
+        }
+        if let Some(fix) = fix {
+            if enrichment.fixed_version.as_deref().map_or(true, |current| {
+                compare_versions(&fix, current) == Ordering::Greater
+            }) {
+                enrichment.fixed_version = Some(fix);
+            }
+        }
+    }
+    enrichment.aliases.sort();
+    enrichment.aliases.dedup();
+    enrichment
+}
+
+/// SCA layer fields and CVEs needed for a lookup.
+#[derive(Debug, sqlx::FromRow)]
+struct ScaTarget {
+    finding_id: Uuid,
+    package_name: String,
+    package_version: String,
+    package_type: Option<String>,
+    cve_ids: serde_json::Value,
+}
+
+const SELECT_TARGET: &str = r#"
+    SELECT s.finding_id, s.package_name, s.package_version, s.package_type, f.cve_ids
+    FROM finding_sca s
+    JOIN findings f ON f.id = s.finding_id
+"#;
+
+async fn query_osv(
+    client: &reqwest::Client,
+    settings: &OsvSettings,
+    target: &ScaTarget,
+    ecosystem: &str,
+) -> Result<OsvResponse, AppError> {
+    let resp = client
+        .post(format!("{}/v1/query", settings.url))
+        .json(&OsvQuery {
+            package: OsvPackage {
+                name: &target.package_name,
+                ecosystem,
+            },
+            version: &target.package_version,
+        })
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("OSV request failed: {e}")))?;
+    if !resp.status().is_success() {
+        return Err(AppError::Internal(format!(
+            "OSV responded with status {}",
+            resp.status()
+        )));
+    }
+    resp.json::<OsvResponse>()
+        .await
+        .map_err(|e| AppError::Internal(format!("Invalid OSV response: {e}")))
+}
+
+/// Look up one SCA layer in OSV and store the result. A package type OSV does
+/// not cover is recorded as checked with nothing found.
+async fn enrich_target(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    settings: &OsvSettings,
+    target: &ScaTarget,
+) -> Result<(), AppError> {
+    let enrichment = match target.package_type.as_deref().and_then(ecosystem) {
+        Some(ecosystem) => {
+            let response = query_osv(client, settings, target, ecosystem).await?;
+            let cve_ids: Vec<String> =
+                serde_json::from_value(target.cve_ids.clone()).unwrap_or_default();
+            summarize(
+                response,
+                &target.package_name,
+                &target.package_version,
+                &cve_ids,
+            )
+        }
+        None => Enrichment::default(),
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE finding_sca
+        SET fixed_version = COALESCE(fixed_version, $2),
+            affected_ranges = $3,
+            vulnerability_aliases = $4,
+            osv_checked_at = NOW()
+        WHERE finding_id = $1
+        "#,
+    )
+    .bind(target.finding_id)
+    .bind(&enrichment.fixed_version)
+    .bind(serde_json::to_value(&enrichment.ranges).unwrap_or_default())
+    .bind(&enrichment.aliases)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Enrich one SCA finding now, regardless of when it was last checked.
+pub async fn enrich_finding(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    settings: &OsvSettings,
+    finding_id: Uuid,
+) -> Result<FindingSca, AppError> {
+    let target =
+        sqlx::query_as::<_, ScaTarget>(&format!("{SELECT_TARGET} WHERE s.finding_id = $1"))
+            .bind(finding_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("SCA finding {finding_id} not found")))?;
+    enrich_target(pool, client, settings, &target).await?;
+
+    let sca = sqlx::query_as::<_, FindingSca>("SELECT * FROM finding_sca WHERE finding_id = $1")
+        .bind(finding_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(sca)
+}
+
+/// Enrich open SCA findings lacking a fixed version that were never checked
+/// or not within the recheck window. Returns the number of findings checked.
+pub async fn enrich_pending(
+    pool: &PgPool,
+    client: &reqwest::Client,
+    settings: &OsvSettings,
+) -> Result<usize, AppError> {
+    let targets = sqlx::query_as::<_, ScaTarget>(&format!(
+        r#"{SELECT_TARGET}
+        WHERE s.fixed_version IS NULL
+          AND (s.osv_checked_at IS NULL
+               OR s.osv_checked_at < NOW() - make_interval(days => $1))
+          AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND f.archived_at IS NULL
+        ORDER BY s.osv_checked_at NULLS FIRST
+        LIMIT $2"#
+    ))
+    .bind(settings.recheck_after_days)
+    .bind(BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    let mut checked = 0;
+    for target in &targets {
+        match enrich_target(pool, client, settings, target).await {
+            Ok(()) => checked += 1,
+            Err(e) => tracing::warn!(
+                finding_id = %target.finding_id,
+                error = %e,
+                "OSV lookup failed"
+            ),
+        }
+    }
+    Ok(checked)
+}
+
+/// Spawn the scheduled OSV enrichment task.
+pub fn spawn_enricher(pool: PgPool, settings: OsvSettings) {
+    tokio::spawn(async move {
+        let client = match client() {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!(error = %e, "OSV enrichment disabled");
+                return;
+            }
+        };
+        let mut ticker = tokio::time::interval(settings.interval);
+        loop {
+            ticker.tick().await;
+            match enrich_pending(&pool, &client, &settings).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "Enriched SCA findings from OSV"),
+                Err(e) => tracing::error!(error = %e, "OSV enrichment failed"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> OsvResponse {
+        serde_json::from_value(serde_json::json!({
+            "vulns": [
+                {
+                    "id": "GHSA-jfh8-c2jp-5v3q",
+                    "aliases": ["CVE-2021-44228"],
+                    "affected": [{
+                        "package": {"name": "org.apache.logging.log4j:log4j-core", "ecosystem": "Maven"},
+                        "ranges": [{"type": "ECOSYSTEM", "events": [
+                            {"introduced": "2.0-beta9"}, {"fixed": "2.3.1"},
+                            {"introduced": "2.4"}, {"fixed": "2.12.2"},
+                            {"introduced": "2.13.0"}, {"fixed": "2.15.0"}
+                        ]}]
+                    }]
+                },
+                {
+                    "id": "GHSA-7rjr-3q55-vv33",
+                    "aliases": ["CVE-2021-45046"],
+                    "affected": [{
+                        "package": {"name": "org.apache.logging.log4j:log4j-core", "ecosystem": "Maven"},
+                        "ranges": [{"type": "ECOSYSTEM", "events": [
+                            {"introduced": "2.13.0"}, {"fixed": "2.16.0"}
+                        ]}]
+                    }]
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert_eq!(compare_versions("2.9.1", "2.10.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("2.0-beta9", "2.0"), Ordering::Less);
+        assert_eq!(compare_versions("v1.2.3", "1.2.3"), Ordering::Equal);
+    }
+
+    #[test]
+    fn fixed_version_resolves_every_advisory() {
+        let enrichment = summarize(
+            response(),
+            "org.apache.logging.log4j:log4j-core",
+            "2.14.1",
+            &[],
+        );
+        assert_eq!(enrichment.fixed_version.as_deref(), Some("2.16.0"));
+        assert_eq!(enrichment.ranges.len(), 4);
+        assert_eq!(
+            enrichment.aliases,
+            [
+                "CVE-2021-44228",
+                "CVE-2021-45046",
+                "GHSA-7rjr-3q55-vv33",
+                "GHSA-jfh8-c2jp-5v3q"
+            ]
+        );
+    }
+
+    #[test]
+    fn finding_cves_select_matching_advisories() {
+        let enrichment = summarize(
+            response(),
+            "org.apache.logging.log4j:log4j-core",
+            "2.14.1",
+            &["cve-2021-44228".to_string()],
+        );
+        assert_eq!(enrichment.fixed_version.as_deref(), Some("2.15.0"));
+        assert_eq!(
+            enrichment.aliases,
+            ["CVE-2021-44228", "GHSA-jfh8-c2jp-5v3q"]
+        );
+    }
+
+    #[test]
+    fn unknown_package_types_have_no_ecosystem() {
+        assert_eq!(ecosystem("PyPI"), Some("PyPI"));
+        assert_eq!(ecosystem("golang"), Some("Go"));
+        assert_eq!(ecosystem("docker"), None);
+    }
+}
@@ -0,0 +1,184 @@
+//! OWASP Top 10 categories derived from CWE ids.
+//!
+//! Scanners that report a category keep it. Otherwise the category of the
+//! first CWE with an entry in `owasp_cwe_mappings` is stored, as the 2021
+//! code (`A03:2021`). Admins maintain the mapping through the API.
+
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+
+use crate::errors::AppError;
+use crate::models::finding::CreateFinding;
+use crate::models::owasp::{OwaspCategory, OwaspMappingResult, UpdateOwaspMappings};
+use crate::services::cwe;
+
+/// Category code (`A03`) of an OWASP category in any of the spellings
+/// scanners use: `A03:2021`, `A03:2021-Injection`, `OWASP-A03`, `owasp-a3`.
+/// Mirrors the `owasp_code` SQL function used for filtering.
+pub fn category_code(category: &str) -> Option<String> {
+    let upper = category.to_ascii_uppercase();
+    upper.match_indices('A').find_map(|(i, _)| {
+        let digits: String = upper[i + 1..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .take(2)
+            .collect();
+        (!digits.is_empty()).then(|| format!("A{digits:0>2}"))
+    })
+}
+
+/// Category of the first CWE in `cwe_ids` that has a mapping.
+fn pick(cwe_ids: &[String], mappings: &HashMap<i32, String>) -> Option<String> {
+    cwe_ids
+        .iter()
+        .filter_map(|id| cwe::parse_cwe_id(id))
+        .find_map(|id| mappings.get(&id).cloned())
+}
+
+async fn mappings_for(pool: &PgPool, cwe_ids: &[i32]) -> Result<HashMap<i32, String>, AppError> {
+    if cwe_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let rows = sqlx::query_as::<_, (i32, String)>(
+        "SELECT cwe_id, category FROM owasp_cwe_mappings WHERE cwe_id = ANY($1)",
+    )
+    .bind(cwe_ids)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// Set `owasp_category` on findings that lack one, from their CWE ids.
+pub async fn fill_categories<'a>(
+    pool: &PgPool,
+    findings: impl IntoIterator<Item = &'a mut CreateFinding>,
+) -> Result<(), AppError> {
+    let mut missing: Vec<&mut CreateFinding> = findings
+        .into_iter()
+        .filter(|f| f.owasp_category.is_none() && !f.cwe_ids.is_empty())
+        .collect();
+    let cwe_ids: Vec<i32> = missing
+        .iter()
+        .flat_map(|f| &f.cwe_ids)
+        .filter_map(|id| cwe::parse_cwe_id(id))
+        .collect();
+    let mappings = mappings_for(pool, &cwe_ids).await?;
+    for finding in &mut missing {
+        finding.owasp_category = pick(&finding.cwe_ids, &mappings);
+    }
+    Ok(())
+}
+
+/// The category for a finding with these CWE ids, if any maps.
+pub async fn category_for(pool: &PgPool, cwe_ids: &[String]) -> Result<Option<String>, AppError> {
+    let ids: Vec<i32> = cwe_ids
+        .iter()
+        .filter_map(|id| cwe::parse_cwe_id(id))
+        .collect();
+    let mappings = mappings_for(pool, &ids).await?;
+    Ok(pick(cwe_ids, &mappings))
+}
+
+/// All categories with their mapped CWEs.
+pub async fn list_categories(pool: &PgPool) -> Result<Vec<OwaspCategory>, AppError> {
+    let rows = sqlx::query_as::<_, OwaspCategory>(
+        r#"
+        SELECT c.code, c.name,
+               COALESCE(array_agg(m.cwe_id ORDER BY m.cwe_id) FILTER (WHERE m.cwe_id IS NOT NULL), '{}') AS cwe_ids
+        FROM owasp_categories c
+        LEFT JOIN owasp_cwe_mappings m ON m.category = c.code
+        GROUP BY c.code, c.name
+        ORDER BY c.code
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Add, change, or remove CWE mappings. Findings already stored keep their
+/// category; the mapping applies to findings ingested afterwards.
+pub async fn update_mappings(
+    pool: &PgPool,
+    input: &UpdateOwaspMappings,
+) -> Result<OwaspMappingResult, AppError> {
+    let mut upserts: Vec<(i32, &str)> = Vec::new();
+    let mut removals: Vec<i32> = Vec::new();
+    for change in &input.mappings {
+        if change.cwe_id <= 0 {
+            return Err(AppError::Validation(format!(
+                "Invalid CWE id {}",
+                change.cwe_id
+            )));
+        }
+        match change.category.as_deref() {
+            Some(category) => upserts.push((change.cwe_id, category)),
+            None => removals.push(change.cwe_id),
+        }
+    }
+
+    let known = sqlx::query_scalar::<_, String>("SELECT code FROM owasp_categories")
+        .fetch_all(pool)
+        .await?;
+    if let Some((_, unknown)) = upserts.iter().find(|(_, c)| !known.iter().any(|k| k == c)) {
+        return Err(AppError::Validation(format!(
+            "Unknown OWASP category '{unknown}'; expected one of {}",
+            known.join(", ")
+        )));
+    }
+
+    let mut tx = pool.begin().await?;
+    let (ids, categories): (Vec<i32>, Vec<&str>) = upserts.into_iter().unzip();
+    let updated = sqlx::query(
+        r#"
+        INSERT INTO owasp_cwe_mappings (cwe_id, category)
+        SELECT DISTINCT ON (cwe_id) cwe_id, category
+        FROM UNNEST($1::int[], $2::text[]) WITH ORDINALITY AS m(cwe_id, category, position)
+        ORDER BY cwe_id, position DESC
+        ON CONFLICT (cwe_id) DO UPDATE SET category = EXCLUDED.category, updated_at = NOW()
+        "#,
+    )
+    .bind(&ids)
+    .bind(&categories)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    let removed = sqlx::query("DELETE FROM owasp_cwe_mappings WHERE cwe_id = ANY($1)")
+        .bind(&removals)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    tx.commit().await?;
+
+    Ok(OwaspMappingResult { updated, removed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_codes_are_normalized() {
+        assert_eq!(category_code("A03:2021").as_deref(), Some("A03"));
+        assert_eq!(
+            category_code("A10:2021-Server-Side Request Forgery").as_deref(),
+            Some("A10")
+        );
+        assert_eq!(category_code("OWASP-A03").as_deref(), Some("A03"));
+        assert_eq!(category_code("owasp-a3").as_deref(), Some("A03"));
+        assert_eq!(category_code("Injection"), None);
+    }
+
+    #[test]
+    fn first_mapped_cwe_wins() {
+        let mappings = HashMap::from([(79, "A03:2021".to_string()), (352, "A01:2021".to_string())]);
+        let ids = |ids: &[&str]| ids.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            pick(&ids(&["CWE-1333", "CWE-352", "CWE-79"]), &mappings).as_deref(),
+            Some("A01:2021")
+        );
+        assert_eq!(pick(&ids(&["CWE-1333"]), &mappings), None);
+        assert_eq!(pick(&[], &mappings), None);
+    }
+}
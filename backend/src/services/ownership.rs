@@ -0,0 +1,388 @@
+//! User-to-application ownership assignments.
+//!
+//! Assignments scope what users without a global AppSec role may do on an
+//! application: viewers read the application and its findings, champions
+//! also comment on the findings, and owners also manage the application's
+//! assignments. Owners and champions are the default recipients of its
+//! notifications.
+//!
+//! Global roles grant the same on every application: AppSec managers and
+//! platform admins pass every check, analysts pass champion checks, and
+//! executives, auditors and service accounts pass viewer checks. Developers
+//! only see the applications they are assigned to.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::middleware::auth::CurrentUser;
+use crate::models::notification::NotificationKind;
+use crate::models::ownership::{
+    ApplicationOwner, AssignOwnership, OwnedApplication, OwnershipRole,
+};
+use crate::models::user::UserRole;
+use crate::services::application;
+use crate::services::notification::{self, NewNotification};
+
+/// Assignment columns plus the assigned user's details.
+const SELECT_OWNER: &str = r#"
+    SELECT o.application_id, o.user_id, u.username, u.display_name, u.email,
+           o.role, o.assigned_by, o.created_at, o.updated_at
+    FROM application_owners o
+    JOIN users u ON u.id = o.user_id
+"#;
+
+fn role_label(role: OwnershipRole) -> &'static str {
+    match role {
+        OwnershipRole::Viewer => "viewer",
+        OwnershipRole::Champion => "champion",
+        OwnershipRole::Owner => "owner",
+    }
+}
+
+/// Whether global role `global` alone meets `required` on every application.
+fn global_grants(global: &UserRole, required: OwnershipRole) -> bool {
+    match global {
+        UserRole::PlatformAdmin | UserRole::AppSecManager => true,
+        UserRole::AppSecAnalyst => required <= OwnershipRole::Champion,
+        UserRole::Executive | UserRole::Auditor | UserRole::ApiServiceAccount => {
+            required == OwnershipRole::Viewer
+        }
+        UserRole::Developer => false,
+    }
+}
+
+/// Whether a user with global role `global` and application role `assigned`
+/// meets `required` on the application.
+fn satisfies(global: &UserRole, assigned: Option<OwnershipRole>, required: OwnershipRole) -> bool {
+    global_grants(global, required) || assigned.is_some_and(|role| role >= required)
+}
+
+/// The user whose assignments bound what `user` may list, or `None` when
+/// their global role reads every application.
+pub fn read_scope(user: &CurrentUser) -> Option<Uuid> {
+    (!global_grants(&user.role, OwnershipRole::Viewer)).then_some(user.id)
+}
+
+/// The user's role on an application, if assigned.
+pub async fn role_for(
+    pool: &PgPool,
+    application_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<OwnershipRole>, AppError> {
+    let role = sqlx::query_scalar::<_, OwnershipRole>(
+        "SELECT role FROM application_owners WHERE application_id = $1 AND user_id = $2",
+    )
+    .bind(application_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(role)
+}
+
+fn forbidden(required: OwnershipRole) -> AppError {
+    AppError::Forbidden(format!(
+        "Application {} role or manager access required",
+        role_label(required)
+    ))
+}
+
+/// Require `required` or a higher role on the application, unless the user's
+/// global role grants it.
+pub async fn authorize(
+    pool: &PgPool,
+    user: &CurrentUser,
+    application_id: Uuid,
+    required: OwnershipRole,
+) -> Result<(), AppError> {
+    if global_grants(&user.role, required) {
+        return Ok(());
+    }
+    let assigned = role_for(pool, application_id, user.id).await?;
+    if satisfies(&user.role, assigned, required) {
+        Ok(())
+    } else {
+        Err(forbidden(required))
+    }
+}
+
+/// Require `required` or a higher role on the finding's application, unless
+/// the user's global role grants it. Findings not mapped to an application
+/// are only reachable through a global role.
+pub async fn authorize_finding(
+    pool: &PgPool,
+    user: &CurrentUser,
+    finding_id: Uuid,
+    required: OwnershipRole,
+) -> Result<(), AppError> {
+    if global_grants(&user.role, required) {
+        return Ok(());
+    }
+    let application_id =
+        sqlx::query_scalar::<_, Option<Uuid>>("SELECT application_id FROM findings WHERE id = $1")
+            .bind(finding_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))?;
+    match application_id {
+        Some(application_id) => authorize(pool, user, application_id, required).await,
+        None => Err(forbidden(required)),
+    }
+}
+
+/// Require `required` or a higher role on each of the applications, unless
+/// the user's global role grants it. `None` stands for findings not mapped to
+/// an application, which are only reachable through a global role.
+pub async fn authorize_each(
+    pool: &PgPool,
+    user: &CurrentUser,
+    application_ids: &[Option<Uuid>],
+    required: OwnershipRole,
+) -> Result<(), AppError> {
+    if global_grants(&user.role, required) {
+        return Ok(());
+    }
+    for application_id in application_ids {
+        match application_id {
+            Some(application_id) => authorize(pool, user, *application_id, required).await?,
+            None => return Err(forbidden(required)),
+        }
+    }
+    Ok(())
+}
+
+/// An application's assignments, most privileged first.
+pub async fn list_for_application(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<Vec<ApplicationOwner>, AppError> {
+    application::find_by_id(pool, application_id).await?;
+    let owners = sqlx::query_as::<_, ApplicationOwner>(&format!(
+        "{SELECT_OWNER} WHERE o.application_id = $1 ORDER BY o.role DESC, u.username"
+    ))
+    .bind(application_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(owners)
+}
+
+/// Applications a user is assigned to, by app code.
+pub async fn list_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<OwnedApplication>, AppError> {
+    let applications = sqlx::query_as::<_, OwnedApplication>(
+        r#"
+        SELECT o.application_id, a.app_code, a.app_name, o.role
+        FROM application_owners o
+        JOIN applications a ON a.id = o.application_id
+        WHERE o.user_id = $1
+        ORDER BY a.app_code
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(applications)
+}
+
+/// IDs of the applications a user is assigned to, in any role.
+pub async fn assigned_application_ids(pool: &PgPool, user_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+    let ids = sqlx::query_scalar::<_, Uuid>(
+        "SELECT application_id FROM application_owners WHERE user_id = $1",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(ids)
+}
+
+/// Assign a user to an application, replacing any existing role, and notify
+/// them unless they assigned themselves.
+pub async fn assign(
+    pool: &PgPool,
+    application_id: Uuid,
+    user_id: Uuid,
+    input: &AssignOwnership,
+    actor: &CurrentUser,
+) -> Result<ApplicationOwner, AppError> {
+    let app = application::find_by_id(pool, application_id).await?;
+    let user_exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND is_active)",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+    if !user_exists {
+        return Err(AppError::NotFound(format!("User {user_id} not found")));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO application_owners (application_id, user_id, role, assigned_by)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (application_id, user_id)
+        DO UPDATE SET role = EXCLUDED.role, assigned_by = EXCLUDED.assigned_by
+        "#,
+    )
+    .bind(application_id)
+    .bind(user_id)
+    .bind(input.role)
+    .bind(actor.id)
+    .execute(pool)
+    .await?;
+
+    if user_id != actor.id {
+        notification::send(
+            pool,
+            &[user_id],
+            &NewNotification {
+                kind: NotificationKind::Assignment,
+                title: format!("You are now {} of {}", role_label(input.role), app.app_code),
+                body: None,
+                finding_id: None,
+                application_id: Some(application_id),
+                actor_name: Some(actor.username.clone()),
+            },
+        )
+        .await;
+    }
+
+    let owner = sqlx::query_as::<_, ApplicationOwner>(&format!(
+        "{SELECT_OWNER} WHERE o.application_id = $1 AND o.user_id = $2"
+    ))
+    .bind(application_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(owner)
+}
+
+/// Remove a user's assignment from an application.
+pub async fn remove(pool: &PgPool, application_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    let result =
+        sqlx::query("DELETE FROM application_owners WHERE application_id = $1 AND user_id = $2")
+            .bind(application_id)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "User {user_id} is not assigned to application {application_id}"
+        )));
+    }
+    Ok(())
+}
+
+/// Active owners and champions of an application: the default recipients of
+/// notifications about its findings.
+pub async fn notification_recipients(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<Vec<Uuid>, AppError> {
+    let recipients = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT o.user_id
+        FROM application_owners o
+        JOIN users u ON u.id = o.user_id
+        WHERE o.application_id = $1 AND o.role >= $2 AND u.is_active
+        "#,
+    )
+    .bind(application_id)
+    .bind(OwnershipRole::Champion)
+    .fetch_all(pool)
+    .await?;
+    Ok(recipients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_are_ordered_by_privilege() {
+        assert!(OwnershipRole::Viewer < OwnershipRole::Champion);
+        assert!(OwnershipRole::Champion < OwnershipRole::Owner);
+    }
+
+    #[test]
+    fn assigned_role_must_meet_requirement() {
+        let dev = UserRole::Developer;
+        assert!(satisfies(
+            &dev,
+            Some(OwnershipRole::Owner),
+            OwnershipRole::Owner
+        ));
+        assert!(satisfies(
+            &dev,
+            Some(OwnershipRole::Owner),
+            OwnershipRole::Viewer
+        ));
+        assert!(!satisfies(
+            &dev,
+            Some(OwnershipRole::Champion),
+            OwnershipRole::Owner
+        ));
+        assert!(!satisfies(&dev, None, OwnershipRole::Viewer));
+    }
+
+    #[test]
+    fn managers_and_admins_bypass_assignments() {
+        assert!(satisfies(
+            &UserRole::AppSecManager,
+            None,
+            OwnershipRole::Owner
+        ));
+        assert!(satisfies(
+            &UserRole::PlatformAdmin,
+            None,
+            OwnershipRole::Owner
+        ));
+        assert!(!satisfies(
+            &UserRole::AppSecAnalyst,
+            None,
+            OwnershipRole::Owner
+        ));
+    }
+
+    #[test]
+    fn global_roles_grant_reading_and_analysts_commenting() {
+        for role in [
+            UserRole::AppSecAnalyst,
+            UserRole::Executive,
+            UserRole::Auditor,
+            UserRole::ApiServiceAccount,
+        ] {
+            assert!(satisfies(&role, None, OwnershipRole::Viewer));
+        }
+        assert!(satisfies(
+            &UserRole::AppSecAnalyst,
+            None,
+            OwnershipRole::Champion
+        ));
+        assert!(!satisfies(&UserRole::Auditor, None, OwnershipRole::Champion));
+        assert!(satisfies(
+            &UserRole::Auditor,
+            Some(OwnershipRole::Champion),
+            OwnershipRole::Champion
+        ));
+        assert!(!satisfies(
+            &UserRole::Developer,
+            Some(OwnershipRole::Viewer),
+            OwnershipRole::Champion
+        ));
+    }
+
+    #[test]
+    fn only_developers_have_a_read_scope() {
+        let user = |role| CurrentUser {
+            id: Uuid::new_v4(),
+            username: "user".to_string(),
+            role,
+        };
+        let dev = user(UserRole::Developer);
+        assert_eq!(read_scope(&dev), Some(dev.id));
+        assert_eq!(read_scope(&user(UserRole::Auditor)), None);
+        assert_eq!(read_scope(&user(UserRole::AppSecAnalyst)), None);
+    }
+}
@@ -0,0 +1,169 @@
+//! Optional monthly range partitioning of `findings` and `finding_history`.
+//!
+//! With `TIME_PARTITIONING_ENABLED`, startup converts both tables in place
+//! (`partition_by_month`, migration 014): findings by `first_seen`, history by
+//! `created_at`. Rows from before the conversion stay in each table's default
+//! partition. A background task creates the partitions for the coming months
+//! before any row can land in them; the current month is never created, since
+//! its rows may already be in the default partition.
+//!
+//! Queries bounded on the partition key only scan the months they need: the
+//! discovery date filters and export keyset on findings, and the activity and
+//! MTTR windows on history.
+
+use chrono::{Datelike, NaiveDate, Utc};
+use sqlx::PgPool;
+
+use crate::errors::AppError;
+
+/// Partitioned tables and their partition keys.
+const TABLES: [(&str, &str); 2] = [
+    ("findings", "first_seen"),
+    ("finding_history", "created_at"),
+];
+
+/// How often upcoming partitions are checked; far shorter than a month, so a
+/// restart or a failed check never leaves a month uncovered.
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(6 * 60 * 60);
+
+/// Partition table of `table` for the month starting at `month`, e.g.
+/// `findings_y2026m11`.
+fn partition_name(table: &str, month: NaiveDate) -> String {
+    format!("{table}_y{}m{:02}", month.year(), month.month())
+}
+
+/// First day of the month after `month`.
+fn next_month(month: NaiveDate) -> NaiveDate {
+    let (year, month) = if month.month() == 12 {
+        (month.year() + 1, 1)
+    } else {
+        (month.year(), month.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).expect("first of month is a valid date")
+}
+
+/// Month starts of the `months_ahead` months following the one containing `today`.
+fn upcoming_months(today: NaiveDate, months_ahead: u32) -> Vec<NaiveDate> {
+    let current = today.with_day(1).expect("first of month is a valid date");
+    std::iter::successors(Some(next_month(current)), |m| Some(next_month(*m)))
+        .take(months_ahead as usize)
+        .collect()
+}
+
+/// Convert `findings` and `finding_history` to monthly partitions, unless
+/// they already are, and create the partitions for the months after `today`.
+///
+/// Each conversion locks its table and rebuilds the primary key index and the
+/// dashboard views, so the first startup with partitioning enabled belongs in
+/// a maintenance window.
+pub async fn enable(pool: &PgPool, today: NaiveDate, months_ahead: u32) -> Result<(), AppError> {
+    for (table, key) in TABLES {
+        let converted = sqlx::query_scalar::<_, bool>("SELECT partition_by_month($1, $2)")
+            .bind(table)
+            .bind(key)
+            .fetch_one(pool)
+            .await?;
+        if converted {
+            tracing::info!(table, "Converted table to monthly partitions");
+        }
+    }
+    ensure_partitions(pool, today, months_ahead).await?;
+    Ok(())
+}
+
+/// Create any missing monthly partitions for the months after `today`, on
+/// each table that is partitioned.
+///
+/// A month that cannot be created is logged and skipped. Returns the number
+/// of partitions created.
+pub async fn ensure_partitions(
+    pool: &PgPool,
+    today: NaiveDate,
+    months_ahead: u32,
+) -> Result<usize, AppError> {
+    let mut created = 0;
+    for (table, _) in TABLES {
+        let partitioned = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM pg_class WHERE oid = to_regclass($1) AND relkind = 'p')",
+        )
+        .bind(table)
+        .fetch_one(pool)
+        .await?;
+        if !partitioned {
+            continue;
+        }
+
+        for month in upcoming_months(today, months_ahead) {
+            let name = partition_name(table, month);
+            let exists = sqlx::query_scalar::<_, bool>("SELECT to_regclass($1) IS NOT NULL")
+                .bind(&name)
+                .fetch_one(pool)
+                .await?;
+            if exists {
+                continue;
+            }
+
+            // Identifiers and bounds come from constants and dates, never from user input
+            let ddl = format!(
+                "CREATE TABLE IF NOT EXISTS {name} PARTITION OF {table} \
+                 FOR VALUES FROM ('{month}') TO ('{}')",
+                next_month(month)
+            );
+            match sqlx::query(&ddl).execute(pool).await {
+                Ok(_) => {
+                    tracing::info!(partition = %name, "Created monthly partition");
+                    created += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(partition = %name, error = %e, "Could not create monthly partition");
+                }
+            }
+        }
+    }
+    Ok(created)
+}
+
+/// Spawn the background task that keeps `months_ahead` monthly partitions
+/// ready on the partitioned tables. Runs whether or not partitioning is
+/// enabled, so tables converted earlier keep getting partitions.
+pub fn spawn_maintainer(pool: PgPool, months_ahead: u32) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = ensure_partitions(&pool, Utc::now().date_naive(), months_ahead).await {
+                tracing::error!(error = %e, "Monthly partition maintenance failed");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn upcoming_months_start_after_the_current_month() {
+        assert_eq!(
+            upcoming_months(date(2026, 11, 17), 3),
+            [date(2026, 12, 1), date(2027, 1, 1), date(2027, 2, 1)]
+        );
+        assert!(upcoming_months(date(2026, 11, 17), 0).is_empty());
+    }
+
+    #[test]
+    fn partition_names_are_zero_padded() {
+        assert_eq!(
+            partition_name("finding_history", date(2027, 2, 1)),
+            "finding_history_y2027m02"
+        );
+        assert_eq!(
+            partition_name("findings", date(2026, 11, 1)),
+            "findings_y2026m11"
+        );
+    }
+}
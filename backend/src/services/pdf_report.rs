@@ -0,0 +1,263 @@
+//! Minimal PDF rendering for generated reports.
+//!
+//! Reports are described as a flat list of blocks (headings, paragraphs,
+//! key/value lists, tables) and laid out top-to-bottom on A4 pages using the
+//! PDF built-in Helvetica fonts, so no font files need to ship with the binary.
+
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference};
+use serde::Serialize;
+
+use crate::errors::AppError;
+use crate::services::text;
+
+/// A4 page width in millimetres.
+const PAGE_WIDTH_MM: f32 = 210.0;
+/// A4 page height in millimetres.
+const PAGE_HEIGHT_MM: f32 = 297.0;
+/// Margin applied on all four sides.
+const MARGIN_MM: f32 = 18.0;
+
+const TITLE_PT: f32 = 18.0;
+const HEADING_PT: f32 = 13.0;
+const BODY_PT: f32 = 10.0;
+const TABLE_PT: f32 = 8.5;
+
+/// Points to millimetres (1pt = 1/72 in).
+const PT_TO_MM: f32 = 0.3528;
+/// Average Helvetica glyph width as a fraction of the font size. Used to
+/// estimate how many characters fit on a line without font metrics.
+const AVG_GLYPH_WIDTH: f32 = 0.5;
+/// Line height as a multiple of the font size.
+const LINE_SPACING: f32 = 1.45;
+
+/// A report ready to be rendered.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportDocument {
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub blocks: Vec<ReportBlock>,
+}
+
+/// One vertical block of report content.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReportBlock {
+    Heading(String),
+    Paragraph(String),
+    /// Label/value pairs rendered as two aligned columns.
+    KeyValues(Vec<(String, String)>),
+    Table {
+        columns: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+/// Render a report to PDF bytes.
+pub fn render(report: &ReportDocument) -> Result<Vec<u8>, AppError> {
+    let mut writer = PageWriter::new(&report.title)?;
+    let usable = writer.usable_width();
+
+    writer.line(&report.title, TITLE_PT, true);
+    if let Some(subtitle) = &report.subtitle {
+        writer.line(subtitle, BODY_PT, false);
+    }
+    writer.gap(BODY_PT);
+
+    for block in &report.blocks {
+        match block {
+            ReportBlock::Heading(text) => {
+                writer.gap(BODY_PT * 0.5);
+                writer.line(text, HEADING_PT, true);
+            }
+            ReportBlock::Paragraph(text) => {
+                for line in wrap(text, chars_per_line(usable, BODY_PT)) {
+                    writer.line(&line, BODY_PT, false);
+                }
+            }
+            ReportBlock::KeyValues(pairs) => {
+                let widths = [usable * 0.4, usable * 0.6];
+                for (label, value) in pairs {
+                    writer.cells(&[label.as_str(), value.as_str()], &widths, BODY_PT, false);
+                }
+            }
+            ReportBlock::Table { columns, rows } => {
+                let width = usable / columns.len().max(1) as f32;
+                let widths = vec![width; columns.len()];
+                let header: Vec<&str> = columns.iter().map(String::as_str).collect();
+                writer.cells(&header, &widths, TABLE_PT, true);
+                for row in rows {
+                    let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+                    writer.cells(&cells, &widths, TABLE_PT, false);
+                }
+            }
+        }
+        writer.gap(BODY_PT * 0.5);
+    }
+
+    writer
+        .doc
+        .save_to_bytes()
+        .map_err(|e| AppError::Internal(format!("PDF generation failed: {e}")))
+}
+
+/// Sequential text layout across as many pages as needed.
+struct PageWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    /// Baseline of the next line, measured from the page bottom.
+    y: f32,
+}
+
+impl PageWriter {
+    fn new(title: &str) -> Result<Self, AppError> {
+        let (doc, page, layer) = PdfDocument::new(
+            printable(title),
+            Mm(PAGE_WIDTH_MM),
+            Mm(PAGE_HEIGHT_MM),
+            "Layer 1",
+        );
+        let font_err = |e: printpdf::Error| AppError::Internal(format!("PDF font error: {e}"));
+        let regular = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(font_err)?;
+        let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(font_err)?;
+        let layer = doc.get_page(page).get_layer(layer);
+        Ok(Self {
+            doc,
+            layer,
+            regular,
+            bold,
+            y: PAGE_HEIGHT_MM - MARGIN_MM,
+        })
+    }
+
+    fn usable_width(&self) -> f32 {
+        PAGE_WIDTH_MM - 2.0 * MARGIN_MM
+    }
+
+    /// Reserve vertical space for one line, starting a new page if needed.
+    fn advance(&mut self, size_pt: f32) -> f32 {
+        let height = size_pt * PT_TO_MM * LINE_SPACING;
+        if self.y - height < MARGIN_MM {
+            let (page, layer) = self
+                .doc
+                .add_page(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Layer 1");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT_MM - MARGIN_MM;
+        }
+        self.y -= height;
+        self.y
+    }
+
+    fn gap(&mut self, size_pt: f32) {
+        self.y -= size_pt * PT_TO_MM;
+    }
+
+    fn line(&mut self, text: &str, size_pt: f32, bold: bool) {
+        let y = self.advance(size_pt);
+        let max = chars_per_line(self.usable_width(), size_pt);
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer
+            .use_text(truncate(text, max), size_pt, Mm(MARGIN_MM), Mm(y), font);
+    }
+
+    fn cells(&mut self, cells: &[&str], widths: &[f32], size_pt: f32, bold: bool) {
+        let y = self.advance(size_pt);
+        let font = if bold { &self.bold } else { &self.regular };
+        let mut x = MARGIN_MM;
+        for (text, width) in cells.iter().zip(widths) {
+            // Leave roughly one glyph of padding between columns.
+            let max = chars_per_line(*width, size_pt).saturating_sub(1);
+            self.layer
+                .use_text(truncate(text, max), size_pt, Mm(x), Mm(y), font);
+            x += width;
+        }
+    }
+}
+
+/// Estimated number of characters that fit in `width_mm` at `size_pt`.
+fn chars_per_line(width_mm: f32, size_pt: f32) -> usize {
+    (width_mm / (size_pt * PT_TO_MM * AVG_GLYPH_WIDTH)).floor() as usize
+}
+
+/// Shorten `text` to at most `max` characters, marking the cut with `...`.
+fn truncate(text: &str, max: usize) -> String {
+    let text = printable(text);
+    if text.chars().count() <= max {
+        return text;
+    }
+    let mut out = text::truncate(&text, max.saturating_sub(3));
+    out.push_str("...");
+    out
+}
+
+/// Greedy word wrap at `width` characters.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Replace characters outside Latin-1, which the built-in fonts cannot encode.
+fn printable(text: &str) -> String {
+    text.chars()
+        .map(|c| if (c as u32) < 0x100 && !c.is_control() { c } else { '?' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_respects_width() {
+        let lines = wrap("the quick brown fox jumps over the lazy dog", 10);
+        assert!(lines.iter().all(|l| l.chars().count() <= 10));
+        assert_eq!(lines.join(" "), "the quick brown fox jumps over the lazy dog");
+    }
+
+    #[test]
+    fn truncate_marks_cut() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a very long application name", 10), "a very ...");
+    }
+
+    #[test]
+    fn printable_replaces_unencodable_chars() {
+        assert_eq!(printable("caffè → ok"), "caffè ? ok");
+    }
+
+    #[test]
+    fn render_produces_pdf() {
+        let rows: Vec<Vec<String>> = (0..200)
+            .map(|i| vec![format!("app-{i}"), i.to_string()])
+            .collect();
+        let doc = ReportDocument {
+            title: "Report".to_string(),
+            subtitle: Some("subtitle".to_string()),
+            blocks: vec![
+                ReportBlock::Heading("Section".to_string()),
+                ReportBlock::Paragraph("Some text ".repeat(50)),
+                ReportBlock::KeyValues(vec![("Open".to_string(), "12".to_string())]),
+                ReportBlock::Table {
+                    columns: vec!["Application".to_string(), "Count".to_string()],
+                    rows,
+                },
+            ],
+        };
+        let bytes = render(&doc).expect("renders");
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+}
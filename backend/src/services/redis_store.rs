@@ -0,0 +1,169 @@
+//! Shared Redis connection and cache helpers.
+//!
+//! Redis holds state shared between backend instances: rate limit buckets,
+//! cached dashboard statistics, revoked refresh tokens, and configuration
+//! cache invalidations. None of it is authoritative, so the helpers degrade
+//! gracefully: while Redis is unreachable reads are misses, writes are
+//! skipped, and the failure is logged. The connection is reopened on the next
+//! call after an error. Helpers whose callers must not proceed on an outage,
+//! such as refresh token claims, report it as `None` instead.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use redis::aio::MultiplexedConnection;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::Mutex;
+
+use crate::config::AppConfig;
+
+/// Lazily (re)opened multiplexed Redis connection. Cheap to clone; clones
+/// share the connection.
+#[derive(Clone)]
+pub struct RedisStore {
+    client: redis::Client,
+    connection: Arc<Mutex<Option<MultiplexedConnection>>>,
+}
+
+impl std::fmt::Debug for RedisStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisStore").finish_non_exhaustive()
+    }
+}
+
+impl RedisStore {
+    /// Wrap a client; no connection is opened until first use.
+    pub fn new(client: redis::Client) -> Self {
+        Self {
+            client,
+            connection: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Build the store, or `None` when the Redis URL is invalid.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        match redis::Client::open(config.redis_url.as_str()) {
+            Ok(client) => Some(Self::new(client)),
+            Err(e) => {
+                tracing::error!(error = %e, "Invalid REDIS_URL; Redis features disabled");
+                None
+            }
+        }
+    }
+
+    /// The underlying client, for dedicated connections such as pub/sub.
+    pub fn client(&self) -> &redis::Client {
+        &self.client
+    }
+
+    /// The shared connection, opened on first use.
+    pub async fn connection(&self) -> redis::RedisResult<MultiplexedConnection> {
+        let mut guard = self.connection.lock().await;
+        if let Some(conn) = guard.as_ref() {
+            return Ok(conn.clone());
+        }
+        let conn = self.client.get_multiplexed_async_connection().await?;
+        *guard = Some(conn.clone());
+        Ok(conn)
+    }
+
+    /// Drop the shared connection so the next call reconnects. Call after a
+    /// command fails.
+    pub async fn reset(&self) {
+        *self.connection.lock().await = None;
+    }
+
+    /// Run a command, logging and resetting the connection on failure.
+    async fn query<T: redis::FromRedisValue>(&self, cmd: &redis::Cmd, what: &str) -> Option<T> {
+        let result = match self.connection().await {
+            Ok(mut conn) => cmd.query_async(&mut conn).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!(error = %e, operation = what, "Redis unavailable");
+                self.reset().await;
+                None
+            }
+        }
+    }
+
+    /// Check that Redis answers.
+    pub async fn ping(&self) -> redis::RedisResult<()> {
+        let mut conn = self.connection().await?;
+        let result: redis::RedisResult<String> = redis::cmd("PING").query_async(&mut conn).await;
+        if result.is_err() {
+            self.reset().await;
+        }
+        result.map(|_| ())
+    }
+
+    /// Cached JSON value; `None` on a miss, an undecodable value, or when
+    /// Redis is unavailable.
+    pub async fn get_json<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let raw: Option<String> = self.query(redis::cmd("GET").arg(key), "get").await?;
+        match serde_json::from_str(&raw?) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!(error = %e, key, "Discarding undecodable cached value");
+                None
+            }
+        }
+    }
+
+    /// Cache a value as JSON for `ttl` (at least one second).
+    pub async fn set_json<T: Serialize>(&self, key: &str, value: &T, ttl: Duration) {
+        let raw = match serde_json::to_string(value) {
+            Ok(raw) => raw,
+            Err(e) => {
+                tracing::warn!(error = %e, key, "Value not cached");
+                return;
+            }
+        };
+        let cmd = redis::cmd("SET")
+            .arg(key)
+            .arg(raw)
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .clone();
+        let _: Option<()> = self.query(&cmd, "set").await;
+    }
+
+    /// Set a marker key that expires after `ttl` (at least one second).
+    pub async fn set_marker(&self, key: &str, ttl: Duration) {
+        self.set_json(key, &1, ttl).await;
+    }
+
+    /// Set a marker key that expires after `ttl` (at least one second) unless
+    /// it already exists. `Some(true)` when this call set it, `Some(false)`
+    /// when it was already set, `None` when Redis is unavailable.
+    pub async fn set_marker_if_absent(&self, key: &str, ttl: Duration) -> Option<bool> {
+        let cmd = redis::cmd("SET")
+            .arg(key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl.as_secs().max(1))
+            .clone();
+        let reply: Option<String> = self.query(&cmd, "set_nx").await?;
+        Some(reply.is_some())
+    }
+
+    /// Whether `key` exists; `None` when Redis is unavailable.
+    pub async fn exists(&self, key: &str) -> Option<bool> {
+        self.query(redis::cmd("EXISTS").arg(key), "exists").await
+    }
+
+    /// Delete `key`.
+    pub async fn delete(&self, key: &str) {
+        let _: Option<i64> = self.query(redis::cmd("DEL").arg(key), "delete").await;
+    }
+
+    /// Publish `message` on `channel` to the other backend instances.
+    pub async fn publish(&self, channel: &str, message: &str) {
+        let _: Option<i64> = self
+            .query(redis::cmd("PUBLISH").arg(channel).arg(message), "publish")
+            .await;
+    }
+}
@@ -0,0 +1,250 @@
+//! Application releases and the findings they introduced and fixed.
+//!
+//! Each finding can point at the release in which it first appeared and the
+//! release that fixed it. Release lists carry both counts for first-introduced
+//! analytics, and release notes list the findings behind them.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::Finding;
+use crate::models::release::{
+    CreateRelease, Release, ReleaseFinding, ReleaseNotes, ReleaseWithCounts, SetFindingReleases,
+    UpdateRelease,
+};
+use crate::services::application;
+
+/// Shortest accepted commit hash, matching git's default abbreviation.
+const MIN_COMMIT_SHA_LEN: usize = 7;
+
+/// Columns of a finding as listed in release notes.
+const SELECT_RELEASE_FINDING: &str = r#"
+    SELECT id, title, finding_category, normalized_severity, status, cve_ids, first_seen
+    FROM findings
+"#;
+
+/// Trim a version label, rejecting blank ones.
+fn normalize_version(version: &str) -> Result<String, AppError> {
+    let version = version.trim();
+    if version.is_empty() {
+        return Err(AppError::Validation("Version cannot be blank".to_string()));
+    }
+    Ok(version.to_string())
+}
+
+/// Lowercase a commit hash, rejecting values that are not abbreviated or full
+/// SHA-1/SHA-256 hex digests.
+fn normalize_commit_sha(sha: &str) -> Result<String, AppError> {
+    let sha = sha.trim().to_ascii_lowercase();
+    if sha.len() < MIN_COMMIT_SHA_LEN || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AppError::Validation(format!(
+            "Invalid commit hash '{sha}': expected at least {MIN_COMMIT_SHA_LEN} hex characters"
+        )));
+    }
+    Ok(sha)
+}
+
+/// Map the `(application_id, version)` unique violation to a readable conflict.
+fn version_conflict(e: sqlx::Error, version: &str) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => AppError::Conflict(
+            format!("Release '{version}' already exists for this application"),
+        ),
+        _ => AppError::Database(e),
+    }
+}
+
+/// An application's releases with introduced and fixed counts, newest build first.
+pub async fn list_for_application(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<Vec<ReleaseWithCounts>, AppError> {
+    application::find_by_id(pool, application_id).await?;
+    let releases = sqlx::query_as::<_, ReleaseWithCounts>(
+        r#"
+        SELECT r.*,
+               (SELECT COUNT(*) FROM findings f WHERE f.introduced_in_release_id = r.id)
+                   AS introduced_count,
+               (SELECT COUNT(*) FROM findings f WHERE f.fixed_in_release_id = r.id)
+                   AS fixed_count
+        FROM releases r
+        WHERE r.application_id = $1
+        ORDER BY r.build_date DESC NULLS LAST, r.created_at DESC
+        "#,
+    )
+    .bind(application_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(releases)
+}
+
+/// Fetch a release by ID.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Release, AppError> {
+    sqlx::query_as::<_, Release>("SELECT * FROM releases WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Release {id} not found")))
+}
+
+/// Create a release for an application.
+pub async fn create(
+    pool: &PgPool,
+    application_id: Uuid,
+    input: &CreateRelease,
+) -> Result<Release, AppError> {
+    application::find_by_id(pool, application_id).await?;
+    let version = normalize_version(&input.version)?;
+    let commit_sha = input
+        .commit_sha
+        .as_deref()
+        .map(normalize_commit_sha)
+        .transpose()?;
+
+    sqlx::query_as::<_, Release>(
+        r#"
+        INSERT INTO releases (application_id, version, build_date, commit_sha)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(application_id)
+    .bind(&version)
+    .bind(input.build_date)
+    .bind(&commit_sha)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| version_conflict(e, &version))
+}
+
+/// Update a release; omitted fields keep their values.
+pub async fn update(pool: &PgPool, id: Uuid, input: &UpdateRelease) -> Result<Release, AppError> {
+    let existing = find_by_id(pool, id).await?;
+    let version = match &input.version {
+        Some(version) => normalize_version(version)?,
+        None => existing.version,
+    };
+    let commit_sha = match &input.commit_sha {
+        Some(sha) => Some(normalize_commit_sha(sha)?),
+        None => existing.commit_sha,
+    };
+
+    sqlx::query_as::<_, Release>(
+        r#"
+        UPDATE releases SET version = $1, build_date = $2, commit_sha = $3
+        WHERE id = $4
+        RETURNING *
+        "#,
+    )
+    .bind(&version)
+    .bind(input.build_date.or(existing.build_date))
+    .bind(&commit_sha)
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| version_conflict(e, &version))
+}
+
+/// Delete a release; findings linked to it lose the link.
+pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM releases WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Release {id} not found")));
+    }
+    Ok(())
+}
+
+/// Link a finding to the releases that introduced and fixed it. Both
+/// releases must belong to the finding's application.
+pub async fn set_finding_releases(
+    pool: &PgPool,
+    finding_id: Uuid,
+    input: &SetFindingReleases,
+) -> Result<Finding, AppError> {
+    let application_id =
+        sqlx::query_scalar::<_, Option<Uuid>>("SELECT application_id FROM findings WHERE id = $1")
+            .bind(finding_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))?;
+
+    for release_id in [input.introduced_in_release_id, input.fixed_in_release_id]
+        .into_iter()
+        .flatten()
+    {
+        let release = find_by_id(pool, release_id).await?;
+        if application_id != Some(release.application_id) {
+            return Err(AppError::Validation(format!(
+                "Release {release_id} does not belong to the finding's application"
+            )));
+        }
+    }
+
+    let finding = sqlx::query_as::<_, Finding>(
+        r#"
+        UPDATE findings SET
+            introduced_in_release_id = $2,
+            fixed_in_release_id = $3,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(finding_id)
+    .bind(input.introduced_in_release_id)
+    .bind(input.fixed_in_release_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(finding)
+}
+
+/// Findings fixed in and introduced by a release, most severe first.
+pub async fn notes(pool: &PgPool, id: Uuid) -> Result<ReleaseNotes, AppError> {
+    let release = find_by_id(pool, id).await?;
+    let order = "ORDER BY normalized_severity, first_seen";
+    let fixed = sqlx::query_as::<_, ReleaseFinding>(&format!(
+        "{SELECT_RELEASE_FINDING} WHERE fixed_in_release_id = $1 {order}"
+    ))
+    .bind(id)
+    .fetch_all(pool)
+    .await?;
+    let introduced = sqlx::query_as::<_, ReleaseFinding>(&format!(
+        "{SELECT_RELEASE_FINDING} WHERE introduced_in_release_id = $1 {order}"
+    ))
+    .bind(id)
+    .fetch_all(pool)
+    .await?;
+    Ok(ReleaseNotes {
+        release,
+        fixed,
+        introduced,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_hashes_are_lowercase_hex() {
+        assert_eq!(normalize_commit_sha(" 3F2A9C1 ").unwrap(), "3f2a9c1");
+        assert_eq!(
+            normalize_commit_sha("0123456789abcdef0123456789abcdef01234567")
+                .unwrap()
+                .len(),
+            40
+        );
+        assert!(normalize_commit_sha("abc123").is_err());
+        assert!(normalize_commit_sha("main-branch").is_err());
+    }
+
+    #[test]
+    fn versions_are_trimmed_and_required() {
+        assert_eq!(normalize_version(" 2.3.0 ").unwrap(), "2.3.0");
+        assert!(normalize_version("   ").is_err());
+    }
+}
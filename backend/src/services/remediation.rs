@@ -0,0 +1,302 @@
+//! SCA upgrade plans: one recommended upgrade per vulnerable package.
+//!
+//! Open SCA findings are grouped by package, and each group gets the lowest
+//! version that resolves all of them: the highest per-finding fixed version,
+//! raised past any OSV affected range of the group's findings that still
+//! contains it. Findings with no known fix are counted so the plan shows what
+//! an upgrade leaves behind.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::SeverityLevel;
+use crate::models::finding_sca::AffectedRange;
+use crate::services::osv::{compare_versions, contains};
+
+/// Query parameters for the portfolio upgrade plan.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UpgradePlanQuery {
+    /// Only this package (case-insensitive exact name).
+    pub package: Option<String>,
+}
+
+/// The recommended upgrade for one package.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct PackageUpgrade {
+    pub package_name: String,
+    pub package_type: Option<String>,
+    /// Versions currently in use by the affected findings, oldest first.
+    pub installed_versions: Vec<String>,
+    /// Lowest version resolving every finding with a known fix; `None` when
+    /// no finding has one.
+    pub target_version: Option<String>,
+    pub highest_severity: SeverityLevel,
+    pub finding_count: i64,
+    /// Findings with no known fixed version, which the upgrade may not resolve.
+    pub unfixed_count: i64,
+    pub application_count: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ScaRow {
+    application_id: Option<Uuid>,
+    normalized_severity: SeverityLevel,
+    package_name: String,
+    package_type: Option<String>,
+    package_version: String,
+    fixed_version: Option<String>,
+    affected_ranges: serde_json::Value,
+}
+
+/// The fix a finding needs. Scanners may list one fix per release line
+/// (`2.12.2, 2.17.1`) or decorate it (`>=2.17.1`); the lowest candidate newer
+/// than the installed version is used, else the highest.
+fn fix_for(fixed_version: &str, installed: &str) -> Option<String> {
+    let mut candidates: Vec<&str> = fixed_version
+        .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+        .map(|v| v.trim_matches(|c: char| matches!(c, '>' | '=' | '[' | ']' | '(' | ')')))
+        .filter(|v| !v.is_empty())
+        .collect();
+    candidates.sort_by(|a, b| compare_versions(a, b));
+    candidates
+        .iter()
+        .find(|v| compare_versions(v, installed) == Ordering::Greater)
+        .or(candidates.last())
+        .map(|v| v.to_string())
+}
+
+fn max_version(current: Option<String>, candidate: &str) -> Option<String> {
+    match current {
+        Some(current) if compare_versions(candidate, &current) != Ordering::Greater => {
+            Some(current)
+        }
+        _ => Some(candidate.to_string()),
+    }
+}
+
+/// Raise `target` past every range that still contains it. Each step moves to
+/// a strictly higher fix, so this ends after at most one step per range.
+fn clear_ranges(mut target: String, ranges: &[AffectedRange]) -> String {
+    loop {
+        let next = ranges
+            .iter()
+            .filter(|r| r.range_type != "GIT" && contains(r, &target))
+            .filter_map(|r| r.fixed.as_deref())
+            .filter(|fixed| compare_versions(fixed, &target) == Ordering::Greater)
+            .max_by(|a, b| compare_versions(a, b));
+        match next {
+            Some(fixed) => target = fixed.to_string(),
+            None => return target,
+        }
+    }
+}
+
+fn plan(rows: Vec<ScaRow>) -> Vec<PackageUpgrade> {
+    let mut groups: BTreeMap<(String, String), Vec<ScaRow>> = BTreeMap::new();
+    for row in rows {
+        let key = (
+            row.package_type
+                .as_deref()
+                .unwrap_or("")
+                .to_ascii_lowercase(),
+            row.package_name.to_ascii_lowercase(),
+        );
+        groups.entry(key).or_default().push(row);
+    }
+
+    let mut upgrades: Vec<PackageUpgrade> = groups
+        .into_values()
+        .map(|rows| {
+            let mut target: Option<String> = None;
+            let mut unfixed_count = 0;
+            let mut ranges: Vec<AffectedRange> = Vec::new();
+            for row in &rows {
+                match row
+                    .fixed_version
+                    .as_deref()
+                    .and_then(|fixed| fix_for(fixed, &row.package_version))
+                {
+                    Some(fix) => target = max_version(target, &fix),
+                    None => unfixed_count += 1,
+                }
+                if let Ok(row_ranges) =
+                    serde_json::from_value::<Vec<AffectedRange>>(row.affected_ranges.clone())
+                {
+                    ranges.extend(row_ranges);
+                }
+            }
+            let target_version = target.map(|t| clear_ranges(t, &ranges));
+
+            let mut installed_versions: Vec<String> =
+                rows.iter().map(|r| r.package_version.clone()).collect();
+            installed_versions.sort_by(|a, b| compare_versions(a, b));
+            installed_versions.dedup();
+            let mut applications: Vec<Uuid> =
+                rows.iter().filter_map(|r| r.application_id).collect();
+            applications.sort();
+            applications.dedup();
+            let highest_severity = rows
+                .iter()
+                .map(|r| r.normalized_severity.clone())
+                .max_by_key(SeverityLevel::rank)
+                .unwrap_or(SeverityLevel::Info);
+
+            PackageUpgrade {
+                package_name: rows[0].package_name.clone(),
+                package_type: rows[0].package_type.clone(),
+                installed_versions,
+                target_version,
+                highest_severity,
+                finding_count: rows.len() as i64,
+                unfixed_count,
+                application_count: applications.len() as i64,
+            }
+        })
+        .collect();
+
+    upgrades.sort_by(|a, b| {
+        b.highest_severity
+            .rank()
+            .cmp(&a.highest_severity.rank())
+            .then(b.finding_count.cmp(&a.finding_count))
+            .then_with(|| a.package_name.cmp(&b.package_name))
+    });
+    upgrades
+}
+
+async fn fetch_rows(
+    pool: &PgPool,
+    application_id: Option<Uuid>,
+    package: Option<&str>,
+) -> Result<Vec<ScaRow>, AppError> {
+    let rows = sqlx::query_as::<_, ScaRow>(
+        r#"
+        SELECT f.application_id, f.normalized_severity, s.package_name, s.package_type,
+               s.package_version, s.fixed_version, s.affected_ranges
+        FROM findings f
+        JOIN finding_sca s ON s.finding_id = f.id
+        WHERE f.finding_category = 'SCA'
+          AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND f.archived_at IS NULL
+          AND ($1::uuid IS NULL OR f.application_id = $1)
+          AND ($2::text IS NULL OR LOWER(s.package_name) = LOWER($2))
+        "#,
+    )
+    .bind(application_id)
+    .bind(package)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Upgrade plan across the portfolio, most severe packages first.
+pub async fn portfolio_plan(
+    pool: &PgPool,
+    query: &UpgradePlanQuery,
+) -> Result<Vec<PackageUpgrade>, AppError> {
+    let package = query
+        .package
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    Ok(plan(fetch_rows(pool, None, package).await?))
+}
+
+/// Upgrade plan for one application.
+pub async fn application_plan(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<Vec<PackageUpgrade>, AppError> {
+    let exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM applications WHERE id = $1)")
+            .bind(application_id)
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Application {application_id} not found"
+        )));
+    }
+    Ok(plan(fetch_rows(pool, Some(application_id), None).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        app: u128,
+        severity: SeverityLevel,
+        version: &str,
+        fixed: Option<&str>,
+        ranges: serde_json::Value,
+    ) -> ScaRow {
+        ScaRow {
+            application_id: Some(Uuid::from_u128(app)),
+            normalized_severity: severity,
+            package_name: "log4j-core".to_string(),
+            package_type: Some("maven".to_string()),
+            package_version: version.to_string(),
+            fixed_version: fixed.map(str::to_string),
+            affected_ranges: ranges,
+        }
+    }
+
+    #[test]
+    fn fix_picks_the_release_line_of_the_installed_version() {
+        assert_eq!(
+            fix_for("2.12.2, 2.17.1", "2.12.1").as_deref(),
+            Some("2.12.2")
+        );
+        assert_eq!(
+            fix_for("2.12.2, 2.17.1", "2.14.0").as_deref(),
+            Some("2.17.1")
+        );
+        assert_eq!(fix_for(">=2.17.1", "2.14.0").as_deref(), Some("2.17.1"));
+        assert_eq!(fix_for(" ", "2.14.0"), None);
+    }
+
+    #[test]
+    fn target_clears_every_finding_and_known_range() {
+        let ranges = serde_json::json!([{
+            "vulnerability_id": "GHSA-p6xc-xr62-6r2g",
+            "range_type": "ECOSYSTEM",
+            "introduced": "2.13.0",
+            "fixed": "2.17.0",
+            "last_affected": null
+        }]);
+        let rows = vec![
+            row(
+                1,
+                SeverityLevel::Critical,
+                "2.14.1",
+                Some("2.15.0"),
+                serde_json::json!([]),
+            ),
+            row(2, SeverityLevel::High, "2.14.1", Some("2.16.0"), ranges),
+            row(
+                2,
+                SeverityLevel::Medium,
+                "2.13.3",
+                None,
+                serde_json::json!([]),
+            ),
+        ];
+        let upgrades = plan(rows);
+        assert_eq!(upgrades.len(), 1);
+        let upgrade = &upgrades[0];
+        assert_eq!(upgrade.target_version.as_deref(), Some("2.17.0"));
+        assert_eq!(upgrade.installed_versions, ["2.13.3", "2.14.1"]);
+        assert_eq!(upgrade.highest_severity, SeverityLevel::Critical);
+        assert_eq!(upgrade.finding_count, 3);
+        assert_eq!(upgrade.unfixed_count, 1);
+        assert_eq!(upgrade.application_count, 2);
+    }
+}
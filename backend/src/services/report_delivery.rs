@@ -0,0 +1,238 @@
+//! Delivery transports for generated reports and notification emails: SMTP
+//! email and HTTP webhook.
+
+use std::time::Duration;
+
+use lettre::message::{header::ContentType, Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::config::AppConfig;
+use crate::errors::AppError;
+
+/// Timeout for a single webhook POST.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A rendered report ready to hand to a transport.
+#[derive(Debug, Clone)]
+pub struct RenderedReport {
+    pub subject: String,
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+/// SMTP relay settings derived from [`AppConfig`].
+#[derive(Clone)]
+pub struct SmtpSettings {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+}
+
+impl std::fmt::Debug for SmtpSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpSettings")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "[redacted]"))
+            .field("from", &self.from)
+            .finish()
+    }
+}
+
+impl SmtpSettings {
+    /// Build settings from config, returning `None` when SMTP is not configured.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        Some(Self {
+            host: config.smtp_host.clone()?,
+            port: config.smtp_port,
+            username: config.smtp_username.clone(),
+            password: config.smtp_password.clone(),
+            from: config.smtp_from.clone(),
+        })
+    }
+}
+
+/// Transports available to the report scheduler.
+#[derive(Debug, Clone)]
+pub struct ReportDelivery {
+    smtp: Option<SmtpSettings>,
+    http: reqwest::Client,
+}
+
+impl ReportDelivery {
+    pub fn from_config(config: &AppConfig) -> Result<Self, AppError> {
+        let http = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|e| AppError::Internal(format!("Failed to build webhook client: {e}")))?;
+        Ok(Self {
+            smtp: SmtpSettings::from_config(config),
+            http,
+        })
+    }
+
+    /// Email the report as an attachment to every recipient in one message.
+    pub async fn send_email(
+        &self,
+        recipients: &[String],
+        report: &RenderedReport,
+    ) -> Result<(), AppError> {
+        let settings = self.smtp.as_ref().ok_or_else(|| {
+            AppError::Internal("Email delivery requested but SMTP_HOST is not configured".to_string())
+        })?;
+
+        let from: Mailbox = settings
+            .from
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid SMTP_FROM address: {e}")))?;
+        let mut builder = Message::builder().from(from).subject(report.subject.clone());
+        for recipient in recipients {
+            builder = builder.to(parse_mailbox(recipient)?);
+        }
+
+        let content_type = ContentType::parse(report.content_type)
+            .map_err(|e| AppError::Internal(format!("Invalid report content type: {e}")))?;
+        let message = builder
+            .multipart(
+                MultiPart::mixed()
+                    .singlepart(SinglePart::plain(format!(
+                        "{}\n\nThe report is attached as {}.\n",
+                        report.subject, report.file_name
+                    )))
+                    .singlepart(
+                        Attachment::new(report.file_name.clone())
+                            .body(report.body.clone(), content_type),
+                    ),
+            )
+            .map_err(|e| AppError::Internal(format!("Failed to build report email: {e}")))?;
+
+        deliver_smtp(settings, message).await
+    }
+
+    /// Email a plain-text message, used for notification emails and digests.
+    pub async fn send_text_email(
+        &self,
+        recipient: &str,
+        subject: &str,
+        body: String,
+    ) -> Result<(), AppError> {
+        let settings = self.smtp.as_ref().ok_or_else(|| {
+            AppError::Internal("Email delivery requested but SMTP_HOST is not configured".to_string())
+        })?;
+
+        let from: Mailbox = settings
+            .from
+            .parse()
+            .map_err(|e| AppError::Internal(format!("Invalid SMTP_FROM address: {e}")))?;
+        let message = Message::builder()
+            .from(from)
+            .to(parse_mailbox(recipient)?)
+            .subject(subject)
+            .singlepart(SinglePart::plain(body))
+            .map_err(|e| AppError::Internal(format!("Failed to build email: {e}")))?;
+
+        deliver_smtp(settings, message).await
+    }
+
+    /// Whether an SMTP relay is configured.
+    pub fn email_enabled(&self) -> bool {
+        self.smtp.is_some()
+    }
+
+    /// POST the report body to a webhook URL.
+    pub async fn post_webhook(&self, url: &str, report: &RenderedReport) -> Result<(), AppError> {
+        let resp = self
+            .http
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, report.content_type)
+            .header(
+                reqwest::header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", report.file_name),
+            )
+            .body(report.body.clone())
+            .send()
+            .await
+            .map_err(|e| AppError::Internal(format!("Webhook request failed: {e}")))?;
+
+        if !resp.status().is_success() {
+            return Err(AppError::Internal(format!(
+                "Webhook responded with status {}",
+                resp.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+async fn deliver_smtp(settings: &SmtpSettings, message: Message) -> Result<(), AppError> {
+    let mut transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.host)
+        .map_err(|e| AppError::Internal(format!("Invalid SMTP relay: {e}")))?
+        .port(settings.port);
+    if let (Some(user), Some(pass)) = (&settings.username, &settings.password) {
+        transport = transport.credentials(Credentials::new(user.clone(), pass.clone()));
+    }
+
+    transport
+        .build()
+        .send(message)
+        .await
+        .map_err(|e| AppError::Internal(format!("SMTP delivery failed: {e}")))?;
+    Ok(())
+}
+
+/// Parse a recipient email address.
+pub fn parse_mailbox(address: &str) -> Result<Mailbox, AppError> {
+    address
+        .parse()
+        .map_err(|e| AppError::Validation(format!("Invalid email recipient '{address}': {e}")))
+}
+
+/// Validate a webhook recipient URL (http or https only).
+pub fn validate_webhook_url(url: &str) -> Result<(), AppError> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| AppError::Validation(format!("Invalid webhook URL '{url}': {e}")))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(()),
+        other => Err(AppError::Validation(format!(
+            "Webhook URL must use http or https, got '{other}'"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mailbox_parsing() {
+        assert!(parse_mailbox("ciso@example.com").is_ok());
+        assert!(parse_mailbox("CISO <ciso@example.com>").is_ok());
+        assert!(parse_mailbox("not-an-address").is_err());
+    }
+
+    #[test]
+    fn webhook_url_scheme() {
+        assert!(validate_webhook_url("https://hooks.example.com/reports").is_ok());
+        assert!(validate_webhook_url("ftp://example.com/drop").is_err());
+        assert!(validate_webhook_url("not a url").is_err());
+    }
+
+    #[test]
+    fn smtp_debug_redacts_password() {
+        let settings = SmtpSettings {
+            host: "smtp.example.com".to_string(),
+            port: 587,
+            username: Some("mailer".to_string()),
+            password: Some("hunter2".to_string()),
+            from: "SynApSec <noreply@example.com>".to_string(),
+        };
+        let debug = format!("{settings:?}");
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("[redacted]"));
+    }
+}
@@ -0,0 +1,544 @@
+//! Scheduled report delivery: schedule CRUD, rendering, and the background
+//! scheduler that runs due schedules and records run history.
+//!
+//! The scheduler claims due schedules with `FOR UPDATE SKIP LOCKED` and
+//! advances `next_run_at` before rendering, so several backend instances can
+//! run it concurrently without delivering the same report twice.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::report_template::ReportKind;
+use crate::models::report_schedule::{
+    CreateReportSchedule, DeliveryMethod, ReportRun, ReportRunStatus, ReportSchedule,
+    ReportType, UpdateReportSchedule,
+};
+use crate::services::executive_report::{self, ReportPeriod};
+use crate::services::finding::{self as finding_service, FindingFilters};
+use crate::services::report_delivery::{self, RenderedReport, ReportDelivery};
+use crate::services::csv_export;
+use crate::services::report_template::{self, DEFAULT_LANGUAGE};
+
+/// Maximum schedules claimed per scheduler tick; the rest wait for the next tick.
+const CLAIM_BATCH_SIZE: i64 = 10;
+
+/// Run trigger recorded for cron-driven runs.
+const TRIGGER_SCHEDULE: &str = "schedule";
+
+/// Run trigger recorded for on-demand runs.
+const TRIGGER_MANUAL: &str = "manual";
+
+/// Parameters of an `executive_pdf` schedule.
+#[derive(Debug, Default, Deserialize)]
+struct ExecutiveFilters {
+    #[serde(default)]
+    period: ReportPeriod,
+}
+
+// ---------------------------------------------------------------------------
+// Cron handling
+// ---------------------------------------------------------------------------
+
+/// Parse a cron expression.
+///
+/// Accepts standard 5-field expressions (`min hour dom month dow`) as well as
+/// the 6/7-field form with seconds understood by the `cron` crate.
+pub fn parse_cron(expr: &str) -> Result<cron::Schedule, AppError> {
+    let trimmed = expr.trim();
+    let normalized = if trimmed.split_whitespace().count() == 5 {
+        format!("0 {trimmed}")
+    } else {
+        trimmed.to_string()
+    };
+    cron::Schedule::from_str(&normalized)
+        .map_err(|e| AppError::Validation(format!("Invalid cron expression '{expr}': {e}")))
+}
+
+/// Next fire time of `expr` strictly after `after`.
+pub fn next_run_after(expr: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>, AppError> {
+    parse_cron(expr)?
+        .after(&after)
+        .next()
+        .ok_or_else(|| AppError::Validation(format!("Cron expression '{expr}' never fires")))
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Check that filters deserialize into the parameters of the report type.
+fn validate_filters(report_type: ReportType, filters: &serde_json::Value) -> Result<(), AppError> {
+    let result = match report_type {
+        ReportType::FindingsCsv => {
+            serde_json::from_value::<FindingFilters>(filters.clone()).map(|_| ())
+        }
+        ReportType::ExecutivePdf => {
+            serde_json::from_value::<ExecutiveFilters>(filters.clone()).map(|_| ())
+        }
+    };
+    result.map_err(|e| AppError::Validation(format!("Invalid report filters: {e}")))
+}
+
+/// Check that recipients are non-empty and valid for the delivery method.
+fn validate_recipients(method: DeliveryMethod, recipients: &[String]) -> Result<(), AppError> {
+    if recipients.is_empty() {
+        return Err(AppError::Validation(
+            "At least one recipient is required".to_string(),
+        ));
+    }
+    for recipient in recipients {
+        match method {
+            DeliveryMethod::Email => {
+                report_delivery::parse_mailbox(recipient)?;
+            }
+            DeliveryMethod::Webhook => report_delivery::validate_webhook_url(recipient)?,
+        }
+    }
+    Ok(())
+}
+
+/// Recipients stored as a JSON array of strings.
+fn recipients_of(schedule: &ReportSchedule) -> Vec<String> {
+    schedule
+        .recipients
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Schedule CRUD
+// ---------------------------------------------------------------------------
+
+/// List all report schedules, most recently created first.
+pub async fn list(pool: &PgPool) -> Result<Vec<ReportSchedule>, AppError> {
+    let schedules = sqlx::query_as::<_, ReportSchedule>(
+        "SELECT * FROM report_schedules ORDER BY created_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(schedules)
+}
+
+/// Fetch a single schedule.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<ReportSchedule, AppError> {
+    sqlx::query_as::<_, ReportSchedule>("SELECT * FROM report_schedules WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report schedule {id} not found")))
+}
+
+/// Create a schedule; `next_run_at` is computed from the cron expression.
+pub async fn create(
+    pool: &PgPool,
+    input: &CreateReportSchedule,
+    user_id: Uuid,
+) -> Result<ReportSchedule, AppError> {
+    if input.name.trim().is_empty() {
+        return Err(AppError::Validation("Schedule name is required".to_string()));
+    }
+    let filters = input
+        .filters
+        .clone()
+        .unwrap_or_else(|| serde_json::json!({}));
+    validate_filters(input.report_type, &filters)?;
+    validate_recipients(input.delivery_method, &input.recipients)?;
+    let next_run_at = next_run_after(&input.cron_expression, Utc::now())?;
+    let is_active = input.is_active.unwrap_or(true);
+
+    let schedule = sqlx::query_as::<_, ReportSchedule>(
+        r#"
+        INSERT INTO report_schedules
+            (name, report_type, filters, cron_expression, delivery_method, recipients,
+             is_active, next_run_at, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+    )
+    .bind(input.name.trim())
+    .bind(input.report_type)
+    .bind(&filters)
+    .bind(input.cron_expression.trim())
+    .bind(input.delivery_method)
+    .bind(serde_json::json!(input.recipients))
+    .bind(is_active)
+    .bind(next_run_at)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(schedule)
+}
+
+/// Update a schedule, recomputing `next_run_at` from the (possibly new) cron expression.
+pub async fn update(
+    pool: &PgPool,
+    id: Uuid,
+    input: &UpdateReportSchedule,
+) -> Result<ReportSchedule, AppError> {
+    let existing = find_by_id(pool, id).await?;
+
+    let name = input.name.as_deref().unwrap_or(&existing.name).trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Schedule name is required".to_string()));
+    }
+    let report_type = input.report_type.unwrap_or(existing.report_type);
+    let filters = input.filters.as_ref().unwrap_or(&existing.filters);
+    let cron_expression = input
+        .cron_expression
+        .as_deref()
+        .unwrap_or(&existing.cron_expression)
+        .trim();
+    let delivery_method = input.delivery_method.unwrap_or(existing.delivery_method);
+    let recipients = input
+        .recipients
+        .clone()
+        .unwrap_or_else(|| recipients_of(&existing));
+    let is_active = input.is_active.unwrap_or(existing.is_active);
+
+    validate_filters(report_type, filters)?;
+    validate_recipients(delivery_method, &recipients)?;
+    let next_run_at = next_run_after(cron_expression, Utc::now())?;
+
+    let schedule = sqlx::query_as::<_, ReportSchedule>(
+        r#"
+        UPDATE report_schedules
+        SET name = $1, report_type = $2, filters = $3, cron_expression = $4,
+            delivery_method = $5, recipients = $6, is_active = $7, next_run_at = $8
+        WHERE id = $9
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(report_type)
+    .bind(filters)
+    .bind(cron_expression)
+    .bind(delivery_method)
+    .bind(serde_json::json!(recipients))
+    .bind(is_active)
+    .bind(next_run_at)
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(schedule)
+}
+
+/// Delete a schedule and its run history.
+pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM report_schedules WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Report schedule {id} not found")));
+    }
+    Ok(())
+}
+
+/// Paginated run history of a schedule, newest first.
+pub async fn list_runs(
+    pool: &PgPool,
+    schedule_id: Uuid,
+    pagination: &Pagination,
+) -> Result<PagedResult<ReportRun>, AppError> {
+    find_by_id(pool, schedule_id).await?;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM report_runs WHERE schedule_id = $1",
+    )
+    .bind(schedule_id)
+    .fetch_one(pool)
+    .await?;
+
+    let runs = sqlx::query_as::<_, ReportRun>(
+        r#"
+        SELECT * FROM report_runs
+        WHERE schedule_id = $1
+        ORDER BY started_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(schedule_id)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(runs, total, pagination))
+}
+
+// ---------------------------------------------------------------------------
+// Rendering and execution
+// ---------------------------------------------------------------------------
+
+/// Render the report described by a schedule.
+pub async fn render(pool: &PgPool, schedule: &ReportSchedule) -> Result<RenderedReport, AppError> {
+    let date = Utc::now().format("%Y-%m-%d");
+    match schedule.report_type {
+        ReportType::FindingsCsv => {
+            let filters: FindingFilters = serde_json::from_value(schedule.filters.clone())
+                .map_err(|e| AppError::Validation(format!("Invalid report filters: {e}")))?;
+            let findings = finding_service::list_all_for_export(pool, &filters).await?;
+            let body = csv_export::encode_all(&findings)
+                .map_err(|e| AppError::Internal(format!("CSV serialization failed: {e}")))?;
+            Ok(RenderedReport {
+                subject: format!("{} ({} findings)", schedule.name, findings.len()),
+                file_name: format!("findings_{date}.csv"),
+                content_type: "text/csv; charset=utf-8",
+                body,
+            })
+        }
+        ReportType::ExecutivePdf => {
+            let params: ExecutiveFilters = serde_json::from_value(schedule.filters.clone())
+                .map_err(|e| AppError::Validation(format!("Invalid report filters: {e}")))?;
+            let summary = executive_report::build(pool, params.period).await?;
+            let body = report_template::render_pdf(
+                pool,
+                ReportKind::Executive,
+                DEFAULT_LANGUAGE,
+                &summary,
+                || executive_report::to_document(&summary),
+            )
+            .await?;
+            Ok(RenderedReport {
+                subject: schedule.name.clone(),
+                file_name: format!("executive_summary_{date}.pdf"),
+                content_type: "application/pdf",
+                body,
+            })
+        }
+    }
+}
+
+/// Render and deliver a schedule's report to all recipients.
+async fn render_and_deliver(
+    pool: &PgPool,
+    delivery: &ReportDelivery,
+    schedule: &ReportSchedule,
+) -> Result<RenderedReport, AppError> {
+    let report = render(pool, schedule).await?;
+    let recipients = recipients_of(schedule);
+    match schedule.delivery_method {
+        DeliveryMethod::Email => delivery.send_email(&recipients, &report).await?,
+        DeliveryMethod::Webhook => {
+            for url in &recipients {
+                delivery.post_webhook(url, &report).await?;
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Record a new run in `running` state.
+async fn start_run(pool: &PgPool, schedule_id: Uuid, trigger: &str) -> Result<ReportRun, AppError> {
+    let run = sqlx::query_as::<_, ReportRun>(
+        r#"
+        INSERT INTO report_runs (schedule_id, status, triggered_by)
+        VALUES ($1, 'running', $2)
+        RETURNING *
+        "#,
+    )
+    .bind(schedule_id)
+    .bind(trigger)
+    .fetch_one(pool)
+    .await?;
+    Ok(run)
+}
+
+/// Execute a started run and record its outcome.
+async fn complete_run(
+    pool: &PgPool,
+    delivery: &ReportDelivery,
+    schedule: &ReportSchedule,
+    run_id: Uuid,
+) -> Result<(), AppError> {
+    let outcome = render_and_deliver(pool, delivery, schedule).await;
+    let (status, file_name, byte_size, error_message) = match &outcome {
+        Ok(report) => (
+            ReportRunStatus::Succeeded,
+            Some(report.file_name.clone()),
+            i32::try_from(report.body.len()).ok(),
+            None,
+        ),
+        Err(e) => {
+            tracing::warn!(schedule_id = %schedule.id, run_id = %run_id, error = %e, "Report run failed");
+            (ReportRunStatus::Failed, None, None, Some(e.to_string()))
+        }
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE report_runs
+        SET status = $1, file_name = $2, byte_size = $3, error_message = $4, completed_at = NOW()
+        WHERE id = $5
+        "#,
+    )
+    .bind(status)
+    .bind(file_name)
+    .bind(byte_size)
+    .bind(error_message)
+    .bind(run_id)
+    .execute(pool)
+    .await?;
+
+    sqlx::query("UPDATE report_schedules SET last_run_at = NOW() WHERE id = $1")
+        .bind(schedule.id)
+        .execute(pool)
+        .await?;
+
+    if status == ReportRunStatus::Succeeded {
+        tracing::info!(schedule_id = %schedule.id, run_id = %run_id, "Report delivered");
+    }
+    Ok(())
+}
+
+/// Start an on-demand run in the background and return it in `running` state.
+pub async fn trigger_now(
+    pool: &PgPool,
+    delivery: ReportDelivery,
+    schedule_id: Uuid,
+) -> Result<ReportRun, AppError> {
+    let schedule = find_by_id(pool, schedule_id).await?;
+    let run = start_run(pool, schedule.id, TRIGGER_MANUAL).await?;
+
+    let pool = pool.clone();
+    let run_id = run.id;
+    tokio::spawn(async move {
+        if let Err(e) = complete_run(&pool, &delivery, &schedule, run_id).await {
+            tracing::error!(run_id = %run_id, error = %e, "Failed to record report run");
+        }
+    });
+
+    Ok(run)
+}
+
+// ---------------------------------------------------------------------------
+// Background scheduler
+// ---------------------------------------------------------------------------
+
+/// Claim due schedules and advance their `next_run_at` in one transaction.
+///
+/// Schedules whose cron expression no longer parses are deactivated.
+async fn claim_due(pool: &PgPool) -> Result<Vec<ReportSchedule>, AppError> {
+    let mut tx = pool.begin().await?;
+    let due = sqlx::query_as::<_, ReportSchedule>(
+        r#"
+        SELECT * FROM report_schedules
+        WHERE is_active AND next_run_at <= NOW()
+        ORDER BY next_run_at
+        LIMIT $1
+        FOR UPDATE SKIP LOCKED
+        "#,
+    )
+    .bind(CLAIM_BATCH_SIZE)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let now = Utc::now();
+    let mut claimed = Vec::with_capacity(due.len());
+    for schedule in due {
+        match next_run_after(&schedule.cron_expression, now) {
+            Ok(next) => {
+                sqlx::query("UPDATE report_schedules SET next_run_at = $1 WHERE id = $2")
+                    .bind(next)
+                    .bind(schedule.id)
+                    .execute(&mut *tx)
+                    .await?;
+                claimed.push(schedule);
+            }
+            Err(e) => {
+                tracing::warn!(schedule_id = %schedule.id, error = %e, "Deactivating report schedule");
+                sqlx::query(
+                    "UPDATE report_schedules SET is_active = false, next_run_at = NULL WHERE id = $1",
+                )
+                .bind(schedule.id)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    Ok(claimed)
+}
+
+/// Run every schedule that is currently due.
+async fn run_due(pool: &PgPool, delivery: &ReportDelivery) -> Result<(), AppError> {
+    for schedule in claim_due(pool).await? {
+        let run = start_run(pool, schedule.id, TRIGGER_SCHEDULE).await?;
+        complete_run(pool, delivery, &schedule, run.id).await?;
+    }
+    Ok(())
+}
+
+/// Start the background scheduler, checking for due schedules every `interval`.
+///
+/// Must be called from within a Tokio runtime.
+pub fn spawn_scheduler(pool: PgPool, delivery: ReportDelivery, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = run_due(&pool, &delivery).await {
+                tracing::error!(error = %e, "Report scheduler tick failed");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, TimeZone, Timelike};
+
+    #[test]
+    fn five_field_cron_is_accepted() {
+        let after = Utc.with_ymd_and_hms(2026, 1, 5, 9, 30, 0).unwrap(); // Monday
+        let next = next_run_after("0 8 * * Mon", after).unwrap();
+        assert_eq!(next.weekday(), chrono::Weekday::Mon);
+        assert_eq!(next.hour(), 8);
+        assert_eq!(next.day(), 12);
+    }
+
+    #[test]
+    fn six_field_cron_is_accepted() {
+        let after = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let next = next_run_after("0 0 6 1 * *", after).unwrap();
+        assert_eq!((next.day(), next.hour()), (1, 6));
+    }
+
+    #[test]
+    fn invalid_cron_is_validation_error() {
+        let err = parse_cron("every day").unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn filters_validated_per_report_type() {
+        assert!(validate_filters(ReportType::ExecutivePdf, &serde_json::json!({"period": "year"})).is_ok());
+        assert!(validate_filters(ReportType::ExecutivePdf, &serde_json::json!({"period": "decade"})).is_err());
+        assert!(validate_filters(ReportType::FindingsCsv, &serde_json::json!({"severity": "Critical"})).is_ok());
+        assert!(validate_filters(ReportType::FindingsCsv, &serde_json::json!({"severity": "Urgent"})).is_err());
+    }
+
+    #[test]
+    fn recipients_validated_per_method() {
+        assert!(validate_recipients(DeliveryMethod::Email, &[]).is_err());
+        assert!(validate_recipients(DeliveryMethod::Email, &["ciso@example.com".to_string()]).is_ok());
+        assert!(validate_recipients(DeliveryMethod::Webhook, &["ciso@example.com".to_string()]).is_err());
+        assert!(validate_recipients(DeliveryMethod::Webhook, &["https://hooks.example.com/x".to_string()]).is_ok());
+    }
+}
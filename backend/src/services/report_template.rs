@@ -0,0 +1,557 @@
+//! Customizable report templates rendered with Tera.
+//!
+//! Admins store one active template per report kind, output format, and
+//! language. Templates receive the report data as `report`, plus `logo_url`,
+//! `language`, and `generated_at`. HTML templates produce the page served to
+//! the browser (output is auto-escaped). PDF templates produce report markup
+//! that is laid out by [`pdf_report`]:
+//!
+//! ```text
+//! # Document title
+//! > Subtitle line
+//! ## Section heading
+//! - Label: value
+//! | Column | Column |
+//! | cell   | cell   |
+//! Any other line is paragraph text; blank lines end a paragraph.
+//! ```
+//!
+//! When no template matches, the built-in layout of each report is used.
+
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::PgPool;
+use tera::{Context, Tera};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::report_template::{
+    CreateReportTemplate, ReportKind, ReportTemplate, TemplateFormat, UpdateReportTemplate,
+};
+use crate::services::pdf_report::{self, ReportBlock, ReportDocument};
+
+/// Language used when no template exists for the requested one.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Template name by output format. The `.html` suffix turns on Tera's
+/// auto-escaping; markup for PDFs is plain text and must not be escaped.
+fn template_name(format: TemplateFormat) -> &'static str {
+    match format {
+        TemplateFormat::Html => "report.html",
+        TemplateFormat::Pdf => "report.txt",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CRUD
+// ---------------------------------------------------------------------------
+
+/// List all templates grouped by report kind.
+pub async fn list(pool: &PgPool) -> Result<Vec<ReportTemplate>, AppError> {
+    let templates = sqlx::query_as::<_, ReportTemplate>(
+        "SELECT * FROM report_templates ORDER BY report_kind, output_format, language, name",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(templates)
+}
+
+/// Fetch a single template.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<ReportTemplate, AppError> {
+    sqlx::query_as::<_, ReportTemplate>("SELECT * FROM report_templates WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Report template {id} not found")))
+}
+
+/// Create a template after checking that it compiles.
+pub async fn create(
+    pool: &PgPool,
+    input: &CreateReportTemplate,
+    user_id: Uuid,
+) -> Result<ReportTemplate, AppError> {
+    let name = validate_name(&input.name)?;
+    let language = normalize_language(input.language.as_deref().unwrap_or(DEFAULT_LANGUAGE))?;
+    compile(input.output_format, &input.body)?;
+
+    sqlx::query_as::<_, ReportTemplate>(
+        r#"
+        INSERT INTO report_templates
+            (name, report_kind, output_format, language, body, logo_url, is_active, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(input.report_kind)
+    .bind(input.output_format)
+    .bind(&language)
+    .bind(&input.body)
+    .bind(&input.logo_url)
+    .bind(input.is_active.unwrap_or(true))
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| active_conflict(e, input.report_kind, input.output_format, &language))
+}
+
+/// Update a template; a new body is compiled before it is stored.
+pub async fn update(
+    pool: &PgPool,
+    id: Uuid,
+    input: &UpdateReportTemplate,
+) -> Result<ReportTemplate, AppError> {
+    let existing = find_by_id(pool, id).await?;
+
+    let name = validate_name(input.name.as_deref().unwrap_or(&existing.name))?;
+    let language = match input.language.as_deref() {
+        Some(lang) => normalize_language(lang)?,
+        None => existing.language.clone(),
+    };
+    let body = input.body.as_deref().unwrap_or(&existing.body);
+    compile(existing.output_format, body)?;
+    let logo_url = input.logo_url.as_ref().or(existing.logo_url.as_ref());
+    let is_active = input.is_active.unwrap_or(existing.is_active);
+
+    sqlx::query_as::<_, ReportTemplate>(
+        r#"
+        UPDATE report_templates
+        SET name = $1, language = $2, body = $3, logo_url = $4, is_active = $5
+        WHERE id = $6
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(&language)
+    .bind(body)
+    .bind(logo_url)
+    .bind(is_active)
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| active_conflict(e, existing.report_kind, existing.output_format, &language))
+}
+
+/// Delete a template.
+pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM report_templates WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Report template {id} not found"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<&str, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Template name is required".to_string(),
+        ));
+    }
+    Ok(name)
+}
+
+/// Lowercase a language tag and check it looks like `en` or `pt-br`.
+fn normalize_language(lang: &str) -> Result<String, AppError> {
+    let lang = lang.trim().to_ascii_lowercase();
+    let valid = (2..=10).contains(&lang.len())
+        && lang.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && lang.chars().next().is_some_and(|c| c.is_ascii_alphabetic());
+    if !valid {
+        return Err(AppError::Validation(format!(
+            "Invalid language tag '{lang}'"
+        )));
+    }
+    Ok(lang)
+}
+
+/// Map the partial unique index violation to a readable conflict.
+fn active_conflict(
+    e: sqlx::Error,
+    kind: ReportKind,
+    format: TemplateFormat,
+    language: &str,
+) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            AppError::Conflict(format!(
+                "An active {kind:?} {format:?} template for language '{language}' already exists"
+            ))
+        }
+        _ => AppError::Database(e),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rendering
+// ---------------------------------------------------------------------------
+
+/// Compile a template body, reporting syntax errors as validation errors.
+fn compile(format: TemplateFormat, body: &str) -> Result<Tera, AppError> {
+    let mut tera = Tera::default();
+    tera.add_raw_template(template_name(format), body)
+        .map_err(|e| AppError::Validation(format!("Invalid template: {}", error_chain(&e))))?;
+    Ok(tera)
+}
+
+/// Tera nests the useful message (line, column, cause) in the source chain.
+fn error_chain(e: &tera::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = std::error::Error::source(e);
+    while let Some(inner) = source {
+        message.push_str(": ");
+        message.push_str(&inner.to_string());
+        source = inner.source();
+    }
+    message
+}
+
+/// Active template for a report, preferring `language` and falling back to
+/// [`DEFAULT_LANGUAGE`].
+pub async fn find_active(
+    pool: &PgPool,
+    kind: ReportKind,
+    format: TemplateFormat,
+    language: &str,
+) -> Result<Option<ReportTemplate>, AppError> {
+    let template = sqlx::query_as::<_, ReportTemplate>(
+        r#"
+        SELECT * FROM report_templates
+        WHERE is_active
+          AND report_kind = $1
+          AND output_format = $2
+          AND language IN ($3, $4)
+        ORDER BY (language = $3) DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(kind)
+    .bind(format)
+    .bind(language.to_ascii_lowercase())
+    .bind(DEFAULT_LANGUAGE)
+    .fetch_optional(pool)
+    .await?;
+    Ok(template)
+}
+
+/// Render a stored template against report data.
+pub fn render_template<T: Serialize>(
+    template: &ReportTemplate,
+    report: &T,
+) -> Result<String, AppError> {
+    let tera = compile(template.output_format, &template.body)?;
+    let mut context = Context::new();
+    context.insert("report", report);
+    context.insert("logo_url", &template.logo_url);
+    context.insert("language", &template.language);
+    context.insert("generated_at", &Utc::now().to_rfc3339());
+    tera.render(template_name(template.output_format), &context)
+        .map_err(|e| {
+            AppError::Internal(format!(
+                "Rendering template '{}' failed: {}",
+                template.name,
+                error_chain(&e)
+            ))
+        })
+}
+
+/// Render a report as PDF, using the active template when there is one.
+pub async fn render_pdf<T: Serialize>(
+    pool: &PgPool,
+    kind: ReportKind,
+    language: &str,
+    report: &T,
+    fallback: impl FnOnce() -> ReportDocument,
+) -> Result<Vec<u8>, AppError> {
+    let document = match find_active(pool, kind, TemplateFormat::Pdf, language).await? {
+        Some(template) => parse_markup(&render_template(&template, report)?),
+        None => fallback(),
+    };
+    pdf_report::render(&document)
+}
+
+/// Render a report as an HTML page, using the active template when there is one.
+pub async fn render_html<T: Serialize>(
+    pool: &PgPool,
+    kind: ReportKind,
+    language: &str,
+    report: &T,
+    fallback: impl FnOnce() -> ReportDocument,
+) -> Result<String, AppError> {
+    match find_active(pool, kind, TemplateFormat::Html, language).await? {
+        Some(template) => render_template(&template, report),
+        None => Ok(document_to_html(&fallback())),
+    }
+}
+
+/// Kind of the previous markup line, used to extend tables and lists.
+#[derive(PartialEq)]
+enum LineKind {
+    Other,
+    TableRow,
+    KeyValue,
+}
+
+/// Parse report markup (see module docs) into a printable document.
+pub fn parse_markup(text: &str) -> ReportDocument {
+    let mut title: Option<String> = None;
+    let mut subtitle = None;
+    let mut blocks: Vec<ReportBlock> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut previous = LineKind::Other;
+
+    fn flush(paragraph: &mut Vec<&str>, blocks: &mut Vec<ReportBlock>) {
+        if !paragraph.is_empty() {
+            blocks.push(ReportBlock::Paragraph(paragraph.join(" ")));
+            paragraph.clear();
+        }
+    }
+
+    for line in text.lines().map(str::trim) {
+        let kind = if line.starts_with('|') {
+            LineKind::TableRow
+        } else if line.starts_with("- ") && line.contains(':') {
+            LineKind::KeyValue
+        } else {
+            LineKind::Other
+        };
+
+        if line.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else if let Some(text) = line.strip_prefix("## ") {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(ReportBlock::Heading(text.to_string()));
+        } else if let Some(text) = line.strip_prefix("# ") {
+            flush(&mut paragraph, &mut blocks);
+            if title.is_none() {
+                title = Some(text.to_string());
+            } else {
+                blocks.push(ReportBlock::Heading(text.to_string()));
+            }
+        } else if let Some(text) = line.strip_prefix("> ") {
+            flush(&mut paragraph, &mut blocks);
+            subtitle = Some(text.to_string());
+        } else if kind == LineKind::TableRow {
+            flush(&mut paragraph, &mut blocks);
+            let cells: Vec<String> = line
+                .trim_matches('|')
+                .split('|')
+                .map(|c| c.trim().to_string())
+                .collect();
+            let is_separator = cells
+                .iter()
+                .all(|c| !c.is_empty() && c.chars().all(|ch| matches!(ch, '-' | ':')));
+            match blocks.last_mut() {
+                Some(ReportBlock::Table { .. }) if is_separator => {}
+                Some(ReportBlock::Table { rows, .. }) if previous == LineKind::TableRow => {
+                    rows.push(cells)
+                }
+                _ => blocks.push(ReportBlock::Table {
+                    columns: cells,
+                    rows: Vec::new(),
+                }),
+            }
+        } else if kind == LineKind::KeyValue {
+            flush(&mut paragraph, &mut blocks);
+            let (label, value) = line[2..].split_once(':').unwrap_or((&line[2..], ""));
+            let pair = (label.trim().to_string(), value.trim().to_string());
+            match blocks.last_mut() {
+                Some(ReportBlock::KeyValues(pairs)) if previous == LineKind::KeyValue => {
+                    pairs.push(pair)
+                }
+                _ => blocks.push(ReportBlock::KeyValues(vec![pair])),
+            }
+        } else {
+            paragraph.push(line);
+        }
+        previous = kind;
+    }
+    flush(&mut paragraph, &mut blocks);
+
+    ReportDocument {
+        title: title.unwrap_or_else(|| "Report".to_string()),
+        subtitle,
+        blocks,
+    }
+}
+
+/// Escape text for inclusion in HTML.
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Built-in HTML layout of a report document.
+pub fn document_to_html(document: &ReportDocument) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+    html.push_str(&format!("<title>{}</title>", escape_html(&document.title)));
+    html.push_str(
+        "<style>body{font-family:Helvetica,Arial,sans-serif;margin:2em;color:#222}\
+         table{border-collapse:collapse;margin:0.5em 0 1.5em}\
+         th,td{border:1px solid #ccc;padding:4px 8px;text-align:left;font-size:0.9em}\
+         th{background:#f2f2f2}</style></head><body>\n",
+    );
+    html.push_str(&format!("<h1>{}</h1>\n", escape_html(&document.title)));
+    if let Some(subtitle) = &document.subtitle {
+        html.push_str(&format!("<p><em>{}</em></p>\n", escape_html(subtitle)));
+    }
+    for block in &document.blocks {
+        match block {
+            ReportBlock::Heading(text) => {
+                html.push_str(&format!("<h2>{}</h2>\n", escape_html(text)));
+            }
+            ReportBlock::Paragraph(text) => {
+                html.push_str(&format!("<p>{}</p>\n", escape_html(text)));
+            }
+            ReportBlock::KeyValues(pairs) => {
+                html.push_str("<table>\n");
+                for (label, value) in pairs {
+                    html.push_str(&format!(
+                        "<tr><th>{}</th><td>{}</td></tr>\n",
+                        escape_html(label),
+                        escape_html(value)
+                    ));
+                }
+                html.push_str("</table>\n");
+            }
+            ReportBlock::Table { columns, rows } => {
+                html.push_str("<table>\n<tr>");
+                for column in columns {
+                    html.push_str(&format!("<th>{}</th>", escape_html(column)));
+                }
+                html.push_str("</tr>\n");
+                for row in rows {
+                    html.push_str("<tr>");
+                    for cell in row {
+                        html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+                    }
+                    html.push_str("</tr>\n");
+                }
+                html.push_str("</table>\n");
+            }
+        }
+    }
+    html.push_str("</body></html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(format: TemplateFormat, body: &str) -> ReportTemplate {
+        ReportTemplate {
+            id: Uuid::new_v4(),
+            name: "custom".to_string(),
+            report_kind: ReportKind::Dora,
+            output_format: format,
+            language: "it".to_string(),
+            body: body.to_string(),
+            logo_url: Some("https://example.com/logo.png".to_string()),
+            is_active: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn parses_markup_blocks() {
+        let doc = parse_markup(
+            "# Rapporto DORA\n> Trimestrale\n\n## Sezione 1\nTesto del\nparagrafo.\n\n\
+             - Applicazioni: 4\n- Aperte: 12\n\n| App | Aperte |\n|---|---|\n| PAY | 3 |\n| INT | 9 |\n",
+        );
+        assert_eq!(doc.title, "Rapporto DORA");
+        assert_eq!(doc.subtitle.as_deref(), Some("Trimestrale"));
+        assert!(matches!(&doc.blocks[0], ReportBlock::Heading(h) if h == "Sezione 1"));
+        assert!(matches!(&doc.blocks[1], ReportBlock::Paragraph(p) if p == "Testo del paragrafo."));
+        assert!(matches!(&doc.blocks[2], ReportBlock::KeyValues(kv) if kv.len() == 2));
+        match &doc.blocks[3] {
+            ReportBlock::Table { columns, rows } => {
+                assert_eq!(columns, &vec!["App".to_string(), "Aperte".to_string()]);
+                assert_eq!(rows.len(), 2);
+                assert_eq!(rows[1][0], "INT");
+            }
+            other => panic!("expected table, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn separate_tables_need_a_break() {
+        let doc = parse_markup("| A |\n| 1 |\n\n| B |\n| 2 |");
+        assert_eq!(doc.blocks.len(), 2);
+        assert_eq!(doc.title, "Report");
+    }
+
+    #[test]
+    fn renders_pdf_template_with_report_context() {
+        let t = template(
+            TemplateFormat::Pdf,
+            "# {{ report.title }}\n{% for a in report.apps %}- {{ a.name }}: {{ a.open }}\n{% endfor %}",
+        );
+        let data = serde_json::json!({
+            "title": "R & D",
+            "apps": [{"name": "PAY", "open": 3}, {"name": "INT", "open": 9}],
+        });
+        let out = render_template(&t, &data).unwrap();
+        let doc = parse_markup(&out);
+        assert_eq!(doc.title, "R & D");
+        assert!(matches!(&doc.blocks[0], ReportBlock::KeyValues(kv) if kv.len() == 2));
+    }
+
+    #[test]
+    fn html_template_escapes_report_data() {
+        let t = template(
+            TemplateFormat::Html,
+            "<img src=\"{{ logo_url }}\"><h1>{{ report.title }}</h1>",
+        );
+        let out = render_template(&t, &serde_json::json!({"title": "<script>"})).unwrap();
+        assert!(out.contains("&lt;script&gt;"));
+        assert!(out.contains("logo.png"));
+    }
+
+    #[test]
+    fn syntax_errors_are_validation_errors() {
+        let err = compile(TemplateFormat::Pdf, "{% for x in %}").unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn language_tags() {
+        assert_eq!(normalize_language("IT").unwrap(), "it");
+        assert_eq!(normalize_language("pt-BR").unwrap(), "pt-br");
+        assert!(normalize_language("e").is_err());
+        assert!(normalize_language("../en").is_err());
+    }
+
+    #[test]
+    fn builtin_html_escapes_cells() {
+        let doc = ReportDocument {
+            title: "T".to_string(),
+            subtitle: None,
+            blocks: vec![ReportBlock::Table {
+                columns: vec!["Title".to_string()],
+                rows: vec![vec!["<b>x</b>".to_string()]],
+            }],
+        };
+        let html = document_to_html(&doc);
+        assert!(html.contains("<td>&lt;b&gt;x&lt;/b&gt;</td>"));
+    }
+}
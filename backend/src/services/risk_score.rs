@@ -7,10 +7,12 @@
 //! - Finding Age: 15% (dynamic — computed relative to SLA)
 //! - Correlation Density: 10%
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-use crate::models::application::AssetCriticality;
-use crate::models::finding::SeverityLevel;
+use crate::models::application::{AssetCriticality, AssetTier};
+use crate::models::finding::{SeverityLevel, SlaStatus};
 use crate::models::finding_sca::ExploitMaturity;
 
 /// Factor weights for risk score computation.
@@ -38,6 +40,7 @@ impl Default for RiskWeights {
 /// Input factors for computing the risk score.
 #[derive(Debug, Clone)]
 pub struct RiskFactors {
+    /// The finding's effective severity: its analyst override when set.
     pub severity: SeverityLevel,
     pub asset_criticality: Option<AssetCriticality>,
     pub exploitability: ExploitabilityInput,
@@ -105,7 +108,7 @@ pub struct FactorScores {
 }
 
 /// Priority level derived from composite score.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PriorityLevel {
     P1,
     P2,
@@ -251,6 +254,31 @@ fn score_to_priority(score: f32) -> PriorityLevel {
     }
 }
 
+/// Remediation SLA in hours by priority and asset tier (`sla_matrix`).
+/// A missing or null cell means the combination has no SLA.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SlaMatrix(HashMap<PriorityLevel, HashMap<AssetTier, Option<u32>>>);
+
+impl SlaMatrix {
+    pub fn hours(&self, priority: &PriorityLevel, tier: &AssetTier) -> Option<u32> {
+        self.0.get(priority)?.get(tier).copied().flatten()
+    }
+}
+
+/// Fraction of the SLA window after which an open finding is at risk.
+pub const SLA_AT_RISK_RATIO: f32 = 0.75;
+
+/// SLA status for a finding that has used `sla_ratio` of its SLA window.
+pub fn sla_status(sla_ratio: f32) -> SlaStatus {
+    if sla_ratio >= 1.0 {
+        SlaStatus::Breached
+    } else if sla_ratio >= SLA_AT_RISK_RATIO {
+        SlaStatus::AtRisk
+    } else {
+        SlaStatus::OnTrack
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -474,4 +502,24 @@ mod tests {
         assert_eq!(score_to_priority(19.9), PriorityLevel::P5);
         assert_eq!(score_to_priority(0.0), PriorityLevel::P5);
     }
+
+    #[test]
+    fn sla_matrix_reads_seeded_shape() {
+        let matrix: SlaMatrix = serde_json::from_value(serde_json::json!({
+            "P1": {"Tier_1": 72, "Tier_2": 168, "Tier_3": 336},
+            "P4": {"Tier_1": 2160, "Tier_2": 4320, "Tier_3": null}
+        }))
+        .unwrap();
+        assert_eq!(matrix.hours(&PriorityLevel::P1, &AssetTier::Tier2), Some(168));
+        assert_eq!(matrix.hours(&PriorityLevel::P4, &AssetTier::Tier3), None);
+        assert_eq!(matrix.hours(&PriorityLevel::P5, &AssetTier::Tier1), None);
+    }
+
+    #[test]
+    fn sla_status_thresholds() {
+        assert_eq!(sla_status(0.0), SlaStatus::OnTrack);
+        assert_eq!(sla_status(0.74), SlaStatus::OnTrack);
+        assert_eq!(sla_status(SLA_AT_RISK_RATIO), SlaStatus::AtRisk);
+        assert_eq!(sla_status(1.0), SlaStatus::Breached);
+    }
 }
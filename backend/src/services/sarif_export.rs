@@ -0,0 +1,565 @@
+//! SARIF 2.1.0 export of an application's normalized findings.
+//!
+//! Emits a single run attributed to SynApSec so code hosting platforms can
+//! ingest findings from every upstream tool in one upload. Cross-tool
+//! correlations are appended to each result's message and carried in
+//! `properties.correlations`; false positives and accepted risks are exported
+//! as externally suppressed results.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{FindingCategory, FindingStatus, SeverityLevel};
+use crate::services::json_value::json_strings;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Driver name reported in the exported run.
+const DRIVER_NAME: &str = "SynApSec";
+
+/// Key used in `partialFingerprints` so platforms track results across uploads.
+const FINGERPRINT_KEY: &str = "synapsecFingerprint/v1";
+
+/// Top-level SARIF log.
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub name: String,
+    pub short_description: SarifMessage,
+    pub properties: SarifRuleProperties,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifRuleProperties {
+    pub tags: Vec<String>,
+    /// CVSS-like 0–10 score used by code hosts to bucket alerts.
+    #[serde(rename = "security-severity")]
+    pub security_severity: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: &'static str,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    pub partial_fingerprints: BTreeMap<&'static str, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppressions: Vec<SarifSuppression>,
+    pub properties: SarifResultProperties,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physical_location: Option<SarifPhysicalLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub logical_locations: Vec<SarifLogicalLocation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<SarifRegion>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLogicalLocation {
+    pub fully_qualified_name: String,
+    pub kind: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifSuppression {
+    pub kind: &'static str,
+    pub status: &'static str,
+    pub justification: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SarifResultProperties {
+    pub finding_id: Uuid,
+    pub source_tool: String,
+    pub category: FindingCategory,
+    pub severity: SeverityLevel,
+    pub status: FindingStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk_score: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cwe: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cve: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub correlations: Vec<CorrelationNote>,
+}
+
+/// A relationship from the exported finding to another finding.
+#[derive(Debug, Clone, Serialize)]
+pub struct CorrelationNote {
+    pub related_finding_id: Uuid,
+    pub related_title: String,
+    pub related_source_tool: String,
+    pub relationship_type: String,
+    pub confidence: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Finding row joined with the category fields needed for locations.
+#[derive(Debug, FromRow)]
+struct SarifRow {
+    id: Uuid,
+    source_tool: String,
+    source_finding_id: String,
+    finding_category: FindingCategory,
+    title: String,
+    description: String,
+    normalized_severity: SeverityLevel,
+    cvss_score: Option<f32>,
+    cwe_ids: serde_json::Value,
+    cve_ids: serde_json::Value,
+    status: FindingStatus,
+    composite_risk_score: Option<f32>,
+    fingerprint: String,
+    file_path: Option<String>,
+    line_number_start: Option<i32>,
+    line_number_end: Option<i32>,
+    rule_id: Option<String>,
+    rule_name: Option<String>,
+    package_name: Option<String>,
+    package_version: Option<String>,
+    target_url: Option<String>,
+}
+
+/// Relationship touching one of the application's findings, with both ends' labels.
+#[derive(Debug, FromRow)]
+struct RelationshipRow {
+    source_finding_id: Uuid,
+    source_title: String,
+    source_tool: String,
+    target_finding_id: Uuid,
+    target_title: String,
+    target_tool: String,
+    relationship_type: String,
+    confidence: Option<String>,
+    notes: Option<String>,
+}
+
+/// Build a SARIF log for an application's unresolved findings.
+pub async fn export_for_application(pool: &PgPool, app_id: Uuid) -> Result<SarifLog, AppError> {
+    let exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM applications WHERE id = $1)")
+            .bind(app_id)
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Application {app_id} not found"
+        )));
+    }
+
+    let rows = sqlx::query_as::<_, SarifRow>(
+        r#"
+        SELECT
+            f.id, f.source_tool, f.source_finding_id, f.finding_category,
+            f.title, f.description, f.normalized_severity, f.cvss_score,
+            f.cwe_ids, f.cve_ids, f.status, f.composite_risk_score, f.fingerprint,
+            fs.file_path, fs.line_number_start, fs.line_number_end,
+            fs.rule_id, fs.rule_name,
+            fc.package_name, fc.package_version,
+            fd.target_url
+        FROM findings f
+        LEFT JOIN finding_sast fs ON fs.finding_id = f.id
+        LEFT JOIN finding_sca fc ON fc.finding_id = f.id
+        LEFT JOIN finding_dast fd ON fd.finding_id = f.id
+        WHERE f.application_id = $1
+          AND f.status NOT IN ('Verified', 'Closed', 'Invalidated')
+        ORDER BY f.first_seen ASC
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+
+    let relationships = sqlx::query_as::<_, RelationshipRow>(
+        r#"
+        SELECT
+            fr.source_finding_id, src.title AS source_title, src.source_tool AS source_tool,
+            fr.target_finding_id, tgt.title AS target_title, tgt.source_tool AS target_tool,
+            fr.relationship_type::text AS relationship_type,
+            fr.confidence::text AS confidence,
+            fr.notes
+        FROM finding_relationships fr
+        JOIN findings src ON src.id = fr.source_finding_id
+        JOIN findings tgt ON tgt.id = fr.target_finding_id
+        WHERE src.application_id = $1 OR tgt.application_id = $1
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(build_log(&rows, &correlation_notes(&relationships)))
+}
+
+/// Index relationships by finding, seen from each end.
+fn correlation_notes(rows: &[RelationshipRow]) -> HashMap<Uuid, Vec<CorrelationNote>> {
+    let mut notes: HashMap<Uuid, Vec<CorrelationNote>> = HashMap::new();
+    for r in rows {
+        let note = |id: Uuid, title: &str, tool: &str| CorrelationNote {
+            related_finding_id: id,
+            related_title: title.to_string(),
+            related_source_tool: tool.to_string(),
+            relationship_type: r.relationship_type.clone(),
+            confidence: r.confidence.clone(),
+            notes: r.notes.clone(),
+        };
+        notes
+            .entry(r.source_finding_id)
+            .or_default()
+            .push(note(r.target_finding_id, &r.target_title, &r.target_tool));
+        notes
+            .entry(r.target_finding_id)
+            .or_default()
+            .push(note(r.source_finding_id, &r.source_title, &r.source_tool));
+    }
+    notes
+}
+
+fn build_log(rows: &[SarifRow], notes: &HashMap<Uuid, Vec<CorrelationNote>>) -> SarifLog {
+    let mut rules: Vec<SarifRule> = Vec::new();
+    let mut rule_index: HashMap<String, usize> = HashMap::new();
+    let mut results = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let rule_id = rule_id_for(row);
+        let index = *rule_index.entry(rule_id.clone()).or_insert_with(|| {
+            rules.push(SarifRule {
+                id: rule_id.clone(),
+                name: row.rule_name.clone().unwrap_or_else(|| row.title.clone()),
+                short_description: SarifMessage {
+                    text: row.title.clone(),
+                },
+                properties: SarifRuleProperties {
+                    tags: std::iter::once("security".to_string())
+                        .chain(json_strings(&row.cwe_ids))
+                        .collect(),
+                    security_severity: security_severity(row),
+                },
+            });
+            rules.len() - 1
+        });
+
+        let correlations = notes.get(&row.id).cloned().unwrap_or_default();
+        results.push(SarifResult {
+            rule_id,
+            rule_index: index,
+            level: sarif_level(&row.normalized_severity),
+            message: SarifMessage {
+                text: message_text(row, &correlations),
+            },
+            locations: vec![location_for(row)],
+            partial_fingerprints: BTreeMap::from([(FINGERPRINT_KEY, row.fingerprint.clone())]),
+            suppressions: suppression_for(&row.status).into_iter().collect(),
+            properties: SarifResultProperties {
+                finding_id: row.id,
+                source_tool: row.source_tool.clone(),
+                category: row.finding_category.clone(),
+                severity: row.normalized_severity.clone(),
+                status: row.status.clone(),
+                risk_score: row.composite_risk_score,
+                cwe: json_strings(&row.cwe_ids),
+                cve: json_strings(&row.cve_ids),
+                correlations,
+            },
+        });
+    }
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: DRIVER_NAME,
+                    rules,
+                },
+            },
+            results,
+        }],
+    }
+}
+
+/// Rule identifier: scanner rule for SAST, first CVE for SCA, otherwise
+/// the source tool and its finding id.
+fn rule_id_for(row: &SarifRow) -> String {
+    if let Some(rule) = &row.rule_id {
+        return format!("{}/{rule}", row.source_tool);
+    }
+    if row.finding_category == FindingCategory::Sca {
+        if let Some(cve) = json_strings(&row.cve_ids).into_iter().next() {
+            return cve;
+        }
+    }
+    format!("{}/{}", row.source_tool, row.source_finding_id)
+}
+
+/// Result message: description followed by one line per correlation.
+fn message_text(row: &SarifRow, correlations: &[CorrelationNote]) -> String {
+    let mut text = if row.description.trim().is_empty() {
+        row.title.clone()
+    } else {
+        format!("{}\n\n{}", row.title, row.description.trim())
+    };
+    if !correlations.is_empty() {
+        text.push_str("\n\nCorrelated findings:");
+        for c in correlations {
+            text.push_str(&format!(
+                "\n- {} ({}): {} [{}",
+                c.related_source_tool, c.relationship_type, c.related_title, c.related_finding_id
+            ));
+            if let Some(conf) = &c.confidence {
+                text.push_str(&format!(", confidence {conf}"));
+            }
+            text.push(']');
+            if let Some(n) = c.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+                text.push_str(&format!(" — {}", n.trim()));
+            }
+        }
+    }
+    text
+}
+
+/// File/line for SAST, package for SCA, URL for DAST.
+fn location_for(row: &SarifRow) -> SarifLocation {
+    if let Some(path) = &row.file_path {
+        return SarifLocation {
+            physical_location: Some(SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation { uri: path.clone() },
+                region: row.line_number_start.filter(|l| *l > 0).map(|start_line| SarifRegion {
+                    start_line,
+                    end_line: row.line_number_end.filter(|e| *e >= start_line),
+                }),
+            }),
+            logical_locations: Vec::new(),
+        };
+    }
+    if let Some(url) = &row.target_url {
+        return SarifLocation {
+            physical_location: Some(SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation { uri: url.clone() },
+                region: None,
+            }),
+            logical_locations: Vec::new(),
+        };
+    }
+    let name = match (&row.package_name, &row.package_version) {
+        (Some(p), Some(v)) => format!("{p}@{v}"),
+        (Some(p), None) => p.clone(),
+        _ => row.title.clone(),
+    };
+    SarifLocation {
+        physical_location: None,
+        logical_locations: vec![SarifLogicalLocation {
+            fully_qualified_name: name,
+            kind: "module",
+        }],
+    }
+}
+
+/// SARIF result level for a normalized severity.
+fn sarif_level(severity: &SeverityLevel) -> &'static str {
+    match severity {
+        SeverityLevel::Critical | SeverityLevel::High => "error",
+        SeverityLevel::Medium => "warning",
+        SeverityLevel::Low | SeverityLevel::Info => "note",
+    }
+}
+
+/// `security-severity` property: the CVSS score when known, otherwise a
+/// representative score inside the CVSS band of the normalized severity.
+fn security_severity(row: &SarifRow) -> String {
+    let score = row.cvss_score.unwrap_or(match row.normalized_severity {
+        SeverityLevel::Critical => 9.5,
+        SeverityLevel::High => 8.0,
+        SeverityLevel::Medium => 5.5,
+        SeverityLevel::Low => 2.0,
+        SeverityLevel::Info => 0.0,
+    });
+    format!("{score:.1}")
+}
+
+/// Triage decisions that should hide the result in the code host.
+fn suppression_for(status: &FindingStatus) -> Option<SarifSuppression> {
+    let justification = match status {
+        FindingStatus::FalsePositive => "Marked as false positive in SynApSec",
+        FindingStatus::RiskAccepted => "Risk accepted in SynApSec",
+        _ => return None,
+    };
+    Some(SarifSuppression {
+        kind: "external",
+        status: "accepted",
+        justification: justification.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sast_row() -> SarifRow {
+        SarifRow {
+            id: Uuid::new_v4(),
+            source_tool: "SonarQube".to_string(),
+            source_finding_id: "AX-1".to_string(),
+            finding_category: FindingCategory::Sast,
+            title: "SQL injection".to_string(),
+            description: "User input reaches query".to_string(),
+            normalized_severity: SeverityLevel::High,
+            cvss_score: None,
+            cwe_ids: serde_json::json!(["CWE-89"]),
+            cve_ids: serde_json::json!([]),
+            status: FindingStatus::Confirmed,
+            composite_risk_score: Some(71.0),
+            fingerprint: "abc".to_string(),
+            file_path: Some("src/db.rs".to_string()),
+            line_number_start: Some(42),
+            line_number_end: Some(44),
+            rule_id: Some("java:S3649".to_string()),
+            rule_name: None,
+            package_name: None,
+            package_version: None,
+            target_url: None,
+        }
+    }
+
+    #[test]
+    fn sast_result_has_file_region_and_rule() {
+        let row = sast_row();
+        let log = build_log(std::slice::from_ref(&row), &HashMap::new());
+        let run = &log.runs[0];
+        assert_eq!(run.tool.driver.rules.len(), 1);
+        assert_eq!(run.results[0].rule_id, "SonarQube/java:S3649");
+        assert_eq!(run.results[0].level, "error");
+        let physical = run.results[0].locations[0].physical_location.as_ref().unwrap();
+        assert_eq!(physical.artifact_location.uri, "src/db.rs");
+        assert_eq!(physical.region.as_ref().unwrap().start_line, 42);
+    }
+
+    #[test]
+    fn rules_are_deduplicated() {
+        let a = sast_row();
+        let b = sast_row();
+        let log = build_log(&[a, b], &HashMap::new());
+        assert_eq!(log.runs[0].tool.driver.rules.len(), 1);
+        assert_eq!(log.runs[0].results[1].rule_index, 0);
+    }
+
+    #[test]
+    fn correlations_are_in_message_and_properties() {
+        let row = sast_row();
+        let other = Uuid::new_v4();
+        let rel = RelationshipRow {
+            source_finding_id: row.id,
+            source_title: row.title.clone(),
+            source_tool: row.source_tool.clone(),
+            target_finding_id: other,
+            target_title: "SQLi on /search".to_string(),
+            target_tool: "Tenable WAS".to_string(),
+            relationship_type: "correlated_with".to_string(),
+            confidence: Some("High".to_string()),
+            notes: Some("Same CWE".to_string()),
+        };
+        let notes = correlation_notes(&[rel]);
+        assert_eq!(notes[&other].len(), 1);
+
+        let log = build_log(std::slice::from_ref(&row), &notes);
+        let result = &log.runs[0].results[0];
+        assert!(result.message.text.contains("Tenable WAS (correlated_with): SQLi on /search"));
+        assert!(result.message.text.contains("Same CWE"));
+        assert_eq!(result.properties.correlations[0].related_finding_id, other);
+    }
+
+    #[test]
+    fn false_positive_is_suppressed() {
+        assert!(suppression_for(&FindingStatus::FalsePositive).is_some());
+        assert!(suppression_for(&FindingStatus::Confirmed).is_none());
+    }
+
+    #[test]
+    fn sca_without_file_uses_logical_location_and_cve_rule() {
+        let mut row = sast_row();
+        row.finding_category = FindingCategory::Sca;
+        row.file_path = None;
+        row.rule_id = None;
+        row.cve_ids = serde_json::json!(["CVE-2021-44228"]);
+        row.package_name = Some("log4j-core".to_string());
+        row.package_version = Some("2.14.1".to_string());
+        assert_eq!(rule_id_for(&row), "CVE-2021-44228");
+        let loc = location_for(&row);
+        assert_eq!(loc.logical_locations[0].fully_qualified_name, "log4j-core@2.14.1");
+    }
+
+    #[test]
+    fn security_severity_prefers_cvss() {
+        let mut row = sast_row();
+        assert_eq!(security_severity(&row), "8.0");
+        row.cvss_score = Some(9.8);
+        assert_eq!(security_severity(&row), "9.8");
+    }
+}
@@ -0,0 +1,276 @@
+//! User-defined saved dashboards: CRUD with per-user and shared visibility.
+//!
+//! Users see their own dashboards plus every shared one. Only the owner (or
+//! a platform admin) can change or delete a dashboard; another user's private
+//! dashboard is reported as not found.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::middleware::auth::CurrentUser;
+use crate::models::saved_dashboard::{
+    CreateSavedDashboard, DashboardVisibility, DashboardWidget, SavedDashboard,
+    UpdateSavedDashboard,
+};
+use crate::models::user::UserRole;
+use crate::services::finding::FindingFilters;
+
+/// Upper bound on widgets per dashboard.
+const MAX_WIDGETS: usize = 24;
+
+// ---------------------------------------------------------------------------
+// Validation and access
+// ---------------------------------------------------------------------------
+
+/// Check widget titles, count, and that filters deserialize into `FindingFilters`.
+fn validate_widgets(widgets: &[DashboardWidget]) -> Result<(), AppError> {
+    if widgets.len() > MAX_WIDGETS {
+        return Err(AppError::Validation(format!(
+            "A dashboard can hold at most {MAX_WIDGETS} widgets"
+        )));
+    }
+    for (i, widget) in widgets.iter().enumerate() {
+        if widget.title.trim().is_empty() {
+            return Err(AppError::Validation(format!(
+                "Widget {} needs a title",
+                i + 1
+            )));
+        }
+        let filters = if widget.filters.is_null() {
+            serde_json::json!({})
+        } else {
+            widget.filters.clone()
+        };
+        serde_json::from_value::<FindingFilters>(filters).map_err(|e| {
+            AppError::Validation(format!("Invalid filters in widget '{}': {e}", widget.title))
+        })?;
+    }
+    Ok(())
+}
+
+fn can_view(dashboard: &SavedDashboard, user: &CurrentUser) -> bool {
+    dashboard.visibility == DashboardVisibility::Shared || can_edit(dashboard, user)
+}
+
+fn can_edit(dashboard: &SavedDashboard, user: &CurrentUser) -> bool {
+    dashboard.owner_id == user.id || user.role == UserRole::PlatformAdmin
+}
+
+/// Fetch a dashboard the user may edit.
+async fn find_editable(
+    pool: &PgPool,
+    id: Uuid,
+    user: &CurrentUser,
+) -> Result<SavedDashboard, AppError> {
+    let dashboard = find_by_id(pool, id, user).await?;
+    if !can_edit(&dashboard, user) {
+        return Err(AppError::Forbidden(
+            "Only the owner can change a shared dashboard".to_string(),
+        ));
+    }
+    Ok(dashboard)
+}
+
+// ---------------------------------------------------------------------------
+// CRUD
+// ---------------------------------------------------------------------------
+
+/// List the user's own dashboards and all shared ones, most recently updated first.
+pub async fn list(pool: &PgPool, user: &CurrentUser) -> Result<Vec<SavedDashboard>, AppError> {
+    let dashboards = sqlx::query_as::<_, SavedDashboard>(
+        r#"
+        SELECT * FROM saved_dashboards
+        WHERE owner_id = $1 OR visibility = 'shared'
+        ORDER BY updated_at DESC
+        "#,
+    )
+    .bind(user.id)
+    .fetch_all(pool)
+    .await?;
+    Ok(dashboards)
+}
+
+/// Fetch a dashboard visible to the user.
+pub async fn find_by_id(
+    pool: &PgPool,
+    id: Uuid,
+    user: &CurrentUser,
+) -> Result<SavedDashboard, AppError> {
+    sqlx::query_as::<_, SavedDashboard>("SELECT * FROM saved_dashboards WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .filter(|d| can_view(d, user))
+        .ok_or_else(|| AppError::NotFound(format!("Dashboard {id} not found")))
+}
+
+/// Create a dashboard owned by the user.
+pub async fn create(
+    pool: &PgPool,
+    input: &CreateSavedDashboard,
+    user: &CurrentUser,
+) -> Result<SavedDashboard, AppError> {
+    if input.name.trim().is_empty() {
+        return Err(AppError::Validation(
+            "Dashboard name is required".to_string(),
+        ));
+    }
+    validate_widgets(&input.widgets)?;
+
+    let dashboard = sqlx::query_as::<_, SavedDashboard>(
+        r#"
+        INSERT INTO saved_dashboards (name, description, visibility, widgets, owner_id)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(input.name.trim())
+    .bind(&input.description)
+    .bind(input.visibility.unwrap_or_default())
+    .bind(serde_json::json!(input.widgets))
+    .bind(user.id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(dashboard)
+}
+
+/// Update a dashboard the user owns; omitted fields keep their values.
+pub async fn update(
+    pool: &PgPool,
+    id: Uuid,
+    input: &UpdateSavedDashboard,
+    user: &CurrentUser,
+) -> Result<SavedDashboard, AppError> {
+    let existing = find_editable(pool, id, user).await?;
+
+    let name = input.name.as_deref().unwrap_or(&existing.name).trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Dashboard name is required".to_string(),
+        ));
+    }
+    let widgets = match &input.widgets {
+        Some(widgets) => {
+            validate_widgets(widgets)?;
+            serde_json::json!(widgets)
+        }
+        None => existing.widgets,
+    };
+
+    let dashboard = sqlx::query_as::<_, SavedDashboard>(
+        r#"
+        UPDATE saved_dashboards
+        SET name = $1, description = $2, visibility = $3, widgets = $4
+        WHERE id = $5
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(input.description.as_ref().or(existing.description.as_ref()))
+    .bind(input.visibility.unwrap_or(existing.visibility))
+    .bind(&widgets)
+    .bind(id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(dashboard)
+}
+
+/// Delete a dashboard the user owns.
+pub async fn delete(pool: &PgPool, id: Uuid, user: &CurrentUser) -> Result<(), AppError> {
+    find_editable(pool, id, user).await?;
+    sqlx::query("DELETE FROM saved_dashboards WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::saved_dashboard::{WidgetGroupBy, WidgetMetric};
+    use chrono::Utc;
+
+    fn widget(title: &str, filters: serde_json::Value) -> DashboardWidget {
+        DashboardWidget {
+            title: title.to_string(),
+            filters,
+            group_by: WidgetGroupBy::Severity,
+            metric: WidgetMetric::Count,
+        }
+    }
+
+    fn user(role: UserRole) -> CurrentUser {
+        CurrentUser {
+            id: Uuid::new_v4(),
+            username: "analyst".to_string(),
+            role,
+        }
+    }
+
+    fn dashboard(owner_id: Uuid, visibility: DashboardVisibility) -> SavedDashboard {
+        SavedDashboard {
+            id: Uuid::new_v4(),
+            name: "Payments triage".to_string(),
+            description: None,
+            visibility,
+            widgets: serde_json::json!([]),
+            owner_id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn accepts_finding_filters_and_empty_filters() {
+        let widgets = [
+            widget(
+                "Critical SAST",
+                serde_json::json!({"severity": "Critical", "category": "SAST"}),
+            ),
+            widget("Everything", serde_json::Value::Null),
+        ];
+        assert!(validate_widgets(&widgets).is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_filters_and_blank_titles() {
+        let bad_filter = [widget("Bad", serde_json::json!({"severity": "Urgent"}))];
+        assert!(validate_widgets(&bad_filter).is_err());
+
+        let blank = [widget("  ", serde_json::json!({}))];
+        assert!(validate_widgets(&blank).is_err());
+    }
+
+    #[test]
+    fn rejects_too_many_widgets() {
+        let widgets = vec![widget("W", serde_json::json!({})); MAX_WIDGETS + 1];
+        assert!(validate_widgets(&widgets).is_err());
+    }
+
+    #[test]
+    fn private_dashboards_are_owner_only() {
+        let owner = user(UserRole::AppSecAnalyst);
+        let other = user(UserRole::AppSecAnalyst);
+        let private = dashboard(owner.id, DashboardVisibility::Private);
+        assert!(can_view(&private, &owner));
+        assert!(!can_view(&private, &other));
+
+        let shared = dashboard(owner.id, DashboardVisibility::Shared);
+        assert!(can_view(&shared, &other));
+        assert!(!can_edit(&shared, &other));
+        assert!(can_edit(&shared, &user(UserRole::PlatformAdmin)));
+    }
+
+    #[test]
+    fn widget_metric_defaults_to_count() {
+        let widget: DashboardWidget =
+            serde_json::from_str(r#"{"title": "By tool", "group_by": "source_tool"}"#).unwrap();
+        assert_eq!(widget.metric, WidgetMetric::Count);
+        assert_eq!(widget.group_by, WidgetGroupBy::SourceTool);
+        assert!(widget.filters.is_null());
+    }
+}
@@ -0,0 +1,301 @@
+//! Saved finding filters (views): CRUD with personal and shared scope.
+//!
+//! A saved filter stores `FindingFilters`, a `FindingSort`, and the visible
+//! columns of the findings list. Users see their own filters plus every
+//! shared one; only the owner (or a platform admin) can change or delete a
+//! filter, and another user's personal filter is reported as not found.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::middleware::auth::CurrentUser;
+use crate::models::saved_filter::{CreateSavedFilter, FilterScope, SavedFilter, UpdateSavedFilter};
+use crate::models::user::UserRole;
+use crate::services::finding::{FindingFilters, FindingSort};
+
+/// Upper bound on visible columns per filter.
+const MAX_COLUMNS: usize = 50;
+
+// ---------------------------------------------------------------------------
+// Validation and access
+// ---------------------------------------------------------------------------
+
+/// Treat a missing JSON value as an empty object.
+fn or_empty(value: &serde_json::Value) -> serde_json::Value {
+    if value.is_null() {
+        serde_json::json!({})
+    } else {
+        value.clone()
+    }
+}
+
+/// Check that filters deserialize into `FindingFilters`.
+fn validate_filters(filters: &serde_json::Value) -> Result<serde_json::Value, AppError> {
+    let filters = or_empty(filters);
+    serde_json::from_value::<FindingFilters>(filters.clone())
+        .map_err(|e| AppError::Validation(format!("Invalid filters: {e}")))?;
+    Ok(filters)
+}
+
+/// Check that sort deserializes into `FindingSort`.
+fn validate_sort(sort: &serde_json::Value) -> Result<serde_json::Value, AppError> {
+    let sort = or_empty(sort);
+    serde_json::from_value::<FindingSort>(sort.clone())
+        .map_err(|e| AppError::Validation(format!("Invalid sort: {e}")))?;
+    Ok(sort)
+}
+
+/// Check column keys are non-blank, unique, and within the limit.
+fn validate_columns(columns: &[String]) -> Result<(), AppError> {
+    if columns.len() > MAX_COLUMNS {
+        return Err(AppError::Validation(format!(
+            "A saved filter can show at most {MAX_COLUMNS} columns"
+        )));
+    }
+    for (i, column) in columns.iter().enumerate() {
+        if column.trim().is_empty() {
+            return Err(AppError::Validation(format!("Column {} is blank", i + 1)));
+        }
+        if columns[..i].contains(column) {
+            return Err(AppError::Validation(format!(
+                "Column '{column}' is listed twice"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn can_view(filter: &SavedFilter, user: &CurrentUser) -> bool {
+    filter.scope == FilterScope::Shared || can_edit(filter, user)
+}
+
+fn can_edit(filter: &SavedFilter, user: &CurrentUser) -> bool {
+    filter.owner_id == user.id || user.role == UserRole::PlatformAdmin
+}
+
+/// Fetch a filter the user may edit.
+async fn find_editable(
+    pool: &PgPool,
+    id: Uuid,
+    user: &CurrentUser,
+) -> Result<SavedFilter, AppError> {
+    let filter = find_by_id(pool, id, user).await?;
+    if !can_edit(&filter, user) {
+        return Err(AppError::Forbidden(
+            "Only the owner can change a shared filter".to_string(),
+        ));
+    }
+    Ok(filter)
+}
+
+// ---------------------------------------------------------------------------
+// CRUD
+// ---------------------------------------------------------------------------
+
+/// List the user's own filters and all shared ones, by name.
+pub async fn list(pool: &PgPool, user: &CurrentUser) -> Result<Vec<SavedFilter>, AppError> {
+    let filters = sqlx::query_as::<_, SavedFilter>(
+        r#"
+        SELECT * FROM saved_filters
+        WHERE owner_id = $1 OR scope = 'shared'
+        ORDER BY (owner_id = $1) DESC, name ASC
+        "#,
+    )
+    .bind(user.id)
+    .fetch_all(pool)
+    .await?;
+    Ok(filters)
+}
+
+/// Fetch a filter visible to the user.
+pub async fn find_by_id(
+    pool: &PgPool,
+    id: Uuid,
+    user: &CurrentUser,
+) -> Result<SavedFilter, AppError> {
+    sqlx::query_as::<_, SavedFilter>("SELECT * FROM saved_filters WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .filter(|f| can_view(f, user))
+        .ok_or_else(|| AppError::NotFound(format!("Saved filter {id} not found")))
+}
+
+/// Create a filter owned by the user.
+pub async fn create(
+    pool: &PgPool,
+    input: &CreateSavedFilter,
+    user: &CurrentUser,
+) -> Result<SavedFilter, AppError> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Saved filter name is required".to_string(),
+        ));
+    }
+    let filters = validate_filters(&input.filters)?;
+    let sort = validate_sort(&input.sort)?;
+    validate_columns(&input.columns)?;
+
+    let filter = sqlx::query_as::<_, SavedFilter>(
+        r#"
+        INSERT INTO saved_filters (name, description, scope, filters, sort, columns, owner_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(&input.description)
+    .bind(input.scope.unwrap_or_default())
+    .bind(&filters)
+    .bind(&sort)
+    .bind(serde_json::json!(input.columns))
+    .bind(user.id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| AppError::name_conflict(e, "saved filter", name))?;
+
+    Ok(filter)
+}
+
+/// Update a filter the user owns; omitted fields keep their values.
+pub async fn update(
+    pool: &PgPool,
+    id: Uuid,
+    input: &UpdateSavedFilter,
+    user: &CurrentUser,
+) -> Result<SavedFilter, AppError> {
+    let existing = find_editable(pool, id, user).await?;
+
+    let name = input.name.as_deref().unwrap_or(&existing.name).trim();
+    if name.is_empty() {
+        return Err(AppError::Validation(
+            "Saved filter name is required".to_string(),
+        ));
+    }
+    let filters = match &input.filters {
+        Some(filters) => validate_filters(filters)?,
+        None => existing.filters,
+    };
+    let sort = match &input.sort {
+        Some(sort) => validate_sort(sort)?,
+        None => existing.sort,
+    };
+    let columns = match &input.columns {
+        Some(columns) => {
+            validate_columns(columns)?;
+            serde_json::json!(columns)
+        }
+        None => existing.columns,
+    };
+
+    let filter = sqlx::query_as::<_, SavedFilter>(
+        r#"
+        UPDATE saved_filters
+        SET name = $1, description = $2, scope = $3, filters = $4, sort = $5, columns = $6
+        WHERE id = $7
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .bind(input.description.as_ref().or(existing.description.as_ref()))
+    .bind(input.scope.unwrap_or(existing.scope))
+    .bind(&filters)
+    .bind(&sort)
+    .bind(&columns)
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| AppError::name_conflict(e, "saved filter", name))?;
+
+    Ok(filter)
+}
+
+/// Delete a filter the user owns.
+pub async fn delete(pool: &PgPool, id: Uuid, user: &CurrentUser) -> Result<(), AppError> {
+    find_editable(pool, id, user).await?;
+    sqlx::query("DELETE FROM saved_filters WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn user(role: UserRole) -> CurrentUser {
+        CurrentUser {
+            id: Uuid::new_v4(),
+            username: "analyst".to_string(),
+            role,
+        }
+    }
+
+    fn saved_filter(owner_id: Uuid, scope: FilterScope) -> SavedFilter {
+        SavedFilter {
+            id: Uuid::new_v4(),
+            name: "My open P1 SCA with fix".to_string(),
+            description: None,
+            scope,
+            filters: serde_json::json!({}),
+            sort: serde_json::json!({}),
+            columns: serde_json::json!([]),
+            owner_id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn accepts_finding_filters_and_sort() {
+        let filters =
+            serde_json::json!({"category": "SCA", "severity": "Critical", "has_fix": true});
+        assert_eq!(validate_filters(&filters).unwrap(), filters);
+
+        let sort = serde_json::json!({"sort_by": "sla_due_date", "sort_dir": "asc"});
+        assert!(validate_sort(&sort).is_ok());
+    }
+
+    #[test]
+    fn missing_filters_and_sort_become_empty_objects() {
+        let empty = serde_json::json!({});
+        assert_eq!(validate_filters(&serde_json::Value::Null).unwrap(), empty);
+        assert_eq!(validate_sort(&serde_json::Value::Null).unwrap(), empty);
+    }
+
+    #[test]
+    fn rejects_unknown_filter_values_and_sort_columns() {
+        assert!(validate_filters(&serde_json::json!({"status": "Done"})).is_err());
+        assert!(validate_sort(&serde_json::json!({"sort_by": "password_hash"})).is_err());
+    }
+
+    #[test]
+    fn rejects_blank_and_duplicate_columns() {
+        let ok = ["title".to_string(), "severity".to_string()];
+        assert!(validate_columns(&ok).is_ok());
+
+        let blank = ["title".to_string(), " ".to_string()];
+        assert!(validate_columns(&blank).is_err());
+
+        let duplicate = ["title".to_string(), "title".to_string()];
+        assert!(validate_columns(&duplicate).is_err());
+    }
+
+    #[test]
+    fn personal_filters_are_owner_only() {
+        let owner = user(UserRole::AppSecAnalyst);
+        let other = user(UserRole::Developer);
+        let personal = saved_filter(owner.id, FilterScope::Personal);
+        assert!(can_view(&personal, &owner));
+        assert!(!can_view(&personal, &other));
+
+        let shared = saved_filter(owner.id, FilterScope::Shared);
+        assert!(can_view(&shared, &other));
+        assert!(!can_edit(&shared, &other));
+        assert!(can_edit(&shared, &user(UserRole::PlatformAdmin)));
+    }
+}
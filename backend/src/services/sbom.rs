@@ -0,0 +1,519 @@
+//! SBOM import and package queries.
+//!
+//! Accepts CycloneDX and SPDX JSON documents per application and release and
+//! stores their components. SCA findings are linked to components by package
+//! name and version when an SBOM is read, so findings ingested after the
+//! upload are linked too. For incident response, the latest SBOM of every
+//! application and release can be searched for a package.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::sbom::{
+    PackageOccurrence, Sbom, SbomComponentWithFindings, SbomDetail, SbomFormat,
+};
+use crate::services::application;
+use crate::services::json_value::str_field;
+
+/// Components written per `INSERT` statement during an import.
+const COMPONENT_INSERT_BATCH: usize = 1_000;
+
+/// Longest release label, in characters.
+const MAX_RELEASE_LEN: usize = 255;
+
+/// SPDX values that carry no license information.
+const SPDX_NO_LICENSE: &[&str] = &["NOASSERTION", "NONE"];
+
+/// Query parameters for the package search.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PackageQuery {
+    /// Package name without group or namespace (case-insensitive).
+    pub name: String,
+    /// Exact version; omit to match every version.
+    pub version: Option<String>,
+    /// Group or namespace, e.g. a Maven groupId (case-insensitive).
+    pub group: Option<String>,
+}
+
+/// A component parsed from an SBOM document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedComponent {
+    pub name: String,
+    pub group_name: Option<String>,
+    pub version: String,
+    pub purl: Option<String>,
+    pub package_type: Option<String>,
+    pub license: Option<String>,
+}
+
+/// An SBOM document reduced to what is stored.
+#[derive(Debug)]
+pub struct ParsedSbom {
+    pub format: SbomFormat,
+    pub spec_version: Option<String>,
+    pub document_name: Option<String>,
+    pub components: Vec<ParsedComponent>,
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Parse a CycloneDX or SPDX JSON document. Components without a name are
+/// skipped and duplicates (same group, name, and version) are kept once.
+pub fn parse_document(doc: &Value) -> Result<ParsedSbom, AppError> {
+    let mut parsed = if doc.get("bomFormat").and_then(Value::as_str) == Some("CycloneDX") {
+        parse_cyclonedx(doc)
+    } else if doc.get("spdxVersion").is_some() {
+        parse_spdx(doc)
+    } else {
+        return Err(AppError::Validation(
+            "Unrecognized SBOM: expected CycloneDX or SPDX JSON".to_string(),
+        ));
+    };
+
+    let mut seen = HashSet::new();
+    parsed
+        .components
+        .retain(|c| seen.insert((c.group_name.clone(), c.name.clone(), c.version.clone())));
+    Ok(parsed)
+}
+
+fn parse_cyclonedx(doc: &Value) -> ParsedSbom {
+    let mut components = Vec::new();
+    if let Some(list) = doc.get("components").and_then(Value::as_array) {
+        collect_cyclonedx_components(list, &mut components);
+    }
+    ParsedSbom {
+        format: SbomFormat::Cyclonedx,
+        spec_version: str_field(doc, "specVersion"),
+        document_name: doc
+            .pointer("/metadata/component")
+            .and_then(|c| str_field(c, "name")),
+        components,
+    }
+}
+
+/// Flatten CycloneDX components, including nested `components`.
+fn collect_cyclonedx_components(list: &[Value], out: &mut Vec<ParsedComponent>) {
+    for item in list {
+        if let Some(name) = str_field(item, "name") {
+            let licenses: Vec<String> = item
+                .get("licenses")
+                .and_then(Value::as_array)
+                .map(|licenses| {
+                    licenses
+                        .iter()
+                        .filter_map(|l| {
+                            str_field(l, "expression").or_else(|| {
+                                l.get("license").and_then(|l| {
+                                    str_field(l, "id").or_else(|| str_field(l, "name"))
+                                })
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            out.push(component(
+                name,
+                str_field(item, "group"),
+                str_field(item, "version"),
+                str_field(item, "purl"),
+                licenses,
+            ));
+        }
+        if let Some(children) = item.get("components").and_then(Value::as_array) {
+            collect_cyclonedx_components(children, out);
+        }
+    }
+}
+
+fn parse_spdx(doc: &Value) -> ParsedSbom {
+    let components = doc
+        .get("packages")
+        .and_then(Value::as_array)
+        .map(|packages| {
+            packages
+                .iter()
+                .filter_map(|package| {
+                    let name = str_field(package, "name")?;
+                    let purl = package
+                        .get("externalRefs")
+                        .and_then(Value::as_array)
+                        .and_then(|refs| {
+                            refs.iter().find(|r| {
+                                r.get("referenceType").and_then(Value::as_str) == Some("purl")
+                            })
+                        })
+                        .and_then(|r| str_field(r, "referenceLocator"));
+                    let license = ["licenseConcluded", "licenseDeclared"]
+                        .iter()
+                        .filter_map(|key| str_field(package, key))
+                        .find(|l| !SPDX_NO_LICENSE.contains(&l.as_str()));
+                    Some(component(
+                        name,
+                        None,
+                        str_field(package, "versionInfo"),
+                        purl,
+                        license.into_iter().collect(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ParsedSbom {
+        format: SbomFormat::Spdx,
+        spec_version: str_field(doc, "spdxVersion"),
+        document_name: str_field(doc, "name"),
+        components,
+    }
+}
+
+/// Build a component, taking the package type and any missing group from
+/// the package URL.
+fn component(
+    name: String,
+    group_name: Option<String>,
+    version: Option<String>,
+    purl: Option<String>,
+    licenses: Vec<String>,
+) -> ParsedComponent {
+    let (package_type, namespace) = match purl.as_deref().and_then(purl_parts) {
+        Some((package_type, namespace)) => (Some(package_type), namespace),
+        None => (None, None),
+    };
+    ParsedComponent {
+        name,
+        group_name: group_name.or(namespace),
+        version: version.unwrap_or_default(),
+        purl,
+        package_type,
+        license: (!licenses.is_empty()).then(|| licenses.join(", ")),
+    }
+}
+
+/// Type and namespace of a package URL (`pkg:type/namespace/name@version`).
+fn purl_parts(purl: &str) -> Option<(String, Option<String>)> {
+    let rest = purl.strip_prefix("pkg:")?;
+    let rest = rest.split(['?', '#']).next()?;
+    let path = rest.rsplit_once('@').map_or(rest, |(path, _)| path);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let (package_type, rest) = segments.split_first()?;
+    if rest.is_empty() {
+        return None;
+    }
+    let namespace = (rest.len() > 1).then(|| percent_decode(&rest[..rest.len() - 1].join("/")));
+    Some((package_type.to_ascii_lowercase(), namespace))
+}
+
+/// Decode `%XX` escapes, leaving malformed ones as they are.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// ---------------------------------------------------------------------------
+// Storage and queries
+// ---------------------------------------------------------------------------
+
+/// Store an SBOM document for an application and optional release.
+pub async fn import(
+    pool: &PgPool,
+    application_id: Uuid,
+    release: Option<&str>,
+    doc: &Value,
+    uploaded_by: Uuid,
+) -> Result<Sbom, AppError> {
+    application::find_by_id(pool, application_id).await?;
+    let release = release.map(str::trim).filter(|r| !r.is_empty());
+    if release.is_some_and(|r| r.chars().count() > MAX_RELEASE_LEN) {
+        return Err(AppError::Validation(format!(
+            "Release cannot exceed {MAX_RELEASE_LEN} characters"
+        )));
+    }
+    let parsed = parse_document(doc)?;
+
+    let mut tx = pool.begin().await?;
+    let sbom = sqlx::query_as::<_, Sbom>(
+        r#"
+        INSERT INTO sboms
+            (application_id, release, format, spec_version, document_name, component_count, uploaded_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+    )
+    .bind(application_id)
+    .bind(release)
+    .bind(parsed.format)
+    .bind(&parsed.spec_version)
+    .bind(&parsed.document_name)
+    .bind(parsed.components.len() as i32)
+    .bind(uploaded_by)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    for batch in parsed.components.chunks(COMPONENT_INSERT_BATCH) {
+        sqlx::query(
+            r#"
+            INSERT INTO sbom_components (sbom_id, name, group_name, version, purl, package_type, license)
+            SELECT $1, name, group_name, version, purl, package_type, license
+            FROM UNNEST($2::text[], $3::text[], $4::text[], $5::text[], $6::text[], $7::text[])
+                AS c(name, group_name, version, purl, package_type, license)
+            "#,
+        )
+        .bind(sbom.id)
+        .bind(batch.iter().map(|c| c.name.clone()).collect::<Vec<_>>())
+        .bind(batch.iter().map(|c| c.group_name.clone()).collect::<Vec<_>>())
+        .bind(batch.iter().map(|c| c.version.clone()).collect::<Vec<_>>())
+        .bind(batch.iter().map(|c| c.purl.clone()).collect::<Vec<_>>())
+        .bind(batch.iter().map(|c| c.package_type.clone()).collect::<Vec<_>>())
+        .bind(batch.iter().map(|c| c.license.clone()).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    tracing::info!(
+        application_id = %application_id,
+        sbom_id = %sbom.id,
+        components = sbom.component_count,
+        "Imported SBOM"
+    );
+    Ok(sbom)
+}
+
+/// An application's SBOMs, newest first.
+pub async fn list_for_application(
+    pool: &PgPool,
+    application_id: Uuid,
+) -> Result<Vec<Sbom>, AppError> {
+    application::find_by_id(pool, application_id).await?;
+    let sboms = sqlx::query_as::<_, Sbom>(
+        "SELECT * FROM sboms WHERE application_id = $1 ORDER BY created_at DESC",
+    )
+    .bind(application_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(sboms)
+}
+
+/// The latest SBOM of an application (for `release`, when given) with its
+/// components and the application's SCA findings for each component.
+pub async fn latest_for_application(
+    pool: &PgPool,
+    application_id: Uuid,
+    release: Option<&str>,
+) -> Result<SbomDetail, AppError> {
+    let sbom = sqlx::query_as::<_, Sbom>(
+        r#"
+        SELECT * FROM sboms
+        WHERE application_id = $1 AND ($2::text IS NULL OR release = $2)
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(application_id)
+    .bind(release)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("No SBOM found for application {application_id}")))?;
+
+    let components = sqlx::query_as::<_, SbomComponentWithFindings>(
+        r#"
+        SELECT c.*,
+               COALESCE(
+                   array_agg(f.id ORDER BY f.id) FILTER (WHERE f.id IS NOT NULL),
+                   '{}'
+               ) AS finding_ids
+        FROM sbom_components c
+        LEFT JOIN finding_sca s
+            ON s.package_name = c.name AND s.package_version = c.version
+        LEFT JOIN findings f
+            ON f.id = s.finding_id AND f.application_id = $2
+        WHERE c.sbom_id = $1
+        GROUP BY c.id
+        ORDER BY c.name, c.version
+        "#,
+    )
+    .bind(sbom.id)
+    .bind(application_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(SbomDetail { sbom, components })
+}
+
+/// Applications whose latest SBOM for a release contains a package.
+pub async fn find_package(
+    pool: &PgPool,
+    query: &PackageQuery,
+) -> Result<Vec<PackageOccurrence>, AppError> {
+    let name = query.name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Package name is required".to_string()));
+    }
+    let version = query
+        .version
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+    let group = query
+        .group
+        .as_deref()
+        .map(str::trim)
+        .filter(|g| !g.is_empty());
+
+    let occurrences = sqlx::query_as::<_, PackageOccurrence>(
+        r#"
+        WITH latest AS (
+            SELECT DISTINCT ON (application_id, release) id, application_id, release, created_at
+            FROM sboms
+            ORDER BY application_id, release, created_at DESC
+        )
+        SELECT a.id AS application_id, a.app_code, a.app_name,
+               l.id AS sbom_id, l.release, l.created_at AS sbom_created_at,
+               c.name, c.group_name, c.version, c.purl
+        FROM latest l
+        JOIN sbom_components c ON c.sbom_id = l.id
+        JOIN applications a ON a.id = l.application_id
+        WHERE lower(c.name) = lower($1)
+          AND ($2::text IS NULL OR c.version = $2)
+          AND ($3::text IS NULL OR lower(c.group_name) = lower($3))
+        ORDER BY a.app_code, l.release NULLS FIRST, c.version
+        "#,
+    )
+    .bind(name)
+    .bind(version)
+    .bind(group)
+    .fetch_all(pool)
+    .await?;
+    Ok(occurrences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_nested_cyclonedx_components() {
+        let doc = json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "metadata": { "component": { "name": "payments-api" } },
+            "components": [
+                {
+                    "group": "org.apache.logging.log4j",
+                    "name": "log4j-core",
+                    "version": "2.14.1",
+                    "purl": "pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1",
+                    "licenses": [{ "license": { "id": "Apache-2.0" } }],
+                    "components": [
+                        { "name": "log4j-api", "version": "2.14.1",
+                          "purl": "pkg:maven/org.apache.logging.log4j/log4j-api@2.14.1" }
+                    ]
+                },
+                { "name": "log4j-core", "group": "org.apache.logging.log4j", "version": "2.14.1" },
+                { "version": "1.0.0" }
+            ]
+        });
+        let parsed = parse_document(&doc).unwrap();
+        assert_eq!(parsed.format, SbomFormat::Cyclonedx);
+        assert_eq!(parsed.spec_version.as_deref(), Some("1.5"));
+        assert_eq!(parsed.document_name.as_deref(), Some("payments-api"));
+        assert_eq!(parsed.components.len(), 2);
+
+        let core = &parsed.components[0];
+        assert_eq!(core.package_type.as_deref(), Some("maven"));
+        assert_eq!(core.license.as_deref(), Some("Apache-2.0"));
+        // Group taken from the package URL when the component has none
+        assert_eq!(
+            parsed.components[1].group_name.as_deref(),
+            Some("org.apache.logging.log4j")
+        );
+    }
+
+    #[test]
+    fn parses_spdx_packages() {
+        let doc = json!({
+            "spdxVersion": "SPDX-2.3",
+            "name": "web-frontend",
+            "packages": [
+                {
+                    "name": "core",
+                    "versionInfo": "17.0.1",
+                    "licenseConcluded": "NOASSERTION",
+                    "licenseDeclared": "MIT",
+                    "externalRefs": [{
+                        "referenceCategory": "PACKAGE-MANAGER",
+                        "referenceType": "purl",
+                        "referenceLocator": "pkg:npm/%40angular/core@17.0.1"
+                    }]
+                },
+                { "name": "no-version" }
+            ]
+        });
+        let parsed = parse_document(&doc).unwrap();
+        assert_eq!(parsed.format, SbomFormat::Spdx);
+        assert_eq!(parsed.document_name.as_deref(), Some("web-frontend"));
+        assert_eq!(
+            parsed.components[0],
+            ParsedComponent {
+                name: "core".to_string(),
+                group_name: Some("@angular".to_string()),
+                version: "17.0.1".to_string(),
+                purl: Some("pkg:npm/%40angular/core@17.0.1".to_string()),
+                package_type: Some("npm".to_string()),
+                license: Some("MIT".to_string()),
+            }
+        );
+        assert_eq!(parsed.components[1].version, "");
+    }
+
+    #[test]
+    fn rejects_unknown_documents() {
+        assert!(parse_document(&json!({ "bomFormat": "Other" })).is_err());
+        assert!(parse_document(&json!([])).is_err());
+    }
+
+    #[test]
+    fn package_urls_yield_type_and_namespace() {
+        assert_eq!(
+            purl_parts("pkg:pypi/requests@2.31.0?extension=whl"),
+            Some(("pypi".to_string(), None))
+        );
+        assert_eq!(
+            purl_parts("pkg:golang/github.com/gorilla/mux@v1.8.0#sub"),
+            Some(("golang".to_string(), Some("github.com/gorilla".to_string())))
+        );
+        assert_eq!(purl_parts("pkg:maven"), None);
+        assert_eq!(purl_parts("maven/x/y@1"), None);
+    }
+}
@@ -0,0 +1,454 @@
+//! Scanner rule catalog: every (tool, rule) seen during ingestion, with
+//! aggregate stats over its findings and a per-rule policy.
+//!
+//! The policy gives new findings of a rule a default severity in place of
+//! the tool's, or suppresses the rule so its records are skipped at
+//! ingestion. Suppressed records still count towards `times_seen`.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{CreateFinding, SeverityLevel};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::scanner_rule::{ScannerRule, UpdateRulePolicy};
+use crate::services::finding::CategoryData;
+use crate::services::search::escape_like;
+use crate::services::text::truncate;
+
+/// Catalog columns plus finding stats.
+const SELECT_RULE: &str = r#"
+    SELECT r.id, r.source_tool, r.rule_id, r.rule_name, r.times_seen,
+           r.first_seen_at, r.last_seen_at, r.default_severity, r.suppressed,
+           r.suppression_reason, r.policy_updated_by, r.policy_updated_at,
+           st.finding_count, st.open_count, st.false_positive_count,
+           st.false_positive_count::float8 / NULLIF(st.finding_count, 0) AS false_positive_rate,
+           st.avg_severity
+    FROM scanner_rules r
+    CROSS JOIN LATERAL (
+        SELECT COUNT(*) AS finding_count,
+               COUNT(*) FILTER (
+                   WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+                     AND f.archived_at IS NULL
+               ) AS open_count,
+               COUNT(*) FILTER (WHERE f.status = 'False_Positive') AS false_positive_count,
+               AVG(CASE f.normalized_severity
+                       WHEN 'Critical' THEN 4
+                       WHEN 'High' THEN 3
+                       WHEN 'Medium' THEN 2
+                       WHEN 'Low' THEN 1
+                       ELSE 0
+                   END)::float8 AS avg_severity
+        FROM findings f
+        WHERE f.scanner_rule_id = r.id
+    ) st
+"#;
+
+/// Filters shared by the count and page queries of [`list`].
+const LIST_CONDITIONS: &str = r#"
+    WHERE ($1::text IS NULL OR r.source_tool = $1)
+      AND ($2::boolean IS NULL OR r.suppressed = $2)
+      AND ($3::text IS NULL OR r.rule_id ILIKE '%' || $3 || '%'
+                            OR r.rule_name ILIKE '%' || $3 || '%')
+"#;
+
+/// Sort order of the catalog, always descending.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleSort {
+    /// Most reported rules first.
+    #[default]
+    TimesSeen,
+    /// Noisiest rules first.
+    FalsePositiveRate,
+    /// Most recently seen first.
+    LastSeen,
+}
+
+impl RuleSort {
+    fn as_sql(self) -> &'static str {
+        match self {
+            Self::TimesSeen => "r.times_seen DESC",
+            Self::FalsePositiveRate => "false_positive_rate DESC NULLS LAST, r.times_seen DESC",
+            Self::LastSeen => "r.last_seen_at DESC",
+        }
+    }
+}
+
+/// Query parameters for the catalog.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ScannerRuleFilters {
+    pub source_tool: Option<String>,
+    pub suppressed: Option<bool>,
+    /// Substring of the rule id or name (case-insensitive).
+    pub q: Option<String>,
+    pub sort: Option<RuleSort>,
+}
+
+/// The catalog key of a parsed record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleKey {
+    pub rule_id: String,
+    pub rule_name: Option<String>,
+}
+
+/// The rule a record reports: the SAST rule, the DAST plugin, or the first
+/// CVE of an SCA finding. `None` when the record names none.
+pub fn rule_key(core: &CreateFinding, data: &CategoryData) -> Option<RuleKey> {
+    let (rule_id, rule_name) = match data {
+        CategoryData::Sast(sast) => (sast.rule_id.clone(), sast.rule_name.clone()),
+        CategoryData::Dast(_) => {
+            let plugin = core.metadata.get("plugin").and_then(|v| v.as_str())?;
+            (plugin.to_string(), core.title.clone())
+        }
+        CategoryData::Sca(_) => (core.cve_ids.first()?.clone(), core.title.clone()),
+        CategoryData::Manual(_) => return None,
+    };
+    if rule_id.trim().is_empty() {
+        return None;
+    }
+    Some(RuleKey {
+        rule_id: truncate(&rule_id, 255),
+        rule_name: Some(truncate(&rule_name, 1000)).filter(|n| !n.is_empty()),
+    })
+}
+
+/// Policy of one rule as applied at ingestion.
+#[derive(Debug, Clone, FromRow)]
+struct RulePolicy {
+    rule_id: String,
+    default_severity: Option<SeverityLevel>,
+    suppressed: bool,
+}
+
+/// Policies of a tool's rules, by rule id.
+#[derive(Debug, Default)]
+pub struct RulePolicies(HashMap<String, RulePolicy>);
+
+impl RulePolicies {
+    pub fn is_suppressed(&self, rule: &RuleKey) -> bool {
+        self.0.get(&rule.rule_id).is_some_and(|p| p.suppressed)
+    }
+
+    pub fn default_severity(&self, rule: &RuleKey) -> Option<SeverityLevel> {
+        self.0.get(&rule.rule_id)?.default_severity.clone()
+    }
+}
+
+/// Load the rules of a tool that have a policy set.
+pub async fn policies(pool: &PgPool, source_tool: &str) -> Result<RulePolicies, AppError> {
+    let rows = sqlx::query_as::<_, RulePolicy>(
+        r#"
+        SELECT rule_id, default_severity, suppressed
+        FROM scanner_rules
+        WHERE source_tool = $1 AND (suppressed OR default_severity IS NOT NULL)
+        "#,
+    )
+    .bind(source_tool)
+    .fetch_all(pool)
+    .await?;
+    Ok(RulePolicies(
+        rows.into_iter().map(|p| (p.rule_id.clone(), p)).collect(),
+    ))
+}
+
+/// Rules seen during an ingestion run and the findings they resolved to.
+#[derive(Debug, Default)]
+pub struct Sightings {
+    seen: HashMap<String, (i64, Option<String>)>,
+    finding_ids: Vec<Uuid>,
+    finding_rules: Vec<String>,
+}
+
+impl Sightings {
+    /// Count one record reporting the rule.
+    pub fn see(&mut self, rule: &RuleKey) {
+        let entry = self.seen.entry(rule.rule_id.clone()).or_insert((0, None));
+        entry.0 += 1;
+        if entry.1.is_none() {
+            entry.1 = rule.rule_name.clone();
+        }
+    }
+
+    /// Link a finding to the rule its record reports.
+    pub fn link(&mut self, finding_id: Uuid, rule: &RuleKey) {
+        self.finding_ids.push(finding_id);
+        self.finding_rules.push(rule.rule_id.clone());
+    }
+}
+
+/// Add a run's sightings to the catalog and link its findings to their rules.
+pub async fn record(
+    pool: &PgPool,
+    source_tool: &str,
+    sightings: &Sightings,
+) -> Result<(), AppError> {
+    if sightings.seen.is_empty() {
+        return Ok(());
+    }
+    let mut rule_ids = Vec::with_capacity(sightings.seen.len());
+    let mut rule_names = Vec::with_capacity(sightings.seen.len());
+    let mut counts = Vec::with_capacity(sightings.seen.len());
+    for (rule_id, (count, rule_name)) in &sightings.seen {
+        rule_ids.push(rule_id.as_str());
+        rule_names.push(rule_name.as_deref());
+        counts.push(*count);
+    }
+
+    sqlx::query(
+        r#"
+        WITH seen AS (
+            INSERT INTO scanner_rules (source_tool, rule_id, rule_name, times_seen)
+            SELECT $1, s.rule_id, s.rule_name, s.times_seen
+            FROM UNNEST($2::text[], $3::text[], $4::int8[]) AS s(rule_id, rule_name, times_seen)
+            ON CONFLICT (source_tool, rule_id) DO UPDATE
+            SET times_seen = scanner_rules.times_seen + EXCLUDED.times_seen,
+                rule_name = COALESCE(EXCLUDED.rule_name, scanner_rules.rule_name),
+                last_seen_at = NOW()
+            RETURNING id, rule_id
+        )
+        UPDATE findings f
+        SET scanner_rule_id = seen.id
+        FROM UNNEST($5::uuid[], $6::text[]) AS l(finding_id, rule_id)
+        JOIN seen ON seen.rule_id = l.rule_id
+        WHERE f.id = l.finding_id
+        "#,
+    )
+    .bind(source_tool)
+    .bind(&rule_ids)
+    .bind(&rule_names)
+    .bind(&counts)
+    .bind(&sightings.finding_ids)
+    .bind(&sightings.finding_rules)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Browse the catalog.
+pub async fn list(
+    pool: &PgPool,
+    filters: &ScannerRuleFilters,
+    pagination: &Pagination,
+) -> Result<PagedResult<ScannerRule>, AppError> {
+    let q = filters
+        .q
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(escape_like);
+
+    let total = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM scanner_rules r {LIST_CONDITIONS}"
+    ))
+    .bind(&filters.source_tool)
+    .bind(filters.suppressed)
+    .bind(&q)
+    .fetch_one(pool)
+    .await?;
+
+    let order = filters.sort.unwrap_or_default().as_sql();
+    let items = sqlx::query_as::<_, ScannerRule>(&format!(
+        "{SELECT_RULE} {LIST_CONDITIONS} \
+         ORDER BY {order}, r.source_tool, r.rule_id LIMIT $4 OFFSET $5"
+    ))
+    .bind(&filters.source_tool)
+    .bind(filters.suppressed)
+    .bind(&q)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(items, total, pagination))
+}
+
+/// Fetch a catalog entry by ID.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<ScannerRule, AppError> {
+    sqlx::query_as::<_, ScannerRule>(&format!("{SELECT_RULE} WHERE r.id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Scanner rule {id} not found")))
+}
+
+/// Replace a rule's default severity and suppression.
+pub async fn update_policy(
+    pool: &PgPool,
+    id: Uuid,
+    input: &UpdateRulePolicy,
+    updated_by: Uuid,
+) -> Result<ScannerRule, AppError> {
+    let reason = input
+        .suppression_reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    if input.suppressed && reason.is_none() {
+        return Err(AppError::Validation(
+            "A suppression reason is required to suppress a rule".to_string(),
+        ));
+    }
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE scanner_rules
+        SET default_severity = $2, suppressed = $3, suppression_reason = $4,
+            policy_updated_by = $5, policy_updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(&input.default_severity)
+    .bind(input.suppressed)
+    .bind(reason.filter(|_| input.suppressed))
+    .bind(updated_by)
+    .execute(pool)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("Scanner rule {id} not found")));
+    }
+
+    find_by_id(pool, id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn core(title: &str, cve_ids: &[&str], metadata: serde_json::Value) -> CreateFinding {
+        serde_json::from_value(serde_json::json!({
+            "source_tool": "Tool",
+            "source_finding_id": "1",
+            "finding_category": "SAST",
+            "title": title,
+            "description": "",
+            "normalized_severity": "High",
+            "original_severity": "HIGH",
+            "cwe_ids": [],
+            "cve_ids": cve_ids,
+            "fingerprint": "fp",
+            "tags": [],
+            "raw_finding": {},
+            "metadata": metadata,
+        }))
+        .unwrap()
+    }
+
+    fn data(category: &str, fields: serde_json::Value) -> CategoryData {
+        match category {
+            "sast" => CategoryData::Sast(serde_json::from_value(fields).unwrap()),
+            "sca" => CategoryData::Sca(serde_json::from_value(fields).unwrap()),
+            _ => CategoryData::Dast(serde_json::from_value(fields).unwrap()),
+        }
+    }
+
+    fn key(rule_id: &str) -> RuleKey {
+        RuleKey {
+            rule_id: rule_id.to_string(),
+            rule_name: None,
+        }
+    }
+
+    #[test]
+    fn rule_key_per_category() {
+        let sast = data(
+            "sast",
+            serde_json::json!({
+                "file_path": "A.java", "project": "p", "rule_name": "SQL injection",
+                "rule_id": "java:S3649", "scanner_tags": [],
+            }),
+        );
+        assert_eq!(
+            rule_key(&core("t", &[], serde_json::json!({})), &sast),
+            Some(RuleKey {
+                rule_id: "java:S3649".to_string(),
+                rule_name: Some("SQL injection".to_string()),
+            })
+        );
+
+        let sca = data(
+            "sca",
+            serde_json::json!({ "package_name": "log4j-core", "package_version": "2.14.1" }),
+        );
+        let log4shell = core("Log4Shell", &["CVE-2021-44228"], serde_json::json!({}));
+        assert_eq!(
+            rule_key(&log4shell, &sca).map(|k| k.rule_id),
+            Some("CVE-2021-44228".to_string())
+        );
+
+        let dast = data("dast", serde_json::json!({ "target_url": "https://x" }));
+        let xss = core("XSS", &[], serde_json::json!({ "plugin": "98104" }));
+        assert_eq!(
+            rule_key(&xss, &dast).map(|k| k.rule_id),
+            Some("98104".to_string())
+        );
+    }
+
+    #[test]
+    fn rule_key_none_without_a_rule() {
+        let sca = data(
+            "sca",
+            serde_json::json!({ "package_name": "lib", "package_version": "1.0" }),
+        );
+        assert_eq!(rule_key(&core("t", &[], serde_json::json!({})), &sca), None);
+
+        let dast = data("dast", serde_json::json!({ "target_url": "https://x" }));
+        assert_eq!(
+            rule_key(&core("t", &[], serde_json::json!({})), &dast),
+            None
+        );
+    }
+
+    #[test]
+    fn policies_apply_by_rule_id() {
+        let policies = RulePolicies(HashMap::from([
+            (
+                "noisy".to_string(),
+                RulePolicy {
+                    rule_id: "noisy".to_string(),
+                    default_severity: None,
+                    suppressed: true,
+                },
+            ),
+            (
+                "underrated".to_string(),
+                RulePolicy {
+                    rule_id: "underrated".to_string(),
+                    default_severity: Some(SeverityLevel::Critical),
+                    suppressed: false,
+                },
+            ),
+        ]));
+
+        assert!(policies.is_suppressed(&key("noisy")));
+        assert!(!policies.is_suppressed(&key("underrated")));
+        assert!(!policies.is_suppressed(&key("unknown")));
+        assert_eq!(
+            policies.default_severity(&key("underrated")),
+            Some(SeverityLevel::Critical)
+        );
+        assert_eq!(policies.default_severity(&key("unknown")), None);
+    }
+
+    #[test]
+    fn sightings_count_records_per_rule() {
+        let mut sightings = Sightings::default();
+        sightings.see(&key("a"));
+        sightings.see(&RuleKey {
+            rule_id: "a".to_string(),
+            rule_name: Some("Rule A".to_string()),
+        });
+        sightings.see(&key("b"));
+        sightings.link(Uuid::nil(), &key("a"));
+
+        assert_eq!(sightings.seen["a"], (2, Some("Rule A".to_string())));
+        assert_eq!(sightings.seen["b"], (1, None));
+        assert_eq!(sightings.finding_rules, vec!["a".to_string()]);
+    }
+}
@@ -0,0 +1,453 @@
+//! Global cross-entity search backing the omnisearch box.
+//!
+//! One query string is matched against findings (full-text over title and
+//! description), applications (name and code), CVE ids, SCA package names,
+//! and finding comments. Each group is ranked and paginated independently
+//! with the same page parameters; `kind` restricts the search to one group.
+//! Users scoped by ownership only see hits, and counts, from the applications
+//! they are assigned to.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{FindingStatus, SeverityLevel};
+use crate::models::pagination::{PagedResult, Pagination};
+
+/// Shortest accepted query after trimming.
+const MIN_QUERY_LEN: usize = 2;
+
+/// Longest accepted query.
+const MAX_QUERY_LEN: usize = 200;
+
+/// Entity groups covered by the search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Findings,
+    Applications,
+    Cves,
+    Packages,
+    Comments,
+}
+
+/// Query parameters for the global search endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchParams {
+    /// Search text (2 to 200 characters).
+    pub q: String,
+    /// Only search this group; the others come back empty.
+    pub kind: Option<SearchKind>,
+    /// Restrict to applications this user is assigned to. Set by the route
+    /// from the caller's ownership scope, never from the query string.
+    #[serde(skip)]
+    #[param(ignore)]
+    pub visible_to: Option<Uuid>,
+}
+
+/// A finding whose title or description matches the query.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct FindingHit {
+    pub id: Uuid,
+    pub title: String,
+    pub source_tool: String,
+    pub normalized_severity: SeverityLevel,
+    pub status: FindingStatus,
+    pub application_id: Option<Uuid>,
+    pub app_code: Option<String>,
+    pub rank: f64,
+    #[serde(skip)]
+    total: i64,
+}
+
+/// An application whose name or code matches the query.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct ApplicationHit {
+    pub id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    pub business_unit: Option<String>,
+    pub open_findings: i64,
+    pub rank: f64,
+    #[serde(skip)]
+    total: i64,
+}
+
+/// A CVE id referenced by at least one finding.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CveHit {
+    pub cve_id: String,
+    pub findings: i64,
+    pub open_findings: i64,
+    pub applications: i64,
+    pub rank: f64,
+    #[serde(skip)]
+    total: i64,
+}
+
+/// An SCA package name with the findings raised against it.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct PackageHit {
+    pub package_name: String,
+    /// Distinct versions seen across findings.
+    pub versions: i64,
+    pub findings: i64,
+    pub open_findings: i64,
+    pub applications: i64,
+    pub rank: f64,
+    #[serde(skip)]
+    total: i64,
+}
+
+/// A finding comment matching the query.
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CommentHit {
+    pub id: Uuid,
+    pub finding_id: Uuid,
+    pub finding_title: String,
+    pub author_name: String,
+    /// Matching fragment of the comment with the terms marked by `**`.
+    pub excerpt: String,
+    pub created_at: DateTime<Utc>,
+    pub rank: f64,
+    #[serde(skip)]
+    total: i64,
+}
+
+/// Search hits grouped by entity type.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchResults {
+    pub query: String,
+    pub findings: PagedResult<FindingHit>,
+    pub applications: PagedResult<ApplicationHit>,
+    pub cves: PagedResult<CveHit>,
+    pub packages: PagedResult<PackageHit>,
+    pub comments: PagedResult<CommentHit>,
+}
+
+/// Trim and length-check the query text.
+fn normalize_query(q: &str) -> Result<&str, AppError> {
+    let q = q.trim();
+    let len = q.chars().count();
+    if len < MIN_QUERY_LEN {
+        return Err(AppError::Validation(format!(
+            "Search query must be at least {MIN_QUERY_LEN} characters"
+        )));
+    }
+    if len > MAX_QUERY_LEN {
+        return Err(AppError::Validation(format!(
+            "Search query must be at most {MAX_QUERY_LEN} characters"
+        )));
+    }
+    Ok(q)
+}
+
+/// Escape `LIKE` wildcards so the query matches literally.
+pub(crate) fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Build a page from rows that carry the group's total as a window count.
+fn page<T: Serialize>(
+    rows: Vec<T>,
+    total: impl Fn(&T) -> i64,
+    pagination: &Pagination,
+) -> PagedResult<T> {
+    let total = rows.first().map(total).unwrap_or(0);
+    PagedResult::new(rows, total, pagination)
+}
+
+/// Run the search across all groups, or only `params.kind` when given.
+pub async fn search(
+    pool: &PgPool,
+    params: &SearchParams,
+    pagination: &Pagination,
+) -> Result<SearchResults, AppError> {
+    let q = normalize_query(&params.q)?;
+    let escaped = escape_like(q);
+    let wanted = |kind: SearchKind| params.kind.is_none() || params.kind == Some(kind);
+
+    let (findings, applications, cves, packages, comments) = tokio::try_join!(
+        async {
+            if wanted(SearchKind::Findings) {
+                search_findings(pool, q, params.visible_to, pagination).await
+            } else {
+                Ok(Vec::new())
+            }
+        },
+        async {
+            if wanted(SearchKind::Applications) {
+                search_applications(pool, &escaped, params.visible_to, pagination).await
+            } else {
+                Ok(Vec::new())
+            }
+        },
+        async {
+            if wanted(SearchKind::Cves) {
+                search_cves(pool, &escaped, params.visible_to, pagination).await
+            } else {
+                Ok(Vec::new())
+            }
+        },
+        async {
+            if wanted(SearchKind::Packages) {
+                search_packages(pool, &escaped, params.visible_to, pagination).await
+            } else {
+                Ok(Vec::new())
+            }
+        },
+        async {
+            if wanted(SearchKind::Comments) {
+                search_comments(pool, q, params.visible_to, pagination).await
+            } else {
+                Ok(Vec::new())
+            }
+        },
+    )?;
+
+    Ok(SearchResults {
+        query: q.to_string(),
+        findings: page(findings, |h| h.total, pagination),
+        applications: page(applications, |h| h.total, pagination),
+        cves: page(cves, |h| h.total, pagination),
+        packages: page(packages, |h| h.total, pagination),
+        comments: page(comments, |h| h.total, pagination),
+    })
+}
+
+/// Full-text match over the generated `search_vector`, excluding archived findings.
+async fn search_findings(
+    pool: &PgPool,
+    q: &str,
+    visible_to: Option<Uuid>,
+    pagination: &Pagination,
+) -> Result<Vec<FindingHit>, AppError> {
+    let rows = sqlx::query_as::<_, FindingHit>(
+        r#"
+        SELECT
+            f.id, f.title, f.source_tool, f.normalized_severity, f.status,
+            f.application_id, a.app_code,
+            ts_rank(f.search_vector, plainto_tsquery('english', $1))::float8 AS rank,
+            COUNT(*) OVER () AS total
+        FROM findings f
+        LEFT JOIN applications a ON a.id = f.application_id
+        WHERE f.search_vector @@ plainto_tsquery('english', $1)
+          AND f.archived_at IS NULL
+          AND ($4::uuid IS NULL OR f.application_id IN (
+              SELECT application_id FROM application_owners WHERE user_id = $4
+          ))
+        ORDER BY rank DESC, f.composite_risk_score DESC NULLS LAST, f.id
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(q)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .bind(visible_to)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Exact code matches rank first, then prefix matches, then substring matches.
+async fn search_applications(
+    pool: &PgPool,
+    escaped: &str,
+    visible_to: Option<Uuid>,
+    pagination: &Pagination,
+) -> Result<Vec<ApplicationHit>, AppError> {
+    let rows = sqlx::query_as::<_, ApplicationHit>(
+        r#"
+        SELECT
+            a.id, a.app_name, a.app_code, a.business_unit,
+            (
+                SELECT COUNT(*) FROM findings f
+                WHERE f.application_id = a.id
+                  AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+            ) AS open_findings,
+            CASE
+                WHEN a.app_code ILIKE $1 OR a.app_name ILIKE $1 THEN 1.0
+                WHEN a.app_code ILIKE $1 || '%' OR a.app_name ILIKE $1 || '%' THEN 0.75
+                ELSE 0.5
+            END::float8 AS rank,
+            COUNT(*) OVER () AS total
+        FROM applications a
+        WHERE (a.app_code ILIKE '%' || $1 || '%' OR a.app_name ILIKE '%' || $1 || '%')
+          AND ($4::uuid IS NULL OR a.id IN (
+              SELECT application_id FROM application_owners WHERE user_id = $4
+          ))
+        ORDER BY rank DESC, a.app_name
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(escaped)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .bind(visible_to)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// CVE ids from `findings.cve_ids`, ranked by match quality then prevalence.
+async fn search_cves(
+    pool: &PgPool,
+    escaped: &str,
+    visible_to: Option<Uuid>,
+    pagination: &Pagination,
+) -> Result<Vec<CveHit>, AppError> {
+    let rows = sqlx::query_as::<_, CveHit>(
+        r#"
+        SELECT
+            UPPER(cve.id) AS cve_id,
+            COUNT(DISTINCT f.id) AS findings,
+            COUNT(DISTINCT f.id) FILTER (
+                WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+            ) AS open_findings,
+            COUNT(DISTINCT f.application_id) AS applications,
+            MAX(CASE
+                WHEN cve.id ILIKE $1 THEN 1.0
+                WHEN cve.id ILIKE $1 || '%' THEN 0.75
+                ELSE 0.5
+            END)::float8 AS rank,
+            COUNT(*) OVER () AS total
+        FROM findings f
+        CROSS JOIN LATERAL jsonb_array_elements_text(f.cve_ids) AS cve(id)
+        WHERE cve.id ILIKE '%' || $1 || '%'
+          AND f.archived_at IS NULL
+          AND ($4::uuid IS NULL OR f.application_id IN (
+              SELECT application_id FROM application_owners WHERE user_id = $4
+          ))
+        GROUP BY UPPER(cve.id)
+        ORDER BY rank DESC, findings DESC, cve_id
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(escaped)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .bind(visible_to)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// SCA package names, ranked by match quality then number of findings.
+async fn search_packages(
+    pool: &PgPool,
+    escaped: &str,
+    visible_to: Option<Uuid>,
+    pagination: &Pagination,
+) -> Result<Vec<PackageHit>, AppError> {
+    let rows = sqlx::query_as::<_, PackageHit>(
+        r#"
+        SELECT
+            sc.package_name,
+            COUNT(DISTINCT sc.package_version) AS versions,
+            COUNT(*) AS findings,
+            COUNT(*) FILTER (
+                WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+            ) AS open_findings,
+            COUNT(DISTINCT f.application_id) AS applications,
+            (CASE
+                WHEN sc.package_name ILIKE $1 THEN 1.0
+                WHEN sc.package_name ILIKE $1 || '%' THEN 0.75
+                ELSE 0.5
+            END)::float8 AS rank,
+            COUNT(*) OVER () AS total
+        FROM finding_sca sc
+        JOIN findings f ON f.id = sc.finding_id
+        WHERE sc.package_name ILIKE '%' || $1 || '%'
+          AND f.archived_at IS NULL
+          AND ($4::uuid IS NULL OR f.application_id IN (
+              SELECT application_id FROM application_owners WHERE user_id = $4
+          ))
+        GROUP BY sc.package_name
+        ORDER BY rank DESC, findings DESC, sc.package_name
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(escaped)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .bind(visible_to)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// Full-text match over comment bodies, newest first among equal ranks.
+async fn search_comments(
+    pool: &PgPool,
+    q: &str,
+    visible_to: Option<Uuid>,
+    pagination: &Pagination,
+) -> Result<Vec<CommentHit>, AppError> {
+    let rows = sqlx::query_as::<_, CommentHit>(
+        r#"
+        SELECT
+            c.id, c.finding_id, f.title AS finding_title, c.author_name,
+            ts_headline(
+                'english', c.content, plainto_tsquery('english', $1),
+                'StartSel=**, StopSel=**, MaxWords=30, MinWords=10'
+            ) AS excerpt,
+            c.created_at,
+            ts_rank(to_tsvector('english', c.content), plainto_tsquery('english', $1))::float8 AS rank,
+            COUNT(*) OVER () AS total
+        FROM finding_comments c
+        JOIN findings f ON f.id = c.finding_id
+        WHERE to_tsvector('english', c.content) @@ plainto_tsquery('english', $1)
+          AND f.archived_at IS NULL
+          AND ($4::uuid IS NULL OR f.application_id IN (
+              SELECT application_id FROM application_owners WHERE user_id = $4
+          ))
+        ORDER BY rank DESC, c.created_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(q)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .bind(visible_to)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_is_trimmed_and_length_checked() {
+        assert_eq!(normalize_query("  log4j ").unwrap(), "log4j");
+        assert!(normalize_query(" a ").is_err());
+        assert!(normalize_query(&"x".repeat(MAX_QUERY_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn like_wildcards_are_escaped() {
+        assert_eq!(escape_like("100%_done"), "100\\%\\_done");
+        assert_eq!(escape_like("C:\\temp"), "C:\\\\temp");
+        assert_eq!(escape_like("CVE-2021-44228"), "CVE-2021-44228");
+    }
+
+    #[test]
+    fn kind_parses_snake_case() {
+        let params: SearchParams =
+            serde_json::from_value(serde_json::json!({"q": "openssl", "kind": "packages"}))
+                .unwrap();
+        assert_eq!(params.kind, Some(SearchKind::Packages));
+    }
+}
@@ -0,0 +1,622 @@
+//! Fuzzy near-duplicate matching.
+//!
+//! Scores pairs of findings in the same application by trigram similarity
+//! (`pg_trgm`) of their titles and locations (SAST file path or DAST target
+//! URL) and by whether they share a CWE. Pairs scoring at least the
+//! configured minimum (`finding_similarity` setting) are "probably the same
+//! issue": they are listed in a finding's similar-findings panel and can be
+//! queued for analyst review as Low-confidence `duplicate_of` relationships.
+//!
+//! The panel also lists findings sharing an exact attribute with the finding
+//! (CVE, CWE, SAST rule or SCA package in any application, file in the same
+//! application), so other instances of the same issue are one click away.
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{ConfidenceLevel, FindingCategory, FindingStatus, SeverityLevel};
+use crate::services::config_cache;
+use crate::services::cross_dedup_service::{known_pairs, pair_key};
+
+/// Default number of entries per list of the similar-findings panel.
+const DEFAULT_SIMILAR_LIMIT: i64 = 25;
+/// Upper bound on the entries per list.
+const MAX_SIMILAR_LIMIT: i64 = 100;
+/// Candidates fetched before scoring; the trigram operators only pre-filter.
+const CANDIDATE_LIMIT: i64 = 200;
+/// Match source recorded on relationships queued by similarity runs.
+pub const MATCH_SOURCE: &str = "similarity";
+
+/// Component weights and threshold of the similarity score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimilaritySettings {
+    /// Minimum score (0.0-1.0) for a pair to be suggested.
+    pub min_score: f64,
+    /// Score at or above which a queued pair is recorded as a confirmed
+    /// duplicate instead of going to review. `None` reviews every pair.
+    pub auto_confirm_score: Option<f64>,
+    pub title_weight: f64,
+    pub location_weight: f64,
+    pub cwe_weight: f64,
+}
+
+impl Default for SimilaritySettings {
+    fn default() -> Self {
+        Self {
+            min_score: 0.6,
+            auto_confirm_score: None,
+            title_weight: 0.5,
+            location_weight: 0.3,
+            cwe_weight: 0.2,
+        }
+    }
+}
+
+impl SimilaritySettings {
+    /// Confidence of the `duplicate_of` relationship recorded for a pair
+    /// with this score; only High skips the review queue.
+    pub fn confidence_for(&self, score: f64) -> ConfidenceLevel {
+        match self.auto_confirm_score {
+            Some(threshold) if score >= threshold => ConfidenceLevel::High,
+            _ => ConfidenceLevel::Low,
+        }
+    }
+}
+
+/// Similarity components of a pair. `None` components are unknown because
+/// one of the findings has no location or no CWE.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimilarityComponents {
+    pub title: f64,
+    pub location: Option<f64>,
+    pub same_cwe: Option<bool>,
+}
+
+impl SimilarityComponents {
+    /// Weighted mean of the known components, so a finding without a
+    /// location is not penalised against one with a location.
+    pub fn score(&self, settings: &SimilaritySettings) -> f64 {
+        let mut total = settings.title_weight * self.title;
+        let mut weights = settings.title_weight;
+        if let Some(location) = self.location {
+            total += settings.location_weight * location;
+            weights += settings.location_weight;
+        }
+        if let Some(same_cwe) = self.same_cwe {
+            total += settings.cwe_weight * if same_cwe { 1.0 } else { 0.0 };
+            weights += settings.cwe_weight;
+        }
+        if weights <= 0.0 {
+            return 0.0;
+        }
+        (total / weights).clamp(0.0, 1.0)
+    }
+}
+
+/// A finding similar to another, for the similar-findings panel.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SimilarFinding {
+    pub finding_id: Uuid,
+    pub title: String,
+    pub finding_category: FindingCategory,
+    pub source_tool: String,
+    pub normalized_severity: SeverityLevel,
+    pub status: FindingStatus,
+    /// Weighted similarity score, 0.0-1.0.
+    pub score: f64,
+    pub title_similarity: f64,
+    pub location_similarity: Option<f64>,
+    pub same_cwe: Option<bool>,
+}
+
+/// An exact attribute two findings have in common.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SharedAttribute {
+    Cve,
+    Cwe,
+    /// SAST rule.
+    Rule,
+    /// SAST file path, within the same application.
+    File,
+    /// SCA package name.
+    Package,
+}
+
+/// A finding sharing exact attributes with another, in any application.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RelatedFinding {
+    pub finding_id: Uuid,
+    pub title: String,
+    pub finding_category: FindingCategory,
+    pub source_tool: String,
+    pub normalized_severity: SeverityLevel,
+    pub status: FindingStatus,
+    pub application_id: Option<Uuid>,
+    pub app_code: Option<String>,
+    pub same_application: bool,
+    /// Attributes in common, most specific first.
+    pub shared: Vec<SharedAttribute>,
+}
+
+/// The similar-findings panel of a finding.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SimilarFindings {
+    /// Probable duplicates in the same application, best match first.
+    pub near_duplicates: Vec<SimilarFinding>,
+    /// Findings sharing a CVE, CWE, rule, file or package, most shared
+    /// attributes first.
+    pub related: Vec<RelatedFinding>,
+}
+
+/// Outcome of queueing an application's near-duplicates for review.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct SimilarityRunResult {
+    /// Pairs at or above the minimum score, including already known ones.
+    pub pairs_found: usize,
+    /// Pairs at or above the auto-confirm score, recorded as confirmed.
+    pub auto_confirmed: usize,
+    pub queued_for_review: usize,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SimilarRow {
+    id: Uuid,
+    title: String,
+    finding_category: FindingCategory,
+    source_tool: String,
+    normalized_severity: SeverityLevel,
+    status: FindingStatus,
+    title_similarity: f64,
+    location_similarity: Option<f64>,
+    same_cwe: Option<bool>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct RelatedRow {
+    id: Uuid,
+    title: String,
+    finding_category: FindingCategory,
+    source_tool: String,
+    normalized_severity: SeverityLevel,
+    status: FindingStatus,
+    application_id: Option<Uuid>,
+    app_code: Option<String>,
+    same_application: bool,
+    shared_cve: bool,
+    shared_cwe: bool,
+    shared_rule: bool,
+    shared_file: bool,
+    shared_package: bool,
+}
+
+impl RelatedRow {
+    fn shared(&self) -> Vec<SharedAttribute> {
+        [
+            (self.shared_cve, SharedAttribute::Cve),
+            (self.shared_package, SharedAttribute::Package),
+            (self.shared_file, SharedAttribute::File),
+            (self.shared_rule, SharedAttribute::Rule),
+            (self.shared_cwe, SharedAttribute::Cwe),
+        ]
+        .into_iter()
+        .filter_map(|(shared, attribute)| shared.then_some(attribute))
+        .collect()
+    }
+
+    fn into_related(self) -> RelatedFinding {
+        let shared = self.shared();
+        RelatedFinding {
+            finding_id: self.id,
+            title: self.title,
+            finding_category: self.finding_category,
+            source_tool: self.source_tool,
+            normalized_severity: self.normalized_severity,
+            status: self.status,
+            application_id: self.application_id,
+            app_code: self.app_code,
+            same_application: self.same_application,
+            shared,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct PairRow {
+    older_id: Uuid,
+    newer_id: Uuid,
+    title_similarity: f64,
+    location_similarity: Option<f64>,
+    same_cwe: Option<bool>,
+}
+
+fn components(title: f64, location: Option<f64>, same_cwe: Option<bool>) -> SimilarityComponents {
+    SimilarityComponents {
+        title,
+        location,
+        same_cwe,
+    }
+}
+
+/// Open findings of the application with their location, shared by both
+/// queries below.
+const CANDIDATES_CTE: &str = r#"
+    candidates AS (
+        SELECT f.id, f.application_id, f.title, f.cwe_ids, f.first_seen,
+               COALESCE(fs.file_path, fd.target_url) AS location
+        FROM findings f
+        LEFT JOIN finding_sast fs ON fs.finding_id = f.id
+        LEFT JOIN finding_dast fd ON fd.finding_id = f.id
+        WHERE f.application_id = $1
+          AND f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+          AND f.archived_at IS NULL
+    )
+"#;
+
+/// Similarity components of two candidates `a` and `b`.
+const COMPONENTS_SQL: &str = r#"
+    similarity(a.title, b.title)::float8 AS title_similarity,
+    CASE WHEN a.location IS NULL OR b.location IS NULL THEN NULL
+         ELSE similarity(a.location, b.location)::float8 END AS location_similarity,
+    CASE WHEN jsonb_array_length(COALESCE(a.cwe_ids, '[]')) = 0
+           OR jsonb_array_length(COALESCE(b.cwe_ids, '[]')) = 0 THEN NULL
+         ELSE a.cwe_ids ?| ARRAY(SELECT jsonb_array_elements_text(b.cwe_ids)) END AS same_cwe
+"#;
+
+/// Findings of the same application most similar to `finding_id`, best
+/// first.
+pub async fn find_similar(
+    pool: &PgPool,
+    finding_id: Uuid,
+    limit: Option<i64>,
+) -> Result<Vec<SimilarFinding>, AppError> {
+    let application_id =
+        sqlx::query_scalar::<_, Option<Uuid>>("SELECT application_id FROM findings WHERE id = $1")
+            .bind(finding_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Finding {finding_id} not found")))?;
+    let Some(application_id) = application_id else {
+        return Ok(Vec::new());
+    };
+
+    let rows = sqlx::query_as::<_, SimilarRow>(&format!(
+        r#"
+        WITH {CANDIDATES_CTE}
+        SELECT b.id, f.title, f.finding_category, f.source_tool, f.normalized_severity, f.status,
+               {COMPONENTS_SQL}
+        FROM (
+            SELECT base.title, base.cwe_ids, COALESCE(fs.file_path, fd.target_url) AS location
+            FROM findings base
+            LEFT JOIN finding_sast fs ON fs.finding_id = base.id
+            LEFT JOIN finding_dast fd ON fd.finding_id = base.id
+            WHERE base.id = $2
+        ) a
+        JOIN candidates b ON b.id <> $2
+        JOIN findings f ON f.id = b.id
+        WHERE b.title % a.title OR b.location % a.location
+        ORDER BY title_similarity DESC
+        LIMIT $3
+        "#
+    ))
+    .bind(application_id)
+    .bind(finding_id)
+    .bind(CANDIDATE_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    let settings = config_cache::global()
+        .settings(pool)
+        .await?
+        .similarity_settings();
+    let limit = limit
+        .unwrap_or(DEFAULT_SIMILAR_LIMIT)
+        .clamp(1, MAX_SIMILAR_LIMIT) as usize;
+    Ok(rank(rows, &settings, limit))
+}
+
+/// Non-archived findings sharing an exact attribute with `finding_id`, most
+/// shared attributes first, then same application first, newest first.
+pub async fn find_related(
+    pool: &PgPool,
+    finding_id: Uuid,
+    limit: Option<i64>,
+) -> Result<Vec<RelatedFinding>, AppError> {
+    let limit = limit
+        .unwrap_or(DEFAULT_SIMILAR_LIMIT)
+        .clamp(1, MAX_SIMILAR_LIMIT);
+    let rows = sqlx::query_as::<_, RelatedRow>(
+        r#"
+        WITH base AS (
+            SELECT f.id, f.application_id,
+                   ARRAY(SELECT jsonb_array_elements_text(COALESCE(f.cve_ids, '[]'))) AS cves,
+                   ARRAY(SELECT jsonb_array_elements_text(COALESCE(f.cwe_ids, '[]'))) AS cwes,
+                   fs.rule_id, fs.file_path, fc.package_name
+            FROM findings f
+            LEFT JOIN finding_sast fs ON fs.finding_id = f.id
+            LEFT JOIN finding_sca fc ON fc.finding_id = f.id
+            WHERE f.id = $1
+        ),
+        matches AS (
+            SELECT f.id, f.title, f.finding_category, f.source_tool, f.normalized_severity,
+                   f.status, f.application_id, f.first_seen,
+                   COALESCE(f.application_id = b.application_id, false) AS same_application,
+                   COALESCE(f.cve_ids ?| b.cves, false) AS shared_cve,
+                   COALESCE(f.cwe_ids ?| b.cwes, false) AS shared_cwe,
+                   COALESCE(fs.rule_id = b.rule_id, false) AS shared_rule,
+                   COALESCE(f.application_id = b.application_id AND fs.file_path = b.file_path, false) AS shared_file,
+                   COALESCE(fc.package_name = b.package_name, false) AS shared_package
+            FROM base b
+            JOIN findings f ON f.id <> b.id AND f.archived_at IS NULL
+            LEFT JOIN finding_sast fs ON fs.finding_id = f.id
+            LEFT JOIN finding_sca fc ON fc.finding_id = f.id
+            WHERE f.cve_ids ?| b.cves
+               OR f.cwe_ids ?| b.cwes
+               OR fs.rule_id = b.rule_id
+               OR (f.application_id = b.application_id AND fs.file_path = b.file_path)
+               OR fc.package_name = b.package_name
+        )
+        SELECT m.id, m.title, m.finding_category, m.source_tool, m.normalized_severity, m.status,
+               m.application_id, a.app_code, m.same_application,
+               m.shared_cve, m.shared_cwe, m.shared_rule, m.shared_file, m.shared_package
+        FROM matches m
+        LEFT JOIN applications a ON a.id = m.application_id
+        ORDER BY m.shared_cve::int + m.shared_cwe::int + m.shared_rule::int
+                     + m.shared_file::int + m.shared_package::int DESC,
+                 m.same_application DESC,
+                 m.first_seen DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(finding_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(RelatedRow::into_related).collect())
+}
+
+/// The similar-findings panel: near-duplicates and findings sharing exact
+/// attributes, up to `limit` of each.
+pub async fn similar_findings(
+    pool: &PgPool,
+    finding_id: Uuid,
+    limit: Option<i64>,
+) -> Result<SimilarFindings, AppError> {
+    let near_duplicates = find_similar(pool, finding_id, limit).await?;
+    let related = find_related(pool, finding_id, limit).await?;
+    Ok(SimilarFindings {
+        near_duplicates,
+        related,
+    })
+}
+
+fn rank(rows: Vec<SimilarRow>, settings: &SimilaritySettings, limit: usize) -> Vec<SimilarFinding> {
+    let mut similar: Vec<SimilarFinding> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let score = components(row.title_similarity, row.location_similarity, row.same_cwe)
+                .score(settings);
+            if score < settings.min_score {
+                return None;
+            }
+            Some(SimilarFinding {
+                finding_id: row.id,
+                title: row.title,
+                finding_category: row.finding_category,
+                source_tool: row.source_tool,
+                normalized_severity: row.normalized_severity,
+                status: row.status,
+                score,
+                title_similarity: row.title_similarity,
+                location_similarity: row.location_similarity,
+                same_cwe: row.same_cwe,
+            })
+        })
+        .collect();
+    similar.sort_by(|a, b| b.score.total_cmp(&a.score));
+    similar.truncate(limit);
+    similar
+}
+
+/// Queue the application's near-duplicate pairs for review as Low-confidence
+/// `duplicate_of` relationships from the newer finding to the older one, or
+/// record them as High confidence when they reach the auto-confirm score.
+/// Pairs already linked as duplicates or rejected are skipped.
+pub async fn queue_near_duplicates(
+    pool: &PgPool,
+    app_id: Uuid,
+    user_id: Uuid,
+) -> Result<SimilarityRunResult, AppError> {
+    let exists =
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM applications WHERE id = $1)")
+            .bind(app_id)
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Application {app_id} not found"
+        )));
+    }
+
+    let rows = sqlx::query_as::<_, PairRow>(&format!(
+        r#"
+        WITH {CANDIDATES_CTE}
+        SELECT a.id AS older_id, b.id AS newer_id,
+               {COMPONENTS_SQL}
+        FROM candidates a
+        JOIN candidates b
+          ON (a.first_seen, a.id) < (b.first_seen, b.id)
+         AND (a.title % b.title OR a.location % b.location)
+        "#
+    ))
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+
+    let settings = config_cache::global()
+        .settings(pool)
+        .await?
+        .similarity_settings();
+    let known = known_pairs(pool, app_id).await?;
+
+    let mut result = SimilarityRunResult::default();
+    let mut sources = Vec::new();
+    let mut targets = Vec::new();
+    let mut confidences = Vec::new();
+    let mut scores = Vec::new();
+    let mut notes = Vec::new();
+    for row in rows {
+        let score = components(row.title_similarity, row.location_similarity, row.same_cwe)
+            .score(&settings);
+        if score < settings.min_score {
+            continue;
+        }
+        result.pairs_found += 1;
+        if known.contains(&pair_key(row.older_id, row.newer_id)) {
+            continue;
+        }
+        sources.push(row.newer_id);
+        targets.push(row.older_id);
+        confidences.push(settings.confidence_for(score));
+        scores.push(score as f32);
+        notes.push(format!("Similar finding (score {score:.2})"));
+    }
+    if sources.is_empty() {
+        return Ok(result);
+    }
+
+    let inserted: Vec<ConfidenceLevel> = sqlx::query_scalar(
+        r#"
+        INSERT INTO finding_relationships
+            (source_finding_id, target_finding_id, relationship_type, confidence, created_by, notes, match_source, match_score)
+        SELECT source, target, 'duplicate_of', confidence, $3, notes, $7, score
+        FROM UNNEST($1::uuid[], $2::uuid[], $4::confidence_level[], $5::real[], $6::text[])
+            AS s(source, target, confidence, score, notes)
+        ON CONFLICT (source_finding_id, target_finding_id, relationship_type) DO NOTHING
+        RETURNING confidence
+        "#,
+    )
+    .bind(&sources)
+    .bind(&targets)
+    .bind(user_id)
+    .bind(&confidences)
+    .bind(&scores)
+    .bind(&notes)
+    .bind(MATCH_SOURCE)
+    .fetch_all(pool)
+    .await?;
+
+    for confidence in inserted {
+        if confidence == ConfidenceLevel::High {
+            result.auto_confirmed += 1;
+        } else {
+            result.queued_for_review += 1;
+        }
+    }
+    tracing::info!(
+        application_id = %app_id,
+        pairs_found = result.pairs_found,
+        auto_confirmed = result.auto_confirmed,
+        queued_for_review = result.queued_for_review,
+        "Near-duplicate similarity run"
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(title_similarity: f64, location: Option<f64>, same_cwe: Option<bool>) -> SimilarRow {
+        SimilarRow {
+            id: Uuid::new_v4(),
+            title: "SQL injection".to_string(),
+            finding_category: FindingCategory::Sast,
+            source_tool: "SonarQube".to_string(),
+            normalized_severity: SeverityLevel::High,
+            status: FindingStatus::New,
+            title_similarity,
+            location_similarity: location,
+            same_cwe,
+        }
+    }
+
+    #[test]
+    fn shared_attributes_are_listed_most_specific_first() {
+        let row = RelatedRow {
+            id: Uuid::new_v4(),
+            title: "SQL injection".to_string(),
+            finding_category: FindingCategory::Sast,
+            source_tool: "SonarQube".to_string(),
+            normalized_severity: SeverityLevel::High,
+            status: FindingStatus::New,
+            application_id: None,
+            app_code: None,
+            same_application: false,
+            shared_cve: false,
+            shared_cwe: true,
+            shared_rule: true,
+            shared_file: false,
+            shared_package: false,
+        };
+        assert_eq!(
+            row.shared(),
+            vec![SharedAttribute::Rule, SharedAttribute::Cwe]
+        );
+    }
+
+    #[test]
+    fn score_weights_known_components_only() {
+        let settings = SimilaritySettings::default();
+        assert_eq!(components(0.8, None, None).score(&settings), 0.8);
+        // (0.5 * 0.8 + 0.3 * 1.0 + 0.2 * 1.0) / 1.0
+        let full = components(0.8, Some(1.0), Some(true)).score(&settings);
+        assert!((full - 0.9).abs() < 1e-9);
+        // (0.5 * 0.8 + 0.2 * 0.0) / 0.7
+        let other_cwe = components(0.8, None, Some(false)).score(&settings);
+        assert!((other_cwe - 0.4 / 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn score_without_weights_is_zero() {
+        let settings = SimilaritySettings {
+            min_score: 0.5,
+            auto_confirm_score: None,
+            title_weight: 0.0,
+            location_weight: 0.0,
+            cwe_weight: 0.0,
+        };
+        assert_eq!(components(1.0, None, None).score(&settings), 0.0);
+    }
+
+    #[test]
+    fn pairs_reaching_the_auto_confirm_score_skip_review() {
+        let mut settings = SimilaritySettings::default();
+        assert_eq!(settings.confidence_for(1.0), ConfidenceLevel::Low);
+
+        settings.auto_confirm_score = Some(0.9);
+        assert_eq!(settings.confidence_for(0.89), ConfidenceLevel::Low);
+        assert_eq!(settings.confidence_for(0.9), ConfidenceLevel::High);
+    }
+
+    #[test]
+    fn rank_filters_below_threshold_and_sorts_best_first() {
+        let settings = SimilaritySettings::default();
+        let rows = vec![
+            row(0.65, None, None),
+            row(0.3, Some(0.2), Some(false)),
+            row(0.9, Some(0.9), Some(true)),
+        ];
+        let ranked = rank(rows, &settings, 10);
+        assert_eq!(ranked.len(), 2);
+        assert!(ranked[0].score > ranked[1].score);
+        assert_eq!(ranked[1].title_similarity, 0.65);
+
+        let rows = vec![row(0.9, None, None), row(0.8, None, None)];
+        assert_eq!(rank(rows, &settings, 1).len(), 1);
+    }
+}
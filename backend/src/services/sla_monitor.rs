@@ -0,0 +1,94 @@
+//! Periodic SLA breach detection.
+//!
+//! Open findings whose `sla_due_date` has passed are flipped to `Breached`
+//! and the owners and champions of their application are notified once per
+//! breach.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::notification::NotificationKind;
+use crate::services::notification::{self, NewNotification};
+
+#[derive(Debug, sqlx::FromRow)]
+struct BreachedFinding {
+    id: Uuid,
+    application_id: Option<Uuid>,
+    title: String,
+}
+
+fn breach_notification(finding: &BreachedFinding) -> NewNotification {
+    NewNotification {
+        kind: NotificationKind::SlaBreach,
+        title: format!("SLA breached: {}", finding.title),
+        body: None,
+        finding_id: Some(finding.id),
+        application_id: finding.application_id,
+        actor_name: None,
+    }
+}
+
+/// Mark open findings past their SLA due date as breached and notify their
+/// applications. Returns the number of newly breached findings.
+pub async fn sweep(pool: &PgPool) -> Result<usize, AppError> {
+    let breached = sqlx::query_as::<_, BreachedFinding>(
+        r#"
+        UPDATE findings SET sla_status = 'Breached', updated_at = NOW()
+        WHERE sla_due_date < NOW()
+          AND sla_status IS DISTINCT FROM 'Breached'
+          AND status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+        RETURNING id, application_id, title
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for finding in &breached {
+        if let Some(application_id) = finding.application_id {
+            notification::send_to_application(
+                pool,
+                application_id,
+                None,
+                &breach_notification(finding),
+            )
+            .await;
+        }
+    }
+    Ok(breached.len())
+}
+
+/// Spawn the background task that sweeps for SLA breaches every `interval`.
+pub fn spawn_monitor(pool: PgPool, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match sweep(&pool).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!(count, "Marked findings as SLA breached"),
+                Err(e) => tracing::error!(error = %e, "SLA breach sweep failed"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breach_notification_links_finding_and_application() {
+        let finding = BreachedFinding {
+            id: Uuid::new_v4(),
+            application_id: Some(Uuid::new_v4()),
+            title: "SQL injection in login".to_string(),
+        };
+        let n = breach_notification(&finding);
+        assert_eq!(n.kind, NotificationKind::SlaBreach);
+        assert_eq!(n.title, "SLA breached: SQL injection in login");
+        assert_eq!(n.finding_id, Some(finding.id));
+        assert_eq!(n.application_id, finding.application_id);
+        assert!(n.actor_name.is_none());
+    }
+}
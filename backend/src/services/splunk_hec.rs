@@ -0,0 +1,332 @@
+//! Splunk HTTP Event Collector (HEC) forwarding for platform events.
+//!
+//! Events are pushed onto a bounded channel by request handlers and the
+//! ingestion pipeline, then drained by a background task that batches them
+//! and POSTs each batch to the collector. Forwarding is best-effort: a full
+//! channel or a failed request is logged and never fails the caller.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::models::finding::{Finding, FindingStatus, SeverityLevel};
+
+/// Number of events buffered in memory before new events are dropped.
+///
+/// Sized to absorb a large ingestion (tens of thousands of new findings)
+/// while the collector is slow, without unbounded memory growth.
+const CHANNEL_CAPACITY: usize = 50_000;
+
+/// Timeout for a single HEC request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Value of the HEC `source` field for every event we emit.
+const HEC_SOURCE: &str = "synapsec";
+
+/// A platform event forwarded to Splunk.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum PlatformEvent {
+    /// An ingestion run completed.
+    IngestionCompleted {
+        ingestion_id: Uuid,
+        source_tool: String,
+        file_name: String,
+        total_parsed: usize,
+        new_findings: usize,
+        updated_findings: usize,
+        reopened_findings: usize,
+        errors: usize,
+        initiated_by: Uuid,
+    },
+    /// A finding was created, either by ingestion or manually.
+    FindingCreated {
+        finding_id: Uuid,
+        application_id: Option<Uuid>,
+        source_tool: String,
+        title: String,
+        normalized_severity: SeverityLevel,
+        fingerprint: String,
+    },
+    /// A finding moved to a new lifecycle status.
+    StatusChanged {
+        finding_id: Uuid,
+        previous_status: FindingStatus,
+        new_status: FindingStatus,
+        actor_name: String,
+        justification: Option<String>,
+    },
+}
+
+impl PlatformEvent {
+    /// Build a [`PlatformEvent::FindingCreated`] from a stored finding.
+    pub fn finding_created(finding: &Finding) -> Self {
+        Self::FindingCreated {
+            finding_id: finding.id,
+            application_id: finding.application_id,
+            source_tool: finding.source_tool.clone(),
+            title: finding.title.clone(),
+            normalized_severity: finding.normalized_severity.clone(),
+            fingerprint: finding.fingerprint.clone(),
+        }
+    }
+
+    /// HEC `sourcetype` used to route this event inside Splunk.
+    pub fn sourcetype(&self) -> &'static str {
+        match self {
+            Self::IngestionCompleted { .. } => "synapsec:ingestion",
+            Self::FindingCreated { .. } => "synapsec:finding",
+            Self::StatusChanged { .. } => "synapsec:status_change",
+        }
+    }
+}
+
+/// Envelope in the shape expected by `/services/collector/event`.
+#[derive(Debug, Serialize)]
+struct HecEnvelope<'a> {
+    /// Epoch seconds with millisecond precision.
+    time: f64,
+    source: &'static str,
+    sourcetype: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<&'a str>,
+    event: &'a PlatformEvent,
+}
+
+/// An event stamped with the time it was emitted.
+#[derive(Debug, Clone)]
+struct TimedEvent {
+    at: DateTime<Utc>,
+    event: PlatformEvent,
+}
+
+/// Collector connection settings derived from [`AppConfig`].
+#[derive(Clone)]
+pub struct HecSettings {
+    pub url: String,
+    pub token: String,
+    pub index: Option<String>,
+    pub batch_size: usize,
+    pub flush_interval: Duration,
+}
+
+impl std::fmt::Debug for HecSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HecSettings")
+            .field("url", &self.url)
+            .field("token", &"[redacted]")
+            .field("index", &self.index)
+            .field("batch_size", &self.batch_size)
+            .field("flush_interval", &self.flush_interval)
+            .finish()
+    }
+}
+
+impl HecSettings {
+    /// Build settings from config, returning `None` when HEC is not configured.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let url = config.splunk_hec_url.as_deref()?.trim_end_matches('/');
+        let token = config.splunk_hec_token.clone()?;
+        Some(Self {
+            url: format!("{url}/services/collector/event"),
+            token,
+            index: config.splunk_hec_index.clone(),
+            batch_size: config.splunk_hec_batch_size.max(1),
+            flush_interval: Duration::from_secs(config.splunk_hec_flush_interval_secs.max(1)),
+        })
+    }
+}
+
+/// Handle for queueing events to the background HEC forwarder.
+///
+/// Cheap to clone; all clones feed the same forwarder task.
+#[derive(Debug, Clone)]
+pub struct HecSink {
+    tx: mpsc::Sender<TimedEvent>,
+}
+
+impl HecSink {
+    /// Queue an event for forwarding without waiting.
+    ///
+    /// Drops the event with a warning when the buffer is full or the
+    /// forwarder has stopped.
+    pub fn send(&self, event: PlatformEvent) {
+        let timed = TimedEvent {
+            at: Utc::now(),
+            event,
+        };
+        if let Err(e) = self.tx.try_send(timed) {
+            tracing::warn!(error = %e, "Dropping Splunk HEC event");
+        }
+    }
+}
+
+/// Queue an event on an optional sink; a no-op when forwarding is disabled.
+pub fn emit(sink: Option<&HecSink>, event: PlatformEvent) {
+    if let Some(sink) = sink {
+        sink.send(event);
+    }
+}
+
+/// Start the background forwarder and return a sink feeding it.
+///
+/// Must be called from within a Tokio runtime.
+pub fn spawn(settings: HecSettings) -> HecSink {
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+    tokio::spawn(run_forwarder(settings, rx));
+    HecSink { tx }
+}
+
+/// Drain the channel, flushing whenever a batch fills or the interval elapses.
+async fn run_forwarder(settings: HecSettings, mut rx: mpsc::Receiver<TimedEvent>) {
+    let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to build Splunk HEC client; forwarding disabled");
+            return;
+        }
+    };
+
+    let mut batch: Vec<TimedEvent> = Vec::with_capacity(settings.batch_size);
+    let mut ticker = tokio::time::interval(settings.flush_interval);
+
+    loop {
+        tokio::select! {
+            received = rx.recv() => match received {
+                Some(event) => {
+                    batch.push(event);
+                    if batch.len() >= settings.batch_size {
+                        flush(&client, &settings, &mut batch).await;
+                    }
+                }
+                None => {
+                    flush(&client, &settings, &mut batch).await;
+                    return;
+                }
+            },
+            _ = ticker.tick() => {
+                flush(&client, &settings, &mut batch).await;
+            }
+        }
+    }
+}
+
+/// POST the current batch to the collector and clear it.
+async fn flush(client: &reqwest::Client, settings: &HecSettings, batch: &mut Vec<TimedEvent>) {
+    if batch.is_empty() {
+        return;
+    }
+
+    let body = encode_batch(batch, settings.index.as_deref());
+    let count = batch.len();
+    batch.clear();
+
+    let result = client
+        .post(&settings.url)
+        .header(reqwest::header::AUTHORIZATION, format!("Splunk {}", settings.token))
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await;
+
+    match result {
+        Ok(resp) if resp.status().is_success() => {
+            tracing::debug!(events = count, "Forwarded events to Splunk HEC");
+        }
+        Ok(resp) => {
+            tracing::warn!(status = %resp.status(), events = count, "Splunk HEC rejected batch");
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, events = count, "Splunk HEC request failed");
+        }
+    }
+}
+
+/// Encode events as concatenated HEC envelopes, the collector's batch format.
+fn encode_batch(events: &[TimedEvent], index: Option<&str>) -> String {
+    let mut body = String::new();
+    for timed in events {
+        let envelope = HecEnvelope {
+            time: timed.at.timestamp_millis() as f64 / 1000.0,
+            source: HEC_SOURCE,
+            sourcetype: timed.event.sourcetype(),
+            index,
+            event: &timed.event,
+        };
+        match serde_json::to_string(&envelope) {
+            Ok(json) => {
+                body.push_str(&json);
+                body.push('\n');
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to encode Splunk HEC event"),
+        }
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_event() -> PlatformEvent {
+        PlatformEvent::StatusChanged {
+            finding_id: Uuid::nil(),
+            previous_status: FindingStatus::New,
+            new_status: FindingStatus::Confirmed,
+            actor_name: "analyst1".to_string(),
+            justification: None,
+        }
+    }
+
+    #[test]
+    fn event_serializes_with_type_tag() {
+        let json = serde_json::to_value(status_event()).unwrap();
+        assert_eq!(json["event_type"], "status_changed");
+        assert_eq!(json["previous_status"], "New");
+        assert_eq!(json["new_status"], "Confirmed");
+    }
+
+    #[test]
+    fn sourcetype_per_event_kind() {
+        assert_eq!(status_event().sourcetype(), "synapsec:status_change");
+        let created = PlatformEvent::FindingCreated {
+            finding_id: Uuid::nil(),
+            application_id: None,
+            source_tool: "sonarqube".to_string(),
+            title: "SQL Injection".to_string(),
+            normalized_severity: SeverityLevel::High,
+            fingerprint: "abc".to_string(),
+        };
+        assert_eq!(created.sourcetype(), "synapsec:finding");
+    }
+
+    #[test]
+    fn batch_is_one_envelope_per_line() {
+        let at = DateTime::from_timestamp(1_700_000_000, 500_000_000).unwrap();
+        let events = vec![
+            TimedEvent { at, event: status_event() },
+            TimedEvent { at, event: status_event() },
+        ];
+        let body = encode_batch(&events, Some("appsec"));
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["time"], 1_700_000_000.5);
+        assert_eq!(first["source"], "synapsec");
+        assert_eq!(first["index"], "appsec");
+        assert_eq!(first["event"]["event_type"], "status_changed");
+    }
+
+    #[test]
+    fn batch_omits_index_when_unset() {
+        let events = vec![TimedEvent { at: Utc::now(), event: status_event() }];
+        let body = encode_batch(&events, None);
+        let parsed: serde_json::Value = serde_json::from_str(body.trim()).unwrap();
+        assert!(parsed.get("index").is_none());
+    }
+}
@@ -0,0 +1,304 @@
+//! Tag management: CRUD, rename, merge, usage counts, and autocomplete.
+//!
+//! Findings keep their tags as a JSONB array of names. The `tags` table
+//! holds each name's color and description, and a database trigger registers
+//! names that findings pick up elsewhere (bulk tagging, edits, ingestion).
+//! Renaming, merging, and deleting a tag rewrite the arrays of every finding
+//! carrying it in the same transaction, so both sides stay in sync.
+
+use serde::Deserialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::tag::{CreateTag, Tag, TagWithUsage, UpdateTag};
+use crate::services::search::escape_like;
+
+/// Longest tag name, in characters.
+const MAX_NAME_LEN: usize = 100;
+
+/// Suggestions returned by autocomplete when no limit is given.
+const DEFAULT_AUTOCOMPLETE_LIMIT: i64 = 10;
+
+/// Upper bound on autocomplete suggestions.
+const MAX_AUTOCOMPLETE_LIMIT: i64 = 50;
+
+/// Tag columns plus the number of findings carrying the tag.
+const SELECT_WITH_USAGE: &str = r#"
+    SELECT t.*,
+           (SELECT COUNT(*) FROM findings f WHERE f.tags ? t.name) AS usage_count
+    FROM tags t
+"#;
+
+/// Query parameters for tag autocomplete.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AutocompleteParams {
+    /// Name prefix, matched case-insensitively; empty lists the most used tags.
+    #[serde(default)]
+    pub q: String,
+    /// Maximum suggestions (1 to 50, default 10).
+    pub limit: Option<i64>,
+}
+
+// ---------------------------------------------------------------------------
+// Validation
+// ---------------------------------------------------------------------------
+
+/// Trim a tag name and check it is non-empty, printable, and within the limit.
+fn normalize_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Tag name is required".to_string()));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(AppError::Validation(
+            "Tag name cannot contain control characters".to_string(),
+        ));
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(AppError::Validation(format!(
+            "Tag name cannot exceed {MAX_NAME_LEN} characters"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Check a color is `#rrggbb` and return it lowercased.
+fn normalize_color(color: &str) -> Result<String, AppError> {
+    let hex = color
+        .strip_prefix('#')
+        .filter(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+    match hex {
+        Some(_) => Ok(color.to_ascii_lowercase()),
+        None => Err(AppError::Validation(format!(
+            "Invalid tag color '{color}'; expected #rrggbb"
+        ))),
+    }
+}
+
+/// Deduplicate merge sources and reject merging a tag into itself.
+fn merge_sources(target_id: Uuid, source_ids: &[Uuid]) -> Result<Vec<Uuid>, AppError> {
+    if source_ids.contains(&target_id) {
+        return Err(AppError::Validation(
+            "A tag cannot be merged into itself".to_string(),
+        ));
+    }
+    let mut sources = source_ids.to_vec();
+    sources.sort();
+    sources.dedup();
+    if sources.is_empty() {
+        return Err(AppError::Validation(
+            "At least one source tag is required".to_string(),
+        ));
+    }
+    Ok(sources)
+}
+
+// ---------------------------------------------------------------------------
+// Finding sync
+// ---------------------------------------------------------------------------
+
+/// Replace `names` in the tags of every finding carrying one of them with
+/// `replacement`, or drop them when `replacement` is `None`. Each finding
+/// keeps its tag order and ends up with no duplicates. Returns the number
+/// of findings changed.
+async fn rewrite_finding_tags(
+    tx: &mut Transaction<'_, Postgres>,
+    names: &[String],
+    replacement: Option<&str>,
+) -> Result<u64, AppError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE findings f
+        SET tags = (
+            SELECT COALESCE(jsonb_agg(to_jsonb(kept.name) ORDER BY kept.pos), '[]'::JSONB)
+            FROM (
+                SELECT DISTINCT ON (mapped.name) mapped.name, mapped.pos
+                FROM (
+                    SELECT CASE WHEN e.name = ANY($1) THEN $2 ELSE e.name END AS name, e.pos
+                    FROM jsonb_array_elements_text(f.tags) WITH ORDINALITY AS e(name, pos)
+                ) mapped
+                WHERE mapped.name IS NOT NULL
+                ORDER BY mapped.name, mapped.pos
+            ) kept
+        )
+        WHERE f.tags ?| $1
+        "#,
+    )
+    .bind(names)
+    .bind(replacement)
+    .execute(&mut **tx)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Lock a tag row for the rest of the transaction.
+async fn lock(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<Tag, AppError> {
+    sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Tag {id} not found")))
+}
+
+// ---------------------------------------------------------------------------
+// CRUD
+// ---------------------------------------------------------------------------
+
+/// List all tags with usage counts, by name.
+pub async fn list(pool: &PgPool) -> Result<Vec<TagWithUsage>, AppError> {
+    let tags = sqlx::query_as::<_, TagWithUsage>(&format!("{SELECT_WITH_USAGE} ORDER BY t.name"))
+        .fetch_all(pool)
+        .await?;
+    Ok(tags)
+}
+
+/// Fetch a tag with its usage count.
+pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<TagWithUsage, AppError> {
+    sqlx::query_as::<_, TagWithUsage>(&format!("{SELECT_WITH_USAGE} WHERE t.id = $1"))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Tag {id} not found")))
+}
+
+/// Tags whose name starts with the query prefix (case-insensitive), most used first.
+pub async fn autocomplete(
+    pool: &PgPool,
+    params: &AutocompleteParams,
+) -> Result<Vec<TagWithUsage>, AppError> {
+    let limit = params.limit.unwrap_or(DEFAULT_AUTOCOMPLETE_LIMIT);
+    let tags = sqlx::query_as::<_, TagWithUsage>(&format!(
+        "{SELECT_WITH_USAGE} WHERE t.name ILIKE $1 || '%' \
+         ORDER BY usage_count DESC, t.name LIMIT $2"
+    ))
+    .bind(escape_like(params.q.trim()))
+    .bind(limit.clamp(1, MAX_AUTOCOMPLETE_LIMIT))
+    .fetch_all(pool)
+    .await?;
+    Ok(tags)
+}
+
+/// Create a tag.
+pub async fn create(pool: &PgPool, input: &CreateTag) -> Result<TagWithUsage, AppError> {
+    let name = normalize_name(&input.name)?;
+    let color = input.color.as_deref().map(normalize_color).transpose()?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO tags (name, color, description) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(&name)
+    .bind(&color)
+    .bind(&input.description)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| AppError::name_conflict(e, "tag", &name))?;
+
+    find_by_id(pool, id).await
+}
+
+/// Update a tag; omitted fields keep their values. A new name replaces the
+/// old one on every finding carrying the tag.
+pub async fn update(pool: &PgPool, id: Uuid, input: &UpdateTag) -> Result<TagWithUsage, AppError> {
+    let name = input.name.as_deref().map(normalize_name).transpose()?;
+    let color = input.color.as_deref().map(normalize_color).transpose()?;
+
+    let mut tx = pool.begin().await?;
+    let existing = lock(&mut tx, id).await?;
+    let name = name.unwrap_or_else(|| existing.name.clone());
+
+    sqlx::query("UPDATE tags SET name = $1, color = $2, description = $3 WHERE id = $4")
+        .bind(&name)
+        .bind(color.as_ref().or(existing.color.as_ref()))
+        .bind(input.description.as_ref().or(existing.description.as_ref()))
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::name_conflict(e, "tag", &name))?;
+
+    if name != existing.name {
+        let renamed =
+            rewrite_finding_tags(&mut tx, std::slice::from_ref(&existing.name), Some(&name))
+                .await?;
+        tracing::info!(from = %existing.name, to = %name, findings = renamed, "Renamed tag");
+    }
+    tx.commit().await?;
+
+    find_by_id(pool, id).await
+}
+
+/// Fold the source tags into the target: findings carrying a source tag get
+/// the target instead, and the source tags are deleted.
+pub async fn merge(
+    pool: &PgPool,
+    target_id: Uuid,
+    source_ids: &[Uuid],
+) -> Result<TagWithUsage, AppError> {
+    let source_ids = merge_sources(target_id, source_ids)?;
+
+    let mut tx = pool.begin().await?;
+    let target = lock(&mut tx, target_id).await?;
+    let mut source_names = Vec::with_capacity(source_ids.len());
+    for id in &source_ids {
+        source_names.push(lock(&mut tx, *id).await?.name);
+    }
+
+    let merged = rewrite_finding_tags(&mut tx, &source_names, Some(&target.name)).await?;
+    sqlx::query("DELETE FROM tags WHERE id = ANY($1)")
+        .bind(&source_ids)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    tracing::info!(into = %target.name, sources = ?source_names, findings = merged, "Merged tags");
+    find_by_id(pool, target_id).await
+}
+
+/// Delete a tag and remove it from every finding carrying it.
+pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    let tag = lock(&mut tx, id).await?;
+    rewrite_finding_tags(&mut tx, &[tag.name], None).await?;
+    sqlx::query("DELETE FROM tags WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_trimmed_and_validated() {
+        assert_eq!(normalize_name("  pci-scope ").unwrap(), "pci-scope");
+        assert!(normalize_name("   ").is_err());
+        assert!(normalize_name("line\nbreak").is_err());
+        assert!(normalize_name(&"x".repeat(MAX_NAME_LEN)).is_ok());
+        assert!(normalize_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn colors_must_be_hex_triplets() {
+        assert_eq!(normalize_color("#FF8800").unwrap(), "#ff8800");
+        assert!(normalize_color("FF8800").is_err());
+        assert!(normalize_color("#f80").is_err());
+        assert!(normalize_color("#gg8800").is_err());
+    }
+
+    #[test]
+    fn merge_sources_are_deduplicated_and_exclude_the_target() {
+        let target = Uuid::new_v4();
+        let source = Uuid::new_v4();
+        assert_eq!(
+            merge_sources(target, &[source, source]).unwrap(),
+            vec![source]
+        );
+        assert!(merge_sources(target, &[source, target]).is_err());
+        assert!(merge_sources(target, &[]).is_err());
+    }
+}
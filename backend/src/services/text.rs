@@ -0,0 +1,17 @@
+//! Plain text helpers.
+
+/// The first `max_chars` characters of `value`, cut on a character boundary.
+pub fn truncate(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_counts_characters_not_bytes() {
+        assert_eq!(truncate("caffè latte", 5), "caffè");
+        assert_eq!(truncate("short", 10), "short");
+    }
+}
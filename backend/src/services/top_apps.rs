@@ -0,0 +1,216 @@
+//! Top risky applications ranked by aggregate composite risk of open
+//! findings, with changes against the start of the period.
+//!
+//! The previous figures reconstruct which findings were open at the period
+//! start using the executive report rule, scored with their current
+//! composite risk.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::services::executive_report::ReportPeriod;
+
+/// Applications returned when no limit is given.
+const DEFAULT_LIMIT: i64 = 10;
+
+/// Upper bound on the limit.
+const MAX_LIMIT: i64 = 50;
+
+/// Query parameters for the top applications endpoint.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TopAppsParams {
+    /// Applications to return (default 10, max 50).
+    pub limit: Option<i64>,
+    /// Comparison window ending now (defaults to `quarter`).
+    #[serde(default)]
+    pub period: ReportPeriod,
+}
+
+impl TopAppsParams {
+    fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+}
+
+/// One ranked application.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TopApp {
+    pub id: Uuid,
+    pub app_name: String,
+    pub app_code: String,
+    pub business_unit: Option<String>,
+    pub rank: i64,
+    /// Rank at the period start; absent if the app had no open findings then.
+    pub previous_rank: Option<i64>,
+    /// Places gained since the period start (negative when the app dropped).
+    pub rank_change: Option<i64>,
+    pub risk_score: f64,
+    pub previous_risk_score: f64,
+    pub risk_score_delta: f64,
+    pub open_findings: i64,
+    pub previous_open_findings: i64,
+    pub open_findings_delta: i64,
+    pub critical_count: i64,
+    pub high_count: i64,
+}
+
+/// Ranking for the period.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TopApps {
+    pub period: ReportPeriod,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub apps: Vec<TopApp>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct TopAppRow {
+    id: Uuid,
+    app_name: String,
+    app_code: String,
+    business_unit: Option<String>,
+    rank: i64,
+    previous_rank: Option<i64>,
+    risk_score: f64,
+    previous_risk_score: f64,
+    open_findings: i64,
+    previous_open_findings: i64,
+    critical_count: i64,
+    high_count: i64,
+}
+
+/// Rank applications by current aggregate risk.
+pub async fn get_top_apps(pool: &PgPool, params: &TopAppsParams) -> Result<TopApps, AppError> {
+    let period_end = Utc::now();
+    let period_start = period_end - params.period.duration();
+
+    let rows = sqlx::query_as::<_, TopAppRow>(
+        r#"
+        WITH scores AS (
+            SELECT
+                a.id,
+                a.app_name,
+                a.app_code,
+                a.business_unit,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')) AS open_findings,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.normalized_severity = 'Critical') AS critical_count,
+                COUNT(f.id) FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') AND f.normalized_severity = 'High') AS high_count,
+                COALESCE(SUM(f.composite_risk_score::double precision)
+                    FILTER (WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')), 0) AS risk_score,
+                COUNT(f.id) FILTER (
+                    WHERE f.first_seen <= $1
+                      AND (f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') OR f.status_changed_at > $1)
+                ) AS previous_open_findings,
+                COALESCE(SUM(f.composite_risk_score::double precision) FILTER (
+                    WHERE f.first_seen <= $1
+                      AND (f.status NOT IN ('Closed', 'Invalidated', 'False_Positive') OR f.status_changed_at > $1)
+                ), 0) AS previous_risk_score
+            FROM applications a
+            INNER JOIN findings f ON f.application_id = a.id
+            GROUP BY a.id
+        ),
+        ranked AS (
+            SELECT
+                *,
+                RANK() OVER (ORDER BY risk_score DESC) AS rank,
+                CASE WHEN previous_open_findings > 0
+                    THEN RANK() OVER (ORDER BY previous_risk_score DESC)
+                END AS previous_rank
+            FROM scores
+        )
+        SELECT
+            id, app_name, app_code, business_unit, rank, previous_rank,
+            risk_score, previous_risk_score, open_findings, previous_open_findings,
+            critical_count, high_count
+        FROM ranked
+        WHERE open_findings > 0
+        ORDER BY rank, open_findings DESC, app_code
+        LIMIT $2
+        "#,
+    )
+    .bind(period_start)
+    .bind(params.limit())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(TopApps {
+        period: params.period,
+        period_start,
+        period_end,
+        apps: rows.into_iter().map(TopApp::from).collect(),
+    })
+}
+
+impl From<TopAppRow> for TopApp {
+    fn from(row: TopAppRow) -> Self {
+        Self {
+            id: row.id,
+            app_name: row.app_name,
+            app_code: row.app_code,
+            business_unit: row.business_unit,
+            rank: row.rank,
+            previous_rank: row.previous_rank,
+            rank_change: row.previous_rank.map(|previous| previous - row.rank),
+            risk_score: row.risk_score,
+            previous_risk_score: row.previous_risk_score,
+            risk_score_delta: row.risk_score - row.previous_risk_score,
+            open_findings: row.open_findings,
+            previous_open_findings: row.previous_open_findings,
+            open_findings_delta: row.open_findings - row.previous_open_findings,
+            critical_count: row.critical_count,
+            high_count: row.high_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(rank: i64, previous_rank: Option<i64>) -> TopAppRow {
+        TopAppRow {
+            id: Uuid::new_v4(),
+            app_name: "Payments".to_string(),
+            app_code: "PAY01".to_string(),
+            business_unit: None,
+            rank,
+            previous_rank,
+            risk_score: 120.5,
+            previous_risk_score: 100.0,
+            open_findings: 8,
+            previous_open_findings: 10,
+            critical_count: 1,
+            high_count: 3,
+        }
+    }
+
+    #[test]
+    fn computes_deltas_against_period_start() {
+        let app = TopApp::from(row(2, Some(5)));
+        assert_eq!(app.rank_change, Some(3));
+        assert_eq!(app.risk_score_delta, 20.5);
+        assert_eq!(app.open_findings_delta, -2);
+    }
+
+    #[test]
+    fn new_entry_has_no_rank_change() {
+        let app = TopApp::from(row(1, None));
+        assert_eq!(app.previous_rank, None);
+        assert_eq!(app.rank_change, None);
+    }
+
+    #[test]
+    fn limit_defaults_and_clamps() {
+        let mut params = TopAppsParams::default();
+        assert_eq!(params.limit(), DEFAULT_LIMIT);
+        params.limit = Some(500);
+        assert_eq!(params.limit(), MAX_LIMIT);
+        params.limit = Some(0);
+        assert_eq!(params.limit(), 1);
+    }
+}
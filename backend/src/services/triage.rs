@@ -0,0 +1,318 @@
+//! Analyst triage queue.
+//!
+//! New findings wait in the queue oldest first, with the context needed to
+//! pick what to look at: application criticality, risk score, and how many
+//! open findings of the same application look alike. An analyst claims a
+//! finding before triaging it so nobody else picks it up at the same time;
+//! claims lapse after [`CLAIM_MINUTES`] unless renewed by claiming again.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::middleware::auth::CurrentUser;
+use crate::models::application::AssetCriticality;
+use crate::models::finding::{FindingCategory, FindingStatus, SeverityLevel};
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::user::UserRole;
+
+/// How long a claim holds before another analyst can take the finding.
+pub const CLAIM_MINUTES: i32 = 30;
+
+/// Joins shared by the count and page queries of [`queue`]; only live
+/// claims count.
+const QUEUE_FROM: &str = r#"
+    FROM findings f
+    LEFT JOIN applications a ON a.id = f.application_id
+    LEFT JOIN triage_claims c ON c.finding_id = f.id AND c.expires_at > NOW()
+    LEFT JOIN users u ON u.id = c.claimed_by
+"#;
+
+/// Filters shared by the count and page queries of [`queue`].
+const QUEUE_CONDITIONS: &str = r#"
+    WHERE f.status = 'New' AND f.archived_at IS NULL
+      AND ($1::uuid IS NULL OR f.application_id = $1)
+      AND ($2::text IS NULL OR f.source_tool = $2)
+      AND ($3::severity_level IS NULL OR f.effective_severity = $3)
+      AND CASE $4
+              WHEN 'unclaimed' THEN c.finding_id IS NULL
+              WHEN 'mine' THEN c.claimed_by = $5
+              ELSE TRUE
+          END
+"#;
+
+/// Which findings of the queue to list by claim.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimFilter {
+    #[default]
+    All,
+    Unclaimed,
+    /// Findings claimed by the caller.
+    Mine,
+}
+
+impl ClaimFilter {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Unclaimed => "unclaimed",
+            Self::Mine => "mine",
+        }
+    }
+}
+
+/// Query parameters for the triage queue.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TriageQueueFilters {
+    pub application_id: Option<Uuid>,
+    pub source_tool: Option<String>,
+    /// Effective severity (the override when set).
+    pub severity: Option<SeverityLevel>,
+    /// Defaults to `all`.
+    pub claim: Option<ClaimFilter>,
+}
+
+/// A New finding awaiting triage.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TriageQueueItem {
+    pub finding_id: Uuid,
+    pub title: String,
+    pub source_tool: String,
+    pub finding_category: FindingCategory,
+    pub severity: SeverityLevel,
+    pub composite_risk_score: Option<f32>,
+    pub first_seen: DateTime<Utc>,
+    pub application_id: Option<Uuid>,
+    pub app_code: Option<String>,
+    pub asset_criticality: Option<AssetCriticality>,
+    /// Open findings of the same application with a similar title.
+    pub similar_count: i64,
+    /// Analyst holding a live claim on the finding.
+    pub claimed_by: Option<Uuid>,
+    pub claimed_by_name: Option<String>,
+    pub claim_expires_at: Option<DateTime<Utc>>,
+}
+
+/// An analyst's hold on a finding of the queue.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TriageClaim {
+    pub finding_id: Uuid,
+    pub claimed_by: Uuid,
+    pub claimed_by_name: String,
+    pub claimed_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// New findings awaiting triage, oldest first.
+pub async fn queue(
+    pool: &PgPool,
+    filters: &TriageQueueFilters,
+    pagination: &Pagination,
+    user_id: Uuid,
+) -> Result<PagedResult<TriageQueueItem>, AppError> {
+    let claim = filters.claim.unwrap_or_default().as_str();
+
+    let total =
+        sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) {QUEUE_FROM} {QUEUE_CONDITIONS}"))
+            .bind(filters.application_id)
+            .bind(&filters.source_tool)
+            .bind(&filters.severity)
+            .bind(claim)
+            .bind(user_id)
+            .fetch_one(pool)
+            .await?;
+
+    let items = sqlx::query_as::<_, TriageQueueItem>(&format!(
+        r#"
+        SELECT f.id AS finding_id, f.title, f.source_tool, f.finding_category,
+               f.effective_severity AS severity, f.composite_risk_score, f.first_seen,
+               f.application_id, a.app_code, a.criticality AS asset_criticality,
+               (
+                   SELECT COUNT(*)
+                   FROM findings s
+                   WHERE s.application_id = f.application_id
+                     AND s.id <> f.id
+                     AND s.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+                     AND s.archived_at IS NULL
+                     AND s.title % f.title
+               ) AS similar_count,
+               c.claimed_by, u.username AS claimed_by_name, c.expires_at AS claim_expires_at
+        {QUEUE_FROM} {QUEUE_CONDITIONS}
+        ORDER BY f.first_seen, f.id
+        LIMIT $6 OFFSET $7
+        "#
+    ))
+    .bind(filters.application_id)
+    .bind(&filters.source_tool)
+    .bind(&filters.severity)
+    .bind(claim)
+    .bind(user_id)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(items, total, pagination))
+}
+
+/// Claim a New finding for triage, or renew the caller's own claim.
+///
+/// Fails with a conflict while another analyst holds a live claim.
+pub async fn claim(
+    pool: &PgPool,
+    finding_id: Uuid,
+    user_id: Uuid,
+) -> Result<TriageClaim, AppError> {
+    let claimed = sqlx::query_as::<_, TriageClaim>(
+        r#"
+        WITH c AS (
+            INSERT INTO triage_claims (finding_id, claimed_by, expires_at)
+            SELECT f.id, $2, NOW() + make_interval(mins => $3)
+            FROM findings f
+            WHERE f.id = $1 AND f.status = 'New' AND f.archived_at IS NULL
+            ON CONFLICT (finding_id) DO UPDATE
+            SET claimed_by = EXCLUDED.claimed_by,
+                claimed_at = CASE
+                    WHEN triage_claims.claimed_by = EXCLUDED.claimed_by
+                     AND triage_claims.expires_at > NOW()
+                    THEN triage_claims.claimed_at
+                    ELSE NOW()
+                END,
+                expires_at = EXCLUDED.expires_at
+            WHERE triage_claims.claimed_by = EXCLUDED.claimed_by
+               OR triage_claims.expires_at <= NOW()
+            RETURNING *
+        )
+        SELECT c.finding_id, c.claimed_by, u.username AS claimed_by_name,
+               c.claimed_at, c.expires_at
+        FROM c
+        JOIN users u ON u.id = c.claimed_by
+        "#,
+    )
+    .bind(finding_id)
+    .bind(user_id)
+    .bind(CLAIM_MINUTES)
+    .fetch_optional(pool)
+    .await?;
+    if let Some(claimed) = claimed {
+        return Ok(claimed);
+    }
+
+    // Nothing claimed: the finding is gone, already triaged, or held by
+    // someone else
+    let status = finding_status(pool, finding_id).await?;
+    if status != FindingStatus::New {
+        return Err(AppError::Conflict(format!(
+            "Finding {finding_id} is no longer awaiting triage"
+        )));
+    }
+    match live_claim(pool, finding_id).await? {
+        Some(held) => Err(AppError::Conflict(format!(
+            "Finding {finding_id} is claimed by {} until {}",
+            held.claimed_by_name, held.expires_at
+        ))),
+        None => Err(AppError::Conflict(format!(
+            "Finding {finding_id} is archived"
+        ))),
+    }
+}
+
+/// Release a claim. Analysts release their own; managers release anyone's.
+pub async fn release(pool: &PgPool, finding_id: Uuid, user: &CurrentUser) -> Result<(), AppError> {
+    finding_status(pool, finding_id).await?;
+    let Some(held) = live_claim(pool, finding_id).await? else {
+        return Err(AppError::NotFound(format!(
+            "Finding {finding_id} has no triage claim"
+        )));
+    };
+    if !can_release(&held, user) {
+        return Err(AppError::Forbidden(format!(
+            "Finding {finding_id} is claimed by {}",
+            held.claimed_by_name
+        )));
+    }
+
+    sqlx::query("DELETE FROM triage_claims WHERE finding_id = $1")
+        .bind(finding_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+fn can_release(held: &TriageClaim, user: &CurrentUser) -> bool {
+    held.claimed_by == user.id
+        || matches!(user.role, UserRole::PlatformAdmin | UserRole::AppSecManager)
+}
+
+async fn finding_status(pool: &PgPool, finding_id: Uuid) -> Result<FindingStatus, AppError> {
+    sqlx::query_scalar::<_, FindingStatus>("SELECT status FROM findings WHERE id = $1")
+        .bind(finding_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Finding {finding_id} not found")))
+}
+
+async fn live_claim(pool: &PgPool, finding_id: Uuid) -> Result<Option<TriageClaim>, AppError> {
+    let held = sqlx::query_as::<_, TriageClaim>(
+        r#"
+        SELECT c.finding_id, c.claimed_by, u.username AS claimed_by_name,
+               c.claimed_at, c.expires_at
+        FROM triage_claims c
+        JOIN users u ON u.id = c.claimed_by
+        WHERE c.finding_id = $1 AND c.expires_at > NOW()
+        "#,
+    )
+    .bind(finding_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(held)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: Uuid, role: UserRole) -> CurrentUser {
+        CurrentUser {
+            id,
+            username: "analyst".to_string(),
+            role,
+        }
+    }
+
+    fn held_by(id: Uuid) -> TriageClaim {
+        TriageClaim {
+            finding_id: Uuid::new_v4(),
+            claimed_by: id,
+            claimed_by_name: "holder".to_string(),
+            claimed_at: Utc::now(),
+            expires_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn claims_are_released_by_holder_or_manager() {
+        let holder = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let claim = held_by(holder);
+
+        assert!(can_release(&claim, &user(holder, UserRole::AppSecAnalyst)));
+        assert!(!can_release(&claim, &user(other, UserRole::AppSecAnalyst)));
+        assert!(can_release(&claim, &user(other, UserRole::AppSecManager)));
+        assert!(can_release(&claim, &user(other, UserRole::PlatformAdmin)));
+    }
+
+    #[test]
+    fn claim_filter_parses_and_maps_to_sql_labels() {
+        let filter: ClaimFilter = serde_json::from_str("\"unclaimed\"").unwrap();
+        assert_eq!(filter, ClaimFilter::Unclaimed);
+        assert_eq!(ClaimFilter::default().as_str(), "all");
+        assert_eq!(ClaimFilter::Mine.as_str(), "mine");
+    }
+}
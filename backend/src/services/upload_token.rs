@@ -0,0 +1,337 @@
+//! Upload tokens for CI jobs.
+//!
+//! A token lets a pipeline upload scanner output without a user session, and
+//! only the output it was issued for: one parser type, optionally the source
+//! tool the file must report, and optionally a set of applications. The
+//! ingestion pipeline rejects an upload breaking any of these before writing
+//! anything (see [`UploadScope`]). Uploads run as the user who issued the
+//! token and are linked to the token in the ingestion log.
+
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::upload_token::{CreateUploadToken, IssuedUploadToken, UploadToken};
+use crate::services::ingestion::ParserType;
+
+/// Prefix of every token, so leaked tokens are easy to recognize.
+const TOKEN_PREFIX: &str = "sut_";
+
+/// Characters of the token kept for listings.
+const DISPLAY_LEN: usize = 12;
+
+/// What an authenticated upload token may upload.
+#[derive(Debug, Clone)]
+pub struct UploadScope {
+    pub token_id: Uuid,
+    /// The user uploads run as.
+    pub created_by: Uuid,
+    pub parser_type: ParserType,
+    pub source_tool: Option<String>,
+    /// Empty allows every application.
+    pub application_ids: Vec<Uuid>,
+}
+
+impl UploadScope {
+    /// Whether a file parsed by `parser_type`, reporting `source_tool`, may
+    /// be uploaded with the token.
+    pub fn check_source(
+        &self,
+        parser_type: &ParserType,
+        source_tool: &str,
+    ) -> Result<(), AppError> {
+        if *parser_type != self.parser_type {
+            return Err(AppError::Forbidden(format!(
+                "Upload token is limited to {} uploads",
+                self.parser_type
+            )));
+        }
+        match &self.source_tool {
+            Some(tool) if !tool.eq_ignore_ascii_case(source_tool.trim()) => {
+                Err(AppError::Forbidden(format!(
+                    "Upload token is limited to {tool} results, the file reports {source_tool}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.trim().as_bytes()))
+}
+
+fn new_token() -> String {
+    format!(
+        "{TOKEN_PREFIX}{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+fn parse_parser_type(value: &str) -> Result<ParserType, AppError> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| AppError::Internal(format!("Unknown parser type '{value}' on upload token")))
+}
+
+#[derive(Debug, FromRow)]
+struct ScopeRow {
+    id: Uuid,
+    created_by: Uuid,
+    parser_type: String,
+    source_tool: Option<String>,
+    application_ids: Vec<Uuid>,
+}
+
+/// The scope of an active token, recording its use. Revoked and expired
+/// tokens, and tokens of deactivated users, are rejected.
+pub async fn authenticate(pool: &PgPool, token: &str) -> Result<UploadScope, AppError> {
+    let row = sqlx::query_as::<_, ScopeRow>(
+        r#"
+        UPDATE upload_tokens t SET last_used_at = NOW()
+        FROM users u
+        WHERE t.token_hash = $1
+          AND t.revoked_at IS NULL
+          AND (t.expires_at IS NULL OR t.expires_at > NOW())
+          AND u.id = t.created_by
+          AND u.is_active
+        RETURNING t.id, t.created_by, t.parser_type, t.source_tool, t.application_ids
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::Unauthorized)?;
+
+    Ok(UploadScope {
+        token_id: row.id,
+        created_by: row.created_by,
+        parser_type: parse_parser_type(&row.parser_type)?,
+        source_tool: row.source_tool,
+        application_ids: row.application_ids,
+    })
+}
+
+/// Id of the active token matching `token`, without recording a use.
+pub async fn active_token_id(pool: &PgPool, token: &str) -> Result<Option<Uuid>, AppError> {
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        SELECT t.id
+        FROM upload_tokens t
+        JOIN users u ON u.id = t.created_by
+        WHERE t.token_hash = $1
+          AND t.revoked_at IS NULL
+          AND (t.expires_at IS NULL OR t.expires_at > NOW())
+          AND u.is_active
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(pool)
+    .await?;
+    Ok(id)
+}
+
+/// An active token and the user its uploads run as.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct TokenUser {
+    pub token_id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+}
+
+/// The active token matching `token` and its issuing user, without
+/// recording a use.
+pub async fn active_token_user(pool: &PgPool, token: &str) -> Result<Option<TokenUser>, AppError> {
+    let user = sqlx::query_as::<_, TokenUser>(
+        r#"
+        SELECT t.id AS token_id, u.id AS user_id, u.username
+        FROM upload_tokens t
+        JOIN users u ON u.id = t.created_by
+        WHERE t.token_hash = $1
+          AND t.revoked_at IS NULL
+          AND (t.expires_at IS NULL OR t.expires_at > NOW())
+          AND u.is_active
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(pool)
+    .await?;
+    Ok(user)
+}
+
+/// All tokens, active first, newest first.
+pub async fn list(pool: &PgPool) -> Result<Vec<UploadToken>, AppError> {
+    let tokens = sqlx::query_as::<_, UploadToken>(
+        r#"
+        SELECT id, name, token_prefix, parser_type, source_tool, application_ids, created_by,
+               created_at, expires_at, last_used_at, revoked_at, revoked_by
+        FROM upload_tokens
+        ORDER BY revoked_at IS NOT NULL, created_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(tokens)
+}
+
+/// Issue a token. The returned secret is not stored and cannot be shown again.
+pub async fn create(
+    pool: &PgPool,
+    input: &CreateUploadToken,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<IssuedUploadToken, AppError> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("name must not be blank".to_string()));
+    }
+    if input.expires_at.is_some_and(|at| at <= chrono::Utc::now()) {
+        return Err(AppError::Validation(
+            "expires_at must be in the future".to_string(),
+        ));
+    }
+    let mut application_ids = input.application_ids.clone();
+    application_ids.sort();
+    application_ids.dedup();
+    let known =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM applications WHERE id = ANY($1)")
+            .bind(&application_ids)
+            .fetch_one(pool)
+            .await?;
+    if known as usize != application_ids.len() {
+        return Err(AppError::NotFound(
+            "One or more applications not found".to_string(),
+        ));
+    }
+
+    let secret = new_token();
+    let mut tx = pool.begin().await?;
+    let token = sqlx::query_as::<_, UploadToken>(
+        r#"
+        INSERT INTO upload_tokens
+            (name, token_hash, token_prefix, parser_type, source_tool, application_ids,
+             created_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id, name, token_prefix, parser_type, source_tool, application_ids, created_by,
+                  created_at, expires_at, last_used_at, revoked_at, revoked_by
+        "#,
+    )
+    .bind(name)
+    .bind(hash_token(&secret))
+    .bind(&secret[..DISPLAY_LEN])
+    .bind(input.parser_type.to_string())
+    .bind(input.source_tool.as_deref().map(str::trim))
+    .bind(&application_ids)
+    .bind(actor_id)
+    .bind(input.expires_at)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('upload_token', $1, 'upload_token_created', $2, $3, $4)
+        "#,
+    )
+    .bind(token.id)
+    .bind(actor_id)
+    .bind(actor_name)
+    .bind(serde_json::json!({
+        "name": token.name,
+        "parser_type": token.parser_type,
+        "source_tool": token.source_tool,
+        "application_ids": token.application_ids,
+    }))
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(IssuedUploadToken { token, secret })
+}
+
+/// Revoke an active token; uploads with it are rejected from then on.
+pub async fn revoke(
+    pool: &PgPool,
+    id: Uuid,
+    actor_id: Uuid,
+    actor_name: &str,
+) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    let revoked = sqlx::query(
+        "UPDATE upload_tokens SET revoked_at = NOW(), revoked_by = $2 \
+         WHERE id = $1 AND revoked_at IS NULL",
+    )
+    .bind(id)
+    .bind(actor_id)
+    .execute(&mut *tx)
+    .await?;
+    if revoked.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Active upload token {id} not found"
+        )));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_name, details)
+        VALUES ('upload_token', $1, 'upload_token_revoked', $2, $3, '{}'::jsonb)
+        "#,
+    )
+    .bind(id)
+    .bind(actor_id)
+    .bind(actor_name)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(source_tool: Option<&str>) -> UploadScope {
+        UploadScope {
+            token_id: Uuid::nil(),
+            created_by: Uuid::nil(),
+            parser_type: ParserType::Sonarqube,
+            source_tool: source_tool.map(str::to_string),
+            application_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn tokens_are_prefixed_and_hashed() {
+        let token = new_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + 64);
+        assert_eq!(hash_token(&token).len(), 64);
+        assert_eq!(hash_token(&format!(" {token} ")), hash_token(&token));
+        assert_ne!(token, new_token());
+    }
+
+    #[test]
+    fn source_must_match_parser_and_tool() {
+        assert!(scope(None)
+            .check_source(&ParserType::Sonarqube, "SonarQube")
+            .is_ok());
+        assert!(matches!(
+            scope(None).check_source(&ParserType::TenableWas, "Tenable WAS"),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(scope(Some("sonarqube"))
+            .check_source(&ParserType::Sonarqube, "SonarQube")
+            .is_ok());
+        assert!(matches!(
+            scope(Some("Semgrep")).check_source(&ParserType::Sonarqube, "SonarQube"),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn stored_parser_types_round_trip() {
+        let stored = ParserType::JfrogXray.to_string();
+        assert_eq!(parse_parser_type(&stored).unwrap(), ParserType::JfrogXray);
+    }
+}
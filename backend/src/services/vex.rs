@@ -0,0 +1,609 @@
+//! VEX (Vulnerability Exploitability eXchange) import and export for SCA findings.
+//!
+//! Imports OpenVEX and CycloneDX VEX documents, applying `not_affected` and
+//! `affected` statements to an application's SCA findings as
+//! `False_Positive` / `Confirmed` transitions. Exports the application's
+//! current SCA posture in either format for downstream consumers.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::FindingStatus;
+use crate::services::finding;
+use crate::services::json_value::str_field;
+use crate::services::splunk_hec::HecSink;
+
+/// OpenVEX JSON-LD context for documents we emit.
+const OPENVEX_CONTEXT: &str = "https://openvex.dev/ns/v0.2.0";
+
+/// Author recorded on exported VEX documents and history entries.
+const VEX_AUTHOR: &str = "SynApSec";
+
+// ---------------------------------------------------------------------------
+// DTOs
+// ---------------------------------------------------------------------------
+
+/// Exploitability status of a vulnerability for a product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VexStatus {
+    NotAffected,
+    Affected,
+    Fixed,
+    UnderInvestigation,
+}
+
+/// A single normalized VEX statement, independent of source format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VexStatement {
+    pub vulnerability_id: String,
+    /// Package URLs the statement applies to; empty means every product.
+    pub products: Vec<String>,
+    pub status: VexStatus,
+    pub justification: Option<String>,
+    pub impact_statement: Option<String>,
+}
+
+/// Supported VEX document formats for export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VexFormat {
+    #[default]
+    Openvex,
+    Cyclonedx,
+}
+
+/// Outcome of applying a VEX document to an application.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VexImportResult {
+    pub statements: usize,
+    pub matched_findings: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// Statements with a status that does not drive a transition.
+    pub ignored_statements: usize,
+    /// Vulnerability IDs that matched no SCA finding in the application.
+    pub unmatched: Vec<String>,
+}
+
+/// SCA finding row used for both import matching and export.
+#[derive(Debug, FromRow)]
+struct ScaPostureRow {
+    id: Uuid,
+    title: String,
+    status: FindingStatus,
+    cve_ids: Value,
+    package_name: String,
+    package_version: String,
+    package_type: Option<String>,
+    fixed_version: Option<String>,
+    last_justification: Option<String>,
+    updated_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// Import
+// ---------------------------------------------------------------------------
+
+/// Parse an OpenVEX or CycloneDX VEX document into normalized statements.
+pub fn parse_document(doc: &Value) -> Result<Vec<VexStatement>, AppError> {
+    if let Some(statements) = doc.get("statements").and_then(Value::as_array) {
+        return Ok(statements.iter().filter_map(parse_openvex_statement).collect());
+    }
+    if let Some(vulns) = doc.get("vulnerabilities").and_then(Value::as_array) {
+        return Ok(vulns.iter().filter_map(parse_cyclonedx_vulnerability).collect());
+    }
+    Err(AppError::Validation(
+        "Unrecognized VEX document: expected OpenVEX 'statements' or CycloneDX 'vulnerabilities'"
+            .to_string(),
+    ))
+}
+
+/// Parse one OpenVEX statement; returns `None` when required fields are missing.
+fn parse_openvex_statement(stmt: &Value) -> Option<VexStatement> {
+    let vuln = stmt.get("vulnerability")?;
+    let vulnerability_id = vuln
+        .as_str()
+        .or_else(|| vuln.get("name").and_then(Value::as_str))
+        .or_else(|| vuln.get("@id").and_then(Value::as_str))?
+        .to_string();
+
+    let status = match stmt.get("status")?.as_str()? {
+        "not_affected" => VexStatus::NotAffected,
+        "affected" => VexStatus::Affected,
+        "fixed" => VexStatus::Fixed,
+        "under_investigation" => VexStatus::UnderInvestigation,
+        _ => return None,
+    };
+
+    let products = stmt
+        .get("products")
+        .and_then(Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| {
+                    p.as_str()
+                        .or_else(|| p.get("@id").and_then(Value::as_str))
+                        .or_else(|| {
+                            p.get("identifiers")
+                                .and_then(|i| i.get("purl"))
+                                .and_then(Value::as_str)
+                        })
+                        .map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(VexStatement {
+        vulnerability_id,
+        products,
+        status,
+        justification: str_field(stmt, "justification"),
+        impact_statement: str_field(stmt, "impact_statement")
+            .or_else(|| str_field(stmt, "action_statement")),
+    })
+}
+
+/// Parse one CycloneDX vulnerability entry carrying an `analysis` block.
+fn parse_cyclonedx_vulnerability(vuln: &Value) -> Option<VexStatement> {
+    let vulnerability_id = vuln.get("id")?.as_str()?.to_string();
+    let analysis = vuln.get("analysis")?;
+
+    let status = match analysis.get("state")?.as_str()? {
+        "not_affected" | "false_positive" => VexStatus::NotAffected,
+        "exploitable" => VexStatus::Affected,
+        "resolved" | "resolved_with_pedigree" => VexStatus::Fixed,
+        "in_triage" => VexStatus::UnderInvestigation,
+        _ => return None,
+    };
+
+    let products = vuln
+        .get("affects")
+        .and_then(Value::as_array)
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|a| a.get("ref").and_then(Value::as_str).map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(VexStatement {
+        vulnerability_id,
+        products,
+        status,
+        justification: str_field(analysis, "justification"),
+        impact_statement: str_field(analysis, "detail"),
+    })
+}
+
+/// Apply VEX statements to an application's SCA findings.
+///
+/// `not_affected` moves matching findings to `False_Positive` and `affected`
+/// moves them to `Confirmed`; other statuses are counted but not applied.
+pub async fn import_for_application(
+    pool: &PgPool,
+    app_id: Uuid,
+    doc: &Value,
+    actor_id: Uuid,
+    actor_name: &str,
+    events: Option<&HecSink>,
+) -> Result<VexImportResult, AppError> {
+    let statements = parse_document(doc)?;
+    fetch_app_code(pool, app_id).await?;
+    let rows = fetch_sca_posture(pool, app_id).await?;
+
+    let mut matched_findings = 0usize;
+    let mut updated = 0usize;
+    let mut unchanged = 0usize;
+    let mut ignored_statements = 0usize;
+    let mut unmatched = Vec::new();
+
+    for stmt in &statements {
+        let target = match stmt.status {
+            VexStatus::NotAffected => FindingStatus::FalsePositive,
+            VexStatus::Affected => FindingStatus::Confirmed,
+            VexStatus::Fixed | VexStatus::UnderInvestigation => {
+                ignored_statements += 1;
+                continue;
+            }
+        };
+
+        let matching: Vec<&ScaPostureRow> = rows
+            .iter()
+            .filter(|row| statement_matches(stmt, row))
+            .collect();
+
+        if matching.is_empty() {
+            unmatched.push(stmt.vulnerability_id.clone());
+            continue;
+        }
+
+        let justification = statement_justification(stmt);
+        for row in matching {
+            matched_findings += 1;
+            if row.status == target {
+                unchanged += 1;
+                continue;
+            }
+            finding::update_status(
+                pool,
+                row.id,
+                &target,
+                Some(actor_id),
+                actor_name,
+                Some(&justification),
+                events,
+            )
+            .await?;
+            updated += 1;
+        }
+    }
+
+    Ok(VexImportResult {
+        statements: statements.len(),
+        matched_findings,
+        updated,
+        unchanged,
+        ignored_statements,
+        unmatched,
+    })
+}
+
+/// Check whether a statement applies to an SCA finding row.
+fn statement_matches(stmt: &VexStatement, row: &ScaPostureRow) -> bool {
+    let has_cve = row
+        .cve_ids
+        .as_array()
+        .is_some_and(|ids| ids.iter().any(|id| id.as_str() == Some(&stmt.vulnerability_id)));
+    if !has_cve {
+        return false;
+    }
+    if stmt.products.is_empty() {
+        return true;
+    }
+    stmt.products.iter().any(|purl| match parse_purl(purl) {
+        Some((name, version)) => {
+            name.eq_ignore_ascii_case(&row.package_name)
+                && version.map_or(true, |v| v == row.package_version)
+        }
+        None => false,
+    })
+}
+
+/// Compose the history justification recorded for a VEX-driven transition.
+fn statement_justification(stmt: &VexStatement) -> String {
+    let detail = match (&stmt.justification, &stmt.impact_statement) {
+        (Some(j), Some(i)) => format!("{j}: {i}"),
+        (Some(j), None) => j.clone(),
+        (None, Some(i)) => i.clone(),
+        (None, None) => "no justification provided".to_string(),
+    };
+    format!("VEX import ({}) — {detail}", stmt.vulnerability_id)
+}
+
+/// Extract `(name, version)` from a package URL such as `pkg:maven/org.x/lib@1.0`.
+fn parse_purl(purl: &str) -> Option<(&str, Option<&str>)> {
+    let rest = purl.strip_prefix("pkg:")?;
+    let rest = rest.split(['?', '#']).next()?;
+    let (path, version) = match rest.rsplit_once('@') {
+        Some((p, v)) => (p, Some(v)),
+        None => (rest, None),
+    };
+    let name = path.rsplit('/').next()?;
+    if name.is_empty() {
+        return None;
+    }
+    Some((name, version))
+}
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+/// Export the application's SCA posture as a VEX document.
+pub async fn export_for_application(
+    pool: &PgPool,
+    app_id: Uuid,
+    format: VexFormat,
+) -> Result<Value, AppError> {
+    let app_code = fetch_app_code(pool, app_id).await?;
+    let rows = fetch_sca_posture(pool, app_id).await?;
+    let now = Utc::now();
+
+    Ok(match format {
+        VexFormat::Openvex => build_openvex(&app_code, &rows, now),
+        VexFormat::Cyclonedx => build_cyclonedx(&app_code, &rows, now),
+    })
+}
+
+/// Map a finding status to its VEX status; `None` excludes the finding.
+fn vex_status_for(status: &FindingStatus) -> Option<VexStatus> {
+    match status {
+        FindingStatus::FalsePositive => Some(VexStatus::NotAffected),
+        FindingStatus::Mitigated | FindingStatus::Verified | FindingStatus::Closed => {
+            Some(VexStatus::Fixed)
+        }
+        FindingStatus::New | FindingStatus::FalsePositiveRequested => {
+            Some(VexStatus::UnderInvestigation)
+        }
+        FindingStatus::Confirmed
+        | FindingStatus::InRemediation
+        | FindingStatus::RiskAccepted
+        | FindingStatus::DeferredRemediation => Some(VexStatus::Affected),
+        FindingStatus::Invalidated => None,
+    }
+}
+
+/// Build a package URL for an SCA row.
+fn purl_for(row: &ScaPostureRow) -> String {
+    let pkg_type = row
+        .package_type
+        .as_deref()
+        .map(str::to_lowercase)
+        .unwrap_or_else(|| "generic".to_string());
+    format!("pkg:{pkg_type}/{}@{}", row.package_name, row.package_version)
+}
+
+/// Iterate over `(row, cve, vex_status)` triples for exportable findings.
+fn exportable<'a>(
+    rows: &'a [ScaPostureRow],
+) -> impl Iterator<Item = (&'a ScaPostureRow, &'a str, VexStatus)> + 'a {
+    rows.iter().flat_map(|row| {
+        let status = vex_status_for(&row.status);
+        row.cve_ids
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .filter_map(move |cve| status.map(|s| (row, cve, s)))
+    })
+}
+
+/// Render rows as an OpenVEX document.
+fn build_openvex(app_code: &str, rows: &[ScaPostureRow], now: DateTime<Utc>) -> Value {
+    let statements: Vec<Value> = exportable(rows)
+        .map(|(row, cve, status)| {
+            let mut stmt = json!({
+                "vulnerability": { "name": cve },
+                "products": [{ "@id": purl_for(row) }],
+                "status": status,
+                "timestamp": row.updated_at.to_rfc3339(),
+            });
+            match status {
+                VexStatus::NotAffected => {
+                    stmt["impact_statement"] = json!(row
+                        .last_justification
+                        .clone()
+                        .unwrap_or_else(|| "Triaged as false positive".to_string()));
+                }
+                VexStatus::Affected => {
+                    stmt["action_statement"] = json!(match &row.fixed_version {
+                        Some(v) => format!("Upgrade {} to {v}", row.package_name),
+                        None => "No fixed version available; remediation tracked".to_string(),
+                    });
+                }
+                VexStatus::Fixed | VexStatus::UnderInvestigation => {}
+            }
+            stmt
+        })
+        .collect();
+
+    json!({
+        "@context": OPENVEX_CONTEXT,
+        "@id": format!("urn:synapsec:vex:{app_code}:{}", now.timestamp()),
+        "author": VEX_AUTHOR,
+        "timestamp": now.to_rfc3339(),
+        "version": 1,
+        "statements": statements,
+    })
+}
+
+/// Render rows as a CycloneDX 1.5 VEX BOM.
+fn build_cyclonedx(app_code: &str, rows: &[ScaPostureRow], now: DateTime<Utc>) -> Value {
+    let vulnerabilities: Vec<Value> = exportable(rows)
+        .map(|(row, cve, status)| {
+            let state = match status {
+                VexStatus::NotAffected => "not_affected",
+                VexStatus::Affected => "exploitable",
+                VexStatus::Fixed => "resolved",
+                VexStatus::UnderInvestigation => "in_triage",
+            };
+            let mut analysis = json!({ "state": state });
+            if let (VexStatus::NotAffected, Some(j)) = (status, &row.last_justification) {
+                analysis["detail"] = json!(j);
+            }
+            json!({
+                "id": cve,
+                "description": row.title,
+                "analysis": analysis,
+                "affects": [{ "ref": purl_for(row) }],
+            })
+        })
+        .collect();
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": format!("urn:uuid:{}", Uuid::new_v4()),
+        "version": 1,
+        "metadata": {
+            "timestamp": now.to_rfc3339(),
+            "component": { "type": "application", "name": app_code },
+            "tools": [{ "name": VEX_AUTHOR }],
+        },
+        "vulnerabilities": vulnerabilities,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/// Look up an application's code, failing with `NotFound` when it does not exist.
+async fn fetch_app_code(pool: &PgPool, app_id: Uuid) -> Result<String, AppError> {
+    sqlx::query_scalar::<_, String>("SELECT app_code FROM applications WHERE id = $1")
+        .bind(app_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Application {app_id} not found")))
+}
+
+/// Load all SCA findings for an application with their latest justification.
+async fn fetch_sca_posture(pool: &PgPool, app_id: Uuid) -> Result<Vec<ScaPostureRow>, AppError> {
+    let rows = sqlx::query_as::<_, ScaPostureRow>(
+        r#"
+        SELECT
+            f.id, f.title, f.status, f.cve_ids,
+            fc.package_name, fc.package_version, fc.package_type, fc.fixed_version,
+            (
+                SELECT fh.justification
+                FROM finding_history fh
+                WHERE fh.finding_id = f.id
+                  AND fh.action = 'status_change'
+                  AND fh.justification IS NOT NULL
+                ORDER BY fh.created_at DESC
+                LIMIT 1
+            ) AS last_justification,
+            f.updated_at
+        FROM findings f
+        JOIN finding_sca fc ON fc.finding_id = f.id
+        WHERE f.application_id = $1
+        ORDER BY fc.package_name, fc.package_version
+        "#,
+    )
+    .bind(app_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(status: FindingStatus, cves: &[&str]) -> ScaPostureRow {
+        ScaPostureRow {
+            id: Uuid::nil(),
+            title: "Path traversal".to_string(),
+            status,
+            cve_ids: json!(cves),
+            package_name: "spring-webmvc".to_string(),
+            package_version: "6.1.6".to_string(),
+            package_type: Some("maven".to_string()),
+            fixed_version: Some("6.1.12".to_string()),
+            last_justification: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn parses_openvex_statements() {
+        let doc = json!({
+            "@context": OPENVEX_CONTEXT,
+            "statements": [{
+                "vulnerability": { "name": "CVE-2024-38816" },
+                "products": [{ "@id": "pkg:maven/org.springframework/spring-webmvc@6.1.6" }],
+                "status": "not_affected",
+                "justification": "vulnerable_code_not_in_execute_path"
+            }]
+        });
+        let stmts = parse_document(&doc).unwrap();
+        assert_eq!(stmts.len(), 1);
+        assert_eq!(stmts[0].vulnerability_id, "CVE-2024-38816");
+        assert_eq!(stmts[0].status, VexStatus::NotAffected);
+        assert_eq!(stmts[0].products.len(), 1);
+    }
+
+    #[test]
+    fn parses_cyclonedx_analysis_states() {
+        let doc = json!({
+            "bomFormat": "CycloneDX",
+            "vulnerabilities": [
+                { "id": "CVE-1", "analysis": { "state": "exploitable" } },
+                { "id": "CVE-2", "analysis": { "state": "false_positive", "detail": "test only" } },
+                { "id": "CVE-3" }
+            ]
+        });
+        let stmts = parse_document(&doc).unwrap();
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0].status, VexStatus::Affected);
+        assert_eq!(stmts[1].status, VexStatus::NotAffected);
+        assert_eq!(stmts[1].impact_statement.as_deref(), Some("test only"));
+    }
+
+    #[test]
+    fn rejects_unknown_document() {
+        assert!(parse_document(&json!({ "foo": [] })).is_err());
+    }
+
+    #[test]
+    fn purl_parsing() {
+        assert_eq!(
+            parse_purl("pkg:maven/org.springframework/spring-webmvc@6.1.6?type=jar"),
+            Some(("spring-webmvc", Some("6.1.6")))
+        );
+        assert_eq!(parse_purl("pkg:npm/lodash"), Some(("lodash", None)));
+        assert_eq!(parse_purl("gav://org.x:lib:1.0"), None);
+    }
+
+    #[test]
+    fn statement_matches_by_cve_and_purl() {
+        let r = row(FindingStatus::New, &["CVE-2024-38816"]);
+        let mut stmt = VexStatement {
+            vulnerability_id: "CVE-2024-38816".to_string(),
+            products: vec!["pkg:maven/org.springframework/spring-webmvc@6.1.6".to_string()],
+            status: VexStatus::NotAffected,
+            justification: None,
+            impact_statement: None,
+        };
+        assert!(statement_matches(&stmt, &r));
+
+        stmt.products = vec!["pkg:maven/org.springframework/spring-webmvc@5.0.0".to_string()];
+        assert!(!statement_matches(&stmt, &r));
+
+        stmt.products.clear();
+        assert!(statement_matches(&stmt, &r));
+
+        stmt.vulnerability_id = "CVE-0000-0000".to_string();
+        assert!(!statement_matches(&stmt, &r));
+    }
+
+    #[test]
+    fn status_mapping_for_export() {
+        assert_eq!(vex_status_for(&FindingStatus::FalsePositive), Some(VexStatus::NotAffected));
+        assert_eq!(vex_status_for(&FindingStatus::Confirmed), Some(VexStatus::Affected));
+        assert_eq!(vex_status_for(&FindingStatus::Closed), Some(VexStatus::Fixed));
+        assert_eq!(vex_status_for(&FindingStatus::New), Some(VexStatus::UnderInvestigation));
+        assert_eq!(vex_status_for(&FindingStatus::Invalidated), None);
+    }
+
+    #[test]
+    fn openvex_export_one_statement_per_cve() {
+        let rows = vec![
+            row(FindingStatus::Confirmed, &["CVE-1", "CVE-2"]),
+            row(FindingStatus::Invalidated, &["CVE-3"]),
+        ];
+        let doc = build_openvex("PAYM1", &rows, Utc::now());
+        let stmts = doc["statements"].as_array().unwrap();
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0]["status"], "affected");
+        assert_eq!(stmts[0]["products"][0]["@id"], "pkg:maven/spring-webmvc@6.1.6");
+        assert!(stmts[0]["action_statement"].as_str().unwrap().contains("6.1.12"));
+    }
+
+    #[test]
+    fn cyclonedx_export_round_trips_through_parser() {
+        let rows = vec![row(FindingStatus::FalsePositive, &["CVE-9"])];
+        let doc = build_cyclonedx("PAYM1", &rows, Utc::now());
+        let stmts = parse_document(&doc).unwrap();
+        assert_eq!(stmts.len(), 1);
+        assert_eq!(stmts[0].status, VexStatus::NotAffected);
+        assert!(statement_matches(&stmts[0], &rows[0]));
+    }
+}
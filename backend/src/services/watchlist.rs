@@ -0,0 +1,422 @@
+//! CVE and package watchlists.
+//!
+//! Users watch a CVE or a package, optionally pinned to one version. After
+//! each ingestion run, every finding of the run matching an entry is
+//! recorded as a hit; so is every status change of a matching finding. Each
+//! entry with new hits notifies its owner once per event (see
+//! [`notification::send`]), the acting user excepted. Like notifications,
+//! hit recording is best effort and never fails the ingestion or status
+//! change that caused it.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::{FromRow, PgPool};
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::finding::{Finding, FindingStatus};
+use crate::models::notification::NotificationKind;
+use crate::models::pagination::{PagedResult, Pagination};
+use crate::models::watchlist::{
+    CreateWatchlistEntry, WatchlistEntry, WatchlistHit, WatchlistKind, WatchlistTrigger,
+};
+use crate::services::notification::{self, NewNotification};
+use crate::services::nvd;
+
+/// Whether finding `f` matches watchlist entry `e`.
+const MATCHES: &str = r#"
+    CASE e.kind
+        WHEN 'cve' THEN f.cve_ids @> jsonb_build_array(e.identifier)
+                     OR f.cve_ids @> jsonb_build_array(lower(e.identifier))
+        ELSE EXISTS (
+            SELECT 1 FROM finding_sca sc
+            WHERE sc.finding_id = f.id
+              AND lower(sc.package_name) = e.identifier
+              AND (e.package_version IS NULL OR sc.package_version = e.package_version)
+        )
+    END
+"#;
+
+/// Entry columns plus open matching findings and the latest hit.
+fn select_entry() -> String {
+    format!(
+        r#"
+        SELECT e.id, e.user_id, e.kind, e.identifier, e.package_version, e.note, e.created_at,
+               (
+                   SELECT COUNT(*) FROM findings f
+                   WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+                     AND f.archived_at IS NULL
+                     AND {MATCHES}
+               ) AS open_findings,
+               (SELECT MAX(h.created_at) FROM watchlist_hits h WHERE h.entry_id = e.id) AS last_hit_at
+        FROM watchlist_entries e
+        "#
+    )
+}
+
+/// Query parameters for the hit list.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WatchlistHitFilters {
+    pub entry_id: Option<Uuid>,
+    pub trigger: Option<WatchlistTrigger>,
+    /// Only hits recorded at or after this time.
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// An entry with new hits, as needed to notify its owner.
+#[derive(Debug, FromRow)]
+struct HitEntry {
+    id: Uuid,
+    user_id: Uuid,
+    kind: WatchlistKind,
+    identifier: String,
+    package_version: Option<String>,
+}
+
+/// Identifier and version in the form entries are stored and matched in:
+/// CVE ids upper case, package names lower case.
+fn normalize(
+    kind: WatchlistKind,
+    identifier: &str,
+    package_version: Option<&str>,
+) -> Result<(String, Option<String>), AppError> {
+    let version = package_version
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string);
+    match kind {
+        WatchlistKind::Cve => {
+            if version.is_some() {
+                return Err(AppError::Validation(
+                    "Only package entries take a version".to_string(),
+                ));
+            }
+            Ok((nvd::normalize_cve_id(identifier)?, None))
+        }
+        WatchlistKind::Package => {
+            let name = identifier.trim().to_lowercase();
+            if name.is_empty() {
+                return Err(AppError::Validation("Package name is required".to_string()));
+            }
+            Ok((name, version))
+        }
+    }
+}
+
+/// What an entry watches, for notification titles.
+fn label(kind: WatchlistKind, identifier: &str, package_version: Option<&str>) -> String {
+    match (kind, package_version) {
+        (WatchlistKind::Package, Some(version)) => format!("{identifier}@{version}"),
+        _ => identifier.to_string(),
+    }
+}
+
+fn hit_notification(
+    entry: &HitEntry,
+    finding_ids: &[Uuid],
+    body: &str,
+    actor_name: Option<&str>,
+) -> NewNotification {
+    let watched = label(
+        entry.kind,
+        &entry.identifier,
+        entry.package_version.as_deref(),
+    );
+    NewNotification {
+        kind: NotificationKind::WatchlistHit,
+        title: match finding_ids {
+            [_] => format!("Watched {watched} matched a finding"),
+            _ => format!("Watched {watched} matched {} findings", finding_ids.len()),
+        },
+        body: Some(body.to_string()),
+        finding_id: match finding_ids {
+            [id] => Some(*id),
+            _ => None,
+        },
+        application_id: None,
+        actor_name: actor_name.map(str::to_string),
+    }
+}
+
+/// Entries of the user, newest first.
+pub async fn list(pool: &PgPool, user_id: Uuid) -> Result<Vec<WatchlistEntry>, AppError> {
+    let entries = sqlx::query_as::<_, WatchlistEntry>(&format!(
+        "{} WHERE e.user_id = $1 ORDER BY e.created_at DESC",
+        select_entry()
+    ))
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(entries)
+}
+
+/// Add an entry to the user's watchlist.
+pub async fn create(
+    pool: &PgPool,
+    user_id: Uuid,
+    input: &CreateWatchlistEntry,
+) -> Result<WatchlistEntry, AppError> {
+    let (identifier, package_version) = normalize(
+        input.kind,
+        &input.identifier,
+        input.package_version.as_deref(),
+    )?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO watchlist_entries (user_id, kind, identifier, package_version, note)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(user_id)
+    .bind(input.kind)
+    .bind(&identifier)
+    .bind(&package_version)
+    .bind(
+        input
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty()),
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            AppError::Conflict(format!(
+                "You already watch {}",
+                label(input.kind, &identifier, package_version.as_deref())
+            ))
+        }
+        _ => AppError::Database(e),
+    })?;
+
+    let entry = sqlx::query_as::<_, WatchlistEntry>(&format!("{} WHERE e.id = $1", select_entry()))
+        .bind(id)
+        .fetch_one(pool)
+        .await?;
+    Ok(entry)
+}
+
+/// Remove one of the user's entries, with its hits.
+pub async fn delete(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    let result = sqlx::query("DELETE FROM watchlist_entries WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Watchlist entry {id} not found"
+        )));
+    }
+    Ok(())
+}
+
+/// Hits of the user's entries, newest first.
+pub async fn hits(
+    pool: &PgPool,
+    user_id: Uuid,
+    filters: &WatchlistHitFilters,
+    pagination: &Pagination,
+) -> Result<PagedResult<WatchlistHit>, AppError> {
+    const CONDITIONS: &str = r#"
+        WHERE e.user_id = $1
+          AND ($2::uuid IS NULL OR h.entry_id = $2)
+          AND ($3::watchlist_trigger IS NULL OR h.trigger = $3)
+          AND ($4::timestamptz IS NULL OR h.created_at >= $4)
+    "#;
+
+    let total = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM watchlist_hits h JOIN watchlist_entries e ON e.id = h.entry_id {CONDITIONS}"
+    ))
+    .bind(user_id)
+    .bind(filters.entry_id)
+    .bind(filters.trigger)
+    .bind(filters.since)
+    .fetch_one(pool)
+    .await?;
+
+    let items = sqlx::query_as::<_, WatchlistHit>(&format!(
+        r#"
+        SELECT h.id, h.entry_id, e.kind, e.identifier, e.package_version,
+               h.finding_id, f.title AS finding_title, f.application_id, a.app_code,
+               h.trigger, h.ingestion_log_id, h.detail, h.created_at
+        FROM watchlist_hits h
+        JOIN watchlist_entries e ON e.id = h.entry_id
+        JOIN findings f ON f.id = h.finding_id
+        LEFT JOIN applications a ON a.id = f.application_id
+        {CONDITIONS}
+        ORDER BY h.created_at DESC, h.id
+        LIMIT $5 OFFSET $6
+        "#
+    ))
+    .bind(user_id)
+    .bind(filters.entry_id)
+    .bind(filters.trigger)
+    .bind(filters.since)
+    .bind(pagination.limit())
+    .bind(pagination.offset())
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PagedResult::new(items, total, pagination))
+}
+
+/// Notify the owners of the entries in `hits` (entry, finding pairs), one
+/// notification per entry, except `actor_id`.
+async fn notify(
+    pool: &PgPool,
+    hits: Vec<(Uuid, Uuid)>,
+    body: &str,
+    actor_id: Option<Uuid>,
+    actor_name: Option<&str>,
+) -> Result<(), AppError> {
+    let mut by_entry: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (entry_id, finding_id) in hits {
+        by_entry.entry(entry_id).or_default().push(finding_id);
+    }
+    if by_entry.is_empty() {
+        return Ok(());
+    }
+
+    let entry_ids: Vec<Uuid> = by_entry.keys().copied().collect();
+    let entries = sqlx::query_as::<_, HitEntry>(
+        "SELECT id, user_id, kind, identifier, package_version FROM watchlist_entries WHERE id = ANY($1)",
+    )
+    .bind(&entry_ids)
+    .fetch_all(pool)
+    .await?;
+    for entry in entries.iter().filter(|e| Some(e.user_id) != actor_id) {
+        let notification = hit_notification(entry, &by_entry[&entry.id], body, actor_name);
+        notification::send(pool, &[entry.user_id], &notification).await;
+    }
+    Ok(())
+}
+
+/// Record hits for the findings an ingestion run reported, and notify.
+pub async fn record_ingestion(pool: &PgPool, ingestion_log_id: Uuid, source_tool: &str) {
+    let result = async {
+        let hits = sqlx::query_as::<_, (Uuid, Uuid)>(&format!(
+            r#"
+            INSERT INTO watchlist_hits (entry_id, finding_id, trigger, ingestion_log_id, detail)
+            SELECT DISTINCT ON (e.id, o.finding_id)
+                   e.id, o.finding_id, 'ingestion', $1, o.outcome
+            FROM finding_occurrences o
+            JOIN findings f ON f.id = o.finding_id
+            JOIN watchlist_entries e ON {MATCHES}
+            WHERE o.ingestion_log_id = $1
+            ORDER BY e.id, o.finding_id, o.seen_at
+            RETURNING entry_id, finding_id
+            "#
+        ))
+        .bind(ingestion_log_id)
+        .fetch_all(pool)
+        .await?;
+        let body = format!("Reported by a {source_tool} ingestion");
+        notify(pool, hits, &body, None, None).await
+    }
+    .await;
+    if let Err(e) = result {
+        tracing::warn!(%ingestion_log_id, error = %e, "Failed to record watchlist hits");
+    }
+}
+
+/// Record hits for a finding that changed status, and notify.
+pub async fn record_status_change(
+    pool: &PgPool,
+    finding: &Finding,
+    old_status: &FindingStatus,
+    actor_id: Option<Uuid>,
+    actor_name: &str,
+) {
+    let status = |s: &FindingStatus| {
+        serde_json::to_string(s)
+            .unwrap_or_default()
+            .trim_matches('"')
+            .to_string()
+    };
+    let detail = format!("{} -> {}", status(old_status), status(&finding.status));
+
+    let result = async {
+        let hits = sqlx::query_as::<_, (Uuid, Uuid)>(&format!(
+            r#"
+            INSERT INTO watchlist_hits (entry_id, finding_id, trigger, detail)
+            SELECT e.id, f.id, 'status_change', $2
+            FROM findings f
+            JOIN watchlist_entries e ON {MATCHES}
+            WHERE f.id = $1
+            RETURNING entry_id, finding_id
+            "#
+        ))
+        .bind(finding.id)
+        .bind(&detail)
+        .fetch_all(pool)
+        .await?;
+        let body = format!("{}: {detail}", finding.title);
+        notify(pool, hits, &body, actor_id, Some(actor_name)).await
+    }
+    .await;
+    if let Err(e) = result {
+        tracing::warn!(finding_id = %finding.id, error = %e, "Failed to record watchlist hits");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: WatchlistKind, identifier: &str, version: Option<&str>) -> HitEntry {
+        HitEntry {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            kind,
+            identifier: identifier.to_string(),
+            package_version: version.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn identifiers_are_normalized_per_kind() {
+        assert_eq!(
+            normalize(WatchlistKind::Cve, " cve-2021-44228 ", None).unwrap(),
+            ("CVE-2021-44228".to_string(), None)
+        );
+        assert_eq!(
+            normalize(WatchlistKind::Package, "Log4j-Core", Some(" 2.14.1 ")).unwrap(),
+            ("log4j-core".to_string(), Some("2.14.1".to_string()))
+        );
+        assert_eq!(
+            normalize(WatchlistKind::Package, "log4j-core", Some("")).unwrap(),
+            ("log4j-core".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn invalid_entries_are_rejected() {
+        assert!(normalize(WatchlistKind::Cve, "log4shell", None).is_err());
+        assert!(normalize(WatchlistKind::Cve, "CVE-2021-44228", Some("2.14.1")).is_err());
+        assert!(normalize(WatchlistKind::Package, "  ", None).is_err());
+    }
+
+    #[test]
+    fn notifications_link_a_single_finding() {
+        let finding = Uuid::new_v4();
+        let cve = entry(WatchlistKind::Cve, "CVE-2021-44228", None);
+        let single = hit_notification(&cve, &[finding], "Reported by a JFrog Xray ingestion", None);
+        assert_eq!(single.kind, NotificationKind::WatchlistHit);
+        assert_eq!(single.title, "Watched CVE-2021-44228 matched a finding");
+        assert_eq!(single.finding_id, Some(finding));
+
+        let package = entry(WatchlistKind::Package, "log4j-core", Some("2.14.1"));
+        let many = hit_notification(&package, &[finding, Uuid::new_v4()], "", Some("alice"));
+        assert_eq!(many.title, "Watched log4j-core@2.14.1 matched 2 findings");
+        assert_eq!(many.finding_id, None);
+        assert_eq!(many.actor_name.as_deref(), Some("alice"));
+    }
+}
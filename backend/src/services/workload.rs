@@ -0,0 +1,260 @@
+//! Analyst workload: per-user assigned findings, overdue items, pending
+//! false-positive requests, and recent activity, for balancing triage.
+//!
+//! Findings are assigned through the free-text `remediation_owner`, which is
+//! matched case-insensitively against each user's username and email.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::user::UserRole;
+
+/// Default activity window in days.
+const DEFAULT_ACTIVITY_DAYS: i64 = 7;
+
+fn default_days() -> i64 {
+    DEFAULT_ACTIVITY_DAYS
+}
+
+/// Query parameters for the workload endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WorkloadParams {
+    /// Window for recent activity in days (defaults to 7).
+    #[serde(default = "default_days")]
+    pub days: i64,
+}
+
+/// Workload of one user.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserWorkload {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: String,
+    pub role: UserRole,
+    /// Open findings whose remediation owner is this user.
+    pub assigned_open: i64,
+    pub assigned_critical_high: i64,
+    /// Assigned open findings past their SLA due date.
+    pub overdue: i64,
+    /// Share of assigned open findings that are overdue, in percent.
+    pub overdue_pct: Option<f64>,
+    /// False-positive requests by this user still awaiting approval.
+    pub fp_requests_pending: i64,
+    /// Status changes made within the activity window.
+    pub status_changes: i64,
+    /// Comments written within the activity window.
+    pub comments: i64,
+    pub last_activity_at: Option<DateTime<Utc>>,
+}
+
+/// Team workload, busiest users first.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkloadReport {
+    pub generated_at: DateTime<Utc>,
+    pub activity_days: i64,
+    /// All findings awaiting false-positive approval.
+    pub pending_fp_approvals: i64,
+    /// Open findings without a remediation owner.
+    pub unassigned_open: i64,
+    pub users: Vec<UserWorkload>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct WorkloadRow {
+    user_id: Uuid,
+    username: String,
+    display_name: String,
+    role: UserRole,
+    assigned_open: i64,
+    assigned_critical_high: i64,
+    overdue: i64,
+    fp_requests_pending: i64,
+    status_changes: i64,
+    comments: i64,
+    last_activity_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct QueueTotals {
+    pending_fp_approvals: i64,
+    unassigned_open: i64,
+}
+
+/// Summarize workload for every active user.
+pub async fn get_workload(
+    pool: &PgPool,
+    params: &WorkloadParams,
+) -> Result<WorkloadReport, AppError> {
+    if params.days < 1 {
+        return Err(AppError::Validation(
+            "'days' must be at least 1".to_string(),
+        ));
+    }
+    let generated_at = Utc::now();
+    let since = generated_at - Duration::days(params.days);
+
+    let (rows, totals) = tokio::try_join!(fetch_user_rows(pool, since), fetch_queue_totals(pool))?;
+
+    Ok(WorkloadReport {
+        generated_at,
+        activity_days: params.days,
+        pending_fp_approvals: totals.pending_fp_approvals,
+        unassigned_open: totals.unassigned_open,
+        users: rows.into_iter().map(UserWorkload::from).collect(),
+    })
+}
+
+async fn fetch_user_rows(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+) -> Result<Vec<WorkloadRow>, AppError> {
+    let rows = sqlx::query_as::<_, WorkloadRow>(
+        r#"
+        WITH assigned AS (
+            SELECT
+                u.id AS user_id,
+                COUNT(f.id) AS assigned_open,
+                COUNT(f.id) FILTER (WHERE f.effective_severity IN ('Critical', 'High')) AS assigned_critical_high,
+                COUNT(f.id) FILTER (WHERE f.sla_status = 'Breached' OR f.sla_due_date < NOW()) AS overdue
+            FROM users u
+            JOIN findings f
+              ON LOWER(f.remediation_owner) IN (LOWER(u.username), LOWER(u.email))
+            WHERE f.status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+            GROUP BY u.id
+        ),
+        fp_requests AS (
+            SELECT DISTINCT ON (h.finding_id) h.finding_id, h.actor_id
+            FROM finding_history h
+            JOIN findings f ON f.id = h.finding_id
+            WHERE f.status = 'False_Positive_Requested'
+              AND h.field_changed = 'status'
+              AND h.new_value = 'False_Positive_Requested'
+            ORDER BY h.finding_id, h.created_at DESC
+        ),
+        requested AS (
+            SELECT actor_id AS user_id, COUNT(*) AS fp_requests_pending
+            FROM fp_requests
+            WHERE actor_id IS NOT NULL
+            GROUP BY actor_id
+        ),
+        activity AS (
+            SELECT
+                actor_id AS user_id,
+                COUNT(*) FILTER (WHERE field_changed = 'status') AS status_changes,
+                MAX(created_at) AS last_at
+            FROM finding_history
+            WHERE created_at >= $1 AND actor_id IS NOT NULL
+            GROUP BY actor_id
+        ),
+        comments AS (
+            SELECT author_id AS user_id, COUNT(*) AS comments, MAX(created_at) AS last_at
+            FROM finding_comments
+            WHERE created_at >= $1
+            GROUP BY author_id
+        )
+        SELECT
+            u.id AS user_id,
+            u.username,
+            u.display_name,
+            u.role,
+            COALESCE(a.assigned_open, 0) AS assigned_open,
+            COALESCE(a.assigned_critical_high, 0) AS assigned_critical_high,
+            COALESCE(a.overdue, 0) AS overdue,
+            COALESCE(r.fp_requests_pending, 0) AS fp_requests_pending,
+            COALESCE(act.status_changes, 0) AS status_changes,
+            COALESCE(c.comments, 0) AS comments,
+            GREATEST(act.last_at, c.last_at) AS last_activity_at
+        FROM users u
+        LEFT JOIN assigned a ON a.user_id = u.id
+        LEFT JOIN requested r ON r.user_id = u.id
+        LEFT JOIN activity act ON act.user_id = u.id
+        LEFT JOIN comments c ON c.user_id = u.id
+        WHERE u.is_active
+          AND u.role <> 'API_Service_Account'
+        ORDER BY assigned_open DESC, overdue DESC, u.username
+        "#,
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+async fn fetch_queue_totals(pool: &PgPool) -> Result<QueueTotals, AppError> {
+    let totals = sqlx::query_as::<_, QueueTotals>(
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE status = 'False_Positive_Requested') AS pending_fp_approvals,
+            COUNT(*) FILTER (
+                WHERE status NOT IN ('Closed', 'Invalidated', 'False_Positive')
+                  AND COALESCE(TRIM(remediation_owner), '') = ''
+            ) AS unassigned_open
+        FROM findings
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(totals)
+}
+
+impl From<WorkloadRow> for UserWorkload {
+    fn from(row: WorkloadRow) -> Self {
+        let overdue_pct = if row.assigned_open > 0 {
+            Some(row.overdue as f64 * 100.0 / row.assigned_open as f64)
+        } else {
+            None
+        };
+        Self {
+            user_id: row.user_id,
+            username: row.username,
+            display_name: row.display_name,
+            role: row.role,
+            assigned_open: row.assigned_open,
+            assigned_critical_high: row.assigned_critical_high,
+            overdue: row.overdue,
+            overdue_pct,
+            fp_requests_pending: row.fp_requests_pending,
+            status_changes: row.status_changes,
+            comments: row.comments,
+            last_activity_at: row.last_activity_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(assigned_open: i64, overdue: i64) -> WorkloadRow {
+        WorkloadRow {
+            user_id: Uuid::new_v4(),
+            username: "jdoe".to_string(),
+            display_name: "J. Doe".to_string(),
+            role: UserRole::AppSecAnalyst,
+            assigned_open,
+            assigned_critical_high: 0,
+            overdue,
+            fp_requests_pending: 0,
+            status_changes: 0,
+            comments: 0,
+            last_activity_at: None,
+        }
+    }
+
+    #[test]
+    fn overdue_share_of_assigned_findings() {
+        assert_eq!(UserWorkload::from(row(8, 2)).overdue_pct, Some(25.0));
+        assert_eq!(UserWorkload::from(row(0, 0)).overdue_pct, None);
+    }
+
+    #[test]
+    fn activity_window_defaults_to_a_week() {
+        let params: WorkloadParams = serde_json::from_str("{}").unwrap();
+        assert_eq!(params.days, DEFAULT_ACTIVITY_DAYS);
+    }
+}
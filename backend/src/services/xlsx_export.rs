@@ -0,0 +1,361 @@
+//! XLSX findings export with one sheet per category plus a summary sheet.
+//!
+//! Category sheets use `rust_xlsxwriter`'s constant-memory mode: each row is
+//! flushed to a temporary file as soon as the next row starts, so worksheet
+//! memory stays flat for exports of 100k+ findings. Only the small summary
+//! sheet is held in memory until the workbook is assembled, and the assembled
+//! workbook goes to a caller-supplied writer (a temporary file for downloads)
+//! rather than a buffer.
+
+use std::io::{Seek, Write};
+
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::errors::AppError;
+use crate::models::finding::{FindingCategory, FindingSummaryWithCategory, SeverityLevel};
+
+/// MIME type of the generated workbook.
+pub const CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// Columns shared by every category sheet, in output order.
+const COMMON_COLUMNS: [&str; 11] = [
+    "id",
+    "application_id",
+    "source_tool",
+    "title",
+    "normalized_severity",
+    "status",
+    "composite_risk_score",
+    "sla_status",
+    "first_seen",
+    "last_seen",
+    "fingerprint",
+];
+
+/// SAST-specific columns appended after the common ones.
+const SAST_COLUMNS: [&str; 6] = [
+    "file_path",
+    "line_number",
+    "rule_id",
+    "project",
+    "language",
+    "branch",
+];
+
+/// SCA-specific columns appended after the common ones.
+const SCA_COLUMNS: [&str; 5] = [
+    "package_name",
+    "package_version",
+    "fixed_version",
+    "dependency_type",
+    "known_exploited",
+];
+
+/// DAST-specific columns appended after the common ones.
+const DAST_COLUMNS: [&str; 3] = ["target_url", "parameter", "web_application_name"];
+
+/// Manual-specific columns appended after the common ones.
+const MANUAL_COLUMNS: [&str; 2] = ["affected_asset", "reporter"];
+
+/// Most findings one workbook takes. Excel opens at most 1,048,576 rows per
+/// sheet, and larger exports are better served by streamed CSV.
+pub const MAX_ROWS: u32 = 1_000_000;
+
+/// Worksheet index of the summary sheet (always first in the workbook).
+const SUMMARY_SHEET: usize = 0;
+
+/// Per-category row counters and severity tallies for the summary sheet.
+#[derive(Debug, Default)]
+struct SheetTally {
+    /// Next row to write (row 0 is the header).
+    next_row: u32,
+    /// Findings per severity, indexed like `SeverityLevel::ALL`.
+    by_severity: [u32; SeverityLevel::ALL.len()],
+}
+
+/// Build an XLSX workbook from findings received on `rows` and write it to `out`.
+///
+/// This is blocking (file I/O and zip compression) and must run on a
+/// blocking thread, e.g. via `tokio::task::spawn_blocking`. Returns once the
+/// sender side is dropped and all rows have been written, or with a
+/// validation error as soon as more than [`MAX_ROWS`] findings arrive.
+pub fn write_workbook<W: Write + Seek + Send>(
+    mut rows: mpsc::Receiver<FindingSummaryWithCategory>,
+    out: W,
+) -> Result<(), AppError> {
+    match build(&mut rows, out) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(AppError::Validation(format!(
+            "XLSX exports are limited to {MAX_ROWS} findings; narrow the filters or export as CSV"
+        ))),
+        Err(e) => Err(AppError::Internal(format!("XLSX generation failed: {e}"))),
+    }
+}
+
+/// Returns `false` without saving once the row cap is exceeded.
+fn build<W: Write + Seek + Send>(
+    rows: &mut mpsc::Receiver<FindingSummaryWithCategory>,
+    out: W,
+) -> Result<bool, XlsxError> {
+    let header = Format::new().set_bold();
+    let mut workbook = Workbook::new();
+
+    workbook.add_worksheet().set_name("Summary")?;
+    for (name, extra) in [
+        ("SAST", &SAST_COLUMNS[..]),
+        ("SCA", &SCA_COLUMNS[..]),
+        ("DAST", &DAST_COLUMNS[..]),
+        ("MANUAL", &MANUAL_COLUMNS[..]),
+    ] {
+        let sheet = workbook.add_worksheet_with_constant_memory();
+        sheet.set_name(name)?;
+        write_header(sheet, extra, &header)?;
+    }
+
+    let mut tallies: [SheetTally; 4] = Default::default();
+    for tally in &mut tallies {
+        tally.next_row = 1;
+    }
+
+    let mut written = 0;
+    while let Some(finding) = rows.blocking_recv() {
+        if written == MAX_ROWS {
+            return Ok(false);
+        }
+        written += 1;
+        let idx = category_index(&finding.summary.finding_category);
+        let tally = &mut tallies[idx];
+        let sheet = workbook.worksheet_from_index(idx + 1)?;
+        write_finding(sheet, tally.next_row, &finding)?;
+        tally.next_row += 1;
+        tally.by_severity[severity_index(&finding.summary.normalized_severity)] += 1;
+    }
+
+    write_summary(
+        workbook.worksheet_from_index(SUMMARY_SHEET)?,
+        &tallies,
+        &header,
+    )?;
+
+    workbook.save_to_writer(out)?;
+    Ok(true)
+}
+
+/// Write the header row: common columns followed by `extra`.
+fn write_header(sheet: &mut Worksheet, extra: &[&str], format: &Format) -> Result<(), XlsxError> {
+    for (col, name) in COMMON_COLUMNS.iter().chain(extra).enumerate() {
+        sheet.write_string_with_format(0, col as u16, *name, format)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    Ok(())
+}
+
+/// Write one finding row, including the category columns of its sheet.
+fn write_finding(
+    sheet: &mut Worksheet,
+    row: u32,
+    finding: &FindingSummaryWithCategory,
+) -> Result<(), XlsxError> {
+    let s = &finding.summary;
+    let application_id = s.application_id.map(|id| id.to_string());
+    let sla_status = s.sla_status.as_ref().map(enum_label);
+
+    sheet.write_string(row, 0, s.id.to_string())?;
+    write_opt_string(sheet, row, 1, application_id.as_deref())?;
+    sheet.write_string(row, 2, &s.source_tool)?;
+    sheet.write_string(row, 3, &s.title)?;
+    sheet.write_string(row, 4, s.normalized_severity.label())?;
+    sheet.write_string(row, 5, enum_label(&s.status))?;
+    if let Some(score) = s.composite_risk_score {
+        sheet.write_number(row, 6, score)?;
+    }
+    write_opt_string(sheet, row, 7, sla_status.as_deref())?;
+    sheet.write_string(row, 8, s.first_seen.to_rfc3339())?;
+    sheet.write_string(row, 9, s.last_seen.to_rfc3339())?;
+    sheet.write_string(row, 10, &s.fingerprint)?;
+
+    let Some(cat) = finding.category_data.as_ref() else {
+        return Ok(());
+    };
+    let col = COMMON_COLUMNS.len() as u16;
+    match s.finding_category {
+        FindingCategory::Sast => {
+            write_opt_string(sheet, row, col, cat.file_path.as_deref())?;
+            if let Some(line) = cat.line_number {
+                sheet.write_number(row, col + 1, line)?;
+            }
+            write_opt_string(sheet, row, col + 2, cat.rule_id.as_deref())?;
+            write_opt_string(sheet, row, col + 3, cat.project.as_deref())?;
+            write_opt_string(sheet, row, col + 4, cat.language.as_deref())?;
+            write_opt_string(sheet, row, col + 5, cat.branch.as_deref())?;
+        }
+        FindingCategory::Sca => {
+            write_opt_string(sheet, row, col, cat.package_name.as_deref())?;
+            write_opt_string(sheet, row, col + 1, cat.package_version.as_deref())?;
+            write_opt_string(sheet, row, col + 2, cat.fixed_version.as_deref())?;
+            write_opt_string(sheet, row, col + 3, cat.dependency_type.as_deref())?;
+            if let Some(kev) = cat.known_exploited {
+                sheet.write_boolean(row, col + 4, kev)?;
+            }
+        }
+        FindingCategory::Dast => {
+            write_opt_string(sheet, row, col, cat.target_url.as_deref())?;
+            write_opt_string(sheet, row, col + 1, cat.parameter.as_deref())?;
+            write_opt_string(sheet, row, col + 2, cat.web_application_name.as_deref())?;
+        }
+        FindingCategory::Manual => {
+            write_opt_string(sheet, row, col, cat.affected_asset.as_deref())?;
+            write_opt_string(sheet, row, col + 1, cat.reporter.as_deref())?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the summary sheet: findings per severity for each category.
+fn write_summary(
+    sheet: &mut Worksheet,
+    tallies: &[SheetTally; 4],
+    format: &Format,
+) -> Result<(), XlsxError> {
+    let columns = ["severity", "SAST", "SCA", "DAST", "MANUAL", "total"];
+    let total_col = columns.len() as u16 - 1;
+    for (col, name) in columns.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *name, format)?;
+    }
+
+    for (i, severity) in SeverityLevel::ALL.iter().enumerate() {
+        let row = i as u32 + 1;
+        sheet.write_string(row, 0, severity.label())?;
+        let mut total = 0;
+        for (c, tally) in tallies.iter().enumerate() {
+            sheet.write_number(row, c as u16 + 1, tally.by_severity[i])?;
+            total += tally.by_severity[i];
+        }
+        sheet.write_number(row, total_col, total)?;
+    }
+
+    let total_row = SeverityLevel::ALL.len() as u32 + 1;
+    sheet.write_string_with_format(total_row, 0, "total", format)?;
+    let mut grand_total = 0;
+    for (c, tally) in tallies.iter().enumerate() {
+        let count = tally.next_row - 1;
+        sheet.write_number_with_format(total_row, c as u16 + 1, count, format)?;
+        grand_total += count;
+    }
+    sheet.write_number_with_format(total_row, total_col, grand_total, format)?;
+    Ok(())
+}
+
+fn write_opt_string(
+    sheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    value: Option<&str>,
+) -> Result<(), XlsxError> {
+    if let Some(v) = value {
+        sheet.write_string(row, col, v)?;
+    }
+    Ok(())
+}
+
+/// Sheet offset (after the summary) for a finding category.
+fn category_index(category: &FindingCategory) -> usize {
+    match category {
+        FindingCategory::Sast => 0,
+        FindingCategory::Sca => 1,
+        FindingCategory::Dast => 2,
+        FindingCategory::Manual => 3,
+    }
+}
+
+/// Position of a severity in `SeverityLevel::ALL`, most severe first.
+fn severity_index(severity: &SeverityLevel) -> usize {
+    match severity {
+        SeverityLevel::Critical => 0,
+        SeverityLevel::High => 1,
+        SeverityLevel::Medium => 2,
+        SeverityLevel::Low => 3,
+        SeverityLevel::Info => 4,
+    }
+}
+
+/// Serialized name of a unit enum variant (e.g. `In_Remediation`).
+fn enum_label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::{FindingCategoryData, FindingStatus, FindingSummary};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn finding(category: FindingCategory, severity: SeverityLevel) -> FindingSummaryWithCategory {
+        FindingSummaryWithCategory {
+            summary: FindingSummary {
+                id: Uuid::new_v4(),
+                source_tool: "tool".to_string(),
+                finding_category: category,
+                title: "title".to_string(),
+                normalized_severity: severity,
+                severity_override: None,
+                status: FindingStatus::InRemediation,
+                composite_risk_score: Some(42.0),
+                fingerprint: "fp".to_string(),
+                application_id: None,
+                first_seen: Utc::now(),
+                last_seen: Utc::now(),
+                sla_status: None,
+            },
+            category_data: Some(FindingCategoryData {
+                package_name: Some("lodash".to_string()),
+                known_exploited: Some(true),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn enum_label_uses_serde_names() {
+        assert_eq!(enum_label(&FindingStatus::InRemediation), "In_Remediation");
+        assert_eq!(enum_label(&FindingCategory::Sca), "SCA");
+    }
+
+    #[test]
+    fn category_and_severity_indexes_are_distinct() {
+        let cats = [
+            FindingCategory::Sast,
+            FindingCategory::Sca,
+            FindingCategory::Dast,
+            FindingCategory::Manual,
+        ];
+        let idx: Vec<usize> = cats.iter().map(category_index).collect();
+        assert_eq!(idx, vec![0, 1, 2, 3]);
+        let sev: Vec<usize> = SeverityLevel::ALL.iter().map(severity_index).collect();
+        assert_eq!(sev, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn workbook_is_a_zip_archive() {
+        let (tx, rx) = mpsc::channel(4);
+        tx.try_send(finding(FindingCategory::Sca, SeverityLevel::High))
+            .unwrap();
+        tx.try_send(finding(FindingCategory::Sast, SeverityLevel::Low))
+            .unwrap();
+        tx.try_send(finding(FindingCategory::Manual, SeverityLevel::Critical))
+            .unwrap();
+        drop(tx);
+
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        write_workbook(rx, &mut bytes).expect("workbook builds");
+        let bytes = bytes.into_inner();
+        // XLSX files are zip archives starting with the local file header magic.
+        assert_eq!(&bytes[..2], b"PK");
+    }
+}
@@ -0,0 +1,122 @@
+//! Graceful shutdown coordination.
+//!
+//! On SIGTERM or SIGINT the server stops accepting connections and job
+//! workers stop claiming jobs; both get until a shared deadline to finish what
+//! they are doing. Periodic background tasks (enrichers, snapshotters) are
+//! dropped with the runtime: each run is idempotent and picks up again on the
+//! next start.
+
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+/// Trigger side of the shutdown signal, held by `main`.
+#[derive(Debug)]
+pub struct Shutdown {
+    tx: watch::Sender<Option<Instant>>,
+}
+
+/// Receiving side of the shutdown signal, cloned into each task that drains.
+#[derive(Debug, Clone)]
+pub struct ShutdownListener {
+    rx: watch::Receiver<Option<Instant>>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (tx, _rx) = watch::channel(None);
+        Self { tx }
+    }
+
+    pub fn listener(&self) -> ShutdownListener {
+        ShutdownListener {
+            rx: self.tx.subscribe(),
+        }
+    }
+
+    /// Start shutting down; later calls keep the first trigger time.
+    pub fn trigger(&self) {
+        self.tx.send_if_modified(|at| {
+            if at.is_some() {
+                return false;
+            }
+            *at = Some(Instant::now());
+            true
+        });
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownListener {
+    pub fn is_triggered(&self) -> bool {
+        self.rx.borrow().is_some()
+    }
+
+    /// Wait for shutdown and return when it was triggered. Never returns if
+    /// the [`Shutdown`] is dropped without triggering.
+    pub async fn triggered(&mut self) -> Instant {
+        let at = match self.rx.wait_for(Option::is_some).await {
+            Ok(at) => *at,
+            Err(_) => None,
+        };
+        match at {
+            Some(at) => at,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+/// Resolve on the first SIGINT (Ctrl+C) or, on Unix, SIGTERM.
+pub async fn signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %e, "Failed to listen for SIGINT");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("SIGINT received, shutting down"),
+        _ = terminate => tracing::info!("SIGTERM received, shutting down"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn listeners_see_the_first_trigger_time() {
+        let shutdown = Shutdown::new();
+        let mut listener = shutdown.listener();
+        assert!(!listener.is_triggered());
+
+        shutdown.trigger();
+        let first = listener.triggered().await;
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        shutdown.trigger();
+
+        assert!(listener.is_triggered());
+        assert_eq!(listener.triggered().await, first);
+        assert_eq!(shutdown.listener().triggered().await, first);
+    }
+}
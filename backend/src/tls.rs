@@ -0,0 +1,171 @@
+//! HTTPS server setup with optional client certificate authentication.
+//!
+//! With a client CA configured, the TLS handshake asks for a client
+//! certificate and verifies any that is presented against the CA, but still
+//! accepts connections without one so browsers keep working. The verified
+//! certificate is attached to every request of the connection as a
+//! [`ClientCertificate`] extension; route groups that need mutual TLS enforce
+//! it with [`require_client_certificate`].
+
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use axum::{extract::Request, middleware::Next, response::Response};
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower_http::add_extension::AddExtension;
+
+use crate::errors::AppError;
+
+/// A verified client certificate presented on the connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertificate {
+    /// Hex SHA-256 of the DER certificate, for logs and allowlists.
+    pub fingerprint: String,
+}
+
+impl ClientCertificate {
+    fn from_der(der: &CertificateDer<'_>) -> Self {
+        Self {
+            fingerprint: hex::encode(Sha256::digest(der.as_ref())),
+        }
+    }
+}
+
+fn pem_error(path: &Path, e: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {e}", path.display()),
+    )
+}
+
+fn load_certs(path: &Path) -> io::Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .map_err(|e| pem_error(path, e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| pem_error(path, e))?;
+    if certs.is_empty() {
+        return Err(pem_error(path, "no certificates found"));
+    }
+    Ok(certs)
+}
+
+/// Build the rustls server configuration, requesting client certificates
+/// signed by `client_ca` when given.
+pub fn server_config(
+    cert: &Path,
+    key: &Path,
+    client_ca: Option<&Path>,
+) -> io::Result<RustlsConfig> {
+    let chain = load_certs(cert)?;
+    let key = PrivateKeyDer::from_pem_file(key).map_err(|e| pem_error(key, e))?;
+
+    let builder = ServerConfig::builder();
+    let builder = match client_ca {
+        Some(ca) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(ca)? {
+                roots.add(cert).map_err(|e| pem_error(ca, e))?;
+            }
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                .allow_unauthenticated()
+                .build()
+                .map_err(|e| pem_error(ca, e))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+    let mut config = builder
+        .with_single_cert(chain, key)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(RustlsConfig::from_config(Arc::new(config)))
+}
+
+/// TLS acceptor that attaches the peer's [`ClientCertificate`] (or `None`) to
+/// the connection's requests.
+#[derive(Debug, Clone)]
+pub struct ClientCertAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl ClientCertAcceptor {
+    pub fn new(config: RustlsConfig) -> Self {
+        Self {
+            inner: RustlsAcceptor::new(config),
+        }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientCertAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, Option<ClientCertificate>>;
+    type Future = Pin<Box<dyn Future<Output = io::Result<(Self::Stream, Self::Service)>> + Send>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let inner = self.inner.clone();
+        Box::pin(async move {
+            let (stream, service) = inner.accept(stream, service).await?;
+            let certificate = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|chain| chain.first())
+                .map(ClientCertificate::from_der);
+            Ok((stream, AddExtension::new(service, certificate)))
+        })
+    }
+}
+
+/// Reject requests whose connection presented no verified client
+/// certificate.
+pub async fn require_client_certificate(request: Request, next: Next) -> Response {
+    match request.extensions().get::<Option<ClientCertificate>>() {
+        Some(Some(certificate)) => {
+            tracing::debug!(fingerprint = %certificate.fingerprint, "Client certificate accepted");
+            next.run(request).await
+        }
+        _ => axum::response::IntoResponse::into_response(AppError::Forbidden(
+            "A client certificate is required for this endpoint".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_is_sha256_of_the_der_bytes() {
+        let certificate = ClientCertificate::from_der(&CertificateDer::from(vec![1, 2, 3]));
+        assert_eq!(
+            certificate.fingerprint,
+            "039058c6f2c0cb492c533b0a4d14ef77cc0f78abccced5287d84a1a2011cfb81"
+        );
+    }
+
+    #[test]
+    fn missing_pem_files_name_the_path() {
+        let err = server_config(
+            Path::new("/nonexistent/cert.pem"),
+            Path::new("/nonexistent/key.pem"),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/cert.pem"));
+    }
+}
@@ -61,8 +61,19 @@ async fn start_server() -> (String, tokio::task::JoinHandle<()>) {
     .ok();
 
     let state = synapsec::AppState {
+        db_read: pool.clone(),
         db: pool,
         config: config.clone(),
+        hec: None,
+        dashboard_cache: synapsec::services::dashboard::StatsCache::new(
+            std::time::Duration::from_secs(config.dashboard_cache_ttl_secs),
+            None,
+        ),
+        attachments: synapsec::services::attachment_storage::AttachmentStorage::from_config(
+            &config,
+        )
+        .expect("attachment storage"),
+        redis: None,
     };
 
     // Build the router (mirrors main.rs)
@@ -257,7 +268,7 @@ async fn full_phase1_pipeline() {
     let app = extract_data(&create_app_resp);
     let app_id = app["id"].as_str().unwrap();
     assert_eq!(app["app_code"].as_str().unwrap(), "PAYM1");
-    assert_eq!(app["is_verified"].as_bool().unwrap(), true);
+    assert!(app["is_verified"].as_bool().unwrap());
 
     // ──────────────────────────────────────────────────────────
     // 5. Upload SonarQube JSON via ingestion endpoint
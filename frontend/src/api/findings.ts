@@ -80,9 +80,16 @@ export function updateFindingStatus(
   return apiPatch<void>(`/findings/${id}/status`, { status, justification })
 }
 
-/** GET /findings/:id/comments — list finding comments. */
-export function listComments(id: string): Promise<FindingComment[]> {
-  return apiGet<FindingComment[]>(`/findings/${id}/comments`)
+/** GET /findings/:id/comments — list finding comments, oldest first. */
+export function listComments(
+  id: string,
+  page = 1,
+  perPage = 100,
+): Promise<PagedResult<FindingComment>> {
+  return apiGet<PagedResult<FindingComment>>(`/findings/${id}/comments`, {
+    page: String(page),
+    per_page: String(perPage),
+  })
 }
 
 /** POST /findings/:id/comments — add a comment. */
@@ -90,9 +97,16 @@ export function addComment(id: string, content: string): Promise<FindingComment>
   return apiPost<FindingComment>(`/findings/${id}/comments`, { content })
 }
 
-/** GET /findings/:id/history — get finding history. */
-export function getHistory(id: string): Promise<FindingHistory[]> {
-  return apiGet<FindingHistory[]>(`/findings/${id}/history`)
+/** GET /findings/:id/history — get finding history, newest first. */
+export function getHistory(
+  id: string,
+  page = 1,
+  perPage = 100,
+): Promise<PagedResult<FindingHistory>> {
+  return apiGet<PagedResult<FindingHistory>>(`/findings/${id}/history`, {
+    page: String(page),
+    per_page: String(perPage),
+  })
 }
 
 /** POST /findings/bulk/status — bulk status update. */
@@ -119,10 +133,10 @@ export function bulkAssign(
   })
 }
 
-/** GET /findings/export — download findings as CSV or JSON blob. */
+/** GET /findings/export — download findings as CSV, JSON, or XLSX blob. */
 export function exportFindings(
   filters: Record<string, string>,
-  format: 'csv' | 'json',
+  format: 'csv' | 'json' | 'xlsx',
 ): Promise<Blob> {
   return apiGetBlob('/findings/export', { ...filters, format })
 }
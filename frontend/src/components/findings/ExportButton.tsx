@@ -30,7 +30,7 @@ export function ExportButton({ filters }: ExportButtonProps) {
   const [exporting, setExporting] = useState(false)
   const [error, setError] = useState<string | null>(null)
 
-  async function handleExport(format: 'csv' | 'json') {
+  async function handleExport(format: 'csv' | 'json' | 'xlsx') {
     setExporting(true)
     setError(null)
     try {
@@ -65,6 +65,9 @@ export function ExportButton({ filters }: ExportButtonProps) {
           <DropdownMenuItem onClick={() => handleExport('json')}>
             {t('findings.exportJson')}
           </DropdownMenuItem>
+          <DropdownMenuItem onClick={() => handleExport('xlsx')}>
+            {t('findings.exportXlsx')}
+          </DropdownMenuItem>
         </DropdownMenuContent>
       </DropdownMenu>
     </div>
@@ -29,6 +29,7 @@ export function FileUpload({ onComplete }: Props) {
     sarif: 'sarif',
     jfrog_xray: 'json',
     tenable_was: 'csv',
+    semgrep: 'json',
   }
 
   function handleParserTypeChange(value: string) {
@@ -146,6 +147,7 @@ export function FileUpload({ onComplete }: Props) {
                 <SelectItem value="sarif">SARIF</SelectItem>
                 <SelectItem value="jfrog_xray">JFrog Xray</SelectItem>
                 <SelectItem value="tenable_was">Tenable WAS</SelectItem>
+                <SelectItem value="semgrep">Semgrep</SelectItem>
               </SelectContent>
             </Select>
           </div>
@@ -17,6 +17,7 @@ import type {
   FindingHistory,
   FindingComment,
   FindingStatus,
+  PagedResult,
 } from '@/types/finding'
 
 /** Valid next statuses from a given status (mirrors backend lifecycle). */
@@ -38,13 +39,41 @@ const TRANSITIONS: Partial<Record<FindingStatus, FindingStatus[]>> = {
   Closed: ['New'],
 }
 
+/** Next page to request after `page`, or null when it was the last one. */
+function nextPage<T>(page: PagedResult<T>): number | null {
+  return page.page < page.total_pages ? page.page + 1 : null
+}
+
+/** `prev` followed by the items of `more` it does not already hold; pages
+ * shift when entries are added between requests. */
+function appendNew<T extends { id: string }>(prev: T[], more: T[]): T[] {
+  const seen = new Set(prev.map((item) => item.id))
+  return [...prev, ...more.filter((item) => !seen.has(item.id))]
+}
+
+/** Top-level comments in order, each followed by its replies. */
+function threadComments(comments: FindingComment[]): FindingComment[][] {
+  const replies = new Map<string, FindingComment[]>()
+  for (const c of comments) {
+    if (c.parent_id) replies.set(c.parent_id, [...(replies.get(c.parent_id) ?? []), c])
+  }
+  const ids = new Set(comments.map((c) => c.id))
+  return comments
+    .filter((c) => !c.parent_id || !ids.has(c.parent_id))
+    .map((c) => [c, ...(replies.get(c.id) ?? [])])
+}
+
 export function FindingDetailPage() {
   const { t } = useTranslation()
   const { id } = useParams({ strict: false })
   const navigate = useNavigate()
   const [finding, setFinding] = useState<FindingDetail | null>(null)
   const [history, setHistory] = useState<FindingHistory[]>([])
+  const [historyTotal, setHistoryTotal] = useState(0)
+  const [historyNext, setHistoryNext] = useState<number | null>(null)
   const [comments, setComments] = useState<FindingComment[]>([])
+  const [commentsTotal, setCommentsTotal] = useState(0)
+  const [commentsNext, setCommentsNext] = useState<number | null>(null)
   const [newComment, setNewComment] = useState('')
   const [transitionTarget, setTransitionTarget] = useState<FindingStatus | null>(null)
   const [loading, setLoading] = useState(true)
@@ -59,8 +88,12 @@ export function FindingDetailPage() {
         findingsApi.listComments(id),
       ])
       setFinding(f)
-      setHistory(h)
-      setComments(c)
+      setHistory(h.items)
+      setHistoryTotal(h.total)
+      setHistoryNext(nextPage(h))
+      setComments(c.items)
+      setCommentsTotal(c.total)
+      setCommentsNext(nextPage(c))
     } catch {
       // handled by client
     } finally {
@@ -72,6 +105,30 @@ export function FindingDetailPage() {
     fetchData()
   }, [fetchData])
 
+  async function loadMoreHistory() {
+    if (!id || historyNext === null) return
+    try {
+      const h = await findingsApi.getHistory(id, historyNext)
+      setHistory((prev) => appendNew(prev, h.items))
+      setHistoryTotal(h.total)
+      setHistoryNext(nextPage(h))
+    } catch {
+      // handled by client
+    }
+  }
+
+  async function loadMoreComments() {
+    if (!id || commentsNext === null) return
+    try {
+      const c = await findingsApi.listComments(id, commentsNext)
+      setComments((prev) => appendNew(prev, c.items))
+      setCommentsTotal(c.total)
+      setCommentsNext(nextPage(c))
+    } catch {
+      // handled by client
+    }
+  }
+
   async function handleTransition(data: {
     justification?: string
   }) {
@@ -93,7 +150,9 @@ export function FindingDetailPage() {
     if (!finding || !newComment.trim()) return
     try {
       const comment = await findingsApi.addComment(finding.id, newComment)
-      setComments((prev) => [...prev, comment])
+      setCommentsTotal((prev) => prev + 1)
+      // Appending out of order would duplicate it when the next page loads
+      if (commentsNext === null) setComments((prev) => [...prev, comment])
       setNewComment('')
     } catch {
       // toast
@@ -240,24 +299,33 @@ export function FindingDetailPage() {
       <Tabs defaultValue="comments" className="animate-in stagger-3">
         <TabsList>
           <TabsTrigger value="comments" className="gap-1">
-            <MessageSquare className="h-3 w-3" /> {t('findingDetail.comments')} ({comments.length})
+            <MessageSquare className="h-3 w-3" /> {t('findingDetail.comments')} ({commentsTotal})
           </TabsTrigger>
           <TabsTrigger value="history" className="gap-1">
-            <History className="h-3 w-3" /> {t('findingDetail.history')} ({history.length})
+            <History className="h-3 w-3" /> {t('findingDetail.history')} ({historyTotal})
           </TabsTrigger>
           <TabsTrigger value="raw">{t('findingDetail.rawFinding')}</TabsTrigger>
         </TabsList>
 
         <TabsContent value="comments" className="space-y-4">
-          {comments.map((c, idx) => (
-            <div key={c.id} className={`animate-in rounded border p-3 ${idx < 8 ? `stagger-${idx + 1}` : ''}`}>
-              <div className="flex items-center gap-2 text-sm">
-                <span className="font-medium">{c.author_name}</span>
-                <span className="text-muted-foreground">{new Date(c.created_at).toLocaleString()}</span>
-              </div>
-              <p className="mt-1 text-sm">{c.content}</p>
+          {threadComments(comments).map((thread, idx) => (
+            <div key={thread[0].id} className={`animate-in space-y-2 ${idx < 8 ? `stagger-${idx + 1}` : ''}`}>
+              {thread.map((c, depth) => (
+                <div key={c.id} className={`rounded border p-3 ${depth > 0 ? 'ml-6' : ''}`}>
+                  <div className="flex items-center gap-2 text-sm">
+                    <span className="font-medium">{c.author_name}</span>
+                    <span className="text-muted-foreground">{new Date(c.created_at).toLocaleString()}</span>
+                  </div>
+                  <p className="mt-1 text-sm">{c.content}</p>
+                </div>
+              ))}
             </div>
           ))}
+          {commentsNext !== null && (
+            <Button variant="outline" size="sm" onClick={loadMoreComments}>
+              {t('findingDetail.loadMore')}
+            </Button>
+          )}
           <div className="flex gap-2">
             <Input
               placeholder={t('findingDetail.addComment')}
@@ -291,6 +359,11 @@ export function FindingDetailPage() {
               </div>
             ))
           )}
+          {historyNext !== null && (
+            <Button variant="outline" size="sm" onClick={loadMoreHistory}>
+              {t('findingDetail.loadMore')}
+            </Button>
+          )}
         </TabsContent>
 
         <TabsContent value="raw">
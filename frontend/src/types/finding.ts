@@ -115,6 +115,8 @@ export type FindingComment = {
   author_name: string
   content: string
   created_at: string
+  /** Comment this one replies to; null for a top-level comment. */
+  parent_id: string | null
 }
 
 export type PagedResult<T> = {